@@ -7,9 +7,16 @@
  * Protocol (newline-delimited JSON):
  *   Request:  {"id":"1","cmd":"gpio_write","args":{"pin":13,"value":1}}
  *   Response: {"id":"1","ok":true,"result":"done"}
+ *   Event:    {"event":"pin_2","value":0}   (unsolicited, no id)
  *
  * Arduino Uno: Pin 13 has built-in LED. Digital pins 0-13 supported.
  *
+ * Events: {"cmd":"gpio_watch","args":{"pin":2}} configures the pin as
+ * INPUT_PULLUP and reports every debounced level change as a "pin_<n>"
+ * event. {"cmd":"analog_watch","args":{"pin":0,"threshold":512}} reports
+ * A0-A5 crossing the threshold as an "a<n>" event carrying the reading.
+ * Pass "enable":0 to stop watching.
+ *
  * 1. Open in Arduino IDE
  * 2. Select Board: Arduino Uno
  * 3. Select correct Port (Tools -> Port)
@@ -19,9 +26,23 @@
 #define BAUDRATE 115200
 #define MAX_LINE 256
 
+#define DEBOUNCE_MS 30
+#define ANALOG_PINS 6
+#define ANALOG_HYSTERESIS 8
+
 char lineBuf[MAX_LINE];
 int lineLen = 0;
 
+// Digital pins reported as events (bit per pin 0-13)
+unsigned int watchMask = 0;
+int lastLevel[14];
+int pendingLevel[14];
+unsigned long pendingSince[14];
+
+// Analog threshold watches (-1 = disabled)
+int analogThreshold[ANALOG_PINS];
+bool analogAbove[ANALOG_PINS];
+
 // Parse integer from JSON: "pin":13 or "value":1
 int parseArg(const char* key, const char* json) {
   char search[32];
@@ -55,6 +76,32 @@ bool hasCmd(const char* json, const char* cmd) {
   return strstr(json, search) != NULL;
 }
 
+void sendOk(const char* idBuf, const char* result) {
+  Serial.print("{\"id\":\"");
+  Serial.print(idBuf);
+  Serial.print("\",\"ok\":true,\"result\":\"");
+  Serial.print(result);
+  Serial.println("\"}");
+}
+
+void sendInvalidPin(const char* idBuf, int pin) {
+  Serial.print("{\"id\":\"");
+  Serial.print(idBuf);
+  Serial.print("\",\"ok\":false,\"result\":\"\",\"error\":\"Invalid pin ");
+  Serial.print(pin);
+  Serial.println("\"}");
+}
+
+// Unsolicited event frame: {"event":"<prefix><pin>","value":<value>}
+void sendEvent(const char* prefix, int pin, int value) {
+  Serial.print("{\"event\":\"");
+  Serial.print(prefix);
+  Serial.print(pin);
+  Serial.print("\",\"value\":");
+  Serial.print(value);
+  Serial.println("}");
+}
+
 void handleLine(const char* line) {
   char idBuf[16];
   copyId(idBuf, sizeof(idBuf), line);
@@ -114,15 +161,84 @@ void handleLine(const char* line) {
     return;
   }
 
+  if (hasCmd(line, "gpio_watch")) {
+    int pin = parseArg("pin", line);
+    int enable = parseArg("enable", line);
+    if (pin < 2 || pin > 13) {
+      // 0/1 are the serial lines
+      sendInvalidPin(idBuf, pin);
+      return;
+    }
+    if (enable == 0) {
+      watchMask &= ~(1u << pin);
+    } else {
+      pinMode(pin, INPUT_PULLUP);
+      lastLevel[pin] = digitalRead(pin);
+      pendingLevel[pin] = lastLevel[pin];
+      watchMask |= (1u << pin);
+    }
+    sendOk(idBuf, "done");
+    return;
+  }
+
+  if (hasCmd(line, "analog_watch")) {
+    int pin = parseArg("pin", line);
+    int enable = parseArg("enable", line);
+    int threshold = parseArg("threshold", line);
+    if (pin < 0 || pin >= ANALOG_PINS) {
+      sendInvalidPin(idBuf, pin);
+      return;
+    }
+    if (enable == 0 || threshold < 0) {
+      analogThreshold[pin] = -1;
+    } else {
+      analogThreshold[pin] = threshold;
+      analogAbove[pin] = analogRead(A0 + pin) > threshold;
+    }
+    sendOk(idBuf, "done");
+    return;
+  }
+
   // Unknown command
   Serial.print("{\"id\":\"");
   Serial.print(idBuf);
   Serial.println("\",\"ok\":false,\"result\":\"\",\"error\":\"Unknown command\"}");
 }
 
+void pollWatches() {
+  unsigned long now = millis();
+  for (int pin = 2; pin <= 13; pin++) {
+    if (!(watchMask & (1u << pin))) continue;
+    int level = digitalRead(pin);
+    if (level != pendingLevel[pin]) {
+      pendingLevel[pin] = level;
+      pendingSince[pin] = now;
+    } else if (level != lastLevel[pin] && now - pendingSince[pin] >= DEBOUNCE_MS) {
+      lastLevel[pin] = level;
+      sendEvent("pin_", pin, level);
+    }
+  }
+
+  for (int pin = 0; pin < ANALOG_PINS; pin++) {
+    int threshold = analogThreshold[pin];
+    if (threshold < 0) continue;
+    int reading = analogRead(A0 + pin);
+    if (!analogAbove[pin] && reading > threshold + ANALOG_HYSTERESIS) {
+      analogAbove[pin] = true;
+      sendEvent("a", pin, reading);
+    } else if (analogAbove[pin] && reading < threshold - ANALOG_HYSTERESIS) {
+      analogAbove[pin] = false;
+      sendEvent("a", pin, reading);
+    }
+  }
+}
+
 void setup() {
   Serial.begin(BAUDRATE);
   lineLen = 0;
+  for (int pin = 0; pin < ANALOG_PINS; pin++) {
+    analogThreshold[pin] = -1;
+  }
 }
 
 void loop() {
@@ -140,4 +256,5 @@ void loop() {
       lineLen = 0;  // Overflow, discard
     }
   }
+  pollWatches();
 }
@@ -4,6 +4,9 @@
 //! USART2 is connected to ST-Link VCP — host sees /dev/ttyACM0 (Linux) or /dev/cu.usbmodem* (macOS).
 //!
 //! Protocol: same as Arduino/ESP32 — see docs/hardware-peripherals-design.md
+//!
+//! The blue user button (B1, PC13) is reported as unsolicited
+//! `{"event":"button","value":1}` (pressed) / `0` (released) frames.
 
 #![no_std]
 #![no_main]
@@ -12,14 +15,18 @@ use core::fmt::Write;
 use core::str;
 use defmt::info;
 use embassy_executor::Spawner;
-use embassy_stm32::gpio::{Level, Output, Speed};
+use embassy_stm32::gpio::{Input, Level, Output, Pull, Speed};
 use embassy_stm32::usart::{Config, Uart};
+use embassy_time::{Duration, Instant};
 use heapless::String;
 use {defmt_rtt as _, panic_probe as _};
 
 /// Arduino-style pin 13 = PA5 (User LED LD2 on Nucleo-F401RE)
 const LED_PIN: u8 = 13;
 
+/// Button level must be stable this long before an event is emitted.
+const DEBOUNCE: Duration = Duration::from_millis(30);
+
 /// Parse integer from JSON: "pin":13 or "value":1
 fn parse_arg(line: &[u8], key: &[u8]) -> Option<i32> {
     // key like b"pin" -> search for b"\"pin\":"
@@ -113,6 +120,8 @@ async fn main(_spawner: Spawner) {
 
     let mut usart = Uart::new_blocking(p.USART2, p.PA3, p.PA2, config).unwrap();
     let mut led = Output::new(p.PA5, Level::Low, Speed::Low);
+    // B1 is active-low with an external pull-up on the Nucleo board
+    let button = Input::new(p.PC13, Pull::None);
 
     info!("ZeroClaw Nucleo firmware ready on USART2 (115200)");
 
@@ -120,10 +129,26 @@ async fn main(_spawner: Spawner) {
     let mut id_buf = [0u8; 16];
     let mut resp_buf: String<128> = String::new();
 
+    let mut pressed = button.is_low();
+    let mut pending = pressed;
+    let mut pending_since = Instant::now();
+
     loop {
-        let mut byte = [0u8; 1];
-        if usart.blocking_read(&mut byte).is_ok() {
-            let b = byte[0];
+        // Report debounced button edges between commands
+        let level = button.is_low();
+        if level != pending {
+            pending = level;
+            pending_since = Instant::now();
+        } else if level != pressed && pending_since.elapsed() >= DEBOUNCE {
+            pressed = level;
+            let _ = usart.blocking_write(if pressed {
+                b"{\"event\":\"button\",\"value\":1}\n"
+            } else {
+                b"{\"event\":\"button\",\"value\":0}\n"
+            });
+        }
+
+        if let Ok(b) = usart.nb_read() {
             if b == b'\n' || b == b'\r' {
                 if !line_buf.is_empty() {
                     let id_len = copy_id(&line_buf, &mut id_buf);
@@ -1,11 +1,13 @@
 use crate::agent::dispatcher::{
     NativeToolDispatcher, ParsedToolCall, ToolDispatcher, ToolExecutionResult, XmlToolDispatcher,
 };
+use crate::agent::loop_::observer_summary;
 use crate::agent::memory_loader::{DefaultMemoryLoader, MemoryLoader};
 use crate::agent::prompt::{PromptContext, SystemPromptBuilder};
 use crate::config::Config;
 use crate::memory::{self, Memory, MemoryCategory};
-use crate::observability::{self, Observer, ObserverEvent};
+use crate::observability::{self, turn_events, Observer, ObserverEvent};
+use crate::providers::tokenizer;
 use crate::providers::{self, ChatMessage, ChatRequest, ConversationMessage, Provider};
 use crate::runtime;
 use crate::security::SecurityPolicy;
@@ -27,6 +29,8 @@ pub struct Agent {
     memory_loader: Box<dyn MemoryLoader>,
     config: crate::config::AgentConfig,
     model_name: String,
+    provider_name: String,
+    channel_name: String,
     temperature: f64,
     workspace_dir: std::path::PathBuf,
     identity_config: crate::config::IdentityConfig,
@@ -52,6 +56,8 @@ pub struct AgentBuilder {
     memory_loader: Option<Box<dyn MemoryLoader>>,
     config: Option<crate::config::AgentConfig>,
     model_name: Option<String>,
+    provider_name: Option<String>,
+    channel_name: Option<String>,
     temperature: Option<f64>,
     workspace_dir: Option<std::path::PathBuf>,
     identity_config: Option<crate::config::IdentityConfig>,
@@ -78,6 +84,8 @@ impl AgentBuilder {
             memory_loader: None,
             config: None,
             model_name: None,
+            provider_name: None,
+            channel_name: None,
             temperature: None,
             workspace_dir: None,
             identity_config: None,
@@ -138,6 +146,17 @@ impl AgentBuilder {
         self
     }
 
+    pub fn provider_name(mut self, provider_name: String) -> Self {
+        self.provider_name = Some(provider_name);
+        self
+    }
+
+    /// Channel reported for this agent's turns on the turn event stream.
+    pub fn channel_name(mut self, channel_name: String) -> Self {
+        self.channel_name = Some(channel_name);
+        self
+    }
+
     pub fn temperature(mut self, temperature: f64) -> Self {
         self.temperature = Some(temperature);
         self
@@ -242,6 +261,8 @@ impl AgentBuilder {
             model_name: self
                 .model_name
                 .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into()),
+            provider_name: self.provider_name.unwrap_or_else(|| "unknown".into()),
+            channel_name: self.channel_name.unwrap_or_else(|| "cli".into()),
             temperature: self.temperature.unwrap_or(0.7),
             workspace_dir: self
                 .workspace_dir
@@ -296,6 +317,10 @@ impl Agent {
         self.memory_session_id = session_id;
     }
 
+    pub fn set_channel_name(&mut self, channel_name: impl Into<String>) {
+        self.channel_name = channel_name.into();
+    }
+
     /// Hydrate the agent with prior chat messages (e.g. from a session backend).
     ///
     /// Ensures a system prompt is prepended if history is empty, then appends all
@@ -395,6 +420,12 @@ impl Agent {
             .collect();
         let available_hints: Vec<String> = route_model_by_hint.keys().cloned().collect();
 
+        // Recalled memory gets an eighth of the history budget.
+        let memory_token_budget = tokenizer::context_budget(
+            config.agent.max_context_tokens,
+            provider.context_window(&model_name),
+        ) / 8;
+
         let response_cache = if config.memory.response_cache_enabled {
             crate::memory::response_cache::ResponseCache::with_hot_cache(
                 &config.workspace_dir,
@@ -415,13 +446,15 @@ impl Agent {
             .observer(observer)
             .response_cache(response_cache)
             .tool_dispatcher(tool_dispatcher)
-            .memory_loader(Box::new(DefaultMemoryLoader::new(
-                5,
-                config.memory.min_relevance_score,
-            )))
+            .memory_loader(Box::new(
+                DefaultMemoryLoader::new(5, config.memory.min_relevance_score)
+                    .with_recency_half_life(config.memory.recency_half_life_days)
+                    .with_token_budget(&model_name, memory_token_budget),
+            ))
             .prompt_builder(SystemPromptBuilder::with_defaults())
             .config(config.agent.clone())
             .model_name(model_name)
+            .provider_name(provider_name.to_string())
             .temperature(config.default_temperature)
             .workspace_dir(config.workspace_dir.clone())
             .classification_config(config.query_classification.clone())
@@ -434,6 +467,7 @@ impl Agent {
             ))
             .skills_prompt_mode(config.skills.prompt_injection_mode)
             .auto_save(config.memory.auto_save)
+            .allowed_tools(config.profile_allowed_tools())
             .build()
     }
 
@@ -500,24 +534,28 @@ impl Agent {
         let result = if let Some(tool) = self.tools.iter().find(|t| t.name() == call.name) {
             match tool.execute(call.arguments.clone()).await {
                 Ok(r) => {
+                    let output = if r.success {
+                        r.output
+                    } else {
+                        format!("Error: {}", r.error.unwrap_or(r.output))
+                    };
                     self.observer.record_event(&ObserverEvent::ToolCall {
                         tool: call.name.clone(),
                         duration: start.elapsed(),
                         success: r.success,
+                        output: Some(observer_summary(&output)),
                     });
-                    if r.success {
-                        r.output
-                    } else {
-                        format!("Error: {}", r.error.unwrap_or(r.output))
-                    }
+                    output
                 }
                 Err(e) => {
+                    let output = format!("Error executing {}: {e}", call.name);
                     self.observer.record_event(&ObserverEvent::ToolCall {
                         tool: call.name.clone(),
                         duration: start.elapsed(),
                         success: false,
+                        output: Some(observer_summary(&output)),
                     });
-                    format!("Error executing {}: {e}", call.name)
+                    output
                 }
             }
         } else {
@@ -573,6 +611,27 @@ impl Agent {
     }
 
     pub async fn turn(&mut self, user_message: &str) -> Result<String> {
+        let turn =
+            turn_events::TurnContext::new(uuid::Uuid::new_v4().to_string(), &self.channel_name);
+        let observer = Arc::clone(&self.observer);
+        turn_events::with_turn(turn, async {
+            observer.record_event(&ObserverEvent::TurnStart {
+                provider: self.provider_name.clone(),
+                model: self.model_name.clone(),
+            });
+            let result = self.run_turn(user_message).await;
+            match &result {
+                Ok(_) => observer.record_event(&ObserverEvent::TurnComplete),
+                Err(e) => observer.record_event(&ObserverEvent::TurnFailed {
+                    error: providers::sanitize_api_error(&e.to_string()),
+                }),
+            }
+            result
+        })
+        .await
+    }
+
+    async fn run_turn(&mut self, user_message: &str) -> Result<String> {
         if self.history.is_empty() {
             let system_prompt = self.build_system_prompt()?;
             self.history
@@ -660,6 +719,20 @@ impl Agent {
                 });
             }
 
+            let budget_model = crate::cost::scope::gate_llm_call(
+                self.provider.as_ref(),
+                None,
+                &effective_model,
+                &messages,
+            )?;
+            let call_model = budget_model.as_deref().unwrap_or(&effective_model);
+
+            self.observer.record_event(&ObserverEvent::LlmRequest {
+                provider: self.provider_name.clone(),
+                model: call_model.to_string(),
+                messages_count: messages.len(),
+            });
+            let llm_started_at = Instant::now();
             let response = match self
                 .provider
                 .chat(
@@ -672,14 +745,41 @@ impl Agent {
                         },
                         tool_choice: None,
                     },
-                    &effective_model,
+                    call_model,
                     self.temperature,
                 )
                 .await
             {
                 Ok(resp) => resp,
-                Err(err) => return Err(err),
+                Err(err) => {
+                    self.observer.record_event(&ObserverEvent::LlmResponse {
+                        provider: self.provider_name.clone(),
+                        model: call_model.to_string(),
+                        duration: llm_started_at.elapsed(),
+                        success: false,
+                        error_message: Some(providers::sanitize_api_error(&err.to_string())),
+                        input_tokens: None,
+                        output_tokens: None,
+                    });
+                    return Err(err);
+                }
             };
+            self.observer.record_event(&ObserverEvent::LlmResponse {
+                provider: self.provider_name.clone(),
+                model: call_model.to_string(),
+                duration: llm_started_at.elapsed(),
+                success: true,
+                error_message: None,
+                input_tokens: response.usage.as_ref().and_then(|u| u.input_tokens),
+                output_tokens: response.usage.as_ref().and_then(|u| u.output_tokens),
+            });
+            crate::cost::scope::record_llm_call(
+                call_model,
+                response.usage.as_ref().and_then(|u| u.input_tokens),
+                response.usage.as_ref().and_then(|u| u.output_tokens),
+                &messages,
+                response.text_or_empty(),
+            );
 
             let (text, calls) = self.tool_dispatcher.parse_response(&response);
             if calls.is_empty() {
@@ -2,15 +2,19 @@ use crate::approval::{ApprovalManager, ApprovalRequest, ApprovalResponse};
 use crate::config::Config;
 use crate::memory::{self, Memory, MemoryCategory};
 use crate::multimodal;
-use crate::observability::{self, runtime_trace, Observer, ObserverEvent};
+use crate::observability::{self, runtime_trace, turn_events, Observer, ObserverEvent};
+use crate::providers::tokenizer;
+use crate::providers::traits::{ChatEventStream, ChatStreamAccumulator};
 use crate::providers::{
-    self, ChatMessage, ChatRequest, Provider, ProviderCapabilityError, ToolCall,
+    self, ChatMessage, ChatRequest, ChatResponse, ChatStreamEvent, Provider,
+    ProviderCapabilityError, ToolCall,
 };
 use crate::runtime;
 use crate::security::SecurityPolicy;
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
+use futures_util::StreamExt;
 use regex::{Regex, RegexSet};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -213,21 +217,38 @@ const COMPACTION_MAX_SOURCE_CHARS: usize = 12_000;
 /// Max characters retained in stored compaction summary.
 const COMPACTION_MAX_SUMMARY_CHARS: usize = 2_000;
 
-/// Estimate token count for a message history using ~4 chars/token heuristic.
-/// Includes a small overhead per message for role/framing tokens.
-fn estimate_history_tokens(history: &[ChatMessage]) -> usize {
-    history
-        .iter()
-        .map(|m| {
-            // ~4 chars per token + ~4 framing tokens per message (role, delimiters)
-            m.content.len().div_ceil(4) + 4
-        })
-        .sum()
+/// Estimate token count for a message history with the tokenizer that best
+/// matches `model`. Includes a small overhead per message for role/framing tokens.
+fn estimate_history_tokens(history: &[ChatMessage], model: &str) -> usize {
+    let tokenizer = tokenizer::tokenizer_for_model(model);
+    tokenizer::count_messages(tokenizer.as_ref(), history)
 }
 
 /// Minimum interval between progress sends to avoid flooding the draft channel.
 pub(crate) const PROGRESS_MIN_INTERVAL_MS: u64 = 500;
 
+/// Drain a provider event stream, relaying text deltas to the draft channel
+/// as they arrive. Returns the assembled response and whether any text was
+/// relayed.
+async fn collect_streamed_chat(
+    mut events: ChatEventStream,
+    tx: &tokio::sync::mpsc::Sender<String>,
+) -> Result<(ChatResponse, bool)> {
+    let mut accumulator = ChatStreamAccumulator::new();
+    let mut streamed_text = false;
+    while let Some(event) = events.next().await {
+        let event = event?;
+        if let ChatStreamEvent::TextDelta(text) = &event {
+            if !text.is_empty() {
+                // A dropped receiver only stops the live preview, not the turn.
+                streamed_text |= tx.send(text.clone()).await.is_ok();
+            }
+        }
+        accumulator.push(&event);
+    }
+    Ok((accumulator.finish(), streamed_text))
+}
+
 /// Sentinel value sent through on_delta to signal the draft updater to clear accumulated text.
 /// Used before streaming the final answer so progress lines are replaced by the clean response.
 pub(crate) const DRAFT_CLEAR_SENTINEL: &str = "\x00CLEAR\x00";
@@ -350,10 +371,12 @@ async fn auto_compact_history(
         history.len()
     };
 
-    let estimated_tokens = estimate_history_tokens(history);
+    let estimated_tokens = estimate_history_tokens(history, model);
+    let token_budget =
+        tokenizer::context_budget(max_context_tokens, provider.context_window(model));
 
     // Trigger compaction when either token budget OR message count is exceeded.
-    if estimated_tokens <= max_context_tokens && non_system_count <= max_history {
+    if estimated_tokens <= token_budget && non_system_count <= max_history {
         return Ok(false);
     }
 
@@ -493,9 +516,32 @@ async fn build_context(
     context
 }
 
+/// Load the datasheet index when `peripherals.datasheet_dir` is set, embedding
+/// it when `peripherals.datasheet_embeddings` is on. Chunk embeddings come from
+/// the workspace cache, so only new or edited chunks reach the embedder.
+async fn load_hardware_rag(config: &Config) -> Option<crate::rag::HardwareRag> {
+    let rag = config
+        .peripherals
+        .datasheet_dir
+        .as_ref()
+        .filter(|d| !d.trim().is_empty())
+        .map(|dir| crate::rag::HardwareRag::load(&config.workspace_dir, dir.trim()))
+        .and_then(Result::ok)
+        .filter(|r| !r.is_empty())?;
+    if !config.peripherals.datasheet_embeddings {
+        return Some(rag);
+    }
+    let embedder = memory::create_embedder(
+        &config.memory,
+        &config.embedding_routes,
+        config.api_key.as_deref(),
+    );
+    Some(rag.with_embeddings(embedder).await)
+}
+
 /// Build hardware datasheet context from RAG when peripherals are enabled.
 /// Includes pin-alias lookup (e.g. "red_led" → 13) when query matches, plus retrieved chunks.
-fn build_hardware_context(
+async fn build_hardware_context(
     rag: &crate::rag::HardwareRag,
     user_msg: &str,
     boards: &[String],
@@ -513,7 +559,7 @@ fn build_hardware_context(
         context.push_str(&pin_ctx);
     }
 
-    let chunks = rag.retrieve(user_msg, boards, chunk_limit);
+    let chunks = rag.retrieve_semantic(user_msg, boards, chunk_limit).await;
     if chunks.is_empty() && pin_ctx.is_empty() {
         return String::new();
     }
@@ -2188,10 +2234,9 @@ async fn execute_one_tool(
     observer: &dyn Observer,
     cancellation_token: Option<&CancellationToken>,
 ) -> Result<ToolExecutionOutcome> {
-    let args_summary = truncate_with_ellipsis(&call_arguments.to_string(), 300);
     observer.record_event(&ObserverEvent::ToolCallStart {
         tool: call_name.to_string(),
-        arguments: Some(args_summary),
+        arguments: Some(observer_summary(&call_arguments.to_string())),
     });
     let start = Instant::now();
 
@@ -2208,6 +2253,7 @@ async fn execute_one_tool(
             tool: call_name.to_string(),
             duration,
             success: false,
+            output: Some(observer_summary(&reason)),
         });
         return Ok(ToolExecutionOutcome {
             output: reason.clone(),
@@ -2230,10 +2276,16 @@ async fn execute_one_tool(
     match tool_result {
         Ok(r) => {
             let duration = start.elapsed();
+            let summary = if r.success {
+                r.output.as_str()
+            } else {
+                r.error.as_deref().unwrap_or(&r.output)
+            };
             observer.record_event(&ObserverEvent::ToolCall {
                 tool: call_name.to_string(),
                 duration,
                 success: r.success,
+                output: Some(observer_summary(summary)),
             });
             if r.success {
                 Ok(ToolExecutionOutcome {
@@ -2254,12 +2306,13 @@ async fn execute_one_tool(
         }
         Err(e) => {
             let duration = start.elapsed();
+            let reason = format!("Error executing {call_name}: {e}");
             observer.record_event(&ObserverEvent::ToolCall {
                 tool: call_name.to_string(),
                 duration,
                 success: false,
+                output: Some(observer_summary(&reason)),
             });
-            let reason = format!("Error executing {call_name}: {e}");
             Ok(ToolExecutionOutcome {
                 output: reason.clone(),
                 success: false,
@@ -2270,6 +2323,14 @@ async fn execute_one_tool(
     }
 }
 
+/// Maximum characters of tool arguments or output carried by observer events.
+const OBSERVER_SUMMARY_CHARS: usize = 300;
+
+/// Credential-scrubbed, truncated text for observer events.
+pub(crate) fn observer_summary(text: &str) -> String {
+    truncate_with_ellipsis(&scrub_credentials(text), OBSERVER_SUMMARY_CHARS)
+}
+
 struct ToolExecutionOutcome {
     output: String,
     success: bool,
@@ -2381,6 +2442,69 @@ pub(crate) async fn run_tool_call_loop(
     excluded_tools: &[String],
     dedup_exempt_tools: &[String],
     activated_tools: Option<&std::sync::Arc<std::sync::Mutex<crate::tools::ActivatedToolSet>>>,
+) -> Result<String> {
+    let turn = turn_events::TurnContext::new(Uuid::new_v4().to_string(), channel_name);
+    let turn_id = turn.turn_id.clone();
+    turn_events::with_turn(turn, async {
+        observer.record_event(&ObserverEvent::TurnStart {
+            provider: provider_name.to_string(),
+            model: model.to_string(),
+        });
+        let result = run_tool_call_loop_turn(
+            provider,
+            history,
+            tools_registry,
+            observer,
+            provider_name,
+            model,
+            temperature,
+            silent,
+            approval,
+            channel_name,
+            multimodal_config,
+            max_tool_iterations,
+            cancellation_token,
+            on_delta,
+            hooks,
+            excluded_tools,
+            dedup_exempt_tools,
+            activated_tools,
+            turn_id,
+        )
+        .await;
+        match &result {
+            Ok(_) => observer.record_event(&ObserverEvent::TurnComplete),
+            Err(e) => observer.record_event(&ObserverEvent::TurnFailed {
+                error: crate::providers::sanitize_api_error(&e.to_string()),
+            }),
+        }
+        result
+    })
+    .await
+}
+
+/// Body of [`run_tool_call_loop`]; `turn_id` tags its runtime trace events.
+#[allow(clippy::too_many_arguments)]
+async fn run_tool_call_loop_turn(
+    provider: &dyn Provider,
+    history: &mut Vec<ChatMessage>,
+    tools_registry: &[Box<dyn Tool>],
+    observer: &dyn Observer,
+    provider_name: &str,
+    model: &str,
+    temperature: f64,
+    silent: bool,
+    approval: Option<&ApprovalManager>,
+    channel_name: &str,
+    multimodal_config: &crate::config::MultimodalConfig,
+    max_tool_iterations: usize,
+    cancellation_token: Option<CancellationToken>,
+    on_delta: Option<tokio::sync::mpsc::Sender<String>>,
+    hooks: Option<&crate::hooks::HookRunner>,
+    excluded_tools: &[String],
+    dedup_exempt_tools: &[String],
+    activated_tools: Option<&std::sync::Arc<std::sync::Mutex<crate::tools::ActivatedToolSet>>>,
+    turn_id: String,
 ) -> Result<String> {
     let max_iterations = if max_tool_iterations == 0 {
         DEFAULT_MAX_TOOL_ITERATIONS
@@ -2389,7 +2513,6 @@ pub(crate) async fn run_tool_call_loop(
     };
 
     let force_tool_use = tools_registry.iter().any(|t| t.force_tool_use());
-    let turn_id = Uuid::new_v4().to_string();
     let mut seen_tool_signatures: HashSet<(String, String)> = HashSet::new();
 
     let loop_start = std::time::Instant::now();
@@ -2444,6 +2567,15 @@ pub(crate) async fn run_tool_call_loop(
         let prepared_messages =
             multimodal::prepare_messages_for_provider(history, multimodal_config).await?;
 
+        // Scoped cost quotas may block the call or move it to a cheaper route.
+        let budget_model = crate::cost::scope::gate_llm_call(
+            provider,
+            Some(provider_name),
+            model,
+            &prepared_messages.messages,
+        )?;
+        let model = budget_model.as_deref().unwrap_or(model);
+
         // ── Progress: LLM thinking ────────────────────────────
         if let Some(ref tx) = on_delta {
             let phase = if iteration == 0 {
@@ -2459,6 +2591,17 @@ pub(crate) async fn run_tool_call_loop(
             model: model.to_string(),
             messages_count: history.len(),
         });
+        let mut request_payload = serde_json::json!({
+            "iteration": iteration + 1,
+            "messages_count": history.len(),
+        });
+        if iteration == 0 {
+            // The triggering message lets `zeroclaw trace export` rebuild the turn.
+            if let Some(user) = history.iter().rev().find(|m| m.role == "user") {
+                request_payload["user_input"] =
+                    serde_json::Value::String(scrub_credentials(&user.content));
+            }
+        }
         runtime_trace::record_event(
             "llm_request",
             Some(channel_name),
@@ -2467,10 +2610,7 @@ pub(crate) async fn run_tool_call_loop(
             Some(&turn_id),
             None,
             None,
-            serde_json::json!({
-                "iteration": iteration + 1,
-                "messages_count": history.len(),
-            }),
+            request_payload,
         );
 
         let llm_started_at = Instant::now();
@@ -2488,19 +2628,37 @@ pub(crate) async fn run_tool_call_loop(
             None
         };
 
-        let chat_future = provider.chat(
-            ChatRequest {
-                messages: &prepared_messages.messages,
-                tools: request_tools,
-                tool_choice: if force_tool_use && iteration == 0 {
-                    Some("required")
-                } else {
-                    None
-                },
+        let chat_request = ChatRequest {
+            messages: &prepared_messages.messages,
+            tools: request_tools,
+            tool_choice: if force_tool_use && iteration == 0 {
+                Some("required")
+            } else {
+                None
             },
-            model,
-            temperature,
-        );
+        };
+
+        // Relay tokens live when the channel shows drafts and the provider can
+        // stream structured responses. Prompt-guided tool calls are embedded in
+        // the text itself, so those turns keep the buffered path.
+        let live_delta = on_delta
+            .as_ref()
+            .filter(|_| provider.supports_streaming_chat())
+            .filter(|_| use_native_tools || tool_specs.is_empty());
+
+        let chat_future = async {
+            if let Some(tx) = live_delta {
+                let events = provider
+                    .stream_chat(chat_request, model, temperature)
+                    .await?;
+                collect_streamed_chat(events, tx).await
+            } else {
+                provider
+                    .chat(chat_request, model, temperature)
+                    .await
+                    .map(|resp| (resp, false))
+            }
+        };
 
         let chat_result = if let Some(token) = cancellation_token.as_ref() {
             tokio::select! {
@@ -2511,9 +2669,11 @@ pub(crate) async fn run_tool_call_loop(
             chat_future.await
         };
 
+        let streamed_text;
         let (response_text, parsed_text, tool_calls, assistant_history_content, native_tool_calls) =
             match chat_result {
-                Ok(resp) => {
+                Ok((resp, streamed)) => {
+                    streamed_text = streamed;
                     let (resp_input_tokens, resp_output_tokens) = resp
                         .usage
                         .as_ref()
@@ -2531,6 +2691,13 @@ pub(crate) async fn run_tool_call_loop(
                     });
 
                     let response_text = resp.text_or_empty().to_string();
+                    crate::cost::scope::record_llm_call(
+                        model,
+                        resp_input_tokens,
+                        resp_output_tokens,
+                        &prepared_messages.messages,
+                        &response_text,
+                    );
                     // First try native structured tool calls (OpenAI-format).
                     // Fall back to text-based parsing (XML tags, markdown blocks,
                     // GLM format) only if the provider returned no native calls —
@@ -2652,6 +2819,9 @@ pub(crate) async fn run_tool_call_loop(
         if let Some(ref tx) = on_delta {
             let llm_secs = llm_started_at.elapsed().as_secs();
             if !tool_calls.is_empty() {
+                if streamed_text && !response_text.ends_with('\n') {
+                    let _ = tx.send("\n".to_string()).await;
+                }
                 let _ = tx
                     .send(format!(
                         "\u{1f4ac} Got {} tool call(s) ({llm_secs}s)\n",
@@ -2681,26 +2851,31 @@ pub(crate) async fn run_tool_call_loop(
             if let Some(ref tx) = on_delta {
                 // Clear accumulated progress lines before streaming the final answer.
                 let _ = tx.send(DRAFT_CLEAR_SENTINEL.to_string()).await;
-                // Split on whitespace boundaries, accumulating chunks of at least
-                // STREAM_CHUNK_MIN_CHARS characters for progressive draft updates.
-                let mut chunk = String::new();
-                for word in display_text.split_inclusive(char::is_whitespace) {
-                    if cancellation_token
-                        .as_ref()
-                        .is_some_and(CancellationToken::is_cancelled)
-                    {
-                        return Err(ToolLoopCancelled.into());
+                if streamed_text {
+                    // Tokens were already shown live; restore the answer in one update.
+                    let _ = tx.send(display_text.clone()).await;
+                } else {
+                    // Split on whitespace boundaries, accumulating chunks of at least
+                    // STREAM_CHUNK_MIN_CHARS characters for progressive draft updates.
+                    let mut chunk = String::new();
+                    for word in display_text.split_inclusive(char::is_whitespace) {
+                        if cancellation_token
+                            .as_ref()
+                            .is_some_and(CancellationToken::is_cancelled)
+                        {
+                            return Err(ToolLoopCancelled.into());
+                        }
+                        chunk.push_str(word);
+                        if chunk.len() >= STREAM_CHUNK_MIN_CHARS
+                            && tx.send(std::mem::take(&mut chunk)).await.is_err()
+                        {
+                            break; // receiver dropped
+                        }
                     }
-                    chunk.push_str(word);
-                    if chunk.len() >= STREAM_CHUNK_MIN_CHARS
-                        && tx.send(std::mem::take(&mut chunk)).await.is_err()
-                    {
-                        break; // receiver dropped
+                    if !chunk.is_empty() {
+                        let _ = tx.send(chunk).await;
                     }
                 }
-                if !chunk.is_empty() {
-                    let _ = tx.send(chunk).await;
-                }
             }
             history.push(ChatMessage::assistant(response_text.clone()));
             tracing::debug!(
@@ -2838,15 +3013,15 @@ pub(crate) async fn run_tool_call_loop(
                         tool_name: tool_name.clone(),
                         arguments: tool_args.clone(),
                     };
+                    observer.record_event(&ObserverEvent::ApprovalRequested {
+                        tool: tool_name.clone(),
+                        arguments: Some(observer_summary(&tool_args.to_string())),
+                    });
 
                     // Interactive CLI: prompt the operator.
-                    // Non-interactive (channels): auto-deny since no operator
-                    // is present to approve.
-                    let decision = if mgr.is_non_interactive() {
-                        ApprovalResponse::No
-                    } else {
-                        mgr.prompt_cli(&request)
-                    };
+                    // Channels: ask the sender through the attached prompter,
+                    // or auto-deny when no prompter is available.
+                    let decision = mgr.request_approval(&request).await;
 
                     mgr.record_decision(&tool_name, &tool_args, decision, channel_name);
 
@@ -3238,6 +3413,7 @@ pub async fn run(
         let skill_tools = crate::skills::create_skill_tools_with_override(
             &skills_for_tools,
             security.clone(),
+            &config.wasm,
             config.skills.tool_choice_required,
         );
         if !skill_tools.is_empty() {
@@ -3345,6 +3521,12 @@ pub async fn run(
         }
     }
 
+    // ── Wire appMCP manifest tools (non-fatal) ───────────────────
+    crate::tools::app_mcp::attach_app_mcp_tools(&config.app_mcp, &mut activated_handle).await;
+
+    // ── Wire mesh peer tools (kept in sync with the peer directory) ──
+    crate::tools::peer_tool::attach_peer_tools(&config.node_transport, &mut activated_handle);
+
     // ── Resolve provider ─────────────────────────────────────────
     let provider_name = provider_override
         .as_deref()
@@ -3374,14 +3556,7 @@ pub async fn run(
     });
 
     // ── Hardware RAG (datasheet retrieval when peripherals + datasheet_dir) ──
-    let hardware_rag: Option<crate::rag::HardwareRag> = config
-        .peripherals
-        .datasheet_dir
-        .as_ref()
-        .filter(|d| !d.trim().is_empty())
-        .map(|dir| crate::rag::HardwareRag::load(&config.workspace_dir, dir.trim()))
-        .and_then(Result::ok)
-        .filter(|r: &crate::rag::HardwareRag| !r.is_empty());
+    let hardware_rag = load_hardware_rag(&config).await;
     if let Some(ref rag) = hardware_rag {
         tracing::info!(chunks = rag.len(), "Hardware RAG loaded");
     }
@@ -3573,10 +3748,10 @@ pub async fn run(
         )
         .await;
         let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-        let hw_context = hardware_rag
-            .as_ref()
-            .map(|r| build_hardware_context(r, &msg, &board_names, rag_limit))
-            .unwrap_or_default();
+        let hw_context = match hardware_rag.as_ref() {
+            Some(r) => build_hardware_context(r, &msg, &board_names, rag_limit).await,
+            None => String::new(),
+        };
         let context = format!("{mem_context}{hw_context}");
         let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
         let enriched = if context.is_empty() {
@@ -3617,7 +3792,6 @@ pub async fn run(
         .await?;
         final_output = response.clone();
         println!("{response}");
-        observer.record_event(&ObserverEvent::TurnComplete);
     } else {
         println!("🦀 ZeroClaw Interactive Mode");
         println!("Type /help for commands.\n");
@@ -3735,10 +3909,10 @@ pub async fn run(
             )
             .await;
             let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-            let hw_context = hardware_rag
-                .as_ref()
-                .map(|r| build_hardware_context(r, &user_input, &board_names, rag_limit))
-                .unwrap_or_default();
+            let hw_context = match hardware_rag.as_ref() {
+                Some(r) => build_hardware_context(r, &user_input, &board_names, rag_limit).await,
+                None => String::new(),
+            };
             let context = format!("{mem_context}{hw_context}");
             let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
             let enriched = if context.is_empty() {
@@ -3793,7 +3967,6 @@ pub async fn run(
             {
                 eprintln!("\nError sending CLI response: {e}\n");
             }
-            observer.record_event(&ObserverEvent::TurnComplete);
 
             // Auto-compaction before hard trimming to preserve long-context signal.
             if let Ok(compacted) = auto_compact_history(
@@ -3838,6 +4011,18 @@ pub async fn process_message(
     config: Config,
     message: &str,
     session_id: Option<&str>,
+) -> Result<String> {
+    Box::pin(process_message_streaming(config, message, session_id, None)).await
+}
+
+/// [`process_message`] that relays progress and the final answer through
+/// `on_delta` as the turn runs. Progress lines precede
+/// `DRAFT_CLEAR_SENTINEL`; everything after it is the answer.
+pub async fn process_message_streaming(
+    config: Config,
+    message: &str,
+    session_id: Option<&str>,
+    on_delta: Option<tokio::sync::mpsc::Sender<String>>,
 ) -> Result<String> {
     let observer: Arc<dyn Observer> =
         Arc::from(observability::create_observer(&config.observability));
@@ -3893,6 +4078,7 @@ pub async fn process_message(
         let skill_tools = crate::skills::create_skill_tools_with_override(
             &skills_for_tools,
             security.clone(),
+            &config.wasm,
             config.skills.tool_choice_required,
         );
         if !skill_tools.is_empty() {
@@ -3912,6 +4098,12 @@ pub async fn process_message(
         }
     }
 
+    // ── Profile tool allowlist (`[profiles.<name>].allowed_tools`) ──
+    let profile_allowed_tools = config.profile_allowed_tools();
+    if let Some(ref allow_list) = profile_allowed_tools {
+        tools_registry.retain(|t| allow_list.iter().any(|name| name == t.name()));
+    }
+
     // ── Wire MCP tools (non-fatal) — process_message path ────────
     // NOTE: Same ordering contract as the CLI path above — MCP tools must be
     // injected after filter_primary_agent_tools_or_fail (or equivalent built-in
@@ -3929,10 +4121,15 @@ pub async fn process_message(
             Ok(registry) => {
                 let registry = std::sync::Arc::new(registry);
                 if config.mcp.deferred_loading {
-                    let deferred_set = crate::tools::DeferredMcpToolSet::from_registry(
+                    let mut deferred_set = crate::tools::DeferredMcpToolSet::from_registry(
                         std::sync::Arc::clone(&registry),
                     )
                     .await;
+                    if let Some(ref allow_list) = profile_allowed_tools {
+                        deferred_set
+                            .stubs
+                            .retain(|stub| allow_list.contains(&stub.prefixed_name));
+                    }
                     tracing::info!(
                         "MCP deferred: {} tool stub(s) from {} server(s)",
                         deferred_set.len(),
@@ -3952,6 +4149,12 @@ pub async fn process_message(
                     let names = registry.tool_names();
                     let mut registered = 0usize;
                     for name in names {
+                        if profile_allowed_tools
+                            .as_ref()
+                            .is_some_and(|allow_list| !allow_list.contains(&name))
+                        {
+                            continue;
+                        }
                         if let Some(def) = registry.get_tool_def(&name).await {
                             let wrapper: std::sync::Arc<dyn Tool> =
                                 std::sync::Arc::new(crate::tools::McpToolWrapper::new(
@@ -3979,6 +4182,17 @@ pub async fn process_message(
         }
     }
 
+    // ── Wire appMCP manifest tools (non-fatal) ───────────────────
+    crate::tools::app_mcp::attach_app_mcp_tools(&config.app_mcp, &mut activated_handle_pm).await;
+
+    // ── Wire mesh peer tools (kept in sync with the peer directory) ──
+    crate::tools::peer_tool::attach_peer_tools(&config.node_transport, &mut activated_handle_pm);
+
+    // App and peer tools arrive at runtime, so the allowlist travels with the set.
+    if let (Some(allow_list), Some(handle)) = (&profile_allowed_tools, &activated_handle_pm) {
+        handle.lock().unwrap().restrict_to(allow_list.clone());
+    }
+
     let provider_name = config.default_provider.as_deref().unwrap_or("openrouter");
     let model_name = config
         .default_model
@@ -3995,14 +4209,7 @@ pub async fn process_message(
         &provider_runtime_options,
     )?;
 
-    let hardware_rag: Option<crate::rag::HardwareRag> = config
-        .peripherals
-        .datasheet_dir
-        .as_ref()
-        .filter(|d| !d.trim().is_empty())
-        .map(|dir| crate::rag::HardwareRag::load(&config.workspace_dir, dir.trim()))
-        .and_then(Result::ok)
-        .filter(|r: &crate::rag::HardwareRag| !r.is_empty());
+    let hardware_rag = load_hardware_rag(&config).await;
     let board_names: Vec<String> = config
         .peripherals
         .boards
@@ -4090,10 +4297,10 @@ pub async fn process_message(
     )
     .await;
     let rag_limit = if config.agent.compact_context { 2 } else { 5 };
-    let hw_context = hardware_rag
-        .as_ref()
-        .map(|r| build_hardware_context(r, message, &board_names, rag_limit))
-        .unwrap_or_default();
+    let hw_context = match hardware_rag.as_ref() {
+        Some(r) => build_hardware_context(r, message, &board_names, rag_limit).await,
+        None => String::new(),
+    };
     let context = format!("{mem_context}{hw_context}");
     let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
     let enriched = if context.is_empty() {
@@ -4109,7 +4316,7 @@ pub async fn process_message(
     let excluded_tools =
         compute_excluded_mcp_tools(&tools_registry, &config.agent.tool_filter_groups, message);
 
-    agent_turn(
+    run_tool_call_loop(
         provider.as_ref(),
         &mut history,
         &tools_registry,
@@ -4118,9 +4325,13 @@ pub async fn process_message(
         &model_name,
         config.default_temperature,
         true,
+        None,
         "daemon",
         &config.multimodal,
         config.agent.max_tool_iterations,
+        None,
+        on_delta,
+        None,
         &excluded_tools,
         &config.agent.tool_call_dedup_exempt,
         activated_handle_pm.as_ref(),
@@ -4251,6 +4462,65 @@ mod tests {
         assert_eq!(invocations.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn execute_one_tool_collects_tool_attachments() {
+        struct ChartTool;
+
+        #[async_trait]
+        impl Tool for ChartTool {
+            fn name(&self) -> &str {
+                "chart"
+            }
+
+            fn description(&self) -> &str {
+                "Renders a chart"
+            }
+
+            fn parameters_schema(&self) -> serde_json::Value {
+                serde_json::json!({ "type": "object" })
+            }
+
+            async fn execute(
+                &self,
+                _args: serde_json::Value,
+            ) -> anyhow::Result<crate::tools::ToolResult> {
+                use crate::channels::traits::{Attachment, AttachmentKind};
+
+                crate::tools::attach_to_reply(Attachment::from_path(
+                    AttachmentKind::Image,
+                    "/tmp/chart.png",
+                ));
+                Ok(crate::tools::ToolResult {
+                    success: true,
+                    output: "rendered".into(),
+                    error: None,
+                })
+            }
+        }
+
+        let observer = NoopObserver;
+        let tools: Vec<Box<dyn Tool>> = vec![Box::new(ChartTool)];
+        let sink = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let outcome = crate::tools::collect_reply_attachments(
+            Arc::clone(&sink),
+            execute_one_tool(
+                "chart",
+                serde_json::json!({}),
+                &tools,
+                None,
+                &observer,
+                None,
+            ),
+        )
+        .await
+        .unwrap();
+
+        assert!(outcome.success);
+        let attachments = sink.lock();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].file_name.as_deref(), Some("chart.png"));
+    }
+
     use crate::memory::{Memory, MemoryCategory, SqliteMemory};
     use crate::observability::NoopObserver;
     use crate::providers::traits::ProviderCapabilities;
@@ -4286,6 +4556,7 @@ mod tests {
                 native_tool_calling: false,
                 vision: true,
                 prompt_caching: false,
+                context_window: None,
             }
         }
 
@@ -4328,6 +4599,7 @@ mod tests {
     struct ScriptedProvider {
         responses: Arc<Mutex<VecDeque<ChatResponse>>>,
         capabilities: ProviderCapabilities,
+        streaming: bool,
     }
 
     impl ScriptedProvider {
@@ -4344,6 +4616,7 @@ mod tests {
             Self {
                 responses: Arc::new(Mutex::new(scripted)),
                 capabilities: ProviderCapabilities::default(),
+                streaming: false,
             }
         }
 
@@ -4351,6 +4624,12 @@ mod tests {
             self.capabilities.native_tool_calling = true;
             self
         }
+
+        /// Serve responses through `stream_chat`, one text delta per word.
+        fn with_streaming(mut self) -> Self {
+            self.streaming = true;
+            self
+        }
     }
 
     #[async_trait]
@@ -4383,6 +4662,27 @@ mod tests {
                 .pop_front()
                 .ok_or_else(|| anyhow::anyhow!("scripted provider exhausted responses"))
         }
+
+        fn supports_streaming_chat(&self) -> bool {
+            self.streaming
+        }
+
+        async fn stream_chat(
+            &self,
+            request: ChatRequest<'_>,
+            model: &str,
+            temperature: f64,
+        ) -> anyhow::Result<ChatEventStream> {
+            let mut response = self.chat(request, model, temperature).await?;
+            let text = response.text.take().unwrap_or_default();
+            let events: Vec<_> = text
+                .split_inclusive(' ')
+                .map(|word| ChatStreamEvent::TextDelta(word.to_string()))
+                .chain(crate::providers::traits::chat_response_events(response))
+                .map(Ok)
+                .collect();
+            Ok(futures_util::stream::iter(events).boxed())
+        }
     }
 
     struct CountingTool {
@@ -4889,6 +5189,49 @@ mod tests {
         assert!(tool_results.content.contains("Skipped duplicate tool call"));
     }
 
+    #[tokio::test]
+    async fn run_tool_call_loop_publishes_turn_lifecycle_events() {
+        let provider = ScriptedProvider::from_text_responses(vec!["done"]);
+        let tools_registry: Vec<Box<dyn Tool>> = Vec::new();
+        let mut history = vec![ChatMessage::user("hello")];
+        let mut rx = turn_events::subscribe();
+        let observer = turn_events::TurnEventObserver::new(Box::new(NoopObserver));
+
+        run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &observer,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "turn-lifecycle-test",
+            &crate::config::MultimodalConfig::default(),
+            3,
+            None,
+            None,
+            None,
+            &[],
+            &[],
+            None,
+        )
+        .await
+        .expect("scripted turn should succeed");
+
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if event.channel.as_deref() == Some("turn-lifecycle-test") {
+                events.push(serde_json::to_value(&event).unwrap());
+            }
+        }
+        let types: Vec<_> = events.iter().map(|e| e["type"].clone()).collect();
+        assert_eq!(types, ["turn_started", "model_call", "turn_finished"]);
+        assert!(events.iter().all(|e| e["turn_id"] == events[0]["turn_id"]));
+        assert_eq!(events[2]["success"], true);
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_allows_low_risk_shell_in_non_interactive_mode() {
         let provider = ScriptedProvider::from_text_responses(vec![
@@ -7024,13 +7367,13 @@ Let me check the result."#;
 
     #[test]
     fn estimate_history_tokens_empty() {
-        assert_eq!(super::estimate_history_tokens(&[]), 0);
+        assert_eq!(super::estimate_history_tokens(&[], "test-model"), 0);
     }
 
     #[test]
     fn estimate_history_tokens_single_message() {
         let history = vec![ChatMessage::user("hello world")]; // 11 chars
-        let tokens = super::estimate_history_tokens(&history);
+        let tokens = super::estimate_history_tokens(&history, "test-model");
         // 11.div_ceil(4) + 4 = 3 + 4 = 7
         assert_eq!(tokens, 7);
     }
@@ -7042,10 +7385,60 @@ Let me check the result."#;
             ChatMessage::user("What is Rust?"),      // 13 chars → 4 + 4 = 8
             ChatMessage::assistant("A language."),   // 11 chars → 3 + 4 = 7
         ];
-        let tokens = super::estimate_history_tokens(&history);
+        let tokens = super::estimate_history_tokens(&history, "test-model");
         assert_eq!(tokens, 23);
     }
 
+    #[test]
+    fn estimate_history_tokens_uses_model_tokenizer() {
+        let history = vec![ChatMessage::user("a".repeat(35))];
+        // Anthropic approximation: 35 / 3.5 = 10 + 4 framing.
+        assert_eq!(
+            super::estimate_history_tokens(&history, "claude-sonnet-4"),
+            14
+        );
+        // Unknown models keep the ~4 chars/token heuristic: 9 + 4.
+        assert_eq!(super::estimate_history_tokens(&history, "test-model"), 13);
+    }
+
+    #[tokio::test]
+    async fn auto_compact_history_triggers_on_provider_context_window() {
+        let build_history = || {
+            let mut history = vec![ChatMessage::system("sys")];
+            for i in 0..30 {
+                let content = format!("{i:02}{}", "x".repeat(98));
+                history.push(if i % 2 == 0 {
+                    ChatMessage::user(content)
+                } else {
+                    ChatMessage::assistant(content)
+                });
+            }
+            history
+        };
+
+        // ~870 tokens fits the configured 32k budget when the window is unknown.
+        let provider = ScriptedProvider::from_text_responses(vec![]);
+        let mut history = build_history();
+        let compacted =
+            auto_compact_history(&mut history, &provider, "test-model", 50, 32_000, 0.0)
+                .await
+                .unwrap();
+        assert!(!compacted);
+        assert_eq!(history.len(), 31);
+
+        // A 1k-token window leaves a 750-token budget, so compaction kicks in.
+        let mut provider = ScriptedProvider::from_text_responses(vec![]);
+        provider.capabilities.context_window = Some(1_000);
+        let mut history = build_history();
+        let compacted =
+            auto_compact_history(&mut history, &provider, "test-model", 50, 32_000, 0.0)
+                .await
+                .unwrap();
+        assert!(compacted);
+        assert!(history.len() < 31);
+        assert!(history[1].content.starts_with("[Compaction summary]"));
+    }
+
     #[tokio::test]
     async fn run_tool_call_loop_surfaces_tool_failure_reason_in_on_delta() {
         let provider = ScriptedProvider::from_text_responses(vec![
@@ -7114,6 +7507,117 @@ Let me check the result."#;
         assert_eq!(result, "I could not execute that command.");
     }
 
+    #[tokio::test]
+    async fn run_tool_call_loop_relays_streamed_tokens_to_on_delta() {
+        let provider =
+            ScriptedProvider::from_text_responses(vec!["Hello streaming world"]).with_streaming();
+        let tools_registry: Vec<Box<dyn Tool>> = Vec::new();
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("say hello"),
+        ];
+        let observer = NoopObserver;
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<String>(64);
+
+        let result = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &observer,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "telegram",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            Some(tx),
+            None,
+            &[],
+            &[],
+            None,
+        )
+        .await
+        .expect("tool loop should complete");
+
+        let mut deltas = Vec::new();
+        while let Ok(msg) = rx.try_recv() {
+            deltas.push(msg);
+        }
+
+        assert_eq!(result, "Hello streaming world");
+        assert_eq!(
+            deltas,
+            vec![
+                "\u{1f914} Thinking...\n".to_string(),
+                "Hello ".to_string(),
+                "streaming ".to_string(),
+                "world".to_string(),
+                DRAFT_CLEAR_SENTINEL.to_string(),
+                "Hello streaming world".to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn run_tool_call_loop_buffers_prompt_guided_tool_turns() {
+        let provider = ScriptedProvider::from_text_responses(vec![
+            r#"<tool_call>
+{"name":"count_tool","arguments":{"value":"A"}}
+</tool_call>"#,
+            "done",
+        ])
+        .with_streaming();
+        let invocations = Arc::new(AtomicUsize::new(0));
+        let tools_registry: Vec<Box<dyn Tool>> = vec![Box::new(CountingTool::new(
+            "count_tool",
+            Arc::clone(&invocations),
+        ))];
+        let mut history = vec![
+            ChatMessage::system("test-system"),
+            ChatMessage::user("run tool"),
+        ];
+        let observer = NoopObserver;
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<String>(64);
+
+        let result = run_tool_call_loop(
+            &provider,
+            &mut history,
+            &tools_registry,
+            &observer,
+            "mock-provider",
+            "mock-model",
+            0.0,
+            true,
+            None,
+            "telegram",
+            &crate::config::MultimodalConfig::default(),
+            4,
+            None,
+            Some(tx),
+            None,
+            &[],
+            &[],
+            None,
+        )
+        .await
+        .expect("tool loop should complete");
+
+        let mut deltas = Vec::new();
+        while let Ok(msg) = rx.try_recv() {
+            deltas.push(msg);
+        }
+
+        assert_eq!(result, "done");
+        assert_eq!(invocations.load(Ordering::SeqCst), 1);
+        assert!(
+            deltas.iter().all(|d| !d.contains("<tool_call>")),
+            "raw tool-call markup must not reach the draft: {deltas:?}"
+        );
+    }
+
     // ── filter_by_allowed_tools tests ─────────────────────────────────────
 
     #[test]
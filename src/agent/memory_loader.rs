@@ -1,6 +1,15 @@
-use crate::memory::{self, Memory};
+use crate::memory::{self, Memory, MemoryEntry};
+use crate::providers::tokenizer::{self, Tokenizer};
 use async_trait::async_trait;
-use std::fmt::Write;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// Candidates recalled per injected entry, so reranking can promote recent
+/// or important memories the backend scored slightly lower.
+const RECALL_OVERFETCH: usize = 3;
+
+/// Default age at which an entry's recency weight is halfway to its floor.
+const DEFAULT_RECENCY_HALF_LIFE_DAYS: u32 = 30;
 
 #[async_trait]
 pub trait MemoryLoader: Send + Sync {
@@ -15,6 +24,8 @@ pub trait MemoryLoader: Send + Sync {
 pub struct DefaultMemoryLoader {
     limit: usize,
     min_relevance_score: f64,
+    token_budget: Option<(Arc<dyn Tokenizer>, usize)>,
+    recency_half_life_days: u32,
 }
 
 impl Default for DefaultMemoryLoader {
@@ -22,6 +33,8 @@ impl Default for DefaultMemoryLoader {
         Self {
             limit: 5,
             min_relevance_score: 0.4,
+            token_budget: None,
+            recency_half_life_days: DEFAULT_RECENCY_HALF_LIFE_DAYS,
         }
     }
 }
@@ -31,8 +44,48 @@ impl DefaultMemoryLoader {
         Self {
             limit: limit.max(1),
             min_relevance_score,
+            token_budget: None,
+            recency_half_life_days: DEFAULT_RECENCY_HALF_LIFE_DAYS,
         }
     }
+
+    /// Cap the injected memory context at `max_tokens`, counted with the
+    /// tokenizer for `model`. Entries are taken in rank order; any entry
+    /// that would overflow the budget is skipped.
+    pub fn with_token_budget(mut self, model: &str, max_tokens: usize) -> Self {
+        self.token_budget = Some((tokenizer::tokenizer_for_model(model), max_tokens));
+        self
+    }
+
+    /// Age in days at which an entry's recency weight drops halfway to its
+    /// floor. `0` ranks entries without regard to age.
+    pub fn with_recency_half_life(mut self, days: u32) -> Self {
+        self.recency_half_life_days = days;
+        self
+    }
+
+    /// Rank score: relevance × recency × importance.
+    ///
+    /// Recency decays from 1.0 toward 0.5 with the entry's age; importance
+    /// scales from 0.5× to 1.5×, so entries stored without one keep their
+    /// relevance.
+    fn rank_score(&self, entry: &MemoryEntry, now: DateTime<Utc>) -> f64 {
+        let relevance = entry.score.unwrap_or(1.0);
+        let importance = 0.5 + entry.metadata.importance();
+        relevance * self.recency_weight(entry, now) * importance
+    }
+
+    fn recency_weight(&self, entry: &MemoryEntry, now: DateTime<Utc>) -> f64 {
+        if self.recency_half_life_days == 0 {
+            return 1.0;
+        }
+        let Ok(written_at) = DateTime::parse_from_rfc3339(&entry.timestamp) else {
+            return 1.0;
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let age_days = now.signed_duration_since(written_at).num_seconds().max(0) as f64 / 86_400.0;
+        0.5 + 0.5 * 0.5_f64.powf(age_days / f64::from(self.recency_half_life_days))
+    }
 }
 
 #[async_trait]
@@ -43,28 +96,48 @@ impl MemoryLoader for DefaultMemoryLoader {
         user_message: &str,
         session_id: Option<&str>,
     ) -> anyhow::Result<String> {
-        let entries = memory.recall(user_message, self.limit, session_id).await?;
-        if entries.is_empty() {
+        let now = Utc::now();
+        let mut ranked: Vec<(f64, MemoryEntry)> = memory
+            .recall(
+                user_message,
+                self.limit.saturating_mul(RECALL_OVERFETCH),
+                session_id,
+            )
+            .await?
+            .into_iter()
+            .filter(|entry| {
+                !memory::is_assistant_autosave_key(&entry.key)
+                    && !memory::should_skip_autosave_content(&entry.content)
+                    && entry
+                        .score
+                        .is_none_or(|score| score >= self.min_relevance_score)
+            })
+            .map(|entry| (self.rank_score(&entry, now), entry))
+            .collect();
+        if ranked.is_empty() {
             return Ok(String::new());
         }
+        ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+        ranked.truncate(self.limit);
 
         let mut context = String::from("[Memory context]\n");
-        for entry in entries {
-            if memory::is_assistant_autosave_key(&entry.key) {
-                continue;
-            }
-            if memory::should_skip_autosave_content(&entry.content) {
-                continue;
-            }
-            if let Some(score) = entry.score {
-                if score < self.min_relevance_score {
+        let mut used_tokens = self
+            .token_budget
+            .as_ref()
+            .map_or(0, |(tokenizer, _)| tokenizer.count(&context));
+        for (_, entry) in ranked {
+            let line = format!("- {}: {}\n", entry.key, entry.content);
+            if let Some((tokenizer, max_tokens)) = &self.token_budget {
+                let line_tokens = tokenizer.count(&line);
+                if used_tokens + line_tokens > *max_tokens {
                     continue;
                 }
+                used_tokens += line_tokens;
             }
-            let _ = writeln!(context, "- {}: {}", entry.key, entry.content);
+            context.push_str(&line);
         }
 
-        // If all entries were below threshold, return empty
+        // If every entry overflowed the token budget, return empty
         if context == "[Memory context]\n" {
             return Ok(String::new());
         }
@@ -77,7 +150,7 @@ impl MemoryLoader for DefaultMemoryLoader {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::memory::{Memory, MemoryCategory, MemoryEntry};
+    use crate::memory::{Memory, MemoryCategory, MemoryEntry, MemoryMetadata};
     use std::sync::Arc;
 
     struct MockMemory;
@@ -114,6 +187,7 @@ mod tests {
                 timestamp: "now".into(),
                 session_id: None,
                 score: None,
+                metadata: MemoryMetadata::default(),
             }])
         }
 
@@ -220,6 +294,7 @@ mod tests {
                     timestamp: "now".into(),
                     session_id: None,
                     score: Some(0.95),
+                    metadata: MemoryMetadata::default(),
                 },
                 MemoryEntry {
                     id: "2".into(),
@@ -229,6 +304,7 @@ mod tests {
                     timestamp: "now".into(),
                     session_id: None,
                     score: Some(0.9),
+                    metadata: MemoryMetadata::default(),
                 },
             ]),
         };
@@ -241,4 +317,72 @@ mod tests {
         assert!(!context.contains("assistant_resp_legacy"));
         assert!(!context.contains("fabricated detail"));
     }
+
+    #[tokio::test]
+    async fn default_loader_respects_token_budget() {
+        let entry = |id: &str, content: String| MemoryEntry {
+            id: id.into(),
+            key: format!("fact_{id}"),
+            content,
+            category: MemoryCategory::Core,
+            timestamp: "now".into(),
+            session_id: None,
+            score: Some(0.9),
+            metadata: MemoryMetadata::default(),
+        };
+        let memory = MockMemoryWithEntries {
+            entries: Arc::new(vec![
+                entry("1", "a".repeat(40)),
+                entry("2", "b".repeat(400)),
+                entry("3", "c".repeat(40)),
+            ]),
+        };
+
+        // Header (5) + two short lines (~13 each) fit; the long one does not.
+        let loader = DefaultMemoryLoader::new(5, 0.0).with_token_budget("test-model", 40);
+        let context = loader.load_context(&memory, "facts", None).await.unwrap();
+        assert!(context.contains("fact_1"));
+        assert!(!context.contains("fact_2"));
+        assert!(context.contains("fact_3"));
+    }
+
+    #[tokio::test]
+    async fn default_loader_ranks_by_recency_and_importance() {
+        let now = Utc::now();
+        let entry = |key: &str, days_old: i64, importance: Option<f64>| MemoryEntry {
+            id: key.into(),
+            key: key.into(),
+            content: format!("{key} content"),
+            category: MemoryCategory::Core,
+            timestamp: (now - chrono::Duration::days(days_old)).to_rfc3339(),
+            session_id: None,
+            score: Some(0.8),
+            metadata: MemoryMetadata {
+                importance,
+                ..Default::default()
+            },
+        };
+        let memory = MockMemoryWithEntries {
+            entries: Arc::new(vec![
+                entry("stale", 365, None),
+                entry("recent", 1, None),
+                entry("pinned", 365, Some(1.0)),
+            ]),
+        };
+
+        let loader = DefaultMemoryLoader::new(2, 0.0);
+        let context = loader.load_context(&memory, "q", None).await.unwrap();
+        let pinned = context.find("pinned").unwrap();
+        let recent = context.find("recent").unwrap();
+        assert!(recent < pinned, "{context}");
+        assert!(!context.contains("stale"));
+
+        // Without recency weighting, the equally important entries tie and
+        // keep recall order.
+        let loader = DefaultMemoryLoader::new(2, 0.0).with_recency_half_life(0);
+        let context = loader.load_context(&memory, "q", None).await.unwrap();
+        assert!(context.contains("pinned"));
+        assert!(context.contains("stale"));
+        assert!(!context.contains("recent"));
+    }
 }
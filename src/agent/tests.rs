@@ -31,6 +31,7 @@ use crate::agent::dispatcher::{
 use crate::config::{AgentConfig, MemoryConfig};
 use crate::memory::{self, Memory};
 use crate::observability::{NoopObserver, Observer};
+use crate::providers::replay::ReplayProvider;
 use crate::providers::{
     ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ToolCall,
     ToolResultMessage,
@@ -1340,3 +1341,58 @@ async fn run_single_delegates_to_turn() {
         "Expected non-empty response from run_single"
     );
 }
+
+// ═══════════════════════════════════════════════════════════════════════════
+// 26. Record-and-replay cassettes
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn replayed_cassette_reproduces_tool_loop_offline() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let cassette = tmp.path().join("echo_turn.jsonl");
+    let scripted = ScriptedProvider::new(vec![
+        tool_response(vec![ToolCall {
+            id: "tc1".into(),
+            name: "echo".into(),
+            arguments: r#"{"message": "recorded"}"#.into(),
+        }]),
+        text_response("echoed: recorded"),
+    ]);
+
+    let recorder = ReplayProvider::record(Box::new(scripted), &cassette).unwrap();
+    let mut agent = build_agent_with(
+        Box::new(recorder),
+        vec![Box::new(EchoTool)],
+        Box::new(NativeToolDispatcher),
+    );
+    let recorded = agent.turn("run echo").await.unwrap();
+
+    let replayer = ReplayProvider::replay(&cassette).unwrap();
+    assert_eq!(replayer.unused_exchanges(), 2);
+    let mut agent = build_agent_with(
+        Box::new(replayer),
+        vec![Box::new(EchoTool)],
+        Box::new(NativeToolDispatcher),
+    );
+    let replayed = agent.turn("run echo").await.unwrap();
+
+    assert_eq!(replayed, recorded);
+}
+
+#[tokio::test]
+async fn replayed_cassette_rejects_diverging_turn() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let cassette = tmp.path().join("hello_turn.jsonl");
+    let recorder = ReplayProvider::record(
+        Box::new(ScriptedProvider::new(vec![text_response("hello")])),
+        &cassette,
+    )
+    .unwrap();
+    let mut agent = build_agent_with(Box::new(recorder), vec![], Box::new(NativeToolDispatcher));
+    agent.turn("hi").await.unwrap();
+
+    let replayer = ReplayProvider::replay(&cassette).unwrap();
+    let mut agent = build_agent_with(Box::new(replayer), vec![], Box::new(NativeToolDispatcher));
+    let err = agent.turn("something else").await.unwrap_err();
+    assert!(err.to_string().contains("no recorded exchange"), "{err}");
+}
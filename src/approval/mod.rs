@@ -5,11 +5,13 @@
 
 use crate::config::AutonomyConfig;
 use crate::security::AutonomyLevel;
+use async_trait::async_trait;
 use chrono::Utc;
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::io::{self, BufRead, Write};
+use std::sync::Arc;
 
 // ── Types ────────────────────────────────────────────────────────
 
@@ -32,6 +34,30 @@ pub enum ApprovalResponse {
     Always,
 }
 
+impl ApprovalResponse {
+    /// Parse a typed reply (`y`/`yes`/`approve`, `n`/`no`/`deny`,
+    /// `a`/`always`). Returns `None` for anything else.
+    pub fn parse_reply(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "y" | "yes" | "approve" => Some(Self::Yes),
+            "n" | "no" | "deny" => Some(Self::No),
+            "a" | "always" => Some(Self::Always),
+            _ => None,
+        }
+    }
+}
+
+/// Delivers an approval request to a remote operator and waits for the answer.
+///
+/// Channel-driven runs attach a prompter that posts the request back to the
+/// originating conversation; see [`ApprovalManager::with_prompter`].
+#[async_trait]
+pub trait ApprovalPrompter: Send + Sync {
+    /// Ask for a decision. Implementations apply their own timeout and
+    /// fallback decision.
+    async fn prompt(&self, request: &ApprovalRequest) -> ApprovalResponse;
+}
+
 /// A single audit log entry for an approval decision.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApprovalLogEntry {
@@ -50,12 +76,15 @@ pub struct ApprovalLogEntry {
 /// - Maintains a session-scoped "always" allowlist
 /// - Records an audit trail of all decisions
 ///
-/// Two modes:
+/// Three modes:
 /// - **Interactive** (CLI): tools needing approval trigger a stdin prompt.
 /// - **Non-interactive** (channels): tools needing approval are auto-denied
 ///   because there is no interactive operator to approve them. `auto_approve`
 ///   policy is still enforced, and `always_ask` / supervised-default tools are
 ///   denied rather than silently allowed.
+/// - **Prompted** (channels with an [`ApprovalPrompter`]): non-interactive
+///   policy, but tools needing approval are sent to the prompter instead of
+///   being auto-denied.
 pub struct ApprovalManager {
     /// Tools that never need approval (from config).
     auto_approve: HashSet<String>,
@@ -66,10 +95,12 @@ pub struct ApprovalManager {
     /// When `true`, tools that would require interactive approval are
     /// auto-denied instead. Used for channel-driven (non-CLI) runs.
     non_interactive: bool,
+    /// Remote prompter used instead of auto-deny in non-interactive mode.
+    prompter: Option<Arc<dyn ApprovalPrompter>>,
     /// Session-scoped allowlist built from "Always" responses.
     session_allowlist: Mutex<HashSet<String>>,
-    /// Audit trail of approval decisions.
-    audit_log: Mutex<Vec<ApprovalLogEntry>>,
+    /// Audit trail of approval decisions, shared with derived managers.
+    audit_log: Arc<Mutex<Vec<ApprovalLogEntry>>>,
 }
 
 impl ApprovalManager {
@@ -80,8 +111,9 @@ impl ApprovalManager {
             always_ask: config.always_ask.iter().cloned().collect(),
             autonomy_level: config.level,
             non_interactive: false,
+            prompter: None,
             session_allowlist: Mutex::new(HashSet::new()),
-            audit_log: Mutex::new(Vec::new()),
+            audit_log: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -96,8 +128,31 @@ impl ApprovalManager {
             always_ask: config.always_ask.iter().cloned().collect(),
             autonomy_level: config.level,
             non_interactive: true,
+            prompter: None,
             session_allowlist: Mutex::new(HashSet::new()),
-            audit_log: Mutex::new(Vec::new()),
+            audit_log: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Derive a manager for a single channel conversation that asks
+    /// `prompter` instead of auto-denying.
+    ///
+    /// Policy lists and the audit log are shared with `self`; the session
+    /// allowlist starts from `allowlist` (e.g. the sender's persisted
+    /// "Always" answers) rather than from this manager's.
+    pub fn with_prompter(
+        &self,
+        prompter: Arc<dyn ApprovalPrompter>,
+        allowlist: impl IntoIterator<Item = String>,
+    ) -> Self {
+        Self {
+            auto_approve: self.auto_approve.clone(),
+            always_ask: self.always_ask.clone(),
+            autonomy_level: self.autonomy_level,
+            non_interactive: self.non_interactive,
+            prompter: Some(prompter),
+            session_allowlist: Mutex::new(allowlist.into_iter().collect()),
+            audit_log: Arc::clone(&self.audit_log),
         }
     }
 
@@ -194,6 +249,20 @@ impl ApprovalManager {
     pub fn prompt_cli(&self, request: &ApprovalRequest) -> ApprovalResponse {
         prompt_cli_interactive(request)
     }
+
+    /// Obtain a decision for a call that [`needs_approval`](Self::needs_approval).
+    ///
+    /// Uses the attached prompter when present, auto-denies in
+    /// non-interactive mode, and otherwise prompts on the CLI.
+    pub async fn request_approval(&self, request: &ApprovalRequest) -> ApprovalResponse {
+        if let Some(prompter) = &self.prompter {
+            return prompter.prompt(request).await;
+        }
+        if self.non_interactive {
+            return ApprovalResponse::No;
+        }
+        self.prompt_cli(request)
+    }
 }
 
 // ── CLI prompt ───────────────────────────────────────────────────
@@ -213,15 +282,11 @@ fn prompt_cli_interactive(request: &ApprovalRequest) -> ApprovalResponse {
         return ApprovalResponse::No;
     }
 
-    match line.trim().to_ascii_lowercase().as_str() {
-        "y" | "yes" => ApprovalResponse::Yes,
-        "a" | "always" => ApprovalResponse::Always,
-        _ => ApprovalResponse::No,
-    }
+    ApprovalResponse::parse_reply(&line).unwrap_or(ApprovalResponse::No)
 }
 
 /// Produce a short human-readable summary of tool arguments.
-fn summarize_args(args: &serde_json::Value) -> String {
+pub(crate) fn summarize_args(args: &serde_json::Value) -> String {
     match args {
         serde_json::Value::Object(map) => {
             let parts: Vec<String> = map
@@ -562,4 +627,81 @@ mod tests {
         let parsed: ApprovalRequest = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed.tool_name, "shell");
     }
+
+    // ── Prompted (channel) mode ──────────────────────────────
+
+    struct FixedPrompter(ApprovalResponse);
+
+    #[async_trait]
+    impl ApprovalPrompter for FixedPrompter {
+        async fn prompt(&self, _request: &ApprovalRequest) -> ApprovalResponse {
+            self.0
+        }
+    }
+
+    fn write_request() -> ApprovalRequest {
+        ApprovalRequest {
+            tool_name: "file_write".into(),
+            arguments: serde_json::json!({"path": "notes.md"}),
+        }
+    }
+
+    #[test]
+    fn parse_reply_accepts_keywords() {
+        assert_eq!(
+            ApprovalResponse::parse_reply(" YES "),
+            Some(ApprovalResponse::Yes)
+        );
+        assert_eq!(
+            ApprovalResponse::parse_reply("deny"),
+            Some(ApprovalResponse::No)
+        );
+        assert_eq!(
+            ApprovalResponse::parse_reply("a"),
+            Some(ApprovalResponse::Always)
+        );
+        assert_eq!(ApprovalResponse::parse_reply("sure, go ahead"), None);
+    }
+
+    #[tokio::test]
+    async fn non_interactive_request_approval_denies_without_prompter() {
+        let mgr = ApprovalManager::for_non_interactive(&supervised_config());
+        assert_eq!(
+            mgr.request_approval(&write_request()).await,
+            ApprovalResponse::No
+        );
+    }
+
+    #[tokio::test]
+    async fn prompter_answers_instead_of_auto_deny() {
+        let base = ApprovalManager::for_non_interactive(&supervised_config());
+        let mgr = base.with_prompter(Arc::new(FixedPrompter(ApprovalResponse::Yes)), Vec::new());
+        assert!(mgr.is_non_interactive());
+        assert_eq!(
+            mgr.request_approval(&write_request()).await,
+            ApprovalResponse::Yes
+        );
+    }
+
+    #[test]
+    fn with_prompter_seeds_allowlist_and_shares_audit_log() {
+        let base = ApprovalManager::for_non_interactive(&supervised_config());
+        let mgr = base.with_prompter(
+            Arc::new(FixedPrompter(ApprovalResponse::No)),
+            vec!["file_write".to_string(), "shell".to_string()],
+        );
+        assert!(!mgr.needs_approval("file_write"));
+        // always_ask still wins over a persisted allowlist.
+        assert!(mgr.needs_approval("shell"));
+        // The base manager's allowlist is untouched.
+        assert!(base.needs_approval("file_write"));
+
+        mgr.record_decision(
+            "file_write",
+            &serde_json::json!({}),
+            ApprovalResponse::Yes,
+            "slack",
+        );
+        assert_eq!(base.audit_log().len(), 1);
+    }
 }
@@ -0,0 +1,410 @@
+//! Interactive tool-call approval over chat channels.
+//!
+//! When a supervised run started from a channel hits a tool that needs
+//! approval, [`ChannelApprovalPrompter`] posts an [`ApprovalPrompt`] to the
+//! originating conversation and waits for the sender's answer. Answers arrive
+//! through the normal message bus — as a typed `yes` / `no` / `always` reply
+//! or as a button callback — and are claimed by
+//! [`ChannelApprovalBroker::try_resolve`] in the dispatch loop before they
+//! reach the agent. "Always" answers are remembered per channel sender and
+//! optionally persisted to `state/channel_approvals.json`.
+
+use super::traits::{
+    parse_approval_callback, ApprovalPrompt, Channel, ChannelMessage, APPROVAL_CALLBACK_PREFIX,
+};
+use crate::approval::{summarize_args, ApprovalPrompter, ApprovalRequest, ApprovalResponse};
+use crate::config::{ApprovalTimeoutAction, ChannelApprovalConfig};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+const ALLOWLIST_FILE: &str = "channel_approvals.json";
+
+struct PendingApproval {
+    request_id: String,
+    reply: oneshot::Sender<ApprovalResponse>,
+}
+
+/// Routes approval prompts to channel senders and their answers back to the
+/// waiting tool-call loop.
+pub(crate) struct ChannelApprovalBroker {
+    timeout: Duration,
+    on_timeout: ApprovalResponse,
+    /// Open prompts keyed by conversation scope (channel, reply target, sender),
+    /// oldest first.
+    pending: Mutex<HashMap<String, Vec<PendingApproval>>>,
+    /// "Always" answers keyed by `channel:sender`.
+    allowlists: Mutex<BTreeMap<String, BTreeSet<String>>>,
+    allowlist_path: Option<PathBuf>,
+}
+
+impl ChannelApprovalBroker {
+    pub(crate) fn new(config: &ChannelApprovalConfig, workspace_dir: &Path) -> Self {
+        let allowlist_path = config
+            .persist_always
+            .then(|| workspace_dir.join("state").join(ALLOWLIST_FILE));
+        let allowlists = allowlist_path
+            .as_deref()
+            .map(load_allowlists)
+            .unwrap_or_default();
+        Self {
+            timeout: Duration::from_secs(config.timeout_secs),
+            on_timeout: match config.on_timeout {
+                ApprovalTimeoutAction::Deny => ApprovalResponse::No,
+                ApprovalTimeoutAction::Approve => ApprovalResponse::Yes,
+            },
+            pending: Mutex::new(HashMap::new()),
+            allowlists: Mutex::new(allowlists),
+            allowlist_path,
+        }
+    }
+
+    /// Build a prompter bound to the conversation `msg` came from.
+    pub(crate) fn prompter(
+        self: &Arc<Self>,
+        channel: Arc<dyn Channel>,
+        msg: &ChannelMessage,
+        thread_ts: Option<String>,
+    ) -> ChannelApprovalPrompter {
+        ChannelApprovalPrompter {
+            broker: Arc::clone(self),
+            channel,
+            scope: pending_scope(msg),
+            sender_key: sender_key(msg),
+            recipient: msg.reply_target.clone(),
+            thread_ts,
+        }
+    }
+
+    /// Tools the sender of `msg` has approved with "always".
+    pub(crate) fn allowed_tools(&self, msg: &ChannelMessage) -> Vec<String> {
+        self.allowlists
+            .lock()
+            .get(&sender_key(msg))
+            .map(|tools| tools.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Claim `msg` if it answers an open prompt from the same sender in the
+    /// same conversation. Returns `true` when the message was consumed.
+    ///
+    /// Button callbacks are always claimed, and answer the prompt whose
+    /// request id they carry; stale presses are dropped. Typed replies answer
+    /// the oldest open prompt and are only claimed while one is pending.
+    pub(crate) fn try_resolve(&self, msg: &ChannelMessage) -> bool {
+        let is_callback = msg
+            .content
+            .trim_start()
+            .starts_with(APPROVAL_CALLBACK_PREFIX);
+        let scope = pending_scope(msg);
+        let mut pending = self.pending.lock();
+        let Some(queue) = pending.get_mut(&scope) else {
+            return is_callback;
+        };
+
+        let (index, decision) = if is_callback {
+            let Some((request_id, decision)) = parse_approval_callback(&msg.content) else {
+                return true;
+            };
+            let Some(index) = queue.iter().position(|p| p.request_id == request_id) else {
+                return true;
+            };
+            (index, decision)
+        } else if let Some(decision) = ApprovalResponse::parse_reply(&msg.content) {
+            (0, decision)
+        } else {
+            return false;
+        };
+
+        let entry = queue.remove(index);
+        if queue.is_empty() {
+            pending.remove(&scope);
+        }
+        let _ = entry.reply.send(decision);
+        true
+    }
+
+    fn register(&self, scope: &str, request_id: &str) -> oneshot::Receiver<ApprovalResponse> {
+        let (reply, rx) = oneshot::channel();
+        self.pending
+            .lock()
+            .entry(scope.to_string())
+            .or_default()
+            .push(PendingApproval {
+                request_id: request_id.to_string(),
+                reply,
+            });
+        rx
+    }
+
+    fn unregister(&self, scope: &str, request_id: &str) {
+        let mut pending = self.pending.lock();
+        if let Some(queue) = pending.get_mut(scope) {
+            queue.retain(|p| p.request_id != request_id);
+            if queue.is_empty() {
+                pending.remove(scope);
+            }
+        }
+    }
+
+    fn remember_always(&self, sender_key: &str, tool_name: &str) {
+        let snapshot = {
+            let mut allowlists = self.allowlists.lock();
+            if !allowlists
+                .entry(sender_key.to_string())
+                .or_default()
+                .insert(tool_name.to_string())
+            {
+                return;
+            }
+            allowlists.clone()
+        };
+        if let Some(path) = &self.allowlist_path {
+            if let Err(e) = save_allowlists(path, &snapshot) {
+                tracing::warn!("Failed to persist channel approval allowlist: {e}");
+            }
+        }
+    }
+}
+
+/// Removes a prompt from the broker when the waiting turn finishes or is
+/// cancelled, so late replies fall through to the agent.
+struct PendingGuard<'a> {
+    broker: &'a ChannelApprovalBroker,
+    scope: &'a str,
+    request_id: &'a str,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        self.broker.unregister(self.scope, self.request_id);
+    }
+}
+
+/// [`ApprovalPrompter`] for one channel conversation.
+pub(crate) struct ChannelApprovalPrompter {
+    broker: Arc<ChannelApprovalBroker>,
+    channel: Arc<dyn Channel>,
+    scope: String,
+    sender_key: String,
+    recipient: String,
+    thread_ts: Option<String>,
+}
+
+#[async_trait]
+impl ApprovalPrompter for ChannelApprovalPrompter {
+    async fn prompt(&self, request: &ApprovalRequest) -> ApprovalResponse {
+        let request_id = uuid::Uuid::new_v4().simple().to_string();
+        let rx = self.broker.register(&self.scope, &request_id);
+        let _guard = PendingGuard {
+            broker: &self.broker,
+            scope: &self.scope,
+            request_id: &request_id,
+        };
+
+        let prompt = ApprovalPrompt {
+            request_id: request_id.clone(),
+            tool_name: request.tool_name.clone(),
+            arguments_summary: summarize_args(&request.arguments),
+            recipient: self.recipient.clone(),
+            thread_ts: self.thread_ts.clone(),
+            timeout_secs: self.broker.timeout.as_secs(),
+        };
+        if let Err(e) = self.channel.send_approval_prompt(&prompt).await {
+            tracing::warn!(
+                channel = self.channel.name(),
+                tool = %request.tool_name,
+                "Failed to deliver approval prompt: {e}"
+            );
+            return ApprovalResponse::No;
+        }
+
+        match tokio::time::timeout(self.broker.timeout, rx).await {
+            Ok(Ok(decision)) => {
+                if decision == ApprovalResponse::Always {
+                    self.broker
+                        .remember_always(&self.sender_key, &request.tool_name);
+                }
+                decision
+            }
+            _ => {
+                tracing::info!(
+                    channel = self.channel.name(),
+                    tool = %request.tool_name,
+                    "Approval prompt timed out"
+                );
+                self.broker.on_timeout
+            }
+        }
+    }
+}
+
+fn pending_scope(msg: &ChannelMessage) -> String {
+    format!("{}_{}_{}", msg.channel, msg.reply_target, msg.sender)
+}
+
+fn sender_key(msg: &ChannelMessage) -> String {
+    format!("{}:{}", msg.channel, msg.sender)
+}
+
+fn load_allowlists(path: &Path) -> BTreeMap<String, BTreeSet<String>> {
+    let Ok(raw) = std::fs::read_to_string(path) else {
+        return BTreeMap::new();
+    };
+    serde_json::from_str(&raw).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring unreadable {}: {e}", path.display());
+        BTreeMap::new()
+    })
+}
+
+fn save_allowlists(
+    path: &Path,
+    allowlists: &BTreeMap<String, BTreeSet<String>>,
+) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(allowlists)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::SendMessage;
+    use tempfile::TempDir;
+
+    #[derive(Default)]
+    struct RecordingChannel {
+        prompts: Mutex<Vec<ApprovalPrompt>>,
+    }
+
+    #[async_trait]
+    impl Channel for RecordingChannel {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn send_approval_prompt(&self, prompt: &ApprovalPrompt) -> anyhow::Result<()> {
+            self.prompts.lock().push(prompt.clone());
+            Ok(())
+        }
+    }
+
+    fn message(sender: &str, content: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: "m1".into(),
+            sender: sender.into(),
+            reply_target: "room".into(),
+            content: content.into(),
+            channel: "test".into(),
+            timestamp: 0,
+            thread_ts: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    fn broker(tmp: &TempDir, timeout_secs: u64) -> Arc<ChannelApprovalBroker> {
+        let config = ChannelApprovalConfig {
+            timeout_secs,
+            ..ChannelApprovalConfig::default()
+        };
+        Arc::new(ChannelApprovalBroker::new(&config, tmp.path()))
+    }
+
+    fn request() -> ApprovalRequest {
+        ApprovalRequest {
+            tool_name: "file_write".into(),
+            arguments: serde_json::json!({"path": "a.txt"}),
+        }
+    }
+
+    /// Wait until the prompter has registered and posted its prompt.
+    async fn next_prompt(channel: &RecordingChannel) -> ApprovalPrompt {
+        loop {
+            if let Some(prompt) = channel.prompts.lock().last().cloned() {
+                return prompt;
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn typed_reply_resolves_pending_prompt() {
+        let tmp = TempDir::new().unwrap();
+        let broker = broker(&tmp, 30);
+        let channel = Arc::new(RecordingChannel::default());
+        let prompter = broker.prompter(channel.clone(), &message("alice", "run it"), None);
+
+        let task = tokio::spawn(async move { prompter.prompt(&request()).await });
+        next_prompt(&channel).await;
+
+        // Another sender cannot answer alice's prompt, and chatter is not claimed.
+        assert!(!broker.try_resolve(&message("bob", "yes")));
+        assert!(!broker.try_resolve(&message("alice", "what is this?")));
+        assert!(broker.try_resolve(&message("alice", "yes")));
+        assert_eq!(task.await.unwrap(), ApprovalResponse::Yes);
+
+        // Nothing pending any more: replies fall through to the agent.
+        assert!(!broker.try_resolve(&message("alice", "yes")));
+    }
+
+    #[tokio::test]
+    async fn button_callback_always_is_persisted_per_sender() {
+        let tmp = TempDir::new().unwrap();
+        let broker = broker(&tmp, 30);
+        let channel = Arc::new(RecordingChannel::default());
+        let prompter = broker.prompter(channel.clone(), &message("alice", "run it"), None);
+
+        let task = tokio::spawn(async move { prompter.prompt(&request()).await });
+        let prompt = next_prompt(&channel).await;
+        let callback = prompt.callback_data(ApprovalResponse::Always);
+        assert!(broker.try_resolve(&message("alice", &callback)));
+        assert_eq!(task.await.unwrap(), ApprovalResponse::Always);
+
+        assert_eq!(
+            broker.allowed_tools(&message("alice", "")),
+            vec!["file_write"]
+        );
+        assert!(broker.allowed_tools(&message("bob", "")).is_empty());
+
+        let reloaded = ChannelApprovalBroker::new(&ChannelApprovalConfig::default(), tmp.path());
+        assert_eq!(
+            reloaded.allowed_tools(&message("alice", "")),
+            vec!["file_write"]
+        );
+    }
+
+    #[tokio::test]
+    async fn unanswered_prompt_applies_timeout_default() {
+        let tmp = TempDir::new().unwrap();
+        let config = ChannelApprovalConfig {
+            timeout_secs: 1,
+            on_timeout: ApprovalTimeoutAction::Approve,
+            ..ChannelApprovalConfig::default()
+        };
+        let broker = Arc::new(ChannelApprovalBroker::new(&config, tmp.path()));
+        let channel = Arc::new(RecordingChannel::default());
+        let prompter = broker.prompter(channel, &message("alice", "run it"), None);
+
+        assert_eq!(prompter.prompt(&request()).await, ApprovalResponse::Yes);
+        assert!(!broker.try_resolve(&message("alice", "no")));
+        // A late button press is swallowed rather than handed to the agent.
+        assert!(broker.try_resolve(&message("alice", "zc-approval:gone:yes")));
+    }
+}
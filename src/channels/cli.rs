@@ -48,6 +48,7 @@ impl Channel for CliChannel {
                     .unwrap_or_default()
                     .as_secs(),
                 thread_ts: None,
+                attachments: Vec::new(),
             };
 
             if tx.send(msg).await.is_err() {
@@ -77,6 +78,7 @@ mod tests {
                 subject: None,
                 thread_ts: None,
                 data: None,
+                attachments: Vec::new(),
             })
             .await;
         assert!(result.is_ok());
@@ -92,6 +94,7 @@ mod tests {
                 subject: None,
                 thread_ts: None,
                 data: None,
+                attachments: Vec::new(),
             })
             .await;
         assert!(result.is_ok());
@@ -113,6 +116,7 @@ mod tests {
             channel: "cli".into(),
             timestamp: 1_234_567_890,
             thread_ts: None,
+            attachments: Vec::new(),
         };
         assert_eq!(msg.id, "test-id");
         assert_eq!(msg.sender, "user");
@@ -132,6 +136,7 @@ mod tests {
             channel: "ch".into(),
             timestamp: 0,
             thread_ts: None,
+            attachments: Vec::new(),
         };
         let cloned = msg.clone();
         assert_eq!(cloned.id, msg.id);
@@ -1,4 +1,4 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{Attachment, AttachmentKind, Channel, ChannelMessage, SendMessage};
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
@@ -104,6 +104,29 @@ async fn process_attachments(
     parts.join("\n---\n")
 }
 
+/// Typed view of Discord message attachments (CDN URLs, not downloaded).
+fn typed_attachments(attachments: &[serde_json::Value]) -> Vec<Attachment> {
+    attachments
+        .iter()
+        .filter_map(|att| {
+            let url = att.get("url").and_then(|v| v.as_str())?;
+            let content_type = att.get("content_type").and_then(|v| v.as_str());
+            let kind = content_type.map_or(AttachmentKind::Document, AttachmentKind::from_mime);
+            let mut typed = Attachment::from_url(kind, url);
+            if let Some(content_type) = content_type {
+                typed = typed.with_mime_type(content_type);
+            }
+            if let Some(name) = att.get("filename").and_then(|v| v.as_str()) {
+                typed = typed.with_file_name(name);
+            }
+            if let Some(size) = att.get("size").and_then(serde_json::Value::as_u64) {
+                typed = typed.with_size(size);
+            }
+            Some(typed)
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum DiscordAttachmentKind {
     Image,
@@ -142,6 +165,22 @@ struct DiscordAttachment {
     target: String,
 }
 
+impl From<&Attachment> for DiscordAttachment {
+    fn from(attachment: &Attachment) -> Self {
+        let kind = match attachment.kind {
+            AttachmentKind::Image => DiscordAttachmentKind::Image,
+            AttachmentKind::Document => DiscordAttachmentKind::Document,
+            AttachmentKind::Video => DiscordAttachmentKind::Video,
+            AttachmentKind::Audio => DiscordAttachmentKind::Audio,
+            AttachmentKind::Voice => DiscordAttachmentKind::Voice,
+        };
+        Self {
+            kind,
+            target: attachment.target(),
+        }
+    }
+}
+
 fn parse_attachment_markers(message: &str) -> (String, Vec<DiscordAttachment>) {
     let mut cleaned = String::with_capacity(message.len());
     let mut attachments = Vec::new();
@@ -487,9 +526,23 @@ impl Channel for DiscordChannel {
         "discord"
     }
 
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let raw_content = super::strip_tool_call_tags(&message.content);
-        let (cleaned_content, parsed_attachments) = parse_attachment_markers(&raw_content);
+        let (mut cleaned_content, mut parsed_attachments) = parse_attachment_markers(&raw_content);
+        // Discord has no per-file captions; typed attachment captions go in the text.
+        for attachment in &message.attachments {
+            if let Some(caption) = &attachment.caption {
+                if !cleaned_content.is_empty() {
+                    cleaned_content.push('\n');
+                }
+                cleaned_content.push_str(caption);
+            }
+            parsed_attachments.push(DiscordAttachment::from(attachment));
+        }
         let (mut local_files, remote_urls, unresolved_markers) =
             classify_outgoing_attachments(&parsed_attachments);
 
@@ -722,14 +775,12 @@ impl Channel for DiscordChannel {
                         continue;
                     };
 
-                    let attachment_text = {
-                        let atts = d
-                            .get("attachments")
-                            .and_then(|a| a.as_array())
-                            .cloned()
-                            .unwrap_or_default();
-                        process_attachments(&atts, &self.http_client()).await
-                    };
+                    let atts = d
+                        .get("attachments")
+                        .and_then(|a| a.as_array())
+                        .cloned()
+                        .unwrap_or_default();
+                    let attachment_text = process_attachments(&atts, &self.http_client()).await;
                     let final_content = if attachment_text.is_empty() {
                         clean_content
                     } else {
@@ -789,6 +840,7 @@ impl Channel for DiscordChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: None,
+                        attachments: typed_attachments(&atts),
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -1492,6 +1544,26 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn typed_attachments_carry_discord_metadata() {
+        let attachments = vec![
+            serde_json::json!({
+                "url": "https://cdn.discordapp.com/attachments/123/456/photo.png",
+                "filename": "photo.png",
+                "content_type": "image/png",
+                "size": 2048
+            }),
+            serde_json::json!({ "filename": "no-url.txt" }),
+        ];
+        let typed = typed_attachments(&attachments);
+
+        assert_eq!(typed.len(), 1);
+        assert_eq!(typed[0].kind, AttachmentKind::Image);
+        assert_eq!(typed[0].mime_type.as_deref(), Some("image/png"));
+        assert_eq!(typed[0].file_name.as_deref(), Some("photo.png"));
+        assert_eq!(typed[0].size_bytes, Some(2048));
+    }
+
     #[test]
     fn parse_attachment_markers_extracts_supported_markers() {
         let input = "Report\n[IMAGE:https://example.com/a.png]\n[DOCUMENT:/tmp/a.pdf]";
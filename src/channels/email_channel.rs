@@ -14,7 +14,8 @@ use async_imap::types::Fetch;
 use async_imap::Session;
 use async_trait::async_trait;
 use futures_util::TryStreamExt;
-use lettre::message::SinglePart;
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment as MailAttachment, MultiPart, SinglePart};
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use mail_parser::{MessageParser, MimeHeaders};
@@ -23,6 +24,8 @@ use rustls_pki_types::DnsName;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::net::TcpStream;
@@ -33,7 +36,9 @@ use tokio_rustls::TlsConnector;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    Attachment, AttachmentKind, AttachmentSource, Channel, ChannelMessage, SendMessage,
+};
 
 /// Email channel configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -121,10 +126,14 @@ impl Default for EmailConfig {
 
 type ImapSession = Session<TlsStream<TcpStream>>;
 
+/// Largest incoming email attachment saved to the workspace (25 MB).
+const EMAIL_MAX_ATTACHMENT_BYTES: usize = 25 * 1024 * 1024;
+
 /// Email channel — IMAP IDLE for instant push notifications, SMTP for outbound
 pub struct EmailChannel {
     pub config: EmailConfig,
     seen_messages: Arc<Mutex<HashSet<String>>>,
+    workspace_dir: Option<PathBuf>,
 }
 
 impl EmailChannel {
@@ -132,9 +141,16 @@ impl EmailChannel {
         Self {
             config,
             seen_messages: Arc::new(Mutex::new(HashSet::new())),
+            workspace_dir: None,
         }
     }
 
+    /// Configure workspace directory for saving incoming attachments.
+    pub fn with_workspace_dir(mut self, dir: PathBuf) -> Self {
+        self.workspace_dir = Some(dir);
+        self
+    }
+
     /// Check if a sender email is in the allowlist
     pub fn is_sender_allowed(&self, email: &str) -> bool {
         if self.config.allowed_senders.is_empty() {
@@ -212,6 +228,80 @@ impl EmailChannel {
         "(no readable content)".to_string()
     }
 
+    /// Collect the attachment parts of a parsed email with sanitized file names.
+    fn extract_attachments(parsed: &mail_parser::Message) -> Vec<IncomingEmailAttachment> {
+        parsed
+            .attachments()
+            .enumerate()
+            .filter_map(|(idx, part)| {
+                let part: &mail_parser::MessagePart = part;
+                let data = part.contents();
+                if data.is_empty() || data.len() > EMAIL_MAX_ATTACHMENT_BYTES {
+                    debug!("Skipping email attachment {idx}: {} bytes", data.len());
+                    return None;
+                }
+                // Only keep the final path component so names cannot escape the save dir.
+                let file_name = MimeHeaders::attachment_name(part)
+                    .and_then(|name| Path::new(name).file_name())
+                    .and_then(|name| name.to_str())
+                    .filter(|name| !name.starts_with('.'))
+                    .map_or_else(|| format!("attachment-{idx}"), str::to_string);
+                let mime_type = MimeHeaders::content_type(part).map(|ct| match ct.subtype() {
+                    Some(subtype) => format!("{}/{}", ct.ctype(), subtype),
+                    None => ct.ctype().to_string(),
+                });
+                Some(IncomingEmailAttachment {
+                    file_name,
+                    mime_type,
+                    data: data.to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Write incoming attachments to `{workspace_dir}/email_files/`.
+    ///
+    /// Returns nothing when no workspace directory is configured.
+    async fn save_attachments(
+        &self,
+        uid: u32,
+        parts: Vec<IncomingEmailAttachment>,
+    ) -> Vec<Attachment> {
+        if parts.is_empty() {
+            return Vec::new();
+        }
+        let Some(workspace) = self.workspace_dir.as_ref() else {
+            debug!("Not saving email attachments: workspace_dir not configured");
+            return Vec::new();
+        };
+        let save_dir = workspace.join("email_files");
+        if let Err(e) = tokio::fs::create_dir_all(&save_dir).await {
+            warn!("Failed to create email_files directory: {e}");
+            return Vec::new();
+        }
+
+        let mut saved = Vec::new();
+        for part in parts {
+            let path = save_dir.join(format!("{uid}_{}", part.file_name));
+            if let Err(e) = tokio::fs::write(&path, &part.data).await {
+                warn!("Failed to save email attachment to {}: {e}", path.display());
+                continue;
+            }
+            let kind = part
+                .mime_type
+                .as_deref()
+                .map_or(AttachmentKind::Document, AttachmentKind::from_mime);
+            let mut attachment = Attachment::from_path(kind, &path)
+                .with_file_name(part.file_name)
+                .with_size(part.data.len() as u64);
+            if let Some(mime) = part.mime_type {
+                attachment = attachment.with_mime_type(mime);
+            }
+            saved.push(attachment);
+        }
+        saved
+    }
+
     /// Connect to IMAP server with TLS and authenticate
     async fn connect_imap(&self) -> Result<ImapSession> {
         let addr = format!("{}:{}", self.config.imap_host, self.config.imap_port);
@@ -272,7 +362,7 @@ impl EmailChannel {
                     let sender = Self::extract_sender(&parsed);
                     let subject = parsed.subject().unwrap_or("(no subject)").to_string();
                     let body_text = Self::extract_text(&parsed);
-                    let content = format!("Subject: {}\n\n{}", subject, body_text);
+                    let mut content = format!("Subject: {}\n\n{}", subject, body_text);
                     let msg_id = parsed
                         .message_id()
                         .map(|s| s.to_string())
@@ -303,12 +393,24 @@ impl EmailChannel {
                                 .unwrap_or(0)
                         });
 
+                    let parts = Self::extract_attachments(&parsed);
+                    let attachments = self.save_attachments(uid, parts).await;
+                    for attachment in &attachments {
+                        let _ = write!(
+                            content,
+                            "\n\n[Attachment: {}] {}",
+                            attachment.display_name(),
+                            attachment.target()
+                        );
+                    }
+
                     results.push(ParsedEmail {
                         _uid: uid,
                         msg_id,
                         sender,
                         content,
                         timestamp: ts,
+                        attachments,
                     });
                 }
             }
@@ -467,6 +569,7 @@ impl EmailChannel {
                 channel: "email".to_string(),
                 timestamp: email.timestamp,
                 thread_ts: None,
+                attachments: email.attachments,
             };
 
             if tx.send(msg).await.is_err() {
@@ -502,6 +605,14 @@ struct ParsedEmail {
     sender: String,
     content: String,
     timestamp: u64,
+    attachments: Vec<Attachment>,
+}
+
+/// An attachment part of an incoming email, before it is written to disk.
+struct IncomingEmailAttachment {
+    file_name: String,
+    mime_type: Option<String>,
+    data: Vec<u8>,
 }
 
 /// Result from waiting on IDLE
@@ -517,6 +628,10 @@ impl Channel for EmailChannel {
         "email"
     }
 
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
     async fn send(&self, message: &SendMessage) -> Result<()> {
         // Use explicit subject if provided, otherwise fall back to legacy parsing or default
         let default_subject = self.config.default_subject.as_str();
@@ -532,11 +647,43 @@ impl Channel for EmailChannel {
             (default_subject, message.content.as_str())
         };
 
-        let email = Message::builder()
+        let builder = Message::builder()
             .from(self.config.from_address.parse()?)
             .to(message.recipient.parse()?)
-            .subject(subject)
-            .singlepart(SinglePart::plain(body.to_string()))?;
+            .subject(subject);
+
+        let email = if message.attachments.is_empty() {
+            builder.singlepart(SinglePart::plain(body.to_string()))?
+        } else {
+            // Local files become MIME attachments; remote URLs are listed in the body.
+            let mut text = body.to_string();
+            let mut files = Vec::new();
+            for attachment in &message.attachments {
+                if let Some(caption) = &attachment.caption {
+                    let _ = write!(text, "\n\n{}: {caption}", attachment.display_name());
+                }
+                match &attachment.source {
+                    AttachmentSource::Path(path) => {
+                        let data = tokio::fs::read(path).await.map_err(|e| {
+                            anyhow!("Email attachment read failed for '{}': {e}", path.display())
+                        })?;
+                        let content_type = ContentType::parse(attachment.mime_type_or_default())
+                            .or_else(|_| ContentType::parse("application/octet-stream"))?;
+                        files.push(
+                            MailAttachment::new(attachment.display_name()).body(data, content_type),
+                        );
+                    }
+                    AttachmentSource::Url(url) => {
+                        let _ = write!(text, "\n\n{url}");
+                    }
+                }
+            }
+            let mut mixed = MultiPart::mixed().singlepart(SinglePart::plain(text));
+            for file in files {
+                mixed = mixed.singlepart(file);
+            }
+            builder.multipart(mixed)?
+        };
 
         let transport = self.create_smtp_transport()?;
         transport.send(&email)?;
@@ -989,4 +1136,34 @@ mod tests {
         let debug_str = format!("{:?}", config);
         assert!(debug_str.contains("imap.debug.com"));
     }
+
+    #[test]
+    fn extract_attachments_sanitizes_names_and_reads_mime() {
+        let raw = concat!(
+            "From: alice@example.com\r\n",
+            "To: bot@test.com\r\n",
+            "Subject: Report\r\n",
+            "MIME-Version: 1.0\r\n",
+            "Content-Type: multipart/mixed; boundary=\"b1\"\r\n",
+            "\r\n",
+            "--b1\r\n",
+            "Content-Type: text/plain\r\n",
+            "\r\n",
+            "See attached.\r\n",
+            "--b1\r\n",
+            "Content-Type: application/pdf\r\n",
+            "Content-Disposition: attachment; filename=\"../../etc/report.pdf\"\r\n",
+            "Content-Transfer-Encoding: base64\r\n",
+            "\r\n",
+            "JVBERi0xLjQK\r\n",
+            "--b1--\r\n",
+        );
+        let parsed = MessageParser::default().parse(raw.as_bytes()).unwrap();
+        let parts = EmailChannel::extract_attachments(&parsed);
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].file_name, "report.pdf");
+        assert_eq!(parts[0].mime_type.as_deref(), Some("application/pdf"));
+        assert_eq!(parts[0].data, b"%PDF-1.4\n");
+    }
 }
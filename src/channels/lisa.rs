@@ -21,9 +21,13 @@
 //! the channel subsystem share the same instance when running inside the same
 //! daemon process.
 
-use super::traits::{Channel, ChannelMessage, DataPart, SendMessage};
+use super::traits::{
+    ApprovalPrompt, Attachment, AttachmentSource, Channel, ChannelMessage, DataPart, SendMessage,
+};
+use crate::approval::ApprovalResponse;
 use async_trait::async_trait;
 use axum::extract::ws::Message;
+use base64::Engine as _;
 use parking_lot::Mutex;
 use std::collections::HashMap;
 use std::sync::{Arc, OnceLock};
@@ -32,6 +36,9 @@ use tokio::sync::mpsc;
 /// Buffer for the incoming-message bridge (WS frames → channel bus).
 const LISA_BRIDGE_BUFFER: usize = 256;
 
+/// Largest local file inlined into an `attachment` frame as a `data:` URL.
+const LISA_INLINE_ATTACHMENT_MAX_BYTES: u64 = 5 * 1024 * 1024;
+
 /// Outgoing WS frame sender handle for a single client connection.
 type WsSender = mpsc::Sender<Message>;
 
@@ -90,6 +97,114 @@ impl LisaChannel {
     pub fn deregister(&self, session_id: &str) {
         self.connections.lock().remove(session_id);
     }
+
+    /// Attachments on a client `message` frame.
+    ///
+    /// Only remote `http(s)` URLs are accepted: a browser client must not be
+    /// able to point the agent at files on the daemon's disk.
+    pub fn client_attachments(frame: &serde_json::Value) -> Vec<Attachment> {
+        let Some(items) = frame.get("attachments").and_then(|v| v.as_array()) else {
+            return Vec::new();
+        };
+        items
+            .iter()
+            .filter_map(|item| serde_json::from_value::<Attachment>(item.clone()).ok())
+            .filter(|attachment| {
+                matches!(&attachment.source, AttachmentSource::Url(url)
+                    if url.starts_with("https://") || url.starts_with("http://"))
+            })
+            .collect()
+    }
+
+    /// `attachment` frame for an outgoing file. Local files are inlined as
+    /// `data:` URLs because the client cannot read the daemon's disk.
+    async fn attachment_frame(attachment: &Attachment) -> Option<serde_json::Value> {
+        let url = match &attachment.source {
+            AttachmentSource::Url(url) => url.clone(),
+            AttachmentSource::Path(path) => {
+                let size = tokio::fs::metadata(path).await.ok()?.len();
+                if size > LISA_INLINE_ATTACHMENT_MAX_BYTES {
+                    tracing::warn!(
+                        path = %path.display(),
+                        size,
+                        "LisaChannel: attachment too large to inline, skipped"
+                    );
+                    return None;
+                }
+                let data = tokio::fs::read(path).await.ok()?;
+                format!(
+                    "data:{};base64,{}",
+                    attachment.mime_type_or_default(),
+                    base64::engine::general_purpose::STANDARD.encode(data)
+                )
+            }
+        };
+
+        let mut frame = serde_json::to_value(attachment).ok()?;
+        let fields = frame.as_object_mut()?;
+        fields.remove("path");
+        fields.insert("type".into(), "attachment".into());
+        fields.insert("url".into(), url.into());
+        Some(frame)
+    }
+
+    /// A2UI card for an approval prompt. Each button's action context carries
+    /// the approval callback payload under `"approval"`, which the gateway
+    /// forwards verbatim as the message content.
+    fn approval_card(prompt: &ApprovalPrompt) -> Vec<serde_json::Value> {
+        let surface_id = format!("approval-{}", prompt.request_id);
+        let mut components = vec![
+            serde_json::json!({
+                "id": "root",
+                "component": "Column",
+                "children": ["summary", "actions"],
+            }),
+            serde_json::json!({
+                "id": "summary",
+                "component": "Text",
+                "text": prompt.summary(),
+            }),
+            serde_json::json!({
+                "id": "actions",
+                "component": "Row",
+                "children": ["approve", "deny", "always"],
+            }),
+        ];
+        for (id, label, decision) in [
+            ("approve", "Approve", ApprovalResponse::Yes),
+            ("deny", "Deny", ApprovalResponse::No),
+            ("always", "Always", ApprovalResponse::Always),
+        ] {
+            let label_id = format!("{id}_label");
+            components.push(serde_json::json!({
+                "id": label_id,
+                "component": "Text",
+                "text": label,
+            }));
+            components.push(serde_json::json!({
+                "id": id,
+                "component": "Button",
+                "child": label_id,
+                "action": {
+                    "event": {
+                        "name": "approval",
+                        "context": { "approval": prompt.callback_data(decision) },
+                    },
+                },
+            }));
+        }
+
+        vec![
+            serde_json::json!({
+                "version": "v0.9",
+                "createSurface": { "surfaceId": surface_id, "catalogId": "basic" },
+            }),
+            serde_json::json!({
+                "version": "v0.9",
+                "updateComponents": { "surfaceId": surface_id, "components": components },
+            }),
+        ]
+    }
 }
 
 #[async_trait]
@@ -102,6 +217,10 @@ impl Channel for LisaChannel {
         true
     }
 
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
     // Delta streaming deferred to Issue #77 (provider-level SSE streaming)
 
     async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
@@ -159,8 +278,24 @@ impl Channel for LisaChannel {
             }
         }
 
+        for attachment in &message.attachments {
+            let Some(frame) = Self::attachment_frame(attachment).await else {
+                continue;
+            };
+            if sender
+                .try_send(Message::Text(frame.to_string().into()))
+                .is_err()
+            {
+                tracing::warn!(
+                    session_id,
+                    "LisaChannel: outbound buffer full, frame dropped"
+                );
+            }
+        }
+
         // Send done frame when there is text or data so the client can finalize rendering.
-        let has_data = message.data.as_ref().is_some_and(|d| !d.is_empty());
+        let has_data =
+            message.data.as_ref().is_some_and(|d| !d.is_empty()) || !message.attachments.is_empty();
         if !message.content.is_empty() || has_data {
             let mut done_frame = serde_json::json!({ "type": "done" });
             if !message.content.is_empty() {
@@ -171,11 +306,18 @@ impl Channel for LisaChannel {
 
         Ok(())
     }
+
+    async fn send_approval_prompt(&self, prompt: &ApprovalPrompt) -> anyhow::Result<()> {
+        let message = SendMessage::new(prompt.text(), &prompt.recipient)
+            .with_data(vec![DataPart::A2ui(Self::approval_card(prompt))]);
+        self.send(&message).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::channels::traits::AttachmentKind;
 
     #[test]
     fn lisa_channel_name_and_a2ui_flag() {
@@ -201,6 +343,7 @@ mod tests {
             channel: "lisa".into(),
             timestamp: 0,
             thread_ts: None,
+            attachments: Vec::new(),
         });
 
         let msg = rx.recv().await.expect("should receive a message");
@@ -232,6 +375,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn client_attachments_accept_only_remote_urls() {
+        let frame = serde_json::json!({
+            "type": "message",
+            "content": "look",
+            "attachments": [
+                { "kind": "image", "url": "https://example.com/cat.png", "caption": "cat" },
+                { "kind": "document", "path": "/etc/passwd" },
+                { "kind": "image", "url": "file:///etc/passwd" }
+            ]
+        });
+        let attachments = LisaChannel::client_attachments(&frame);
+
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].target(), "https://example.com/cat.png");
+        assert_eq!(attachments[0].caption.as_deref(), Some("cat"));
+    }
+
+    #[tokio::test]
+    async fn send_inlines_local_attachments_as_data_urls() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("note.txt");
+        std::fs::write(&path, b"hi").unwrap();
+
+        let ch = LisaChannel::new();
+        let (out_tx, mut out_rx) = mpsc::channel::<Message>(256);
+        ch.register("sess_att".into(), out_tx);
+
+        let attachment = Attachment::from_path(AttachmentKind::Document, &path);
+        ch.send(&SendMessage::new("", "sess_att").with_attachments(vec![attachment]))
+            .await
+            .unwrap();
+
+        let Some(Message::Text(text)) = out_rx.recv().await else {
+            panic!("expected attachment frame");
+        };
+        let v: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(v["type"], "attachment");
+        assert_eq!(v["kind"], "document");
+        assert_eq!(v["url"], "data:text/plain;base64,aGk=");
+        assert!(v.get("path").is_none());
+    }
+
     #[tokio::test]
     async fn send_delivers_a2ui_parts_before_text() {
         let ch = LisaChannel::new();
@@ -282,4 +468,38 @@ mod tests {
             .await
             .is_ok());
     }
+
+    #[tokio::test]
+    async fn approval_prompt_sends_card_with_callback_buttons() {
+        let ch = LisaChannel::new();
+        let (out_tx, mut out_rx) = mpsc::channel::<Message>(256);
+        ch.register("sess_approval".into(), out_tx);
+
+        let prompt = ApprovalPrompt {
+            request_id: "r1".into(),
+            tool_name: "shell".into(),
+            arguments_summary: "command: ls".into(),
+            recipient: "sess_approval".into(),
+            thread_ts: None,
+            timeout_secs: 60,
+        };
+        ch.send_approval_prompt(&prompt).await.unwrap();
+
+        let Message::Text(card) = out_rx.recv().await.expect("a2ui frame") else {
+            panic!("expected Text frame for a2ui");
+        };
+        let v: serde_json::Value = serde_json::from_str(&card).unwrap();
+        assert_eq!(v["type"], "a2ui");
+        let components = &v["messages"][1]["updateComponents"]["components"];
+        let approve = components
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["id"] == "approve")
+            .unwrap();
+        assert_eq!(
+            approve["action"]["event"]["context"]["approval"],
+            "zc-approval:r1:yes"
+        );
+    }
 }
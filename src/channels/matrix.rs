@@ -1,4 +1,6 @@
-use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::channels::traits::{
+    Attachment, AttachmentKind, AttachmentSource, Channel, ChannelMessage, SendMessage,
+};
 use async_trait::async_trait;
 use matrix_sdk::{
     authentication::matrix::MatrixSession,
@@ -202,6 +204,99 @@ impl MatrixChannel {
         format!("Bearer {}", self.access_token)
     }
 
+    fn attachment_msgtype(kind: AttachmentKind) -> &'static str {
+        match kind {
+            AttachmentKind::Image => "m.image",
+            AttachmentKind::Video => "m.video",
+            AttachmentKind::Audio | AttachmentKind::Voice => "m.audio",
+            AttachmentKind::Document => "m.file",
+        }
+    }
+
+    /// Upload a local file to the media repository and post it to the room
+    /// as an `m.image` / `m.file` / `m.audio` / `m.video` event.
+    async fn send_media_attachment(
+        &self,
+        room_id: &str,
+        thread_ts: Option<&str>,
+        attachment: &Attachment,
+        path: &std::path::Path,
+    ) -> anyhow::Result<()> {
+        let data = tokio::fs::read(path).await.map_err(|e| {
+            anyhow::anyhow!(
+                "Matrix attachment read failed for '{}': {e}",
+                path.display()
+            )
+        })?;
+        let file_name = attachment.display_name();
+        let mime = attachment.mime_type_or_default().to_string();
+        let size = data.len();
+
+        let upload_url = format!(
+            "{}/_matrix/media/v3/upload?filename={}",
+            self.homeserver,
+            Self::encode_path_segment(&file_name)
+        );
+        let resp = self
+            .http_client
+            .post(&upload_url)
+            .header("Authorization", self.auth_header_value())
+            .header("Content-Type", &mime)
+            .body(data)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Matrix media upload failed ({status}): {err}");
+        }
+        let body: serde_json::Value = resp.json().await?;
+        let Some(content_uri) = body["content_uri"].as_str() else {
+            anyhow::bail!("Matrix media upload returned no content_uri");
+        };
+
+        let mut event = serde_json::json!({
+            "msgtype": Self::attachment_msgtype(attachment.kind),
+            "body": attachment.caption.clone().unwrap_or_else(|| file_name.clone()),
+            "filename": file_name,
+            "url": content_uri,
+            "info": { "mimetype": mime, "size": size }
+        });
+        if let Some(thread_root) = thread_ts {
+            event["m.relates_to"] = serde_json::json!({
+                "rel_type": "m.thread",
+                "event_id": thread_root,
+            });
+        }
+
+        let txn_id = format!(
+            "attachment_{}",
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        );
+        let send_url = format!(
+            "{}/_matrix/client/v3/rooms/{}/send/m.room.message/{}",
+            self.homeserver,
+            Self::encode_path_segment(room_id),
+            txn_id
+        );
+        let resp = self
+            .http_client
+            .put(&send_url)
+            .header("Authorization", self.auth_header_value())
+            .json(&event)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Matrix attachment send failed ({status}): {err}");
+        }
+        Ok(())
+    }
+
     fn matrix_store_dir(&self) -> Option<PathBuf> {
         self.zeroclaw_dir
             .as_ref()
@@ -539,6 +634,10 @@ impl Channel for MatrixChannel {
         "matrix"
     }
 
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
         let client = self.matrix_client().await?;
         let target_room_id = if message.recipient.contains("||") {
@@ -567,18 +666,49 @@ impl Channel for MatrixChannel {
             tracing::warn!("Matrix failed to stop typing notification: {error}");
         }
 
-        let mut content = RoomMessageEventContent::text_markdown(&message.content);
+        // Local files are uploaded as media events; remote URLs go in the text.
+        let mut text = message.content.clone();
+        let mut uploads = Vec::new();
+        for attachment in &message.attachments {
+            match &attachment.source {
+                AttachmentSource::Path(path) => uploads.push((attachment, path)),
+                AttachmentSource::Url(url) => {
+                    let link = match &attachment.caption {
+                        Some(caption) => format!("[{caption}]({url})"),
+                        None => url.clone(),
+                    };
+                    if !text.is_empty() {
+                        text.push('\n');
+                    }
+                    text.push_str(&link);
+                }
+            }
+        }
+
+        if !text.is_empty() || uploads.is_empty() {
+            let mut content = RoomMessageEventContent::text_markdown(&text);
 
-        if let Some(ref thread_ts) = message.thread_ts {
-            if let Ok(thread_root) = thread_ts.parse::<OwnedEventId>() {
-                content.relates_to = Some(Relation::Thread(Thread::plain(
-                    thread_root.clone(),
-                    thread_root,
-                )));
+            if let Some(ref thread_ts) = message.thread_ts {
+                if let Ok(thread_root) = thread_ts.parse::<OwnedEventId>() {
+                    content.relates_to = Some(Relation::Thread(Thread::plain(
+                        thread_root.clone(),
+                        thread_root,
+                    )));
+                }
             }
+
+            room.send(content).await?;
         }
 
-        room.send(content).await?;
+        for (attachment, path) in uploads {
+            self.send_media_attachment(
+                &target_room_id,
+                message.thread_ts.as_deref(),
+                attachment,
+                path,
+            )
+            .await?;
+        }
 
         // Voice reply: generate TTS audio and send as m.audio when voice_mode is active
         if self.voice_mode.load(Ordering::Relaxed) {
@@ -729,13 +859,16 @@ impl Channel for MatrixChannel {
                 }
 
                 // Helper: extract mxc:// download URL and filename for media types
-                let media_info = |source: &MediaSource, name: &str| -> Option<(String, String)> {
+                let media_info = |source: &MediaSource,
+                                  name: &str,
+                                  kind: AttachmentKind|
+                 -> Option<(String, String, AttachmentKind)> {
                     match source {
                         MediaSource::Plain(mxc) => {
                             let rest = mxc.as_str().strip_prefix("mxc://")?;
                             let url =
                                 format!("{}/_matrix/client/v1/media/download/{}", homeserver, rest);
-                            Some((url, name.to_string()))
+                            Some((url, name.to_string(), kind))
                         }
                         MediaSource::Encrypted(_) => None,
                     }
@@ -745,26 +878,28 @@ impl Channel for MatrixChannel {
                     MessageType::Text(content) => (content.body.clone(), None),
                     MessageType::Notice(content) => (content.body.clone(), None),
                     MessageType::Image(content) => {
-                        let dl = media_info(&content.source, &content.body);
+                        let dl = media_info(&content.source, &content.body, AttachmentKind::Image);
                         (format!("[IMAGE:{}]", content.body), dl)
                     }
                     MessageType::File(content) => {
-                        let dl = media_info(&content.source, &content.body);
+                        let dl =
+                            media_info(&content.source, &content.body, AttachmentKind::Document);
                         (format!("[file: {}]", content.body), dl)
                     }
                     MessageType::Audio(content) => {
-                        let dl = media_info(&content.source, &content.body);
+                        let dl = media_info(&content.source, &content.body, AttachmentKind::Audio);
                         (format!("[audio: {}]", content.body), dl)
                     }
                     MessageType::Video(content) => {
-                        let dl = media_info(&content.source, &content.body);
+                        let dl = media_info(&content.source, &content.body, AttachmentKind::Video);
                         (format!("[video: {}]", content.body), dl)
                     }
                     _ => return,
                 };
 
                 // Download media to workspace if present
-                let body = if let Some((url, filename)) = media_download {
+                let mut attachments = Vec::new();
+                let body = if let Some((url, filename, kind)) = media_download {
                     let workspace = std::path::PathBuf::from(
                         shellexpand::tilde(
                             &std::env::var("ZEROCLAW_WORKSPACE")
@@ -783,7 +918,10 @@ impl Channel for MatrixChannel {
                     {
                         Ok(resp) if resp.status().is_success() => match resp.bytes().await {
                             Ok(bytes) => match tokio::fs::write(&dest, &bytes).await {
-                                Ok(()) => format!("{} — saved to {}", body, dest.display()),
+                                Ok(()) => {
+                                    attachments.push(Attachment::from_path(kind, &dest));
+                                    format!("{} — saved to {}", body, dest.display())
+                                }
                                 Err(_) => format!("{} — failed to write to disk", body),
                             },
                             Err(_) => format!("{} — download failed", body),
@@ -894,6 +1032,7 @@ impl Channel for MatrixChannel {
                         .unwrap_or_default()
                         .as_secs(),
                     thread_ts,
+                    attachments,
                 };
 
                 let _ = tx.send(msg).await;
@@ -1248,6 +1387,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn attachment_kinds_map_to_matrix_msgtypes() {
+        assert_eq!(
+            MatrixChannel::attachment_msgtype(AttachmentKind::Image),
+            "m.image"
+        );
+        assert_eq!(
+            MatrixChannel::attachment_msgtype(AttachmentKind::Voice),
+            "m.audio"
+        );
+        assert_eq!(
+            MatrixChannel::attachment_msgtype(AttachmentKind::Document),
+            "m.file"
+        );
+    }
+
     #[test]
     fn supported_message_type_detection() {
         assert!(MatrixChannel::is_supported_message_type("m.text"));
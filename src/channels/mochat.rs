@@ -198,6 +198,7 @@ impl Channel for MochatChannel {
                                     .unwrap_or_default()
                                     .as_secs(),
                                 thread_ts: None,
+                                attachments: Vec::new(),
                             };
 
                             if tx.send(channel_msg).await.is_err() {
@@ -14,6 +14,7 @@
 //! To add a new channel, implement [`Channel`] in a new submodule and wire it into
 //! [`start_channels`]. See `AGENTS.md` §7.2 for the full change playbook.
 
+pub mod approval;
 pub mod bluesky;
 pub mod clawdtalk;
 pub mod cli;
@@ -30,6 +31,7 @@ pub mod lisa;
 pub mod matrix;
 pub mod mattermost;
 pub mod mochat;
+pub mod mqtt;
 pub mod nextcloud_talk;
 #[cfg(feature = "channel-nostr")]
 pub mod nostr;
@@ -71,6 +73,7 @@ pub use lisa::LisaChannel;
 pub use matrix::MatrixChannel;
 pub use mattermost::MattermostChannel;
 pub use mochat::MochatChannel;
+pub use mqtt::MqttChannel;
 pub use nextcloud_talk::NextcloudTalkChannel;
 #[cfg(feature = "channel-nostr")]
 pub use nostr::NostrChannel;
@@ -98,9 +101,11 @@ use crate::identity;
 use crate::memory::{self, Memory};
 use crate::observability::traits::{ObserverEvent, ObserverMetric};
 use crate::observability::{self, runtime_trace, Observer};
+use crate::providers::tokenizer::{context_budget, count_messages, tokenizer_for_model, Tokenizer};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
 use crate::security::SecurityPolicy;
+use crate::sop::dispatch::{dispatch_channel_message, process_headless_results};
 use crate::tools::{self, Tool};
 use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
@@ -199,13 +204,13 @@ const MEMORY_CONTEXT_ENTRY_MAX_CHARS: usize = 800;
 const MEMORY_CONTEXT_MAX_CHARS: usize = 4_000;
 const CHANNEL_HISTORY_COMPACT_KEEP_MESSAGES: usize = 12;
 const CHANNEL_HISTORY_COMPACT_CONTENT_CHARS: usize = 600;
-/// Proactive context-window budget in estimated characters (~4 chars/token).
-/// When the total character count of conversation history exceeds this limit,
+/// Proactive context-window budget in tokens.
+/// When the token count of conversation history exceeds this limit (or three
+/// quarters of the active model's context window, whichever is smaller),
 /// older turns are dropped before the request is sent to the provider,
-/// preventing context-window-exceeded errors.  Set conservatively below
-/// common context windows (128 k tokens ≈ 512 k chars) to leave room for
+/// preventing context-window-exceeded errors.  The remainder is left for the
 /// system prompt, memory context, and model output.
-const PROACTIVE_CONTEXT_BUDGET_CHARS: usize = 400_000;
+const PROACTIVE_CONTEXT_BUDGET_TOKENS: usize = 100_000;
 /// Guardrail for hook-modified outbound channel content.
 const CHANNEL_HOOK_MAX_OUTBOUND_CHARS: usize = 20_000;
 
@@ -335,9 +340,32 @@ struct ChannelRuntimeContext {
     /// Non-interactive approval manager for channel-driven runs.
     /// Enforces `auto_approve` / `always_ask` / supervised policy from
     /// `[autonomy]` config; auto-denies tools that would need interactive
-    /// approval since no operator is present on channel runs.
+    /// approval unless `approval_broker` is set.
     approval_manager: Arc<ApprovalManager>,
+    /// Routes approval prompts to the originating sender when
+    /// `[autonomy.channel_approval]` is enabled.
+    approval_broker: Option<Arc<approval::ChannelApprovalBroker>>,
     activated_tools: Option<std::sync::Arc<std::sync::Mutex<crate::tools::ActivatedToolSet>>>,
+    /// `[profiles.<name>]` this context serves; `None` for the top-level agent.
+    profile: Option<Arc<str>>,
+    /// Profile contexts that bound messages are dispatched to (top-level only).
+    profile_routes: Option<Arc<ProfileRoutes>>,
+    /// SOP runtime that `channel` triggers are matched against (`[sop].enabled`).
+    sop: Option<Arc<crate::sop::SopRuntime>>,
+}
+
+/// Runtime contexts for `[profiles]`, keyed by profile name, with the
+/// bindings that select them.
+struct ProfileRoutes {
+    bindings: HashMap<String, crate::config::AgentProfileConfig>,
+    contexts: HashMap<String, Arc<ChannelRuntimeContext>>,
+}
+
+impl ProfileRoutes {
+    fn context_for(&self, msg: &traits::ChannelMessage) -> Option<&Arc<ChannelRuntimeContext>> {
+        crate::config::AgentProfileConfig::select(&self.bindings, &msg.channel, &msg.sender)
+            .and_then(|name| self.contexts.get(name))
+    }
 }
 
 #[derive(Clone)]
@@ -732,6 +760,11 @@ fn runtime_defaults_from_config(config: &Config) -> ChannelRuntimeDefaults {
 }
 
 fn runtime_config_path(ctx: &ChannelRuntimeContext) -> Option<PathBuf> {
+    // Profile overrides are fixed at startup; hot reload only tracks the
+    // top-level defaults.
+    if ctx.profile.is_some() {
+        return None;
+    }
     ctx.provider_runtime_options
         .zeroclaw_dir
         .as_ref()
@@ -973,22 +1006,26 @@ fn compact_sender_history(ctx: &ChannelRuntimeContext, sender_key: &str) -> bool
     true
 }
 
-/// Proactively trim conversation turns so that the total estimated character
-/// count stays within [`PROACTIVE_CONTEXT_BUDGET_CHARS`].  Drops the oldest
-/// turns first, but always preserves the most recent turn (the current user
+/// Proactively trim conversation turns so that their total token count, as
+/// measured by `tokenizer`, stays within `budget`.  Drops the oldest turns
+/// first, but always preserves the most recent turn (the current user
 /// message).  Returns the number of turns dropped.
-fn proactive_trim_turns(turns: &mut Vec<ChatMessage>, budget: usize) -> usize {
-    let total_chars: usize = turns.iter().map(|t| t.content.chars().count()).sum();
-    if total_chars <= budget || turns.len() <= 1 {
+fn proactive_trim_turns(
+    turns: &mut Vec<ChatMessage>,
+    tokenizer: &dyn Tokenizer,
+    budget: usize,
+) -> usize {
+    let total_tokens = count_messages(tokenizer, turns);
+    if total_tokens <= budget || turns.len() <= 1 {
         return 0;
     }
 
-    let mut excess = total_chars.saturating_sub(budget);
+    let mut excess = total_tokens.saturating_sub(budget);
     let mut drop_count = 0;
 
     // Walk from the oldest turn forward, but never drop the very last turn.
     while excess > 0 && drop_count < turns.len().saturating_sub(1) {
-        excess = excess.saturating_sub(turns[drop_count].content.chars().count());
+        excess = excess.saturating_sub(tokenizer.count_message(&turns[drop_count]));
         drop_count += 1;
     }
 
@@ -2036,13 +2073,19 @@ async fn process_channel_message(
         && !memory::should_skip_autosave_content(&msg.content)
     {
         let autosave_key = conversation_memory_key(&msg);
+        let metadata = crate::memory::MemoryMetadata {
+            source_channel: Some(msg.channel.clone()),
+            source_sender: Some(msg.sender.clone()),
+            ..Default::default()
+        };
         let _ = ctx
             .memory
-            .store(
+            .store_with_metadata(
                 &autosave_key,
                 &msg.content,
                 crate::memory::MemoryCategory::Conversation,
                 Some(&history_key),
+                &metadata,
             )
             .await;
     }
@@ -2058,7 +2101,8 @@ async fn process_channel_message(
         .is_some_and(|turns| !turns.is_empty());
 
     // Preserve user turn before the LLM call so interrupted requests keep context.
-    append_sender_turn(ctx.as_ref(), &history_key, ChatMessage::user(&msg.content));
+    let user_content = msg.agent_content();
+    append_sender_turn(ctx.as_ref(), &history_key, ChatMessage::user(&user_content));
 
     // Build history from per-sender conversation cache.
     let prior_turns_raw = ctx
@@ -2104,7 +2148,15 @@ async fn process_channel_message(
 
     // Proactively trim conversation history before sending to the provider
     // to prevent context-window-exceeded errors (bug #3460).
-    let dropped = proactive_trim_turns(&mut prior_turns, PROACTIVE_CONTEXT_BUDGET_CHARS);
+    let history_budget = context_budget(
+        PROACTIVE_CONTEXT_BUDGET_TOKENS,
+        active_provider.context_window(&route.model),
+    );
+    let dropped = proactive_trim_turns(
+        &mut prior_turns,
+        tokenizer_for_model(&route.model).as_ref(),
+        history_budget,
+    );
     if dropped > 0 {
         tracing::info!(
             channel = %msg.channel,
@@ -2127,7 +2179,7 @@ async fn process_channel_message(
         .await;
         if let Some(last_turn) = prior_turns.last_mut() {
             if last_turn.role == "user" && !memory_context.is_empty() {
-                last_turn.content = format!("{memory_context}{}", msg.content);
+                last_turn.content = format!("{memory_context}{user_content}");
             }
         }
     }
@@ -2265,35 +2317,51 @@ async fn process_channel_message(
         Cancelled,
     }
 
+    // Ask the sender for approval instead of auto-denying when enabled.
+    let prompted_approval = match (ctx.approval_broker.as_ref(), target_channel.as_ref()) {
+        (Some(broker), Some(channel)) => Some(ctx.approval_manager.with_prompter(
+            Arc::new(broker.prompter(Arc::clone(channel), &msg, msg.thread_ts.clone())),
+            broker.allowed_tools(&msg),
+        )),
+        _ => None,
+    };
+    let approval_manager = prompted_approval
+        .as_ref()
+        .unwrap_or(ctx.approval_manager.as_ref());
+
     let timeout_budget_secs =
         channel_message_timeout_budget_secs(ctx.message_timeout_secs, ctx.max_tool_iterations);
+    let tool_attachments = Arc::new(parking_lot::Mutex::new(Vec::new()));
     let llm_result = tokio::select! {
         () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
         result = tokio::time::timeout(
             Duration::from_secs(timeout_budget_secs),
-            run_tool_call_loop(
-                active_provider.as_ref(),
-                &mut history,
-                ctx.tools_registry.as_ref(),
-                notify_observer.as_ref() as &dyn Observer,
-                route.provider.as_str(),
-                route.model.as_str(),
-                runtime_defaults.temperature,
-                true,
-                Some(&*ctx.approval_manager),
-                msg.channel.as_str(),
-                &ctx.multimodal,
-                ctx.max_tool_iterations,
-                Some(cancellation_token.clone()),
-                delta_tx,
-                ctx.hooks.as_deref(),
-                if msg.channel == "cli" {
-                    &[]
-                } else {
-                    ctx.non_cli_excluded_tools.as_ref()
-                },
-                ctx.tool_call_dedup_exempt.as_ref(),
-                ctx.activated_tools.as_ref(),
+            crate::tools::collect_reply_attachments(
+                Arc::clone(&tool_attachments),
+                run_tool_call_loop(
+                    active_provider.as_ref(),
+                    &mut history,
+                    ctx.tools_registry.as_ref(),
+                    notify_observer.as_ref() as &dyn Observer,
+                    route.provider.as_str(),
+                    route.model.as_str(),
+                    runtime_defaults.temperature,
+                    true,
+                    Some(approval_manager),
+                    msg.channel.as_str(),
+                    &ctx.multimodal,
+                    ctx.max_tool_iterations,
+                    Some(cancellation_token.clone()),
+                    delta_tx,
+                    ctx.hooks.as_deref(),
+                    if msg.channel == "cli" {
+                        &[]
+                    } else {
+                        ctx.non_cli_excluded_tools.as_ref()
+                    },
+                    ctx.tool_call_dedup_exempt.as_ref(),
+                    ctx.activated_tools.as_ref(),
+                ),
             ),
         ) => LlmExecutionResult::Completed(result),
     };
@@ -2489,6 +2557,13 @@ async fn process_channel_message(
                     (delivered_response.clone(), None)
                 };
 
+                // Files returned by tools go out as typed attachments.
+                let outbound_attachments = if channel.supports_attachments() {
+                    std::mem::take(&mut *tool_attachments.lock())
+                } else {
+                    Vec::new()
+                };
+
                 if let Some(ref draft_id) = draft_message_id {
                     if let Err(e) = channel
                         .finalize_draft(&msg.reply_target, draft_id, &outbound_text)
@@ -2496,15 +2571,33 @@ async fn process_channel_message(
                     {
                         tracing::warn!("Failed to finalize draft: {e}; sending as new message");
                         let mut send_msg = SendMessage::new(&outbound_text, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone());
+                            .in_thread(msg.thread_ts.clone())
+                            .with_attachments(outbound_attachments);
                         if let Some(data) = outbound_data.clone() {
                             send_msg = send_msg.with_data(data);
                         }
                         let _ = channel.send(&send_msg).await;
+                    } else if !outbound_attachments.is_empty() {
+                        let send_msg = SendMessage::new("", &msg.reply_target)
+                            .in_thread(msg.thread_ts.clone())
+                            .with_attachments(outbound_attachments);
+                        if let Err(e) = channel.send(&send_msg).await {
+                            eprintln!("  ❌ Failed to send attachments on {}: {e}", channel.name());
+                        }
                     }
                 } else {
+                    // Attachment-capable channels get reply markers as typed
+                    // attachments so files are delivered natively.
+                    let (outbound_text, outbound_attachments) = if channel.supports_attachments() {
+                        let (text, mut marked) = traits::split_attachment_markers(&outbound_text);
+                        marked.extend(outbound_attachments);
+                        (text, marked)
+                    } else {
+                        (outbound_text, outbound_attachments)
+                    };
                     let mut send_msg = SendMessage::new(outbound_text, &msg.reply_target)
-                        .in_thread(msg.thread_ts.clone());
+                        .in_thread(msg.thread_ts.clone())
+                        .with_attachments(outbound_attachments);
                     if let Some(data) = outbound_data {
                         send_msg = send_msg.with_data(data);
                     }
@@ -2605,7 +2698,7 @@ async fn process_channel_message(
                     .downcast_ref::<providers::ProviderCapabilityError>()
                     .is_some_and(|capability| capability.capability.eq_ignore_ascii_case("vision"));
                 let rolled_back = should_rollback_user_turn
-                    && rollback_orphan_user_turn(ctx.as_ref(), &history_key, &msg.content);
+                    && rollback_orphan_user_turn(ctx.as_ref(), &history_key, &user_content);
 
                 if !rolled_back {
                     // Close the orphan user turn so subsequent messages don't
@@ -2711,12 +2804,36 @@ async fn run_message_dispatch_loop(
     let task_sequence = Arc::new(AtomicU32::new(1));
 
     while let Some(msg) = rx.recv().await {
+        let worker_ctx = ctx
+            .profile_routes
+            .as_ref()
+            .and_then(|routes| routes.context_for(&msg))
+            .map_or_else(|| Arc::clone(&ctx), Arc::clone);
+
+        // Answers to pending approval prompts resume the waiting turn instead
+        // of starting (or interrupting) one.
+        if worker_ctx
+            .approval_broker
+            .as_ref()
+            .is_some_and(|broker| broker.try_resolve(&msg))
+        {
+            continue;
+        }
+
+        // `channel` SOP triggers fire alongside the normal reply.
+        if let Some(sop) = ctx.sop.as_ref().map(Arc::clone) {
+            let sop_msg = msg.clone();
+            tokio::spawn(async move {
+                let results = dispatch_channel_message(&sop.engine, &sop.audit, &sop_msg).await;
+                process_headless_results(&results);
+            });
+        }
+
         let permit = match Arc::clone(&semaphore).acquire_owned().await {
             Ok(permit) => permit,
             Err(_) => break,
         };
 
-        let worker_ctx = Arc::clone(&ctx);
         let in_flight = Arc::clone(&in_flight_by_sender);
         let task_sequence = Arc::clone(&task_sequence);
         workers.spawn(async move {
@@ -2753,7 +2870,20 @@ async fn run_message_dispatch_loop(
                 }
             }
 
-            process_channel_message(worker_ctx, msg, cancellation_token).await;
+            let trace_session = conversation_history_key(&msg);
+            let usage_scope = crate::cost::UsageScope {
+                channel: Some(msg.channel.clone()),
+                sender: Some(msg.sender.clone()),
+                ..Default::default()
+            };
+            runtime_trace::with_session(
+                trace_session,
+                crate::cost::scope::with_scope(
+                    usage_scope,
+                    Box::pin(process_channel_message(worker_ctx, msg, cancellation_token)),
+                ),
+            )
+            .await;
 
             if interrupt_enabled {
                 let mut active = in_flight.lock().await;
@@ -3578,10 +3708,22 @@ fn collect_configured_channels(
         }
     }
 
+    if let Some(ref mqtt) = config.channels_config.mqtt {
+        if let Some(ref options) = mqtt.channel {
+            channels.push(ConfiguredChannel {
+                display_name: "MQTT",
+                channel: Arc::new(MqttChannel::new(mqtt.clone(), options.clone())),
+            });
+        }
+    }
+
     if let Some(ref email_cfg) = config.channels_config.email {
         channels.push(ConfiguredChannel {
             display_name: "Email",
-            channel: Arc::new(EmailChannel::new(email_cfg.clone())),
+            channel: Arc::new(
+                EmailChannel::new(email_cfg.clone())
+                    .with_workspace_dir(config.workspace_dir.clone()),
+            ),
         });
     }
 
@@ -3831,10 +3973,15 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
     Ok(())
 }
 
-/// Start all configured channels and route messages to the agent
+/// Build the provider, memory, tools and system prompt that handle channel
+/// messages for one configuration — the top-level agent or a profile.
 #[allow(clippy::too_many_lines)]
-pub async fn start_channels(config: Config) -> Result<()> {
-    let provider_name = resolved_default_provider(&config);
+async fn build_channel_runtime_context(
+    config: &Config,
+    channels_by_name: Arc<HashMap<String, Arc<dyn Channel>>>,
+    observer: Arc<dyn Observer>,
+) -> Result<ChannelRuntimeContext> {
+    let provider_name = resolved_default_provider(config);
     let provider_runtime_options = providers::ProviderRuntimeOptions {
         auth_profile_override: None,
         provider_api_url: config.api_url.clone(),
@@ -3848,6 +3995,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         reasoning_level: config.runtime.reasoning_level.clone(),
         custom_provider_auth_header: config.custom_provider_auth_header.clone(),
         service_tier: config.runtime.service_tier.clone(),
+        llama_server: config.llama_server.clone(),
     };
     let provider: Arc<dyn Provider> = Arc::from(
         create_resilient_provider_nonblocking(
@@ -3866,29 +4014,13 @@ pub async fn start_channels(config: Config) -> Result<()> {
         tracing::warn!("Provider warmup failed (non-fatal): {e}");
     }
 
-    let initial_stamp = config_file_stamp(&config.config_path).await;
-    {
-        let mut store = runtime_config_store()
-            .lock()
-            .unwrap_or_else(|e| e.into_inner());
-        store.insert(
-            config.config_path.clone(),
-            RuntimeConfigState {
-                defaults: runtime_defaults_from_config(&config),
-                last_applied_stamp: initial_stamp,
-            },
-        );
-    }
-
-    let observer: Arc<dyn Observer> =
-        Arc::from(observability::create_observer(&config.observability));
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
         &config.autonomy,
         &config.workspace_dir,
     ));
-    let model = resolved_default_model(&config);
+    let model = resolved_default_model(config);
     let temperature = config.default_temperature;
     let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage_and_routes(
         &config.memory,
@@ -3921,9 +4053,15 @@ pub async fn start_channels(config: Config) -> Result<()> {
             &workspace,
             &config.agents,
             config.api_key.as_deref(),
-            &config,
+            config,
         );
 
+    // Profile tool allowlist, applied to MCP, app and peer tools below as well.
+    let profile_allowed_tools = config.profile_allowed_tools();
+    if let Some(ref allow_list) = profile_allowed_tools {
+        built_tools.retain(|t| allow_list.iter().any(|name| name == t.name()));
+    }
+
     // Wire MCP tools into the registry before freezing — non-fatal.
     // When `deferred_loading` is enabled, MCP tools are NOT added eagerly.
     // Instead, a `tool_search` built-in is registered for on-demand loading.
@@ -3940,10 +4078,15 @@ pub async fn start_channels(config: Config) -> Result<()> {
             Ok(registry) => {
                 let registry = std::sync::Arc::new(registry);
                 if config.mcp.deferred_loading {
-                    let deferred_set = crate::tools::DeferredMcpToolSet::from_registry(
+                    let mut deferred_set = crate::tools::DeferredMcpToolSet::from_registry(
                         std::sync::Arc::clone(&registry),
                     )
                     .await;
+                    if let Some(ref allow_list) = profile_allowed_tools {
+                        deferred_set
+                            .stubs
+                            .retain(|stub| allow_list.contains(&stub.prefixed_name));
+                    }
                     tracing::info!(
                         "MCP deferred: {} tool stub(s) from {} server(s)",
                         deferred_set.len(),
@@ -3963,6 +4106,12 @@ pub async fn start_channels(config: Config) -> Result<()> {
                     let names = registry.tool_names();
                     let mut registered = 0usize;
                     for name in names {
+                        if profile_allowed_tools
+                            .as_ref()
+                            .is_some_and(|allow_list| !allow_list.contains(&name))
+                        {
+                            continue;
+                        }
                         if let Some(def) = registry.get_tool_def(&name).await {
                             let wrapper: std::sync::Arc<dyn Tool> =
                                 std::sync::Arc::new(crate::tools::McpToolWrapper::new(
@@ -3991,6 +4140,17 @@ pub async fn start_channels(config: Config) -> Result<()> {
         }
     }
 
+    // ── Wire appMCP manifest tools (non-fatal) ───────────────────
+    crate::tools::app_mcp::attach_app_mcp_tools(&config.app_mcp, &mut ch_activated_handle).await;
+
+    // ── Wire mesh peer tools (kept in sync with the peer directory) ──
+    crate::tools::peer_tool::attach_peer_tools(&config.node_transport, &mut ch_activated_handle);
+
+    // App and peer tools arrive at runtime, so the allowlist travels with the set.
+    if let (Some(allow_list), Some(handle)) = (&profile_allowed_tools, &ch_activated_handle) {
+        handle.lock().unwrap().restrict_to(allow_list.clone());
+    }
+
     // ── Register SKILL.toml-defined tools (all active channels) ──
     // Use load_skills_with_config (not load_skills) so that config options such as
     // allow_scripts are respected when auditing skill directories.
@@ -3998,14 +4158,18 @@ pub async fn start_channels(config: Config) -> Result<()> {
     // happens at system-prompt build time via channel_name.
     {
         let skills_for_tools = crate::skills::filter_skills_by_channel(
-            crate::skills::load_skills_full_with_config(&workspace, &config),
+            crate::skills::load_skills_full_with_config(&workspace, config),
             None,
         );
-        let skill_tools = crate::skills::create_skill_tools_with_override(
+        let mut skill_tools = crate::skills::create_skill_tools_with_override(
             &skills_for_tools,
             security.clone(),
+            &config.wasm,
             config.skills.tool_choice_required,
         );
+        if let Some(ref allow_list) = profile_allowed_tools {
+            skill_tools.retain(|t| allow_list.iter().any(|name| name == t.name()));
+        }
         if !skill_tools.is_empty() {
             tracing::info!(count = skill_tools.len(), "Channel skill tools registered");
             built_tools.extend(skill_tools);
@@ -4026,7 +4190,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     let tools_registry = Arc::new(built_tools);
 
     let skills = crate::skills::filter_skills_by_channel(
-        crate::skills::load_skills_with_config(&workspace, &config),
+        crate::skills::load_skills_with_config(&workspace, config),
         None,
     );
 
@@ -4129,84 +4293,6 @@ pub async fn start_channels(config: Config) -> Result<()> {
         );
     }
 
-    // Collect active channels from a shared builder to keep startup and doctor parity.
-    #[allow(unused_mut)]
-    let mut channels: Vec<Arc<dyn Channel>> =
-        collect_configured_channels(&config, "runtime startup")
-            .into_iter()
-            .map(|configured| configured.channel)
-            .collect();
-
-    #[cfg(feature = "channel-nostr")]
-    if let Some(ref ns) = config.channels_config.nostr {
-        channels.push(Arc::new(
-            NostrChannel::new(&ns.private_key, ns.relays.clone(), &ns.allowed_pubkeys).await?,
-        ));
-    }
-    if channels.is_empty() {
-        println!("No channels configured. Run `zeroclaw onboard` to set up channels.");
-        return Ok(());
-    }
-
-    println!("🦀 ZeroClaw Channel Server");
-    println!("  🤖 Model:    {model}");
-    let effective_backend = memory::effective_memory_backend_name(
-        &config.memory.backend,
-        Some(&config.storage.provider.config),
-    );
-    println!(
-        "  🧠 Memory:   {} (auto-save: {})",
-        effective_backend,
-        if config.memory.auto_save { "on" } else { "off" }
-    );
-    println!(
-        "  📡 Channels: {}",
-        channels
-            .iter()
-            .map(|c| c.name())
-            .collect::<Vec<_>>()
-            .join(", ")
-    );
-    println!();
-    println!("  Listening for messages... (Ctrl+C to stop)");
-    println!();
-
-    crate::health::mark_component_ok("channels");
-
-    let initial_backoff_secs = config
-        .reliability
-        .channel_initial_backoff_secs
-        .max(DEFAULT_CHANNEL_INITIAL_BACKOFF_SECS);
-    let max_backoff_secs = config
-        .reliability
-        .channel_max_backoff_secs
-        .max(DEFAULT_CHANNEL_MAX_BACKOFF_SECS);
-
-    // Single message bus — all channels send messages here
-    let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(100);
-
-    // Spawn a listener for each channel
-    let mut handles = Vec::new();
-    for ch in &channels {
-        handles.push(spawn_supervised_listener(
-            ch.clone(),
-            tx.clone(),
-            initial_backoff_secs,
-            max_backoff_secs,
-        ));
-    }
-    drop(tx); // Drop our copy so rx closes when all channels stop
-
-    let channels_by_name = Arc::new(
-        channels
-            .iter()
-            .map(|ch| (ch.name().to_string(), Arc::clone(ch)))
-            .collect::<HashMap<_, _>>(),
-    );
-    let max_in_flight_messages = compute_max_in_flight_messages(channels.len());
-
-    println!("  🚦 In-flight message limit: {max_in_flight_messages}");
-
     let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
     provider_cache_seed.insert(provider_name.clone(), Arc::clone(&provider));
     let message_timeout_secs =
@@ -4222,7 +4308,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .as_ref()
         .is_some_and(|sl| sl.interrupt_on_new_message);
 
-    let runtime_ctx = Arc::new(ChannelRuntimeContext {
+    let runtime_ctx = ChannelRuntimeContext {
         channels_by_name,
         provider: Arc::clone(&provider),
         default_provider: Arc::new(provider_name),
@@ -4284,8 +4370,17 @@ pub async fn start_channels(config: Config) -> Result<()> {
             None
         },
         approval_manager: Arc::new(ApprovalManager::for_non_interactive(&config.autonomy)),
+        approval_broker: config.autonomy.channel_approval.enabled.then(|| {
+            Arc::new(approval::ChannelApprovalBroker::new(
+                &config.autonomy.channel_approval,
+                &config.workspace_dir,
+            ))
+        }),
         activated_tools: ch_activated_handle,
-    });
+        profile: config.active_profile.as_deref().map(Arc::from),
+        profile_routes: None,
+        sop: crate::sop::SopRuntime::shared(config),
+    };
 
     // Hydrate in-memory conversation histories from persisted JSONL session files.
     if let Some(ref store) = runtime_ctx.session_store {
@@ -4307,7 +4402,150 @@ pub async fn start_channels(config: Config) -> Result<()> {
         }
     }
 
-    run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
+    Ok(runtime_ctx)
+}
+
+/// Start all configured channels and route messages to the agent
+#[allow(clippy::too_many_lines)]
+pub async fn start_channels(config: Config) -> Result<()> {
+    // Collect active channels from a shared builder to keep startup and doctor parity.
+    #[allow(unused_mut)]
+    let mut channels: Vec<Arc<dyn Channel>> =
+        collect_configured_channels(&config, "runtime startup")
+            .into_iter()
+            .map(|configured| configured.channel)
+            .collect();
+
+    #[cfg(feature = "channel-nostr")]
+    if let Some(ref ns) = config.channels_config.nostr {
+        channels.push(Arc::new(
+            NostrChannel::new(&ns.private_key, ns.relays.clone(), &ns.allowed_pubkeys).await?,
+        ));
+    }
+    if channels.is_empty() {
+        println!("No channels configured. Run `zeroclaw onboard` to set up channels.");
+        return Ok(());
+    }
+
+    let channels_by_name = Arc::new(
+        channels
+            .iter()
+            .map(|ch| (ch.name().to_string(), Arc::clone(ch)))
+            .collect::<HashMap<_, _>>(),
+    );
+
+    let observer: Arc<dyn Observer> =
+        Arc::from(observability::create_observer(&config.observability));
+
+    let initial_stamp = config_file_stamp(&config.config_path).await;
+    {
+        let mut store = runtime_config_store()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        store.insert(
+            config.config_path.clone(),
+            RuntimeConfigState {
+                defaults: runtime_defaults_from_config(&config),
+                last_applied_stamp: initial_stamp,
+            },
+        );
+    }
+
+    let mut runtime_ctx = build_channel_runtime_context(
+        &config,
+        Arc::clone(&channels_by_name),
+        Arc::clone(&observer),
+    )
+    .await?;
+
+    // Each profile gets its own provider, memory, tools and sessions; the
+    // dispatch loop routes bound senders and channels to them.
+    if !config.profiles.is_empty() {
+        let mut names: Vec<&String> = config.profiles.keys().collect();
+        names.sort();
+        let mut contexts = HashMap::new();
+        for name in names {
+            let profile_config = config.for_profile(name)?;
+            let profile_ctx = build_channel_runtime_context(
+                &profile_config,
+                Arc::clone(&channels_by_name),
+                Arc::clone(&observer),
+            )
+            .await
+            .with_context(|| format!("Failed to start profile '{name}'"))?;
+            contexts.insert(name.clone(), Arc::new(profile_ctx));
+        }
+        runtime_ctx.profile_routes = Some(Arc::new(ProfileRoutes {
+            bindings: config.profiles.clone(),
+            contexts,
+        }));
+    }
+
+    println!("🦀 ZeroClaw Channel Server");
+    println!("  🤖 Model:    {}", runtime_ctx.model);
+    let effective_backend = memory::effective_memory_backend_name(
+        &config.memory.backend,
+        Some(&config.storage.provider.config),
+    );
+    println!(
+        "  🧠 Memory:   {} (auto-save: {})",
+        effective_backend,
+        if config.memory.auto_save { "on" } else { "off" }
+    );
+    println!(
+        "  📡 Channels: {}",
+        channels
+            .iter()
+            .map(|c| c.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if let Some(ref routes) = runtime_ctx.profile_routes {
+        let mut profiles: Vec<_> = routes.contexts.iter().collect();
+        profiles.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, ctx) in profiles {
+            println!(
+                "  👤 Profile:  {name} — {} ({})",
+                ctx.model,
+                ctx.workspace_dir.display()
+            );
+        }
+    }
+    println!();
+    println!("  Listening for messages... (Ctrl+C to stop)");
+    println!();
+
+    crate::health::mark_component_ok("channels");
+
+    let initial_backoff_secs = config
+        .reliability
+        .channel_initial_backoff_secs
+        .max(DEFAULT_CHANNEL_INITIAL_BACKOFF_SECS);
+    let max_backoff_secs = config
+        .reliability
+        .channel_max_backoff_secs
+        .max(DEFAULT_CHANNEL_MAX_BACKOFF_SECS);
+
+    // Single message bus — all channels send messages here
+    let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(100);
+
+    // Spawn a listener for each channel
+    let mut handles = Vec::new();
+    for ch in &channels {
+        handles.push(spawn_supervised_listener(
+            ch.clone(),
+            tx.clone(),
+            initial_backoff_secs,
+            max_backoff_secs,
+        ));
+    }
+    drop(tx); // Drop our copy so rx closes when all channels stop
+
+    let max_in_flight_messages = compute_max_in_flight_messages(channels.len());
+
+    println!("  🚦 In-flight message limit: {max_in_flight_messages}");
+
+    run_message_dispatch_loop(rx, Arc::new(runtime_ctx), max_in_flight_messages).await;
 
     // Wait for all channel tasks
     for h in handles {
@@ -4322,6 +4560,7 @@ mod tests {
     use super::*;
     use crate::memory::{Memory, MemoryCategory, SqliteMemory};
     use crate::observability::NoopObserver;
+    use crate::providers::tokenizer::HeuristicTokenizer;
     use crate::providers::{ChatMessage, Provider};
     use crate::tools::{Tool, ToolResult};
     use std::collections::{HashMap, HashSet};
@@ -4577,7 +4816,11 @@ mod tests {
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         };
 
         assert!(compact_sender_history(&ctx, &sender));
@@ -4612,8 +4855,10 @@ mod tests {
             })
             .collect();
 
-        // Budget of 500 should drop roughly half (oldest turns).
-        let dropped = proactive_trim_turns(&mut turns, 500);
+        // Heuristic tokenizer: 25 content + 4 framing tokens per turn, 290 total.
+        // A budget of 150 should drop roughly half (oldest turns).
+        let tokenizer = HeuristicTokenizer;
+        let dropped = proactive_trim_turns(&mut turns, &tokenizer, 150);
         assert!(dropped > 0, "should have dropped some turns");
         assert!(turns.len() < 10, "should have fewer turns after trimming");
         // Last turn should always be preserved.
@@ -4621,9 +4866,9 @@ mod tests {
             turns.last().unwrap().content.starts_with("m9-"),
             "most recent turn must be preserved"
         );
-        // Total chars should now be within budget.
-        let total: usize = turns.iter().map(|t| t.content.chars().count()).sum();
-        assert!(total <= 500, "total chars {total} should be within budget");
+        // Total tokens should now be within budget.
+        let total = count_messages(&tokenizer, &turns);
+        assert!(total <= 150, "total tokens {total} should be within budget");
     }
 
     #[test]
@@ -4632,7 +4877,7 @@ mod tests {
             ChatMessage::user("hello".to_string()),
             ChatMessage::assistant("hi there".to_string()),
         ];
-        let dropped = proactive_trim_turns(&mut turns, 10_000);
+        let dropped = proactive_trim_turns(&mut turns, &HeuristicTokenizer, 10_000);
         assert_eq!(dropped, 0);
         assert_eq!(turns.len(), 2);
     }
@@ -4640,7 +4885,7 @@ mod tests {
     #[test]
     fn proactive_trim_preserves_last_turn_even_when_over_budget() {
         let mut turns = vec![ChatMessage::user("x".repeat(2000))];
-        let dropped = proactive_trim_turns(&mut turns, 100);
+        let dropped = proactive_trim_turns(&mut turns, &HeuristicTokenizer, 100);
         assert_eq!(dropped, 0, "single turn must never be dropped");
         assert_eq!(turns.len(), 1);
     }
@@ -4686,7 +4931,11 @@ mod tests {
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         };
 
         append_sender_turn(&ctx, &sender, ChatMessage::user("hello"));
@@ -4751,7 +5000,11 @@ mod tests {
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         };
 
         assert!(rollback_orphan_user_turn(&ctx, &sender, "pending"));
@@ -4835,7 +5088,11 @@ mod tests {
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         };
 
         assert!(rollback_orphan_user_turn(
@@ -5369,7 +5626,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -5382,6 +5643,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5442,7 +5704,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -5455,6 +5721,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5529,7 +5796,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -5542,6 +5813,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5601,7 +5873,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -5614,6 +5890,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5683,7 +5960,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -5696,6 +5977,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5785,7 +6067,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -5798,6 +6084,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5869,7 +6156,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -5882,6 +6173,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 3,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -5968,7 +6260,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -5981,6 +6277,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 4,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -6052,7 +6349,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -6065,6 +6366,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -6126,7 +6428,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -6139,6 +6445,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -6235,6 +6542,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 timestamp: "2026-02-20T00:00:00Z".to_string(),
                 session_id: None,
                 score: Some(0.9),
+                metadata: crate::memory::MemoryMetadata::default(),
             }])
         }
 
@@ -6311,7 +6619,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
@@ -6323,6 +6635,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         })
         .await
         .unwrap();
@@ -6334,6 +6647,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "test-channel".to_string(),
             timestamp: 2,
             thread_ts: None,
+            attachments: Vec::new(),
         })
         .await
         .unwrap();
@@ -6353,6 +6667,146 @@ BTC is currently around $65,000 based on latest tool output."#
         assert_eq!(sent_messages.len(), 2);
     }
 
+    #[tokio::test]
+    async fn message_dispatch_forwards_messages_to_channel_sops() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let mut engine = crate::sop::SopEngine::new(crate::config::SopConfig::default());
+        engine.set_sops_for_test(vec![crate::sop::Sop {
+            name: "deploy-request".into(),
+            description: "Deploy on request".into(),
+            version: "1.0.0".into(),
+            priority: crate::sop::SopPriority::Normal,
+            execution_mode: crate::sop::SopExecutionMode::Supervised,
+            triggers: vec![crate::sop::SopTrigger::Channel {
+                channel: Some("test-channel".into()),
+                sender: None,
+                pattern: Some(r"(?i)\bdeploy\b".into()),
+            }],
+            steps: vec![crate::sop::SopStep {
+                number: 1,
+                title: "Deploy".into(),
+                body: "Run the deploy".into(),
+                suggested_tools: vec![],
+                requires_confirmation: false,
+                condition: None,
+                goto: None,
+                on_failure: None,
+            }],
+            cooldown_secs: 0,
+            max_concurrent: 2,
+            max_step_visits: 3,
+            location: None,
+        }]);
+        let sop = Arc::new(crate::sop::SopRuntime {
+            engine: Arc::new(Mutex::new(engine)),
+            audit: Arc::new(crate::sop::SopAuditLogger::new(Arc::new(NoopMemory))),
+            collector: Arc::new(crate::sop::SopMetricsCollector::new()),
+        });
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(SlowProvider {
+                delay: Duration::from_millis(250),
+            }),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: InterruptOnNewMessageConfig {
+                telegram: false,
+                slack: false,
+            },
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_call_dedup_exempt: Arc::new(Vec::new()),
+            model_routes: Arc::new(Vec::new()),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            ack_reactions: true,
+            show_tool_calls: true,
+            session_store: None,
+            approval_manager: Arc::new(ApprovalManager::for_non_interactive(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            approval_broker: None,
+            activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: Some(Arc::clone(&sop)),
+        });
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
+        tx.send(traits::ChannelMessage {
+            id: "1".to_string(),
+            sender: "alice".to_string(),
+            reply_target: "alice".to_string(),
+            content: "please deploy now".to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+            attachments: Vec::new(),
+        })
+        .await
+        .unwrap();
+        tx.send(traits::ChannelMessage {
+            id: "2".to_string(),
+            sender: "bob".to_string(),
+            reply_target: "bob".to_string(),
+            content: "world".to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 2,
+            thread_ts: None,
+            attachments: Vec::new(),
+        })
+        .await
+        .unwrap();
+        drop(tx);
+
+        run_message_dispatch_loop(rx, runtime_ctx, 2).await;
+
+        // Dispatch runs in a detached task; only the matching message starts a run.
+        let deadline = Instant::now() + Duration::from_secs(2);
+        loop {
+            let runs: Vec<String> = sop
+                .engine
+                .lock()
+                .unwrap()
+                .active_runs()
+                .values()
+                .map(|run| run.sop_name.clone())
+                .collect();
+            if !runs.is_empty() {
+                assert_eq!(runs, vec!["deploy-request".to_string()]);
+                break;
+            }
+            assert!(Instant::now() < deadline, "channel SOP run never started");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let sent_messages = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent_messages.len(), 2);
+    }
+
     #[tokio::test]
     async fn message_dispatch_interrupts_in_flight_telegram_request_and_preserves_context() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -6404,7 +6858,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -6417,6 +6875,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -6429,6 +6888,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -6511,7 +6971,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
             query_classification: crate::config::QueryClassificationConfig::default(),
         });
 
@@ -6525,6 +6989,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "slack".to_string(),
                 timestamp: 1,
                 thread_ts: Some("1741234567.100001".to_string()),
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -6537,6 +7002,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "slack".to_string(),
                 timestamp: 2,
                 thread_ts: Some("1741234567.100001".to_string()),
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -6617,7 +7083,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(8);
@@ -6630,6 +7100,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -6642,6 +7113,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .unwrap();
@@ -6656,6 +7128,120 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(sent_messages.iter().any(|msg| msg.starts_with("chat-2:")));
     }
 
+    #[tokio::test]
+    async fn message_dispatch_routes_bound_senders_to_profile_context() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let base_ctx = ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::new(SlowProvider {
+                delay: Duration::from_millis(1),
+            }),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            interrupt_on_new_message: InterruptOnNewMessageConfig {
+                telegram: false,
+                slack: false,
+            },
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Vec::new()),
+            tool_call_dedup_exempt: Arc::new(Vec::new()),
+            model_routes: Arc::new(Vec::new()),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            ack_reactions: true,
+            show_tool_calls: true,
+            session_store: None,
+            approval_manager: Arc::new(ApprovalManager::for_non_interactive(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            approval_broker: None,
+            activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
+        };
+        let profile_ctx = Arc::new(ChannelRuntimeContext {
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            profile: Some(Arc::from("lisa")),
+            ..base_ctx.clone()
+        });
+        let bindings = HashMap::from([(
+            "lisa".to_string(),
+            crate::config::AgentProfileConfig {
+                workspace_dir: "profiles/lisa".into(),
+                senders: vec!["test-channel:alice".into()],
+                ..Default::default()
+            },
+        )]);
+        let root_ctx = Arc::new(ChannelRuntimeContext {
+            profile_routes: Some(Arc::new(ProfileRoutes {
+                bindings,
+                contexts: HashMap::from([("lisa".to_string(), Arc::clone(&profile_ctx))]),
+            })),
+            ..base_ctx
+        });
+
+        let alice = traits::ChannelMessage {
+            id: "1".to_string(),
+            sender: "alice".to_string(),
+            reply_target: "alice".to_string(),
+            content: "hello".to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+            attachments: Vec::new(),
+        };
+        let bob = traits::ChannelMessage {
+            id: "2".to_string(),
+            sender: "bob".to_string(),
+            reply_target: "bob".to_string(),
+            content: "world".to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 2,
+            thread_ts: None,
+            attachments: Vec::new(),
+        };
+        let alice_key = conversation_history_key(&alice);
+        let bob_key = conversation_history_key(&bob);
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(4);
+        tx.send(alice).await.unwrap();
+        tx.send(bob).await.unwrap();
+        drop(tx);
+        run_message_dispatch_loop(rx, Arc::clone(&root_ctx), 2).await;
+        assert_eq!(channel_impl.sent_messages.lock().await.len(), 2);
+
+        let profile_histories = profile_ctx.conversation_histories.lock().unwrap();
+        assert!(profile_histories.contains_key(&alice_key));
+        assert!(!profile_histories.contains_key(&bob_key));
+        drop(profile_histories);
+        let root_histories = root_ctx.conversation_histories.lock().unwrap();
+        assert!(root_histories.contains_key(&bob_key));
+        assert!(!root_histories.contains_key(&alice_key));
+    }
+
     #[tokio::test]
     async fn process_channel_message_cancels_scoped_typing_task() {
         let channel_impl = Arc::new(RecordingChannel::default());
@@ -6704,7 +7290,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -6717,6 +7307,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -6776,7 +7367,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -6789,6 +7384,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -6984,6 +7580,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                capabilities: None,
             }],
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
@@ -7022,6 +7619,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                capabilities: None,
             }],
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
@@ -7066,6 +7664,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell&exec".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                capabilities: None,
             }],
             prompts: vec!["Use <tool_call> and & keep output \"safe\"".into()],
             location: None,
@@ -7200,6 +7799,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         assert_eq!(conversation_memory_key(&msg), "slack_U123_msg_abc123");
@@ -7215,6 +7815,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: Some("1741234567.123456".into()),
+            attachments: Vec::new(),
         };
 
         assert_eq!(
@@ -7233,6 +7834,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "cli".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         assert_eq!(followup_thread_id(&msg).as_deref(), Some("msg_abc123"));
@@ -7248,6 +7850,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -7257,6 +7860,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         assert_ne!(
@@ -7278,6 +7882,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
@@ -7287,6 +7892,7 @@ BTC is currently around $65,000 based on latest tool output."#
             channel: "slack".into(),
             timestamp: 2,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         mem.store(
@@ -7414,7 +8020,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -7427,6 +8037,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -7442,6 +8053,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -7512,7 +8124,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -7525,6 +8141,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -7610,7 +8227,11 @@ BTC is currently around $65,000 based on latest tool output."#
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -7623,6 +8244,7 @@ BTC is currently around $65,000 based on latest tool output."#
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -7938,6 +8560,33 @@ This is an example JSON object for profile settings."#;
             .any(|entry| entry.channel.name() == "mattermost"));
     }
 
+    #[test]
+    fn collect_configured_channels_includes_mqtt_only_with_channel_section() {
+        let mut config = Config::default();
+        let mut mqtt = crate::config::MqttConfig {
+            broker_url: "mqtt://localhost:1883".to_string(),
+            client_id: "zeroclaw".to_string(),
+            topics: vec!["sensors/#".to_string()],
+            qos: 1,
+            username: None,
+            password: None,
+            use_tls: false,
+            keep_alive_secs: 30,
+            channel: None,
+        };
+        config.channels_config.mqtt = Some(mqtt.clone());
+        assert!(!collect_configured_channels(&config, "test")
+            .iter()
+            .any(|entry| entry.channel.name() == "mqtt"));
+
+        mqtt.channel = Some(mqtt::MqttChannelConfig::default());
+        config.channels_config.mqtt = Some(mqtt);
+        let channels = collect_configured_channels(&config, "test");
+        assert!(channels
+            .iter()
+            .any(|entry| entry.display_name == "MQTT" && entry.channel.name() == "mqtt"));
+    }
+
     struct AlwaysFailChannel {
         name: &'static str,
         calls: Arc<AtomicUsize>,
@@ -8172,7 +8821,11 @@ This is an example JSON object for profile settings."#;
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         // Simulate a photo attachment message with [IMAGE:] marker.
@@ -8186,6 +8839,7 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -8251,7 +8905,11 @@ This is an example JSON object for profile settings."#;
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -8264,6 +8922,7 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -8279,6 +8938,7 @@ This is an example JSON object for profile settings."#;
                 channel: "test-channel".to_string(),
                 timestamp: 2,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -8404,7 +9064,11 @@ This is an example JSON object for profile settings."#;
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -8417,6 +9081,7 @@ This is an example JSON object for profile settings."#;
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -8507,7 +9172,11 @@ This is an example JSON object for profile settings."#;
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -8520,6 +9189,7 @@ This is an example JSON object for profile settings."#;
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -8602,7 +9272,11 @@ This is an example JSON object for profile settings."#;
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -8615,6 +9289,7 @@ This is an example JSON object for profile settings."#;
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
@@ -8717,7 +9392,11 @@ This is an example JSON object for profile settings."#;
             approval_manager: Arc::new(ApprovalManager::for_non_interactive(
                 &crate::config::AutonomyConfig::default(),
             )),
+            approval_broker: None,
             activated_tools: None,
+            profile: None,
+            profile_routes: None,
+            sop: None,
         });
 
         process_channel_message(
@@ -8730,6 +9409,7 @@ This is an example JSON object for profile settings."#;
                 channel: "telegram".to_string(),
                 timestamp: 1,
                 thread_ts: None,
+                attachments: Vec::new(),
             },
             CancellationToken::new(),
         )
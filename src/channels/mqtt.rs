@@ -1,18 +1,32 @@
-//! MQTT → SOP event fan-in listener.
+//! MQTT integration.
 //!
-//! This is NOT a `Channel` trait implementor — it routes MQTT messages
-//! to the SOP engine via `dispatch_sop_event`, not to the chat loop.
+//! - [`run_mqtt_sop_listener`] is the SOP event fan-in: it routes MQTT
+//!   messages to the SOP engine via `dispatch_sop_event`, not to the chat loop.
+//! - [`MqttChannel`] is a conversational [`Channel`] for devices and
+//!   home-automation bridges that talk to the agent over MQTT 5.
+//!
+//! Both share the [`MqttConfig`] connection settings and its validation.
 
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use rumqttc::v5::{
+    self,
+    mqttbytes::v5::{LastWill, Packet as V5Packet, Publish, PublishProperties},
+};
 use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS, Transport};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 
+use super::traits::{Channel, ChannelMessage, SendMessage};
 use crate::config::MqttConfig;
 use crate::sop::audit::SopAuditLogger;
 use crate::sop::dispatch::{dispatch_sop_event, process_headless_results};
-use crate::sop::engine::{now_iso8601, SopEngine};
+use crate::sop::engine::{mqtt_topic_matches, now_iso8601, SopEngine};
 use crate::sop::types::{SopEvent, SopTriggerSource};
 
 /// Run the MQTT SOP listener loop.
@@ -51,12 +65,6 @@ pub async fn run_mqtt_sop_listener(
         _ => QoS::ExactlyOnce,
     };
 
-    // Subscribe to all configured topics
-    for topic in &config.topics {
-        client.subscribe(topic, qos).await?;
-        info!("MQTT SOP listener: subscribed to '{topic}'");
-    }
-
     crate::health::mark_component_ok("mqtt");
 
     loop {
@@ -73,11 +81,19 @@ pub async fn run_mqtt_sop_listener(
                 };
 
                 let results = dispatch_sop_event(&engine, &audit, event).await;
-                process_headless_results(&results).await;
+                process_headless_results(&results);
             }
-            Ok(Event::Incoming(Packet::ConnAck(_))) => {
+            Ok(Event::Incoming(Packet::ConnAck(connack))) => {
                 crate::health::mark_component_ok("mqtt");
                 info!("MQTT SOP listener: connected to broker");
+                // A clean session starts without subscriptions, including
+                // after every reconnect.
+                if !connack.session_present {
+                    for topic in &config.topics {
+                        client.subscribe(topic, qos).await?;
+                        info!("MQTT SOP listener: subscribed to '{topic}'");
+                    }
+                }
             }
             Ok(_) => {
                 // Other events (PingResp, SubAck, etc.) — ignore
@@ -91,6 +107,375 @@ pub async fn run_mqtt_sop_listener(
     }
 }
 
+/// Conversational settings for [`MqttChannel`] (`[channels_config.mqtt.channel]`),
+/// layered on the shared [`MqttConfig`] connection settings (broker,
+/// credentials, TLS, QoS).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MqttChannelConfig {
+    /// Topic filters carrying requests from devices. A single-level `+`
+    /// wildcard marks the device id, e.g. `zeroclaw/+/request`.
+    #[serde(default = "default_mqtt_request_topics")]
+    pub request_topics: Vec<String>,
+    /// Response topic for requests that carry none (MQTT 5 property or JSON).
+    /// `{device}` is replaced with the device id.
+    #[serde(default = "default_mqtt_response_topic")]
+    pub default_response_topic: String,
+    /// Retained topic carrying `online` / `offline` status for health checks.
+    #[serde(default = "default_mqtt_status_topic")]
+    pub status_topic: String,
+    /// Device ids allowed to talk to the agent. Empty denies all, `"*"` allows all.
+    #[serde(default)]
+    pub allowed_devices: Vec<String>,
+}
+
+fn default_mqtt_request_topics() -> Vec<String> {
+    vec!["zeroclaw/+/request".into()]
+}
+
+fn default_mqtt_response_topic() -> String {
+    "zeroclaw/{device}/response".into()
+}
+
+fn default_mqtt_status_topic() -> String {
+    "zeroclaw/status".into()
+}
+
+impl Default for MqttChannelConfig {
+    fn default() -> Self {
+        Self {
+            request_topics: default_mqtt_request_topics(),
+            default_response_topic: default_mqtt_response_topic(),
+            status_topic: default_mqtt_status_topic(),
+            allowed_devices: Vec::new(),
+        }
+    }
+}
+
+impl MqttChannelConfig {
+    pub fn validate(&self) -> Result<()> {
+        if self.request_topics.is_empty() {
+            bail!("mqtt channel: request_topics must contain at least one topic");
+        }
+        if self.request_topics.iter().any(|t| t.trim().is_empty()) {
+            bail!("mqtt channel: request_topics must not contain empty topics");
+        }
+        for (name, topic) in [
+            ("default_response_topic", &self.default_response_topic),
+            ("status_topic", &self.status_topic),
+        ] {
+            if !is_publish_topic(topic) {
+                bail!("mqtt channel: {name} must be a non-empty topic without wildcards");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A request decoded from an MQTT publish.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MqttRequest {
+    text: String,
+    correlation_id: Option<String>,
+    response_topic: Option<String>,
+}
+
+/// Decode a request payload.
+///
+/// JSON objects use `text` (or `message` / `content`) plus optional
+/// `correlation_id` and `response_topic`, which lets MQTT 3.1.1 devices ask
+/// for a reply topic too. Anything else is treated as plain UTF-8 text.
+fn parse_request(payload: &[u8]) -> Option<MqttRequest> {
+    let raw = String::from_utf8_lossy(payload);
+    if let Ok(serde_json::Value::Object(fields)) = serde_json::from_str(&raw) {
+        let field = |name: &str| {
+            fields
+                .get(name)
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let text = field("text")
+            .or_else(|| field("message"))
+            .or_else(|| field("content"))?;
+        return Some(MqttRequest {
+            text,
+            correlation_id: field("correlation_id"),
+            response_topic: field("response_topic"),
+        });
+    }
+
+    let text = raw.trim();
+    (!text.is_empty()).then(|| MqttRequest {
+        text: text.to_string(),
+        correlation_id: None,
+        response_topic: None,
+    })
+}
+
+/// Device id for `topic` under the first matching request filter: the level
+/// matched by the filter's first `+`, or the whole topic when it has none.
+fn device_from_topic(filters: &[String], topic: &str) -> Option<String> {
+    filters.iter().find_map(|filter| {
+        if !mqtt_topic_matches(filter, topic) {
+            return None;
+        }
+        let position = filter.split('/').position(|level| level == "+");
+        Some(match position {
+            Some(idx) => topic.split('/').nth(idx)?.to_string(),
+            None => topic.to_string(),
+        })
+    })
+}
+
+fn is_publish_topic(topic: &str) -> bool {
+    !topic.trim().is_empty() && !topic.contains(['+', '#'])
+}
+
+fn qos_from_level(level: u8) -> v5::mqttbytes::QoS {
+    match level {
+        0 => v5::mqttbytes::QoS::AtMostOnce,
+        1 => v5::mqttbytes::QoS::AtLeastOnce,
+        _ => v5::mqttbytes::QoS::ExactlyOnce,
+    }
+}
+
+fn status_payload(online: bool) -> String {
+    serde_json::json!({ "status": if online { "online" } else { "offline" } }).to_string()
+}
+
+/// Conversational MQTT channel.
+///
+/// Devices publish requests on `request_topics`; the agent's reply is
+/// published to the request's MQTT 5 response topic (or JSON
+/// `response_topic`), falling back to `default_response_topic` for the
+/// device. A retained `online` status is published on connect, with a
+/// retained `offline` last will.
+///
+/// Correlation ids are usually unique per request, so they stay out of the
+/// message's `thread_ts` (which keys conversation history) and history is
+/// kept per device. The channel remembers the latest request's correlation
+/// id per response topic and echoes it on every send to that topic, in the
+/// reply payload and, for MQTT 5, the correlation-data property.
+pub struct MqttChannel {
+    config: MqttConfig,
+    options: MqttChannelConfig,
+    client: tokio::sync::Mutex<Option<v5::AsyncClient>>,
+    connected: AtomicBool,
+    /// Response topic → correlation id of the latest request replying there.
+    correlations: parking_lot::Mutex<HashMap<String, String>>,
+}
+
+impl MqttChannel {
+    pub fn new(config: MqttConfig, options: MqttChannelConfig) -> Self {
+        Self {
+            config,
+            options,
+            client: tokio::sync::Mutex::new(None),
+            connected: AtomicBool::new(false),
+            correlations: parking_lot::Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn qos(&self) -> v5::mqttbytes::QoS {
+        qos_from_level(self.config.qos)
+    }
+
+    fn is_device_allowed(&self, device: &str) -> bool {
+        self.options
+            .allowed_devices
+            .iter()
+            .any(|allowed| allowed == "*" || allowed == device)
+    }
+
+    fn mqtt_options(&self) -> v5::MqttOptions {
+        // A distinct client id keeps the SOP listener's session alive on the broker.
+        let mut mqtt_options = v5::MqttOptions::new(
+            format!("{}-channel", self.config.client_id),
+            broker_host(&self.config.broker_url),
+            broker_port(&self.config.broker_url),
+        );
+        mqtt_options.set_keep_alive(std::time::Duration::from_secs(self.config.keep_alive_secs));
+        if let (Some(ref user), Some(ref pass)) = (&self.config.username, &self.config.password) {
+            mqtt_options.set_credentials(user, pass);
+        }
+        if self.config.use_tls {
+            mqtt_options.set_transport(Transport::tls_with_default_config());
+        }
+        mqtt_options.set_last_will(LastWill::new(
+            self.options.status_topic.clone(),
+            status_payload(false),
+            self.qos(),
+            true,
+            None,
+        ));
+        mqtt_options
+    }
+
+    /// Response topic for a request from `device`.
+    fn response_topic_for(&self, device: &str, request: &MqttRequest) -> String {
+        request
+            .response_topic
+            .as_deref()
+            .filter(|topic| is_publish_topic(topic))
+            .map_or_else(
+                || {
+                    self.options
+                        .default_response_topic
+                        .replace("{device}", device)
+                },
+                str::to_string,
+            )
+    }
+
+    /// Turn an incoming publish into a [`ChannelMessage`], applying the device allowlist.
+    fn to_channel_message(&self, publish: &Publish) -> Option<ChannelMessage> {
+        let topic = String::from_utf8_lossy(&publish.topic).to_string();
+        let device = device_from_topic(&self.options.request_topics, &topic)?;
+        if !self.is_device_allowed(&device) {
+            warn!("MQTT channel: ignoring request from unauthorized device '{device}'");
+            return None;
+        }
+
+        let mut request = parse_request(&publish.payload)?;
+        if let Some(props) = &publish.properties {
+            if let Some(topic) = &props.response_topic {
+                request.response_topic = Some(topic.clone());
+            }
+            if let Some(data) = &props.correlation_data {
+                match std::str::from_utf8(data) {
+                    Ok(id) => request.correlation_id = Some(id.to_string()),
+                    Err(_) => warn!("MQTT channel: ignoring non-UTF-8 correlation data"),
+                }
+            }
+        }
+
+        let reply_target = self.response_topic_for(&device, &request);
+        {
+            let mut correlations = self.correlations.lock();
+            match request.correlation_id {
+                Some(id) => correlations.insert(reply_target.clone(), id),
+                None => correlations.remove(&reply_target),
+            };
+        }
+
+        Some(ChannelMessage {
+            id: format!("mqtt_{}", uuid::Uuid::new_v4()),
+            reply_target,
+            sender: device,
+            content: request.text,
+            channel: "mqtt".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: None,
+            attachments: Vec::new(),
+        })
+    }
+}
+
+#[async_trait]
+impl Channel for MqttChannel {
+    fn name(&self) -> &str {
+        "mqtt"
+    }
+
+    async fn send(&self, message: &SendMessage) -> Result<()> {
+        let guard = self.client.lock().await;
+        let Some(client) = guard.as_ref() else {
+            bail!("MQTT channel is not connected");
+        };
+
+        let mut payload = serde_json::json!({ "text": message.content });
+        let mut properties = PublishProperties {
+            content_type: Some("application/json".into()),
+            ..PublishProperties::default()
+        };
+        let correlation_id = self.correlations.lock().get(&message.recipient).cloned();
+        if let Some(correlation_id) = correlation_id {
+            payload["correlation_id"] = serde_json::json!(correlation_id);
+            properties.correlation_data = Some(correlation_id.into_bytes().into());
+        }
+
+        client
+            .publish_with_properties(
+                &message.recipient,
+                self.qos(),
+                false,
+                payload.to_string(),
+                properties,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> Result<()> {
+        self.config.validate()?;
+        self.options.validate()?;
+
+        let (client, mut eventloop) = v5::AsyncClient::new(self.mqtt_options(), 64);
+        *self.client.lock().await = Some(client.clone());
+
+        loop {
+            match eventloop.poll().await {
+                Ok(v5::Event::Incoming(V5Packet::Publish(publish))) => {
+                    let Some(msg) = self.to_channel_message(&publish) else {
+                        continue;
+                    };
+                    if tx.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(v5::Event::Incoming(V5Packet::ConnAck(connack))) => {
+                    // rumqttc starts clean sessions, so the broker forgets
+                    // our subscriptions on every reconnect.
+                    if !connack.session_present {
+                        for topic in &self.options.request_topics {
+                            client.subscribe(topic, self.qos()).await?;
+                            info!("MQTT channel: subscribed to '{topic}'");
+                        }
+                    }
+                    self.connected.store(true, Ordering::Relaxed);
+                    crate::health::mark_component_ok("mqtt_channel");
+                    info!("MQTT channel: connected to broker");
+                    if let Err(e) = client
+                        .publish(
+                            &self.options.status_topic,
+                            self.qos(),
+                            true,
+                            status_payload(true),
+                        )
+                        .await
+                    {
+                        warn!("MQTT channel: failed to publish online status: {e}");
+                    }
+                }
+                Ok(_) => {
+                    // Other events (PingResp, SubAck, etc.) — ignore
+                }
+                Err(e) => {
+                    self.connected.store(false, Ordering::Relaxed);
+                    crate::health::mark_component_error("mqtt_channel", e.to_string());
+                    warn!("MQTT channel: connection error: {e}");
+                    // Back off before rumqttc reconnects on the next poll.
+                    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+                }
+            }
+        }
+
+        // The bus is closed; the broker publishes the retained `offline` will
+        // once the connection drops.
+        self.client.lock().await.take();
+        self.connected.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn health_check(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+}
+
 /// Extract host from broker URL like "mqtt://host:port"
 fn broker_host(url: &str) -> String {
     let without_scheme = url
@@ -134,6 +519,7 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            channel: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("qos must be 0, 1, or 2"));
@@ -150,6 +536,7 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            channel: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("mqtt://"));
@@ -166,11 +553,38 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            channel: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("at least one topic"));
     }
 
+    #[test]
+    fn mqtt_config_validation_allows_channel_without_sop_topics() {
+        let config = MqttConfig {
+            broker_url: "mqtt://localhost:1883".into(),
+            client_id: "zeroclaw".into(),
+            topics: vec![],
+            qos: 1,
+            username: None,
+            password: None,
+            use_tls: false,
+            keep_alive_secs: 30,
+            channel: Some(MqttChannelConfig::default()),
+        };
+        assert!(config.validate().is_ok());
+
+        let bad_channel = MqttConfig {
+            channel: Some(MqttChannelConfig {
+                default_response_topic: "zeroclaw/+/response".into(),
+                ..MqttChannelConfig::default()
+            }),
+            ..config
+        };
+        let err = bad_channel.validate().unwrap_err();
+        assert!(err.to_string().contains("default_response_topic"));
+    }
+
     #[test]
     fn mqtt_config_validation_rejects_empty_client_id() {
         let config = MqttConfig {
@@ -182,6 +596,7 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            channel: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("client_id must not be empty"));
@@ -198,6 +613,7 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            channel: None,
         };
         assert!(config.validate().is_ok());
     }
@@ -213,6 +629,7 @@ mod tests {
             password: None,
             use_tls: true,
             keep_alive_secs: 30,
+            channel: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("use_tls is true"));
@@ -229,6 +646,7 @@ mod tests {
             password: None,
             use_tls: false,
             keep_alive_secs: 30,
+            channel: None,
         };
         let err = config.validate().unwrap_err();
         assert!(err.to_string().contains("mqtts://"));
@@ -245,6 +663,7 @@ mod tests {
             password: None,
             use_tls: true,
             keep_alive_secs: 30,
+            channel: None,
         };
         assert!(config.validate().is_ok());
     }
@@ -273,4 +692,217 @@ mod tests {
     fn broker_port_defaults_8883_for_mqtts() {
         assert_eq!(broker_port("mqtts://secure.example.com"), 8883);
     }
+
+    fn channel(options: MqttChannelConfig) -> MqttChannel {
+        let config = MqttConfig {
+            broker_url: "mqtt://localhost:1883".into(),
+            client_id: "zeroclaw".into(),
+            topics: vec!["sensors/#".into()],
+            qos: 1,
+            username: None,
+            password: None,
+            use_tls: false,
+            keep_alive_secs: 30,
+            channel: None,
+        };
+        MqttChannel::new(config, options)
+    }
+
+    #[test]
+    fn mqtt_channel_config_validation() {
+        assert!(MqttChannelConfig::default().validate().is_ok());
+
+        let no_topics = MqttChannelConfig {
+            request_topics: vec![],
+            ..MqttChannelConfig::default()
+        };
+        assert!(no_topics
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("at least one topic"));
+
+        let wildcard_status = MqttChannelConfig {
+            status_topic: "zeroclaw/#".into(),
+            ..MqttChannelConfig::default()
+        };
+        assert!(wildcard_status
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("status_topic"));
+    }
+
+    #[test]
+    fn parse_request_accepts_json_and_plain_text() {
+        let json = parse_request(
+            br#"{"text": "lights off", "correlation_id": "c-1", "response_topic": "home/reply"}"#,
+        )
+        .unwrap();
+        assert_eq!(json.text, "lights off");
+        assert_eq!(json.correlation_id.as_deref(), Some("c-1"));
+        assert_eq!(json.response_topic.as_deref(), Some("home/reply"));
+
+        let plain = parse_request(b"  what is the temperature?  ").unwrap();
+        assert_eq!(plain.text, "what is the temperature?");
+        assert!(plain.correlation_id.is_none());
+
+        assert!(parse_request(b"   ").is_none());
+        assert!(parse_request(br#"{"value": 42}"#).is_none());
+    }
+
+    #[test]
+    fn device_from_topic_uses_plus_level() {
+        let filters = vec!["zeroclaw/+/request".to_string(), "bridge/hass".to_string()];
+        assert_eq!(
+            device_from_topic(&filters, "zeroclaw/kitchen/request").as_deref(),
+            Some("kitchen")
+        );
+        assert_eq!(
+            device_from_topic(&filters, "bridge/hass").as_deref(),
+            Some("bridge/hass")
+        );
+        assert!(device_from_topic(&filters, "zeroclaw/kitchen/other").is_none());
+    }
+
+    #[test]
+    fn response_topic_for_prefers_request_topic_and_rejects_wildcards() {
+        let ch = channel(MqttChannelConfig {
+            allowed_devices: vec!["kitchen".into()],
+            ..MqttChannelConfig::default()
+        });
+        let mut request = parse_request(b"hi").unwrap();
+        assert_eq!(
+            ch.response_topic_for("kitchen", &request),
+            "zeroclaw/kitchen/response"
+        );
+
+        request.response_topic = Some("devices/kitchen/inbox".into());
+        assert_eq!(
+            ch.response_topic_for("kitchen", &request),
+            "devices/kitchen/inbox"
+        );
+
+        request.response_topic = Some("devices/#".into());
+        assert_eq!(
+            ch.response_topic_for("kitchen", &request),
+            "zeroclaw/kitchen/response"
+        );
+
+        assert!(ch.is_device_allowed("kitchen"));
+        assert!(!ch.is_device_allowed("garage"));
+    }
+
+    #[test]
+    fn correlation_ids_stay_out_of_the_history_key() {
+        let ch = channel(MqttChannelConfig {
+            allowed_devices: vec!["kitchen".into()],
+            ..MqttChannelConfig::default()
+        });
+        let request = |correlation: &str| {
+            let properties = PublishProperties {
+                correlation_data: Some(correlation.as_bytes().to_vec().into()),
+                ..PublishProperties::default()
+            };
+            let publish = Publish::new(
+                "zeroclaw/kitchen/request",
+                v5::mqttbytes::QoS::AtLeastOnce,
+                "lights off",
+                Some(properties),
+            );
+            ch.to_channel_message(&publish).unwrap()
+        };
+
+        let first = request("c-1");
+        assert_eq!(first.thread_ts, None);
+        assert_eq!(
+            ch.correlations.lock().get("zeroclaw/kitchen/response"),
+            Some(&"c-1".to_string())
+        );
+        let second = request("c-2");
+        assert_eq!(
+            crate::channels::conversation_history_key(&first),
+            crate::channels::conversation_history_key(&second)
+        );
+        assert_eq!(
+            ch.correlations.lock().get("zeroclaw/kitchen/response"),
+            Some(&"c-2".to_string())
+        );
+
+        // A request without correlation data clears the stale id.
+        let plain = Publish::new(
+            "zeroclaw/kitchen/request",
+            v5::mqttbytes::QoS::AtLeastOnce,
+            "status?",
+            None,
+        );
+        ch.to_channel_message(&plain).unwrap();
+        assert!(ch.correlations.lock().is_empty());
+    }
+
+    /// Read one packet on a fake broker connection and return its first
+    /// (packet type) byte.
+    async fn read_packet(stream: &mut tokio::net::TcpStream) -> u8 {
+        use tokio::io::AsyncReadExt;
+        let packet_type = stream.read_u8().await.unwrap();
+        let mut remaining = 0usize;
+        let mut shift = 0;
+        loop {
+            let byte = stream.read_u8().await.unwrap();
+            remaining |= usize::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        let mut body = vec![0; remaining];
+        stream.read_exact(&mut body).await.unwrap();
+        packet_type
+    }
+
+    /// Accept a client, grant it a fresh session and wait for its SUBSCRIBE.
+    async fn accept_and_expect_subscribe(
+        listener: &tokio::net::TcpListener,
+    ) -> tokio::net::TcpStream {
+        use tokio::io::AsyncWriteExt;
+        let (mut stream, _) = listener.accept().await.unwrap();
+        assert_eq!(read_packet(&mut stream).await, 0x10, "expected CONNECT");
+        // CONNACK: session_present = 0, success, no properties.
+        stream
+            .write_all(&[0x20, 0x03, 0x00, 0x00, 0x00])
+            .await
+            .unwrap();
+        while read_packet(&mut stream).await != 0x82 {}
+        stream
+    }
+
+    #[tokio::test]
+    async fn listen_resubscribes_after_broker_reconnect() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mut ch = channel(MqttChannelConfig {
+            allowed_devices: vec!["*".into()],
+            ..MqttChannelConfig::default()
+        });
+        ch.config.broker_url = format!("mqtt://127.0.0.1:{port}");
+        ch.config.qos = 0;
+        let ch = Arc::new(ch);
+        let (tx, _rx) = tokio::sync::mpsc::channel(1);
+        let task = tokio::spawn({
+            let ch = Arc::clone(&ch);
+            async move { ch.listen(tx).await }
+        });
+
+        let timeout = std::time::Duration::from_secs(10);
+        let first = tokio::time::timeout(timeout, accept_and_expect_subscribe(&listener))
+            .await
+            .expect("SUBSCRIBE after the first CONNACK");
+        // The broker goes away; the new session has no subscriptions.
+        drop(first);
+        let _second = tokio::time::timeout(timeout, accept_and_expect_subscribe(&listener))
+            .await
+            .expect("SUBSCRIBE after reconnecting");
+        assert!(ch.health_check().await);
+        task.abort();
+    }
 }
@@ -360,6 +360,7 @@ impl Channel for NotionChannel {
                                 channel: "notion".into(),
                                 timestamp,
                                 thread_ts: None,
+                                attachments: Vec::new(),
                             })
                             .await
                             .is_err()
@@ -1,4 +1,8 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    ApprovalPrompt, Attachment, AttachmentKind, AttachmentSource, Channel, ChannelMessage,
+    SendMessage,
+};
+use crate::approval::ApprovalResponse;
 use anyhow::Context;
 use async_trait::async_trait;
 use base64::Engine as _;
@@ -184,6 +188,204 @@ impl SlackChannel {
             .map(ToOwned::to_owned)
     }
 
+    /// Block Kit layout for an approval prompt: summary plus Approve / Deny /
+    /// Always buttons whose values are approval callback payloads.
+    fn approval_blocks(prompt: &ApprovalPrompt) -> serde_json::Value {
+        let button = |label: &str, decision: ApprovalResponse, style: Option<&str>| {
+            let mut button = serde_json::json!({
+                "type": "button",
+                "text": { "type": "plain_text", "text": label },
+                "action_id": prompt.callback_data(decision),
+                "value": prompt.callback_data(decision),
+            });
+            if let Some(style) = style {
+                button["style"] = serde_json::json!(style);
+            }
+            button
+        };
+        serde_json::json!([
+            {
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": format!("{}\nExpires in {}s.", prompt.summary(), prompt.timeout_secs),
+                },
+            },
+            {
+                "type": "actions",
+                "elements": [
+                    button("Approve", ApprovalResponse::Yes, Some("primary")),
+                    button("Deny", ApprovalResponse::No, Some("danger")),
+                    button("Always", ApprovalResponse::Always, None),
+                ],
+            },
+        ])
+    }
+
+    /// Extract `(user_id, channel_id, callback_value, thread_ts)` from a
+    /// Socket Mode `block_actions` payload for an approval button.
+    fn parse_approval_action(
+        payload: &serde_json::Value,
+    ) -> Option<(String, String, String, Option<String>)> {
+        if payload.get("type").and_then(|v| v.as_str()) != Some("block_actions") {
+            return None;
+        }
+        let value = payload
+            .get("actions")
+            .and_then(|actions| actions.as_array())?
+            .iter()
+            .filter_map(|action| action.get("value").and_then(|v| v.as_str()))
+            .find(|value| value.starts_with(super::traits::APPROVAL_CALLBACK_PREFIX))?;
+        let user_id = payload
+            .get("user")
+            .and_then(|user| user.get("id"))
+            .and_then(|v| v.as_str())?;
+        let channel_id = payload
+            .get("channel")
+            .and_then(|channel| channel.get("id"))
+            .and_then(|v| v.as_str())?;
+        let thread_ts = payload
+            .get("message")
+            .and_then(|message| message.get("thread_ts"))
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+        Some((
+            user_id.to_string(),
+            channel_id.to_string(),
+            value.to_string(),
+            thread_ts,
+        ))
+    }
+
+    async fn post_chat_message(&self, body: &serde_json::Value) -> anyhow::Result<()> {
+        let resp = self
+            .http_client()
+            .post("https://slack.com/api/chat.postMessage")
+            .bearer_auth(&self.bot_token)
+            .json(body)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+
+        if !status.is_success() {
+            let sanitized = crate::providers::sanitize_api_error(&body);
+            anyhow::bail!("Slack chat.postMessage failed ({status}): {sanitized}");
+        }
+
+        // Slack returns 200 for most app-level errors; check JSON "ok" field
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
+            let err = parsed
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack chat.postMessage failed: {err}");
+        }
+
+        Ok(())
+    }
+
+    /// Read a Slack Web API response, failing on HTTP errors and `"ok": false`.
+    async fn slack_api_response(
+        method: &str,
+        resp: reqwest::Response,
+    ) -> anyhow::Result<serde_json::Value> {
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+        if !status.is_success() {
+            let sanitized = crate::providers::sanitize_api_error(&body);
+            anyhow::bail!("Slack {method} failed ({status}): {sanitized}");
+        }
+
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
+            let err = parsed
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack {method} failed: {err}");
+        }
+        Ok(parsed)
+    }
+
+    /// Upload a local file through Slack's external upload flow
+    /// (`files.getUploadURLExternal` → upload → `files.completeUploadExternal`)
+    /// and share it in `channel`, using the caption as the initial comment.
+    async fn upload_file(
+        &self,
+        channel: &str,
+        thread_ts: Option<&str>,
+        attachment: &Attachment,
+        path: &Path,
+    ) -> anyhow::Result<()> {
+        let bytes = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("Slack upload: failed to read {}", path.display()))?;
+        let file_name = attachment.display_name();
+
+        let resp = self
+            .http_client()
+            .post("https://slack.com/api/files.getUploadURLExternal")
+            .bearer_auth(&self.bot_token)
+            .form(&[
+                ("filename", file_name.clone()),
+                ("length", bytes.len().to_string()),
+            ])
+            .send()
+            .await?;
+        let ticket = Self::slack_api_response("files.getUploadURLExternal", resp).await?;
+        let upload_url = ticket
+            .get("upload_url")
+            .and_then(|value| value.as_str())
+            .context("Slack files.getUploadURLExternal returned no upload_url")?;
+        let file_id = ticket
+            .get("file_id")
+            .and_then(|value| value.as_str())
+            .context("Slack files.getUploadURLExternal returned no file_id")?;
+
+        let resp = self
+            .http_client()
+            .post(upload_url)
+            .header(
+                reqwest::header::CONTENT_TYPE,
+                attachment.mime_type_or_default(),
+            )
+            .body(bytes)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Slack file upload failed ({})", resp.status());
+        }
+
+        let mut complete = serde_json::json!({
+            "files": [{ "id": file_id, "title": file_name }],
+            "channel_id": channel,
+        });
+        if let Some(ts) = thread_ts {
+            complete["thread_ts"] = serde_json::json!(ts);
+        }
+        if let Some(caption) = &attachment.caption {
+            complete["initial_comment"] = serde_json::json!(caption);
+        }
+        let resp = self
+            .http_client()
+            .post("https://slack.com/api/files.completeUploadExternal")
+            .bearer_auth(&self.bot_token)
+            .json(&complete)
+            .send()
+            .await?;
+        Self::slack_api_response("files.completeUploadExternal", resp).await?;
+        Ok(())
+    }
+
     fn normalize_group_reply_allowed_sender_ids(sender_ids: Vec<String>) -> Vec<String> {
         let mut normalized = sender_ids
             .into_iter()
@@ -572,6 +774,34 @@ impl SlackChannel {
             .or_else(|| file.get("url_private").and_then(|value| value.as_str()))
     }
 
+    /// Typed attachments for the files on a Slack message. Sources are the
+    /// private download URLs, which need the bot token to fetch.
+    fn slack_file_attachments(message: &serde_json::Value) -> Vec<Attachment> {
+        let Some(files) = message.get("files").and_then(|value| value.as_array()) else {
+            return Vec::new();
+        };
+        files
+            .iter()
+            .take(SLACK_ATTACHMENT_MAX_FILES_PER_MESSAGE)
+            .filter_map(|file| {
+                let url = Self::slack_file_download_url(file)?;
+                let mime = Self::slack_file_mime(file);
+                let kind = mime
+                    .as_deref()
+                    .map_or(AttachmentKind::Document, AttachmentKind::from_mime);
+                let mut attachment =
+                    Attachment::from_url(kind, url).with_file_name(Self::slack_file_name(file));
+                if let Some(mime) = mime {
+                    attachment = attachment.with_mime_type(mime);
+                }
+                if let Some(size) = file.get("size").and_then(serde_json::Value::as_u64) {
+                    attachment = attachment.with_size(size);
+                }
+                Some(attachment)
+            })
+            .collect()
+    }
+
     fn slack_image_candidate_urls(file: &serde_json::Value) -> Vec<String> {
         let mut urls = Vec::new();
         let mut seen = HashSet::new();
@@ -1693,6 +1923,38 @@ impl SlackChannel {
                     tracing::warn!("Slack Socket Mode: received disconnect event");
                     break;
                 }
+                if envelope_type == "interactive" {
+                    let Some((user, channel_id, value, thread_ts)) = envelope
+                        .get("payload")
+                        .and_then(Self::parse_approval_action)
+                    else {
+                        continue;
+                    };
+                    if !self.is_user_allowed(&user) {
+                        tracing::warn!(
+                            "Slack: ignoring approval action from unauthorized user: {user}"
+                        );
+                        continue;
+                    }
+                    let sender = self.resolve_sender_identity(&user).await;
+                    let now = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .unwrap_or_default();
+                    let channel_msg = ChannelMessage {
+                        id: format!("slack_action_{channel_id}_{}", now.as_millis()),
+                        sender,
+                        reply_target: channel_id,
+                        content: value,
+                        channel: "slack".to_string(),
+                        timestamp: now.as_secs(),
+                        thread_ts,
+                        attachments: Vec::new(),
+                    };
+                    if tx.send(channel_msg).await.is_err() {
+                        return Ok(());
+                    }
+                    continue;
+                }
                 if envelope_type != "events_api" {
                     continue;
                 }
@@ -1776,6 +2038,7 @@ impl SlackChannel {
                         .unwrap_or_default()
                         .as_secs(),
                     thread_ts: Self::inbound_thread_ts(event, ts),
+                    attachments: Self::slack_file_attachments(event),
                 };
 
                 if tx.send(channel_msg).await.is_err() {
@@ -2143,48 +2406,76 @@ impl Channel for SlackChannel {
         "slack"
     }
 
-    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let mut body = serde_json::json!({
-            "channel": message.recipient,
-            "text": message.content
-        });
+    fn supports_attachments(&self) -> bool {
+        true
+    }
 
-        if let Some(ref ts) = message.thread_ts {
-            body["thread_ts"] = serde_json::json!(ts);
+    async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        // Local files are uploaded; remote URLs are posted as links Slack unfurls.
+        let mut text = message.content.clone();
+        let mut uploads = Vec::new();
+        for attachment in &message.attachments {
+            match &attachment.source {
+                AttachmentSource::Path(path) => uploads.push((attachment, path)),
+                AttachmentSource::Url(url) => {
+                    for line in attachment.caption.iter().chain(std::iter::once(url)) {
+                        if !text.is_empty() {
+                            text.push('\n');
+                        }
+                        text.push_str(line);
+                    }
+                }
+            }
         }
 
-        let resp = self
-            .http_client()
-            .post("https://slack.com/api/chat.postMessage")
-            .bearer_auth(&self.bot_token)
-            .json(&body)
-            .send()
-            .await?;
+        if !text.is_empty() || uploads.is_empty() {
+            let mut body = serde_json::json!({
+                "channel": message.recipient,
+                "text": text
+            });
 
-        let status = resp.status();
-        let body = resp
-            .text()
-            .await
-            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+            if let Some(ref ts) = message.thread_ts {
+                body["thread_ts"] = serde_json::json!(ts);
+            }
 
-        if !status.is_success() {
-            let sanitized = crate::providers::sanitize_api_error(&body);
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {sanitized}");
+            self.post_chat_message(&body).await?;
         }
 
-        // Slack returns 200 for most app-level errors; check JSON "ok" field
-        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
-        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
-            let err = parsed
-                .get("error")
-                .and_then(|e| e.as_str())
-                .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
+        for (attachment, path) in uploads {
+            self.upload_file(
+                &message.recipient,
+                message.thread_ts.as_deref(),
+                attachment,
+                path,
+            )
+            .await?;
         }
-
         Ok(())
     }
 
+    async fn send_approval_prompt(&self, prompt: &ApprovalPrompt) -> anyhow::Result<()> {
+        // Button presses only reach the bot over Socket Mode; otherwise fall
+        // back to a typed-reply prompt.
+        if self.configured_app_token().is_none() {
+            return self
+                .send(
+                    &SendMessage::new(prompt.text(), &prompt.recipient)
+                        .in_thread(prompt.thread_ts.clone()),
+                )
+                .await;
+        }
+
+        let mut body = serde_json::json!({
+            "channel": prompt.recipient,
+            "text": prompt.summary(),
+            "blocks": Self::approval_blocks(prompt),
+        });
+        if let Some(ref ts) = prompt.thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+        self.post_chat_message(&body).await
+    }
+
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         let bot_user_id = self.get_bot_user_id().await.unwrap_or_default();
         let scoped_channels = self.scoped_channel_ids();
@@ -2340,6 +2631,7 @@ impl Channel for SlackChannel {
                                 .unwrap_or_default()
                                 .as_secs(),
                             thread_ts: Self::inbound_thread_ts(msg, ts),
+                            attachments: Self::slack_file_attachments(msg),
                         };
 
                         if tx.send(channel_msg).await.is_err() {
@@ -2424,6 +2716,7 @@ impl Channel for SlackChannel {
                             .unwrap_or_default()
                             .as_secs(),
                         thread_ts: Some(thread_ts.clone()),
+                        attachments: Self::slack_file_attachments(reply),
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -2498,6 +2791,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn slack_file_attachments_are_typed_from_file_objects() {
+        let message = serde_json::json!({
+            "files": [
+                {
+                    "name": "report.pdf",
+                    "mimetype": "application/pdf",
+                    "size": 1234,
+                    "url_private_download": "https://files.slack.com/files-pri/T1-F1/download/report.pdf"
+                },
+                { "name": "clip.mp4", "mimetype": "video/mp4" }
+            ]
+        });
+        let attachments = SlackChannel::slack_file_attachments(&message);
+
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].kind, AttachmentKind::Document);
+        assert_eq!(attachments[0].file_name.as_deref(), Some("report.pdf"));
+        assert_eq!(attachments[0].size_bytes, Some(1234));
+        assert_eq!(
+            attachments[0].source,
+            AttachmentSource::Url(
+                "https://files.slack.com/files-pri/T1-F1/download/report.pdf".into()
+            )
+        );
+    }
+
     #[test]
     fn slack_group_reply_policy_applies_sender_overrides() {
         let ch = SlackChannel::new("xoxb-fake".into(), None, None, vec![], vec!["*".into()])
@@ -2922,6 +3242,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_approval_action_reads_block_actions_payload() {
+        let payload = serde_json::json!({
+            "type": "block_actions",
+            "user": { "id": "U123" },
+            "channel": { "id": "C456" },
+            "message": { "thread_ts": "1700000000.000100" },
+            "actions": [{ "action_id": "x", "value": "zc-approval:r1:no" }]
+        });
+        let (user, channel, value, thread_ts) =
+            SlackChannel::parse_approval_action(&payload).unwrap();
+        assert_eq!(user, "U123");
+        assert_eq!(channel, "C456");
+        assert_eq!(value, "zc-approval:r1:no");
+        assert_eq!(thread_ts.as_deref(), Some("1700000000.000100"));
+
+        let unrelated = serde_json::json!({
+            "type": "block_actions",
+            "user": { "id": "U123" },
+            "channel": { "id": "C456" },
+            "actions": [{ "value": "something-else" }]
+        });
+        assert!(SlackChannel::parse_approval_action(&unrelated).is_none());
+    }
+
+    #[test]
+    fn approval_blocks_carry_callback_values() {
+        let prompt = ApprovalPrompt {
+            request_id: "r1".into(),
+            tool_name: "shell".into(),
+            arguments_summary: "command: ls".into(),
+            recipient: "C456".into(),
+            thread_ts: None,
+            timeout_secs: 30,
+        };
+        let blocks = SlackChannel::approval_blocks(&prompt);
+        let buttons = blocks[1]["elements"].as_array().unwrap();
+        assert_eq!(buttons.len(), 3);
+        assert_eq!(buttons[0]["value"], "zc-approval:r1:yes");
+        assert_eq!(buttons[1]["style"], "danger");
+    }
+
     #[test]
     fn evaluate_health_enforces_socket_mode_probe_when_enabled() {
         assert!(!SlackChannel::evaluate_health(false, false, true));
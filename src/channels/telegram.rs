@@ -1,4 +1,7 @@
-use super::traits::{Channel, ChannelMessage, SendMessage};
+use super::traits::{
+    ApprovalPrompt, Attachment, AttachmentKind, Channel, ChannelMessage, SendMessage,
+};
+use crate::approval::ApprovalResponse;
 use crate::config::{Config, StreamMode};
 use crate::security::pairing::PairingGuard;
 use anyhow::Context;
@@ -154,6 +157,22 @@ impl TelegramAttachmentKind {
     }
 }
 
+impl From<&Attachment> for TelegramAttachment {
+    fn from(attachment: &Attachment) -> Self {
+        let kind = match attachment.kind {
+            AttachmentKind::Image => TelegramAttachmentKind::Image,
+            AttachmentKind::Document => TelegramAttachmentKind::Document,
+            AttachmentKind::Video => TelegramAttachmentKind::Video,
+            AttachmentKind::Audio => TelegramAttachmentKind::Audio,
+            AttachmentKind::Voice => TelegramAttachmentKind::Voice,
+        };
+        Self {
+            kind,
+            target: attachment.target(),
+        }
+    }
+}
+
 /// Check whether a file path has a recognized image extension.
 fn is_image_extension(path: &Path) -> bool {
     path.extension()
@@ -1063,6 +1082,18 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             content = format!("{quote}\n\n{content}");
         }
 
+        let kind = if is_image_extension(&local_path) {
+            AttachmentKind::Image
+        } else {
+            AttachmentKind::Document
+        };
+        let mut typed = Attachment::from_path(kind, &local_path)
+            .with_file_name(local_filename)
+            .with_size(file_data.len() as u64);
+        if let Some(caption) = attachment.caption {
+            typed = typed.with_caption(caption);
+        }
+
         Some(ChannelMessage {
             id: format!("telegram_{chat_id}_{message_id}"),
             sender: sender_identity,
@@ -1074,6 +1105,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: thread_id,
+            attachments: vec![typed],
         })
     }
 
@@ -1151,6 +1183,23 @@ Allowlist Telegram username (without '@') or numeric user ID.",
             }
         };
 
+        // Keep the voice note in the workspace so it travels as an attachment.
+        let mut attachments = Vec::new();
+        if let Some(workspace) = &self.workspace_dir {
+            let save_dir = workspace.join("telegram_files");
+            let local_path = save_dir.join(format!("voice_{chat_id}_{message_id}_{file_name}"));
+            let saved = match tokio::fs::create_dir_all(&save_dir).await {
+                Ok(()) => tokio::fs::write(&local_path, &audio_data).await,
+                Err(e) => Err(e),
+            };
+            match saved {
+                Ok(()) => {
+                    attachments.push(Attachment::from_path(AttachmentKind::Voice, &local_path));
+                }
+                Err(e) => tracing::warn!("Failed to cache voice note: {e}"),
+            }
+        }
+
         let text =
             match super::transcription::transcribe_audio(audio_data, &file_name, config).await {
                 Ok(t) => t,
@@ -1191,6 +1240,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: thread_id,
+            attachments,
         })
     }
 
@@ -1347,6 +1397,7 @@ Allowlist Telegram username (without '@') or numeric user ID.",
                 .unwrap_or_default()
                 .as_secs(),
             thread_ts: thread_id,
+            attachments: Vec::new(),
         })
     }
 
@@ -1684,29 +1735,30 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         chat_id: &str,
         thread_id: Option<&str>,
         attachment: &TelegramAttachment,
+        caption: Option<&str>,
     ) -> anyhow::Result<()> {
         let target = attachment.target.trim();
 
         if is_http_url(target) {
             let result = match attachment.kind {
                 TelegramAttachmentKind::Image => {
-                    self.send_photo_by_url(chat_id, thread_id, target, None)
+                    self.send_photo_by_url(chat_id, thread_id, target, caption)
                         .await
                 }
                 TelegramAttachmentKind::Document => {
-                    self.send_document_by_url(chat_id, thread_id, target, None)
+                    self.send_document_by_url(chat_id, thread_id, target, caption)
                         .await
                 }
                 TelegramAttachmentKind::Video => {
-                    self.send_video_by_url(chat_id, thread_id, target, None)
+                    self.send_video_by_url(chat_id, thread_id, target, caption)
                         .await
                 }
                 TelegramAttachmentKind::Audio => {
-                    self.send_audio_by_url(chat_id, thread_id, target, None)
+                    self.send_audio_by_url(chat_id, thread_id, target, caption)
                         .await
                 }
                 TelegramAttachmentKind::Voice => {
-                    self.send_voice_by_url(chat_id, thread_id, target, None)
+                    self.send_voice_by_url(chat_id, thread_id, target, caption)
                         .await
                 }
             };
@@ -1756,13 +1808,21 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         }
 
         match attachment.kind {
-            TelegramAttachmentKind::Image => self.send_photo(chat_id, thread_id, path, None).await,
+            TelegramAttachmentKind::Image => {
+                self.send_photo(chat_id, thread_id, path, caption).await
+            }
             TelegramAttachmentKind::Document => {
-                self.send_document(chat_id, thread_id, path, None).await
+                self.send_document(chat_id, thread_id, path, caption).await
+            }
+            TelegramAttachmentKind::Video => {
+                self.send_video(chat_id, thread_id, path, caption).await
+            }
+            TelegramAttachmentKind::Audio => {
+                self.send_audio(chat_id, thread_id, path, caption).await
+            }
+            TelegramAttachmentKind::Voice => {
+                self.send_voice(chat_id, thread_id, path, caption).await
             }
-            TelegramAttachmentKind::Video => self.send_video(chat_id, thread_id, path, None).await,
-            TelegramAttachmentKind::Audio => self.send_audio(chat_id, thread_id, path, None).await,
-            TelegramAttachmentKind::Voice => self.send_voice(chat_id, thread_id, path, None).await,
         }
     }
 
@@ -2173,6 +2233,94 @@ Allowlist Telegram username (without '@') or numeric user ID.",
         self.send_media_by_url("sendVoice", "voice", chat_id, thread_id, url, caption)
             .await
     }
+
+    /// Inline keyboard with Approve / Deny / Always buttons for a prompt.
+    fn approval_keyboard(prompt: &ApprovalPrompt) -> serde_json::Value {
+        serde_json::json!({
+            "inline_keyboard": [[
+                {
+                    "text": "\u{2705} Approve",
+                    "callback_data": prompt.callback_data(ApprovalResponse::Yes),
+                },
+                {
+                    "text": "\u{274C} Deny",
+                    "callback_data": prompt.callback_data(ApprovalResponse::No),
+                },
+                {
+                    "text": "Always",
+                    "callback_data": prompt.callback_data(ApprovalResponse::Always),
+                },
+            ]]
+        })
+    }
+
+    /// Turn an approval button press into a message from the pressing user,
+    /// addressed like a text reply in the same chat. Returns the callback
+    /// query id alongside so the press can be acknowledged.
+    fn parse_approval_callback_query(
+        &self,
+        update: &serde_json::Value,
+    ) -> Option<(String, ChannelMessage)> {
+        let query = update.get("callback_query")?;
+        let data = query.get("data").and_then(serde_json::Value::as_str)?;
+        if !data.starts_with(super::traits::APPROVAL_CALLBACK_PREFIX) {
+            return None;
+        }
+        let callback_id = query.get("id").and_then(serde_json::Value::as_str)?;
+
+        let (username, sender_id, sender_identity) = Self::extract_sender_info(query);
+        let mut identities = vec![username.as_str()];
+        if let Some(id) = sender_id.as_deref() {
+            identities.push(id);
+        }
+        if !self.is_any_user_allowed(identities.iter().copied()) {
+            return None;
+        }
+
+        let message = query.get("message")?;
+        let chat_id = message
+            .get("chat")
+            .and_then(|chat| chat.get("id"))
+            .and_then(serde_json::Value::as_i64)?;
+        let thread_id = message
+            .get("message_thread_id")
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string());
+        let reply_target = match &thread_id {
+            Some(tid) => format!("{chat_id}:{tid}"),
+            None => chat_id.to_string(),
+        };
+
+        Some((
+            callback_id.to_string(),
+            ChannelMessage {
+                id: format!("telegram_callback_{callback_id}"),
+                sender: sender_identity,
+                reply_target,
+                content: data.to_string(),
+                channel: "telegram".to_string(),
+                timestamp: std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                thread_ts: thread_id,
+                attachments: Vec::new(),
+            },
+        ))
+    }
+
+    async fn answer_callback_query(&self, callback_id: &str) {
+        let body = serde_json::json!({ "callback_query_id": callback_id });
+        if let Err(e) = self
+            .http_client()
+            .post(self.api_url("answerCallbackQuery"))
+            .json(&body)
+            .send()
+            .await
+        {
+            tracing::debug!("Telegram answerCallbackQuery failed: {e}");
+        }
+    }
 }
 
 #[async_trait]
@@ -2185,6 +2333,10 @@ impl Channel for TelegramChannel {
         self.stream_mode != StreamMode::Off
     }
 
+    fn supports_attachments(&self) -> bool {
+        true
+    }
+
     async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
         if self.stream_mode == StreamMode::Off {
             return Ok(None);
@@ -2347,7 +2499,7 @@ impl Channel for TelegramChannel {
 
             // Send attachments
             for attachment in &attachments {
-                self.send_attachment(&chat_id, thread_id.as_deref(), attachment)
+                self.send_attachment(&chat_id, thread_id.as_deref(), attachment, None)
                     .await?;
             }
 
@@ -2503,21 +2655,31 @@ impl Channel for TelegramChannel {
 
         let (text_without_markers, attachments) = parse_attachment_markers(&content);
 
-        if !attachments.is_empty() {
+        if !attachments.is_empty() || !message.attachments.is_empty() {
             if !text_without_markers.is_empty() {
                 self.send_text_chunks(&text_without_markers, chat_id, thread_id)
                     .await?;
             }
 
             for attachment in &attachments {
-                self.send_attachment(chat_id, thread_id, attachment).await?;
+                self.send_attachment(chat_id, thread_id, attachment, None)
+                    .await?;
+            }
+            for attachment in &message.attachments {
+                self.send_attachment(
+                    chat_id,
+                    thread_id,
+                    &TelegramAttachment::from(attachment),
+                    attachment.caption.as_deref(),
+                )
+                .await?;
             }
 
             return Ok(());
         }
 
         if let Some(attachment) = parse_path_only_attachment(&content) {
-            self.send_attachment(chat_id, thread_id, &attachment)
+            self.send_attachment(chat_id, thread_id, &attachment, None)
                 .await?;
             return Ok(());
         }
@@ -2544,7 +2706,7 @@ impl Channel for TelegramChannel {
             let probe = serde_json::json!({
                 "offset": offset,
                 "timeout": 0,
-                "allowed_updates": ["message"]
+                "allowed_updates": ["message", "callback_query"]
             });
             match self.http_client().post(&url).json(&probe).send().await {
                 Err(e) => {
@@ -2617,7 +2779,7 @@ impl Channel for TelegramChannel {
             let body = serde_json::json!({
                 "offset": offset,
                 "timeout": 30,
-                "allowed_updates": ["message"]
+                "allowed_updates": ["message", "callback_query"]
             });
 
             let resp = match self.http_client().post(&url).json(&body).send().await {
@@ -2678,6 +2840,14 @@ Ensure only one `zeroclaw` process is using this bot token."
                         offset = uid + 1;
                     }
 
+                    if let Some((callback_id, msg)) = self.parse_approval_callback_query(update) {
+                        self.answer_callback_query(&callback_id).await;
+                        if tx.send(msg).await.is_err() {
+                            return Ok(());
+                        }
+                        continue;
+                    }
+
                     let msg = if let Some(m) = self.parse_update_message(update) {
                         m
                     } else if let Some(m) = self.try_parse_voice_message(update).await {
@@ -2771,6 +2941,31 @@ Ensure only one `zeroclaw` process is using this bot token."
         }
         Ok(())
     }
+
+    async fn send_approval_prompt(&self, prompt: &ApprovalPrompt) -> anyhow::Result<()> {
+        let (chat_id, thread_id) = Self::parse_reply_target(&prompt.recipient);
+        let mut body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": format!("{}\n\nExpires in {}s.", prompt.summary(), prompt.timeout_secs),
+            "reply_markup": Self::approval_keyboard(prompt),
+        });
+        if let Some(tid) = thread_id {
+            body["message_thread_id"] = serde_json::Value::String(tid);
+        }
+
+        let resp = self
+            .http_client()
+            .post(self.api_url("sendMessage"))
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Telegram sendMessage (approval prompt) failed ({status}): {err}");
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -2814,6 +3009,50 @@ mod tests {
         assert_eq!(target, Some(("-100123456".to_string(), 99)));
     }
 
+    #[test]
+    fn telegram_approval_callback_query_becomes_channel_message() {
+        let ch = TelegramChannel::new("fake-token".into(), vec!["alice".into()], false);
+        let update = serde_json::json!({
+            "update_id": 2,
+            "callback_query": {
+                "id": "cbq-1",
+                "from": { "id": 42, "username": "alice" },
+                "data": "zc-approval:abc:always",
+                "message": {
+                    "message_id": 7,
+                    "chat": { "id": -100_123 },
+                    "message_thread_id": 5
+                }
+            }
+        });
+
+        let (callback_id, msg) = ch.parse_approval_callback_query(&update).unwrap();
+        assert_eq!(callback_id, "cbq-1");
+        assert_eq!(msg.sender, "alice");
+        assert_eq!(msg.reply_target, "-100123:5");
+        assert_eq!(msg.content, "zc-approval:abc:always");
+
+        let stranger = TelegramChannel::new("fake-token".into(), vec!["bob".into()], false);
+        assert!(stranger.parse_approval_callback_query(&update).is_none());
+    }
+
+    #[test]
+    fn telegram_approval_keyboard_carries_callback_data() {
+        let prompt = ApprovalPrompt {
+            request_id: "r1".into(),
+            tool_name: "shell".into(),
+            arguments_summary: String::new(),
+            recipient: "123".into(),
+            thread_ts: None,
+            timeout_secs: 60,
+        };
+        let keyboard = TelegramChannel::approval_keyboard(&prompt);
+        let row = keyboard["inline_keyboard"][0].as_array().unwrap();
+        assert_eq!(row.len(), 3);
+        assert_eq!(row[0]["callback_data"], "zc-approval:r1:yes");
+        assert_eq!(row[2]["callback_data"], "zc-approval:r1:always");
+    }
+
     #[test]
     fn typing_handle_starts_as_none() {
         let ch = TelegramChannel::new("fake-token".into(), vec!["*".into()], false);
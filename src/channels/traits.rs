@@ -1,5 +1,9 @@
+use crate::approval::ApprovalResponse;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::fmt::Write;
+use std::path::PathBuf;
 
 /// Structured data parts that may accompany a channel message.
 ///
@@ -13,6 +17,235 @@ pub enum DataPart {
     A2web { url: String, id: String, title: String },
 }
 
+/// What kind of file an [`Attachment`] carries.
+///
+/// Mirrors the `[IMAGE:...]`, `[DOCUMENT:...]`, `[VIDEO:...]`, `[AUDIO:...]`
+/// and `[VOICE:...]` reply markers the agent is prompted to emit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    Image,
+    Document,
+    Video,
+    Audio,
+    Voice,
+}
+
+impl AttachmentKind {
+    /// Parse a reply marker name (`IMAGE`, `PHOTO`, `FILE`, ...), case-insensitively.
+    pub fn from_marker(marker: &str) -> Option<Self> {
+        match marker.trim().to_ascii_uppercase().as_str() {
+            "IMAGE" | "PHOTO" => Some(Self::Image),
+            "DOCUMENT" | "FILE" => Some(Self::Document),
+            "VIDEO" => Some(Self::Video),
+            "AUDIO" => Some(Self::Audio),
+            "VOICE" => Some(Self::Voice),
+            _ => None,
+        }
+    }
+
+    /// Canonical marker name, e.g. `IMAGE`.
+    pub fn marker_name(self) -> &'static str {
+        match self {
+            Self::Image => "IMAGE",
+            Self::Document => "DOCUMENT",
+            Self::Video => "VIDEO",
+            Self::Audio => "AUDIO",
+            Self::Voice => "VOICE",
+        }
+    }
+
+    /// Classify a MIME type. Anything that is not image, video or audio is a document.
+    pub fn from_mime(mime: &str) -> Self {
+        let mime = mime.trim().to_ascii_lowercase();
+        if mime.starts_with("image/") {
+            Self::Image
+        } else if mime.starts_with("video/") {
+            Self::Video
+        } else if mime.starts_with("audio/ogg") || mime.starts_with("audio/opus") {
+            Self::Voice
+        } else if mime.starts_with("audio/") {
+            Self::Audio
+        } else {
+            Self::Document
+        }
+    }
+}
+
+/// Where the bytes of an [`Attachment`] live.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentSource {
+    /// A file in the local cache (usually under the workspace directory).
+    Path(PathBuf),
+    /// A remote `http(s)` URL.
+    Url(String),
+}
+
+/// A file carried by a [`ChannelMessage`] or [`SendMessage`].
+///
+/// Serialized flat: `{"kind": "image", "url": "https://...", "mime_type": ...}`
+/// or `{"kind": "document", "path": "/workspace/report.pdf", ...}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub kind: AttachmentKind,
+    #[serde(flatten)]
+    pub source: AttachmentSource,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+}
+
+impl Attachment {
+    /// Attachment backed by a local file. File name, MIME type and size are
+    /// filled in from the path when available.
+    pub fn from_path(kind: AttachmentKind, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        Self {
+            kind,
+            mime_type: mime_guess::from_path(&path).first().map(|m| m.to_string()),
+            file_name: path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(str::to_string),
+            size_bytes: std::fs::metadata(&path).ok().map(|meta| meta.len()),
+            caption: None,
+            source: AttachmentSource::Path(path),
+        }
+    }
+
+    /// Attachment backed by a remote URL. File name and MIME type are guessed
+    /// from the URL path.
+    pub fn from_url(kind: AttachmentKind, url: impl Into<String>) -> Self {
+        let url = url.into();
+        let url_path = url.split(['?', '#']).next().unwrap_or(&url);
+        let file_name = url_path
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty() && !name.contains(':'))
+            .map(str::to_string);
+        Self {
+            kind,
+            mime_type: file_name
+                .as_deref()
+                .and_then(|name| mime_guess::from_path(name).first())
+                .map(|m| m.to_string()),
+            file_name,
+            size_bytes: None,
+            caption: None,
+            source: AttachmentSource::Url(url),
+        }
+    }
+
+    /// Build an attachment from a marker target: `http(s)` URLs stay remote,
+    /// anything else (optionally `file://`-prefixed) is a local path.
+    pub fn from_target(kind: AttachmentKind, target: &str) -> Self {
+        let target = target.trim();
+        if target.starts_with("http://") || target.starts_with("https://") {
+            Self::from_url(kind, target)
+        } else {
+            Self::from_path(kind, target.strip_prefix("file://").unwrap_or(target))
+        }
+    }
+
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.mime_type = Some(mime_type.into());
+        self
+    }
+
+    pub fn with_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    pub fn with_size(mut self, size_bytes: u64) -> Self {
+        self.size_bytes = Some(size_bytes);
+        self
+    }
+
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        let caption = caption.into();
+        self.caption = (!caption.trim().is_empty()).then_some(caption);
+        self
+    }
+
+    /// The local path or URL as a string.
+    pub fn target(&self) -> String {
+        match &self.source {
+            AttachmentSource::Path(path) => path.display().to_string(),
+            AttachmentSource::Url(url) => url.clone(),
+        }
+    }
+
+    /// MIME type, falling back to `application/octet-stream`.
+    pub fn mime_type_or_default(&self) -> &str {
+        self.mime_type
+            .as_deref()
+            .unwrap_or("application/octet-stream")
+    }
+
+    /// File name, falling back to the last segment of the target.
+    pub fn display_name(&self) -> String {
+        self.file_name.clone().unwrap_or_else(|| {
+            let target = self.target();
+            target
+                .rsplit(['/', '\\'])
+                .next()
+                .filter(|name| !name.is_empty())
+                .unwrap_or("attachment")
+                .to_string()
+        })
+    }
+
+    /// Reply-marker form, e.g. `[IMAGE:/workspace/chart.png]`.
+    pub fn marker(&self) -> String {
+        format!("[{}:{}]", self.kind.marker_name(), self.target())
+    }
+}
+
+/// Split `[KIND:target]` attachment markers out of `content`.
+///
+/// Returns the text with recognised markers removed (trimmed) and the typed
+/// attachments in order. Brackets that are not attachment markers are kept.
+pub fn split_attachment_markers(content: &str) -> (String, Vec<Attachment>) {
+    let mut cleaned = String::with_capacity(content.len());
+    let mut attachments = Vec::new();
+    let mut cursor = 0;
+
+    while let Some(open_rel) = content[cursor..].find('[') {
+        let open = cursor + open_rel;
+        cleaned.push_str(&content[cursor..open]);
+
+        let Some(close_rel) = content[open..].find(']') else {
+            cleaned.push_str(&content[open..]);
+            cursor = content.len();
+            break;
+        };
+        let close = open + close_rel;
+
+        let parsed = content[open + 1..close]
+            .split_once(':')
+            .and_then(|(kind, target)| {
+                let kind = AttachmentKind::from_marker(kind)?;
+                let target = target.trim();
+                (!target.is_empty()).then(|| Attachment::from_target(kind, target))
+            });
+        match parsed {
+            Some(attachment) => attachments.push(attachment),
+            None => cleaned.push_str(&content[open..=close]),
+        }
+        cursor = close + 1;
+    }
+    cleaned.push_str(&content[cursor..]);
+
+    (cleaned.trim().to_string(), attachments)
+}
+
 /// A message received from or sent to a channel
 #[derive(Debug, Clone)]
 pub struct ChannelMessage {
@@ -25,6 +258,52 @@ pub struct ChannelMessage {
     /// Platform thread identifier (e.g. Slack `ts`, Discord thread ID).
     /// When set, replies should be posted as threaded responses.
     pub thread_ts: Option<String>,
+    /// Files carried by the message. `content` keeps whatever text references
+    /// the channel already produced (e.g. `[IMAGE:/path]`);
+    /// [`ChannelMessage::agent_content`] adds the rest for the agent.
+    pub attachments: Vec<Attachment>,
+}
+
+impl ChannelMessage {
+    /// Text the agent sees for this message: `content` followed by one line
+    /// per attachment (name, MIME type, size, location, caption). Images that
+    /// `content` does not already reference get an `[IMAGE:...]` marker so
+    /// the multimodal pipeline loads them.
+    pub fn agent_content(&self) -> String {
+        if self.attachments.is_empty() {
+            return self.content.clone();
+        }
+
+        let mut text = self.content.trim_end().to_string();
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        text.push_str("[Attachments]");
+        for attachment in &self.attachments {
+            let _ = write!(
+                text,
+                "\n- {} ({}",
+                attachment.display_name(),
+                attachment.mime_type_or_default()
+            );
+            if let Some(size) = attachment.size_bytes {
+                let _ = write!(text, ", {size} bytes");
+            }
+            let _ = write!(text, "): {}", attachment.target());
+            if let Some(caption) = &attachment.caption {
+                let _ = write!(text, " \"{caption}\"");
+            }
+        }
+        for attachment in &self.attachments {
+            if attachment.kind == AttachmentKind::Image
+                && !self.content.contains(&attachment.target())
+            {
+                text.push('\n');
+                text.push_str(&attachment.marker());
+            }
+        }
+        text
+    }
 }
 
 /// Message to send through a channel
@@ -38,6 +317,9 @@ pub struct SendMessage {
     /// Optional structured data parts (e.g. A2UI cards, a2web payloads).
     /// Only consumed by channels that declare `supports_a2ui() == true`.
     pub data: Option<Vec<DataPart>>,
+    /// Files to deliver natively. Only consumed by channels that declare
+    /// `supports_attachments() == true`.
+    pub attachments: Vec<Attachment>,
 }
 
 impl SendMessage {
@@ -49,6 +331,7 @@ impl SendMessage {
             subject: None,
             thread_ts: None,
             data: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -64,6 +347,7 @@ impl SendMessage {
             subject: Some(subject.into()),
             thread_ts: None,
             data: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -73,6 +357,11 @@ impl SendMessage {
         self
     }
 
+    /// Attach files to deliver natively alongside the text.
+    pub fn with_attachments(mut self, attachments: Vec<Attachment>) -> Self {
+        self.attachments = attachments;
+        self
+    }
 
     /// Set the thread identifier for threaded replies.
     pub fn in_thread(mut self, thread_ts: Option<String>) -> Self {
@@ -81,6 +370,70 @@ impl SendMessage {
     }
 }
 
+/// Prefix of the payload carried by approval buttons and cards.
+///
+/// Button callbacks are delivered back through `listen()` as a regular
+/// [`ChannelMessage`] whose content is `zc-approval:<request_id>:<decision>`.
+pub const APPROVAL_CALLBACK_PREFIX: &str = "zc-approval:";
+
+/// An interactive tool-approval prompt addressed to the sender whose
+/// message triggered the tool call.
+#[derive(Debug, Clone)]
+pub struct ApprovalPrompt {
+    /// Correlation id echoed back in button callbacks.
+    pub request_id: String,
+    pub tool_name: String,
+    /// Short human-readable summary of the tool arguments.
+    pub arguments_summary: String,
+    pub recipient: String,
+    pub thread_ts: Option<String>,
+    /// Seconds before the prompt expires and the default decision applies.
+    pub timeout_secs: u64,
+}
+
+impl ApprovalPrompt {
+    /// Headline and argument summary shared by every rendering.
+    pub fn summary(&self) -> String {
+        if self.arguments_summary.is_empty() {
+            format!("\u{1F527} Approval needed to run `{}`", self.tool_name)
+        } else {
+            format!(
+                "\u{1F527} Approval needed to run `{}`\n{}",
+                self.tool_name, self.arguments_summary
+            )
+        }
+    }
+
+    /// Plain-text rendering for channels without buttons.
+    pub fn text(&self) -> String {
+        format!(
+            "{}\n\nReply `yes`, `no`, or `always` within {}s.",
+            self.summary(),
+            self.timeout_secs
+        )
+    }
+
+    /// Callback payload for a button that answers with `decision`.
+    pub fn callback_data(&self, decision: ApprovalResponse) -> String {
+        let keyword = match decision {
+            ApprovalResponse::Yes => "yes",
+            ApprovalResponse::No => "no",
+            ApprovalResponse::Always => "always",
+        };
+        format!("{APPROVAL_CALLBACK_PREFIX}{}:{keyword}", self.request_id)
+    }
+}
+
+/// Parse a button callback payload into `(request_id, decision)`.
+pub fn parse_approval_callback(content: &str) -> Option<(&str, ApprovalResponse)> {
+    let rest = content.trim().strip_prefix(APPROVAL_CALLBACK_PREFIX)?;
+    let (request_id, decision) = rest.rsplit_once(':')?;
+    if request_id.is_empty() {
+        return None;
+    }
+    Some((request_id, ApprovalResponse::parse_reply(decision)?))
+}
+
 /// Core channel trait — implement for any messaging platform
 #[async_trait]
 pub trait Channel: Send + Sync {
@@ -108,6 +461,16 @@ pub trait Channel: Send + Sync {
         false
     }
 
+    /// Whether this channel delivers [`SendMessage::attachments`] natively.
+    ///
+    /// When true, `process_channel_message` moves `[IMAGE:...]`-style markers
+    /// out of the reply text into typed [`Attachment`]s and sends them, along
+    /// with files tools queued via `tools::attach_to_reply`, with the reply.
+    /// Otherwise markers are left in the text for the channel to handle.
+    fn supports_attachments(&self) -> bool {
+        false
+    }
+
     /// Signal that the bot is processing a response (e.g. "typing" indicator).
     /// Implementations should repeat the indicator as needed for their platform.
     async fn start_typing(&self, _recipient: &str) -> anyhow::Result<()> {
@@ -187,6 +550,18 @@ pub trait Channel: Send + Sync {
     async fn unpin_message(&self, _channel_id: &str, _message_id: &str) -> anyhow::Result<()> {
         Ok(())
     }
+
+    /// Ask the sender to approve a tool call.
+    ///
+    /// The default posts [`ApprovalPrompt::text`] and relies on a typed
+    /// `yes` / `no` / `always` reply. Channels with buttons or cards override
+    /// this and deliver presses as [`APPROVAL_CALLBACK_PREFIX`] messages.
+    async fn send_approval_prompt(&self, prompt: &ApprovalPrompt) -> anyhow::Result<()> {
+        self.send(
+            &SendMessage::new(prompt.text(), &prompt.recipient).in_thread(prompt.thread_ts.clone()),
+        )
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -217,6 +592,7 @@ mod tests {
                 channel: "dummy".into(),
                 timestamp: 123,
                 thread_ts: None,
+                attachments: Vec::new(),
             })
             .await
             .map_err(|e| anyhow::anyhow!(e.to_string()))
@@ -233,6 +609,7 @@ mod tests {
             channel: "dummy".into(),
             timestamp: 999,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         let cloned = message.clone();
@@ -301,4 +678,113 @@ mod tests {
         assert_eq!(received.content, "hello");
         assert_eq!(received.channel, "dummy");
     }
+
+    #[test]
+    fn approval_callback_roundtrip() {
+        let prompt = ApprovalPrompt {
+            request_id: "abc123".into(),
+            tool_name: "shell".into(),
+            arguments_summary: "command: ls".into(),
+            recipient: "chat".into(),
+            thread_ts: None,
+            timeout_secs: 60,
+        };
+        let data = prompt.callback_data(ApprovalResponse::Always);
+        assert_eq!(data, "zc-approval:abc123:always");
+        assert_eq!(
+            parse_approval_callback(&data),
+            Some(("abc123", ApprovalResponse::Always))
+        );
+        assert_eq!(parse_approval_callback("zc-approval::yes"), None);
+        assert_eq!(parse_approval_callback("yes"), None);
+        assert!(prompt.text().contains("within 60s"));
+    }
+
+    #[test]
+    fn split_attachment_markers_extracts_typed_attachments() {
+        let (text, attachments) = split_attachment_markers(
+            "Here you go [IMAGE:https://example.com/chart.png?x=1] and [file:/tmp/report.pdf] [note]",
+        );
+
+        assert_eq!(text, "Here you go  and  [note]");
+        assert_eq!(attachments.len(), 2);
+        assert_eq!(attachments[0].kind, AttachmentKind::Image);
+        assert_eq!(
+            attachments[0].source,
+            AttachmentSource::Url("https://example.com/chart.png?x=1".into())
+        );
+        assert_eq!(attachments[0].file_name.as_deref(), Some("chart.png"));
+        assert_eq!(attachments[0].mime_type.as_deref(), Some("image/png"));
+        assert_eq!(attachments[1].kind, AttachmentKind::Document);
+        assert_eq!(
+            attachments[1].source,
+            AttachmentSource::Path("/tmp/report.pdf".into())
+        );
+        assert_eq!(attachments[1].marker(), "[DOCUMENT:/tmp/report.pdf]");
+    }
+
+    #[test]
+    fn agent_content_describes_attachments_and_adds_missing_image_markers() {
+        let mut message = ChannelMessage {
+            id: "1".into(),
+            sender: "alice".into(),
+            reply_target: "alice".into(),
+            content: "What is this?".into(),
+            channel: "lisa".into(),
+            timestamp: 1,
+            thread_ts: None,
+            attachments: vec![
+                Attachment::from_url(AttachmentKind::Image, "https://example.com/cat.png"),
+                Attachment::from_path(AttachmentKind::Document, "/tmp/report.pdf")
+                    .with_size(2048)
+                    .with_caption("Q3"),
+            ],
+        };
+
+        assert_eq!(
+            message.agent_content(),
+            "What is this?\n\n[Attachments]\n\
+             - cat.png (image/png): https://example.com/cat.png\n\
+             - report.pdf (application/pdf, 2048 bytes): /tmp/report.pdf \"Q3\"\n\
+             [IMAGE:https://example.com/cat.png]"
+        );
+
+        message.content = "[IMAGE:https://example.com/cat.png]".into();
+        assert_eq!(message.agent_content().matches("[IMAGE:").count(), 1);
+
+        message.attachments.clear();
+        assert_eq!(message.agent_content(), message.content);
+    }
+
+    #[test]
+    fn attachment_serializes_flat_and_classifies_mime() {
+        let attachment = Attachment::from_url(AttachmentKind::Voice, "https://example.com/a.ogg")
+            .with_caption("listen")
+            .with_size(42);
+        let value = serde_json::to_value(&attachment).unwrap();
+        assert_eq!(value["kind"], "voice");
+        assert_eq!(value["url"], "https://example.com/a.ogg");
+        assert_eq!(value["size_bytes"], 42);
+        assert_eq!(
+            serde_json::from_value::<Attachment>(value).unwrap(),
+            attachment
+        );
+
+        assert_eq!(
+            AttachmentKind::from_mime("image/jpeg"),
+            AttachmentKind::Image
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("audio/ogg"),
+            AttachmentKind::Voice
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("audio/mpeg"),
+            AttachmentKind::Audio
+        );
+        assert_eq!(
+            AttachmentKind::from_mime("application/pdf"),
+            AttachmentKind::Document
+        );
+    }
 }
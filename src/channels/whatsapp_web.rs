@@ -741,6 +741,7 @@ impl Channel for WhatsAppWebChannel {
                                         content,
                                         timestamp: chrono::Utc::now().timestamp() as u64,
                                         thread_ts: None,
+                                        attachments: Vec::new(),
                                     })
                                     .await
                                 {
@@ -70,6 +70,9 @@ pub struct Config {
     /// Path to config.toml - computed from home, not serialized
     #[serde(skip)]
     pub config_path: PathBuf,
+    /// Profile this config was derived for by [`Config::for_profile`], not serialized
+    #[serde(skip)]
+    pub active_profile: Option<String>,
     /// API key for the selected provider. Overridden by `ZEROCLAW_API_KEY` or `API_KEY` env vars.
     pub api_key: Option<String>,
     /// Base URL override for provider API (e.g. "http://10.0.0.1:11434" for remote Ollama)
@@ -116,6 +119,10 @@ pub struct Config {
     #[serde(default)]
     pub extra_headers: HashMap<String, String>,
 
+    /// Managed local model server for the `llama-server` provider (`[llama_server]`).
+    #[serde(default)]
+    pub llama_server: LlamaServerConfig,
+
     /// Observability backend configuration (`[observability]`).
     #[serde(default)]
     pub observability: ObservabilityConfig,
@@ -192,6 +199,10 @@ pub struct Config {
     #[serde(default)]
     pub cron: CronConfig,
 
+    /// Standard operating procedure engine configuration (`[sop]`).
+    #[serde(default)]
+    pub sop: SopConfig,
+
     /// Channel configurations: Telegram, Discord, Slack, etc. (`[channels_config]`).
     #[serde(default)]
     pub channels_config: ChannelsConfig,
@@ -308,6 +319,10 @@ pub struct Config {
     #[serde(default)]
     pub swarms: HashMap<String, SwarmConfig>,
 
+    /// Named agent profiles served by the same daemon (`[profiles.<name>]`).
+    #[serde(default)]
+    pub profiles: HashMap<String, AgentProfileConfig>,
+
     /// Hooks configuration (lifecycle hooks and built-in hook toggles).
     #[serde(default)]
     pub hooks: HooksConfig,
@@ -328,6 +343,10 @@ pub struct Config {
     #[serde(default, alias = "mcpServers")]
     pub mcp: McpConfig,
 
+    /// App manifest (appMCP.json) tool registry (`[app_mcp]`).
+    #[serde(default)]
+    pub app_mcp: AppMcpConfig,
+
     /// Dynamic node discovery configuration (`[nodes]`).
     #[serde(default)]
     pub nodes: NodesConfig,
@@ -484,6 +503,30 @@ pub enum SwarmStrategy {
     Parallel,
     /// Use the LLM to pick the best agent for the task.
     Router,
+    /// Run agents as a dependency graph (see [`SwarmNodeConfig`]); independent
+    /// branches run concurrently.
+    Graph,
+}
+
+/// Per-agent settings for the `graph` swarm strategy
+/// (`[swarms.<name>.nodes.<agent>]`).
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct SwarmNodeConfig {
+    /// Agents whose outputs this agent needs; each must be listed in the
+    /// swarm's `agents`.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Prompt template. `{{task}}`, `{{context}}` and `{{<agent>}}` (an
+    /// upstream agent's output) are substituted. When unset, upstream outputs
+    /// are prepended to the task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_template: Option<String>,
+    /// Timeout for a single attempt in seconds. Defaults to the swarm timeout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Extra attempts after a failed or timed-out call. Default: `0`.
+    #[serde(default)]
+    pub retries: u32,
 }
 
 /// Configuration for a swarm of coordinated agents.
@@ -502,6 +545,15 @@ pub struct SwarmConfig {
     /// Maximum total timeout for the swarm execution in seconds.
     #[serde(default = "default_swarm_timeout_secs")]
     pub timeout_secs: u64,
+    /// Graph strategy: per-agent dependencies, templates, timeouts and retries.
+    /// Agents without an entry have no dependencies.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub nodes: HashMap<String, SwarmNodeConfig>,
+    /// Graph strategy: agent that runs last and produces the swarm result.
+    /// Without explicit `depends_on`, it receives every other terminal agent's
+    /// output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregator: Option<String>,
 }
 
 const DEFAULT_SWARM_TIMEOUT_SECS: u64 = 300;
@@ -510,6 +562,123 @@ fn default_swarm_timeout_secs() -> u64 {
     DEFAULT_SWARM_TIMEOUT_SECS
 }
 
+/// A named agent profile served alongside the default agent (`[profiles.<name>]`).
+///
+/// Each profile runs in its own workspace, so identity files (`SOUL.md`, ...),
+/// skills, memory and persisted sessions never leak between profiles.
+/// Messages are routed to a profile by sender, channel or gateway token;
+/// anything unbound is handled by the top-level configuration.
+///
+/// ```toml
+/// [profiles.lisa]
+/// workspace_dir = "profiles/lisa"
+/// model = "gpt-4o-mini"
+/// channels = ["lisa"]
+/// senders = ["telegram:123456789"]
+/// allowed_tools = ["memory_recall", "memory_store", "web_search"]
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct AgentProfileConfig {
+    /// Workspace directory for this profile. Relative paths resolve against
+    /// the directory containing `config.toml`.
+    pub workspace_dir: String,
+    /// Provider override (default: `default_provider`).
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Model override (default: `default_model`).
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Temperature override (default: `default_temperature`).
+    #[serde(default)]
+    pub temperature: Option<f64>,
+    /// Memory backend override (default: `[memory]`). File-backed stores and
+    /// the knowledge graph always live inside the profile workspace; Postgres
+    /// tables and Qdrant collections get a `_<profile>` suffix.
+    #[serde(default)]
+    pub memory: Option<MemoryConfig>,
+    /// Identity format override (default: `[identity]`).
+    #[serde(default)]
+    pub identity: Option<IdentityConfig>,
+    /// Tool allowlist, covering built-in, skill, MCP, app and peer tools.
+    /// Empty keeps every tool available.
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    /// Channel names whose messages go to this profile.
+    #[serde(default)]
+    pub channels: Vec<String>,
+    /// Senders whose messages go to this profile, as `"<channel>:<sender>"`
+    /// or a bare sender on any channel. Sender bindings win over channel bindings.
+    #[serde(default)]
+    pub senders: Vec<String>,
+    /// Gateway bearer tokens that select this profile, plaintext or SHA-256
+    /// hex like `gateway.paired_tokens`. Tokens must still pass pairing.
+    #[serde(default)]
+    pub gateway_tokens: Vec<String>,
+}
+
+impl AgentProfileConfig {
+    /// Whether `sender` on `channel` is bound to this profile.
+    pub fn binds_sender(&self, channel: &str, sender: &str) -> bool {
+        self.senders.iter().any(|binding| {
+            binding == sender
+                || binding
+                    .split_once(':')
+                    .is_some_and(|(ch, id)| ch == channel_base_name(channel) && id == sender)
+        })
+    }
+
+    /// Whether messages on `channel` are bound to this profile.
+    pub fn binds_channel(&self, channel: &str) -> bool {
+        self.channels
+            .iter()
+            .any(|binding| binding == channel || binding == channel_base_name(channel))
+    }
+
+    /// Whether the gateway bearer `token` selects this profile.
+    pub fn binds_gateway_token(&self, token: &str) -> bool {
+        if token.is_empty() {
+            return false;
+        }
+        let hashed = crate::security::pairing::PairingGuard::token_hash(token);
+        self.gateway_tokens
+            .iter()
+            .any(|binding| binding == token || binding.eq_ignore_ascii_case(&hashed))
+    }
+
+    /// Pick the profile for a channel message: sender bindings first, then
+    /// channel bindings. Ties resolve to the alphabetically first profile.
+    pub fn select<'a>(
+        profiles: &'a HashMap<String, Self>,
+        channel: &str,
+        sender: &str,
+    ) -> Option<&'a str> {
+        let mut names: Vec<&String> = profiles.keys().collect();
+        names.sort();
+        names
+            .iter()
+            .find(|name| profiles[name.as_str()].binds_sender(channel, sender))
+            .or_else(|| {
+                names
+                    .iter()
+                    .find(|name| profiles[name.as_str()].binds_channel(channel))
+            })
+            .map(|name| name.as_str())
+    }
+}
+
+/// Channel name without an instance suffix (`"slack:team"` → `"slack"`).
+fn channel_base_name(channel: &str) -> &str {
+    channel.split_once(':').map_or(channel, |(base, _)| base)
+}
+
+/// Suffix a profile adds to its Postgres table and Qdrant collection
+/// (`"a-b"` → `"a_b"`).
+fn profile_suffix(name: &str) -> String {
+    name.chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+        .collect()
+}
+
 /// Valid temperature range for all paths (config, CLI, env override).
 pub const TEMPERATURE_RANGE: std::ops::RangeInclusive<f64> = 0.0..=2.0;
 
@@ -819,6 +988,161 @@ impl Default for McpConfig {
     }
 }
 
+// ── appMCP (App Manifest Tool Registry) ──────────────────────────
+
+/// Configuration for app-manifest tool discovery (`[app_mcp]`).
+///
+/// Installed apps drop an `appMCP.json` into `<apps_dir>/<appId>/`. Each
+/// declared tool is registered as `<app>__<tool>` and the app is launched
+/// lazily on first invocation, after which it connects back to
+/// `ws://<listen_addr>/mcp/<appId>`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AppMcpConfig {
+    /// Enable appMCP manifest discovery.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Directory scanned for `<appId>/appMCP.json` manifests.
+    #[serde(default = "default_app_mcp_apps_dir")]
+    pub apps_dir: String,
+    /// Address of the WebSocket endpoint apps connect back to.
+    #[serde(default = "default_app_mcp_listen_addr")]
+    pub listen_addr: String,
+    /// Interval between manifest directory scans, in seconds.
+    #[serde(default = "default_app_mcp_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// How long to wait for a launched app to connect, in seconds.
+    #[serde(default = "default_app_mcp_launch_timeout_secs")]
+    pub launch_timeout_secs: u64,
+    /// Token apps must present when connecting back, as
+    /// `Authorization: Bearer <token>` or a `?token=` query parameter. When
+    /// unset, a random token is generated at startup and handed to
+    /// `exec`-launched apps in `ZEROCLAW_APP_MCP_TOKEN`.
+    #[serde(default)]
+    pub connect_token: Option<String>,
+}
+
+fn default_app_mcp_apps_dir() -> String {
+    "/var/lisa/apps".into()
+}
+
+fn default_app_mcp_listen_addr() -> String {
+    "127.0.0.1:9100".into()
+}
+
+fn default_app_mcp_poll_interval_secs() -> u64 {
+    2
+}
+
+fn default_app_mcp_launch_timeout_secs() -> u64 {
+    15
+}
+
+impl Default for AppMcpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            apps_dir: default_app_mcp_apps_dir(),
+            listen_addr: default_app_mcp_listen_addr(),
+            poll_interval_secs: default_app_mcp_poll_interval_secs(),
+            launch_timeout_secs: default_app_mcp_launch_timeout_secs(),
+            connect_token: None,
+        }
+    }
+}
+
+// ── llama-server (Managed Local Model Server) ─────────────────────
+
+/// Local model server spawned and supervised by the `llama-server` provider
+/// (`[llama_server]`).
+///
+/// The server is started on first use (or by the daemon at startup),
+/// health-checked, and restarted when it exits or stops answering. Any
+/// OpenAI-compatible server binary works when `args` is set; the defaults
+/// target llama.cpp's `llama-server`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Hash)]
+pub struct LlamaServerConfig {
+    /// Server binary, resolved via `PATH` when not absolute.
+    #[serde(default = "default_llama_server_binary")]
+    pub binary: String,
+    /// GGUF model file, passed as `--model`.
+    #[serde(default)]
+    pub model_path: String,
+    /// Context size passed as `--ctx-size`. `0` keeps the server default.
+    #[serde(default)]
+    pub ctx_size: u32,
+    /// Interface the server binds to.
+    #[serde(default = "default_llama_server_host")]
+    pub host: String,
+    /// Listen port. `0` picks a free port at startup.
+    #[serde(default)]
+    pub port: u16,
+    /// Full argument list, replacing the llama-server defaults. `{model}`,
+    /// `{host}`, `{port}` and `{ctx_size}` are substituted.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Extra arguments appended after the defaults (e.g. `["-ngl", "99"]`).
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+    /// Expose native tool calling; adds `--jinja` to the default arguments.
+    #[serde(default)]
+    pub native_tools: bool,
+    /// Path polled for readiness and health.
+    #[serde(default = "default_llama_server_health_path")]
+    pub health_path: String,
+    /// How long to wait for the server to become healthy, in seconds.
+    #[serde(default = "default_llama_server_startup_timeout_secs")]
+    pub startup_timeout_secs: u64,
+    /// Interval between health checks while supervised, in seconds.
+    #[serde(default = "default_llama_server_health_check_interval_secs")]
+    pub health_check_interval_secs: u64,
+    /// Consecutive failed health checks before the server is restarted.
+    #[serde(default = "default_llama_server_max_failed_health_checks")]
+    pub max_failed_health_checks: u32,
+}
+
+fn default_llama_server_binary() -> String {
+    "llama-server".into()
+}
+
+fn default_llama_server_host() -> String {
+    "127.0.0.1".into()
+}
+
+fn default_llama_server_health_path() -> String {
+    "/health".into()
+}
+
+fn default_llama_server_startup_timeout_secs() -> u64 {
+    180
+}
+
+fn default_llama_server_health_check_interval_secs() -> u64 {
+    15
+}
+
+fn default_llama_server_max_failed_health_checks() -> u32 {
+    3
+}
+
+impl Default for LlamaServerConfig {
+    fn default() -> Self {
+        Self {
+            binary: default_llama_server_binary(),
+            model_path: String::new(),
+            ctx_size: 0,
+            host: default_llama_server_host(),
+            port: 0,
+            args: Vec::new(),
+            extra_args: Vec::new(),
+            native_tools: false,
+            health_path: default_llama_server_health_path(),
+            startup_timeout_secs: default_llama_server_startup_timeout_secs(),
+            health_check_interval_secs: default_llama_server_health_check_interval_secs(),
+            max_failed_health_checks: default_llama_server_max_failed_health_checks(),
+        }
+    }
+}
+
 // ── Nodes (Dynamic Node Discovery) ───────────────────────────────
 
 /// Configuration for the dynamic node discovery system (`[nodes]`).
@@ -1096,8 +1420,9 @@ pub struct AgentConfig {
     #[serde(default = "default_agent_max_history_messages")]
     pub max_history_messages: usize,
     /// Maximum estimated tokens for conversation history before compaction triggers.
-    /// Uses ~4 chars/token heuristic. When this threshold is exceeded, older messages
-    /// are summarized to preserve context while staying within budget. Default: `32000`.
+    /// Counted with the active model's tokenizer and capped at three quarters of the
+    /// model's context window when that is known. When this threshold is exceeded, older
+    /// messages are summarized to preserve context while staying within budget. Default: `32000`.
     #[serde(default = "default_agent_max_context_tokens")]
     pub max_context_tokens: usize,
     /// Enable parallel tool execution within a single iteration. Default: `false`.
@@ -1343,6 +1668,101 @@ pub struct CostConfig {
     /// Per-model pricing (USD per 1M tokens)
     #[serde(default)]
     pub prices: std::collections::HashMap<String, ModelPricing>,
+
+    /// Spending quotas scoped to a channel, sender, delegate agent or cron job
+    /// (`[[cost.quotas]]`). Checked in addition to the global limits.
+    #[serde(default)]
+    pub quotas: Vec<CostQuotaConfig>,
+}
+
+/// What a cost quota is keyed by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CostQuotaScope {
+    /// Channel name (e.g. "telegram", "slack").
+    Channel,
+    /// Sender identity as reported by the channel.
+    Sender,
+    /// Delegate agent name (`[agents.<name>]`).
+    Agent,
+    /// Cron job name, or its id when unnamed.
+    CronJob,
+}
+
+impl CostQuotaScope {
+    pub const ALL: [Self; 4] = [Self::Channel, Self::Sender, Self::Agent, Self::CronJob];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Channel => "channel",
+            Self::Sender => "sender",
+            Self::Agent => "agent",
+            Self::CronJob => "cron_job",
+        }
+    }
+}
+
+impl std::fmt::Display for CostQuotaScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for CostQuotaScope {
+    type Err = anyhow::Error;
+
+    fn from_str(raw: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == raw.trim().replace('-', "_"))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "unknown quota scope '{raw}' (expected channel, sender, agent or cron_job)"
+                )
+            })
+    }
+}
+
+/// A spending quota for one usage key (`[[cost.quotas]]`).
+///
+/// ```toml
+/// [[cost.quotas]]
+/// scope = "channel"
+/// key = "slack"
+/// daily_limit_usd = 2.0
+/// downgrade_hint = "fast"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CostQuotaConfig {
+    /// What the quota is keyed by.
+    pub scope: CostQuotaScope,
+    /// Key to match; `"*"` (default) gives every key its own quota.
+    #[serde(default = "default_cost_quota_key")]
+    pub key: String,
+    /// Daily hard-stop limit in USD.
+    #[serde(default)]
+    pub daily_limit_usd: Option<f64>,
+    /// Monthly hard-stop limit in USD.
+    #[serde(default)]
+    pub monthly_limit_usd: Option<f64>,
+    /// Warn at this percentage of a limit (default: `cost.warn_at_percent`).
+    #[serde(default)]
+    pub warn_at_percent: Option<u8>,
+    /// Once past the warn threshold, route requests through this
+    /// `[[model_routes]]` hint until the hard stop.
+    #[serde(default)]
+    pub downgrade_hint: Option<String>,
+}
+
+fn default_cost_quota_key() -> String {
+    "*".into()
+}
+
+impl CostQuotaConfig {
+    /// Whether this quota applies to `key` in its scope.
+    pub fn matches(&self, key: &str) -> bool {
+        self.key == "*" || self.key == key
+    }
 }
 
 /// Per-model pricing entry (USD per 1M tokens).
@@ -1378,6 +1798,7 @@ impl Default for CostConfig {
             warn_at_percent: default_warn_percent(),
             allow_override: false,
             prices: get_default_pricing(),
+            quotas: Vec::new(),
         }
     }
 }
@@ -1475,6 +1896,12 @@ pub struct PeripheralsConfig {
     /// Place .md/.txt files named by board (e.g. nucleo-f401re.md, rpi-gpio.md).
     #[serde(default)]
     pub datasheet_dir: Option<String>,
+    /// Rank datasheet chunks by embedding similarity using the `[memory]`
+    /// embedding provider (e.g. `embedding_provider = "local"`) instead of
+    /// keyword overlap. Chunk vectors are cached in
+    /// `state/datasheet_embeddings.json`; only new or edited chunks are embedded.
+    #[serde(default)]
+    pub datasheet_embeddings: bool,
 }
 
 /// Configuration for a single peripheral board (e.g. STM32, RPi GPIO).
@@ -1491,6 +1918,9 @@ pub struct PeripheralBoardConfig {
     /// Baud rate for serial (default: 115200)
     #[serde(default = "default_peripheral_baud")]
     pub baud: u32,
+    /// Input pins the board reports as `pin_<n>` events (serial; armed with `gpio_watch` on connect)
+    #[serde(default)]
+    pub watch_pins: Vec<u8>,
 }
 
 fn default_peripheral_transport() -> String {
@@ -1508,6 +1938,7 @@ impl Default for PeripheralBoardConfig {
             transport: default_peripheral_transport(),
             path: None,
             baud: default_peripheral_baud(),
+            watch_pins: Vec::new(),
         }
     }
 }
@@ -1753,6 +2184,40 @@ pub struct NodeTransportConfig {
     /// Maximum number of connections per peer.
     #[serde(default = "default_connection_pool_size")]
     pub connection_pool_size: usize,
+    /// Name this daemon announces to mesh peers (defaults to the host name).
+    /// Must match the `name` peers list for it.
+    #[serde(default)]
+    pub node_name: Option<String>,
+    /// Other daemons in the federated mesh (`[[node_transport.peers]]`).
+    #[serde(default)]
+    pub peers: Vec<MeshPeerConfig>,
+    /// Seconds between peer advertisement refreshes (minimum 5).
+    #[serde(default = "default_mesh_refresh_secs")]
+    pub mesh_refresh_secs: u64,
+}
+
+/// One peer daemon in the federated mesh (`[[node_transport.peers]]`).
+///
+/// The allowlists control what this peer may use *here*; what we may use on
+/// the peer is decided by the peer's own configuration.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MeshPeerConfig {
+    /// Peer node name (its `node_transport.node_name`).
+    pub name: String,
+    /// `host:port` of the peer gateway (HTTPS), or a full `https://` URL.
+    /// `http://` URLs are accepted only with `require_https = false`.
+    pub address: String,
+    /// HMAC-SHA256 key shared with this peer only; both sides configure the
+    /// same value. Calls claiming to come from this peer must be signed with
+    /// it, so one mesh member cannot pose as another.
+    #[serde(default)]
+    pub shared_secret: String,
+    /// Local tools this peer may invoke. `"*"` allows all; empty allows none.
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    /// Local delegate agents (`[agents.<name>]`) this peer may run.
+    #[serde(default)]
+    pub allowed_agents: Vec<String>,
 }
 
 fn default_node_transport_enabled() -> bool {
@@ -1767,6 +2232,9 @@ fn default_require_https() -> bool {
 fn default_connection_pool_size() -> usize {
     4
 }
+fn default_mesh_refresh_secs() -> u64 {
+    60
+}
 
 impl Default for NodeTransportConfig {
     fn default() -> Self {
@@ -1780,6 +2248,9 @@ impl Default for NodeTransportConfig {
             tls_key_path: None,
             mutual_tls: false,
             connection_pool_size: default_connection_pool_size(),
+            node_name: None,
+            peers: Vec::new(),
+            mesh_refresh_secs: default_mesh_refresh_secs(),
         }
     }
 }
@@ -2584,6 +3055,11 @@ pub struct ScreenControlConfig {
     /// action 후 자동 screenshot 전 대기 시간 (밀리초, 기본 2000)
     #[serde(default = "default_screenshot_delay_ms")]
     pub screenshot_delay_ms: u64,
+
+    /// linux 백엔드 X 디스플레이 (예: ":0", Xvfb ":99"). 미지정 시 $DISPLAY 사용.
+    /// Wayland 세션에서는 필수 (xdotool은 X11 전용)
+    #[serde(default)]
+    pub display: Option<String>,
 }
 
 /// 지원 플랫폼 백엔드
@@ -2610,6 +3086,7 @@ impl Default for ScreenControlConfig {
             backend: ScreenControlBackend::default(),
             resize_width: default_screen_control_resize_width(),
             screenshot_delay_ms: default_screenshot_delay_ms(),
+            display: None,
         }
     }
 }
@@ -3467,6 +3944,45 @@ impl Default for QdrantConfig {
     }
 }
 
+/// HNSW vector index tuning (`[memory.hnsw]`).
+/// Used when `[memory].vector_index = "hnsw"`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HnswConfig {
+    /// Neighbours kept per node (twice this on the base layer). Higher values
+    /// improve recall at the cost of memory and insert time. Changing it
+    /// requires `zeroclaw memory rebuild-index`. Default: 16.
+    #[serde(default = "default_hnsw_m")]
+    pub m: usize,
+    /// Candidate list size while inserting. Default: 200.
+    #[serde(default = "default_hnsw_ef_construction")]
+    pub ef_construction: usize,
+    /// Candidate list size while searching; raise for better recall. Default: 64.
+    #[serde(default = "default_hnsw_ef_search")]
+    pub ef_search: usize,
+}
+
+fn default_hnsw_m() -> usize {
+    16
+}
+
+fn default_hnsw_ef_construction() -> usize {
+    200
+}
+
+fn default_hnsw_ef_search() -> usize {
+    64
+}
+
+impl Default for HnswConfig {
+    fn default() -> Self {
+        Self {
+            m: default_hnsw_m(),
+            ef_construction: default_hnsw_ef_construction(),
+            ef_search: default_hnsw_ef_search(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct MemoryConfig {
@@ -3489,6 +4005,11 @@ pub struct MemoryConfig {
     /// For sqlite backend: prune conversation rows older than this many days
     #[serde(default = "default_conversation_retention_days")]
     pub conversation_retention_days: u32,
+    /// For sqlite backend: decay the importance of memories not written or
+    /// recalled for this many days, halving it per further half-life.
+    /// 0 = no decay (default).
+    #[serde(default)]
+    pub importance_half_life_days: u32,
     /// Embedding provider: "none" | "openai" | "custom:URL"
     #[serde(default = "default_embedding_provider")]
     pub embedding_provider: String,
@@ -3509,6 +4030,11 @@ pub struct MemoryConfig {
     /// context from bleeding into conversations. Default: 0.4
     #[serde(default = "default_min_relevance_score")]
     pub min_relevance_score: f64,
+    /// Age in days at which a memory's recency weight in context ranking
+    /// drops halfway (from 1.0 toward 0.5). Context memories are ranked by
+    /// relevance × recency × importance. 0 = ignore age. Default: 30
+    #[serde(default = "default_recency_half_life_days")]
+    pub recency_half_life_days: u32,
     /// Max embedding cache entries before LRU eviction
     #[serde(default = "default_cache_size")]
     pub embedding_cache_size: usize,
@@ -3546,6 +4072,15 @@ pub struct MemoryConfig {
     /// None = wait indefinitely (default). Recommended max: 300.
     #[serde(default)]
     pub sqlite_open_timeout_secs: Option<u64>,
+    /// For sqlite backend: vector index used for recall.
+    /// "flat" (default) scans every stored embedding; "hnsw" keeps an
+    /// approximate nearest-neighbour graph in memory, persisted next to the
+    /// database as `memory/brain.hnsw`.
+    #[serde(default = "default_vector_index")]
+    pub vector_index: String,
+    /// HNSW tuning, used when `vector_index = "hnsw"`.
+    #[serde(default)]
+    pub hnsw: HnswConfig,
 
     // ── Qdrant backend options ─────────────────────────────────
     /// Configuration for Qdrant vector database backend.
@@ -3584,6 +4119,9 @@ fn default_keyword_weight() -> f64 {
 fn default_min_relevance_score() -> f64 {
     0.4
 }
+fn default_recency_half_life_days() -> u32 {
+    30
+}
 fn default_cache_size() -> usize {
     10_000
 }
@@ -3601,6 +4139,10 @@ fn default_response_cache_hot_entries() -> usize {
     256
 }
 
+fn default_vector_index() -> String {
+    "flat".into()
+}
+
 impl Default for MemoryConfig {
     fn default() -> Self {
         Self {
@@ -3610,12 +4152,14 @@ impl Default for MemoryConfig {
             archive_after_days: default_archive_after_days(),
             purge_after_days: default_purge_after_days(),
             conversation_retention_days: default_conversation_retention_days(),
+            importance_half_life_days: 0,
             embedding_provider: default_embedding_provider(),
             embedding_model: default_embedding_model(),
             embedding_dimensions: default_embedding_dims(),
             vector_weight: default_vector_weight(),
             keyword_weight: default_keyword_weight(),
             min_relevance_score: default_min_relevance_score(),
+            recency_half_life_days: default_recency_half_life_days(),
             embedding_cache_size: default_cache_size(),
             chunk_max_tokens: default_chunk_size(),
             response_cache_enabled: false,
@@ -3626,6 +4170,8 @@ impl Default for MemoryConfig {
             snapshot_on_hygiene: false,
             auto_hydrate: true,
             sqlite_open_timeout_secs: None,
+            vector_index: default_vector_index(),
+            hnsw: HnswConfig::default(),
             qdrant: QdrantConfig::default(),
         }
     }
@@ -3823,6 +4369,60 @@ pub struct AutonomyConfig {
     /// model in tool specs.
     #[serde(default)]
     pub non_cli_excluded_tools: Vec<String>,
+
+    /// Interactive approval prompts for channel-driven runs
+    /// (`[autonomy.channel_approval]`).
+    #[serde(default)]
+    pub channel_approval: ChannelApprovalConfig,
+}
+
+/// Decision applied when a channel approval prompt is not answered in time.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalTimeoutAction {
+    /// Deny the tool call (default).
+    #[default]
+    Deny,
+    /// Execute the tool call once.
+    Approve,
+}
+
+/// Channel tool-approval configuration (`[autonomy.channel_approval]`).
+///
+/// In supervised mode, tool calls that need approval on a chat channel are
+/// sent back to the originating conversation as a prompt: inline buttons on
+/// Telegram and Slack (Socket Mode), an A2UI card on Lisa, and a
+/// `yes` / `no` / `always` reply keyword elsewhere.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChannelApprovalConfig {
+    /// Prompt the sender instead of auto-denying. Default: `true`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Seconds to wait for an answer before applying `on_timeout`. Default: `120`.
+    #[serde(default = "default_channel_approval_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Decision applied when the prompt times out. Default: `deny`.
+    #[serde(default)]
+    pub on_timeout: ApprovalTimeoutAction,
+    /// Persist "always" answers per channel sender across restarts
+    /// (`state/channel_approvals.json`). Default: `true`.
+    #[serde(default = "default_true")]
+    pub persist_always: bool,
+}
+
+fn default_channel_approval_timeout_secs() -> u64 {
+    120
+}
+
+impl Default for ChannelApprovalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            timeout_secs: default_channel_approval_timeout_secs(),
+            on_timeout: ApprovalTimeoutAction::default(),
+            persist_always: true,
+        }
+    }
 }
 
 fn default_auto_approve() -> Vec<String> {
@@ -3891,6 +4491,7 @@ impl Default for AutonomyConfig {
             always_ask: default_always_ask(),
             allowed_roots: Vec::new(),
             non_cli_excluded_tools: Vec::new(),
+            channel_approval: ChannelApprovalConfig::default(),
         }
     }
 }
@@ -3958,6 +4559,37 @@ pub struct DockerRuntimeConfig {
     pub allowed_workspace_roots: Vec<String>,
 }
 
+/// Limits for one [`WasmRuntime`](crate::runtime::wasm::WasmRuntime) sandbox.
+///
+/// Not a `config.toml` section: wasm skill tools build one per call from
+/// their manifest grants, which `[wasm]` already caps.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WasmRuntimeConfig {
+    /// Directory of `.wasm` tool modules, relative to the workspace.
+    #[serde(default = "default_wasm_runtime_tools_dir")]
+    pub tools_dir: String,
+
+    /// CPU fuel budget per module invocation.
+    #[serde(default = "default_wasm_runtime_fuel_limit")]
+    pub fuel_limit: u64,
+
+    /// Maximum linear memory per module invocation in MB.
+    #[serde(default = "default_wasm_memory_limit_mb")]
+    pub memory_limit_mb: u64,
+
+    /// Let modules read the workspace.
+    #[serde(default)]
+    pub allow_workspace_read: bool,
+
+    /// Let modules write the workspace.
+    #[serde(default)]
+    pub allow_workspace_write: bool,
+
+    /// Hosts modules may reach over HTTP (empty = no network).
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+}
+
 fn default_runtime_kind() -> String {
     "native".into()
 }
@@ -3992,6 +4624,27 @@ impl Default for DockerRuntimeConfig {
     }
 }
 
+fn default_wasm_runtime_tools_dir() -> String {
+    "tools/wasm".into()
+}
+
+fn default_wasm_runtime_fuel_limit() -> u64 {
+    1_000_000
+}
+
+impl Default for WasmRuntimeConfig {
+    fn default() -> Self {
+        Self {
+            tools_dir: default_wasm_runtime_tools_dir(),
+            fuel_limit: default_wasm_runtime_fuel_limit(),
+            memory_limit_mb: default_wasm_memory_limit_mb(),
+            allow_workspace_read: false,
+            allow_workspace_write: false,
+            allowed_hosts: Vec::new(),
+        }
+    }
+}
+
 impl Default for RuntimeConfig {
     fn default() -> Self {
         Self {
@@ -4333,6 +4986,78 @@ impl Default for CronConfig {
     }
 }
 
+// ── SOP ─────────────────────────────────────────────────────────
+
+/// Standard operating procedure engine configuration (`[sop]` section).
+///
+/// When enabled, SOPs are loaded from `sops_dir`, run state is persisted to
+/// `<workspace>/sop/runs.db` and rehydrated per `resume_policy`, incoming
+/// channel messages are matched against `channel` triggers, and the daemon
+/// forwards board events to `peripheral` triggers, publishes on
+/// `[channels_config.mqtt].topics` to `mqtt` triggers, and polls `file_watch`
+/// triggers.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SopConfig {
+    /// Enable the SOP engine in the daemon. Default: `false`.
+    #[serde(default)]
+    pub enabled: bool,
+    /// Directory holding `<name>/SOP.toml` definitions. Default: `<workspace>/sops`.
+    #[serde(default)]
+    pub sops_dir: Option<String>,
+    /// Execution mode for SOPs that do not declare one. Default: `supervised`.
+    #[serde(default)]
+    pub default_execution_mode: crate::sop::SopExecutionMode,
+    /// Maximum number of runs active at once across all SOPs. Default: `4`.
+    #[serde(default = "default_sop_max_concurrent_total")]
+    pub max_concurrent_total: usize,
+    /// Seconds before a pending approval times out; critical and high priority
+    /// runs are then auto-approved. `0` disables the timeout. Default: `300`.
+    #[serde(default = "default_sop_approval_timeout_secs")]
+    pub approval_timeout_secs: u64,
+    /// Finished runs kept for status queries and cooldowns (`0` = unlimited).
+    /// Default: `100`.
+    #[serde(default = "default_sop_max_finished_runs")]
+    pub max_finished_runs: usize,
+    /// Seconds between `file_watch` trigger scans. Default: `5`.
+    #[serde(default = "default_sop_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+    /// What to do with runs that were still active when the daemon stopped:
+    /// `resume`, `interrupt` or `rerequest` approval. Default: `resume`.
+    #[serde(default)]
+    pub resume_policy: crate::sop::SopResumePolicy,
+}
+
+fn default_sop_max_concurrent_total() -> usize {
+    4
+}
+
+fn default_sop_approval_timeout_secs() -> u64 {
+    300
+}
+
+fn default_sop_max_finished_runs() -> usize {
+    100
+}
+
+fn default_sop_poll_interval_secs() -> u64 {
+    5
+}
+
+impl Default for SopConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sops_dir: None,
+            default_execution_mode: crate::sop::SopExecutionMode::default(),
+            max_concurrent_total: default_sop_max_concurrent_total(),
+            approval_timeout_secs: default_sop_approval_timeout_secs(),
+            max_finished_runs: default_sop_max_finished_runs(),
+            poll_interval_secs: default_sop_poll_interval_secs(),
+            resume_policy: crate::sop::SopResumePolicy::default(),
+        }
+    }
+}
+
 // ── Tunnel ──────────────────────────────────────────────────────
 
 /// Tunnel configuration for exposing the gateway publicly (`[tunnel]` section).
@@ -4522,6 +5247,8 @@ pub struct ChannelsConfig {
     pub bluesky: Option<BlueskyConfig>,
     /// Lisa WebSocket channel configuration (browser/app clients, A2UI).
     pub lisa: Option<LisaConfig>,
+    /// MQTT broker configuration (SOP triggers and the conversational MQTT channel).
+    pub mqtt: Option<MqttConfig>,
     /// Base timeout in seconds for processing a single channel message (LLM + tools).
     /// Runtime uses this as a per-turn budget that scales with tool-loop depth
     /// (up to 4x, capped) so one slow/retried model call does not consume the
@@ -4649,6 +5376,10 @@ impl ChannelsConfig {
                 Box::new(ConfigWrapper::new(self.lisa.as_ref())),
                 self.lisa.is_some(),
             ),
+            (
+                Box::new(ConfigWrapper::new(self.mqtt.as_ref())),
+                self.mqtt.is_some(),
+            ),
         ]
     }
 
@@ -4701,6 +5432,7 @@ impl Default for ChannelsConfig {
             reddit: None,
             bluesky: None,
             lisa: None,
+            mqtt: None,
             message_timeout_secs: default_channel_message_timeout_secs(),
             ack_reactions: true,
             show_tool_calls: false,
@@ -5112,6 +5844,95 @@ impl ChannelConfig for LisaConfig {
     }
 }
 
+/// MQTT broker configuration.
+///
+/// `topics` feed SOP `mqtt` triggers; the optional `channel` section turns on
+/// the conversational MQTT channel on the same broker.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MqttConfig {
+    /// Broker URL, `mqtt://host:port` or `mqtts://host:port`.
+    pub broker_url: String,
+    /// Client id for the SOP listener. The channel connects as `<client_id>-channel`.
+    #[serde(default = "default_mqtt_client_id")]
+    pub client_id: String,
+    /// Topic filters routed to SOP `mqtt` triggers (`+` and `#` wildcards).
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// QoS level for subscriptions and publishes (0, 1 or 2). Default: `1`.
+    #[serde(default = "default_mqtt_qos")]
+    pub qos: u8,
+    /// Broker username (optional)
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Broker password (optional)
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Use TLS; must match the `mqtts://` scheme.
+    #[serde(default)]
+    pub use_tls: bool,
+    /// Keep-alive interval in seconds. Default: `30`.
+    #[serde(default = "default_mqtt_keep_alive_secs")]
+    pub keep_alive_secs: u64,
+    /// Conversational channel settings; omit to use MQTT for SOP triggers only.
+    #[serde(default)]
+    pub channel: Option<crate::channels::mqtt::MqttChannelConfig>,
+}
+
+fn default_mqtt_client_id() -> String {
+    "zeroclaw".into()
+}
+
+fn default_mqtt_qos() -> u8 {
+    1
+}
+
+fn default_mqtt_keep_alive_secs() -> u64 {
+    30
+}
+
+impl MqttConfig {
+    pub fn validate(&self) -> Result<()> {
+        let is_tls_scheme = self.broker_url.starts_with("mqtts://");
+        if !is_tls_scheme && !self.broker_url.starts_with("mqtt://") {
+            anyhow::bail!("mqtt: broker_url must start with mqtt:// or mqtts://");
+        }
+        if self.use_tls && !is_tls_scheme {
+            anyhow::bail!("mqtt: use_tls is true but broker_url does not use mqtts://");
+        }
+        if is_tls_scheme && !self.use_tls {
+            anyhow::bail!("mqtt: broker_url uses mqtts:// but use_tls is false");
+        }
+        if self.client_id.trim().is_empty() {
+            anyhow::bail!("mqtt: client_id must not be empty");
+        }
+        if self.qos > 2 {
+            anyhow::bail!("mqtt: qos must be 0, 1, or 2");
+        }
+        if self.keep_alive_secs == 0 {
+            anyhow::bail!("mqtt: keep_alive_secs must be greater than 0");
+        }
+        if self.topics.iter().any(|t| t.trim().is_empty()) {
+            anyhow::bail!("mqtt: topics must not contain empty topics");
+        }
+        match &self.channel {
+            Some(channel) => channel.validate(),
+            None if self.topics.is_empty() => {
+                anyhow::bail!("mqtt: topics must contain at least one topic unless [channels_config.mqtt.channel] is set")
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl ChannelConfig for MqttConfig {
+    fn name() -> &'static str {
+        "MQTT"
+    }
+    fn desc() -> &'static str {
+        "MQTT broker for devices and SOP triggers"
+    }
+}
+
 impl WhatsAppConfig {
     /// Detect which backend to use based on config fields.
     /// Returns "cloud" if phone_number_id is set, "web" if session_path is set.
@@ -6194,6 +7015,7 @@ impl Default for Config {
         Self {
             workspace_dir: zeroclaw_dir.join("workspace"),
             config_path: zeroclaw_dir.join("config.toml"),
+            active_profile: None,
             api_key: None,
             api_url: None,
             api_path: None,
@@ -6204,6 +7026,7 @@ impl Default for Config {
             default_temperature: default_temperature(),
             provider_timeout_secs: default_provider_timeout_secs(),
             extra_headers: HashMap::new(),
+            llama_server: LlamaServerConfig::default(),
             observability: ObservabilityConfig::default(),
             autonomy: AutonomyConfig::default(),
             backup: BackupConfig::default(),
@@ -6222,6 +7045,7 @@ impl Default for Config {
             embedding_routes: Vec::new(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            sop: SopConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -6246,12 +7070,14 @@ impl Default for Config {
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
             swarms: HashMap::new(),
+            profiles: HashMap::new(),
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             query_classification: QueryClassificationConfig::default(),
             transcription: TranscriptionConfig::default(),
             tts: TtsConfig::default(),
             mcp: McpConfig::default(),
+            app_mcp: AppMcpConfig::default(),
             nodes: NodesConfig::default(),
             workspace: WorkspaceConfig::default(),
             notion: NotionConfig::default(),
@@ -6934,6 +7760,13 @@ impl Config {
                     "config.channels_config.email.password",
                 )?;
             }
+            if let Some(ref mut mq) = config.channels_config.mqtt {
+                decrypt_optional_secret(
+                    &store,
+                    &mut mq.password,
+                    "config.channels_config.mqtt.password",
+                )?;
+            }
             if let Some(ref mut irc) = config.channels_config.irc {
                 decrypt_optional_secret(
                     &store,
@@ -7247,6 +8080,85 @@ impl Config {
         }
     }
 
+    /// Profile bound to a channel message, if any (see [`AgentProfileConfig::select`]).
+    pub fn profile_for_message(&self, channel: &str, sender: &str) -> Option<&str> {
+        AgentProfileConfig::select(&self.profiles, channel, sender)
+    }
+
+    /// Profile selected by a gateway bearer token, if any.
+    pub fn profile_for_gateway_token(&self, token: &str) -> Option<&str> {
+        let mut names: Vec<&String> = self.profiles.keys().collect();
+        names.sort();
+        names
+            .into_iter()
+            .find(|name| self.profiles[name.as_str()].binds_gateway_token(token))
+            .map(String::as_str)
+    }
+
+    /// Derive the configuration a profile runs with: the top-level config with
+    /// the profile workspace and overrides applied.
+    pub fn for_profile(&self, name: &str) -> Result<Self> {
+        let profile = self
+            .profiles
+            .get(name)
+            .with_context(|| format!("Unknown profile '{name}'"))?;
+        let mut config = self.clone();
+        config.workspace_dir = self.profile_workspace_dir(profile);
+        if let Some(provider) = &profile.provider {
+            config.default_provider = Some(provider.clone());
+        }
+        if let Some(model) = &profile.model {
+            config.default_model = Some(model.clone());
+        }
+        if let Some(temperature) = profile.temperature {
+            config.default_temperature = temperature;
+        }
+        if let Some(memory) = &profile.memory {
+            config.memory = memory.clone();
+        }
+        if let Some(identity) = &profile.identity {
+            config.identity = identity.clone();
+        }
+        // Remote memory backends are shared between profiles, so give each
+        // profile its own Postgres table and Qdrant collection.
+        let suffix = profile_suffix(name);
+        let storage = &mut config.storage.provider.config;
+        storage.table = format!("{}_{suffix}", storage.table);
+        config.memory.qdrant.collection = format!("{}_{suffix}", config.memory.qdrant.collection);
+        // The knowledge graph defaults to one file under ~/.zeroclaw; keep
+        // each profile's graph in its workspace.
+        config.knowledge.db_path = config
+            .workspace_dir
+            .join("knowledge.db")
+            .to_string_lossy()
+            .into_owned();
+        config.active_profile = Some(name.to_string());
+        Ok(config)
+    }
+
+    /// Workspace of `profile`; relative paths resolve against the directory
+    /// containing `config.toml`.
+    fn profile_workspace_dir(&self, profile: &AgentProfileConfig) -> PathBuf {
+        let workspace = PathBuf::from(shellexpand::tilde(profile.workspace_dir.trim()).as_ref());
+        if workspace.is_absolute() {
+            workspace
+        } else {
+            self.config_path
+                .parent()
+                .unwrap_or_else(|| Path::new("."))
+                .join(workspace)
+        }
+    }
+
+    /// Tool allowlist of the active profile; `None` when every tool is allowed.
+    pub fn profile_allowed_tools(&self) -> Option<Vec<String>> {
+        self.active_profile
+            .as_ref()
+            .and_then(|name| self.profiles.get(name))
+            .map(|profile| profile.allowed_tools.clone())
+            .filter(|tools| !tools.is_empty())
+    }
+
     /// Validate configuration values that would cause runtime failures.
     ///
     /// Called after TOML deserialization and env-override application to catch
@@ -7271,6 +8183,32 @@ impl Config {
             anyhow::bail!("gateway.host must not be empty");
         }
 
+        // Node transport mesh
+        let mut mesh_peers = std::collections::HashSet::new();
+        let mut mesh_secrets = std::collections::HashSet::new();
+        for (i, peer) in self.node_transport.peers.iter().enumerate() {
+            if peer.name.trim().is_empty() || peer.address.trim().is_empty() {
+                anyhow::bail!("node_transport.peers[{i}] requires a name and an address");
+            }
+            if !mesh_peers.insert(peer.name.as_str()) {
+                anyhow::bail!(
+                    "node_transport.peers[{i}]: duplicate peer name {}",
+                    peer.name
+                );
+            }
+            if peer.shared_secret.trim().is_empty() {
+                anyhow::bail!("node_transport.peers[{i}] requires a shared_secret");
+            }
+            if !mesh_secrets.insert(peer.shared_secret.as_str()) {
+                anyhow::bail!("node_transport.peers[{i}]: shared_secret is reused by another peer");
+            }
+        }
+
+        // MQTT
+        if let Some(ref mqtt) = self.channels_config.mqtt {
+            mqtt.validate()?;
+        }
+
         // Autonomy
         if self.autonomy.max_actions_per_hour == 0 {
             anyhow::bail!("autonomy.max_actions_per_hour must be greater than 0");
@@ -7282,6 +8220,11 @@ impl Config {
                 );
             }
         }
+        if self.autonomy.channel_approval.enabled
+            && self.autonomy.channel_approval.timeout_secs == 0
+        {
+            anyhow::bail!("autonomy.channel_approval.timeout_secs must be greater than 0");
+        }
 
         // Security OTP / estop
         if self.security.otp.token_ttl_secs == 0 {
@@ -7341,6 +8284,34 @@ impl Config {
             }
         }
 
+        // Cost quotas
+        for (i, quota) in self.cost.quotas.iter().enumerate() {
+            if quota.key.trim().is_empty() {
+                anyhow::bail!("cost.quotas[{i}].key must not be empty (use \"*\" for every key)");
+            }
+            if quota.daily_limit_usd.is_none() && quota.monthly_limit_usd.is_none() {
+                anyhow::bail!("cost.quotas[{i}] must set daily_limit_usd and/or monthly_limit_usd");
+            }
+            for (field, limit) in [
+                ("daily_limit_usd", quota.daily_limit_usd),
+                ("monthly_limit_usd", quota.monthly_limit_usd),
+            ] {
+                if limit.is_some_and(|value| !value.is_finite() || value < 0.0) {
+                    anyhow::bail!("cost.quotas[{i}].{field} must be a non-negative number");
+                }
+            }
+            if quota.warn_at_percent.is_some_and(|pct| pct > 100) {
+                anyhow::bail!("cost.quotas[{i}].warn_at_percent must be between 0 and 100");
+            }
+            if let Some(hint) = quota.downgrade_hint.as_deref() {
+                if !self.model_routes.iter().any(|route| route.hint == hint) {
+                    anyhow::bail!(
+                        "cost.quotas[{i}].downgrade_hint \"{hint}\" does not match any [[model_routes]] hint"
+                    );
+                }
+            }
+        }
+
         // Embedding routes
         for (i, route) in self.embedding_routes.iter().enumerate() {
             if route.hint.trim().is_empty() {
@@ -7545,6 +8516,136 @@ impl Config {
             validate_mcp_config(&self.mcp)?;
         }
 
+        // appMCP
+        if self.app_mcp.enabled {
+            if self.app_mcp.apps_dir.trim().is_empty() {
+                anyhow::bail!("app_mcp.apps_dir must not be empty");
+            }
+            if self
+                .app_mcp
+                .listen_addr
+                .parse::<std::net::SocketAddr>()
+                .is_err()
+            {
+                anyhow::bail!("app_mcp.listen_addr must be a socket address (e.g. 127.0.0.1:9100)");
+            }
+            if self.app_mcp.poll_interval_secs == 0 {
+                anyhow::bail!("app_mcp.poll_interval_secs must be greater than 0");
+            }
+            if self.app_mcp.launch_timeout_secs == 0 {
+                anyhow::bail!("app_mcp.launch_timeout_secs must be greater than 0");
+            }
+        }
+
+        // llama-server
+        if self.llama_server.binary.trim().is_empty() {
+            anyhow::bail!("llama_server.binary must not be empty");
+        }
+        if !self.llama_server.health_path.starts_with('/') {
+            anyhow::bail!("llama_server.health_path must start with '/'");
+        }
+        if self.llama_server.startup_timeout_secs == 0 {
+            anyhow::bail!("llama_server.startup_timeout_secs must be greater than 0");
+        }
+        if self.llama_server.health_check_interval_secs == 0 {
+            anyhow::bail!("llama_server.health_check_interval_secs must be greater than 0");
+        }
+
+        // Memory vector index
+        match self.memory.vector_index.trim() {
+            "flat" => {}
+            "hnsw" => {
+                if self.memory.hnsw.m < 2 {
+                    anyhow::bail!("memory.hnsw.m must be at least 2");
+                }
+                if self.memory.hnsw.ef_construction == 0 || self.memory.hnsw.ef_search == 0 {
+                    anyhow::bail!(
+                        "memory.hnsw.ef_construction and ef_search must be greater than 0"
+                    );
+                }
+            }
+            other => {
+                anyhow::bail!("memory.vector_index must be \"flat\" or \"hnsw\", got {other:?}")
+            }
+        }
+
+        // Swarms
+        for (name, swarm) in &self.swarms {
+            let members: std::collections::HashSet<&str> =
+                swarm.agents.iter().map(String::as_str).collect();
+            for (node, node_config) in &swarm.nodes {
+                if !members.contains(node.as_str()) {
+                    anyhow::bail!(
+                        "swarms.{name}.nodes.{node} is not listed in swarms.{name}.agents"
+                    );
+                }
+                for dep in &node_config.depends_on {
+                    if !members.contains(dep.as_str()) || dep == node {
+                        anyhow::bail!(
+                            "swarms.{name}.nodes.{node}.depends_on has invalid entry {dep:?}"
+                        );
+                    }
+                }
+            }
+            if let Some(aggregator) = &swarm.aggregator {
+                if !members.contains(aggregator.as_str()) {
+                    anyhow::bail!(
+                        "swarms.{name}.aggregator {aggregator:?} is not listed in swarms.{name}.agents"
+                    );
+                }
+            }
+        }
+
+        // Profiles
+        let mut channel_owners: HashMap<&str, &str> = HashMap::new();
+        let mut sender_owners: HashMap<&str, &str> = HashMap::new();
+        let mut suffix_owners: HashMap<String, &str> = HashMap::new();
+        let mut workspace_owners: HashMap<PathBuf, &str> = HashMap::new();
+        for (name, profile) in &self.profiles {
+            if name.trim().is_empty() {
+                anyhow::bail!("profiles must not have an empty name");
+            }
+            if profile.workspace_dir.trim().is_empty() {
+                anyhow::bail!("profiles.{name}.workspace_dir must not be empty");
+            }
+            let suffix = profile_suffix(name);
+            if let Some(other) = suffix_owners.insert(suffix.clone(), name.as_str()) {
+                anyhow::bail!(
+                    "profiles.{name}: shares the memory table/collection suffix {suffix:?} with profile '{other}'"
+                );
+            }
+            let workspace = self.profile_workspace_dir(profile);
+            if workspace == self.workspace_dir {
+                anyhow::bail!(
+                    "profiles.{name}.workspace_dir must differ from the top-level workspace_dir"
+                );
+            }
+            if let Some(other) = workspace_owners.insert(workspace, name.as_str()) {
+                anyhow::bail!("profiles.{name}.workspace_dir is already used by profile '{other}'");
+            }
+            if let Some(temperature) = profile.temperature {
+                if !TEMPERATURE_RANGE.contains(&temperature) {
+                    anyhow::bail!(
+                        "profiles.{name}.temperature must be between 0.0 and 2.0 (got {temperature})"
+                    );
+                }
+            }
+            for channel in &profile.channels {
+                if let Some(other) = channel_owners.insert(channel.as_str(), name.as_str()) {
+                    anyhow::bail!(
+                        "profiles.{name}.channels: channel {channel:?} is already bound to profile '{other}'"
+                    );
+                }
+            }
+            for sender in &profile.senders {
+                if let Some(other) = sender_owners.insert(sender.as_str(), name.as_str()) {
+                    anyhow::bail!(
+                        "profiles.{name}.senders: sender {sender:?} is already bound to profile '{other}'"
+                    );
+                }
+            }
+        }
+
         // Knowledge graph
         if self.knowledge.enabled {
             if self.knowledge.max_nodes == 0 {
@@ -8294,6 +9395,13 @@ impl Config {
                 "config.channels_config.email.password",
             )?;
         }
+        if let Some(ref mut mq) = config_to_save.channels_config.mqtt {
+            encrypt_optional_secret(
+                &store,
+                &mut mq.password,
+                "config.channels_config.mqtt.password",
+            )?;
+        }
         if let Some(ref mut irc) = config_to_save.channels_config.irc {
             encrypt_optional_secret(
                 &store,
@@ -8750,6 +9858,7 @@ default_temperature = 0.7
         let config = Config {
             workspace_dir: PathBuf::from("/tmp/test/workspace"),
             config_path: PathBuf::from("/tmp/test/config.toml"),
+            active_profile: None,
             api_key: Some("sk-test-key".into()),
             api_url: None,
             api_path: None,
@@ -8760,6 +9869,7 @@ default_temperature = 0.7
             provider_timeout_secs: 120,
             custom_provider_auth_header: None,
             extra_headers: HashMap::new(),
+            llama_server: LlamaServerConfig::default(),
             observability: ObservabilityConfig {
                 backend: "log".into(),
                 ..ObservabilityConfig::default()
@@ -8778,6 +9888,7 @@ default_temperature = 0.7
                 always_ask: vec![],
                 allowed_roots: vec![],
                 non_cli_excluded_tools: vec![],
+                channel_approval: ChannelApprovalConfig::default(),
             },
             backup: BackupConfig::default(),
             data_retention: DataRetentionConfig::default(),
@@ -8806,6 +9917,7 @@ default_temperature = 0.7
                 ..HeartbeatConfig::default()
             },
             cron: CronConfig::default(),
+            sop: SopConfig::default(),
             channels_config: ChannelsConfig {
                 cli: true,
                 telegram: Some(TelegramConfig {
@@ -8842,6 +9954,7 @@ default_temperature = 0.7
                 reddit: None,
                 bluesky: None,
                 lisa: None,
+                mqtt: None,
                 message_timeout_secs: 300,
                 ack_reactions: true,
                 show_tool_calls: true,
@@ -8873,11 +9986,13 @@ default_temperature = 0.7
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
             swarms: HashMap::new(),
+            profiles: HashMap::new(),
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
             tts: TtsConfig::default(),
             mcp: McpConfig::default(),
+            app_mcp: AppMcpConfig::default(),
             nodes: NodesConfig::default(),
             workspace: WorkspaceConfig::default(),
             notion: NotionConfig::default(),
@@ -9157,6 +10272,7 @@ tool_dispatcher = "xml"
         let config = Config {
             workspace_dir: dir.join("workspace"),
             config_path: config_path.clone(),
+            active_profile: None,
             api_key: Some("sk-roundtrip".into()),
             api_url: None,
             api_path: None,
@@ -9167,6 +10283,7 @@ tool_dispatcher = "xml"
             provider_timeout_secs: 120,
             custom_provider_auth_header: None,
             extra_headers: HashMap::new(),
+            llama_server: LlamaServerConfig::default(),
             observability: ObservabilityConfig::default(),
             autonomy: AutonomyConfig::default(),
             backup: BackupConfig::default(),
@@ -9185,6 +10302,7 @@ tool_dispatcher = "xml"
             query_classification: QueryClassificationConfig::default(),
             heartbeat: HeartbeatConfig::default(),
             cron: CronConfig::default(),
+            sop: SopConfig::default(),
             channels_config: ChannelsConfig::default(),
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -9210,11 +10328,13 @@ tool_dispatcher = "xml"
             peripherals: PeripheralsConfig::default(),
             agents: HashMap::new(),
             swarms: HashMap::new(),
+            profiles: HashMap::new(),
             hooks: HooksConfig::default(),
             hardware: HardwareConfig::default(),
             transcription: TranscriptionConfig::default(),
             tts: TtsConfig::default(),
             mcp: McpConfig::default(),
+            app_mcp: AppMcpConfig::default(),
             nodes: NodesConfig::default(),
             workspace: WorkspaceConfig::default(),
             notion: NotionConfig::default(),
@@ -9623,6 +10743,7 @@ allowed_users = ["@ops:matrix.org"]
             reddit: None,
             bluesky: None,
             lisa: None,
+            mqtt: None,
             message_timeout_secs: 300,
             ack_reactions: true,
             show_tool_calls: true,
@@ -9870,6 +10991,7 @@ channel_id = "C123"
             reddit: None,
             bluesky: None,
             lisa: None,
+            mqtt: None,
             message_timeout_secs: 300,
             ack_reactions: true,
             show_tool_calls: true,
@@ -10686,6 +11808,81 @@ requires_openai_auth = true
         assert!(error.to_string().contains("auth_header is invalid"));
     }
 
+    #[test]
+    async fn validate_cost_quotas() {
+        let _env_guard = env_override_lock().await;
+        let mut config: Config = toml::from_str(
+            r#"
+[[model_routes]]
+hint = "fast"
+provider = "openrouter"
+model = "cheap/model"
+
+[[cost.quotas]]
+scope = "channel"
+key = "slack"
+daily_limit_usd = 2.0
+downgrade_hint = "fast"
+
+[[cost.quotas]]
+scope = "cron_job"
+monthly_limit_usd = 10.0
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.cost.quotas[1].scope, CostQuotaScope::CronJob);
+        assert_eq!(config.cost.quotas[1].key, "*");
+        config.validate().unwrap();
+
+        config.cost.quotas[0].downgrade_hint = Some("missing".into());
+        let error = config.validate().expect_err("expected validation failure");
+        assert!(error
+            .to_string()
+            .contains("does not match any [[model_routes]]"));
+
+        config.cost.quotas[0].downgrade_hint = None;
+        config.cost.quotas[0].daily_limit_usd = None;
+        let error = config.validate().expect_err("expected validation failure");
+        assert!(error.to_string().contains("cost.quotas[0] must set"));
+    }
+
+    #[test]
+    async fn validate_node_transport_mesh_peers() {
+        let _env_guard = env_override_lock().await;
+        let mut config: Config = toml::from_str(
+            r#"
+[node_transport]
+node_name = "site-a"
+
+[[node_transport.peers]]
+name = "site-b"
+address = "site-b.example.com:443"
+shared_secret = "a-b-secret"
+allowed_tools = ["file_read"]
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.node_transport.mesh_refresh_secs, 60);
+        assert!(config.node_transport.peers[0].allowed_agents.is_empty());
+        config.validate().unwrap();
+
+        config
+            .node_transport
+            .peers
+            .push(config.node_transport.peers[0].clone());
+        let error = config.validate().expect_err("expected validation failure");
+        assert!(error.to_string().contains("duplicate peer name"));
+
+        config.node_transport.peers[1].name = "site-c".into();
+        let error = config.validate().expect_err("expected validation failure");
+        assert!(error.to_string().contains("reused by another peer"));
+
+        config.node_transport.peers.pop();
+        config.node_transport.peers[0].shared_secret.clear();
+        let error = config.validate().expect_err("expected validation failure");
+        assert!(error.to_string().contains("requires a shared_secret"));
+    }
+
     #[test]
     async fn validate_rejects_conflicting_model_provider_auth_headers_for_same_base_url() {
         let _env_guard = env_override_lock().await;
@@ -11503,8 +12700,10 @@ default_model = "legacy-model"
                 transport: "serial".into(),
                 path: Some("/dev/ttyACM0".into()),
                 baud: 115_200,
+                watch_pins: vec![2],
             }],
             datasheet_dir: None,
+            datasheet_embeddings: false,
         };
         let toml_str = toml::to_string(&p).unwrap();
         let parsed: PeripheralsConfig = toml::from_str(&toml_str).unwrap();
@@ -11512,6 +12711,7 @@ default_model = "legacy-model"
         assert_eq!(parsed.boards.len(), 1);
         assert_eq!(parsed.boards[0].board, "nucleo-f401re");
         assert_eq!(parsed.boards[0].path.as_deref(), Some("/dev/ttyACM0"));
+        assert_eq!(parsed.boards[0].watch_pins, vec![2]);
     }
 
     #[test]
@@ -12162,6 +13362,7 @@ require_otp_to_resume = true
             (SwarmStrategy::Sequential, "\"sequential\""),
             (SwarmStrategy::Parallel, "\"parallel\""),
             (SwarmStrategy::Router, "\"router\""),
+            (SwarmStrategy::Graph, "\"graph\""),
         ];
         for (variant, expected_json) in &cases {
             let serialized = serde_json::to_string(variant).expect("serialize");
@@ -12224,6 +13425,179 @@ require_otp_to_resume = true
         assert!(config.swarms.contains_key("pipeline"));
     }
 
+    #[test]
+    async fn graph_swarm_deserializes_and_validates_references() {
+        let toml_str = r#"
+            [agents.search]
+            provider = "ollama"
+            model = "llama3"
+
+            [agents.summarize]
+            provider = "ollama"
+            model = "llama3"
+
+            [swarms.research]
+            agents = ["search", "summarize"]
+            strategy = "graph"
+            aggregator = "summarize"
+
+            [swarms.research.nodes.summarize]
+            depends_on = ["search"]
+            prompt_template = "Summarize: {{search}}"
+            timeout_secs = 30
+            retries = 2
+        "#;
+        let mut config: Config = toml::from_str(toml_str).expect("deserialize");
+        let swarm = &config.swarms["research"];
+        assert_eq!(swarm.strategy, SwarmStrategy::Graph);
+        assert_eq!(swarm.aggregator.as_deref(), Some("summarize"));
+        let node = &swarm.nodes["summarize"];
+        assert_eq!(node.depends_on, vec!["search"]);
+        assert_eq!(node.timeout_secs, Some(30));
+        assert_eq!(node.retries, 2);
+        config.validate().expect("valid graph");
+
+        config
+            .swarms
+            .get_mut("research")
+            .unwrap()
+            .nodes
+            .get_mut("summarize")
+            .unwrap()
+            .depends_on = vec!["critic".into()];
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("depends_on"), "{err}");
+    }
+
+    #[test]
+    async fn profiles_route_by_sender_channel_and_gateway_token() {
+        let toml_str = r#"
+            default_model = "base-model"
+
+            [profiles.lisa]
+            workspace_dir = "profiles/lisa"
+            model = "lisa-model"
+            channels = ["lisa", "slack"]
+            gateway_tokens = ["zc_lisa"]
+
+            [profiles.ops]
+            workspace_dir = "/srv/ops"
+            senders = ["slack:U42", "ops-bot"]
+        "#;
+        let mut config: Config = toml::from_str(toml_str).expect("deserialize");
+        config.validate().expect("valid profiles");
+
+        assert_eq!(config.profile_for_message("lisa", "anyone"), Some("lisa"));
+        assert_eq!(config.profile_for_message("slack", "U1"), Some("lisa"));
+        assert_eq!(config.profile_for_message("slack", "U42"), Some("ops"));
+        assert_eq!(
+            config.profile_for_message("telegram", "ops-bot"),
+            Some("ops")
+        );
+        assert_eq!(config.profile_for_message("telegram", "U42"), None);
+
+        assert_eq!(config.profile_for_gateway_token("zc_lisa"), Some("lisa"));
+        assert_eq!(config.profile_for_gateway_token(""), None);
+        config.profiles.get_mut("ops").unwrap().gateway_tokens =
+            vec![crate::security::pairing::PairingGuard::token_hash("zc_ops")];
+        assert_eq!(config.profile_for_gateway_token("zc_ops"), Some("ops"));
+
+        config.profiles.get_mut("ops").unwrap().channels = vec!["lisa".into()];
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("already bound"), "{err}");
+    }
+
+    #[test]
+    async fn validate_rejects_profiles_sharing_storage_or_workspace() {
+        let toml_str = r#"
+            [profiles.a-b]
+            workspace_dir = "profiles/a-b"
+
+            [profiles.a_b]
+            workspace_dir = "profiles/a_b"
+        "#;
+        let mut config: Config = toml::from_str(toml_str).expect("deserialize");
+        config.config_path = PathBuf::from("/etc/zeroclaw/config.toml");
+        config.workspace_dir = PathBuf::from("/etc/zeroclaw/workspace");
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("suffix \"a_b\""), "{err}");
+
+        let a_b = config.profiles.remove("a_b").unwrap();
+        config.profiles.insert("other".into(), a_b);
+        config.validate().expect("distinct profiles");
+
+        config.profiles.get_mut("other").unwrap().workspace_dir =
+            "/etc/zeroclaw/profiles/a-b".into();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("already used by profile"), "{err}");
+
+        config.profiles.get_mut("other").unwrap().workspace_dir = "workspace".into();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("top-level workspace_dir"), "{err}");
+    }
+
+    #[test]
+    async fn for_profile_applies_workspace_and_overrides() {
+        let toml_str = r#"
+            default_provider = "openrouter"
+            default_model = "base-model"
+
+            [profiles.lisa]
+            workspace_dir = "profiles/lisa"
+            provider = "ollama"
+            model = "lisa-model"
+            temperature = 0.2
+            allowed_tools = ["memory_recall"]
+
+            [profiles.lisa.memory]
+            backend = "markdown"
+            auto_save = false
+
+            [profiles.open]
+            workspace_dir = "/srv/open"
+        "#;
+        let mut config: Config = toml::from_str(toml_str).expect("deserialize");
+        config.config_path = PathBuf::from("/etc/zeroclaw/config.toml");
+        config.workspace_dir = PathBuf::from("/etc/zeroclaw/workspace");
+
+        let lisa = config.for_profile("lisa").expect("lisa profile");
+        assert_eq!(
+            lisa.workspace_dir,
+            PathBuf::from("/etc/zeroclaw/profiles/lisa")
+        );
+        assert_eq!(lisa.default_provider.as_deref(), Some("ollama"));
+        assert_eq!(lisa.default_model.as_deref(), Some("lisa-model"));
+        assert!((lisa.default_temperature - 0.2).abs() < f64::EPSILON);
+        assert_eq!(lisa.memory.backend, "markdown");
+        assert_eq!(lisa.active_profile.as_deref(), Some("lisa"));
+        assert_eq!(
+            lisa.profile_allowed_tools(),
+            Some(vec!["memory_recall".to_string()])
+        );
+
+        let open = config.for_profile("open").expect("open profile");
+        assert_eq!(open.workspace_dir, PathBuf::from("/srv/open"));
+        assert_eq!(open.default_model.as_deref(), Some("base-model"));
+        assert_eq!(open.profile_allowed_tools(), None);
+        assert_eq!(config.profile_allowed_tools(), None);
+
+        assert_eq!(lisa.storage.provider.config.table, "memories_lisa");
+        assert_eq!(open.storage.provider.config.table, "memories_open");
+        assert_eq!(lisa.memory.qdrant.collection, "zeroclaw_memories_lisa");
+        assert_eq!(config.storage.provider.config.table, "memories");
+        assert_eq!(
+            PathBuf::from(&lisa.knowledge.db_path),
+            PathBuf::from("/etc/zeroclaw/profiles/lisa/knowledge.db")
+        );
+        assert_eq!(
+            PathBuf::from(&open.knowledge.db_path),
+            PathBuf::from("/srv/open/knowledge.db")
+        );
+        assert_eq!(config.knowledge.db_path, "~/.zeroclaw/knowledge.db");
+
+        assert!(config.for_profile("missing").is_err());
+    }
+
     #[tokio::test]
     async fn nevis_client_secret_encrypt_decrypt_roundtrip() {
         let dir = std::env::temp_dir().join(format!(
@@ -0,0 +1,62 @@
+//! `zeroclaw cost` — report spend from the cost ledger.
+
+use super::tracker::CostTracker;
+use super::types::KeyUsage;
+use crate::config::{Config, CostQuotaScope};
+use anyhow::Result;
+
+/// Handle `zeroclaw cost <subcommand>` CLI commands.
+pub fn handle_command(command: crate::CostCommands, config: &Config) -> Result<()> {
+    if !config.cost.enabled {
+        println!("Cost tracking is disabled; set [cost] enabled = true to record spend.");
+    }
+    let tracker = CostTracker::new(config.cost.clone(), &config.workspace_dir)?;
+
+    match command {
+        crate::CostCommands::Summary => {
+            let summary = tracker.get_summary()?;
+            println!(
+                "Today:      ${:.4} of ${:.2} daily limit",
+                summary.daily_cost_usd, config.cost.daily_limit_usd
+            );
+            println!(
+                "This month: ${:.4} of ${:.2} monthly limit",
+                summary.monthly_cost_usd, config.cost.monthly_limit_usd
+            );
+            println!("Quotas:     {} configured", config.cost.quotas.len());
+            Ok(())
+        }
+        crate::CostCommands::Usage { scope, key } => {
+            let scope = scope
+                .as_deref()
+                .map(str::parse::<CostQuotaScope>)
+                .transpose()?;
+            let usage = tracker.usage_by_key(scope, key.as_deref())?;
+            if usage.is_empty() {
+                println!("No scoped spend recorded this month.");
+                return Ok(());
+            }
+
+            println!("Spend per quota key (UTC day / month)");
+            println!();
+            for entry in &usage {
+                println!("- {}", format_key_usage(entry));
+            }
+            Ok(())
+        }
+    }
+}
+
+fn format_key_usage(usage: &KeyUsage) -> String {
+    let limit = |limit: Option<f64>| limit.map_or_else(|| "-".to_string(), |v| format!("${v:.2}"));
+    format!(
+        "{}:{} | today ${:.4} / {} | month ${:.4} / {} | {} requests",
+        usage.scope,
+        usage.key,
+        usage.daily_cost_usd,
+        limit(usage.daily_limit_usd),
+        usage.monthly_cost_usd,
+        limit(usage.monthly_limit_usd),
+        usage.request_count,
+    )
+}
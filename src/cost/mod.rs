@@ -1,3 +1,5 @@
+pub mod cli;
+pub mod scope;
 pub mod tracker;
 pub mod types;
 
@@ -5,4 +7,7 @@ pub mod types;
 #[allow(unused_imports)]
 pub use tracker::CostTracker;
 #[allow(unused_imports)]
-pub use types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
+pub use types::{
+    BudgetCheck, CostRecord, CostSummary, KeyUsage, ModelStats, QuotaKey, TokenUsage, UsagePeriod,
+    UsageScope,
+};
@@ -0,0 +1,243 @@
+//! Process-wide cost tracking and usage attribution for scoped quotas.
+//!
+//! Entry points (channels, cron, delegate agents) wrap their work in
+//! [`with_scope`] / [`with_agent`] so every LLM call made underneath is
+//! attributed to the right channel, sender, agent or cron job. The agent loop
+//! calls [`gate_llm_call`] before each request and [`record_llm_call`] after.
+
+use super::tracker::CostTracker;
+use super::types::{BudgetCheck, TokenUsage, UsageScope};
+use crate::config::{Config, ModelRouteConfig};
+use crate::providers::{tokenizer, ChatMessage, Provider};
+use std::future::Future;
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// Expected reply size used when estimating a request before it is sent.
+const ESTIMATED_OUTPUT_TOKENS: u64 = 1024;
+
+struct GlobalCost {
+    tracker: Arc<CostTracker>,
+    model_routes: Vec<ModelRouteConfig>,
+}
+
+static GLOBAL_COST: LazyLock<RwLock<Option<Arc<GlobalCost>>>> = LazyLock::new(|| RwLock::new(None));
+
+tokio::task_local! {
+    static USAGE_SCOPE: UsageScope;
+}
+
+/// Initialize the process-wide cost tracker from config.
+///
+/// With `[cost] enabled = false` no tracker is installed, so calls are
+/// neither gated nor recorded.
+pub fn init_from_config(config: &Config) {
+    let global = if config.cost.enabled {
+        match CostTracker::new(config.cost.clone(), &config.workspace_dir) {
+            Ok(tracker) => Some(Arc::new(GlobalCost {
+                tracker: Arc::new(tracker),
+                model_routes: config.model_routes.clone(),
+            })),
+            Err(e) => {
+                tracing::warn!("Failed to initialize cost tracker: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    let mut guard = GLOBAL_COST.write().unwrap_or_else(|e| e.into_inner());
+    *guard = global;
+}
+
+fn global() -> Option<Arc<GlobalCost>> {
+    GLOBAL_COST
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// The process-wide cost tracker, if cost tracking is enabled.
+pub fn global_tracker() -> Option<Arc<CostTracker>> {
+    global().map(|global| Arc::clone(&global.tracker))
+}
+
+/// Run `future` with every LLM call it makes attributed to `scope`.
+pub async fn with_scope<F: Future>(scope: UsageScope, future: F) -> F::Output {
+    USAGE_SCOPE.scope(scope, future).await
+}
+
+/// Run `future` attributed to delegate agent `agent`, keeping the channel,
+/// sender and cron job of the enclosing scope.
+pub async fn with_agent<F: Future>(agent: &str, future: F) -> F::Output {
+    let mut scope = current_scope();
+    scope.agent = Some(agent.to_string());
+    with_scope(scope, future).await
+}
+
+/// Scope set by the enclosing [`with_scope`], or an empty scope.
+pub fn current_scope() -> UsageScope {
+    USAGE_SCOPE.try_with(Clone::clone).unwrap_or_default()
+}
+
+/// Check the budget for an LLM call about to be sent.
+///
+/// Returns `Ok(None)` to proceed with `model`, `Ok(Some(model))` to send the
+/// request to a cheaper model instead (a quota past its warning threshold
+/// with a `downgrade_hint`), or an error when a limit would be exceeded.
+///
+/// The downgrade is expressed as `hint:<name>` when `provider` routes that
+/// hint, or as the route's model when `provider_name` is the route's own
+/// provider. Otherwise the call proceeds unchanged.
+pub fn gate_llm_call(
+    provider: &dyn Provider,
+    provider_name: Option<&str>,
+    model: &str,
+    messages: &[ChatMessage],
+) -> anyhow::Result<Option<String>> {
+    let Some(global) = global() else {
+        return Ok(None);
+    };
+    let scope = current_scope();
+    let priced_model = global.priced_model(model);
+    let check = global.tracker.check_scoped_request_budget(
+        priced_model,
+        messages,
+        ESTIMATED_OUTPUT_TOKENS,
+        &scope,
+    )?;
+
+    match &check {
+        BudgetCheck::Allowed => Ok(None),
+        BudgetCheck::Exceeded { .. } => {
+            let reason = check.describe().unwrap_or_default();
+            tracing::warn!(?scope, "{reason}");
+            anyhow::bail!("{reason}. Request blocked.")
+        }
+        BudgetCheck::Warning { downgrade_hint, .. } => {
+            let reason = check.describe().unwrap_or_default();
+            let Some(hint) = downgrade_hint.as_deref() else {
+                tracing::warn!(?scope, "{reason}");
+                return Ok(None);
+            };
+            let downgraded = if provider.supports_route_hint(hint) {
+                Some(format!("hint:{hint}"))
+            } else {
+                global
+                    .route(hint)
+                    .filter(|route| provider_name == Some(route.provider.as_str()))
+                    .map(|route| route.model.clone())
+            };
+            match downgraded {
+                Some(downgraded) if downgraded != model => {
+                    tracing::info!(?scope, "{reason}; downgrading to {downgraded}");
+                    Ok(Some(downgraded))
+                }
+                Some(_) => Ok(None),
+                None => {
+                    tracing::warn!(
+                        ?scope,
+                        "{reason}; downgrade hint '{hint}' is not routable by this provider"
+                    );
+                    Ok(None)
+                }
+            }
+        }
+    }
+}
+
+/// Record the spend of a completed LLM call against the current scope.
+///
+/// When the provider reports no token usage, input and output are estimated
+/// with the model's tokenizer.
+pub fn record_llm_call(
+    model: &str,
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+    messages: &[ChatMessage],
+    response_text: &str,
+) {
+    let Some(global) = global() else {
+        return;
+    };
+    let priced_model = global.priced_model(model);
+    let tokenizer = tokenizer::tokenizer_for_model(priced_model);
+    let input_tokens = input_tokens
+        .unwrap_or_else(|| tokenizer::count_messages(tokenizer.as_ref(), messages) as u64);
+    let output_tokens = output_tokens.unwrap_or_else(|| tokenizer.count(response_text) as u64);
+
+    let usage = global.usage_for(model, input_tokens, output_tokens);
+    if let Err(e) = global.tracker.record_scoped_usage(usage, &current_scope()) {
+        tracing::warn!("Failed to record LLM usage: {e}");
+    }
+}
+
+/// Price an LLM call with the configured model prices, if cost tracking is
+/// enabled.
+pub fn price_llm_call(model: &str, input_tokens: u64, output_tokens: u64) -> Option<TokenUsage> {
+    global().map(|global| global.usage_for(model, input_tokens, output_tokens))
+}
+
+impl GlobalCost {
+    fn usage_for(&self, model: &str, input_tokens: u64, output_tokens: u64) -> TokenUsage {
+        self.tracker
+            .usage_for(self.priced_model(model), input_tokens, output_tokens)
+    }
+
+    fn route(&self, hint: &str) -> Option<&ModelRouteConfig> {
+        self.model_routes.iter().find(|route| route.hint == hint)
+    }
+
+    /// Model name to price: `hint:<name>` resolves to the route's model.
+    fn priced_model<'a>(&'a self, model: &'a str) -> &'a str {
+        model
+            .strip_prefix("hint:")
+            .and_then(|hint| self.route(hint))
+            .map_or(model, |route| route.model.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn with_agent_keeps_enclosing_scope() {
+        assert!(current_scope().is_empty());
+
+        let channel_scope = UsageScope {
+            channel: Some("slack".into()),
+            sender: Some("alice".into()),
+            ..Default::default()
+        };
+        let scope = with_scope(channel_scope, async {
+            with_agent("researcher", async { current_scope() }).await
+        })
+        .await;
+
+        assert_eq!(scope.channel.as_deref(), Some("slack"));
+        assert_eq!(scope.sender.as_deref(), Some("alice"));
+        assert_eq!(scope.agent.as_deref(), Some("researcher"));
+        assert!(scope.cron_job.is_none());
+    }
+
+    #[test]
+    fn hint_models_are_priced_by_route_model() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let global = GlobalCost {
+            tracker: Arc::new(
+                CostTracker::new(crate::config::CostConfig::default(), tmp.path()).unwrap(),
+            ),
+            model_routes: vec![ModelRouteConfig {
+                hint: "fast".into(),
+                provider: "openrouter".into(),
+                model: "cheap/model".into(),
+                api_key: None,
+            }],
+        };
+
+        assert_eq!(global.priced_model("hint:fast"), "cheap/model");
+        assert_eq!(global.priced_model("hint:unknown"), "hint:unknown");
+        assert_eq!(global.priced_model("big/model"), "big/model");
+    }
+}
@@ -1,5 +1,10 @@
-use super::types::{BudgetCheck, CostRecord, CostSummary, ModelStats, TokenUsage, UsagePeriod};
-use crate::config::schema::CostConfig;
+use super::types::{
+    BudgetCheck, CostRecord, CostSummary, KeyUsage, ModelStats, QuotaKey, TokenUsage, UsagePeriod,
+    UsageScope,
+};
+use crate::config::schema::{CostConfig, CostQuotaConfig, CostQuotaScope, ModelPricing};
+use crate::providers::tokenizer;
+use crate::providers::ChatMessage;
 use anyhow::{anyhow, Context, Result};
 use chrono::{Datelike, NaiveDate, Utc};
 use parking_lot::{Mutex, MutexGuard};
@@ -47,8 +52,21 @@ impl CostTracker {
         self.session_costs.lock()
     }
 
-    /// Check if a request is within budget.
+    /// Check if a request is within the global budget.
     pub fn check_budget(&self, estimated_cost_usd: f64) -> Result<BudgetCheck> {
+        self.check_scoped_budget(estimated_cost_usd, &UsageScope::default())
+    }
+
+    /// Check if a request is within the global budget and every quota that
+    /// applies to `scope`.
+    ///
+    /// An exceeded limit wins over a warning; among warnings, one carrying a
+    /// downgrade hint wins so callers can switch to the cheaper route.
+    pub fn check_scoped_budget(
+        &self,
+        estimated_cost_usd: f64,
+        scope: &UsageScope,
+    ) -> Result<BudgetCheck> {
         if !self.config.enabled {
             return Ok(BudgetCheck::Allowed);
         }
@@ -62,52 +80,138 @@ impl CostTracker {
         let mut storage = self.lock_storage();
         let (daily_cost, monthly_cost) = storage.get_aggregated_costs()?;
 
-        // Check daily limit
-        let projected_daily = daily_cost + estimated_cost_usd;
-        if projected_daily > self.config.daily_limit_usd {
-            return Ok(BudgetCheck::Exceeded {
-                current_usd: daily_cost,
-                limit_usd: self.config.daily_limit_usd,
-                period: UsagePeriod::Day,
-            });
+        let mut result = evaluate_limits(
+            daily_cost,
+            monthly_cost,
+            estimated_cost_usd,
+            Some(self.config.daily_limit_usd),
+            Some(self.config.monthly_limit_usd),
+            self.config.warn_at_percent,
+            None,
+            None,
+        );
+        if matches!(result, BudgetCheck::Exceeded { .. }) {
+            return Ok(result);
         }
 
-        // Check monthly limit
-        let projected_monthly = monthly_cost + estimated_cost_usd;
-        if projected_monthly > self.config.monthly_limit_usd {
-            return Ok(BudgetCheck::Exceeded {
-                current_usd: monthly_cost,
-                limit_usd: self.config.monthly_limit_usd,
-                period: UsagePeriod::Month,
-            });
+        for (quota_scope, key) in scope.keys() {
+            let aggregate = storage.key_aggregate(quota_scope, key);
+            for quota in self
+                .config
+                .quotas
+                .iter()
+                .filter(|quota| quota.scope == quota_scope && quota.matches(key))
+            {
+                let check = evaluate_limits(
+                    aggregate.daily_cost_usd,
+                    aggregate.monthly_cost_usd,
+                    estimated_cost_usd,
+                    quota.daily_limit_usd,
+                    quota.monthly_limit_usd,
+                    quota.warn_at_percent.unwrap_or(self.config.warn_at_percent),
+                    Some(QuotaKey {
+                        scope: quota_scope,
+                        key: key.to_string(),
+                    }),
+                    quota.downgrade_hint.clone(),
+                );
+                if matches!(check, BudgetCheck::Exceeded { .. }) {
+                    return Ok(check);
+                }
+                if budget_check_rank(&check) > budget_check_rank(&result) {
+                    result = check;
+                }
+            }
         }
 
-        // Check warning thresholds
-        let warn_threshold = f64::from(self.config.warn_at_percent.min(100)) / 100.0;
-        let daily_warn_threshold = self.config.daily_limit_usd * warn_threshold;
-        let monthly_warn_threshold = self.config.monthly_limit_usd * warn_threshold;
+        Ok(result)
+    }
 
-        if projected_daily >= daily_warn_threshold {
-            return Ok(BudgetCheck::Warning {
-                current_usd: daily_cost,
-                limit_usd: self.config.daily_limit_usd,
-                period: UsagePeriod::Day,
-            });
-        }
+    /// Estimate the cost of a request before it is sent.
+    ///
+    /// Input tokens are counted with the model's tokenizer; `max_output_tokens`
+    /// is the expected reply size. Models without a configured price are
+    /// estimated at zero.
+    pub fn estimate_request_cost(
+        &self,
+        model: &str,
+        messages: &[ChatMessage],
+        max_output_tokens: u64,
+    ) -> f64 {
+        let Some(pricing) = self.pricing_for(model) else {
+            return 0.0;
+        };
+        let tokenizer = tokenizer::tokenizer_for_model(model);
+        let input_tokens = tokenizer::count_messages(tokenizer.as_ref(), messages) as u64;
+        TokenUsage::new(
+            model,
+            input_tokens,
+            max_output_tokens,
+            pricing.input,
+            pricing.output,
+        )
+        .cost()
+    }
 
-        if projected_monthly >= monthly_warn_threshold {
-            return Ok(BudgetCheck::Warning {
-                current_usd: monthly_cost,
-                limit_usd: self.config.monthly_limit_usd,
-                period: UsagePeriod::Month,
-            });
-        }
+    /// Check whether a request is within budget, using its estimated cost.
+    pub fn check_request_budget(
+        &self,
+        model: &str,
+        messages: &[ChatMessage],
+        max_output_tokens: u64,
+    ) -> Result<BudgetCheck> {
+        self.check_budget(self.estimate_request_cost(model, messages, max_output_tokens))
+    }
+
+    /// Check whether a request attributed to `scope` is within budget, using
+    /// its estimated cost.
+    pub fn check_scoped_request_budget(
+        &self,
+        model: &str,
+        messages: &[ChatMessage],
+        max_output_tokens: u64,
+        scope: &UsageScope,
+    ) -> Result<BudgetCheck> {
+        self.check_scoped_budget(
+            self.estimate_request_cost(model, messages, max_output_tokens),
+            scope,
+        )
+    }
 
-        Ok(BudgetCheck::Allowed)
+    /// Price a completed request. Models without a configured price cost zero.
+    pub fn usage_for(&self, model: &str, input_tokens: u64, output_tokens: u64) -> TokenUsage {
+        let (input_price, output_price) = self
+            .pricing_for(model)
+            .map_or((0.0, 0.0), |pricing| (pricing.input, pricing.output));
+        TokenUsage::new(
+            model,
+            input_tokens,
+            output_tokens,
+            input_price,
+            output_price,
+        )
+    }
+
+    /// Price entry for `model`, matching either the full `provider/model` key
+    /// or a bare model name.
+    fn pricing_for(&self, model: &str) -> Option<&ModelPricing> {
+        self.config.prices.get(model).or_else(|| {
+            self.config
+                .prices
+                .iter()
+                .filter(|(key, _)| key.rsplit('/').next() == Some(model))
+                .min_by(|a, b| a.0.cmp(b.0))
+                .map(|(_, pricing)| pricing)
+        })
     }
 
     /// Record a usage event.
     pub fn record_usage(&self, usage: TokenUsage) -> Result<()> {
+        self.record_scoped_usage(usage, &UsageScope::default())
+    }
+
+    /// Record a usage event attributed to `scope`.
+    pub fn record_scoped_usage(&self, usage: TokenUsage, scope: &UsageScope) -> Result<()> {
         if !self.config.enabled {
             return Ok(());
         }
@@ -118,7 +222,7 @@ impl CostTracker {
             ));
         }
 
-        let record = CostRecord::new(&self.session_id, usage);
+        let record = CostRecord::new(&self.session_id, usage).with_scope(scope.clone());
 
         // Persist first for durability guarantees.
         {
@@ -162,6 +266,69 @@ impl CostTracker {
         })
     }
 
+    /// Spend per quota key for the current day and month, optionally
+    /// filtered by scope and key.
+    ///
+    /// Keys named by an exact-key quota are listed even before they have
+    /// any spend, so configured quotas are always visible.
+    pub fn usage_by_key(
+        &self,
+        scope: Option<CostQuotaScope>,
+        key: Option<&str>,
+    ) -> Result<Vec<KeyUsage>> {
+        let mut aggregates = {
+            let mut storage = self.lock_storage();
+            storage.ensure_period_cache_current()?;
+            storage.key_costs.clone()
+        };
+        for quota in self.config.quotas.iter().filter(|quota| quota.key != "*") {
+            aggregates
+                .entry((quota.scope, quota.key.clone()))
+                .or_default();
+        }
+
+        let mut usage: Vec<KeyUsage> = aggregates
+            .into_iter()
+            .filter(|((entry_scope, entry_key), _)| {
+                scope.is_none_or(|scope| scope == *entry_scope)
+                    && key.is_none_or(|key| key == entry_key)
+            })
+            .map(|((entry_scope, entry_key), aggregate)| {
+                let quota = self.quota_for(entry_scope, &entry_key);
+                KeyUsage {
+                    scope: entry_scope,
+                    daily_cost_usd: aggregate.daily_cost_usd,
+                    monthly_cost_usd: aggregate.monthly_cost_usd,
+                    request_count: aggregate.monthly_requests,
+                    daily_limit_usd: quota.and_then(|quota| quota.daily_limit_usd),
+                    monthly_limit_usd: quota.and_then(|quota| quota.monthly_limit_usd),
+                    key: entry_key,
+                }
+            })
+            .collect();
+        usage.sort_by(|a, b| {
+            a.scope
+                .as_str()
+                .cmp(b.scope.as_str())
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        Ok(usage)
+    }
+
+    /// Most specific quota for a key: an exact-key quota before a `*` one.
+    fn quota_for(&self, scope: CostQuotaScope, key: &str) -> Option<&CostQuotaConfig> {
+        let mut candidates = self
+            .config
+            .quotas
+            .iter()
+            .filter(|quota| quota.scope == scope && quota.matches(key));
+        let first = candidates.next()?;
+        if first.key == key {
+            return Some(first);
+        }
+        Some(candidates.find(|quota| quota.key == key).unwrap_or(first))
+    }
+
     /// Get the daily cost for a specific date.
     pub fn get_daily_cost(&self, date: NaiveDate) -> Result<f64> {
         let storage = self.lock_storage();
@@ -204,6 +371,67 @@ fn resolve_storage_path(workspace_dir: &Path) -> Result<PathBuf> {
     Ok(storage_path)
 }
 
+/// Compare spend (plus the estimated request) against optional daily and
+/// monthly limits.
+#[allow(clippy::too_many_arguments)]
+fn evaluate_limits(
+    daily_cost: f64,
+    monthly_cost: f64,
+    estimated_cost_usd: f64,
+    daily_limit_usd: Option<f64>,
+    monthly_limit_usd: Option<f64>,
+    warn_at_percent: u8,
+    quota: Option<QuotaKey>,
+    downgrade_hint: Option<String>,
+) -> BudgetCheck {
+    let periods = [
+        (UsagePeriod::Day, daily_cost, daily_limit_usd),
+        (UsagePeriod::Month, monthly_cost, monthly_limit_usd),
+    ];
+
+    for (period, current_usd, limit) in periods {
+        if let Some(limit_usd) = limit {
+            if current_usd + estimated_cost_usd > limit_usd {
+                return BudgetCheck::Exceeded {
+                    current_usd,
+                    limit_usd,
+                    period,
+                    quota,
+                };
+            }
+        }
+    }
+
+    let warn_threshold = f64::from(warn_at_percent.min(100)) / 100.0;
+    for (period, current_usd, limit) in periods {
+        if let Some(limit_usd) = limit {
+            if current_usd + estimated_cost_usd >= limit_usd * warn_threshold {
+                return BudgetCheck::Warning {
+                    current_usd,
+                    limit_usd,
+                    period,
+                    quota,
+                    downgrade_hint,
+                };
+            }
+        }
+    }
+
+    BudgetCheck::Allowed
+}
+
+fn budget_check_rank(check: &BudgetCheck) -> u8 {
+    match check {
+        BudgetCheck::Allowed => 0,
+        BudgetCheck::Warning {
+            downgrade_hint: None,
+            ..
+        } => 1,
+        BudgetCheck::Warning { .. } => 2,
+        BudgetCheck::Exceeded { .. } => 3,
+    }
+}
+
 fn build_session_model_stats(session_costs: &[CostRecord]) -> HashMap<String, ModelStats> {
     let mut by_model: HashMap<String, ModelStats> = HashMap::new();
 
@@ -225,11 +453,20 @@ fn build_session_model_stats(session_costs: &[CostRecord]) -> HashMap<String, Mo
     by_model
 }
 
+/// Current-period spend for one quota key.
+#[derive(Debug, Clone, Copy, Default)]
+struct KeyAggregate {
+    daily_cost_usd: f64,
+    monthly_cost_usd: f64,
+    monthly_requests: usize,
+}
+
 /// Persistent storage for cost records.
 struct CostStorage {
     path: PathBuf,
     daily_cost_usd: f64,
     monthly_cost_usd: f64,
+    key_costs: HashMap<(CostQuotaScope, String), KeyAggregate>,
     cached_day: NaiveDate,
     cached_year: i32,
     cached_month: u32,
@@ -248,6 +485,7 @@ impl CostStorage {
             path: path.to_path_buf(),
             daily_cost_usd: 0.0,
             monthly_cost_usd: 0.0,
+            key_costs: HashMap::new(),
             cached_day: now.date_naive(),
             cached_year: now.year(),
             cached_month: now.month(),
@@ -306,21 +544,27 @@ impl CostStorage {
     fn rebuild_aggregates(&mut self, day: NaiveDate, year: i32, month: u32) -> Result<()> {
         let mut daily_cost = 0.0;
         let mut monthly_cost = 0.0;
+        let mut key_costs = HashMap::new();
 
         self.for_each_record(|record| {
             let timestamp = record.usage.timestamp.naive_utc();
+            let in_day = timestamp.date() == day;
+            let in_month = timestamp.year() == year && timestamp.month() == month;
 
-            if timestamp.date() == day {
+            if in_day {
                 daily_cost += record.usage.cost_usd;
             }
 
-            if timestamp.year() == year && timestamp.month() == month {
+            if in_month {
                 monthly_cost += record.usage.cost_usd;
             }
+
+            add_key_costs(&mut key_costs, &record, in_day, in_month);
         })?;
 
         self.daily_cost_usd = daily_cost;
         self.monthly_cost_usd = monthly_cost;
+        self.key_costs = key_costs;
         self.cached_day = day;
         self.cached_year = year;
         self.cached_month = month;
@@ -357,16 +601,29 @@ impl CostStorage {
         self.ensure_period_cache_current()?;
 
         let timestamp = record.usage.timestamp.naive_utc();
-        if timestamp.date() == self.cached_day {
+        let in_day = timestamp.date() == self.cached_day;
+        let in_month =
+            timestamp.year() == self.cached_year && timestamp.month() == self.cached_month;
+        if in_day {
             self.daily_cost_usd += record.usage.cost_usd;
         }
-        if timestamp.year() == self.cached_year && timestamp.month() == self.cached_month {
+        if in_month {
             self.monthly_cost_usd += record.usage.cost_usd;
         }
+        add_key_costs(&mut self.key_costs, &record, in_day, in_month);
 
         Ok(())
     }
 
+    /// Current-period spend for one quota key. Callers refresh the period
+    /// cache first (see [`Self::get_aggregated_costs`]).
+    fn key_aggregate(&self, scope: CostQuotaScope, key: &str) -> KeyAggregate {
+        self.key_costs
+            .get(&(scope, key.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
     /// Get aggregated costs for current day and month.
     fn get_aggregated_costs(&mut self) -> Result<(f64, f64)> {
         self.ensure_period_cache_current()?;
@@ -401,6 +658,25 @@ impl CostStorage {
     }
 }
 
+fn add_key_costs(
+    key_costs: &mut HashMap<(CostQuotaScope, String), KeyAggregate>,
+    record: &CostRecord,
+    in_day: bool,
+    in_month: bool,
+) {
+    if !in_month {
+        return;
+    }
+    for (scope, key) in record.scope.keys() {
+        let aggregate = key_costs.entry((scope, key.to_string())).or_default();
+        aggregate.monthly_cost_usd += record.usage.cost_usd;
+        aggregate.monthly_requests += 1;
+        if in_day {
+            aggregate.daily_cost_usd += record.usage.cost_usd;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -533,4 +809,197 @@ mod tests {
             .to_string()
             .contains("Estimated cost must be a finite, non-negative value"));
     }
+
+    #[test]
+    fn request_cost_estimate_uses_model_tokenizer_and_pricing() {
+        let tmp = TempDir::new().unwrap();
+        let tracker = CostTracker::new(enabled_config(), tmp.path()).unwrap();
+        let messages = [ChatMessage::user("a".repeat(3500))];
+
+        // Anthropic approximation: 1000 content + 4 framing tokens at $3/M in,
+        // plus 1000 output tokens at $15/M.
+        let expected = 1004.0 * 3.0 / 1_000_000.0 + 1000.0 * 15.0 / 1_000_000.0;
+        for model in [
+            "anthropic/claude-sonnet-4-20250514",
+            "claude-sonnet-4-20250514",
+        ] {
+            let cost = tracker.estimate_request_cost(model, &messages, 1000);
+            assert!((cost - expected).abs() < 1e-12, "{model}: {cost}");
+        }
+
+        assert_eq!(
+            tracker.estimate_request_cost("unpriced-model", &messages, 1000),
+            0.0
+        );
+        assert!(matches!(
+            tracker
+                .check_request_budget("claude-sonnet-4-20250514", &messages, 1000)
+                .unwrap(),
+            BudgetCheck::Allowed
+        ));
+    }
+
+    fn quota(scope: CostQuotaScope, key: &str, daily: f64) -> CostQuotaConfig {
+        CostQuotaConfig {
+            scope,
+            key: key.into(),
+            daily_limit_usd: Some(daily),
+            monthly_limit_usd: None,
+            warn_at_percent: Some(50),
+            downgrade_hint: None,
+        }
+    }
+
+    fn channel_scope(channel: &str) -> UsageScope {
+        UsageScope {
+            channel: Some(channel.into()),
+            sender: Some("alice".into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn scoped_quota_warns_downgrades_and_blocks() {
+        let tmp = TempDir::new().unwrap();
+        let mut slack = quota(CostQuotaScope::Channel, "slack", 1.0);
+        slack.downgrade_hint = Some("fast".into());
+        let config = CostConfig {
+            enabled: true,
+            quotas: vec![slack],
+            ..Default::default()
+        };
+        let tracker = CostTracker::new(config, tmp.path()).unwrap();
+        let scope = channel_scope("slack");
+
+        assert!(matches!(
+            tracker.check_scoped_budget(0.1, &scope).unwrap(),
+            BudgetCheck::Allowed
+        ));
+
+        // $0.60 spent: past the 50% warning threshold, below the hard stop.
+        tracker
+            .record_scoped_usage(TokenUsage::new("m", 600_000, 0, 1.0, 0.0), &scope)
+            .unwrap();
+        match tracker.check_scoped_budget(0.1, &scope).unwrap() {
+            BudgetCheck::Warning {
+                quota,
+                downgrade_hint,
+                ..
+            } => {
+                assert_eq!(quota.unwrap().to_string(), "channel:slack");
+                assert_eq!(downgrade_hint.as_deref(), Some("fast"));
+            }
+            other => panic!("expected warning, got {other:?}"),
+        }
+
+        // Other channels are unaffected by the slack quota.
+        assert!(matches!(
+            tracker
+                .check_scoped_budget(0.1, &channel_scope("telegram"))
+                .unwrap(),
+            BudgetCheck::Allowed
+        ));
+
+        let check = tracker.check_scoped_budget(0.5, &scope).unwrap();
+        assert!(matches!(
+            check,
+            BudgetCheck::Exceeded {
+                quota: Some(_),
+                period: UsagePeriod::Day,
+                ..
+            }
+        ));
+        assert!(check.describe().unwrap().contains("quota channel:slack"));
+    }
+
+    #[test]
+    fn wildcard_quota_tracks_each_key_separately() {
+        let tmp = TempDir::new().unwrap();
+        let config = CostConfig {
+            enabled: true,
+            quotas: vec![quota(CostQuotaScope::Sender, "*", 1.0)],
+            ..Default::default()
+        };
+        let tracker = CostTracker::new(config, tmp.path()).unwrap();
+        let alice = channel_scope("slack");
+        let bob = UsageScope {
+            sender: Some("bob".into()),
+            ..Default::default()
+        };
+
+        tracker
+            .record_scoped_usage(TokenUsage::new("m", 900_000, 0, 1.0, 0.0), &alice)
+            .unwrap();
+        assert!(matches!(
+            tracker.check_scoped_budget(0.2, &alice).unwrap(),
+            BudgetCheck::Exceeded { .. }
+        ));
+        assert!(matches!(
+            tracker.check_scoped_budget(0.2, &bob).unwrap(),
+            BudgetCheck::Allowed
+        ));
+    }
+
+    #[test]
+    fn usage_by_key_reports_spend_and_limits() {
+        let tmp = TempDir::new().unwrap();
+        let config = CostConfig {
+            enabled: true,
+            quotas: vec![
+                quota(CostQuotaScope::Channel, "*", 5.0),
+                quota(CostQuotaScope::Channel, "slack", 1.0),
+                quota(CostQuotaScope::CronJob, "digest", 2.0),
+            ],
+            ..Default::default()
+        };
+        let tracker = CostTracker::new(config.clone(), tmp.path()).unwrap();
+        tracker
+            .record_scoped_usage(
+                TokenUsage::new("m", 100_000, 0, 1.0, 0.0),
+                &channel_scope("slack"),
+            )
+            .unwrap();
+        tracker
+            .record_scoped_usage(
+                TokenUsage::new("m", 200_000, 0, 1.0, 0.0),
+                &channel_scope("telegram"),
+            )
+            .unwrap();
+
+        let all = tracker.usage_by_key(None, None).unwrap();
+        let keys: Vec<String> = all
+            .iter()
+            .map(|usage| format!("{}:{}", usage.scope, usage.key))
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                "channel:slack",
+                "channel:telegram",
+                "cron_job:digest",
+                "sender:alice"
+            ]
+        );
+        assert_eq!(all[0].daily_limit_usd, Some(1.0));
+        assert_eq!(all[1].daily_limit_usd, Some(5.0));
+        assert_eq!(all[2].request_count, 0);
+        assert_eq!(all[3].request_count, 2);
+        assert!((all[3].monthly_cost_usd - 0.3).abs() < 1e-9);
+
+        let slack = tracker
+            .usage_by_key(Some(CostQuotaScope::Channel), Some("slack"))
+            .unwrap();
+        assert_eq!(slack.len(), 1);
+        assert!((slack[0].daily_cost_usd - 0.1).abs() < 1e-9);
+
+        // Aggregates are rebuilt from disk.
+        let reopened = CostTracker::new(config, tmp.path()).unwrap();
+        assert_eq!(
+            reopened
+                .usage_by_key(Some(CostQuotaScope::Sender), None)
+                .unwrap()[0]
+                .request_count,
+            2
+        );
+    }
 }
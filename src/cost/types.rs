@@ -1,3 +1,4 @@
+use crate::config::schema::CostQuotaScope;
 use serde::{Deserialize, Serialize};
 
 /// Token usage information from a single API call.
@@ -68,6 +69,50 @@ pub enum UsagePeriod {
     Month,
 }
 
+/// Who a usage event is attributed to, for scoped quotas.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageScope {
+    /// Channel the request came in on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// Sender identity on that channel
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>,
+    /// Delegate agent making the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// Cron job that triggered the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron_job: Option<String>,
+}
+
+impl UsageScope {
+    /// Whether no key is set.
+    pub fn is_empty(&self) -> bool {
+        self.channel.is_none()
+            && self.sender.is_none()
+            && self.agent.is_none()
+            && self.cron_job.is_none()
+    }
+
+    /// Key for `scope`, if this usage is attributed to one.
+    pub fn key(&self, scope: CostQuotaScope) -> Option<&str> {
+        match scope {
+            CostQuotaScope::Channel => self.channel.as_deref(),
+            CostQuotaScope::Sender => self.sender.as_deref(),
+            CostQuotaScope::Agent => self.agent.as_deref(),
+            CostQuotaScope::CronJob => self.cron_job.as_deref(),
+        }
+    }
+
+    /// All `(scope, key)` pairs this usage is attributed to.
+    pub fn keys(&self) -> impl Iterator<Item = (CostQuotaScope, &str)> {
+        CostQuotaScope::ALL
+            .into_iter()
+            .filter_map(|scope| self.key(scope).map(|key| (scope, key)))
+    }
+}
+
 /// A single cost record for persistent storage.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CostRecord {
@@ -77,6 +122,9 @@ pub struct CostRecord {
     pub usage: TokenUsage,
     /// Session identifier (for grouping)
     pub session_id: String,
+    /// Quota keys the usage is attributed to
+    #[serde(default, skip_serializing_if = "UsageScope::is_empty")]
+    pub scope: UsageScope,
 }
 
 impl CostRecord {
@@ -86,8 +134,29 @@ impl CostRecord {
             id: uuid::Uuid::new_v4().to_string(),
             usage,
             session_id: session_id.into(),
+            scope: UsageScope::default(),
         }
     }
+
+    /// Attribute the record to `scope`.
+    #[must_use]
+    pub fn with_scope(mut self, scope: UsageScope) -> Self {
+        self.scope = scope;
+        self
+    }
+}
+
+/// A scoped quota key, e.g. `channel:slack`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QuotaKey {
+    pub scope: CostQuotaScope,
+    pub key: String,
+}
+
+impl std::fmt::Display for QuotaKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.scope, self.key)
+    }
 }
 
 /// Budget enforcement result.
@@ -100,15 +169,71 @@ pub enum BudgetCheck {
         current_usd: f64,
         limit_usd: f64,
         period: UsagePeriod,
+        /// Scoped quota that triggered the warning (`None` for global limits)
+        quota: Option<QuotaKey>,
+        /// Route hint to downgrade to while over the warning threshold
+        downgrade_hint: Option<String>,
     },
     /// Budget exceeded, request blocked
     Exceeded {
         current_usd: f64,
         limit_usd: f64,
         period: UsagePeriod,
+        /// Scoped quota that was exceeded (`None` for global limits)
+        quota: Option<QuotaKey>,
     },
 }
 
+impl BudgetCheck {
+    /// Human-readable description of a warning or exceeded budget.
+    pub fn describe(&self) -> Option<String> {
+        let (verb, current_usd, limit_usd, period, quota) = match self {
+            Self::Allowed => return None,
+            Self::Warning {
+                current_usd,
+                limit_usd,
+                period,
+                quota,
+                ..
+            } => ("nearing", current_usd, limit_usd, period, quota),
+            Self::Exceeded {
+                current_usd,
+                limit_usd,
+                period,
+                quota,
+            } => ("exceeded", current_usd, limit_usd, period, quota),
+        };
+        let period = match period {
+            UsagePeriod::Session => "session",
+            UsagePeriod::Day => "daily",
+            UsagePeriod::Month => "monthly",
+        };
+        let subject = quota
+            .as_ref()
+            .map_or_else(|| "global".to_string(), |quota| format!("quota {quota}"));
+        Some(format!(
+            "Cost budget {verb}: {subject} {period} spend ${current_usd:.4} of ${limit_usd:.2} limit"
+        ))
+    }
+}
+
+/// Spend attributed to one quota key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyUsage {
+    pub scope: CostQuotaScope,
+    pub key: String,
+    /// Spend today (UTC)
+    pub daily_cost_usd: f64,
+    /// Spend this month (UTC)
+    pub monthly_cost_usd: f64,
+    /// Requests this month
+    pub request_count: usize,
+    /// Daily limit of the quota applying to this key, if any
+    pub daily_limit_usd: Option<f64>,
+    /// Monthly limit of the quota applying to this key, if any
+    pub monthly_limit_usd: Option<f64>,
+}
+
 /// Cost summary for reporting.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CostSummary {
@@ -189,5 +314,30 @@ mod tests {
         assert_eq!(record.session_id, "session-123");
         assert!(!record.id.is_empty());
         assert_eq!(record.usage.model, "test/model");
+        assert!(record.scope.is_empty());
+    }
+
+    #[test]
+    fn cost_record_scope_is_optional_on_disk() {
+        let usage = TokenUsage::new("test/model", 100, 50, 1.0, 2.0);
+        let plain = serde_json::to_string(&CostRecord::new("s", usage.clone())).unwrap();
+        assert!(!plain.contains("scope"));
+        let legacy: CostRecord = serde_json::from_str(&plain).unwrap();
+        assert!(legacy.scope.is_empty());
+
+        let scoped = CostRecord::new("s", usage).with_scope(UsageScope {
+            channel: Some("slack".into()),
+            cron_job: Some("digest".into()),
+            ..Default::default()
+        });
+        let parsed: CostRecord =
+            serde_json::from_str(&serde_json::to_string(&scoped).unwrap()).unwrap();
+        assert_eq!(
+            parsed.scope.keys().collect::<Vec<_>>(),
+            vec![
+                (CostQuotaScope::Channel, "slack"),
+                (CostQuotaScope::CronJob, "digest")
+            ]
+        );
     }
 }
@@ -179,19 +179,27 @@ async fn run_agent_job(
     let prefixed_prompt = format!("[cron:{} {name}] {prompt}", job.id);
     let model_override = job.model.clone();
 
+    let usage_scope = crate::cost::UsageScope {
+        cron_job: Some(job.name.clone().unwrap_or_else(|| job.id.clone())),
+        ..Default::default()
+    };
+
     let run_result = match job.session_target {
         SessionTarget::Main | SessionTarget::Isolated => {
-            Box::pin(crate::agent::run(
-                config.clone(),
-                Some(prefixed_prompt),
-                None,
-                model_override,
-                config.default_temperature,
-                vec![],
-                false,
-                None,
-                job.allowed_tools.clone(),
-            ))
+            crate::cost::scope::with_scope(
+                usage_scope,
+                Box::pin(crate::agent::run(
+                    config.clone(),
+                    Some(prefixed_prompt),
+                    None,
+                    model_override,
+                    config.default_temperature,
+                    vec![],
+                    false,
+                    None,
+                    job.allowed_tools.clone(),
+                )),
+            )
             .await
         }
     };
@@ -62,6 +62,24 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
 
     let mut handles: Vec<JoinHandle<()>> = vec![spawn_state_writer(config.clone())];
 
+    if crate::nodes::mesh::mesh_enabled(&config.node_transport) {
+        // Learn the peers' tools before the gateway and channels build
+        // their tool registries, then keep the directory fresh.
+        if let Some(client) = crate::nodes::mesh::MeshClient::from_config(&config.node_transport) {
+            client.refresh().await;
+        }
+        let transport_cfg = config.node_transport.clone();
+        handles.push(spawn_component_supervisor(
+            "mesh",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = transport_cfg.clone();
+                async move { crate::nodes::mesh::run_mesh_sync(cfg).await }
+            },
+        ));
+    }
+
     {
         let gateway_cfg = config.clone();
         let gateway_host = host.clone();
@@ -108,6 +126,19 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         ));
     }
 
+    if uses_llama_server(&config) {
+        let server = crate::providers::llama_server::LlamaServer::shared(&config.llama_server)?;
+        handles.push(spawn_component_supervisor(
+            "llama-server",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let server = std::sync::Arc::clone(&server);
+                async move { server.supervise().await }
+            },
+        ));
+    }
+
     if config.cron.enabled {
         let scheduler_cfg = config.clone();
         handles.push(spawn_component_supervisor(
@@ -124,6 +155,19 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
         tracing::info!("Cron disabled; scheduler supervisor not started");
     }
 
+    if config.sop.enabled {
+        let sop_cfg = config.clone();
+        handles.push(spawn_component_supervisor(
+            "sop",
+            initial_backoff,
+            max_backoff,
+            move || {
+                let cfg = sop_cfg.clone();
+                async move { Box::pin(crate::sop::runtime::run(cfg)).await }
+            },
+        ));
+    }
+
     println!("🧠 ZeroClaw daemon started");
     println!("   Gateway:  http://{host}:{port}");
     println!("   Components: gateway, channels, heartbeat, scheduler");
@@ -142,10 +186,26 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     for handle in handles {
         let _ = handle.await;
     }
+    crate::providers::llama_server::stop_all().await;
 
     Ok(())
 }
 
+/// Whether the default provider or any model route runs on the managed
+/// llama-server, in which case the daemon owns the model process.
+fn uses_llama_server(config: &Config) -> bool {
+    use crate::providers::llama_server::is_llama_server_provider;
+
+    config
+        .default_provider
+        .as_deref()
+        .is_some_and(is_llama_server_provider)
+        || config
+            .model_routes
+            .iter()
+            .any(|route| is_llama_server_provider(&route.provider))
+}
+
 pub fn state_file_path(config: &Config) -> PathBuf {
     config
         .config_path
@@ -626,6 +686,24 @@ mod tests {
             .contains("component exited unexpectedly"));
     }
 
+    #[test]
+    fn detects_llama_server_from_default_provider_or_routes() {
+        let mut config = Config::default();
+        assert!(!uses_llama_server(&config));
+
+        config.default_provider = Some("llama-server".into());
+        assert!(uses_llama_server(&config));
+
+        config.default_provider = Some("openrouter".into());
+        config.model_routes.push(crate::config::ModelRouteConfig {
+            hint: "local".into(),
+            provider: "llama_server".into(),
+            model: "qwen".into(),
+            api_key: None,
+        });
+        assert!(uses_llama_server(&config));
+    }
+
     #[test]
     fn detects_no_supervised_channels() {
         let config = Config::default();
@@ -66,6 +66,19 @@ pub fn format_user_action(payload: &Value) -> String {
     )
 }
 
+/// Extract the approval callback payload from a tool-approval card action.
+///
+/// Approval cards (see `LisaChannel::send_approval_prompt`) put the callback
+/// under `context.approval`; it is forwarded as-is so the channel dispatcher
+/// can resolve the pending prompt.
+pub fn approval_callback(payload: &Value) -> Option<&str> {
+    payload
+        .get("context")
+        .and_then(|context| context.get("approval"))
+        .and_then(Value::as_str)
+        .filter(|value| value.starts_with(crate::channels::traits::APPROVAL_CALLBACK_PREFIX))
+}
+
 // ── Internal helpers ─────────────────────────────────────────
 
 /// Google official A2UI v0.9 XML-style tags.
@@ -555,6 +568,20 @@ mod tests {
         assert_eq!(parsed["context"]["answer"], "a");
     }
 
+    #[test]
+    fn approval_callback_reads_card_context() {
+        let payload = serde_json::json!({
+            "surfaceId": "approval-r1",
+            "name": "approval",
+            "sourceComponentId": "deny",
+            "context": {"approval": "zc-approval:r1:no"}
+        });
+        assert_eq!(approval_callback(&payload), Some("zc-approval:r1:no"));
+
+        let other = serde_json::json!({"name": "submit", "context": {"approval": "yes"}});
+        assert_eq!(approval_callback(&other), None);
+    }
+
     #[test]
     fn format_user_action_includes_data_model() {
         let payload = serde_json::json!({
@@ -64,6 +64,12 @@ pub struct CronRunsQuery {
     pub limit: Option<u32>,
 }
 
+#[derive(Deserialize)]
+pub struct CostQuery {
+    pub scope: Option<String>,
+    pub key: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct CronAddBody {
     pub name: Option<String>,
@@ -547,18 +553,43 @@ pub async fn handle_api_memory_delete(
     }
 }
 
-/// GET /api/cost — cost summary
+/// GET /api/cost — cost summary plus per-key quota usage
+/// (`?scope=channel|sender|agent|cron_job&key=...` filters the usage list)
 pub async fn handle_api_cost(
     State(state): State<AppState>,
     headers: HeaderMap,
+    Query(params): Query<CostQuery>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
 
+    let scope = match params
+        .scope
+        .as_deref()
+        .map(str::parse::<crate::config::CostQuotaScope>)
+        .transpose()
+    {
+        Ok(scope) => scope,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({"error": e.to_string()})),
+            )
+                .into_response();
+        }
+    };
+
     if let Some(ref tracker) = state.cost_tracker {
-        match tracker.get_summary() {
-            Ok(summary) => Json(serde_json::json!({"cost": summary})).into_response(),
+        let result = tracker.get_summary().and_then(|summary| {
+            tracker
+                .usage_by_key(scope, params.key.as_deref())
+                .map(|usage| (summary, usage))
+        });
+        match result {
+            Ok((summary, usage)) => {
+                Json(serde_json::json!({"cost": summary, "usage": usage})).into_response()
+            }
             Err(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(serde_json::json!({"error": format!("Cost summary failed: {e}")})),
@@ -574,7 +605,8 @@ pub async fn handle_api_cost(
                 "total_tokens": 0,
                 "request_count": 0,
                 "by_model": {},
-            }
+            },
+            "usage": [],
         }))
         .into_response()
     }
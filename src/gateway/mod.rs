@@ -10,7 +10,9 @@
 pub mod a2ui;
 pub mod api;
 pub mod api_pairing;
+pub mod node_control;
 pub mod nodes;
+mod openai_compat;
 mod openclaw_compat;
 pub mod sse;
 pub mod static_files;
@@ -392,6 +394,8 @@ pub struct AppState {
     pub pending_pairings: Option<Arc<api_pairing::PairingStore>>,
     /// Root directory for a2web rendered pages (`{zeroclaw_dir}/web/`).
     pub a2web_dir: Option<std::path::PathBuf>,
+    /// Federated mesh endpoint serving `/api/node-control/*` (when peers are configured)
+    pub mesh: Option<Arc<crate::nodes::mesh::MeshServer>>,
 }
 
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
@@ -438,6 +442,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             reasoning_level: config.runtime.reasoning_level.clone(),
             custom_provider_auth_header: config.custom_provider_auth_header.clone(),
             service_tier: config.runtime.service_tier.clone(),
+            llama_server: config.llama_server.clone(),
         },
     )?);
     let model = config
@@ -490,7 +495,11 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             crate::skills::load_skills_with_config(&config.workspace_dir, &config),
             None,
         );
-        let skill_tools = crate::skills::create_skill_tools(&skills_for_tools, security.clone());
+        let skill_tools = crate::skills::create_skill_tools(
+            &skills_for_tools,
+            security.clone(),
+            &config.wasm,
+        );
         if !skill_tools.is_empty() {
             tracing::info!(count = skill_tools.len(), "WS skill tools registered");
             tools_registry_raw.extend(skill_tools);
@@ -518,8 +527,20 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     let tools_registry: Arc<Vec<ToolSpec>> =
         Arc::new(tools_registry_raw.iter().map(|t| t.spec()).collect());
 
-    // Cost tracker (optional)
-    let cost_tracker = if config.cost.enabled {
+    // Peers invoke the same tool set the gateway agent sees, filtered by
+    // their per-peer allowlists.
+    let mesh = crate::nodes::mesh::MeshServer::from_config(
+        &config.node_transport,
+        &crate::sop::resolve_sops_dir(&config.workspace_dir, config.sop.sops_dir.as_deref()),
+        tools_registry_raw,
+    )
+    .map(Arc::new);
+
+    // Cost tracker (optional); share the process-wide one so quota spend
+    // recorded by the agent loop is visible here.
+    let cost_tracker = if let Some(tracker) = crate::cost::scope::global_tracker() {
+        Some(tracker)
+    } else if config.cost.enabled {
         match CostTracker::new(config.cost.clone(), &config.workspace_dir) {
             Ok(ct) => Some(Arc::new(ct)),
             Err(e) => {
@@ -845,6 +866,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         } else {
             None
         },
+        mesh,
     };
 
     // Config PUT needs larger body limit (1MB)
@@ -871,6 +893,13 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/nextcloud-talk", post(handle_nextcloud_talk_webhook))
         // ── Tools-enabled chat endpoint (agent loop) ──
         .route("/api/chat", post(openclaw_compat::handle_api_chat))
+        // ── OpenAI-compatible API (agent loop, models, embeddings) ──
+        .route(
+            "/v1/chat/completions",
+            post(openclaw_compat::handle_v1_chat_completions_with_tools),
+        )
+        .route("/v1/models", get(openai_compat::handle_v1_models))
+        .route("/v1/embeddings", post(openai_compat::handle_v1_embeddings))
         // ── Web Dashboard API routes ──
         .route("/api/status", get(api::handle_api_status))
         .route("/api/config", get(api::handle_api_config_get))
@@ -907,8 +936,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         )
         // ── SSE event stream ──
         .route("/api/events", get(sse::handle_sse_events))
+        .route("/api/events/turns", get(sse::handle_sse_turn_events))
         // ── WebSocket agent chat ──
         .route("/ws/chat", get(ws::handle_ws_chat))
+        .route("/ws/events", get(ws::handle_ws_turn_events))
         // ── Lisa WebSocket channel ──
         .route(&lisa_path, get(handle_lisa_ws))
         // ── A2Web rendered pages ──
@@ -916,6 +947,11 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/web/{id}", get(handle_a2web_redirect))
         // ── WebSocket node discovery ──
         .route("/ws/nodes", get(nodes::handle_ws_nodes))
+        // ── Federated mesh (signed peer calls) ──
+        .route(
+            "/api/node-control/{endpoint}",
+            post(node_control::handle_node_control),
+        )
         // ── Static assets (web dashboard) ──
         .route("/_app/{*path}", get(static_files::handle_static))
         // ── Config PUT with larger body limit ──
@@ -1176,19 +1212,17 @@ async fn handle_webhook(
     }
 
     // ── Bearer token auth (pairing) ──
-    if state.pairing.require_pairing() {
-        let auth = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-        let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            tracing::warn!("Webhook: rejected — not paired / invalid bearer token");
-            let err = serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err));
-        }
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("");
+    if state.pairing.require_pairing() && !state.pairing.is_authenticated(token) {
+        tracing::warn!("Webhook: rejected — not paired / invalid bearer token");
+        let err = serde_json::json!({
+            "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
+        });
+        return (StatusCode::UNAUTHORIZED, Json(err));
     }
 
     // ── Webhook secret auth (optional, additional layer) ──
@@ -1242,6 +1276,16 @@ async fn handle_webhook(
     let message = &webhook_body.message;
     let session_id = webhook_session_id(&headers);
 
+    // ── Profile selected by bearer token (`[profiles.<name>].gateway_tokens`) ──
+    let profile = state
+        .config
+        .lock()
+        .profile_for_gateway_token(token)
+        .map(str::to_string);
+    if let Some(profile) = profile {
+        return run_profile_webhook(&state, &profile, message, session_id.as_deref()).await;
+    }
+
     if state.auto_save && !memory::should_skip_autosave_content(message) {
         let key = webhook_memory_key();
         let _ = state
@@ -1349,6 +1393,41 @@ async fn handle_webhook(
     }
 }
 
+/// Webhook turn for a profile: runs the full agent with the profile's
+/// workspace, memory and tools instead of the shared gateway provider.
+async fn run_profile_webhook(
+    state: &AppState,
+    profile: &str,
+    message: &str,
+    session_id: Option<&str>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let config = match state.config.lock().for_profile(profile) {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("Webhook profile '{profile}' unavailable: {e:#}");
+            let err = serde_json::json!({"error": "Profile unavailable"});
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(err));
+        }
+    };
+    let model = config.default_model.clone();
+    match Box::pin(crate::agent::process_message(config, message, session_id)).await {
+        Ok(response) => {
+            let body = serde_json::json!({
+                "response": response,
+                "model": model,
+                "profile": profile,
+            });
+            (StatusCode::OK, Json(body))
+        }
+        Err(e) => {
+            let sanitized = providers::sanitize_api_error(&e.to_string());
+            tracing::error!("Webhook profile '{profile}' error: {sanitized}");
+            let err = serde_json::json!({"error": "LLM request failed"});
+            (StatusCode::INTERNAL_SERVER_ERROR, Json(err))
+        }
+    }
+}
+
 /// `WhatsApp` verification query params
 #[derive(serde::Deserialize)]
 pub struct WhatsAppVerifyQuery {
@@ -1490,7 +1569,7 @@ async fn handle_whatsapp_message(
 
         match Box::pin(run_gateway_chat_with_tools(
             &state,
-            &msg.content,
+            &msg.agent_content(),
             Some(&session_id),
         ))
         .await
@@ -1610,7 +1689,7 @@ async fn handle_linq_webhook(
         // Call the LLM
         match Box::pin(run_gateway_chat_with_tools(
             &state,
-            &msg.content,
+            &msg.agent_content(),
             Some(&session_id),
         ))
         .await
@@ -1714,7 +1793,7 @@ async fn handle_wati_webhook(State(state): State<AppState>, body: Bytes) -> impl
         // Call the LLM
         match Box::pin(run_gateway_chat_with_tools(
             &state,
-            &msg.content,
+            &msg.agent_content(),
             Some(&session_id),
         ))
         .await
@@ -1830,7 +1909,7 @@ async fn handle_nextcloud_talk_webhook(
 
         match Box::pin(run_gateway_chat_with_tools(
             &state,
-            &msg.content,
+            &msg.agent_content(),
             Some(&session_id),
         ))
         .await
@@ -1962,16 +2041,23 @@ async fn handle_lisa_socket(
         };
 
         let msg_type = parsed["type"].as_str().unwrap_or("");
-        let content = match msg_type {
+        let (content, attachments) = match msg_type {
             "message" => {
+                let attachments = crate::channels::lisa::LisaChannel::client_attachments(&parsed);
                 let c = parsed["content"].as_str().unwrap_or("").to_string();
-                if c.is_empty() {
+                if c.is_empty() && attachments.is_empty() {
                     continue;
                 }
-                c
+                (c, attachments)
             }
             "a2ui_action" => match parsed.get("payload") {
-                Some(payload) => crate::gateway::a2ui::format_user_action(payload),
+                Some(payload) => (
+                    crate::gateway::a2ui::approval_callback(payload).map_or_else(
+                        || crate::gateway::a2ui::format_user_action(payload),
+                        str::to_string,
+                    ),
+                    Vec::new(),
+                ),
                 None => continue,
             },
             _ => continue,
@@ -1990,6 +2076,7 @@ async fn handle_lisa_socket(
             channel: "lisa".to_string(),
             timestamp: now_secs,
             thread_ts: None,
+            attachments,
         });
     }
 
@@ -2189,7 +2276,7 @@ mod tests {
             session_backend: None,
             device_registry: None,
             pending_pairings: None,
-            a2web_dir: None, lisa: None,
+            a2web_dir: None, lisa: None, mesh: None,
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -2245,7 +2332,7 @@ mod tests {
             session_backend: None,
             device_registry: None,
             pending_pairings: None,
-            a2web_dir: None, lisa: None,
+            a2web_dir: None, lisa: None, mesh: None,
         };
 
         let response = handle_metrics(State(state)).await.into_response();
@@ -2452,6 +2539,7 @@ mod tests {
             channel: "whatsapp".into(),
             timestamp: 1,
             thread_ts: None,
+            attachments: Vec::new(),
         };
 
         let key = whatsapp_memory_key(&msg);
@@ -2625,7 +2713,7 @@ mod tests {
             session_backend: None,
             device_registry: None,
             pending_pairings: None,
-            a2web_dir: None, lisa: None,
+            a2web_dir: None, lisa: None, mesh: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -2695,7 +2783,7 @@ mod tests {
             session_backend: None,
             device_registry: None,
             pending_pairings: None,
-            a2web_dir: None, lisa: None,
+            a2web_dir: None, lisa: None, mesh: None,
         };
 
         let headers = HeaderMap::new();
@@ -2777,7 +2865,7 @@ mod tests {
             session_backend: None,
             device_registry: None,
             pending_pairings: None,
-            a2web_dir: None, lisa: None,
+            a2web_dir: None, lisa: None, mesh: None,
         };
 
         let response = handle_webhook(
@@ -2831,7 +2919,7 @@ mod tests {
             session_backend: None,
             device_registry: None,
             pending_pairings: None,
-            a2web_dir: None, lisa: None,
+            a2web_dir: None, lisa: None, mesh: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -2890,7 +2978,7 @@ mod tests {
             session_backend: None,
             device_registry: None,
             pending_pairings: None,
-            a2web_dir: None, lisa: None,
+            a2web_dir: None, lisa: None, mesh: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -2954,7 +3042,7 @@ mod tests {
             session_backend: None,
             device_registry: None,
             pending_pairings: None,
-            a2web_dir: None, lisa: None,
+            a2web_dir: None, lisa: None, mesh: None,
         };
 
         let response = Box::pin(handle_nextcloud_talk_webhook(
@@ -3014,7 +3102,7 @@ mod tests {
             session_backend: None,
             device_registry: None,
             pending_pairings: None,
-            a2web_dir: None, lisa: None,
+            a2web_dir: None, lisa: None, mesh: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -0,0 +1,90 @@
+//! Inbound side of the federated daemon mesh: `POST /api/node-control/{endpoint}`.
+//!
+//! Peers call these endpoints with requests signed by
+//! [`crate::nodes::NodeTransport`]. Signature and replay checks, per-peer
+//! allowlists and execution live in [`crate::nodes::mesh::MeshServer`]; this
+//! module only maps them onto HTTP. Pairing tokens are not used here — the
+//! shared secret is the credential.
+
+use super::{client_key_from_request, AppState};
+use crate::nodes::mesh::MeshRejection;
+use axum::{
+    body::Bytes,
+    extract::{ConnectInfo, Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use std::net::{IpAddr, SocketAddr};
+
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    let err = serde_json::json!({ "error": message.into() });
+    (status, Json(err)).into_response()
+}
+
+fn rejection_response(rejection: MeshRejection) -> Response {
+    let (status, message) = match rejection {
+        MeshRejection::Unauthorized(message) => (StatusCode::UNAUTHORIZED, message),
+        MeshRejection::Forbidden(message) => (StatusCode::FORBIDDEN, message),
+        MeshRejection::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+        MeshRejection::NotFound(message) => (StatusCode::NOT_FOUND, message),
+    };
+    error_response(status, message)
+}
+
+/// POST /api/node-control/{endpoint} — signed call from a mesh peer
+pub async fn handle_node_control(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    Path(endpoint): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let Some(mesh) = state.mesh.clone() else {
+        return error_response(StatusCode::NOT_FOUND, "Node mesh is not enabled");
+    };
+
+    let client_key =
+        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&client_key) {
+        tracing::warn!("/api/node-control rate limit exceeded");
+        return error_response(StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded");
+    }
+    if !client_key
+        .parse::<IpAddr>()
+        .is_ok_and(|ip| mesh.ip_allowed(ip))
+    {
+        tracing::warn!("/api/node-control: rejected {client_key} (not in allowed_peers)");
+        return error_response(StatusCode::FORBIDDEN, "Address not allowed");
+    }
+
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("")
+    };
+    let (request, peer) = match mesh.authenticate(
+        &endpoint,
+        &body,
+        header("X-ZeroClaw-Timestamp"),
+        header("X-ZeroClaw-Nonce"),
+        header("X-ZeroClaw-Signature"),
+    ) {
+        Ok(authenticated) => authenticated,
+        Err(rejection) => {
+            tracing::warn!("/api/node-control/{endpoint}: rejected — {rejection:?}");
+            return rejection_response(rejection);
+        }
+    };
+
+    match mesh.handle(peer, request.call).await {
+        Ok(value) => Json(value).into_response(),
+        Err(rejection) => {
+            tracing::warn!(
+                "/api/node-control/{endpoint}: peer '{}' rejected — {rejection:?}",
+                peer.name
+            );
+            rejection_response(rejection)
+        }
+    }
+}
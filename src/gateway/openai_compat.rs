@@ -0,0 +1,473 @@
+//! OpenAI-compatible API surface for off-the-shelf clients and IDE plugins.
+//!
+//! - **`GET /v1/models`** — the default model plus one `hint:<name>` id per
+//!   `[[model_routes]]` entry.
+//! - **`POST /v1/embeddings`** — vectors from the `[memory]` embedding provider.
+//! - **`POST /v1/chat/completions`** — the full agent loop, served by
+//!   [`super::openclaw_compat::handle_v1_chat_completions_with_tools`].
+//!
+//! Clients send the pairing token as their API key (`Authorization: Bearer
+//! <token>`). A token listed in `[profiles.<name>].gateway_tokens` is served
+//! by that profile's configuration.
+
+use super::{client_key_from_request, AppState};
+use crate::config::Config;
+use crate::providers;
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+};
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+
+/// Maximum number of inputs accepted by one `/v1/embeddings` request.
+const MAX_EMBEDDING_INPUTS: usize = 256;
+
+/// OpenAI-style error response: `{"error": {"message", "type", "code"}}`.
+pub(super) fn openai_error(
+    status: StatusCode,
+    message: impl Into<String>,
+    kind: &str,
+    code: &str,
+) -> Response {
+    let err = serde_json::json!({
+        "error": {
+            "message": message.into(),
+            "type": kind,
+            "code": code
+        }
+    });
+    (status, Json(err)).into_response()
+}
+
+/// Rate-limit and authenticate a `/v1/*` request.
+///
+/// Returns the profile selected by the bearer token, if any.
+#[allow(clippy::result_large_err)] // the error is the finished response
+pub(super) fn authorize(
+    state: &AppState,
+    peer_addr: SocketAddr,
+    headers: &HeaderMap,
+) -> Result<Option<String>, Response> {
+    let rate_key = client_key_from_request(Some(peer_addr), headers, state.trust_forwarded_headers);
+    if !state.rate_limiter.allow_webhook(&rate_key) {
+        tracing::warn!("/v1 rate limit exceeded");
+        return Err(openai_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            "Rate limit exceeded. Please retry later.",
+            "rate_limit_error",
+            "rate_limit_exceeded",
+        ));
+    }
+
+    let token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|auth| auth.strip_prefix("Bearer "))
+        .unwrap_or("");
+    if state.pairing.require_pairing() && !state.pairing.is_authenticated(token) {
+        tracing::warn!("/v1: rejected — not paired / invalid bearer token");
+        return Err(openai_error(
+            StatusCode::UNAUTHORIZED,
+            "Invalid API key. Pair first via POST /pair, then use Authorization: Bearer <token>",
+            "invalid_request_error",
+            "invalid_api_key",
+        ));
+    }
+
+    Ok(state
+        .config
+        .lock()
+        .profile_for_gateway_token(token)
+        .map(String::from))
+}
+
+/// Configuration serving a `/v1/*` request: the profile's, or the top level.
+#[allow(clippy::result_large_err)] // the error is the finished response
+pub(super) fn request_config(state: &AppState, profile: Option<&str>) -> Result<Config, Response> {
+    let config = state.config.lock();
+    let Some(profile) = profile else {
+        return Ok(config.clone());
+    };
+    config.for_profile(profile).map_err(|e| {
+        tracing::error!("/v1 profile '{profile}' unavailable: {e:#}");
+        openai_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Profile unavailable",
+            "server_error",
+            "profile_unavailable",
+        )
+    })
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// /v1/models
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Serialize)]
+struct OaiModel {
+    id: String,
+    object: &'static str,
+    created: u64,
+    owned_by: String,
+}
+
+#[derive(Debug, Serialize)]
+struct OaiModelList {
+    object: &'static str,
+    data: Vec<OaiModel>,
+}
+
+/// Model ids clients may request, with the provider serving each:
+/// `default_model` first, then `hint:<name>` for every model route.
+pub(super) fn model_ids(config: &Config, default_model: &str) -> Vec<(String, String)> {
+    let default_provider = config
+        .default_provider
+        .clone()
+        .unwrap_or_else(|| "openrouter".to_string());
+    let mut ids = vec![(default_model.to_string(), default_provider)];
+    for route in &config.model_routes {
+        let id = format!("hint:{}", route.hint);
+        if !ids.iter().any(|(existing, _)| *existing == id) {
+            ids.push((id, route.provider.clone()));
+        }
+    }
+    ids
+}
+
+/// Map a requested model onto one the agent can serve.
+///
+/// Listed ids are used as-is and a route's underlying model name selects its
+/// hint. Anything else — clients often hard-code `gpt-4o` — falls back to
+/// the default model.
+pub(super) fn resolve_model(
+    config: &Config,
+    default_model: &str,
+    requested: Option<&str>,
+) -> String {
+    let Some(requested) = requested.map(str::trim).filter(|m| !m.is_empty()) else {
+        return default_model.to_string();
+    };
+    if requested == default_model {
+        return requested.to_string();
+    }
+    config
+        .model_routes
+        .iter()
+        .find(|route| requested.strip_prefix("hint:") == Some(route.hint.as_str()))
+        .or_else(|| {
+            config
+                .model_routes
+                .iter()
+                .find(|route| route.model == requested)
+        })
+        .map_or_else(
+            || default_model.to_string(),
+            |route| format!("hint:{}", route.hint),
+        )
+}
+
+/// `GET /v1/models` — default model and route hints as OpenAI model objects.
+pub async fn handle_v1_models(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> Response {
+    let profile = match authorize(&state, peer_addr, &headers) {
+        Ok(profile) => profile,
+        Err(response) => return response,
+    };
+    let config = match request_config(&state, profile.as_deref()) {
+        Ok(config) => config,
+        Err(response) => return response,
+    };
+    let default_model = config
+        .default_model
+        .clone()
+        .unwrap_or_else(|| state.model.clone());
+
+    let created = super::openclaw_compat::unix_timestamp();
+    let data = model_ids(&config, &default_model)
+        .into_iter()
+        .map(|(id, owned_by)| OaiModel {
+            id,
+            object: "model",
+            created,
+            owned_by,
+        })
+        .collect();
+    Json(OaiModelList {
+        object: "list",
+        data,
+    })
+    .into_response()
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// /v1/embeddings
+// ══════════════════════════════════════════════════════════════════════════════
+
+/// `input` is a single string or an array of strings.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum OaiEmbeddingInput {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OaiEmbeddingInput {
+    fn into_vec(self) -> Vec<String> {
+        match self {
+            Self::One(text) => vec![text],
+            Self::Many(texts) => texts,
+        }
+    }
+}
+
+/// OpenAI-compatible embeddings request body.
+#[derive(Debug, Deserialize)]
+pub struct OaiEmbeddingRequest {
+    pub input: OaiEmbeddingInput,
+    /// `hint:<name>` selects an `[[embedding_routes]]` entry; other values
+    /// use the `[memory]` embedding model.
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub encoding_format: Option<String>,
+    // Accept and ignore other OpenAI params for compat
+    #[serde(default)]
+    pub dimensions: Option<usize>,
+    #[serde(default)]
+    pub user: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OaiEmbedding {
+    object: &'static str,
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[derive(Debug, Serialize)]
+struct OaiEmbeddingUsage {
+    prompt_tokens: u32,
+    total_tokens: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct OaiEmbeddingList {
+    object: &'static str,
+    data: Vec<OaiEmbedding>,
+    model: String,
+    usage: OaiEmbeddingUsage,
+}
+
+/// `POST /v1/embeddings` — embed inputs with the memory embedding provider.
+///
+/// Vectors share the space of stored memories, so clients can compare them
+/// against `memory_recall` results or build their own index.
+pub async fn handle_v1_embeddings(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Result<Json<OaiEmbeddingRequest>, axum::extract::rejection::JsonRejection>,
+) -> Response {
+    let profile = match authorize(&state, peer_addr, &headers) {
+        Ok(profile) => profile,
+        Err(response) => return response,
+    };
+
+    let Json(request) = match body {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!("/v1/embeddings JSON parse error: {e}");
+            return openai_error(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid JSON body: {e}"),
+                "invalid_request_error",
+                "invalid_json",
+            );
+        }
+    };
+
+    if request
+        .encoding_format
+        .as_deref()
+        .is_some_and(|format| format != "float")
+    {
+        return openai_error(
+            StatusCode::BAD_REQUEST,
+            "Only encoding_format \"float\" is supported",
+            "invalid_request_error",
+            "unsupported_encoding_format",
+        );
+    }
+
+    let inputs = request.input.into_vec();
+    if inputs.is_empty() || inputs.len() > MAX_EMBEDDING_INPUTS {
+        return openai_error(
+            StatusCode::BAD_REQUEST,
+            format!("input must contain 1 to {MAX_EMBEDDING_INPUTS} strings"),
+            "invalid_request_error",
+            "invalid_input",
+        );
+    }
+
+    let mut config = match request_config(&state, profile.as_deref()) {
+        Ok(config) => config,
+        Err(response) => return response,
+    };
+    if let Some(hint) = request.model.as_deref().filter(|m| m.starts_with("hint:")) {
+        config.memory.embedding_model = hint.to_string();
+    }
+
+    let embedder = crate::memory::create_embedder(
+        &config.memory,
+        &config.embedding_routes,
+        config.api_key.as_deref(),
+    );
+    if embedder.dimensions() == 0 {
+        return openai_error(
+            StatusCode::NOT_IMPLEMENTED,
+            "No embedding provider configured — set [memory].embedding_provider",
+            "invalid_request_error",
+            "embeddings_unavailable",
+        );
+    }
+
+    let texts: Vec<&str> = inputs.iter().map(String::as_str).collect();
+    let vectors = match embedder.embed(&texts).await {
+        Ok(vectors) if vectors.len() == texts.len() => vectors,
+        Ok(vectors) => {
+            tracing::error!(
+                "/v1/embeddings: provider returned {} vectors for {} inputs",
+                vectors.len(),
+                texts.len()
+            );
+            return openai_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Embedding request failed",
+                "server_error",
+                "provider_error",
+            );
+        }
+        Err(e) => {
+            let sanitized = providers::sanitize_api_error(&e.to_string());
+            tracing::error!("/v1/embeddings provider error: {sanitized}");
+            return openai_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Embedding request failed",
+                "server_error",
+                "provider_error",
+            );
+        }
+    };
+
+    let model = config.memory.embedding_model.clone();
+    let tokenizer = providers::tokenizer::tokenizer_for_model(&model);
+    let prompt_tokens: usize = texts.iter().map(|text| tokenizer.count(text)).sum();
+    #[allow(clippy::cast_possible_truncation)]
+    let prompt_tokens = prompt_tokens as u32;
+
+    let data = vectors
+        .into_iter()
+        .enumerate()
+        .map(|(index, embedding)| OaiEmbedding {
+            object: "embedding",
+            index,
+            embedding,
+        })
+        .collect();
+    Json(OaiEmbeddingList {
+        object: "list",
+        data,
+        model,
+        usage: OaiEmbeddingUsage {
+            prompt_tokens,
+            total_tokens: prompt_tokens,
+        },
+    })
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ModelRouteConfig;
+
+    fn config_with_routes() -> Config {
+        Config {
+            default_provider: Some("anthropic".into()),
+            model_routes: vec![
+                ModelRouteConfig {
+                    hint: "fast".into(),
+                    provider: "groq".into(),
+                    model: "llama-3.3-70b".into(),
+                    api_key: None,
+                },
+                ModelRouteConfig {
+                    hint: "reasoning".into(),
+                    provider: "openai".into(),
+                    model: "o3".into(),
+                    api_key: None,
+                },
+            ],
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn model_ids_list_default_then_route_hints() {
+        let ids = model_ids(&config_with_routes(), "claude-sonnet");
+        assert_eq!(
+            ids,
+            vec![
+                ("claude-sonnet".to_string(), "anthropic".to_string()),
+                ("hint:fast".to_string(), "groq".to_string()),
+                ("hint:reasoning".to_string(), "openai".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_model_maps_hints_route_models_and_unknown_ids() {
+        let config = config_with_routes();
+        assert_eq!(
+            resolve_model(&config, "claude-sonnet", None),
+            "claude-sonnet"
+        );
+        assert_eq!(
+            resolve_model(&config, "claude-sonnet", Some("hint:fast")),
+            "hint:fast"
+        );
+        assert_eq!(
+            resolve_model(&config, "claude-sonnet", Some("o3")),
+            "hint:reasoning"
+        );
+        assert_eq!(
+            resolve_model(&config, "claude-sonnet", Some("gpt-4o")),
+            "claude-sonnet"
+        );
+        assert_eq!(
+            resolve_model(&config, "claude-sonnet", Some("hint:missing")),
+            "claude-sonnet"
+        );
+    }
+
+    #[test]
+    fn embedding_request_accepts_string_or_array_input() {
+        let one: OaiEmbeddingRequest =
+            serde_json::from_str(r#"{"input": "hello", "model": "text-embedding-3-small"}"#)
+                .unwrap();
+        assert_eq!(one.input.into_vec(), vec!["hello".to_string()]);
+
+        let many: OaiEmbeddingRequest =
+            serde_json::from_str(r#"{"input": ["a", "b"], "encoding_format": "float"}"#).unwrap();
+        assert_eq!(many.encoding_format.as_deref(), Some("float"));
+        assert_eq!(
+            many.input.into_vec(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+    }
+}
@@ -13,32 +13,38 @@
 //! ## Why this exists
 //!
 //! OpenClaw exposed `/v1/chat/completions` as an OpenAI-compatible API server.
-//! ZeroClaw's existing `/v1/chat/completions` (in `openai_compat.rs`) uses the
-//! simpler `provider.chat_with_history()` path — no tools, no memory, no agent loop.
-//!
-//! This module bridges the gap so callers coming from OpenClaw get the full agent
-//! experience without code changes on their side.
+//! ZeroClaw serves the same route through the full agent loop, so callers coming
+//! from OpenClaw get tools and memory without code changes on their side.
+//! `stream: true` relays the answer as it is produced; see `openai_compat` for
+//! `/v1/models`, `/v1/embeddings` and the shared auth.
 //!
 //! ## Migration path
 //!
-//! New integrations should use `POST /api/chat`. The `/v1/chat/completions` shim
-//! is provided for backward compatibility and may be deprecated once all callers
-//! have migrated to the native endpoint.
+//! New ZeroClaw-specific integrations should use `POST /api/chat`. Off-the-shelf
+//! OpenAI clients and IDE plugins should point their base URL at `<gateway>/v1`.
 
 use super::{
-    client_key_from_request, run_gateway_chat_with_tools, AppState, RATE_LIMIT_WINDOW_SECS,
+    client_key_from_request, openai_compat, run_gateway_chat_with_tools, AppState,
+    RATE_LIMIT_WINDOW_SECS,
 };
+use crate::agent::loop_::DRAFT_CLEAR_SENTINEL;
+use crate::config::Config;
 use crate::memory::MemoryCategory;
 use crate::providers;
 use axum::{
-    body::Body,
     extract::{ConnectInfo, State},
     http::{header, HeaderMap, StatusCode},
-    response::{IntoResponse, Json},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json,
+    },
 };
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::time::Instant;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use uuid::Uuid;
 
 // ══════════════════════════════════════════════════════════════════════════════
@@ -347,51 +353,21 @@ struct OaiDelta {
 
 /// `POST /v1/chat/completions` — OpenAI-compatible shim over ZeroClaw's agent loop.
 ///
-/// This replaces the simple `provider.chat_with_history()` path from `openai_compat.rs`
-/// with the full `run_gateway_chat_with_tools()` agent loop, giving OpenClaw callers
-/// the same tools + memory experience as native ZeroClaw channels.
+/// Runs the full agent loop, giving OpenClaw callers and OpenAI clients the same
+/// tools + memory experience as native ZeroClaw channels. `model` picks one of
+/// the `/v1/models` ids, `temperature` overrides the default, and `user` scopes
+/// memory recall like `session_id` on `/api/chat`.
 pub async fn handle_v1_chat_completions_with_tools(
     State(state): State<AppState>,
     ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
     body: axum::body::Bytes,
 ) -> impl IntoResponse {
-    // ── Rate limit ──
-    let rate_key =
-        client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
-    if !state.rate_limiter.allow_webhook(&rate_key) {
-        tracing::warn!("/v1/chat/completions (compat) rate limit exceeded");
-        let err = serde_json::json!({
-            "error": {
-                "message": "Rate limit exceeded. Please retry later.",
-                "type": "rate_limit_error",
-                "code": "rate_limit_exceeded"
-            }
-        });
-        return (StatusCode::TOO_MANY_REQUESTS, Json(err)).into_response();
-    }
-
-    // ── Bearer token auth (pairing) ──
-    if state.pairing.require_pairing() {
-        let auth = headers
-            .get(header::AUTHORIZATION)
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("");
-        let token = auth.strip_prefix("Bearer ").unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            tracing::warn!(
-                "/v1/chat/completions (compat): rejected — not paired / invalid bearer token"
-            );
-            let err = serde_json::json!({
-                "error": {
-                    "message": "Invalid API key. Pair first via POST /pair, then use Authorization: Bearer <token>",
-                    "type": "invalid_request_error",
-                    "code": "invalid_api_key"
-                }
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err)).into_response();
-        }
-    }
+    // ── Rate limit + bearer token auth (pairing, profile tokens) ──
+    let profile = match openai_compat::authorize(&state, peer_addr, &headers) {
+        Ok(profile) => profile,
+        Err(response) => return response,
+    };
 
     // ── Body size ──
     if body.len() > 524_288 {
@@ -496,12 +472,35 @@ pub async fn handle_v1_chat_completions_with_tools(
         )
     };
 
+    let mut config = match openai_compat::request_config(&state, profile.as_deref()) {
+        Ok(config) => config,
+        Err(response) => return response,
+    };
+    let default_model = config
+        .default_model
+        .clone()
+        .unwrap_or_else(|| state.model.clone());
+    let model_name =
+        openai_compat::resolve_model(&config, &default_model, request.model.as_deref());
+    config.default_model = Some(model_name.clone());
+    if let Some(temperature) = request.temperature {
+        if !crate::config::schema::TEMPERATURE_RANGE.contains(&temperature) {
+            return openai_compat::openai_error(
+                StatusCode::BAD_REQUEST,
+                "temperature must be between 0 and 2",
+                "invalid_request_error",
+                "invalid_temperature",
+            );
+        }
+        config.default_temperature = temperature;
+    }
+
     let is_stream = request.stream.unwrap_or(false);
     let request_id = format!("chatcmpl-{}", Uuid::new_v4().to_string().replace('-', ""));
     let created = unix_timestamp();
 
-    // ── Auto-save ──
-    if state.auto_save {
+    // ── Auto-save (profiles keep memory in their own workspace) ──
+    if state.auto_save && profile.is_none() {
         let key = api_chat_memory_key();
         let _ = state
             .mem
@@ -509,213 +508,300 @@ pub async fn handle_v1_chat_completions_with_tools(
             .await;
     }
 
-    // ── Observability ──
-    let provider_label = state
-        .config
-        .lock()
-        .default_provider
-        .clone()
-        .unwrap_or_else(|| "unknown".to_string());
-    let model_label = state.model.clone();
-    let started_at = Instant::now();
-
-    state
-        .observer
-        .record_event(&crate::observability::ObserverEvent::AgentStart {
-            provider: provider_label.clone(),
-            model: model_label.clone(),
-        });
-    state
-        .observer
-        .record_event(&crate::observability::ObserverEvent::LlmRequest {
-            provider: provider_label.clone(),
-            model: model_label.clone(),
-            messages_count: request.messages.len(),
-        });
-
     tracing::info!(
         stream = is_stream,
         messages_count = request.messages.len(),
+        profile = profile.as_deref().unwrap_or(""),
         "Processing /v1/chat/completions (compat shim — full agent loop)"
     );
 
-    // ── Run the full agent loop ──
-    let reply = match run_gateway_chat_with_tools(&state, &enriched_message, None).await {
-        Ok(response) => {
-            let safe = response;
-            let duration = started_at.elapsed();
+    let turn = CompatTurn {
+        state,
+        config,
+        message: enriched_message,
+        session_id: request.user,
+        messages_count: request.messages.len(),
+    };
 
-            state
-                .observer
-                .record_event(&crate::observability::ObserverEvent::LlmResponse {
-                    provider: provider_label.clone(),
-                    model: model_label.clone(),
-                    duration,
-                    success: true,
-                    error_message: None,
-                    input_tokens: None,
-                    output_tokens: None,
-                });
-            state.observer.record_metric(
-                &crate::observability::traits::ObserverMetric::RequestLatency(duration),
-            );
-            state
-                .observer
-                .record_event(&crate::observability::ObserverEvent::AgentEnd {
-                    provider: provider_label,
-                    model: model_label,
-                    duration,
-                    tokens_used: None,
-                    cost_usd: None,
-                });
+    if is_stream {
+        return stream_chat_completion(turn, request_id, created, model_name).into_response();
+    }
 
-            safe
-        }
-        Err(e) => {
-            let duration = started_at.elapsed();
-            let sanitized = providers::sanitize_api_error(&e.to_string());
+    let prompt = turn.message.clone();
+    let Ok(reply) = Box::pin(turn.run(None)).await else {
+        return openai_compat::openai_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "LLM request failed",
+            "server_error",
+            "provider_error",
+        );
+    };
 
-            state
-                .observer
-                .record_event(&crate::observability::ObserverEvent::LlmResponse {
-                    provider: provider_label.clone(),
-                    model: model_label.clone(),
-                    duration,
-                    success: false,
-                    error_message: Some(sanitized.clone()),
-                    input_tokens: None,
-                    output_tokens: None,
-                });
-            state.observer.record_metric(
-                &crate::observability::traits::ObserverMetric::RequestLatency(duration),
-            );
-            state
-                .observer
-                .record_event(&crate::observability::ObserverEvent::AgentEnd {
-                    provider: provider_label,
-                    model: model_label,
-                    duration,
-                    tokens_used: None,
-                    cost_usd: None,
-                });
+    let tokenizer = providers::tokenizer::tokenizer_for_model(&model_name);
+    #[allow(clippy::cast_possible_truncation)]
+    let prompt_tokens = tokenizer.count(&prompt) as u32;
+    #[allow(clippy::cast_possible_truncation)]
+    let completion_tokens = tokenizer.count(&reply) as u32;
+
+    let response = OaiChatResponse {
+        id: request_id,
+        object: "chat.completion",
+        created,
+        model: model_name,
+        choices: vec![OaiChoice {
+            index: 0,
+            message: OaiMessage {
+                role: "assistant".into(),
+                content: reply,
+            },
+            finish_reason: "stop",
+        }],
+        usage: OaiUsage {
+            prompt_tokens,
+            completion_tokens,
+            total_tokens: prompt_tokens + completion_tokens,
+        },
+    };
+    Json(serde_json::to_value(response).unwrap()).into_response()
+}
 
-            tracing::error!("/v1/chat/completions (compat) provider error: {sanitized}");
-            let err = serde_json::json!({
-                "error": {
-                    "message": "LLM request failed",
-                    "type": "server_error",
-                    "code": "provider_error"
-                }
+/// One `/v1/chat/completions` agent turn with its observability bookkeeping.
+struct CompatTurn {
+    state: AppState,
+    config: Config,
+    message: String,
+    session_id: Option<String>,
+    messages_count: usize,
+}
+
+impl CompatTurn {
+    /// Run the full agent loop, relaying progress and answer text through
+    /// `on_delta` when streaming. Errors are logged here and sanitized.
+    async fn run(self, on_delta: Option<mpsc::Sender<String>>) -> Result<String, ()> {
+        let Self {
+            state,
+            config,
+            message,
+            session_id,
+            messages_count,
+        } = self;
+
+        // ── Observability ──
+        let provider_label = config
+            .default_provider
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string());
+        let model_label = config
+            .default_model
+            .clone()
+            .unwrap_or_else(|| state.model.clone());
+        let started_at = Instant::now();
+
+        state
+            .observer
+            .record_event(&crate::observability::ObserverEvent::AgentStart {
+                provider: provider_label.clone(),
+                model: model_label.clone(),
+            });
+        state
+            .observer
+            .record_event(&crate::observability::ObserverEvent::LlmRequest {
+                provider: provider_label.clone(),
+                model: model_label.clone(),
+                messages_count,
             });
-            return (StatusCode::INTERNAL_SERVER_ERROR, Json(err)).into_response();
+
+        // ── Run the full agent loop ──
+        let result = Box::pin(crate::agent::process_message_streaming(
+            config,
+            &message,
+            session_id.as_deref(),
+            on_delta,
+        ))
+        .await;
+
+        let duration = started_at.elapsed();
+        let error_message = result
+            .as_ref()
+            .err()
+            .map(|e| providers::sanitize_api_error(&e.to_string()));
+        state
+            .observer
+            .record_event(&crate::observability::ObserverEvent::LlmResponse {
+                provider: provider_label.clone(),
+                model: model_label.clone(),
+                duration,
+                success: error_message.is_none(),
+                error_message: error_message.clone(),
+                input_tokens: None,
+                output_tokens: None,
+            });
+        state
+            .observer
+            .record_metric(&crate::observability::traits::ObserverMetric::RequestLatency(duration));
+        state
+            .observer
+            .record_event(&crate::observability::ObserverEvent::AgentEnd {
+                provider: provider_label,
+                model: model_label,
+                duration,
+                tokens_used: None,
+                cost_usd: None,
+            });
+
+        if let Some(sanitized) = error_message {
+            tracing::error!("/v1/chat/completions (compat) provider error: {sanitized}");
+            return Err(());
         }
+        result.map_err(|_| ())
+    }
+}
+
+fn stream_chunk(
+    id: &str,
+    created: u64,
+    model: &str,
+    delta: OaiDelta,
+    finish_reason: Option<&'static str>,
+) -> Event {
+    let chunk = OaiStreamChunk {
+        id: id.to_string(),
+        object: "chat.completion.chunk",
+        created,
+        model: model.to_string(),
+        choices: vec![OaiStreamChoice {
+            index: 0,
+            delta,
+            finish_reason,
+        }],
     };
+    Event::default().data(serde_json::to_string(&chunk).unwrap_or_else(|_| "{}".into()))
+}
 
-    let model_name = request.model.unwrap_or_else(|| state.model.clone());
+/// Translate agent loop deltas into SSE events.
+///
+/// Tool-round progress (before the clear sentinel) becomes SSE comments, which
+/// keep the connection alive without reaching the client's transcript; text
+/// after the sentinel is the answer and is sent as content chunks.
+struct DeltaRelay {
+    answering: bool,
+    streamed_answer: bool,
+}
 
-    #[allow(clippy::cast_possible_truncation)]
-    let prompt_tokens = (enriched_message.len() / 4) as u32;
-    #[allow(clippy::cast_possible_truncation)]
-    let completion_tokens = (reply.len() / 4) as u32;
+impl DeltaRelay {
+    fn new() -> Self {
+        Self {
+            answering: false,
+            streamed_answer: false,
+        }
+    }
 
-    if is_stream {
-        // ── Simulated streaming SSE ──
-        // The full agent loop returns a complete response; we chunk it into SSE format.
-        let role_chunk = OaiStreamChunk {
-            id: request_id.clone(),
-            object: "chat.completion.chunk",
+    fn event(&mut self, delta: String, id: &str, created: u64, model: &str) -> Option<Event> {
+        if delta == DRAFT_CLEAR_SENTINEL {
+            self.answering = true;
+            return None;
+        }
+        if !self.answering {
+            // SSE comments are single-line
+            let progress = delta.trim().replace(['\r', '\n'], " ");
+            return (!progress.is_empty()).then(|| Event::default().comment(progress));
+        }
+        if delta.is_empty() {
+            return None;
+        }
+        self.streamed_answer = true;
+        Some(stream_chunk(
+            id,
             created,
-            model: model_name.clone(),
-            choices: vec![OaiStreamChoice {
-                index: 0,
-                delta: OaiDelta {
-                    role: Some("assistant"),
-                    content: None,
-                },
-                finish_reason: None,
-            }],
+            model,
+            OaiDelta {
+                role: None,
+                content: Some(delta),
+            },
+            None,
+        ))
+    }
+}
+
+/// `stream: true` — relay the agent turn as `chat.completion.chunk` events
+/// while it runs, then `data: [DONE]`.
+fn stream_chat_completion(
+    turn: CompatTurn,
+    request_id: String,
+    created: u64,
+    model: String,
+) -> impl IntoResponse {
+    let (event_tx, event_rx) = mpsc::channel::<Result<Event, Infallible>>(64);
+
+    tokio::spawn(async move {
+        let send = |event: Event| {
+            let event_tx = event_tx.clone();
+            async move { event_tx.send(Ok(event)).await.is_ok() }
         };
 
-        let content_chunk = OaiStreamChunk {
-            id: request_id.clone(),
-            object: "chat.completion.chunk",
-            created,
-            model: model_name.clone(),
-            choices: vec![OaiStreamChoice {
-                index: 0,
-                delta: OaiDelta {
-                    role: None,
-                    content: Some(reply),
-                },
-                finish_reason: None,
-            }],
+        let (delta_tx, mut delta_rx) = mpsc::channel::<String>(64);
+        let run = tokio::spawn(turn.run(Some(delta_tx)));
+
+        let role = OaiDelta {
+            role: Some("assistant"),
+            content: None,
         };
+        send(stream_chunk(&request_id, created, &model, role, None)).await;
+
+        // A disconnected client stops the relay, not the turn.
+        let mut relay = DeltaRelay::new();
+        while let Some(delta) = delta_rx.recv().await {
+            if let Some(event) = relay.event(delta, &request_id, created, &model) {
+                if !send(event).await {
+                    return;
+                }
+            }
+        }
 
-        let stop_chunk = OaiStreamChunk {
-            id: request_id,
-            object: "chat.completion.chunk",
-            created,
-            model: model_name,
-            choices: vec![OaiStreamChoice {
-                index: 0,
-                delta: OaiDelta {
+        match run.await {
+            Ok(Ok(reply)) => {
+                if !relay.streamed_answer && !reply.is_empty() {
+                    let content = OaiDelta {
+                        role: None,
+                        content: Some(reply),
+                    };
+                    send(stream_chunk(&request_id, created, &model, content, None)).await;
+                }
+                let stop = OaiDelta {
                     role: None,
                     content: None,
-                },
-                finish_reason: Some("stop"),
-            }],
-        };
+                };
+                send(stream_chunk(
+                    &request_id,
+                    created,
+                    &model,
+                    stop,
+                    Some("stop"),
+                ))
+                .await;
+            }
+            _ => {
+                let err = serde_json::json!({
+                    "error": {
+                        "message": "LLM request failed",
+                        "type": "server_error",
+                        "code": "provider_error"
+                    }
+                });
+                send(Event::default().data(err.to_string())).await;
+            }
+        }
+        send(Event::default().data("[DONE]")).await;
+    });
 
-        let mut output = String::new();
-        output.push_str("data: ");
-        output.push_str(&serde_json::to_string(&role_chunk).unwrap_or_else(|_| "{}".into()));
-        output.push_str("\n\n");
-        output.push_str("data: ");
-        output.push_str(&serde_json::to_string(&content_chunk).unwrap_or_else(|_| "{}".into()));
-        output.push_str("\n\n");
-        output.push_str("data: ");
-        output.push_str(&serde_json::to_string(&stop_chunk).unwrap_or_else(|_| "{}".into()));
-        output.push_str("\n\n");
-        output.push_str("data: [DONE]\n\n");
-
-        axum::response::Response::builder()
-            .status(StatusCode::OK)
-            .header(header::CONTENT_TYPE, "text/event-stream")
-            .header(header::CACHE_CONTROL, "no-cache")
-            .header(header::CONNECTION, "keep-alive")
-            .body(Body::from(output))
-            .unwrap()
-            .into_response()
-    } else {
-        // ── Non-streaming JSON ──
-        let response = OaiChatResponse {
-            id: request_id,
-            object: "chat.completion",
-            created,
-            model: model_name,
-            choices: vec![OaiChoice {
-                index: 0,
-                message: OaiMessage {
-                    role: "assistant".into(),
-                    content: reply,
-                },
-                finish_reason: "stop",
-            }],
-            usage: OaiUsage {
-                prompt_tokens,
-                completion_tokens,
-                total_tokens: prompt_tokens + completion_tokens,
-            },
-        };
-        Json(serde_json::to_value(response).unwrap()).into_response()
-    }
+    Sse::new(ReceiverStream::new(event_rx)).keep_alive(KeepAlive::default())
 }
 
 // ══════════════════════════════════════════════════════════════════════════════
 // HELPERS
 // ══════════════════════════════════════════════════════════════════════════════
 
-fn unix_timestamp() -> u64 {
+pub(super) fn unix_timestamp() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
@@ -821,6 +907,26 @@ mod tests {
         assert!(!json.contains("content"));
     }
 
+    #[test]
+    fn delta_relay_hides_progress_and_streams_answer() {
+        let mut relay = DeltaRelay::new();
+        let data = |delta: &str, relay: &mut DeltaRelay| {
+            relay
+                .event(delta.to_string(), "chatcmpl-test", 0, "test-model")
+                .map(|event| format!("{event:?}"))
+        };
+
+        let progress = data("\u{1f914} Thinking...\n", &mut relay).unwrap();
+        assert!(!progress.contains("chat.completion.chunk"));
+        assert!(data(DRAFT_CLEAR_SENTINEL, &mut relay).is_none());
+        assert!(!relay.streamed_answer);
+
+        let answer = data("Hello ", &mut relay).unwrap();
+        assert!(answer.contains("chat.completion.chunk"));
+        assert!(answer.contains("Hello "));
+        assert!(relay.streamed_answer);
+    }
+
     #[test]
     fn memory_key_is_unique() {
         let k1 = api_chat_memory_key();
@@ -1,16 +1,19 @@
 //! Server-Sent Events (SSE) stream for real-time event delivery.
 //!
-//! Wraps the broadcast channel in AppState to deliver events to web dashboard clients.
+//! Wraps the broadcast channel in AppState to deliver events to web dashboard clients,
+//! and serves the typed agent turn stream from [`crate::observability::turn_events`].
 
 use super::AppState;
+use crate::observability::turn_events::{self, TurnEvent, TurnEventFilter};
 use axum::{
-    extract::State,
+    extract::{Query, State},
     http::{header, HeaderMap, StatusCode},
     response::{
         sse::{Event, KeepAlive, Sse},
         IntoResponse,
     },
 };
+use serde::Deserialize;
 use std::convert::Infallible;
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::StreamExt;
@@ -57,6 +60,61 @@ pub async fn handle_sse_events(
         .into_response()
 }
 
+/// Query parameters for the turn event streams (`/api/events/turns`, `/ws/events`).
+#[derive(Debug, Default, Deserialize)]
+pub struct TurnEventsQuery {
+    /// Pairing token, for clients that cannot set an `Authorization` header.
+    pub token: Option<String>,
+    /// Only deliver events of this session (e.g. `telegram_alice`, `gw_<id>`).
+    pub session: Option<String>,
+    /// Only deliver events of this channel (e.g. `telegram`, `ws`, `cli`).
+    pub channel: Option<String>,
+}
+
+impl TurnEventsQuery {
+    pub fn filter(&self) -> TurnEventFilter {
+        TurnEventFilter {
+            session: self.session.clone(),
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+/// GET /api/events/turns — SSE stream of typed agent turn events
+pub async fn handle_sse_turn_events(
+    State(state): State<AppState>,
+    Query(query): Query<TurnEventsQuery>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if state.pairing.require_pairing() {
+        let token = super::ws::extract_ws_token(&headers, query.token.as_deref()).unwrap_or("");
+        if !state.pairing.is_authenticated(token) {
+            return (
+                StatusCode::UNAUTHORIZED,
+                "Unauthorized — provide Authorization: Bearer <token> or ?token= query param",
+            )
+                .into_response();
+        }
+    }
+
+    let filter = query.filter();
+    let stream = BroadcastStream::new(turn_events::subscribe()).filter_map(
+        move |result: Result<
+            TurnEvent,
+            tokio_stream::wrappers::errors::BroadcastStreamRecvError,
+        >| {
+            // Skip lagged messages and events outside the filter
+            let event = result.ok().filter(|event| filter.matches(event))?;
+            let data = serde_json::to_string(&event).ok()?;
+            Some(Ok::<_, Infallible>(Event::default().data(data)))
+        },
+    );
+
+    Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
 /// Broadcast observer that forwards events to the SSE broadcast channel.
 pub struct BroadcastObserver {
     inner: Box<dyn crate::observability::Observer>,
@@ -91,6 +149,7 @@ impl crate::observability::Observer for BroadcastObserver {
                 tool,
                 duration,
                 success,
+                ..
             } => serde_json::json!({
                 "type": "tool_call",
                 "tool": tool,
@@ -8,8 +8,12 @@
 //! Server -> Client: {"type":"tool_result","name":"shell","output":"..."}
 //! Server -> Client: {"type":"done","full_response":"..."}
 //! ```
+//!
+//! `/ws/events` is a read-only companion that pushes the typed agent turn
+//! events (see [`crate::observability::turn_events`]) as JSON text frames.
 
 use super::AppState;
+use crate::observability::runtime_trace;
 use axum::{
     extract::{
         ws::{Message, WebSocket},
@@ -110,15 +114,13 @@ pub async fn handle_ws_chat(
     ws: WebSocketUpgrade,
 ) -> impl IntoResponse {
     // Auth: check header, subprotocol, then query param (precedence order)
-    if state.pairing.require_pairing() {
-        let token = extract_ws_token(&headers, params.token.as_deref()).unwrap_or("");
-        if !state.pairing.is_authenticated(token) {
-            return (
-                axum::http::StatusCode::UNAUTHORIZED,
-                "Unauthorized — provide Authorization header, Sec-WebSocket-Protocol bearer, or ?token= query param",
-            )
-                .into_response();
-        }
+    let token = extract_ws_token(&headers, params.token.as_deref()).unwrap_or("");
+    if state.pairing.require_pairing() && !state.pairing.is_authenticated(token) {
+        return (
+            axum::http::StatusCode::UNAUTHORIZED,
+            "Unauthorized — provide Authorization header, Sec-WebSocket-Protocol bearer, or ?token= query param",
+        )
+            .into_response();
     }
 
     // Echo Sec-WebSocket-Protocol if the client requests our sub-protocol.
@@ -134,23 +136,94 @@ pub async fn handle_ws_chat(
     };
 
     let session_id = params.session_id;
-    ws.on_upgrade(move |socket| handle_socket(socket, state, session_id))
+    let profile = state
+        .config
+        .lock()
+        .profile_for_gateway_token(token)
+        .map(str::to_string);
+    ws.on_upgrade(move |socket| handle_socket(socket, state, session_id, profile))
         .into_response()
 }
 
+/// GET /ws/events — WebSocket stream of typed agent turn events
+pub async fn handle_ws_turn_events(
+    State(state): State<AppState>,
+    Query(query): Query<super::sse::TurnEventsQuery>,
+    headers: HeaderMap,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    if state.pairing.require_pairing() {
+        let token = extract_ws_token(&headers, query.token.as_deref()).unwrap_or("");
+        if !state.pairing.is_authenticated(token) {
+            return (
+                axum::http::StatusCode::UNAUTHORIZED,
+                "Unauthorized — provide Authorization header, Sec-WebSocket-Protocol bearer, or ?token= query param",
+            )
+                .into_response();
+        }
+    }
+
+    let filter = query.filter();
+    ws.on_upgrade(move |socket| stream_turn_events(socket, filter))
+        .into_response()
+}
+
+async fn stream_turn_events(
+    socket: WebSocket,
+    filter: crate::observability::turn_events::TurnEventFilter,
+) {
+    use tokio::sync::broadcast::error::RecvError;
+
+    let (mut sender, mut receiver) = socket.split();
+    let mut events = crate::observability::turn_events::subscribe();
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) if filter.matches(&event) => {
+                    let Ok(json) = serde_json::to_string(&event) else {
+                        continue;
+                    };
+                    if sender.send(Message::Text(json.into())).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            },
+            // The stream is push-only; client frames are ignored until it closes.
+            msg = receiver.next() => match msg {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
 /// Gateway session key prefix to avoid collisions with channel sessions.
 const GW_SESSION_PREFIX: &str = "gw_";
 
-async fn handle_socket(socket: WebSocket, state: AppState, session_id: Option<String>) {
+async fn handle_socket(
+    socket: WebSocket,
+    state: AppState,
+    session_id: Option<String>,
+    profile: Option<String>,
+) {
     let (mut sender, mut receiver) = socket.split();
 
     // Resolve session ID: use provided or generate a new UUID
     let session_id = session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
-    let session_key = format!("{GW_SESSION_PREFIX}{session_id}");
+    // Profile sessions get their own key space so they never resume each other.
+    let session_key = match profile.as_deref() {
+        Some(profile) => format!("{GW_SESSION_PREFIX}{profile}_{session_id}"),
+        None => format!("{GW_SESSION_PREFIX}{session_id}"),
+    };
 
     // Build a persistent Agent for this connection so history is maintained across turns.
-    let config = state.config.lock().clone();
-    let mut agent = match crate::agent::Agent::from_config(&config) {
+    let config = match profile.as_deref() {
+        Some(profile) => state.config.lock().for_profile(profile),
+        None => Ok(state.config.lock().clone()),
+    };
+    let mut agent = match config.and_then(|config| crate::agent::Agent::from_config(&config)) {
         Ok(a) => a,
         Err(e) => {
             let err = serde_json::json!({"type": "error", "message": format!("Failed to initialise agent: {e}")});
@@ -159,6 +232,7 @@ async fn handle_socket(socket: WebSocket, state: AppState, session_id: Option<St
         }
     };
     agent.set_memory_session_id(Some(session_id.clone()));
+    agent.set_channel_name("ws");
 
     // Hydrate agent from persisted session (if available)
     let mut resumed = false;
@@ -303,7 +377,8 @@ async fn process_chat_message(
     }));
 
     // Multi-turn chat via persistent Agent (history is maintained across turns)
-    match agent.turn(content).await {
+    let turn = runtime_trace::with_session(session_key.to_string(), agent.turn(content));
+    match turn.await {
         Ok(response) => {
             // Persist assistant response
             if let Some(ref backend) = state.session_backend {
@@ -69,6 +69,7 @@ pub mod runtime;
 pub(crate) mod security;
 pub(crate) mod service;
 pub(crate) mod skills;
+pub mod sop;
 pub mod tools;
 pub(crate) mod tunnel;
 pub(crate) mod util;
@@ -455,6 +456,88 @@ pub enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Rebuild the sqlite vector index (brain.hnsw) from stored embeddings
+    RebuildIndex,
+    /// Re-embed every sqlite memory with the configured embedding provider
+    Reembed,
+    /// Export memories and the knowledge graph as a portable JSONL bundle
+    Export {
+        /// Write the bundle to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+        /// Only export this category (core, daily, conversation, or custom name)
+        #[arg(long)]
+        category: Option<String>,
+        /// Only export this session ID
+        #[arg(long)]
+        session: Option<String>,
+        /// Include stored embeddings
+        #[arg(long)]
+        embeddings: bool,
+        /// Read from this memory backend instead of the configured one
+        #[arg(long)]
+        backend: Option<String>,
+    },
+    /// Import a JSONL bundle written by `memory export`
+    Import {
+        /// Bundle file to read
+        input: std::path::PathBuf,
+        /// Keep existing entries whose key is also in the bundle
+        #[arg(long)]
+        skip_existing: bool,
+        /// Write to this memory backend instead of the configured one
+        #[arg(long)]
+        backend: Option<String>,
+    },
+}
+
+/// Runtime trace subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TraceCommands {
+    /// List recorded agent turns (newest first)
+    List {
+        /// Only turns from this channel
+        #[arg(long)]
+        channel: Option<String>,
+        /// Only turns from this conversation (e.g. telegram_alice)
+        #[arg(long)]
+        session: Option<String>,
+        /// Only turns that called this tool
+        #[arg(long)]
+        tool: Option<String>,
+        /// Maximum number of turns to display
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Render a turn as a tree of model calls and tool calls
+    Show {
+        /// Turn id (a unique prefix is enough)
+        turn_id: String,
+    },
+    /// Export a turn as a trace fixture for the integration test harness
+    Export {
+        /// Turn id (a unique prefix is enough)
+        turn_id: String,
+        /// Write the fixture to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
+}
+
+/// Cost tracking subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CostCommands {
+    /// Show today's and this month's spend against the global limits
+    Summary,
+    /// Show spend per quota key (channel, sender, agent, cron job)
+    Usage {
+        /// Only keys of this scope: channel, sender, agent or cron_job
+        #[arg(long)]
+        scope: Option<String>,
+        /// Only this key (e.g. a channel name or sender id)
+        #[arg(long)]
+        key: Option<String>,
+    },
 }
 
 /// Integration subcommands
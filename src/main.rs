@@ -76,9 +76,6 @@ mod approval;
 mod auth;
 mod channels;
 mod commands;
-mod rag {
-    pub use zeroclaw::rag::*;
-}
 mod config;
 mod cost;
 mod cron;
@@ -94,15 +91,18 @@ mod integrations;
 mod memory;
 mod migration;
 mod multimodal;
+mod nodes;
 mod observability;
 mod onboard;
 mod peripherals;
 mod providers;
+mod rag;
 mod runtime;
 mod security;
 mod service;
 mod skillforge;
 mod skills;
+mod sop;
 mod tools;
 mod tunnel;
 mod util;
@@ -111,8 +111,9 @@ use config::Config;
 
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
-    ChannelCommands, CronCommands, GatewayCommands, HardwareCommands, IntegrationCommands,
-    MigrateCommands, PeripheralCommands, ServiceCommands, SkillCommands,
+    ChannelCommands, CostCommands, CronCommands, GatewayCommands, HardwareCommands,
+    IntegrationCommands, MigrateCommands, PeripheralCommands, ServiceCommands, SkillCommands,
+    TraceCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -282,6 +283,43 @@ Examples:
         doctor_command: Option<DoctorCommands>,
     },
 
+    /// Inspect recorded agent turns from the runtime trace
+    #[command(long_about = "\
+Inspect recorded agent turns from the runtime trace.
+
+Reads observability.runtime_trace_path (requires runtime_trace_mode \
+\"rolling\" or \"full\") and groups events by turn. Turn ids may be \
+shortened to any unique prefix.
+
+Examples:
+  zeroclaw trace list
+  zeroclaw trace list --channel telegram --tool shell
+  zeroclaw trace show 3f2a9c1e
+  zeroclaw trace export 3f2a9c1e -o tests/fixtures/traces/shell_loop.json")]
+    Trace {
+        #[command(subcommand)]
+        trace_command: TraceCommands,
+    },
+
+    /// Inspect LLM spend and scoped cost quotas
+    #[command(long_about = "\
+Inspect LLM spend and scoped cost quotas.
+
+Reads the cost ledger (workspace/state/costs.jsonl) recorded when \
+[cost] enabled = true. Spend is attributed to the channel, sender, \
+delegate agent and cron job that made each request, and checked \
+against [[cost.quotas]].
+
+Examples:
+  zeroclaw cost summary
+  zeroclaw cost usage
+  zeroclaw cost usage --scope channel
+  zeroclaw cost usage --scope sender --key alice")]
+    Cost {
+        #[command(subcommand)]
+        cost_command: CostCommands,
+    },
+
     /// Show system status (full details)
     Status {
         /// Output format: "exit-code" exits 0 if healthy, 1 otherwise (for Docker HEALTHCHECK)
@@ -432,20 +470,24 @@ Examples:
         peripheral_command: zeroclaw::PeripheralCommands,
     },
 
-    /// Manage agent memory (list, get, stats, clear)
+    /// Manage agent memory (list, get, stats, clear, rebuild-index, export, import)
     #[command(long_about = "\
 Manage agent memory entries.
 
 List, inspect, and clear memory entries stored by the agent. \
 Supports filtering by category and session, pagination, and \
-batch clearing with confirmation.
+batch clearing with confirmation. Export and import move memories \
+and the knowledge graph between backends as JSONL bundles.
 
 Examples:
   zeroclaw memory stats
   zeroclaw memory list
   zeroclaw memory list --category core --limit 10
   zeroclaw memory get <key>
-  zeroclaw memory clear --category conversation --yes")]
+  zeroclaw memory clear --category conversation --yes
+  zeroclaw memory rebuild-index
+  zeroclaw memory export --backend sqlite --embeddings -o memory.jsonl
+  zeroclaw memory import memory.jsonl --backend postgres")]
     Memory {
         #[command(subcommand)]
         memory_command: MemoryCommands,
@@ -728,6 +770,36 @@ enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Rebuild the sqlite vector index (brain.hnsw) from stored embeddings
+    RebuildIndex,
+    /// Re-embed every sqlite memory with the configured embedding provider
+    Reembed,
+    /// Export memories and the knowledge graph as a portable JSONL bundle
+    Export {
+        /// Write the bundle to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+        #[arg(long)]
+        category: Option<String>,
+        #[arg(long)]
+        session: Option<String>,
+        /// Include stored embeddings
+        #[arg(long)]
+        embeddings: bool,
+        /// Read from this memory backend instead of the configured one
+        #[arg(long)]
+        backend: Option<String>,
+    },
+    /// Import a JSONL bundle written by `memory export`
+    Import {
+        input: std::path::PathBuf,
+        /// Keep existing entries whose key is also in the bundle
+        #[arg(long)]
+        skip_existing: bool,
+        /// Write to this memory backend instead of the configured one
+        #[arg(long)]
+        backend: Option<String>,
+    },
 }
 
 #[tokio::main]
@@ -886,6 +958,7 @@ async fn main() -> Result<()> {
     let mut config = Box::pin(Config::load_or_init()).await?;
     config.apply_env_overrides();
     observability::runtime_trace::init_from_config(&config.observability, &config.workspace_dir);
+    cost::scope::init_from_config(&config);
     if config.security.otp.enabled {
         let config_dir = config
             .config_path
@@ -1262,6 +1335,12 @@ async fn main() -> Result<()> {
             migration::handle_command(migrate_command, &config).await
         }
 
+        Commands::Trace { trace_command } => {
+            observability::trace_cli::handle_command(trace_command, &config)
+        }
+
+        Commands::Cost { cost_command } => cost::cli::handle_command(cost_command, &config),
+
         Commands::Memory { memory_command } => {
             memory::cli::handle_command(memory_command, &config).await
         }
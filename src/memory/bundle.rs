@@ -0,0 +1,343 @@
+//! Portable JSONL memory bundles for backup and migration between backends.
+//!
+//! A bundle is one JSON object per line, tagged by `kind`: a `header`, then
+//! every `memory` entry (with its embedding when requested), then the
+//! knowledge graph's `knowledge_node`s and `knowledge_edge`s. Memories go
+//! through the [`Memory`] trait, so any backend can export or import.
+
+use super::knowledge_graph::{KnowledgeEdge, KnowledgeGraph, KnowledgeNode};
+use super::traits::{Memory, MemoryCategory, MemoryRecord};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+/// Bundle format version written to the header line.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Memories fetched per [`Memory::export_page`] call while exporting.
+const EXPORT_PAGE_SIZE: usize = 500;
+
+/// One line of a bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BundleRecord {
+    Header {
+        version: u32,
+        exported_at: String,
+        backend: String,
+    },
+    Memory(MemoryRecord),
+    KnowledgeNode(KnowledgeNode),
+    KnowledgeEdge(KnowledgeEdge),
+}
+
+/// Which memories to export.
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    pub category: Option<MemoryCategory>,
+    pub session_id: Option<String>,
+    pub include_embeddings: bool,
+}
+
+/// Records written or read, by kind.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BundleStats {
+    pub memories: usize,
+    pub knowledge_nodes: usize,
+    pub knowledge_edges: usize,
+    /// Memories left alone on import because the key already existed.
+    pub skipped: usize,
+}
+
+/// Write `memory` (and `graph`, when given) to `writer` as a bundle.
+pub async fn export_bundle(
+    memory: &dyn Memory,
+    graph: Option<&KnowledgeGraph>,
+    options: &ExportOptions,
+    mut writer: impl Write,
+) -> Result<BundleStats> {
+    let mut stats = BundleStats::default();
+    write_record(
+        &mut writer,
+        &BundleRecord::Header {
+            version: BUNDLE_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            backend: memory.name().to_string(),
+        },
+    )?;
+
+    let mut cursor: Option<String> = None;
+    loop {
+        let page = memory
+            .export_page(
+                options.include_embeddings,
+                cursor.as_deref(),
+                EXPORT_PAGE_SIZE,
+            )
+            .await?;
+        for record in page.records {
+            let entry = &record.entry;
+            if options
+                .category
+                .as_ref()
+                .is_some_and(|category| &entry.category != category)
+                || options
+                    .session_id
+                    .as_deref()
+                    .is_some_and(|sid| entry.session_id.as_deref() != Some(sid))
+            {
+                continue;
+            }
+            write_record(&mut writer, &BundleRecord::Memory(record))?;
+            stats.memories += 1;
+        }
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    if let Some(graph) = graph {
+        for node in graph.all_nodes()? {
+            write_record(&mut writer, &BundleRecord::KnowledgeNode(node))?;
+            stats.knowledge_nodes += 1;
+        }
+        for edge in graph.all_edges()? {
+            write_record(&mut writer, &BundleRecord::KnowledgeEdge(edge))?;
+            stats.knowledge_edges += 1;
+        }
+    }
+
+    writer.flush()?;
+    Ok(stats)
+}
+
+/// Read a bundle from `reader` into `memory` (and `graph`, when given).
+///
+/// Existing keys are overwritten unless `skip_existing` is set. Knowledge
+/// records are ignored when no graph is given.
+pub async fn import_bundle(
+    memory: &dyn Memory,
+    graph: Option<&KnowledgeGraph>,
+    reader: impl BufRead,
+    skip_existing: bool,
+) -> Result<BundleStats> {
+    let mut stats = BundleStats::default();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line_no = index + 1;
+        let line = line.with_context(|| format!("failed to read bundle line {line_no}"))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: BundleRecord = serde_json::from_str(&line)
+            .with_context(|| format!("invalid bundle record on line {line_no}"))?;
+
+        match record {
+            BundleRecord::Header { version, .. } => {
+                if version > BUNDLE_VERSION {
+                    anyhow::bail!(
+                        "bundle version {version} is newer than supported version {BUNDLE_VERSION}"
+                    );
+                }
+            }
+            BundleRecord::Memory(record) => {
+                if skip_existing && memory.get(&record.entry.key).await?.is_some() {
+                    stats.skipped += 1;
+                    continue;
+                }
+                memory.import_entry(&record).await.with_context(|| {
+                    format!(
+                        "failed to import memory '{}' (line {line_no})",
+                        record.entry.key
+                    )
+                })?;
+                stats.memories += 1;
+            }
+            BundleRecord::KnowledgeNode(node) => {
+                if let Some(graph) = graph {
+                    graph.import_node(&node).with_context(|| {
+                        format!(
+                            "failed to import knowledge node '{}' (line {line_no})",
+                            node.id
+                        )
+                    })?;
+                    stats.knowledge_nodes += 1;
+                }
+            }
+            BundleRecord::KnowledgeEdge(edge) => {
+                if let Some(graph) = graph {
+                    graph
+                        .add_edge(&edge.from_id, &edge.to_id, edge.relation)
+                        .with_context(|| {
+                            format!("failed to import knowledge edge (line {line_no})")
+                        })?;
+                    stats.knowledge_edges += 1;
+                }
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+fn write_record(writer: &mut impl Write, record: &BundleRecord) -> Result<()> {
+    serde_json::to_writer(&mut *writer, record)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::knowledge_graph::{NodeType, Relation};
+    use crate::memory::{MarkdownMemory, SqliteMemory};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn sqlite_bundle_roundtrip_keeps_timestamps_sessions_and_graph() {
+        let src_dir = TempDir::new().unwrap();
+        let source = SqliteMemory::new(src_dir.path()).unwrap();
+        source
+            .store("lang", "Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        source
+            .store("chat", "hello", MemoryCategory::Conversation, Some("s1"))
+            .await
+            .unwrap();
+        let graph = KnowledgeGraph::new(&src_dir.path().join("kg.db"), 100).unwrap();
+        let a = graph
+            .add_node(NodeType::Pattern, "A", "a", &["x".into()], None)
+            .unwrap();
+        let b = graph
+            .add_node(NodeType::Technology, "B", "b", &[], None)
+            .unwrap();
+        graph.add_edge(&a, &b, Relation::Uses).unwrap();
+
+        let mut bundle = Vec::new();
+        let stats = export_bundle(
+            &source,
+            Some(&graph),
+            &ExportOptions::default(),
+            &mut bundle,
+        )
+        .await
+        .unwrap();
+        assert_eq!(stats.memories, 2);
+        assert_eq!(stats.knowledge_nodes, 2);
+        assert_eq!(stats.knowledge_edges, 1);
+
+        let dst_dir = TempDir::new().unwrap();
+        let target = SqliteMemory::new(dst_dir.path()).unwrap();
+        let target_graph = KnowledgeGraph::new(&dst_dir.path().join("kg.db"), 100).unwrap();
+        let stats = import_bundle(&target, Some(&target_graph), bundle.as_slice(), false)
+            .await
+            .unwrap();
+        assert_eq!(stats.memories, 2);
+
+        let original = source.get("chat").await.unwrap().unwrap();
+        let imported = target.get("chat").await.unwrap().unwrap();
+        assert_eq!(imported.content, "hello");
+        assert_eq!(imported.category, MemoryCategory::Conversation);
+        assert_eq!(imported.session_id.as_deref(), Some("s1"));
+        assert_eq!(imported.timestamp, original.timestamp);
+
+        let related = target_graph.find_related(&a).unwrap();
+        assert_eq!(related.len(), 1);
+        assert_eq!(related[0].0.id, b);
+        assert_eq!(target_graph.get_node(&a).unwrap().unwrap().tags, ["x"]);
+    }
+
+    #[tokio::test]
+    async fn export_filters_and_import_skips_existing() {
+        let src_dir = TempDir::new().unwrap();
+        let source = SqliteMemory::new(src_dir.path()).unwrap();
+        source
+            .store("a", "one", MemoryCategory::Core, Some("s1"))
+            .await
+            .unwrap();
+        source
+            .store("b", "two", MemoryCategory::Core, Some("s2"))
+            .await
+            .unwrap();
+
+        let options = ExportOptions {
+            session_id: Some("s1".into()),
+            ..Default::default()
+        };
+        let mut bundle = Vec::new();
+        let stats = export_bundle(&source, None, &options, &mut bundle)
+            .await
+            .unwrap();
+        assert_eq!(stats.memories, 1);
+
+        let dst_dir = TempDir::new().unwrap();
+        let target = SqliteMemory::new(dst_dir.path()).unwrap();
+        target
+            .store("a", "kept", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let stats = import_bundle(&target, None, bundle.as_slice(), true)
+            .await
+            .unwrap();
+        assert_eq!(stats.skipped, 1);
+        assert_eq!(target.get("a").await.unwrap().unwrap().content, "kept");
+    }
+
+    #[tokio::test]
+    async fn bundle_imports_into_markdown_backend() {
+        let src_dir = TempDir::new().unwrap();
+        let source = SqliteMemory::new(src_dir.path()).unwrap();
+        source
+            .store("lang", "Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let mut bundle = Vec::new();
+        export_bundle(&source, None, &ExportOptions::default(), &mut bundle)
+            .await
+            .unwrap();
+
+        let dst_dir = TempDir::new().unwrap();
+        let target = MarkdownMemory::new(dst_dir.path());
+        import_bundle(&target, None, bundle.as_slice(), false)
+            .await
+            .unwrap();
+        let entries = target
+            .list(Some(&MemoryCategory::Core), None)
+            .await
+            .unwrap();
+        assert!(entries.iter().any(|e| e.content.contains("Rust")));
+    }
+
+    #[tokio::test]
+    async fn default_export_page_pages_through_list() {
+        let dir = TempDir::new().unwrap();
+        let memory = MarkdownMemory::new(dir.path());
+        for key in ["one", "two", "three"] {
+            memory
+                .store(key, key, MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+
+        let first = memory.export_page(false, None, 2).await.unwrap();
+        assert_eq!(first.records.len(), 2);
+        let cursor = first.next_cursor.expect("a second page");
+        let second = memory.export_page(false, Some(&cursor), 2).await.unwrap();
+        assert_eq!(second.records.len(), 1);
+        assert!(second.next_cursor.is_none());
+        assert!(memory.export_page(false, Some("x"), 2).await.is_err());
+    }
+
+    #[test]
+    fn memory_records_serialize_flat_with_kind_tag() {
+        let line = r#"{"kind":"memory","id":"1","key":"k","content":"c","category":"core","timestamp":"2026-01-01T00:00:00Z","session_id":null,"score":null,"embedding":[0.5]}"#;
+        let record: BundleRecord = serde_json::from_str(line).unwrap();
+        let BundleRecord::Memory(record) = record else {
+            panic!("expected memory record");
+        };
+        assert_eq!(record.entry.key, "k");
+        assert_eq!(record.embedding, Some(vec![0.5]));
+    }
+}
@@ -1,13 +1,16 @@
+use super::bundle::{export_bundle, import_bundle, ExportOptions};
+use super::hnsw::HnswParams;
+use super::knowledge_graph::KnowledgeGraph;
 use super::traits::{Memory, MemoryCategory};
 use super::{
-    classify_memory_backend, create_memory_for_migration, effective_memory_backend_name,
-    MemoryBackendKind,
+    classify_memory_backend, create_memory_for_migration, create_sqlite_memory,
+    effective_memory_backend_name, MemoryBackendKind, SqliteMemory,
 };
 use crate::config::Config;
-#[cfg(feature = "memory-postgres")]
-use anyhow::Context;
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use console::style;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
 
 /// Handle `zeroclaw memory <subcommand>` CLI commands.
 pub async fn handle_command(command: crate::MemoryCommands, config: &Config) -> Result<()> {
@@ -23,6 +26,27 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
         crate::MemoryCommands::Clear { key, category, yes } => {
             handle_clear(config, key, category, yes).await
         }
+        crate::MemoryCommands::RebuildIndex => handle_rebuild_index(config).await,
+        crate::MemoryCommands::Reembed => handle_reembed(config).await,
+        crate::MemoryCommands::Export {
+            output,
+            category,
+            session,
+            embeddings,
+            backend,
+        } => {
+            let options = ExportOptions {
+                category: category.as_deref().map(parse_category),
+                session_id: session,
+                include_embeddings: embeddings,
+            };
+            handle_export(config, backend.as_deref(), output.as_deref(), &options).await
+        }
+        crate::MemoryCommands::Import {
+            input,
+            skip_existing,
+            backend,
+        } => handle_import(config, backend.as_deref(), &input, skip_existing).await,
     }
 }
 
@@ -297,6 +321,165 @@ async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()>
     Ok(())
 }
 
+/// Create the full memory backend (with embeddings) for bundle export/import,
+/// optionally overriding the configured backend.
+fn create_bundle_memory(config: &Config, backend: Option<&str>) -> Result<Box<dyn Memory>> {
+    let mut memory_config = config.memory.clone();
+    let mut storage = config.storage.provider.config.clone();
+    if let Some(backend) = backend {
+        memory_config.backend = backend.to_string();
+        storage.provider.clear();
+    }
+    super::create_memory_with_storage_and_routes(
+        &memory_config,
+        &config.embedding_routes,
+        Some(&storage),
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )
+}
+
+/// The configured knowledge graph, when `[knowledge]` is enabled.
+fn open_knowledge_graph(config: &Config) -> Result<Option<KnowledgeGraph>> {
+    if !config.knowledge.enabled {
+        return Ok(None);
+    }
+    KnowledgeGraph::from_config(&config.knowledge).map(Some)
+}
+
+async fn handle_export(
+    config: &Config,
+    backend: Option<&str>,
+    output: Option<&Path>,
+    options: &ExportOptions,
+) -> Result<()> {
+    let mem = create_bundle_memory(config, backend)?;
+    let graph = open_knowledge_graph(config)?;
+
+    let stats = match output {
+        Some(path) => {
+            let file = std::fs::File::create(path)
+                .with_context(|| format!("failed to create {}", path.display()))?;
+            export_bundle(&*mem, graph.as_ref(), options, BufWriter::new(file)).await?
+        }
+        None => export_bundle(&*mem, graph.as_ref(), options, std::io::stdout().lock()).await?,
+    };
+
+    // Keep stdout clean for the bundle itself.
+    eprintln!(
+        "{} Exported {} memories, {} knowledge nodes, {} knowledge edges from {}",
+        style("✓").green().bold(),
+        stats.memories,
+        stats.knowledge_nodes,
+        stats.knowledge_edges,
+        mem.name(),
+    );
+    Ok(())
+}
+
+async fn handle_import(
+    config: &Config,
+    backend: Option<&str>,
+    input: &Path,
+    skip_existing: bool,
+) -> Result<()> {
+    let file = std::fs::File::open(input)
+        .with_context(|| format!("failed to open {}", input.display()))?;
+    let mem = create_bundle_memory(config, backend)?;
+    let graph = open_knowledge_graph(config)?;
+
+    let stats = import_bundle(&*mem, graph.as_ref(), BufReader::new(file), skip_existing).await?;
+
+    println!(
+        "{} Imported {} memories ({} skipped), {} knowledge nodes, {} knowledge edges into {}",
+        style("✓").green().bold(),
+        stats.memories,
+        stats.skipped,
+        stats.knowledge_nodes,
+        stats.knowledge_edges,
+        mem.name(),
+    );
+    if graph.is_none() {
+        println!("  Knowledge graph records were skipped: [knowledge] is disabled.");
+    }
+    Ok(())
+}
+
+/// Rebuild the sqlite HNSW vector index from stored embeddings.
+async fn handle_rebuild_index(config: &Config) -> Result<()> {
+    let backend = effective_memory_backend_name(
+        &config.memory.backend,
+        Some(&config.storage.provider.config),
+    );
+    if !matches!(
+        classify_memory_backend(&backend),
+        MemoryBackendKind::Sqlite | MemoryBackendKind::Lucid
+    ) {
+        bail!("Vector index rebuild requires the sqlite or lucid memory backend (current: '{backend}').");
+    }
+
+    if config.memory.vector_index.trim() != "hnsw" {
+        println!(
+            "Vector index is '{}': recall scans embeddings directly, nothing to rebuild.",
+            config.memory.vector_index
+        );
+        println!("Set [memory].vector_index = \"hnsw\" to enable the HNSW index.");
+        return Ok(());
+    }
+
+    let params = HnswParams::from(&config.memory.hnsw);
+    let workspace_dir = config.workspace_dir.clone();
+    let (count, path) = tokio::task::spawn_blocking(move || -> Result<_> {
+        let mut mem = SqliteMemory::new(&workspace_dir)?;
+        let count = mem.rebuild_hnsw_index(params)?;
+        Ok((count, mem.vector_index_path()))
+    })
+    .await??;
+
+    println!(
+        "{} Rebuilt vector index with {count} embeddings: {}",
+        style("✓").green().bold(),
+        path.display(),
+    );
+
+    Ok(())
+}
+
+/// Regenerate stored embeddings after the embedding provider changed.
+async fn handle_reembed(config: &Config) -> Result<()> {
+    let backend = effective_memory_backend_name(
+        &config.memory.backend,
+        Some(&config.storage.provider.config),
+    );
+    if !matches!(
+        classify_memory_backend(&backend),
+        MemoryBackendKind::Sqlite | MemoryBackendKind::Lucid
+    ) {
+        bail!("Re-embedding requires the sqlite or lucid memory backend (current: '{backend}').");
+    }
+
+    let mem = create_sqlite_memory(
+        &config.memory,
+        &config.embedding_routes,
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    if mem.embedder().dimensions() == 0 {
+        bail!(
+            "No usable embedding provider (embedding_provider = '{}'); nothing to re-embed.",
+            config.memory.embedding_provider
+        );
+    }
+
+    let count = mem.reembed().await?;
+    println!(
+        "{} Re-embedded {count} memories with {}",
+        style("✓").green().bold(),
+        mem.embedder().fingerprint(),
+    );
+    Ok(())
+}
+
 fn parse_category(s: &str) -> MemoryCategory {
     match s.trim().to_ascii_lowercase().as_str() {
         "core" => MemoryCategory::Core,
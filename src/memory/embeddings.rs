@@ -1,4 +1,9 @@
 use async_trait::async_trait;
+#[cfg(feature = "embeddings-onnx")]
+use parking_lot::Mutex;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "embeddings-onnx")]
+use std::sync::Arc;
 
 /// Trait for embedding providers — convert text to vectors
 #[async_trait]
@@ -9,6 +14,13 @@ pub trait EmbeddingProvider: Send + Sync {
     /// Embedding dimensions
     fn dimensions(&self) -> usize;
 
+    /// Identity of the vector space this provider produces. Vectors with
+    /// different fingerprints are not comparable, so stored embeddings must be
+    /// regenerated when it changes.
+    fn fingerprint(&self) -> String {
+        format!("{}:{}", self.name(), self.dimensions())
+    }
+
     /// Embed a batch of texts into vectors
     async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>>;
 
@@ -103,6 +115,10 @@ impl EmbeddingProvider for OpenAiEmbedding {
         self.dims
     }
 
+    fn fingerprint(&self) -> String {
+        format!("openai:{}:{}", self.model, self.dims)
+    }
+
     async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
         if texts.is_empty() {
             return Ok(Vec::new());
@@ -154,6 +170,307 @@ impl EmbeddingProvider for OpenAiEmbedding {
     }
 }
 
+// ── Local ONNX provider (CPU, air-gapped) ────────────────────
+
+/// Texts per ONNX forward pass. Every sequence in a batch is padded to the
+/// longest one, so modest batches keep CPU memory bounded.
+pub const LOCAL_EMBEDDING_BATCH_SIZE: usize = 32;
+
+/// Tokens kept per text when the tokenizer does not set its own limit.
+#[cfg(feature = "embeddings-onnx")]
+const LOCAL_MAX_TOKENS: usize = 512;
+
+/// Sentence-embedding model run in-process on CPU.
+///
+/// `model_path` is a directory holding `model.onnx` (or `onnx/model.onnx`)
+/// and `tokenizer.json` — the layout of a Hugging Face ONNX export such as
+/// all-MiniLM-L6-v2 — or the `.onnx` file itself with `tokenizer.json` beside
+/// it. Token embeddings are mean-pooled over the attention mask and
+/// L2-normalized; models that already emit pooled `[batch, dims]` output are
+/// only normalized.
+///
+/// The ONNX Runtime shared library (`ORT_DYLIB_PATH`, or the system
+/// `libonnxruntime`) and the model are loaded on first use, on a blocking
+/// thread. Requires the `embeddings-onnx` feature.
+pub struct LocalEmbedding {
+    model_file: PathBuf,
+    tokenizer_file: PathBuf,
+    label: String,
+    dims: usize,
+    batch_size: usize,
+    #[cfg(feature = "embeddings-onnx")]
+    runtime: Arc<Mutex<Option<onnx::Runtime>>>,
+}
+
+impl LocalEmbedding {
+    pub fn new(model_path: &Path, dims: usize) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            dims > 0,
+            "local embeddings need [memory].embedding_dimensions > 0"
+        );
+        let (model_file, tokenizer_file) = resolve_local_model(model_path)?;
+        let label = model_path
+            .file_stem()
+            .map_or_else(|| "model".to_string(), |s| s.to_string_lossy().into_owned());
+
+        Ok(Self {
+            model_file,
+            tokenizer_file,
+            label,
+            dims,
+            batch_size: LOCAL_EMBEDDING_BATCH_SIZE,
+            #[cfg(feature = "embeddings-onnx")]
+            runtime: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    #[must_use]
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    #[cfg(feature = "embeddings-onnx")]
+    async fn run_batch(&self, texts: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> {
+        let runtime = self.runtime.clone();
+        let model_file = self.model_file.clone();
+        let tokenizer_file = self.tokenizer_file.clone();
+
+        // A missing ONNX Runtime library panics inside `ort`; running on a
+        // blocking task turns that into an error instead of a crash.
+        tokio::task::spawn_blocking(move || {
+            let mut slot = runtime.lock();
+            let mut runtime = match slot.take() {
+                Some(runtime) => runtime,
+                None => onnx::Runtime::load(&model_file, &tokenizer_file)?,
+            };
+            let result = runtime.embed(&texts);
+            *slot = Some(runtime);
+            result
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!("local embedding model failed to run: {e}"))?
+    }
+
+    #[cfg(not(feature = "embeddings-onnx"))]
+    #[allow(clippy::unused_async)]
+    async fn run_batch(&self, _texts: Vec<String>) -> anyhow::Result<Vec<Vec<f32>>> {
+        anyhow::bail!(
+            "local embeddings are not enabled in this build. \
+             Rebuild with `cargo build --features embeddings-onnx`."
+        )
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for LocalEmbedding {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims
+    }
+
+    fn fingerprint(&self) -> String {
+        format!("local:{}:{}", self.label, self.dims)
+    }
+
+    async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(self.batch_size) {
+            let batch: Vec<String> = batch.iter().map(|t| (*t).to_string()).collect();
+            let expected = batch.len();
+            let vectors = self.run_batch(batch).await?;
+            anyhow::ensure!(
+                vectors.len() == expected,
+                "local embedding model returned {} vectors for {expected} texts",
+                vectors.len()
+            );
+            if let Some(v) = vectors.iter().find(|v| v.len() != self.dims) {
+                anyhow::bail!(
+                    "local embedding model {} produces {}-dimensional vectors but \
+                     [memory].embedding_dimensions = {}",
+                    self.model_file.display(),
+                    v.len(),
+                    self.dims
+                );
+            }
+            embeddings.extend(vectors);
+        }
+        Ok(embeddings)
+    }
+}
+
+/// Locate `(model.onnx, tokenizer.json)` for a local model path.
+fn resolve_local_model(path: &Path) -> anyhow::Result<(PathBuf, PathBuf)> {
+    let (model_file, dir) = if path.is_dir() {
+        let nested = path.join("onnx").join("model.onnx");
+        let model = if nested.is_file() {
+            nested
+        } else {
+            path.join("model.onnx")
+        };
+        (model, path.to_path_buf())
+    } else {
+        let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        (path.to_path_buf(), dir)
+    };
+
+    anyhow::ensure!(
+        model_file.is_file(),
+        "local embedding model not found: {}",
+        model_file.display()
+    );
+    let tokenizer_file = dir.join("tokenizer.json");
+    anyhow::ensure!(
+        tokenizer_file.is_file(),
+        "local embedding tokenizer not found: {}",
+        tokenizer_file.display()
+    );
+    Ok((model_file, tokenizer_file))
+}
+
+/// Average token embeddings over the attention mask, one vector per sequence.
+/// `hidden` is `[batch, seq_len, dims]` and `mask` is `[batch, seq_len]`.
+#[cfg_attr(not(feature = "embeddings-onnx"), allow(dead_code))]
+fn mean_pool(hidden: &[f32], mask: &[i64], seq_len: usize, dims: usize) -> Vec<Vec<f32>> {
+    if seq_len == 0 || dims == 0 {
+        return Vec::new();
+    }
+    hidden
+        .chunks(seq_len * dims)
+        .zip(mask.chunks(seq_len))
+        .map(|(tokens, mask)| {
+            let mut sum = vec![0.0_f32; dims];
+            let mut count = 0_u32;
+            for (token, _) in tokens.chunks(dims).zip(mask).filter(|(_, &m)| m != 0) {
+                for (acc, value) in sum.iter_mut().zip(token) {
+                    *acc += value;
+                }
+                count += 1;
+            }
+            if count > 0 {
+                #[allow(clippy::cast_precision_loss)]
+                let count = count as f32;
+                for acc in &mut sum {
+                    *acc /= count;
+                }
+            }
+            sum
+        })
+        .collect()
+}
+
+#[cfg_attr(not(feature = "embeddings-onnx"), allow(dead_code))]
+fn l2_normalize(v: &mut [f32]) {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for x in v {
+            *x /= norm;
+        }
+    }
+}
+
+#[cfg(feature = "embeddings-onnx")]
+mod onnx {
+    use super::{l2_normalize, mean_pool, LOCAL_MAX_TOKENS};
+    use anyhow::Context;
+    use ort::session::{Session, SessionInputValue};
+    use ort::value::Tensor;
+    use std::borrow::Cow;
+    use std::path::Path;
+    use tokenizers::{Encoding, PaddingParams, Tokenizer, TruncationParams};
+
+    pub(super) struct Runtime {
+        session: Session,
+        tokenizer: Tokenizer,
+        input_names: Vec<String>,
+    }
+
+    impl Runtime {
+        pub(super) fn load(model_file: &Path, tokenizer_file: &Path) -> anyhow::Result<Self> {
+            let mut tokenizer = Tokenizer::from_file(tokenizer_file).map_err(|e| {
+                anyhow::anyhow!("failed to load tokenizer {}: {e}", tokenizer_file.display())
+            })?;
+            if tokenizer.get_truncation().is_none() {
+                tokenizer
+                    .with_truncation(Some(TruncationParams {
+                        max_length: LOCAL_MAX_TOKENS,
+                        ..TruncationParams::default()
+                    }))
+                    .map_err(|e| anyhow::anyhow!("invalid tokenizer truncation: {e}"))?;
+            }
+            // Pad each batch to its longest sequence.
+            tokenizer.with_padding(Some(PaddingParams::default()));
+
+            let session = Session::builder()?
+                .commit_from_file(model_file)
+                .with_context(|| format!("failed to load ONNX model {}", model_file.display()))?;
+            let input_names = session.inputs.iter().map(|i| i.name.clone()).collect();
+
+            tracing::info!(model = %model_file.display(), "Loaded local embedding model");
+            Ok(Self {
+                session,
+                tokenizer,
+                input_names,
+            })
+        }
+
+        pub(super) fn embed(&mut self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+            let encodings = self
+                .tokenizer
+                .encode_batch(texts.iter().map(String::as_str).collect(), true)
+                .map_err(|e| anyhow::anyhow!("tokenization failed: {e}"))?;
+            let batch = encodings.len();
+            let seq_len = encodings.first().map_or(0, |e| e.get_ids().len());
+            let shape = vec![i64::try_from(batch)?, i64::try_from(seq_len)?];
+
+            let flatten = |field: fn(&Encoding) -> &[u32]| -> Vec<i64> {
+                encodings
+                    .iter()
+                    .flat_map(|e| field(e).iter().map(|&v| i64::from(v)))
+                    .collect()
+            };
+            let mask = flatten(Encoding::get_attention_mask);
+
+            let mut inputs: Vec<(Cow<'_, str>, SessionInputValue<'_>)> =
+                Vec::with_capacity(self.input_names.len());
+            for name in &self.input_names {
+                let data = match name.as_str() {
+                    "input_ids" => flatten(Encoding::get_ids),
+                    "attention_mask" => mask.clone(),
+                    "token_type_ids" => flatten(Encoding::get_type_ids),
+                    other => anyhow::bail!("unsupported embedding model input '{other}'"),
+                };
+                let tensor = Tensor::from_array((shape.clone(), data))?;
+                inputs.push((Cow::Owned(name.clone()), tensor.into()));
+            }
+
+            let outputs = self.session.run(inputs)?;
+            let output = outputs
+                .get("sentence_embedding")
+                .unwrap_or_else(|| &outputs[0]);
+            let (dims, data) = output.try_extract_tensor::<f32>()?;
+
+            let mut vectors = match &**dims {
+                // Already pooled: [batch, dims]
+                [_, d] => data
+                    .chunks(usize::try_from(*d)?.max(1))
+                    .map(<[f32]>::to_vec)
+                    .collect(),
+                // Token embeddings: [batch, seq_len, dims]
+                [_, s, d] => mean_pool(data, &mask, usize::try_from(*s)?, usize::try_from(*d)?),
+                other => anyhow::bail!("unexpected embedding output shape {other:?}"),
+            };
+            for v in &mut vectors {
+                l2_normalize(v);
+            }
+            Ok(vectors)
+        }
+    }
+}
+
 // ── Factory ──────────────────────────────────────────────────
 
 pub fn create_embedding_provider(
@@ -186,10 +503,32 @@ pub fn create_embedding_provider(
             let key = api_key.unwrap_or("");
             Box::new(OpenAiEmbedding::new(base_url, key, model, dims))
         }
+        "local" => create_local_embedding(model, dims),
         _ => Box::new(NoopEmbedding),
     }
 }
 
+/// Local provider: `model` is the model directory (or `.onnx` file). Falls
+/// back to keyword-only recall when the model is unusable.
+fn create_local_embedding(model: &str, dims: usize) -> Box<dyn EmbeddingProvider> {
+    if !cfg!(feature = "embeddings-onnx") {
+        tracing::warn!(
+            "embedding_provider = \"local\" requires a build with `--features embeddings-onnx`; \
+             falling back to keyword-only recall"
+        );
+        return Box::new(NoopEmbedding);
+    }
+    match LocalEmbedding::new(Path::new(model), dims) {
+        Ok(provider) => Box::new(provider),
+        Err(e) => {
+            tracing::warn!(
+                "Local embeddings unavailable, falling back to keyword-only recall: {e}"
+            );
+            Box::new(NoopEmbedding)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -355,4 +694,105 @@ mod tests {
             "https://my-api.example.com/api/v2/embeddings"
         );
     }
+
+    // ── Local provider ───────────────────────────────────────────
+
+    fn local_model_dir(nested: bool) -> tempfile::TempDir {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let model_dir = if nested {
+            std::fs::create_dir(tmp.path().join("onnx")).unwrap();
+            tmp.path().join("onnx")
+        } else {
+            tmp.path().to_path_buf()
+        };
+        std::fs::write(model_dir.join("model.onnx"), b"onnx").unwrap();
+        std::fs::write(tmp.path().join("tokenizer.json"), b"{}").unwrap();
+        tmp
+    }
+
+    #[test]
+    fn local_resolves_model_directory_layouts() {
+        for nested in [false, true] {
+            let tmp = local_model_dir(nested);
+            let p = LocalEmbedding::new(tmp.path(), 384).unwrap();
+            assert!(p.model_file.ends_with("model.onnx"));
+            assert_eq!(p.tokenizer_file, tmp.path().join("tokenizer.json"));
+            assert_eq!(p.name(), "local");
+            assert_eq!(p.dimensions(), 384);
+        }
+
+        let tmp = local_model_dir(false);
+        let p = LocalEmbedding::new(&tmp.path().join("model.onnx"), 384).unwrap();
+        assert_eq!(p.tokenizer_file, tmp.path().join("tokenizer.json"));
+    }
+
+    #[test]
+    fn local_rejects_missing_files_and_zero_dims() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        assert!(LocalEmbedding::new(tmp.path(), 384).is_err());
+
+        std::fs::write(tmp.path().join("model.onnx"), b"onnx").unwrap();
+        let err = LocalEmbedding::new(tmp.path(), 384).err().unwrap();
+        assert!(err.to_string().contains("tokenizer"));
+
+        let tmp = local_model_dir(false);
+        assert!(LocalEmbedding::new(tmp.path(), 0).is_err());
+    }
+
+    #[test]
+    fn factory_local_without_model_falls_back_to_noop() {
+        let p = create_embedding_provider("local", None, "/nonexistent/model", 384);
+        assert_eq!(p.name(), "none");
+    }
+
+    #[cfg(not(feature = "embeddings-onnx"))]
+    #[tokio::test]
+    async fn local_embed_requires_feature() {
+        let tmp = local_model_dir(false);
+        let p = LocalEmbedding::new(tmp.path(), 384).unwrap();
+        let err = p.embed(&["hello"]).await.unwrap_err();
+        assert!(err.to_string().contains("embeddings-onnx"));
+
+        let p = create_embedding_provider("local", None, tmp.path().to_str().unwrap(), 384);
+        assert_eq!(p.name(), "none");
+    }
+
+    #[test]
+    fn fingerprints_identify_model_and_dimensions() {
+        let tmp = local_model_dir(false);
+        let local = LocalEmbedding::new(tmp.path(), 384).unwrap();
+        assert!(local.fingerprint().starts_with("local:"));
+        assert!(local.fingerprint().ends_with(":384"));
+
+        let small = OpenAiEmbedding::new("http://localhost", "k", "text-embedding-3-small", 1536);
+        let large = OpenAiEmbedding::new("http://localhost", "k", "text-embedding-3-large", 1536);
+        assert_ne!(small.fingerprint(), large.fingerprint());
+        assert_eq!(NoopEmbedding.fingerprint(), "none:0");
+    }
+
+    #[test]
+    fn mean_pool_ignores_padding_tokens() {
+        // Two sequences of three tokens, two dimensions; the second sequence
+        // has one padding token that must not affect the average.
+        let hidden = [
+            1.0, 0.0, 3.0, 0.0, 5.0, 0.0, //
+            0.0, 2.0, 0.0, 4.0, 9.0, 9.0,
+        ];
+        let mask = [1, 1, 1, 1, 1, 0];
+        let pooled = mean_pool(&hidden, &mask, 3, 2);
+        assert_eq!(pooled, vec![vec![3.0, 0.0], vec![0.0, 3.0]]);
+        assert!(mean_pool(&hidden, &mask, 0, 2).is_empty());
+    }
+
+    #[test]
+    fn l2_normalize_produces_unit_vectors() {
+        let mut v = [3.0, 4.0];
+        l2_normalize(&mut v);
+        assert!((v[0] - 0.6).abs() < 1e-6);
+        assert!((v[1] - 0.8).abs() < 1e-6);
+
+        let mut zero = [0.0, 0.0];
+        l2_normalize(&mut zero);
+        assert_eq!(zero, [0.0, 0.0]);
+    }
 }
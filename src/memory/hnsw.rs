@@ -0,0 +1,740 @@
+// HNSW (Hierarchical Navigable Small World) index for approximate
+// nearest-neighbour recall over memory embeddings.
+//
+// Vectors are kept normalized in memory so similarity is a dot product. Only
+// the graph is persisted (next to brain.db as brain.hnsw): ids, per-vector
+// fingerprints and neighbour lists. Embeddings are reloaded from SQLite on
+// open, and nodes whose row vanished or was re-embedded are dropped, so the
+// file never has to be written on every store.
+
+use super::vector::{vector_fingerprint, VectorIndex};
+use anyhow::{bail, Context};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const FILE_MAGIC: &[u8; 8] = b"ZCHNSW01";
+
+/// Upper bound on node levels; with M ≥ 2 real levels stay far below this.
+const MAX_LEVEL: usize = 16;
+
+/// Tuning knobs for [`HnswIndex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HnswParams {
+    /// Neighbours kept per node on upper layers (twice this on layer 0).
+    pub m: usize,
+    /// Candidate list size while inserting.
+    pub ef_construction: usize,
+    /// Candidate list size while searching.
+    pub ef_search: usize,
+}
+
+impl Default for HnswParams {
+    fn default() -> Self {
+        Self {
+            m: 16,
+            ef_construction: 200,
+            ef_search: 64,
+        }
+    }
+}
+
+impl From<&crate::config::HnswConfig> for HnswParams {
+    fn from(config: &crate::config::HnswConfig) -> Self {
+        Self {
+            m: config.m,
+            ef_construction: config.ef_construction,
+            ef_search: config.ef_search,
+        }
+    }
+}
+
+struct Node {
+    id: String,
+    /// Unit-length embedding.
+    vector: Vec<f32>,
+    /// Fingerprint of the embedding as stored (before normalization).
+    fingerprint: u64,
+    /// Neighbour lists, one per layer the node lives on. Empty once removed.
+    links: Vec<Vec<u32>>,
+    removed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate {
+    dist: f32,
+    node: u32,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.dist
+            .total_cmp(&other.dist)
+            .then(self.node.cmp(&other.node))
+    }
+}
+
+/// In-process HNSW graph keyed by memory id.
+pub struct HnswIndex {
+    params: HnswParams,
+    nodes: Vec<Node>,
+    by_id: HashMap<String, u32>,
+    entry: Option<u32>,
+    dims: usize,
+    rng: u64,
+}
+
+fn normalize(v: &[f32]) -> Option<Vec<f32>> {
+    let norm = v
+        .iter()
+        .map(|x| f64::from(*x) * f64::from(*x))
+        .sum::<f64>()
+        .sqrt();
+    if v.is_empty() || !norm.is_finite() || norm < f64::EPSILON {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    Some(v.iter().map(|x| (f64::from(*x) / norm) as f32).collect())
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+impl HnswIndex {
+    pub fn new(params: HnswParams) -> Self {
+        Self {
+            params: HnswParams {
+                m: params.m.max(2),
+                ef_construction: params.ef_construction.max(1),
+                ef_search: params.ef_search.max(1),
+            },
+            nodes: Vec::new(),
+            by_id: HashMap::new(),
+            entry: None,
+            dims: 0,
+            rng: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+
+    /// Load a persisted graph, attaching embeddings from `embeddings`
+    /// (id → embedding as stored). Nodes without a matching embedding are
+    /// dropped; callers insert any embeddings the graph does not cover.
+    pub fn load(
+        path: &Path,
+        params: HnswParams,
+        embeddings: &HashMap<String, Vec<f32>>,
+    ) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        let mut reader = BufReader::new(file);
+
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != FILE_MAGIC {
+            bail!("{} is not an HNSW index file", path.display());
+        }
+
+        let mut index = Self::new(params);
+        index.dims = read_u32(&mut reader)? as usize;
+        let stored_m = read_u32(&mut reader)? as usize;
+        if stored_m != index.params.m {
+            bail!(
+                "index was built with m = {stored_m}, config has m = {}",
+                index.params.m
+            );
+        }
+        let count = read_u32(&mut reader)?;
+        let entry = read_u32(&mut reader)?;
+
+        let mut stale = Vec::new();
+        for node_idx in 0..count {
+            let id_len = read_u32(&mut reader)? as usize;
+            let mut id = vec![0u8; id_len];
+            reader.read_exact(&mut id)?;
+            let id = String::from_utf8(id).context("index contains a non-UTF-8 id")?;
+            let fingerprint = read_u64(&mut reader)?;
+            let levels = read_u32(&mut reader)? as usize;
+            if levels == 0 || levels > MAX_LEVEL + 1 {
+                bail!("index node {node_idx} has invalid level count {levels}");
+            }
+            let mut links = Vec::with_capacity(levels);
+            for _ in 0..levels {
+                let n = read_u32(&mut reader)? as usize;
+                let mut layer = Vec::with_capacity(n);
+                for _ in 0..n {
+                    let neighbour = read_u32(&mut reader)?;
+                    if neighbour >= count {
+                        bail!("index node {node_idx} links to missing node {neighbour}");
+                    }
+                    layer.push(neighbour);
+                }
+                links.push(layer);
+            }
+
+            let vector = embeddings
+                .get(&id)
+                .filter(|raw| vector_fingerprint(raw) == fingerprint)
+                .and_then(|raw| normalize(raw))
+                .filter(|unit| unit.len() == index.dims);
+            if vector.is_none() {
+                stale.push(id.clone());
+            }
+            index.by_id.insert(id.clone(), node_idx);
+            index.nodes.push(Node {
+                id,
+                vector: vector.unwrap_or_default(),
+                fingerprint,
+                links,
+                removed: false,
+            });
+        }
+        index.entry = (entry < count).then_some(entry);
+
+        for id in stale {
+            index.remove(&id);
+        }
+        Ok(index)
+    }
+
+    fn next_random(&mut self) -> u64 {
+        // SplitMix64: deterministic, so identical inserts build identical graphs.
+        self.rng = self.rng.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn random_level(&mut self) -> usize {
+        // Uniform in (0, 1]; level = floor(-ln(u) / ln(M)).
+        let u = ((self.next_random() >> 11) + 1) as f64 / (1u64 << 53) as f64;
+        let level = (-u.ln() / (self.params.m as f64).ln()).floor() as usize;
+        level.min(MAX_LEVEL)
+    }
+
+    fn capacity(&self, layer: usize) -> usize {
+        if layer == 0 {
+            self.params.m * 2
+        } else {
+            self.params.m
+        }
+    }
+
+    fn top_level(&self, node: u32) -> usize {
+        self.nodes[node as usize].links.len().saturating_sub(1)
+    }
+
+    fn distance(&self, query: &[f32], node: u32) -> f32 {
+        1.0 - dot(query, &self.nodes[node as usize].vector)
+    }
+
+    fn greedy(&self, query: &[f32], mut current: u32, layer: usize) -> u32 {
+        let mut current_dist = self.distance(query, current);
+        loop {
+            let mut improved = false;
+            if let Some(links) = self.nodes[current as usize].links.get(layer) {
+                for &n in links {
+                    let d = self.distance(query, n);
+                    if d < current_dist {
+                        current = n;
+                        current_dist = d;
+                        improved = true;
+                    }
+                }
+            }
+            if !improved {
+                return current;
+            }
+        }
+    }
+
+    /// Best-first search on one layer. Returns up to `ef` candidates,
+    /// closest first.
+    fn search_layer(&self, query: &[f32], entry: u32, ef: usize, layer: usize) -> Vec<Candidate> {
+        let first = Candidate {
+            dist: self.distance(query, entry),
+            node: entry,
+        };
+        let mut visited = HashSet::from([entry]);
+        let mut frontier = BinaryHeap::from([Reverse(first)]);
+        let mut results = BinaryHeap::from([first]);
+
+        while let Some(Reverse(current)) = frontier.pop() {
+            let worst = results.peek().map_or(f32::INFINITY, |c| c.dist);
+            if results.len() >= ef && current.dist > worst {
+                break;
+            }
+            let Some(links) = self.nodes[current.node as usize].links.get(layer) else {
+                continue;
+            };
+            for &n in links {
+                if !visited.insert(n) {
+                    continue;
+                }
+                let candidate = Candidate {
+                    dist: self.distance(query, n),
+                    node: n,
+                };
+                let worst = results.peek().map_or(f32::INFINITY, |c| c.dist);
+                if results.len() < ef || candidate.dist < worst {
+                    frontier.push(Reverse(candidate));
+                    results.push(candidate);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        results.into_sorted_vec()
+    }
+
+    /// Add a directed edge, pruning `from` back to its closest neighbours.
+    fn link(&mut self, from: u32, to: u32, layer: usize) {
+        let cap = self.capacity(layer);
+        let links = &mut self.nodes[from as usize].links[layer];
+        if links.contains(&to) {
+            return;
+        }
+        links.push(to);
+        if links.len() <= cap {
+            return;
+        }
+
+        let base = &self.nodes[from as usize].vector;
+        let mut scored: Vec<Candidate> = self.nodes[from as usize].links[layer]
+            .iter()
+            .map(|&n| Candidate {
+                dist: 1.0 - dot(base, &self.nodes[n as usize].vector),
+                node: n,
+            })
+            .collect();
+        scored.sort();
+        scored.truncate(cap);
+        self.nodes[from as usize].links[layer] = scored.into_iter().map(|c| c.node).collect();
+    }
+
+    /// Drop removed nodes and renumber the rest.
+    #[allow(clippy::cast_possible_truncation)]
+    fn compact(&mut self) {
+        let mut remap = vec![u32::MAX; self.nodes.len()];
+        let mut next = 0u32;
+        for (old, node) in self.nodes.iter().enumerate() {
+            if !node.removed {
+                remap[old] = next;
+                next += 1;
+            }
+        }
+
+        let nodes = std::mem::take(&mut self.nodes);
+        self.nodes = nodes
+            .into_iter()
+            .filter(|node| !node.removed)
+            .map(|mut node| {
+                for layer in &mut node.links {
+                    for n in layer.iter_mut() {
+                        *n = remap[*n as usize];
+                    }
+                }
+                node
+            })
+            .collect();
+        self.by_id = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.id.clone(), i as u32))
+            .collect();
+        self.entry = self.entry.map(|e| remap[e as usize]);
+    }
+
+    fn write_to(&self, writer: &mut impl Write) -> anyhow::Result<()> {
+        // Removed nodes are skipped; live ones are renumbered densely.
+        let mut remap = vec![u32::MAX; self.nodes.len()];
+        let mut live = 0u32;
+        for (old, node) in self.nodes.iter().enumerate() {
+            if !node.removed {
+                remap[old] = live;
+                live += 1;
+            }
+        }
+
+        writer.write_all(FILE_MAGIC)?;
+        write_u32(writer, self.dims)?;
+        write_u32(writer, self.params.m)?;
+        writer.write_all(&live.to_le_bytes())?;
+        let entry = self.entry.map_or(u32::MAX, |e| remap[e as usize]);
+        writer.write_all(&entry.to_le_bytes())?;
+
+        for node in self.nodes.iter().filter(|node| !node.removed) {
+            write_u32(writer, node.id.len())?;
+            writer.write_all(node.id.as_bytes())?;
+            writer.write_all(&node.fingerprint.to_le_bytes())?;
+            write_u32(writer, node.links.len())?;
+            for layer in &node.links {
+                write_u32(writer, layer.len())?;
+                for n in layer {
+                    writer.write_all(&remap[*n as usize].to_le_bytes())?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl VectorIndex for HnswIndex {
+    fn name(&self) -> &str {
+        "hnsw"
+    }
+
+    fn len(&self) -> usize {
+        self.by_id.len()
+    }
+
+    fn contains(&self, id: &str, fingerprint: u64) -> bool {
+        self.by_id
+            .get(id)
+            .is_some_and(|&idx| self.nodes[idx as usize].fingerprint == fingerprint)
+    }
+
+    fn insert(&mut self, id: &str, embedding: &[f32]) {
+        self.remove(id);
+        let Some(unit) = normalize(embedding) else {
+            return;
+        };
+        if self.by_id.is_empty() {
+            self.dims = unit.len();
+        } else if unit.len() != self.dims {
+            tracing::warn!(
+                id,
+                expected = self.dims,
+                actual = unit.len(),
+                "Skipping embedding with mismatched dimensions; rebuild the vector index after changing embedding models"
+            );
+            return;
+        }
+
+        let level = self.random_level();
+        #[allow(clippy::cast_possible_truncation)]
+        let idx = self.nodes.len() as u32;
+        self.nodes.push(Node {
+            id: id.to_string(),
+            vector: unit,
+            fingerprint: vector_fingerprint(embedding),
+            links: vec![Vec::new(); level + 1],
+            removed: false,
+        });
+        self.by_id.insert(id.to_string(), idx);
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(idx);
+            return;
+        };
+
+        let query = self.nodes[idx as usize].vector.clone();
+        let top = self.top_level(entry);
+        let mut ep = entry;
+        for layer in (level + 1..=top).rev() {
+            ep = self.greedy(&query, ep, layer);
+        }
+        for layer in (0..=level.min(top)).rev() {
+            let found = self.search_layer(&query, ep, self.params.ef_construction, layer);
+            let chosen: Vec<u32> = found
+                .iter()
+                .filter(|c| c.node != idx)
+                .take(self.capacity(layer))
+                .map(|c| c.node)
+                .collect();
+            for &n in &chosen {
+                self.link(n, idx, layer);
+            }
+            self.nodes[idx as usize].links[layer] = chosen;
+            if let Some(best) = found.first() {
+                ep = best.node;
+            }
+        }
+
+        if level > top {
+            self.entry = Some(idx);
+        }
+    }
+
+    fn remove(&mut self, id: &str) -> bool {
+        let Some(idx) = self.by_id.remove(id) else {
+            return false;
+        };
+        let former = std::mem::take(&mut self.nodes[idx as usize].links);
+        self.nodes[idx as usize].removed = true;
+        self.nodes[idx as usize].vector = Vec::new();
+
+        // Unlink every node pointing at the removed one and reconnect it
+        // through the removed node's own neighbours.
+        for (layer, former_links) in former.iter().enumerate() {
+            #[allow(clippy::cast_possible_truncation)]
+            let orphans: Vec<u32> = (0..self.nodes.len() as u32)
+                .filter(|&n| {
+                    self.nodes[n as usize]
+                        .links
+                        .get(layer)
+                        .is_some_and(|links| links.contains(&idx))
+                })
+                .collect();
+            for n in orphans {
+                self.nodes[n as usize].links[layer].retain(|&x| x != idx);
+                for &candidate in former_links {
+                    if candidate != n && !self.nodes[candidate as usize].removed {
+                        self.link(n, candidate, layer);
+                    }
+                }
+            }
+        }
+
+        if self.entry == Some(idx) {
+            #[allow(clippy::cast_possible_truncation)]
+            let replacement = self
+                .nodes
+                .iter()
+                .enumerate()
+                .filter(|(_, node)| !node.removed)
+                .max_by_key(|(_, node)| node.links.len())
+                .map(|(i, _)| i as u32);
+            self.entry = replacement;
+        }
+
+        if self.nodes.len() > 64 && self.by_id.len() < self.nodes.len() / 2 {
+            self.compact();
+        }
+        true
+    }
+
+    fn search(&self, query: &[f32], limit: usize) -> Vec<(String, f32)> {
+        let Some(entry) = self.entry else {
+            return Vec::new();
+        };
+        if limit == 0 {
+            return Vec::new();
+        }
+        let Some(query) = normalize(query) else {
+            return Vec::new();
+        };
+        if query.len() != self.dims {
+            return Vec::new();
+        }
+
+        let mut ep = entry;
+        for layer in (1..=self.top_level(entry)).rev() {
+            ep = self.greedy(&query, ep, layer);
+        }
+        self.search_layer(&query, ep, self.params.ef_search.max(limit), 0)
+            .into_iter()
+            .take(limit)
+            .filter_map(|c| {
+                let sim = (1.0 - c.dist).clamp(0.0, 1.0);
+                (sim > 0.0).then(|| (self.nodes[c.node as usize].id.clone(), sim))
+            })
+            .collect()
+    }
+
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        // Write to a sibling temp file and rename, so a crash never leaves a
+        // truncated index behind.
+        let tmp = path.with_extension("hnsw.tmp");
+        {
+            let file = std::fs::File::create(&tmp)
+                .with_context(|| format!("failed to create {}", tmp.display()))?;
+            let mut writer = BufWriter::new(file);
+            self.write_to(&mut writer)?;
+            writer.flush()?;
+        }
+        std::fs::rename(&tmp, path)
+            .with_context(|| format!("failed to move index into {}", path.display()))?;
+        Ok(())
+    }
+}
+
+fn write_u32(writer: &mut impl Write, value: usize) -> anyhow::Result<()> {
+    let value = u32::try_from(value).context("index too large to persist")?;
+    writer.write_all(&value.to_le_bytes())?;
+    Ok(())
+}
+
+fn read_u32(reader: &mut impl Read) -> anyhow::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64(reader: &mut impl Read) -> anyhow::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+#[cfg(test)]
+#[allow(clippy::cast_precision_loss)]
+mod tests {
+    use super::*;
+    use crate::memory::vector::cosine_similarity;
+    use tempfile::TempDir;
+
+    /// Deterministic pseudo-random vectors.
+    fn vectors(count: usize, dims: usize) -> Vec<(String, Vec<f32>)> {
+        let mut state = 42u64;
+        (0..count)
+            .map(|i| {
+                let v = (0..dims)
+                    .map(|_| {
+                        state = state
+                            .wrapping_mul(6_364_136_223_846_793_005)
+                            .wrapping_add(1);
+                        ((state >> 33) as f32 / u32::MAX as f32) + 0.01
+                    })
+                    .collect();
+                (format!("id-{i}"), v)
+            })
+            .collect()
+    }
+
+    fn brute_force(data: &[(String, Vec<f32>)], query: &[f32], k: usize) -> Vec<String> {
+        let mut scored: Vec<_> = data
+            .iter()
+            .map(|(id, v)| (id.clone(), cosine_similarity(query, v)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().take(k).map(|(id, _)| id).collect()
+    }
+
+    fn build(data: &[(String, Vec<f32>)]) -> HnswIndex {
+        let mut index = HnswIndex::new(HnswParams::default());
+        for (id, v) in data {
+            index.insert(id, v);
+        }
+        index
+    }
+
+    #[test]
+    fn empty_index_returns_nothing() {
+        let index = HnswIndex::new(HnswParams::default());
+        assert!(index.is_empty());
+        assert!(index.search(&[1.0, 0.0], 5).is_empty());
+    }
+
+    #[test]
+    fn exact_match_ranks_first() {
+        let data = vectors(300, 16);
+        let index = build(&data);
+        assert_eq!(index.len(), 300);
+        for (id, v) in data.iter().step_by(37) {
+            let hits = index.search(v, 3);
+            assert_eq!(&hits[0].0, id);
+            assert!((hits[0].1 - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn recall_matches_brute_force() {
+        let data = vectors(500, 24);
+        let index = build(&data);
+        let queries = vectors(20, 24);
+        let mut overlap = 0;
+        for (_, q) in &queries {
+            let exact = brute_force(&data, q, 10);
+            let approx: Vec<String> = index.search(q, 10).into_iter().map(|(id, _)| id).collect();
+            overlap += exact.iter().filter(|id| approx.contains(id)).count();
+        }
+        let recall = overlap as f32 / 200.0;
+        assert!(recall >= 0.9, "recall@10 too low: {recall}");
+    }
+
+    #[test]
+    fn remove_and_replace_keep_graph_searchable() {
+        let data = vectors(200, 16);
+        let mut index = build(&data);
+
+        for (id, _) in data.iter().take(120) {
+            assert!(index.remove(id));
+        }
+        assert!(!index.remove("id-0"));
+        assert_eq!(index.len(), 80);
+
+        for (id, v) in data.iter().skip(120) {
+            let hits = index.search(v, 1);
+            assert_eq!(&hits[0].0, id);
+        }
+        let removed: HashSet<&str> = data.iter().take(120).map(|(id, _)| id.as_str()).collect();
+        let hits = index.search(&data[0].1, 10);
+        assert_eq!(hits.len(), 10);
+        assert!(hits.iter().all(|(id, _)| !removed.contains(id.as_str())));
+
+        // Replacing an id swaps its embedding.
+        let replacement = data[0].1.clone();
+        index.insert("id-150", &replacement);
+        assert_eq!(index.len(), 80);
+        assert_eq!(index.search(&replacement, 1)[0].0, "id-150");
+        assert!(index.contains("id-150", vector_fingerprint(&replacement)));
+        assert!(!index.contains("id-150", vector_fingerprint(&data[150].1)));
+    }
+
+    #[test]
+    fn mismatched_dimensions_are_skipped() {
+        let mut index = HnswIndex::new(HnswParams::default());
+        index.insert("a", &[1.0, 0.0, 0.0]);
+        index.insert("b", &[1.0, 0.0]);
+        index.insert("zero", &[0.0, 0.0, 0.0]);
+        assert_eq!(index.len(), 1);
+        assert!(index.search(&[1.0, 0.0], 5).is_empty());
+    }
+
+    #[test]
+    fn save_and_load_roundtrip_drops_stale_nodes() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("brain.hnsw");
+        let data = vectors(150, 12);
+        let mut index = build(&data);
+        index.remove("id-3");
+        index.save(&path).unwrap();
+
+        let mut embeddings: HashMap<String, Vec<f32>> = data.iter().cloned().collect();
+        embeddings.remove("id-7"); // row deleted behind the index's back
+        embeddings.insert("id-9".into(), data[10].1.clone()); // row re-embedded
+
+        let loaded = HnswIndex::load(&path, HnswParams::default(), &embeddings).unwrap();
+        assert_eq!(loaded.len(), 147);
+        assert!(!loaded.contains("id-3", vector_fingerprint(&data[3].1)));
+        assert!(!loaded.contains("id-7", vector_fingerprint(&data[7].1)));
+        assert!(!loaded.contains("id-9", vector_fingerprint(&data[9].1)));
+        for (id, v) in data.iter().skip(20) {
+            assert_eq!(&loaded.search(v, 1)[0].0, id);
+        }
+    }
+
+    #[test]
+    fn load_rejects_foreign_files_and_param_changes() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("brain.hnsw");
+        std::fs::write(&path, b"not an index").unwrap();
+        assert!(HnswIndex::load(&path, HnswParams::default(), &HashMap::new()).is_err());
+
+        build(&vectors(10, 4)).save(&path).unwrap();
+        let params = HnswParams {
+            m: 8,
+            ..HnswParams::default()
+        };
+        assert!(HnswIndex::load(&path, params, &HashMap::new()).is_err());
+    }
+}
@@ -11,12 +11,15 @@ const HYGIENE_INTERVAL_HOURS: i64 = 12;
 const STATE_FILE: &str = "memory_hygiene_state.json";
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 struct HygieneReport {
     archived_memory_files: u64,
     archived_session_files: u64,
     purged_memory_archives: u64,
     purged_session_archives: u64,
     pruned_conversation_rows: u64,
+    expired_memory_rows: u64,
+    decayed_memory_rows: u64,
 }
 
 impl HygieneReport {
@@ -26,6 +29,8 @@ impl HygieneReport {
             + self.purged_memory_archives
             + self.purged_session_archives
             + self.pruned_conversation_rows
+            + self.expired_memory_rows
+            + self.decayed_memory_rows
     }
 }
 
@@ -43,9 +48,18 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
         return Ok(());
     }
 
-    if !should_run_now(workspace_dir)? {
+    let last_run_at = last_run_at(workspace_dir)?;
+    let now = Utc::now();
+    if last_run_at.is_some_and(|last| {
+        now.signed_duration_since(last) < Duration::hours(HYGIENE_INTERVAL_HOURS)
+    }) {
         return Ok(());
     }
+    // Decay covers the time since the previous pass (one cadence window on
+    // the first run).
+    let since_last_run = last_run_at.map_or(Duration::hours(HYGIENE_INTERVAL_HOURS), |last| {
+        now.signed_duration_since(last)
+    });
 
     let report = HygieneReport {
         archived_memory_files: archive_daily_memory_files(
@@ -59,46 +73,49 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
             workspace_dir,
             config.conversation_retention_days,
         )?,
+        expired_memory_rows: expire_memory_rows(workspace_dir)?,
+        decayed_memory_rows: decay_memory_importance(
+            workspace_dir,
+            config.importance_half_life_days,
+            since_last_run,
+        )?,
     };
 
     write_state(workspace_dir, &report)?;
 
     if report.total_actions() > 0 {
         tracing::info!(
-            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={}",
+            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={} expired_memory_rows={} decayed_memory_rows={}",
             report.archived_memory_files,
             report.archived_session_files,
             report.purged_memory_archives,
             report.purged_session_archives,
             report.pruned_conversation_rows,
+            report.expired_memory_rows,
+            report.decayed_memory_rows,
         );
     }
 
     Ok(())
 }
 
-fn should_run_now(workspace_dir: &Path) -> Result<bool> {
+/// Time of the previous hygiene pass, or `None` when it should run now
+/// regardless (no state yet, or unreadable state).
+fn last_run_at(workspace_dir: &Path) -> Result<Option<DateTime<Utc>>> {
     let path = state_path(workspace_dir);
     if !path.exists() {
-        return Ok(true);
+        return Ok(None);
     }
 
     let raw = fs::read_to_string(&path)?;
-    let state: HygieneState = match serde_json::from_str(&raw) {
-        Ok(s) => s,
-        Err(_) => return Ok(true),
-    };
-
-    let Some(last_run_at) = state.last_run_at else {
-        return Ok(true);
+    let Ok(state) = serde_json::from_str::<HygieneState>(&raw) else {
+        return Ok(None);
     };
 
-    let last = match DateTime::parse_from_rfc3339(&last_run_at) {
-        Ok(ts) => ts.with_timezone(&Utc),
-        Err(_) => return Ok(true),
-    };
-
-    Ok(Utc::now().signed_duration_since(last) >= Duration::hours(HYGIENE_INTERVAL_HOURS))
+    Ok(state
+        .last_run_at
+        .and_then(|ts| DateTime::parse_from_rfc3339(&ts).ok())
+        .map(|ts| ts.with_timezone(&Utc)))
 }
 
 fn write_state(workspace_dir: &Path, report: &HygieneReport) -> Result<()> {
@@ -318,6 +335,107 @@ fn prune_conversation_rows(workspace_dir: &Path, retention_days: u32) -> Result<
     Ok(u64::try_from(affected).unwrap_or(0))
 }
 
+/// Open the sqlite backend's database for a hygiene pass, if it exists and
+/// already has entry metadata columns.
+fn open_memory_db_with_metadata(workspace_dir: &Path) -> Result<Option<Connection>> {
+    let db_path = workspace_dir.join("memory").join("brain.db");
+    if !db_path.exists() {
+        return Ok(None);
+    }
+
+    let conn = Connection::open(db_path)?;
+    conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
+    // Columns are added when the backend next opens the database.
+    let has_metadata = conn
+        .prepare("SELECT 1 FROM pragma_table_info('memories') WHERE name = 'expires_at'")?
+        .exists([])?;
+    Ok(has_metadata.then_some(conn))
+}
+
+/// Delete sqlite memories whose `expires_at` has passed, along with their
+/// vectors in any index a live backend in this process has attached.
+fn expire_memory_rows(workspace_dir: &Path) -> Result<u64> {
+    let Some(conn) = open_memory_db_with_metadata(workspace_dir)? else {
+        return Ok(0);
+    };
+
+    // Backends store `expires_at` as UTC RFC 3339, so text order is time order.
+    let expired: Vec<String> = {
+        let mut stmt = conn.prepare(
+            "DELETE FROM memories WHERE expires_at IS NOT NULL AND expires_at <= ?1
+             RETURNING id",
+        )?;
+        let rows = stmt.query_map(params![Utc::now().to_rfc3339()], |row| row.get(0))?;
+        rows.collect::<rusqlite::Result<_>>()?
+    };
+    if !expired.is_empty() {
+        let db_path = workspace_dir.join("memory").join("brain.db");
+        super::sqlite::remove_from_attached_indexes(&db_path, &expired);
+    }
+
+    Ok(u64::try_from(expired.len()).unwrap_or(0))
+}
+
+/// Decay the importance of sqlite memories idle (neither written nor
+/// recalled) for at least `half_life_days`, halving it for every half-life
+/// in `elapsed`.
+fn decay_memory_importance(
+    workspace_dir: &Path,
+    half_life_days: u32,
+    elapsed: Duration,
+) -> Result<u64> {
+    if half_life_days == 0 {
+        return Ok(0);
+    }
+    let Some(mut conn) = open_memory_db_with_metadata(workspace_dir)? else {
+        return Ok(0);
+    };
+
+    let now = Utc::now();
+    let half_life = Duration::days(i64::from(half_life_days));
+    #[allow(clippy::cast_precision_loss)]
+    let factor = 0.5_f64.powf(elapsed.num_seconds().max(0) as f64 / half_life.num_seconds() as f64);
+    let parse = |ts: Option<String>| {
+        ts.and_then(|ts| DateTime::parse_from_rfc3339(&ts).ok())
+            .map(|ts| ts.with_timezone(&Utc))
+    };
+
+    let tx = conn.transaction()?;
+    let idle: Vec<(String, f64)> = {
+        let mut stmt = tx.prepare(
+            "SELECT id, importance, updated_at, last_accessed_at FROM memories
+             WHERE importance IS NOT NULL AND importance > 0",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, f64>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })?;
+        let mut idle = Vec::new();
+        for row in rows {
+            let (id, importance, updated_at, last_accessed_at) = row?;
+            let last_active = parse(updated_at).max(parse(last_accessed_at));
+            if last_active.is_some_and(|last| now.signed_duration_since(last) >= half_life) {
+                idle.push((id, importance));
+            }
+        }
+        idle
+    };
+
+    for (id, importance) in &idle {
+        tx.execute(
+            "UPDATE memories SET importance = ?1 WHERE id = ?2",
+            params![importance * factor, id],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(idle.len() as u64)
+}
+
 fn memory_date_from_filename(filename: &str) -> Option<NaiveDate> {
     let stem = filename.strip_suffix(".md")?;
     let date_part = stem.split('_').next().unwrap_or(stem);
@@ -386,7 +504,7 @@ fn split_name(filename: &str) -> (&str, &str) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::memory::{Memory, MemoryCategory, SqliteMemory};
+    use crate::memory::{Memory, MemoryCategory, MemoryMetadata, SqliteMemory};
     use tempfile::TempDir;
 
     fn default_cfg() -> MemoryConfig {
@@ -544,4 +662,51 @@ mod tests {
             "core memory should remain"
         );
     }
+
+    #[tokio::test]
+    async fn expires_and_decays_sqlite_memory_rows() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+
+        let mem = SqliteMemory::new(workspace).unwrap();
+        let expired = MemoryMetadata {
+            expires_at: Some((Utc::now() - Duration::hours(1)).to_rfc3339()),
+            ..Default::default()
+        };
+        let important = MemoryMetadata {
+            importance: Some(0.8),
+            ..Default::default()
+        };
+        mem.store_with_metadata("temp", "one-off", MemoryCategory::Core, None, &expired)
+            .await
+            .unwrap();
+        for key in ["idle", "fresh"] {
+            mem.store_with_metadata(key, key, MemoryCategory::Core, None, &important)
+                .await
+                .unwrap();
+        }
+        drop(mem);
+
+        let conn = Connection::open(workspace.join("memory").join("brain.db")).unwrap();
+        conn.execute(
+            "UPDATE memories SET updated_at = ?1 WHERE key = 'idle'",
+            params![(Local::now() - Duration::days(60)).to_rfc3339()],
+        )
+        .unwrap();
+        drop(conn);
+
+        let mut cfg = default_cfg();
+        cfg.archive_after_days = 0;
+        cfg.purge_after_days = 0;
+        cfg.importance_half_life_days = 30;
+
+        run_if_due(&cfg, workspace).unwrap();
+
+        let mem2 = SqliteMemory::new(workspace).unwrap();
+        assert!(mem2.get("temp").await.unwrap().is_none());
+        let idle = mem2.get("idle").await.unwrap().unwrap();
+        assert!(idle.metadata.importance.unwrap() < 0.8);
+        let fresh = mem2.get("fresh").await.unwrap().unwrap();
+        assert_eq!(fresh.metadata.importance, Some(0.8));
+    }
 }
@@ -201,6 +201,17 @@ impl KnowledgeGraph {
         })
     }
 
+    /// Open the graph configured in `[knowledge]`, expanding `~` in `db_path`.
+    pub fn from_config(config: &crate::config::KnowledgeConfig) -> anyhow::Result<Self> {
+        let db_path = config.db_path.replace(
+            '~',
+            &directories::UserDirs::new()
+                .map(|u| u.home_dir().to_string_lossy().to_string())
+                .unwrap_or_else(|| ".".to_string()),
+        );
+        Self::new(Path::new(&db_path), config.max_nodes)
+    }
+
     /// Add a node to the graph. Returns the generated node id.
     pub fn add_node(
         &self,
@@ -470,6 +481,93 @@ impl KnowledgeGraph {
         Ok(results)
     }
 
+    /// Every node, oldest first.
+    pub fn all_nodes(&self) -> anyhow::Result<Vec<KnowledgeNode>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT id, node_type, title, content, tags, created_at, updated_at, source_project
+             FROM nodes ORDER BY created_at, rowid",
+        )?;
+
+        let mut results = Vec::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            results.push(row_to_node(row)?);
+        }
+        Ok(results)
+    }
+
+    /// Every edge.
+    pub fn all_edges(&self) -> anyhow::Result<Vec<KnowledgeEdge>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare("SELECT from_id, to_id, relation FROM edges ORDER BY rowid")?;
+
+        let mut results = Vec::new();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let relation: String = row.get(2)?;
+            results.push(KnowledgeEdge {
+                from_id: row.get(0)?,
+                to_id: row.get(1)?,
+                relation: Relation::parse(&relation)?,
+            });
+        }
+        Ok(results)
+    }
+
+    /// Insert or replace a node keeping its id and timestamps, e.g. when
+    /// restoring an exported graph.
+    pub fn import_node(&self, node: &KnowledgeNode) -> anyhow::Result<()> {
+        let conn = self.conn.lock();
+
+        let exists: usize = conn.query_row(
+            "SELECT COUNT(*) FROM nodes WHERE id = ?1",
+            params![node.id],
+            |r| r.get(0),
+        )?;
+        if exists == 0 {
+            let count: usize = conn.query_row("SELECT COUNT(*) FROM nodes", [], |r| r.get(0))?;
+            if count >= self.max_nodes {
+                anyhow::bail!(
+                    "knowledge graph node limit reached ({}/{})",
+                    count,
+                    self.max_nodes
+                );
+            }
+        }
+        if let Some(tag) = node.tags.iter().find(|tag| tag.contains(',')) {
+            anyhow::bail!(
+                "tag '{}' contains a comma, which is used as the tag separator",
+                tag
+            );
+        }
+
+        conn.execute(
+            "INSERT INTO nodes (id, node_type, title, content, tags, created_at, updated_at, source_project)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+             ON CONFLICT(id) DO UPDATE SET
+                node_type = excluded.node_type,
+                title = excluded.title,
+                content = excluded.content,
+                tags = excluded.tags,
+                created_at = excluded.created_at,
+                updated_at = excluded.updated_at,
+                source_project = excluded.source_project",
+            params![
+                node.id,
+                node.node_type.as_str(),
+                node.title,
+                node.content,
+                node.tags.join(","),
+                node.created_at.to_rfc3339(),
+                node.updated_at.to_rfc3339(),
+                node.source_project,
+            ],
+        )?;
+
+        Ok(())
+    }
+
     /// Return summary statistics for the graph.
     pub fn stats(&self) -> anyhow::Result<GraphStats> {
         let conn = self.conn.lock();
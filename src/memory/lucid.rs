@@ -1,5 +1,7 @@
 use super::sqlite::SqliteMemory;
-use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::traits::{
+    Memory, MemoryCategory, MemoryEntry, MemoryExportPage, MemoryMetadata, MemoryRecord,
+};
 use async_trait::async_trait;
 use chrono::Local;
 use parking_lot::Mutex;
@@ -226,6 +228,7 @@ impl LucidMemory {
                 timestamp: now.clone(),
                 session_id: None,
                 score: Some((1.0 - rank as f64 * 0.05).max(0.1)),
+                metadata: MemoryMetadata::default(),
             });
         }
 
@@ -320,6 +323,21 @@ impl Memory for LucidMemory {
         Ok(())
     }
 
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        self.local
+            .store_with_metadata(key, content, category.clone(), session_id, metadata)
+            .await?;
+        self.sync_to_lucid_async(key, content, &category).await;
+        Ok(())
+    }
+
     async fn recall(
         &self,
         query: &str,
@@ -382,6 +400,25 @@ impl Memory for LucidMemory {
     async fn health_check(&self) -> bool {
         self.local.health_check().await
     }
+
+    async fn export_page(
+        &self,
+        include_embeddings: bool,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<MemoryExportPage> {
+        self.local
+            .export_page(include_embeddings, cursor, limit)
+            .await
+    }
+
+    async fn import_entry(&self, record: &MemoryRecord) -> anyhow::Result<()> {
+        self.local.import_entry(record).await?;
+        let entry = &record.entry;
+        self.sync_to_lucid_async(&entry.key, &entry.content, &entry.category)
+            .await;
+        Ok(())
+    }
 }
 
 #[cfg(all(test, unix))]
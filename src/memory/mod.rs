@@ -1,8 +1,10 @@
 pub mod backend;
+pub mod bundle;
 pub mod chunker;
 pub mod cli;
 pub mod consolidation;
 pub mod embeddings;
+pub mod hnsw;
 pub mod hygiene;
 pub mod knowledge_graph;
 pub mod lucid;
@@ -32,7 +34,9 @@ pub use response_cache::ResponseCache;
 pub use sqlite::SqliteMemory;
 pub use traits::Memory;
 #[allow(unused_imports)]
-pub use traits::{MemoryCategory, MemoryEntry};
+pub use traits::{
+    MemoryCategory, MemoryEntry, MemoryExportPage, MemoryMetadata, MemoryRecord, DEFAULT_IMPORTANCE,
+};
 
 use crate::config::{EmbeddingRouteConfig, MemoryConfig, StorageProviderConfig};
 use anyhow::Context;
@@ -206,6 +210,61 @@ fn resolve_embedding_config(
     }
 }
 
+/// Build the embedding provider configured in `[memory]`, following
+/// `hint:` routes. Memory backends, `memory reembed` and the datasheet RAG
+/// share it so they all embed into the same vector space.
+pub fn create_embedder(
+    config: &MemoryConfig,
+    embedding_routes: &[EmbeddingRouteConfig],
+    api_key: Option<&str>,
+) -> Arc<dyn embeddings::EmbeddingProvider> {
+    build_embedder(&resolve_embedding_config(config, embedding_routes, api_key))
+}
+
+fn build_embedder(resolved: &ResolvedEmbeddingConfig) -> Arc<dyn embeddings::EmbeddingProvider> {
+    Arc::from(embeddings::create_embedding_provider(
+        &resolved.provider,
+        resolved.api_key.as_deref(),
+        &resolved.model,
+        resolved.dimensions,
+    ))
+}
+
+/// Open the sqlite memory with the configured embedder and vector index,
+/// skipping the hygiene and hydration passes of [`create_memory`].
+pub fn create_sqlite_memory(
+    config: &MemoryConfig,
+    embedding_routes: &[EmbeddingRouteConfig],
+    workspace_dir: &Path,
+    api_key: Option<&str>,
+) -> anyhow::Result<SqliteMemory> {
+    build_sqlite_memory(
+        config,
+        workspace_dir,
+        &resolve_embedding_config(config, embedding_routes, api_key),
+    )
+}
+
+fn build_sqlite_memory(
+    config: &MemoryConfig,
+    workspace_dir: &Path,
+    resolved_embedding: &ResolvedEmbeddingConfig,
+) -> anyhow::Result<SqliteMemory> {
+    #[allow(clippy::cast_possible_truncation)]
+    let mem = SqliteMemory::with_embedder(
+        workspace_dir,
+        build_embedder(resolved_embedding),
+        config.vector_weight as f32,
+        config.keyword_weight as f32,
+        config.embedding_cache_size,
+        config.sqlite_open_timeout_secs,
+    )?;
+    if config.vector_index.trim() == "hnsw" {
+        return mem.with_hnsw_index(hnsw::HnswParams::from(&config.hnsw));
+    }
+    Ok(mem)
+}
+
 /// Factory: create the right memory backend from config
 pub fn create_memory(
     config: &MemoryConfig,
@@ -277,31 +336,6 @@ pub fn create_memory_with_storage_and_routes(
         }
     }
 
-    fn build_sqlite_memory(
-        config: &MemoryConfig,
-        workspace_dir: &Path,
-        resolved_embedding: &ResolvedEmbeddingConfig,
-    ) -> anyhow::Result<SqliteMemory> {
-        let embedder: Arc<dyn embeddings::EmbeddingProvider> =
-            Arc::from(embeddings::create_embedding_provider(
-                &resolved_embedding.provider,
-                resolved_embedding.api_key.as_deref(),
-                &resolved_embedding.model,
-                resolved_embedding.dimensions,
-            ));
-
-        #[allow(clippy::cast_possible_truncation)]
-        let mem = SqliteMemory::with_embedder(
-            workspace_dir,
-            embedder,
-            config.vector_weight as f32,
-            config.keyword_weight as f32,
-            config.embedding_cache_size,
-            config.sqlite_open_timeout_secs,
-        )?;
-        Ok(mem)
-    }
-
     #[cfg(feature = "memory-postgres")]
     fn build_postgres_memory(
         storage_provider: Option<&StorageProviderConfig>,
@@ -356,13 +390,7 @@ pub fn create_memory_with_storage_and_routes(
             .clone()
             .or_else(|| std::env::var("QDRANT_API_KEY").ok())
             .filter(|s| !s.trim().is_empty());
-        let embedder: Arc<dyn embeddings::EmbeddingProvider> =
-            Arc::from(embeddings::create_embedding_provider(
-                &resolved_embedding.provider,
-                resolved_embedding.api_key.as_deref(),
-                &resolved_embedding.model,
-                resolved_embedding.dimensions,
-            ));
+        let embedder = build_embedder(&resolved_embedding);
         tracing::info!(
             "📦 Qdrant memory backend configured (url: {}, collection: {})",
             url,
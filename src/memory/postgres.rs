@@ -1,4 +1,6 @@
-use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::traits::{
+    Memory, MemoryCategory, MemoryEntry, MemoryExportPage, MemoryMetadata, MemoryRecord,
+};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -11,6 +13,18 @@ use uuid::Uuid;
 /// Maximum allowed connect timeout (seconds) to avoid unreasonable waits.
 const POSTGRES_CONNECT_TIMEOUT_CAP_SECS: u64 = 300;
 
+/// Columns read by [`PostgresMemory::row_to_entry`], in order.
+const ENTRY_COLUMNS: &str = "id, key, content, category, created_at, session_id, tags, \
+     source_channel, source_sender, importance, expires_at, access_count, last_accessed_at";
+
+/// Metadata columns replaced when an entry is stored with metadata.
+const METADATA_UPSERT: &str = ",
+                    tags = EXCLUDED.tags,
+                    source_channel = EXCLUDED.source_channel,
+                    source_sender = EXCLUDED.source_sender,
+                    importance = EXCLUDED.importance,
+                    expires_at = EXCLUDED.expires_at";
+
 /// PostgreSQL-backed persistent memory.
 ///
 /// This backend focuses on reliable CRUD and keyword recall using SQL, without
@@ -99,6 +113,17 @@ impl PostgresMemory {
             CREATE INDEX IF NOT EXISTS idx_memories_category ON {qualified_table}(category);
             CREATE INDEX IF NOT EXISTS idx_memories_session_id ON {qualified_table}(session_id);
             CREATE INDEX IF NOT EXISTS idx_memories_updated_at ON {qualified_table}(updated_at DESC);
+
+            ALTER TABLE {qualified_table}
+                ADD COLUMN IF NOT EXISTS tags TEXT[] NOT NULL DEFAULT '{{}}',
+                ADD COLUMN IF NOT EXISTS source_channel TEXT,
+                ADD COLUMN IF NOT EXISTS source_sender TEXT,
+                ADD COLUMN IF NOT EXISTS importance DOUBLE PRECISION,
+                ADD COLUMN IF NOT EXISTS expires_at TIMESTAMPTZ,
+                ADD COLUMN IF NOT EXISTS access_count BIGINT NOT NULL DEFAULT 0,
+                ADD COLUMN IF NOT EXISTS last_accessed_at TIMESTAMPTZ;
+
+            CREATE INDEX IF NOT EXISTS idx_memories_expires_at ON {qualified_table}(expires_at);
             "
         ))?;
 
@@ -123,8 +148,13 @@ impl PostgresMemory {
         }
     }
 
+    /// Map a row whose leading columns are [`ENTRY_COLUMNS`], optionally
+    /// followed by a score.
     fn row_to_entry(row: &Row) -> Result<MemoryEntry> {
         let timestamp: DateTime<Utc> = row.get(4);
+        let expires_at: Option<DateTime<Utc>> = row.get(10);
+        let access_count: i64 = row.get(11);
+        let last_accessed_at: Option<DateTime<Utc>> = row.get(12);
 
         Ok(MemoryEntry {
             id: row.get(0),
@@ -133,9 +163,89 @@ impl PostgresMemory {
             category: Self::parse_category(&row.get::<_, String>(3)),
             timestamp: timestamp.to_rfc3339(),
             session_id: row.get(5),
-            score: row.try_get(6).ok(),
+            score: row.try_get(13).ok(),
+            metadata: MemoryMetadata {
+                tags: row.get(6),
+                source_channel: row.get(7),
+                source_sender: row.get(8),
+                importance: row.get(9),
+                expires_at: expires_at.map(|ts| ts.to_rfc3339()),
+                access_count: u64::try_from(access_count).unwrap_or(0),
+                last_accessed_at: last_accessed_at.map(|ts| ts.to_rfc3339()),
+            },
         })
     }
+
+    fn parse_timestamp(value: Option<&str>) -> Option<DateTime<Utc>> {
+        value
+            .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+            .map(|ts| ts.with_timezone(&Utc))
+    }
+
+    /// Upsert an entry. Existing metadata is replaced only when `metadata`
+    /// is given; access statistics are always kept.
+    async fn upsert(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: Option<MemoryMetadata>,
+    ) -> Result<()> {
+        let client = self.client.clone();
+        let qualified_table = self.qualified_table.clone();
+        let key = key.to_string();
+        let content = content.to_string();
+        let category = Self::category_to_str(&category);
+        let sid = session_id.map(str::to_string);
+
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let now = Utc::now();
+            let mut client = client.lock();
+            let metadata_upsert = if metadata.is_some() {
+                METADATA_UPSERT
+            } else {
+                ""
+            };
+            let metadata = metadata.unwrap_or_default();
+            let expires_at = Self::parse_timestamp(metadata.expires_at.as_deref());
+            let stmt = format!(
+                "
+                INSERT INTO {qualified_table}
+                    (id, key, content, category, created_at, updated_at, session_id,
+                     tags, source_channel, source_sender, importance, expires_at)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                ON CONFLICT (key) DO UPDATE SET
+                    content = EXCLUDED.content,
+                    category = EXCLUDED.category,
+                    updated_at = EXCLUDED.updated_at,
+                    session_id = EXCLUDED.session_id{metadata_upsert}
+                "
+            );
+
+            let id = Uuid::new_v4().to_string();
+            client.execute(
+                &stmt,
+                &[
+                    &id,
+                    &key,
+                    &content,
+                    &category,
+                    &now,
+                    &now,
+                    &sid,
+                    &metadata.tags,
+                    &metadata.source_channel,
+                    &metadata.source_sender,
+                    &metadata.importance,
+                    &expires_at,
+                ],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
 }
 
 fn validate_identifier(value: &str, field_name: &str) -> Result<()> {
@@ -178,35 +288,20 @@ impl Memory for PostgresMemory {
         category: MemoryCategory,
         session_id: Option<&str>,
     ) -> Result<()> {
-        let client = self.client.clone();
-        let qualified_table = self.qualified_table.clone();
-        let key = key.to_string();
-        let content = content.to_string();
-        let category = Self::category_to_str(&category);
-        let sid = session_id.map(str::to_string);
-
-        tokio::task::spawn_blocking(move || -> Result<()> {
-            let now = Utc::now();
-            let mut client = client.lock();
-            let stmt = format!(
-                "
-                INSERT INTO {qualified_table}
-                    (id, key, content, category, created_at, updated_at, session_id)
-                VALUES
-                    ($1, $2, $3, $4, $5, $6, $7)
-                ON CONFLICT (key) DO UPDATE SET
-                    content = EXCLUDED.content,
-                    category = EXCLUDED.category,
-                    updated_at = EXCLUDED.updated_at,
-                    session_id = EXCLUDED.session_id
-                "
-            );
+        self.upsert(key, content, category, session_id, None).await
+    }
 
-            let id = Uuid::new_v4().to_string();
-            client.execute(&stmt, &[&id, &key, &content, &category, &now, &now, &sid])?;
-            Ok(())
-        })
-        .await?
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> Result<()> {
+        let metadata = metadata.normalized()?;
+        self.upsert(key, content, category, session_id, Some(metadata))
+            .await
     }
 
     async fn recall(
@@ -224,7 +319,7 @@ impl Memory for PostgresMemory {
             let mut client = client.lock();
             let stmt = format!(
                 "
-                SELECT id, key, content, category, created_at, session_id,
+                SELECT {ENTRY_COLUMNS},
                        (
                          CASE WHEN key ILIKE '%' || $1 || '%' THEN 2.0 ELSE 0.0 END +
                          CASE WHEN content ILIKE '%' || $1 || '%' THEN 1.0 ELSE 0.0 END
@@ -232,6 +327,7 @@ impl Memory for PostgresMemory {
                 FROM {qualified_table}
                 WHERE ($2::TEXT IS NULL OR session_id = $2)
                   AND ($1 = '' OR key ILIKE '%' || $1 || '%' OR content ILIKE '%' || $1 || '%')
+                  AND (expires_at IS NULL OR expires_at > NOW())
                 ORDER BY score DESC, updated_at DESC
                 LIMIT $3
                 "
@@ -241,9 +337,28 @@ impl Memory for PostgresMemory {
             let limit_i64 = limit as i64;
 
             let rows = client.query(&stmt, &[&query, &sid, &limit_i64])?;
-            rows.iter()
+            let mut entries = rows
+                .iter()
                 .map(Self::row_to_entry)
-                .collect::<Result<Vec<MemoryEntry>>>()
+                .collect::<Result<Vec<MemoryEntry>>>()?;
+
+            if !entries.is_empty() {
+                let now = Utc::now();
+                let ids: Vec<String> = entries.iter().map(|entry| entry.id.clone()).collect();
+                let stmt = format!(
+                    "
+                    UPDATE {qualified_table}
+                    SET access_count = access_count + 1, last_accessed_at = $1
+                    WHERE id = ANY($2)
+                    "
+                );
+                client.execute(&stmt, &[&now, &ids])?;
+                for entry in &mut entries {
+                    entry.metadata.access_count += 1;
+                    entry.metadata.last_accessed_at = Some(now.to_rfc3339());
+                }
+            }
+            Ok(entries)
         })
         .await?
     }
@@ -257,7 +372,7 @@ impl Memory for PostgresMemory {
             let mut client = client.lock();
             let stmt = format!(
                 "
-                SELECT id, key, content, category, created_at, session_id
+                SELECT {ENTRY_COLUMNS}
                 FROM {qualified_table}
                 WHERE key = $1
                 LIMIT 1
@@ -284,10 +399,11 @@ impl Memory for PostgresMemory {
             let mut client = client.lock();
             let stmt = format!(
                 "
-                SELECT id, key, content, category, created_at, session_id
+                SELECT {ENTRY_COLUMNS}
                 FROM {qualified_table}
                 WHERE ($1::TEXT IS NULL OR category = $1)
                   AND ($2::TEXT IS NULL OR session_id = $2)
+                  AND (expires_at IS NULL OR expires_at > NOW())
                 ORDER BY updated_at DESC
                 "
             );
@@ -337,6 +453,117 @@ impl Memory for PostgresMemory {
             .await
             .unwrap_or(false)
     }
+
+    /// Pages by `key`; the cursor is the last entry's key.
+    async fn export_page(
+        &self,
+        include_embeddings: bool,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Result<MemoryExportPage> {
+        let _ = include_embeddings;
+        let client = self.client.clone();
+        let qualified_table = self.qualified_table.clone();
+        let after = cursor.map(str::to_string);
+        let limit = i64::try_from(limit.max(1)).unwrap_or(i64::MAX);
+
+        tokio::task::spawn_blocking(move || -> Result<MemoryExportPage> {
+            let mut client = client.lock();
+            let stmt = format!(
+                "
+                SELECT {ENTRY_COLUMNS}
+                FROM {qualified_table}
+                WHERE ($1::TEXT IS NULL OR key > $1)
+                  AND (expires_at IS NULL OR expires_at > NOW())
+                ORDER BY key
+                LIMIT $2
+                "
+            );
+            let rows = client.query(&stmt, &[&after.as_deref(), &limit])?;
+            let entries = rows
+                .iter()
+                .map(Self::row_to_entry)
+                .collect::<Result<Vec<MemoryEntry>>>()?;
+            let next_cursor = if i64::try_from(entries.len()).unwrap_or(i64::MAX) == limit {
+                entries.last().map(|entry| entry.key.clone())
+            } else {
+                None
+            };
+            Ok(MemoryExportPage {
+                records: entries
+                    .into_iter()
+                    .map(|entry| MemoryRecord {
+                        entry,
+                        embedding: None,
+                    })
+                    .collect(),
+                next_cursor,
+            })
+        })
+        .await?
+    }
+
+    async fn import_entry(&self, record: &MemoryRecord) -> Result<()> {
+        let client = self.client.clone();
+        let qualified_table = self.qualified_table.clone();
+        let entry = record.entry.clone();
+        let metadata = entry.metadata.normalized()?;
+        let category = Self::category_to_str(&entry.category);
+        let timestamp = Self::parse_timestamp(Some(&entry.timestamp)).unwrap_or_else(Utc::now);
+        let expires_at = Self::parse_timestamp(metadata.expires_at.as_deref());
+        let last_accessed_at = Self::parse_timestamp(metadata.last_accessed_at.as_deref());
+        let access_count = i64::try_from(metadata.access_count).unwrap_or(i64::MAX);
+
+        tokio::task::spawn_blocking(move || -> Result<()> {
+            let mut client = client.lock();
+            let stmt = format!(
+                "
+                INSERT INTO {qualified_table}
+                    (id, key, content, category, created_at, updated_at, session_id,
+                     tags, source_channel, source_sender, importance, expires_at,
+                     access_count, last_accessed_at)
+                VALUES
+                    ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                ON CONFLICT (key) DO UPDATE SET
+                    content = EXCLUDED.content,
+                    category = EXCLUDED.category,
+                    created_at = EXCLUDED.created_at,
+                    updated_at = EXCLUDED.updated_at,
+                    session_id = EXCLUDED.session_id,
+                    tags = EXCLUDED.tags,
+                    source_channel = EXCLUDED.source_channel,
+                    source_sender = EXCLUDED.source_sender,
+                    importance = EXCLUDED.importance,
+                    expires_at = EXCLUDED.expires_at,
+                    access_count = EXCLUDED.access_count,
+                    last_accessed_at = EXCLUDED.last_accessed_at
+                "
+            );
+
+            let id = Uuid::new_v4().to_string();
+            client.execute(
+                &stmt,
+                &[
+                    &id,
+                    &entry.key,
+                    &entry.content,
+                    &category,
+                    &timestamp,
+                    &timestamp,
+                    &entry.session_id,
+                    &metadata.tags,
+                    &metadata.source_channel,
+                    &metadata.source_sender,
+                    &metadata.importance,
+                    &expires_at,
+                    &access_count,
+                    &last_accessed_at,
+                ],
+            )?;
+            Ok(())
+        })
+        .await?
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,7 @@
 use super::embeddings::EmbeddingProvider;
-use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::traits::{
+    Memory, MemoryCategory, MemoryEntry, MemoryExportPage, MemoryMetadata, MemoryRecord,
+};
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use chrono::Utc;
@@ -160,6 +162,111 @@ impl QdrantMemory {
         Ok(())
     }
 
+    /// Embed and upsert one memory, replacing any point with the same key.
+    async fn upsert_point(
+        &self,
+        key: &str,
+        content: &str,
+        category: &MemoryCategory,
+        session_id: Option<&str>,
+        timestamp: String,
+    ) -> Result<()> {
+        self.ensure_initialized().await?;
+
+        // Generate embedding for the content
+        let combined_text = format!("{}\n{}", key, content);
+        let embedding = self.embedder.embed_one(&combined_text).await?;
+
+        if embedding.is_empty() {
+            anyhow::bail!("Qdrant requires non-zero dimensional embeddings");
+        }
+
+        let id = Uuid::new_v4().to_string();
+
+        let payload = MemoryPayload {
+            key: key.to_string(),
+            content: content.to_string(),
+            category: Self::category_to_str(category),
+            timestamp,
+            session_id: session_id.map(str::to_string),
+        };
+
+        // Delete any existing point with the same key first
+        let _ = self.forget(key).await;
+
+        // Upsert point
+        let upsert_body = serde_json::json!({
+            "points": [{
+                "id": id,
+                "vector": embedding,
+                "payload": payload
+            }]
+        });
+
+        let resp = self
+            .request(
+                reqwest::Method::PUT,
+                &format!("/collections/{}/points", self.collection),
+            )
+            .query(&[("wait", "true")])
+            .json(&upsert_body)
+            .send()
+            .await
+            .context("failed to upsert point to Qdrant")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Qdrant upsert failed ({status}): {text}");
+        }
+
+        Ok(())
+    }
+
+    /// Scroll every point in the collection, following `next_page_offset`.
+    /// One scroll request starting at `offset`; returns the points and the
+    /// offset of the next page, if any.
+    async fn scroll_page(
+        &self,
+        with_vector: bool,
+        offset: Option<serde_json::Value>,
+        limit: usize,
+    ) -> Result<(Vec<QdrantPoint>, Option<serde_json::Value>)> {
+        self.ensure_initialized().await?;
+
+        let mut scroll_body = serde_json::json!({
+            "limit": limit,
+            "with_payload": true,
+            "with_vector": with_vector
+        });
+        if let Some(offset) = offset {
+            scroll_body["offset"] = offset;
+        }
+
+        let resp = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/collections/{}/points/scroll", self.collection),
+            )
+            .json(&scroll_body)
+            .send()
+            .await
+            .context("failed to scroll Qdrant")?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            anyhow::bail!("Qdrant scroll failed ({status}): {text}");
+        }
+
+        let result: QdrantScrollResult = resp.json().await?;
+        let next = result
+            .result
+            .next_page_offset
+            .filter(|next| !next.is_null());
+        Ok((result.result.points, next))
+    }
+
     fn category_to_str(category: &MemoryCategory) -> String {
         match category {
             MemoryCategory::Core => "core".to_string(),
@@ -212,12 +319,38 @@ struct QdrantScrollResult {
 #[derive(Debug, Deserialize)]
 struct QdrantScrollPoints {
     points: Vec<QdrantPoint>,
+    #[serde(default)]
+    next_page_offset: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
 struct QdrantPoint {
     id: serde_json::Value,
     payload: Option<MemoryPayload>,
+    #[serde(default)]
+    vector: Option<Vec<f32>>,
+}
+
+impl QdrantPoint {
+    fn into_entry(self) -> Option<MemoryEntry> {
+        let payload = self.payload?;
+        let id = match &self.id {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Number(n) => n.to_string(),
+            _ => return None,
+        };
+
+        Some(MemoryEntry {
+            id,
+            key: payload.key,
+            content: payload.content,
+            category: QdrantMemory::parse_category(&payload.category),
+            timestamp: payload.timestamp,
+            session_id: payload.session_id,
+            score: None,
+            metadata: MemoryMetadata::default(),
+        })
+    }
 }
 
 #[async_trait]
@@ -233,57 +366,9 @@ impl Memory for QdrantMemory {
         category: MemoryCategory,
         session_id: Option<&str>,
     ) -> Result<()> {
-        self.ensure_initialized().await?;
-
-        // Generate embedding for the content
-        let combined_text = format!("{}\n{}", key, content);
-        let embedding = self.embedder.embed_one(&combined_text).await?;
-
-        if embedding.is_empty() {
-            anyhow::bail!("Qdrant requires non-zero dimensional embeddings");
-        }
-
-        let id = Uuid::new_v4().to_string();
         let timestamp = Utc::now().to_rfc3339();
-
-        let payload = MemoryPayload {
-            key: key.to_string(),
-            content: content.to_string(),
-            category: Self::category_to_str(&category),
-            timestamp,
-            session_id: session_id.map(str::to_string),
-        };
-
-        // Delete any existing point with the same key first
-        let _ = self.forget(key).await;
-
-        // Upsert point
-        let upsert_body = serde_json::json!({
-            "points": [{
-                "id": id,
-                "vector": embedding,
-                "payload": payload
-            }]
-        });
-
-        let resp = self
-            .request(
-                reqwest::Method::PUT,
-                &format!("/collections/{}/points", self.collection),
-            )
-            .query(&[("wait", "true")])
-            .json(&upsert_body)
-            .send()
+        self.upsert_point(key, content, &category, session_id, timestamp)
             .await
-            .context("failed to upsert point to Qdrant")?;
-
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let text = resp.text().await.unwrap_or_default();
-            anyhow::bail!("Qdrant upsert failed ({status}): {text}");
-        }
-
-        Ok(())
     }
 
     async fn recall(
@@ -363,6 +448,7 @@ impl Memory for QdrantMemory {
                     timestamp: payload.timestamp,
                     session_id: payload.session_id,
                     score: Some(point.score),
+                    metadata: MemoryMetadata::default(),
                 })
             })
             .collect();
@@ -419,6 +505,7 @@ impl Memory for QdrantMemory {
                 timestamp: payload.timestamp,
                 session_id: payload.session_id,
                 score: None,
+                metadata: MemoryMetadata::default(),
             })
         });
 
@@ -480,24 +567,7 @@ impl Memory for QdrantMemory {
             .result
             .points
             .into_iter()
-            .filter_map(|point| {
-                let payload = point.payload?;
-                let id = match &point.id {
-                    serde_json::Value::String(s) => s.clone(),
-                    serde_json::Value::Number(n) => n.to_string(),
-                    _ => return None,
-                };
-
-                Some(MemoryEntry {
-                    id,
-                    key: payload.key,
-                    content: payload.content,
-                    category: Self::parse_category(&payload.category),
-                    timestamp: payload.timestamp,
-                    session_id: payload.session_id,
-                    score: None,
-                })
-            })
+            .filter_map(QdrantPoint::into_entry)
             .collect();
 
         Ok(entries)
@@ -573,6 +643,49 @@ impl Memory for QdrantMemory {
 
         matches!(resp, Ok(r) if r.status().is_success())
     }
+
+    /// Pages with the scroll API; the cursor is Qdrant's next page offset
+    /// as JSON.
+    async fn export_page(
+        &self,
+        include_embeddings: bool,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Result<MemoryExportPage> {
+        let offset = cursor
+            .map(serde_json::from_str)
+            .transpose()
+            .context("invalid memory export cursor")?;
+        let (points, next) = self
+            .scroll_page(include_embeddings, offset, limit.max(1))
+            .await?;
+        Ok(MemoryExportPage {
+            records: points
+                .into_iter()
+                .filter_map(|mut point| {
+                    let embedding = point.vector.take();
+                    point
+                        .into_entry()
+                        .map(|entry| MemoryRecord { entry, embedding })
+                })
+                .collect(),
+            next_cursor: next.map(|next| next.to_string()),
+        })
+    }
+
+    /// Keeps the entry's timestamp; the vector is always recomputed because
+    /// this backend embeds `key` and `content` together.
+    async fn import_entry(&self, record: &MemoryRecord) -> Result<()> {
+        let entry = &record.entry;
+        self.upsert_point(
+            &entry.key,
+            &entry.content,
+            &entry.category,
+            entry.session_id.as_deref(),
+            entry.timestamp.clone(),
+        )
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -1,15 +1,20 @@
 use super::embeddings::EmbeddingProvider;
-use super::traits::{Memory, MemoryCategory, MemoryEntry};
-use super::vector;
+use super::hnsw::{HnswIndex, HnswParams};
+use super::traits::{
+    Memory, MemoryCategory, MemoryEntry, MemoryExportPage, MemoryMetadata, MemoryRecord,
+};
+use super::vector::{self, VectorIndex};
 use anyhow::Context;
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::{Local, Utc};
 use parking_lot::Mutex;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
 use std::fmt::Write as _;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::mpsc;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock, Weak};
 use std::thread;
 use std::time::Duration;
 use uuid::Uuid;
@@ -17,10 +22,87 @@ use uuid::Uuid;
 /// Maximum allowed open timeout (seconds) to avoid unreasonable waits.
 const SQLITE_OPEN_TIMEOUT_CAP_SECS: u64 = 300;
 
+/// Persist the vector index after this many unsaved store/forget updates.
+/// Updates lost to a crash are reconciled from the database on next open.
+const VECTOR_INDEX_SAVE_INTERVAL: usize = 64;
+
+/// Texts sent to the embedding provider per request during [`SqliteMemory::reembed`].
+const REEMBED_BATCH_SIZE: usize = 32;
+
+/// Columns read by [`SqliteMemory::row_to_entry`], in order.
+const ENTRY_COLUMNS: &str = "id, key, content, category, created_at, session_id, tags, \
+     source_channel, source_sender, importance, expires_at, access_count, last_accessed_at";
+
+/// Metadata columns replaced when an entry is stored with metadata.
+const METADATA_UPSERT: &str = ",
+                    tags = excluded.tags,
+                    source_channel = excluded.source_channel,
+                    source_sender = excluded.source_sender,
+                    importance = excluded.importance,
+                    expires_at = excluded.expires_at";
+
+/// ANN index attached to a [`SqliteMemory`], saved every few writes and on drop.
+struct AttachedIndex {
+    index: Mutex<Box<dyn VectorIndex>>,
+    path: PathBuf,
+    unsaved_writes: AtomicUsize,
+}
+
+impl AttachedIndex {
+    fn update(&self, apply: impl FnOnce(&mut dyn VectorIndex)) {
+        let mut index = self.index.lock();
+        apply(index.as_mut());
+        if self.unsaved_writes.fetch_add(1, Ordering::Relaxed) + 1 >= VECTOR_INDEX_SAVE_INTERVAL {
+            self.save(index.as_ref());
+        }
+    }
+
+    fn save(&self, index: &dyn VectorIndex) {
+        match index.save(&self.path) {
+            Ok(()) => self.unsaved_writes.store(0, Ordering::Relaxed),
+            Err(e) => tracing::warn!("Failed to save vector index {}: {e}", self.path.display()),
+        }
+    }
+}
+
+/// Indexes attached by live [`SqliteMemory`] handles, keyed by index path,
+/// so rows deleted outside the handle (e.g. hygiene expiry) leave no stale
+/// vectors behind.
+static ATTACHED_INDEXES: LazyLock<Mutex<HashMap<PathBuf, Vec<Weak<AttachedIndex>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Remove `ids` from every index attached in this process to the database at
+/// `db_path`. Returns the number of vectors removed.
+pub(crate) fn remove_from_attached_indexes(db_path: &Path, ids: &[String]) -> usize {
+    let attached: Vec<Arc<AttachedIndex>> = ATTACHED_INDEXES
+        .lock()
+        .get(&db_path.with_extension("hnsw"))
+        .map(|indexes| indexes.iter().filter_map(Weak::upgrade).collect())
+        .unwrap_or_default();
+
+    let mut removed = 0;
+    for index in attached {
+        index.update(|index| {
+            removed += ids.iter().filter(|id| index.remove(id)).count();
+        });
+    }
+    removed
+}
+
+impl Drop for AttachedIndex {
+    fn drop(&mut self) {
+        if self.unsaved_writes.load(Ordering::Relaxed) > 0 {
+            let index = self.index.lock();
+            self.save(index.as_ref());
+        }
+    }
+}
+
 /// SQLite-backed persistent memory — the brain
 ///
 /// Full-stack search engine:
 /// - **Vector DB**: embeddings stored as BLOB, cosine similarity search
+///   (brute-force scan, or an optional HNSW index persisted as `brain.hnsw`)
 /// - **Keyword Search**: FTS5 virtual table with BM25 scoring
 /// - **Hybrid Merge**: weighted fusion of vector + keyword results
 /// - **Embedding Cache**: LRU-evicted cache to avoid redundant API calls
@@ -32,6 +114,11 @@ pub struct SqliteMemory {
     vector_weight: f32,
     keyword_weight: f32,
     cache_max: usize,
+    /// Optional ANN index; `None` scans every embedding in `vector_search`.
+    vector_index: Option<Arc<AttachedIndex>>,
+    /// Stored embeddings came from a different provider or have the wrong
+    /// dimensions; vector recall is skipped until [`Self::reembed`] runs.
+    embeddings_stale: AtomicBool,
 }
 
 impl SqliteMemory {
@@ -82,6 +169,7 @@ impl SqliteMemory {
         )?;
 
         Self::init_schema(&conn)?;
+        let embeddings_stale = Self::check_embedder(&conn, embedder.as_ref())?;
 
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
@@ -90,9 +178,167 @@ impl SqliteMemory {
             vector_weight,
             keyword_weight,
             cache_max,
+            vector_index: None,
+            embeddings_stale: AtomicBool::new(embeddings_stale),
         })
     }
 
+    /// Compare the configured embedder with the one that produced the stored
+    /// vectors. Returns `true` when they differ (another provider fingerprint,
+    /// or vectors whose length does not match the configured dimensions).
+    /// Databases without a recorded provider adopt the configured one when
+    /// every stored vector has the right length.
+    fn check_embedder(conn: &Connection, embedder: &dyn EmbeddingProvider) -> anyhow::Result<bool> {
+        let dims = embedder.dimensions();
+        if dims == 0 {
+            // Keyword-only: stored vectors are left untouched for later use.
+            return Ok(false);
+        }
+
+        let fingerprint = embedder.fingerprint();
+        let stored = Self::stored_embedder(conn)?;
+        let expected_bytes = i64::try_from(dims * std::mem::size_of::<f32>())?;
+        let mismatched: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM memories
+             WHERE embedding IS NOT NULL AND length(embedding) != ?1",
+            params![expected_bytes],
+            |row| row.get(0),
+        )?;
+
+        if mismatched == 0 && stored.as_deref().map_or(true, |s| s == fingerprint) {
+            if stored.is_none() {
+                Self::record_embedder(conn, &fingerprint)?;
+            }
+            return Ok(false);
+        }
+
+        // Cached vectors are keyed by content only, so they belong to the
+        // previous provider too.
+        conn.execute("DELETE FROM embedding_cache", [])?;
+        tracing::warn!(
+            stored = stored.as_deref().unwrap_or("unknown"),
+            configured = %fingerprint,
+            mismatched_vectors = mismatched,
+            "Stored memory embeddings do not match the configured embedding provider; \
+             vector recall is disabled until `zeroclaw memory reembed` runs"
+        );
+        Ok(true)
+    }
+
+    fn stored_embedder(conn: &Connection) -> anyhow::Result<Option<String>> {
+        Ok(conn
+            .query_row(
+                "SELECT value FROM embedding_meta WHERE key = 'provider'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?)
+    }
+
+    fn record_embedder(conn: &Connection, fingerprint: &str) -> anyhow::Result<()> {
+        conn.execute(
+            "INSERT INTO embedding_meta (key, value) VALUES ('provider', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![fingerprint],
+        )?;
+        Ok(())
+    }
+
+    /// The provider used to embed stored and recalled text.
+    pub fn embedder(&self) -> &dyn EmbeddingProvider {
+        self.embedder.as_ref()
+    }
+
+    /// Whether stored embeddings need [`Self::reembed`] before vector recall
+    /// can use them.
+    pub fn embeddings_stale(&self) -> bool {
+        self.embeddings_stale.load(Ordering::Relaxed)
+    }
+
+    /// Path of the persisted vector index, next to `brain.db`.
+    pub fn vector_index_path(&self) -> PathBuf {
+        self.db_path.with_extension("hnsw")
+    }
+
+    /// Serve vector recall from an HNSW index instead of scanning every
+    /// embedding. Loads `brain.hnsw` when present and reconciles it with the
+    /// embeddings stored in the database; a missing or unreadable file is
+    /// rebuilt from scratch.
+    pub fn with_hnsw_index(mut self, params: HnswParams) -> anyhow::Result<Self> {
+        let embeddings = Self::load_embeddings(&self.conn.lock())?;
+        let path = self.vector_index_path();
+
+        let mut index = if path.exists() {
+            let by_id: HashMap<String, Vec<f32>> = embeddings.iter().cloned().collect();
+            HnswIndex::load(&path, params, &by_id).unwrap_or_else(|e| {
+                tracing::warn!("Rebuilding vector index {}: {e}", path.display());
+                HnswIndex::new(params)
+            })
+        } else {
+            HnswIndex::new(params)
+        };
+
+        let mut added = 0;
+        for (id, embedding) in &embeddings {
+            if !index.contains(id, vector::vector_fingerprint(embedding)) {
+                index.insert(id, embedding);
+                added += 1;
+            }
+        }
+        if added > 0 {
+            tracing::info!(
+                added,
+                total = index.len(),
+                "Vector index synced with brain.db"
+            );
+        }
+        index.save(&path)?;
+
+        self.attach_index(Box::new(index), path);
+        Ok(self)
+    }
+
+    /// Rebuild the HNSW index from every stored embedding, replacing
+    /// `brain.hnsw`. Returns the number of indexed vectors.
+    pub fn rebuild_hnsw_index(&mut self, params: HnswParams) -> anyhow::Result<usize> {
+        let embeddings = Self::load_embeddings(&self.conn.lock())?;
+        let mut index = HnswIndex::new(params);
+        for (id, embedding) in &embeddings {
+            index.insert(id, embedding);
+        }
+        let path = self.vector_index_path();
+        index.save(&path)?;
+        let count = index.len();
+        self.attach_index(Box::new(index), path);
+        Ok(count)
+    }
+
+    fn attach_index(&mut self, index: Box<dyn VectorIndex>, path: PathBuf) {
+        let attached = Arc::new(AttachedIndex {
+            index: Mutex::new(index),
+            path: path.clone(),
+            unsaved_writes: AtomicUsize::new(0),
+        });
+        let mut registry = ATTACHED_INDEXES.lock();
+        let indexes = registry.entry(path).or_default();
+        indexes.retain(|index| index.strong_count() > 0);
+        indexes.push(Arc::downgrade(&attached));
+        self.vector_index = Some(attached);
+    }
+
+    /// All stored embeddings, in insertion order.
+    fn load_embeddings(conn: &Connection) -> anyhow::Result<Vec<(String, Vec<f32>)>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, embedding FROM memories WHERE embedding IS NOT NULL ORDER BY rowid",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let id: String = row.get(0)?;
+            let blob: Vec<u8> = row.get(1)?;
+            Ok((id, vector::bytes_to_vec(&blob)))
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
     /// Open SQLite connection, optionally with a timeout (for locked/slow storage).
     fn open_connection(
         db_path: &Path,
@@ -168,7 +414,13 @@ impl SqliteMemory {
                 created_at   TEXT NOT NULL,
                 accessed_at  TEXT NOT NULL
             );
-            CREATE INDEX IF NOT EXISTS idx_cache_accessed ON embedding_cache(accessed_at);",
+            CREATE INDEX IF NOT EXISTS idx_cache_accessed ON embedding_cache(accessed_at);
+
+            -- Identity of the embedding provider that produced stored vectors
+            CREATE TABLE IF NOT EXISTS embedding_meta (
+                key   TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );",
         )?;
 
         // Migration: add session_id column if not present (safe to run repeatedly)
@@ -183,6 +435,24 @@ impl SqliteMemory {
             )?;
         }
 
+        // Migration: add entry metadata columns if not present
+        let has_metadata: bool = conn
+            .prepare("SELECT sql FROM sqlite_master WHERE type='table' AND name='memories'")?
+            .query_row([], |row| row.get::<_, String>(0))?
+            .contains("expires_at");
+        if !has_metadata {
+            conn.execute_batch(
+                "ALTER TABLE memories ADD COLUMN tags TEXT;
+                 ALTER TABLE memories ADD COLUMN source_channel TEXT;
+                 ALTER TABLE memories ADD COLUMN source_sender TEXT;
+                 ALTER TABLE memories ADD COLUMN importance REAL;
+                 ALTER TABLE memories ADD COLUMN expires_at TEXT;
+                 ALTER TABLE memories ADD COLUMN access_count INTEGER NOT NULL DEFAULT 0;
+                 ALTER TABLE memories ADD COLUMN last_accessed_at TEXT;
+                 CREATE INDEX IF NOT EXISTS idx_memories_expires ON memories(expires_at);",
+            )?;
+        }
+
         Ok(())
     }
 
@@ -204,6 +474,121 @@ impl SqliteMemory {
         }
     }
 
+    /// Map a row whose leading columns are [`ENTRY_COLUMNS`].
+    fn row_to_entry(row: &rusqlite::Row) -> rusqlite::Result<MemoryEntry> {
+        let tags: Option<String> = row.get(6)?;
+        let access_count: i64 = row.get(11)?;
+        Ok(MemoryEntry {
+            id: row.get(0)?,
+            key: row.get(1)?,
+            content: row.get(2)?,
+            category: Self::str_to_category(&row.get::<_, String>(3)?),
+            timestamp: row.get(4)?,
+            session_id: row.get(5)?,
+            score: None,
+            metadata: MemoryMetadata {
+                tags: tags
+                    .and_then(|tags| serde_json::from_str(&tags).ok())
+                    .unwrap_or_default(),
+                source_channel: row.get(7)?,
+                source_sender: row.get(8)?,
+                importance: row.get(9)?,
+                expires_at: row.get(10)?,
+                access_count: u64::try_from(access_count).unwrap_or(0),
+                last_accessed_at: row.get(12)?,
+            },
+        })
+    }
+
+    /// Tags as stored in the `tags` column: a JSON array, or NULL when empty.
+    fn tags_to_json(tags: &[String]) -> Option<String> {
+        (!tags.is_empty()).then(|| serde_json::to_string(tags).unwrap_or_default())
+    }
+
+    /// Count a recall of `entries`, both in the database and on the entries.
+    fn record_access(conn: &Connection, entries: &mut [MemoryEntry]) -> anyhow::Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let mut stmt = conn.prepare_cached(
+            "UPDATE memories SET access_count = access_count + 1, last_accessed_at = ?1
+             WHERE id = ?2",
+        )?;
+        for entry in entries {
+            stmt.execute(params![now, entry.id])?;
+            entry.metadata.access_count += 1;
+            entry.metadata.last_accessed_at = Some(now.clone());
+        }
+        Ok(())
+    }
+
+    /// Upsert an entry. Existing metadata is replaced only when `metadata`
+    /// is given; access statistics are always kept.
+    async fn upsert(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: Option<MemoryMetadata>,
+    ) -> anyhow::Result<()> {
+        // Compute embedding (async, before blocking work)
+        let embedding = self.get_or_compute_embedding(content).await?;
+        let embedding_bytes = embedding.as_deref().map(vector::vec_to_bytes);
+
+        let conn = self.conn.clone();
+        let vector_index = self.vector_index.clone();
+        let key = key.to_string();
+        let content = content.to_string();
+        let sid = session_id.map(String::from);
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+            let conn = conn.lock();
+            let now = Local::now().to_rfc3339();
+            let cat = Self::category_to_str(&category);
+            let id = Uuid::new_v4().to_string();
+            let metadata_upsert = if metadata.is_some() {
+                METADATA_UPSERT
+            } else {
+                ""
+            };
+            let metadata = metadata.unwrap_or_default();
+
+            conn.execute(
+                &format!(
+                    "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id,
+                                           tags, source_channel, source_sender, importance, expires_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+                     ON CONFLICT(key) DO UPDATE SET
+                        content = excluded.content,
+                        category = excluded.category,
+                        embedding = excluded.embedding,
+                        updated_at = excluded.updated_at,
+                        session_id = excluded.session_id{metadata_upsert}"
+                ),
+                params![
+                    id,
+                    key,
+                    content,
+                    cat,
+                    embedding_bytes,
+                    now,
+                    now,
+                    sid,
+                    Self::tags_to_json(&metadata.tags),
+                    metadata.source_channel,
+                    metadata.source_sender,
+                    metadata.importance,
+                    metadata.expires_at
+                ],
+            )?;
+
+            if let Some(attached) = vector_index {
+                Self::index_embedding(&conn, &attached, &key, embedding.as_deref())?;
+            }
+            Ok(())
+        })
+        .await?
+    }
+
     /// Deterministic content hash for embedding cache.
     /// Uses SHA-256 (truncated) instead of DefaultHasher, which is
     /// explicitly documented as unstable across Rust versions.
@@ -376,6 +761,118 @@ impl SqliteMemory {
         Ok(scored)
     }
 
+    /// Point the attached vector index at the current embedding of `key`.
+    fn index_embedding(
+        conn: &Connection,
+        attached: &AttachedIndex,
+        key: &str,
+        embedding: Option<&[f32]>,
+    ) -> anyhow::Result<()> {
+        // Upserts keep the original row id, so look it up.
+        let id: String = conn.query_row(
+            "SELECT id FROM memories WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )?;
+        attached.update(|index| match embedding {
+            Some(emb) => index.insert(&id, emb),
+            None => {
+                index.remove(&id);
+            }
+        });
+        Ok(())
+    }
+
+    /// Regenerate every stored embedding with the configured provider, in
+    /// batches, and record it as the provider of the stored vectors. Clears
+    /// the embedding cache and re-fills an attached vector index. Run after
+    /// changing the embedding provider, model or dimensions. Returns the
+    /// number of re-embedded memories.
+    pub async fn reembed(&self) -> anyhow::Result<usize> {
+        let dims = self.embedder.dimensions();
+        anyhow::ensure!(
+            dims > 0,
+            "no embedding provider configured; set [memory].embedding_provider first"
+        );
+
+        let conn = self.conn.clone();
+        let entries: Vec<(String, String)> = tokio::task::spawn_blocking(move || {
+            let conn = conn.lock();
+            let mut stmt = conn.prepare("SELECT id, content FROM memories ORDER BY rowid")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            Ok::<_, anyhow::Error>(rows.collect::<Result<Vec<_>, _>>()?)
+        })
+        .await??;
+
+        if let Some(attached) = &self.vector_index {
+            attached.update(|index| {
+                for (id, _) in &entries {
+                    index.remove(id);
+                }
+            });
+        }
+
+        for batch in entries.chunks(REEMBED_BATCH_SIZE) {
+            let texts: Vec<&str> = batch.iter().map(|(_, content)| content.as_str()).collect();
+            let vectors = self.embedder.embed(&texts).await?;
+            anyhow::ensure!(
+                vectors.len() == batch.len(),
+                "embedding provider returned {} vectors for {} texts",
+                vectors.len(),
+                batch.len()
+            );
+            if let Some(v) = vectors.iter().find(|v| v.len() != dims) {
+                anyhow::bail!(
+                    "embedding provider returned {}-dimensional vectors, expected {dims}",
+                    v.len()
+                );
+            }
+
+            let conn = self.conn.clone();
+            let vector_index = self.vector_index.clone();
+            let updates: Vec<(String, Vec<f32>)> = batch
+                .iter()
+                .map(|(id, _)| id.clone())
+                .zip(vectors)
+                .collect();
+            tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+                let mut conn = conn.lock();
+                let tx = conn.transaction()?;
+                for (id, embedding) in &updates {
+                    tx.execute(
+                        "UPDATE memories SET embedding = ?1 WHERE id = ?2",
+                        params![vector::vec_to_bytes(embedding), id],
+                    )?;
+                }
+                tx.commit()?;
+                if let Some(attached) = vector_index {
+                    attached.update(|index| {
+                        for (id, embedding) in &updates {
+                            index.insert(id, embedding);
+                        }
+                    });
+                }
+                Ok(())
+            })
+            .await??;
+        }
+
+        let conn = self.conn.clone();
+        let fingerprint = self.embedder.fingerprint();
+        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+            let conn = conn.lock();
+            conn.execute("DELETE FROM embedding_cache", [])?;
+            Self::record_embedder(&conn, &fingerprint)
+        })
+        .await??;
+        if let Some(attached) = &self.vector_index {
+            attached.save(attached.index.lock().as_ref());
+        }
+        self.embeddings_stale.store(false, Ordering::Relaxed);
+
+        Ok(entries.len())
+    }
+
     /// Safe reindex: rebuild FTS5 + embeddings with rollback on failure
     #[allow(dead_code)]
     pub async fn reindex(&self) -> anyhow::Result<usize> {
@@ -412,6 +909,7 @@ impl SqliteMemory {
             if let Ok(Some(emb)) = self.get_or_compute_embedding(content).await {
                 let bytes = vector::vec_to_bytes(&emb);
                 let conn = self.conn.clone();
+                let vector_index = self.vector_index.clone();
                 let id = id.clone();
                 tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
                     let conn = conn.lock();
@@ -419,6 +917,9 @@ impl SqliteMemory {
                         "UPDATE memories SET embedding = ?1 WHERE id = ?2",
                         params![bytes, id],
                     )?;
+                    if let Some(attached) = vector_index {
+                        attached.update(|index| index.insert(&id, &emb));
+                    }
                     Ok(())
                 })
                 .await??;
@@ -443,37 +944,20 @@ impl Memory for SqliteMemory {
         category: MemoryCategory,
         session_id: Option<&str>,
     ) -> anyhow::Result<()> {
-        // Compute embedding (async, before blocking work)
-        let embedding_bytes = self
-            .get_or_compute_embedding(content)
-            .await?
-            .map(|emb| vector::vec_to_bytes(&emb));
-
-        let conn = self.conn.clone();
-        let key = key.to_string();
-        let content = content.to_string();
-        let sid = session_id.map(String::from);
-
-        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
-            let conn = conn.lock();
-            let now = Local::now().to_rfc3339();
-            let cat = Self::category_to_str(&category);
-            let id = Uuid::new_v4().to_string();
+        self.upsert(key, content, category, session_id, None).await
+    }
 
-            conn.execute(
-                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
-                 ON CONFLICT(key) DO UPDATE SET
-                    content = excluded.content,
-                    category = excluded.category,
-                    embedding = excluded.embedding,
-                    updated_at = excluded.updated_at,
-                    session_id = excluded.session_id",
-                params![id, key, content, cat, embedding_bytes, now, now, sid],
-            )?;
-            Ok(())
-        })
-        .await?
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        let metadata = metadata.normalized()?;
+        self.upsert(key, content, category, session_id, Some(metadata))
+            .await
     }
 
     async fn recall(
@@ -487,26 +971,43 @@ impl Memory for SqliteMemory {
         }
 
         // Compute query embedding (async, before blocking work)
-        let query_embedding = self.get_or_compute_embedding(query).await?;
+        let query_embedding = if self.embeddings_stale() {
+            None
+        } else {
+            self.get_or_compute_embedding(query).await?
+        };
 
         let conn = self.conn.clone();
         let query = query.to_string();
         let sid = session_id.map(String::from);
         let vector_weight = self.vector_weight;
         let keyword_weight = self.keyword_weight;
+        let vector_index = self.vector_index.clone();
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<MemoryEntry>> {
             let conn = conn.lock();
             let session_ref = sid.as_deref();
+            let now = Utc::now();
 
             // FTS5 BM25 keyword search
             let keyword_results = Self::fts5_search(&conn, &query, limit * 2).unwrap_or_default();
 
             // Vector similarity search (if embeddings available)
-            let vector_results = if let Some(ref qe) = query_embedding {
-                Self::vector_search(&conn, qe, limit * 2, None, session_ref).unwrap_or_default()
-            } else {
-                Vec::new()
+            let vector_results = match (&query_embedding, &vector_index) {
+                // The index is not session-aware; results are filtered by
+                // session below, so over-fetch when scoped.
+                (Some(qe), Some(attached)) => {
+                    let fetch = if session_ref.is_some() {
+                        limit * 8
+                    } else {
+                        limit * 2
+                    };
+                    attached.index.lock().search(qe, fetch)
+                }
+                (Some(qe), None) => {
+                    Self::vector_search(&conn, qe, limit * 2, None, session_ref).unwrap_or_default()
+                }
+                (None, _) => Vec::new(),
             };
 
             // Hybrid merge
@@ -538,10 +1039,8 @@ impl Memory for SqliteMemory {
                     .map(|i| format!("?{i}"))
                     .collect::<Vec<_>>()
                     .join(", ");
-                let sql = format!(
-                    "SELECT id, key, content, category, created_at, session_id \
-                     FROM memories WHERE id IN ({placeholders})"
-                );
+                let sql =
+                    format!("SELECT {ENTRY_COLUMNS} FROM memories WHERE id IN ({placeholders})");
                 let mut stmt = conn.prepare(&sql)?;
                 let id_params: Vec<Box<dyn rusqlite::types::ToSql>> = merged
                     .iter()
@@ -549,39 +1048,25 @@ impl Memory for SqliteMemory {
                     .collect();
                 let params_ref: Vec<&dyn rusqlite::types::ToSql> =
                     id_params.iter().map(AsRef::as_ref).collect();
-                let rows = stmt.query_map(params_ref.as_slice(), |row| {
-                    Ok((
-                        row.get::<_, String>(0)?,
-                        row.get::<_, String>(1)?,
-                        row.get::<_, String>(2)?,
-                        row.get::<_, String>(3)?,
-                        row.get::<_, String>(4)?,
-                        row.get::<_, Option<String>>(5)?,
-                    ))
-                })?;
+                let rows = stmt.query_map(params_ref.as_slice(), Self::row_to_entry)?;
 
                 let mut entry_map = std::collections::HashMap::new();
                 for row in rows {
-                    let (id, key, content, cat, ts, sid) = row?;
-                    entry_map.insert(id, (key, content, cat, ts, sid));
+                    let entry = row?;
+                    entry_map.insert(entry.id.clone(), entry);
                 }
 
                 for scored in &merged {
-                    if let Some((key, content, cat, ts, sid)) = entry_map.remove(&scored.id) {
-                        let entry = MemoryEntry {
-                            id: scored.id.clone(),
-                            key,
-                            content,
-                            category: Self::str_to_category(&cat),
-                            timestamp: ts,
-                            session_id: sid,
-                            score: Some(f64::from(scored.final_score)),
-                        };
+                    if let Some(mut entry) = entry_map.remove(&scored.id) {
+                        entry.score = Some(f64::from(scored.final_score));
                         if let Some(filter_sid) = session_ref {
                             if entry.session_id.as_deref() != Some(filter_sid) {
                                 continue;
                             }
                         }
+                        if entry.metadata.is_expired_at(now) {
+                            continue;
+                        }
                         results.push(entry);
                     }
                 }
@@ -607,7 +1092,7 @@ impl Memory for SqliteMemory {
                         .collect();
                     let where_clause = conditions.join(" OR ");
                     let sql = format!(
-                        "SELECT id, key, content, category, created_at, session_id FROM memories
+                        "SELECT {ENTRY_COLUMNS} FROM memories
                          WHERE {where_clause}
                          ORDER BY updated_at DESC
                          LIMIT ?{}",
@@ -623,30 +1108,25 @@ impl Memory for SqliteMemory {
                     param_values.push(Box::new(limit as i64));
                     let params_ref: Vec<&dyn rusqlite::types::ToSql> =
                         param_values.iter().map(AsRef::as_ref).collect();
-                    let rows = stmt.query_map(params_ref.as_slice(), |row| {
-                        Ok(MemoryEntry {
-                            id: row.get(0)?,
-                            key: row.get(1)?,
-                            content: row.get(2)?,
-                            category: Self::str_to_category(&row.get::<_, String>(3)?),
-                            timestamp: row.get(4)?,
-                            session_id: row.get(5)?,
-                            score: Some(1.0),
-                        })
-                    })?;
+                    let rows = stmt.query_map(params_ref.as_slice(), Self::row_to_entry)?;
                     for row in rows {
-                        let entry = row?;
+                        let mut entry = row?;
                         if let Some(sid) = session_ref {
                             if entry.session_id.as_deref() != Some(sid) {
                                 continue;
                             }
                         }
+                        if entry.metadata.is_expired_at(now) {
+                            continue;
+                        }
+                        entry.score = Some(1.0);
                         results.push(entry);
                     }
                 }
             }
 
             results.truncate(limit);
+            Self::record_access(&conn, &mut results)?;
             Ok(results)
         })
         .await?
@@ -658,21 +1138,11 @@ impl Memory for SqliteMemory {
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<Option<MemoryEntry>> {
             let conn = conn.lock();
-            let mut stmt = conn.prepare(
-                "SELECT id, key, content, category, created_at, session_id FROM memories WHERE key = ?1",
-            )?;
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {ENTRY_COLUMNS} FROM memories WHERE key = ?1"
+            ))?;
 
-            let mut rows = stmt.query_map(params![key], |row| {
-                Ok(MemoryEntry {
-                    id: row.get(0)?,
-                    key: row.get(1)?,
-                    content: row.get(2)?,
-                    category: Self::str_to_category(&row.get::<_, String>(3)?),
-                    timestamp: row.get(4)?,
-                    session_id: row.get(5)?,
-                    score: None,
-                })
-            })?;
+            let mut rows = stmt.query_map(params![key], Self::row_to_entry)?;
 
             match rows.next() {
                 Some(Ok(entry)) => Ok(Some(entry)),
@@ -696,27 +1166,17 @@ impl Memory for SqliteMemory {
         tokio::task::spawn_blocking(move || -> anyhow::Result<Vec<MemoryEntry>> {
             let conn = conn.lock();
             let session_ref = sid.as_deref();
+            let now = Utc::now();
             let mut results = Vec::new();
 
-            let row_mapper = |row: &rusqlite::Row| -> rusqlite::Result<MemoryEntry> {
-                Ok(MemoryEntry {
-                    id: row.get(0)?,
-                    key: row.get(1)?,
-                    content: row.get(2)?,
-                    category: Self::str_to_category(&row.get::<_, String>(3)?),
-                    timestamp: row.get(4)?,
-                    session_id: row.get(5)?,
-                    score: None,
-                })
-            };
-
             if let Some(ref cat) = category {
                 let cat_str = Self::category_to_str(cat);
-                let mut stmt = conn.prepare(
-                    "SELECT id, key, content, category, created_at, session_id FROM memories
-                     WHERE category = ?1 ORDER BY updated_at DESC LIMIT ?2",
-                )?;
-                let rows = stmt.query_map(params![cat_str, DEFAULT_LIST_LIMIT], row_mapper)?;
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {ENTRY_COLUMNS} FROM memories
+                     WHERE category = ?1 ORDER BY updated_at DESC LIMIT ?2"
+                ))?;
+                let rows =
+                    stmt.query_map(params![cat_str, DEFAULT_LIST_LIMIT], Self::row_to_entry)?;
                 for row in rows {
                     let entry = row?;
                     if let Some(sid) = session_ref {
@@ -724,14 +1184,17 @@ impl Memory for SqliteMemory {
                             continue;
                         }
                     }
+                    if entry.metadata.is_expired_at(now) {
+                        continue;
+                    }
                     results.push(entry);
                 }
             } else {
-                let mut stmt = conn.prepare(
-                    "SELECT id, key, content, category, created_at, session_id FROM memories
-                     ORDER BY updated_at DESC LIMIT ?1",
-                )?;
-                let rows = stmt.query_map(params![DEFAULT_LIST_LIMIT], row_mapper)?;
+                let mut stmt = conn.prepare(&format!(
+                    "SELECT {ENTRY_COLUMNS} FROM memories
+                     ORDER BY updated_at DESC LIMIT ?1"
+                ))?;
+                let rows = stmt.query_map(params![DEFAULT_LIST_LIMIT], Self::row_to_entry)?;
                 for row in rows {
                     let entry = row?;
                     if let Some(sid) = session_ref {
@@ -739,6 +1202,9 @@ impl Memory for SqliteMemory {
                             continue;
                         }
                     }
+                    if entry.metadata.is_expired_at(now) {
+                        continue;
+                    }
                     results.push(entry);
                 }
             }
@@ -750,11 +1216,24 @@ impl Memory for SqliteMemory {
 
     async fn forget(&self, key: &str) -> anyhow::Result<bool> {
         let conn = self.conn.clone();
+        let vector_index = self.vector_index.clone();
         let key = key.to_string();
 
         tokio::task::spawn_blocking(move || -> anyhow::Result<bool> {
             let conn = conn.lock();
+            let id: Option<String> = conn
+                .query_row(
+                    "SELECT id FROM memories WHERE key = ?1",
+                    params![key],
+                    |row| row.get(0),
+                )
+                .optional()?;
             let affected = conn.execute("DELETE FROM memories WHERE key = ?1", params![key])?;
+            if let (Some(attached), Some(id)) = (vector_index, id) {
+                attached.update(|index| {
+                    index.remove(&id);
+                });
+            }
             Ok(affected > 0)
         })
         .await?
@@ -779,6 +1258,116 @@ impl Memory for SqliteMemory {
             .await
             .unwrap_or(false)
     }
+
+    /// Pages by `rowid`; the cursor is the last row's `rowid`.
+    async fn export_page(
+        &self,
+        include_embeddings: bool,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<MemoryExportPage> {
+        let after: i64 = match cursor {
+            Some(cursor) => cursor
+                .parse()
+                .with_context(|| format!("invalid memory export cursor '{cursor}'"))?,
+            None => 0,
+        };
+        let limit = i64::try_from(limit.max(1)).unwrap_or(i64::MAX);
+        let conn = self.conn.clone();
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<MemoryExportPage> {
+            let conn = conn.lock();
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {ENTRY_COLUMNS}, embedding, rowid FROM memories
+                 WHERE rowid > ?1 ORDER BY rowid LIMIT ?2"
+            ))?;
+            let rows = stmt.query_map(params![after, limit], |row| {
+                let embedding: Option<Vec<u8>> = row.get(13)?;
+                let rowid: i64 = row.get(14)?;
+                let record = MemoryRecord {
+                    entry: Self::row_to_entry(row)?,
+                    embedding: embedding
+                        .filter(|_| include_embeddings)
+                        .map(|bytes| vector::bytes_to_vec(&bytes)),
+                };
+                Ok((rowid, record))
+            })?;
+            let rows = rows.collect::<Result<Vec<_>, _>>()?;
+            let next_cursor = if i64::try_from(rows.len()).unwrap_or(i64::MAX) == limit {
+                rows.last().map(|(rowid, _)| rowid.to_string())
+            } else {
+                None
+            };
+            Ok(MemoryExportPage {
+                records: rows.into_iter().map(|(_, record)| record).collect(),
+                next_cursor,
+            })
+        })
+        .await?
+    }
+
+    async fn import_entry(&self, record: &MemoryRecord) -> anyhow::Result<()> {
+        let entry = record.entry.clone();
+        let metadata = entry.metadata.normalized()?;
+        let embedding = match &record.embedding {
+            Some(embedding) => Some(embedding.clone()),
+            None => self.get_or_compute_embedding(&entry.content).await?,
+        };
+        let embedding_bytes = embedding.as_deref().map(vector::vec_to_bytes);
+
+        let conn = self.conn.clone();
+        let vector_index = self.vector_index.clone();
+
+        tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+            let conn = conn.lock();
+            let cat = Self::category_to_str(&entry.category);
+            let id = Uuid::new_v4().to_string();
+
+            conn.execute(
+                "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at, session_id,
+                                       tags, source_channel, source_sender, importance, expires_at,
+                                       access_count, last_accessed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+                 ON CONFLICT(key) DO UPDATE SET
+                    content = excluded.content,
+                    category = excluded.category,
+                    embedding = excluded.embedding,
+                    created_at = excluded.created_at,
+                    updated_at = excluded.updated_at,
+                    session_id = excluded.session_id,
+                    tags = excluded.tags,
+                    source_channel = excluded.source_channel,
+                    source_sender = excluded.source_sender,
+                    importance = excluded.importance,
+                    expires_at = excluded.expires_at,
+                    access_count = excluded.access_count,
+                    last_accessed_at = excluded.last_accessed_at",
+                params![
+                    id,
+                    entry.key,
+                    entry.content,
+                    cat,
+                    embedding_bytes,
+                    entry.timestamp,
+                    entry.timestamp,
+                    entry.session_id,
+                    Self::tags_to_json(&metadata.tags),
+                    metadata.source_channel,
+                    metadata.source_sender,
+                    metadata.importance,
+                    metadata.expires_at,
+                    i64::try_from(metadata.access_count).unwrap_or(i64::MAX),
+                    metadata.last_accessed_at
+                ],
+            )?;
+
+            if let Some(attached) = vector_index {
+                Self::index_embedding(&conn, &attached, &entry.key, embedding.as_deref())?;
+            }
+            Ok(())
+        })
+        .await?
+    }
 }
 
 #[cfg(test)]
@@ -922,6 +1511,32 @@ mod tests {
         assert_eq!(all.len(), 3);
     }
 
+    #[tokio::test]
+    async fn sqlite_export_page_walks_entries_by_cursor() {
+        let (_tmp, mem) = temp_sqlite();
+        for key in ["a", "b", "c", "d", "e"] {
+            mem.store(key, "value", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+
+        let mut keys = Vec::new();
+        let mut cursor: Option<String> = None;
+        let mut pages = 0;
+        loop {
+            let page = mem.export_page(false, cursor.as_deref(), 2).await.unwrap();
+            assert!(page.records.len() <= 2);
+            keys.extend(page.records.into_iter().map(|record| record.entry.key));
+            pages += 1;
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(keys, ["a", "b", "c", "d", "e"]);
+        assert_eq!(pages, 3);
+    }
+
     #[tokio::test]
     async fn sqlite_list_by_category() {
         let (_tmp, mem) = temp_sqlite();
@@ -1785,6 +2400,103 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn schema_migration_adds_metadata_columns_to_old_tables() {
+        let tmp = TempDir::new().unwrap();
+        let db_path = tmp.path().join("memory").join("brain.db");
+        std::fs::create_dir_all(db_path.parent().unwrap()).unwrap();
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE memories (
+                    id TEXT PRIMARY KEY, key TEXT NOT NULL UNIQUE, content TEXT NOT NULL,
+                    category TEXT NOT NULL DEFAULT 'core', embedding BLOB,
+                    created_at TEXT NOT NULL, updated_at TEXT NOT NULL
+                 );
+                 INSERT INTO memories VALUES ('1', 'old', 'legacy row', 'core', NULL, 't', 't');",
+            )
+            .unwrap();
+        }
+
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let entry = mem.get("old").await.unwrap().unwrap();
+        assert_eq!(entry.content, "legacy row");
+        assert!(entry.metadata.is_empty());
+    }
+
+    // ── Entry metadata ───────────────────────────────────────
+
+    #[tokio::test]
+    async fn store_with_metadata_roundtrips_and_plain_store_keeps_it() {
+        let (_tmp, mem) = temp_sqlite();
+        let metadata = MemoryMetadata {
+            tags: vec!["prefs".into(), " ".into()],
+            source_channel: Some("telegram".into()),
+            source_sender: Some("alice".into()),
+            importance: Some(0.9),
+            expires_at: Some("2999-01-01T01:00:00+01:00".into()),
+            ..Default::default()
+        };
+        mem.store_with_metadata("lang", "Rust", MemoryCategory::Core, None, &metadata)
+            .await
+            .unwrap();
+
+        let entry = mem.get("lang").await.unwrap().unwrap();
+        assert_eq!(entry.metadata.tags, ["prefs"]);
+        assert_eq!(entry.metadata.source_channel.as_deref(), Some("telegram"));
+        assert_eq!(entry.metadata.source_sender.as_deref(), Some("alice"));
+        assert_eq!(entry.metadata.importance, Some(0.9));
+        assert_eq!(
+            entry.metadata.expires_at.as_deref(),
+            Some("2999-01-01T00:00:00+00:00")
+        );
+
+        mem.store("lang", "Rust and Zig", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let entry = mem.get("lang").await.unwrap().unwrap();
+        assert_eq!(entry.content, "Rust and Zig");
+        assert_eq!(entry.metadata.importance, Some(0.9));
+
+        let bad = MemoryMetadata {
+            expires_at: Some("soon".into()),
+            ..Default::default()
+        };
+        assert!(mem
+            .store_with_metadata("x", "y", MemoryCategory::Core, None, &bad)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn recall_counts_access_and_skips_expired_entries() {
+        let (_tmp, mem) = temp_sqlite();
+        mem.store("fresh", "rust tips", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let expired = MemoryMetadata {
+            expires_at: Some((Utc::now() - chrono::Duration::hours(1)).to_rfc3339()),
+            ..Default::default()
+        };
+        mem.store_with_metadata("stale", "rust news", MemoryCategory::Core, None, &expired)
+            .await
+            .unwrap();
+
+        let results = mem.recall("rust", 10, None).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].key, "fresh");
+        assert_eq!(results[0].metadata.access_count, 1);
+
+        mem.recall("rust", 10, None).await.unwrap();
+        let entry = mem.get("fresh").await.unwrap().unwrap();
+        assert_eq!(entry.metadata.access_count, 2);
+        assert!(entry.metadata.last_accessed_at.is_some());
+
+        let listed = mem.list(None, None).await.unwrap();
+        assert!(listed.iter().all(|e| e.key != "stale"));
+        assert!(mem.get("stale").await.unwrap().is_some());
+    }
+
     // ── §4.1 Concurrent write contention tests ──────────────
 
     #[tokio::test]
@@ -1897,4 +2609,235 @@ mod tests {
 
         assert_eq!(mem.count().await.unwrap(), 1);
     }
+
+    // ── HNSW index tests ─────────────────────────────────────────
+
+    /// Embeds text as letter frequencies so related strings land close together.
+    struct LetterEmbedding;
+
+    #[async_trait]
+    impl super::super::embeddings::EmbeddingProvider for LetterEmbedding {
+        fn name(&self) -> &str {
+            "letters"
+        }
+
+        fn dimensions(&self) -> usize {
+            26
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let mut v = vec![0.0_f32; 26];
+                    for b in text.bytes().filter(u8::is_ascii_lowercase) {
+                        v[usize::from(b - b'a')] += 1.0;
+                    }
+                    v
+                })
+                .collect())
+        }
+    }
+
+    fn hnsw_sqlite(dir: &Path) -> SqliteMemory {
+        SqliteMemory::with_embedder(dir, Arc::new(LetterEmbedding), 1.0, 0.0, 1000, None)
+            .unwrap()
+            .with_hnsw_index(HnswParams::default())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn hnsw_index_tracks_store_and_forget() {
+        let tmp = TempDir::new().unwrap();
+        let mem = hnsw_sqlite(tmp.path());
+        mem.store("a", "aaaa aaaa", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("z", "zzzz zzzz", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let hits = mem.recall("zzz", 1, None).await.unwrap();
+        assert_eq!(hits[0].key, "z");
+
+        assert!(mem.forget("z").await.unwrap());
+        let hits = mem.recall("zzz", 5, None).await.unwrap();
+        assert!(hits.iter().all(|e| e.key != "z"));
+    }
+
+    #[tokio::test]
+    async fn out_of_band_deletes_reach_the_attached_index() {
+        let tmp = TempDir::new().unwrap();
+        let mem = hnsw_sqlite(tmp.path());
+        for (key, content) in [("a", "aaaa"), ("z", "zzzz")] {
+            mem.store(key, content, MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+        let id: String = mem
+            .conn
+            .lock()
+            .query_row("SELECT id FROM memories WHERE key = 'z'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        let indexed = || mem.vector_index.as_ref().unwrap().index.lock().len();
+        assert_eq!(indexed(), 2);
+
+        assert_eq!(remove_from_attached_indexes(&mem.db_path, &[id]), 1);
+        assert_eq!(indexed(), 1);
+        assert_eq!(
+            remove_from_attached_indexes(&tmp.path().join("other.db"), &["a".into()]),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn hnsw_index_persists_and_syncs_on_reopen() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let mem = hnsw_sqlite(tmp.path());
+            mem.store("a", "aaaa", MemoryCategory::Core, None)
+                .await
+                .unwrap();
+            assert!(mem.vector_index_path().exists());
+        }
+
+        // Written while no index was attached; must be picked up on reopen.
+        let plain = SqliteMemory::with_embedder(
+            tmp.path(),
+            Arc::new(LetterEmbedding),
+            1.0,
+            0.0,
+            1000,
+            None,
+        )
+        .unwrap();
+        plain
+            .store("q", "qqqq", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        drop(plain);
+
+        let mem = hnsw_sqlite(tmp.path());
+        let hits = mem.recall("qq", 1, None).await.unwrap();
+        assert_eq!(hits[0].key, "q");
+    }
+
+    #[tokio::test]
+    async fn rebuild_hnsw_index_counts_embeddings() {
+        let tmp = TempDir::new().unwrap();
+        let mut mem = hnsw_sqlite(tmp.path());
+        for (key, text) in [("a", "abc"), ("b", "bcd"), ("c", "cde")] {
+            mem.store(key, text, MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+        assert_eq!(mem.rebuild_hnsw_index(HnswParams::default()).unwrap(), 3);
+    }
+
+    /// Letter frequencies followed by a constant tail: another vector space
+    /// with different dimensions, as after switching embedding models.
+    struct WideLetterEmbedding;
+
+    #[async_trait]
+    impl super::super::embeddings::EmbeddingProvider for WideLetterEmbedding {
+        fn name(&self) -> &str {
+            "wide-letters"
+        }
+
+        fn dimensions(&self) -> usize {
+            32
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            let mut vectors = LetterEmbedding.embed(texts).await?;
+            for v in &mut vectors {
+                v.resize(32, 1.0);
+            }
+            Ok(vectors)
+        }
+    }
+
+    fn stored_embedding_lengths(mem: &SqliteMemory) -> Vec<usize> {
+        SqliteMemory::load_embeddings(&mem.conn.lock())
+            .unwrap()
+            .into_iter()
+            .map(|(_, v)| v.len())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn embedder_change_marks_vectors_stale_until_reembed() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let mem = SqliteMemory::with_embedder(
+                tmp.path(),
+                Arc::new(LetterEmbedding),
+                0.7,
+                0.3,
+                1000,
+                None,
+            )
+            .unwrap();
+            assert!(!mem.embeddings_stale());
+            for (key, text) in [("a", "alpha apples"), ("z", "zebra zigzag")] {
+                mem.store(key, text, MemoryCategory::Core, None)
+                    .await
+                    .unwrap();
+            }
+        }
+
+        // Keyword-only access leaves the recorded provider alone.
+        let plain = SqliteMemory::new(tmp.path()).unwrap();
+        assert!(!plain.embeddings_stale());
+        drop(plain);
+
+        let mem = SqliteMemory::with_embedder(
+            tmp.path(),
+            Arc::new(WideLetterEmbedding),
+            0.7,
+            0.3,
+            1000,
+            None,
+        )
+        .unwrap();
+        assert!(mem.embeddings_stale());
+        let hits = mem.recall("zebra", 5, None).await.unwrap();
+        assert_eq!(hits[0].key, "z");
+
+        assert_eq!(mem.reembed().await.unwrap(), 2);
+        assert!(!mem.embeddings_stale());
+        assert_eq!(stored_embedding_lengths(&mem), vec![32, 32]);
+        drop(mem);
+
+        let mem = SqliteMemory::with_embedder(
+            tmp.path(),
+            Arc::new(WideLetterEmbedding),
+            0.7,
+            0.3,
+            1000,
+            None,
+        )
+        .unwrap();
+        assert!(!mem.embeddings_stale());
+    }
+
+    #[tokio::test]
+    async fn reembed_refills_hnsw_index_and_requires_embedder() {
+        let tmp = TempDir::new().unwrap();
+        let mem = hnsw_sqlite(tmp.path());
+        mem.store("a", "aaaa", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("z", "zzzz", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert_eq!(mem.reembed().await.unwrap(), 2);
+        let hits = mem.recall("zz", 1, None).await.unwrap();
+        assert_eq!(hits[0].key, "z");
+
+        let (_tmp, plain) = temp_sqlite();
+        assert!(plain.reembed().await.is_err());
+    }
 }
@@ -11,6 +11,8 @@ pub struct MemoryEntry {
     pub timestamp: String,
     pub session_id: Option<String>,
     pub score: Option<f64>,
+    #[serde(default, skip_serializing_if = "MemoryMetadata::is_empty")]
+    pub metadata: MemoryMetadata,
 }
 
 impl std::fmt::Debug for MemoryEntry {
@@ -22,10 +24,106 @@ impl std::fmt::Debug for MemoryEntry {
             .field("category", &self.category)
             .field("timestamp", &self.timestamp)
             .field("score", &self.score)
+            .field("metadata", &self.metadata)
             .finish_non_exhaustive()
     }
 }
 
+/// Optional structured metadata attached to a memory entry.
+///
+/// `access_count` and `last_accessed_at` are maintained by backends that
+/// track recall; they are ignored when storing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MemoryMetadata {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_channel: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_sender: Option<String>,
+    /// Importance in `0.0..=1.0`; unset entries rank as [`DEFAULT_IMPORTANCE`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub importance: Option<f64>,
+    /// RFC 3339 time after which the entry is no longer recalled and is
+    /// removed by hygiene.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    #[serde(skip_serializing_if = "is_zero")]
+    pub access_count: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_accessed_at: Option<String>,
+}
+
+/// Importance assumed for entries stored without one.
+pub const DEFAULT_IMPORTANCE: f64 = 0.5;
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // serde `skip_serializing_if` signature
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+impl MemoryMetadata {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Importance clamped to `0.0..=1.0`, or [`DEFAULT_IMPORTANCE`].
+    pub fn importance(&self) -> f64 {
+        self.importance
+            .filter(|value| value.is_finite())
+            .map_or(DEFAULT_IMPORTANCE, |value| value.clamp(0.0, 1.0))
+    }
+
+    /// Whether `expires_at` is at or before `now`. Unparseable expiry times
+    /// never expire.
+    pub fn is_expired_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.expires_at
+            .as_deref()
+            .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(chrono::Utc::now())
+    }
+
+    /// Copy with `expires_at` normalized to UTC and `importance` clamped, as
+    /// backends persist it.
+    pub fn normalized(&self) -> anyhow::Result<Self> {
+        let mut metadata = self.clone();
+        if let Some(expires_at) = &self.expires_at {
+            let parsed = chrono::DateTime::parse_from_rfc3339(expires_at).map_err(|e| {
+                anyhow::anyhow!("invalid expires_at '{expires_at}': expected RFC 3339 ({e})")
+            })?;
+            metadata.expires_at = Some(parsed.with_timezone(&chrono::Utc).to_rfc3339());
+        }
+        if self.importance.is_some() {
+            metadata.importance = Some(self.importance());
+        }
+        metadata.tags.retain(|tag| !tag.trim().is_empty());
+        Ok(metadata)
+    }
+}
+
+/// A memory entry with its stored embedding, as carried by portable
+/// export bundles (see [`super::bundle`]).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryRecord {
+    #[serde(flatten)]
+    pub entry: MemoryEntry,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// One page of [`Memory::export_page`].
+#[derive(Debug, Clone, Default)]
+pub struct MemoryExportPage {
+    pub records: Vec<MemoryRecord>,
+    /// Opaque cursor for the next page; `None` after the last page.
+    pub next_cursor: Option<String>,
+}
+
 /// Memory categories for organization
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MemoryCategory {
@@ -83,6 +181,21 @@ pub trait Memory: Send + Sync {
         session_id: Option<&str>,
     ) -> anyhow::Result<()>;
 
+    /// Store a memory entry with structured metadata.
+    ///
+    /// Backends without metadata support store the entry without it.
+    async fn store_with_metadata(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+        metadata: &MemoryMetadata,
+    ) -> anyhow::Result<()> {
+        let _ = metadata;
+        self.store(key, content, category, session_id).await
+    }
+
     /// Recall memories matching a query (keyword search), optionally scoped to a session
     async fn recall(
         &self,
@@ -109,6 +222,57 @@ pub trait Memory: Send + Sync {
 
     /// Health check
     async fn health_check(&self) -> bool;
+
+    /// Up to `limit` stored entries after `cursor`, for backup and migration
+    /// between backends. Start with `None` and pass each page's
+    /// `next_cursor` back until it is `None`.
+    ///
+    /// Embeddings are included when requested and the backend stores them.
+    /// The default pages through [`Memory::list`] by offset, without
+    /// embeddings.
+    async fn export_page(
+        &self,
+        include_embeddings: bool,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<MemoryExportPage> {
+        let _ = include_embeddings;
+        let offset = match cursor {
+            Some(cursor) => cursor
+                .parse::<usize>()
+                .map_err(|_| anyhow::anyhow!("invalid memory export cursor '{cursor}'"))?,
+            None => 0,
+        };
+        let entries = self.list(None, None).await?;
+        let total = entries.len();
+        let end = offset.saturating_add(limit.max(1)).min(total);
+        Ok(MemoryExportPage {
+            records: entries
+                .into_iter()
+                .take(end)
+                .skip(offset)
+                .map(|entry| MemoryRecord {
+                    entry,
+                    embedding: None,
+                })
+                .collect(),
+            next_cursor: (end < total).then(|| end.to_string()),
+        })
+    }
+
+    /// Store an exported entry, keeping its timestamp and embedding where
+    /// the backend can. The default stores it like a new entry.
+    async fn import_entry(&self, record: &MemoryRecord) -> anyhow::Result<()> {
+        let entry = &record.entry;
+        self.store_with_metadata(
+            &entry.key,
+            &entry.content,
+            entry.category.clone(),
+            entry.session_id.as_deref(),
+            &entry.metadata,
+        )
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -156,6 +320,11 @@ mod tests {
             timestamp: "2026-02-16T00:00:00Z".into(),
             session_id: Some("session-abc".into()),
             score: Some(0.98),
+            metadata: MemoryMetadata {
+                tags: vec!["lang".into()],
+                importance: Some(0.8),
+                ..Default::default()
+            },
         };
 
         let json = serde_json::to_string(&entry).unwrap();
@@ -167,5 +336,46 @@ mod tests {
         assert_eq!(parsed.category, MemoryCategory::Core);
         assert_eq!(parsed.session_id.as_deref(), Some("session-abc"));
         assert_eq!(parsed.score, Some(0.98));
+        assert_eq!(parsed.metadata.tags, ["lang"]);
+        assert_eq!(parsed.metadata.importance, Some(0.8));
+    }
+
+    #[test]
+    fn memory_entry_without_metadata_deserializes_with_defaults() {
+        let json = r#"{"id":"1","key":"k","content":"c","category":"core","timestamp":"t","session_id":null,"score":null}"#;
+        let parsed: MemoryEntry = serde_json::from_str(json).unwrap();
+        assert!(parsed.metadata.is_empty());
+        assert!(!serde_json::to_string(&parsed).unwrap().contains("metadata"));
+    }
+
+    #[test]
+    fn metadata_expiry_and_importance() {
+        let now = chrono::Utc::now();
+        let metadata = MemoryMetadata {
+            expires_at: Some((now - chrono::Duration::minutes(1)).to_rfc3339()),
+            importance: Some(3.0),
+            ..Default::default()
+        };
+        assert!(metadata.is_expired_at(now));
+        assert!((metadata.importance() - 1.0).abs() < f64::EPSILON);
+        assert!((MemoryMetadata::default().importance() - DEFAULT_IMPORTANCE).abs() < f64::EPSILON);
+        assert!(!MemoryMetadata::default().is_expired_at(now));
+
+        let normalized = MemoryMetadata {
+            expires_at: Some("2026-01-01T02:00:00+02:00".into()),
+            ..Default::default()
+        }
+        .normalized()
+        .unwrap();
+        assert_eq!(
+            normalized.expires_at.as_deref(),
+            Some("2026-01-01T00:00:00+00:00")
+        );
+        assert!(MemoryMetadata {
+            expires_at: Some("tomorrow".into()),
+            ..Default::default()
+        }
+        .normalized()
+        .is_err());
     }
 }
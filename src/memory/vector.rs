@@ -54,6 +54,53 @@ pub fn bytes_to_vec(bytes: &[u8]) -> Vec<f32> {
         .collect()
 }
 
+/// Stable fingerprint of an embedding (FNV-1a over its little-endian bytes).
+///
+/// Lets persisted indexes detect rows that were re-embedded while the index
+/// was not watching.
+pub fn vector_fingerprint(v: &[f32]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for f in v {
+        for byte in f.to_le_bytes() {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+/// Approximate nearest-neighbour index over memory embeddings.
+///
+/// Backends that scan embeddings directly (the default "flat" mode) do not
+/// need one; an index trades memory for sub-linear recall on large stores.
+pub trait VectorIndex: Send + Sync {
+    /// Index kind, e.g. `"hnsw"`.
+    fn name(&self) -> &str;
+
+    /// Number of live vectors.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether `id` is indexed with an embedding matching `fingerprint`.
+    fn contains(&self, id: &str, fingerprint: u64) -> bool;
+
+    /// Insert or replace the embedding for `id`.
+    fn insert(&mut self, id: &str, embedding: &[f32]);
+
+    /// Remove `id`. Returns `true` if it was indexed.
+    fn remove(&mut self, id: &str) -> bool;
+
+    /// Up to `limit` `(id, cosine_similarity)` pairs, best first. Only
+    /// positive similarities are returned, matching [`cosine_similarity`].
+    fn search(&self, query: &[f32], limit: usize) -> Vec<(String, f32)>;
+
+    /// Persist the index to `path`.
+    fn save(&self, path: &std::path::Path) -> anyhow::Result<()>;
+}
+
 /// A scored result for hybrid merging
 #[derive(Debug, Clone)]
 pub struct ScoredResult {
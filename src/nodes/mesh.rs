@@ -0,0 +1,726 @@
+//! Federated daemon mesh over [`NodeTransport`].
+//!
+//! Each daemon lists the other sites under `[[node_transport.peers]]`. A
+//! daemon periodically pulls every peer's `advertise` endpoint to learn which
+//! tools, delegate agents and SOPs that peer lets it use, and calls `invoke` /
+//! `delegate` to run them remotely. Every pair of daemons shares its own
+//! secret (`shared_secret` on the peer entry). Requests are HMAC-signed with
+//! it and carry the sender's node name inside the signed body; the receiver
+//! checks the signature with the key of the peer named there, so one mesh
+//! member cannot borrow another member's allowlist.
+//!
+//! ```text
+//! A -> B: POST /api/node-control/advertise {"from":"site-a","kind":"advertise"}
+//! B -> A: {"node":"site-b","tools":[{"name":"shell",...}],"agents":["researcher"],"sops":[]}
+//! A -> B: POST /api/node-control/invoke {"from":"site-a","kind":"invoke","tool":"shell","args":{...}}
+//! B -> A: {"success":true,"output":"...","error":null}
+//! ```
+
+use super::NodeTransport;
+use crate::config::{MeshPeerConfig, NodeTransportConfig};
+use crate::tools::peer_tool::MESH_DELEGATE_TOOL_NAME;
+use crate::tools::traits::{Tool, ToolResult, ToolSpec};
+use anyhow::{anyhow, bail, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Name of the local tool that runs delegate agents; `delegate` calls are
+/// served through it.
+const DELEGATE_TOOL_NAME: &str = "delegate";
+
+/// Latest advertisement received from each peer, keyed by peer name.
+static DIRECTORY: LazyLock<RwLock<BTreeMap<String, MeshAdvertisement>>> =
+    LazyLock::new(|| RwLock::new(BTreeMap::new()));
+
+/// What a peer offers to the requesting node.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MeshAdvertisement {
+    /// Node name of the advertising daemon.
+    pub node: String,
+    /// Tools the requester may call through `invoke`.
+    #[serde(default)]
+    pub tools: Vec<ToolSpec>,
+    /// Delegate agents the requester may run through `delegate`.
+    #[serde(default)]
+    pub agents: Vec<String>,
+    /// SOPs installed on the peer (informational).
+    #[serde(default)]
+    pub sops: Vec<String>,
+}
+
+/// One mesh call. The variant decides the control endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MeshCall {
+    Advertise,
+    Invoke {
+        tool: String,
+        #[serde(default)]
+        args: Value,
+    },
+    Delegate {
+        agent: String,
+        prompt: String,
+        #[serde(default)]
+        context: Option<String>,
+    },
+}
+
+impl MeshCall {
+    /// Control endpoint (`/api/node-control/<endpoint>`) serving this call.
+    pub fn endpoint(&self) -> &'static str {
+        match self {
+            Self::Advertise => "advertise",
+            Self::Invoke { .. } => "invoke",
+            Self::Delegate { .. } => "delegate",
+        }
+    }
+}
+
+/// Signed request body: the sender's node name plus the call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeshRequest {
+    pub from: String,
+    #[serde(flatten)]
+    pub call: MeshCall,
+}
+
+/// Name this daemon uses when talking to peers: `node_name`, else the host name.
+pub fn local_node_name(config: &NodeTransportConfig) -> String {
+    config
+        .node_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            hostname::get().map_or_else(|_| "unknown".into(), |h| h.to_string_lossy().to_string())
+        })
+}
+
+/// Whether the mesh is configured: transport enabled and at least one peer,
+/// each with its own shared secret.
+pub fn mesh_enabled(config: &NodeTransportConfig) -> bool {
+    config.enabled
+        && !config.peers.is_empty()
+        && config
+            .peers
+            .iter()
+            .all(|peer| !peer.shared_secret.trim().is_empty())
+}
+
+/// Snapshot of the latest advertisement from every reachable peer.
+pub fn directory() -> Vec<(String, MeshAdvertisement)> {
+    DIRECTORY
+        .read()
+        .iter()
+        .map(|(peer, ad)| (peer.clone(), ad.clone()))
+        .collect()
+}
+
+/// Health component name for a peer, reported under `/api/health`.
+fn health_component(peer: &str) -> String {
+    format!("mesh:{peer}")
+}
+
+/// `"*"` allows everything; otherwise the name must be listed.
+fn allowlisted(allowlist: &[String], name: &str) -> bool {
+    allowlist.iter().any(|entry| entry == "*" || entry == name)
+}
+
+/// Whether `ip` matches one of `allowed` (plain addresses or CIDRs). An
+/// empty list allows every address; the signature is still required.
+pub fn peer_ip_allowed(allowed: &[String], ip: IpAddr) -> bool {
+    if allowed.is_empty() {
+        return true;
+    }
+    allowed.iter().any(|entry| {
+        let entry = entry.trim();
+        let (addr, prefix) = match entry.split_once('/') {
+            Some((addr, prefix)) => match prefix.parse::<u32>() {
+                Ok(prefix) => (addr, Some(prefix)),
+                Err(_) => return false,
+            },
+            None => (entry, None),
+        };
+        let Ok(network) = addr.parse::<IpAddr>() else {
+            return false;
+        };
+        match (network, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                prefix_match(u32::from(net).into(), u32::from(ip).into(), prefix, 32)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                prefix_match(u128::from(net), u128::from(ip), prefix, 128)
+            }
+            _ => false,
+        }
+    })
+}
+
+fn prefix_match(network: u128, ip: u128, prefix: Option<u32>, bits: u32) -> bool {
+    let prefix = prefix.unwrap_or(bits);
+    if prefix > bits {
+        return false;
+    }
+    if prefix == 0 {
+        return true;
+    }
+    let shift = bits - prefix;
+    (network >> shift) == (ip >> shift)
+}
+
+/// Names of the SOP directories under `sops_dir`.
+fn installed_sops(sops_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(sops_dir) else {
+        return Vec::new();
+    };
+    let mut sops: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().join("SOP.toml").is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+    sops.sort();
+    sops
+}
+
+// ── Client side ─────────────────────────────────────────────────
+
+/// Calls peers listed in `[[node_transport.peers]]`.
+pub struct MeshClient {
+    transport: NodeTransport,
+    node_name: String,
+    peers: HashMap<String, MeshPeerConfig>,
+}
+
+impl MeshClient {
+    /// `None` when the mesh is not configured.
+    pub fn from_config(config: &NodeTransportConfig) -> Option<Self> {
+        if !mesh_enabled(config) {
+            return None;
+        }
+        Some(Self {
+            transport: NodeTransport::from_config(config),
+            node_name: local_node_name(config),
+            peers: config
+                .peers
+                .iter()
+                .map(|peer| (peer.name.clone(), peer.clone()))
+                .collect(),
+        })
+    }
+
+    async fn call(&self, peer: &str, call: MeshCall) -> Result<Value> {
+        let peer = self
+            .peers
+            .get(peer)
+            .ok_or_else(|| anyhow!("Unknown mesh peer '{peer}'"))?;
+        let endpoint = call.endpoint();
+        let request = MeshRequest {
+            from: self.node_name.clone(),
+            call,
+        };
+        self.transport
+            .send_signed(
+                &peer.shared_secret,
+                &peer.address,
+                endpoint,
+                serde_json::to_value(&request)?,
+            )
+            .await
+    }
+
+    /// Fetch what `peer` offers to this node.
+    pub async fn advertisement(&self, peer: &str) -> Result<MeshAdvertisement> {
+        Ok(serde_json::from_value(
+            self.call(peer, MeshCall::Advertise).await?,
+        )?)
+    }
+
+    /// Run one of `peer`'s tools.
+    pub async fn invoke(&self, peer: &str, tool: &str, args: Value) -> Result<ToolResult> {
+        let call = MeshCall::Invoke {
+            tool: tool.to_string(),
+            args,
+        };
+        Ok(serde_json::from_value(self.call(peer, call).await?)?)
+    }
+
+    /// Run a task on one of `peer`'s delegate agents.
+    pub async fn delegate(
+        &self,
+        peer: &str,
+        agent: &str,
+        prompt: &str,
+        context: Option<String>,
+    ) -> Result<ToolResult> {
+        let call = MeshCall::Delegate {
+            agent: agent.to_string(),
+            prompt: prompt.to_string(),
+            context,
+        };
+        Ok(serde_json::from_value(self.call(peer, call).await?)?)
+    }
+
+    /// Pull every peer's advertisement into the directory and update its
+    /// health component. Unreachable peers drop out of the directory.
+    pub async fn refresh(&self) {
+        for peer in self.peers.keys() {
+            let component = health_component(peer);
+            match self.advertisement(peer).await {
+                Ok(ad) => {
+                    tracing::debug!(
+                        peer,
+                        tools = ad.tools.len(),
+                        agents = ad.agents.len(),
+                        "Mesh peer advertisement refreshed"
+                    );
+                    DIRECTORY.write().insert(peer.clone(), ad);
+                    crate::health::mark_component_ok(&component);
+                }
+                Err(e) => {
+                    tracing::warn!(peer, "Mesh peer unreachable: {e}");
+                    DIRECTORY.write().remove(peer);
+                    crate::health::mark_component_error(&component, e);
+                }
+            }
+        }
+        crate::tools::peer_tool::sync_peer_tools(&directory());
+    }
+}
+
+/// Keep the peer directory fresh. Runs until aborted.
+pub async fn run_mesh_sync(config: NodeTransportConfig) -> Result<()> {
+    let Some(client) = MeshClient::from_config(&config) else {
+        bail!("node_transport mesh is not configured");
+    };
+    let interval = Duration::from_secs(config.mesh_refresh_secs.max(5));
+    loop {
+        client.refresh().await;
+        tokio::time::sleep(interval).await;
+    }
+}
+
+// ── Server side ─────────────────────────────────────────────────
+
+/// Rejection reasons for an incoming mesh call, mapped to HTTP statuses by
+/// the gateway.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MeshRejection {
+    /// Bad signature, stale timestamp or replayed nonce.
+    Unauthorized(String),
+    /// Unknown peer or not on its allowlist.
+    Forbidden(String),
+    /// Malformed body or endpoint mismatch.
+    BadRequest(String),
+    /// Allowed, but not available on this node.
+    NotFound(String),
+}
+
+/// Serves `/api/node-control/*` for the configured peers.
+pub struct MeshServer {
+    transport: NodeTransport,
+    node_name: String,
+    allowed_ips: Vec<String>,
+    peers: HashMap<String, MeshPeerConfig>,
+    tools: Vec<Box<dyn Tool>>,
+    sops: Vec<String>,
+}
+
+impl MeshServer {
+    /// `None` when the mesh is not configured. `tools` is the registry the
+    /// gateway would expose to its own agent; peers only see the subset their
+    /// allowlist names. `sops_dir` is the resolved `[sop].sops_dir`.
+    pub fn from_config(
+        config: &NodeTransportConfig,
+        sops_dir: &Path,
+        mut tools: Vec<Box<dyn Tool>>,
+    ) -> Option<Self> {
+        if !mesh_enabled(config) {
+            return None;
+        }
+        // Peers reach other peers directly; never relay through this node.
+        tools.retain(|tool| {
+            !tool.name().starts_with("peer:") && tool.name() != MESH_DELEGATE_TOOL_NAME
+        });
+        Some(Self {
+            transport: NodeTransport::from_config(config),
+            node_name: local_node_name(config),
+            allowed_ips: config.allowed_peers.clone(),
+            peers: config
+                .peers
+                .iter()
+                .map(|peer| (peer.name.clone(), peer.clone()))
+                .collect(),
+            tools,
+            sops: installed_sops(sops_dir),
+        })
+    }
+
+    /// Whether a caller at `ip` passes `node_transport.allowed_peers`.
+    pub fn ip_allowed(&self, ip: IpAddr) -> bool {
+        peer_ip_allowed(&self.allowed_ips, ip)
+    }
+
+    /// Parse the body for `endpoint` and verify the signature headers with
+    /// the secret of the peer it claims to come from.
+    pub fn authenticate(
+        &self,
+        endpoint: &str,
+        body: &[u8],
+        timestamp: &str,
+        nonce: &str,
+        signature: &str,
+    ) -> Result<(MeshRequest, &MeshPeerConfig), MeshRejection> {
+        let request: MeshRequest = serde_json::from_slice(body)
+            .map_err(|e| MeshRejection::BadRequest(format!("invalid mesh request: {e}")))?;
+        let peer = self
+            .peers
+            .get(&request.from)
+            .ok_or_else(|| MeshRejection::Forbidden(format!("unknown peer '{}'", request.from)))?;
+        match self.transport.verify_incoming_with(
+            &peer.shared_secret,
+            body,
+            timestamp,
+            nonce,
+            signature,
+        ) {
+            Ok(true) => {}
+            Ok(false) => return Err(MeshRejection::Unauthorized("invalid signature".into())),
+            Err(e) => return Err(MeshRejection::Unauthorized(e.to_string())),
+        }
+        if request.call.endpoint() != endpoint {
+            return Err(MeshRejection::BadRequest(format!(
+                "'{}' call sent to /{endpoint}",
+                request.call.endpoint()
+            )));
+        }
+        Ok((request, peer))
+    }
+
+    fn tool(&self, name: &str) -> Option<&dyn Tool> {
+        self.tools
+            .iter()
+            .find(|tool| tool.name() == name)
+            .map(AsRef::as_ref)
+    }
+
+    /// What `peer` may use on this node.
+    pub fn advertisement_for(&self, peer: &MeshPeerConfig) -> MeshAdvertisement {
+        let delegate_allowed = self.tool(DELEGATE_TOOL_NAME).is_some();
+        MeshAdvertisement {
+            node: self.node_name.clone(),
+            tools: self
+                .tools
+                .iter()
+                .filter(|tool| allowlisted(&peer.allowed_tools, tool.name()))
+                .map(|tool| tool.spec())
+                .collect(),
+            agents: if delegate_allowed {
+                peer.allowed_agents
+                    .iter()
+                    .filter(|agent| agent.as_str() != "*")
+                    .cloned()
+                    .collect()
+            } else {
+                Vec::new()
+            },
+            sops: self.sops.clone(),
+        }
+    }
+
+    /// Execute an authenticated call.
+    pub async fn handle(
+        &self,
+        peer: &MeshPeerConfig,
+        call: MeshCall,
+    ) -> Result<Value, MeshRejection> {
+        let result = match call {
+            MeshCall::Advertise => {
+                return serde_json::to_value(self.advertisement_for(peer))
+                    .map_err(|e| MeshRejection::BadRequest(e.to_string()));
+            }
+            MeshCall::Invoke { tool, args } => {
+                if !allowlisted(&peer.allowed_tools, &tool) {
+                    return Err(MeshRejection::Forbidden(format!(
+                        "tool '{tool}' is not allowed for peer '{}'",
+                        peer.name
+                    )));
+                }
+                let local = self
+                    .tool(&tool)
+                    .ok_or_else(|| MeshRejection::NotFound(format!("unknown tool '{tool}'")))?;
+                tracing::info!(peer = %peer.name, tool, "Mesh tool invocation");
+                local.execute(args).await
+            }
+            MeshCall::Delegate {
+                agent,
+                prompt,
+                context,
+            } => {
+                if !allowlisted(&peer.allowed_agents, &agent) {
+                    return Err(MeshRejection::Forbidden(format!(
+                        "agent '{agent}' is not allowed for peer '{}'",
+                        peer.name
+                    )));
+                }
+                let delegate = self.tool(DELEGATE_TOOL_NAME).ok_or_else(|| {
+                    MeshRejection::NotFound("no delegate agents configured".into())
+                })?;
+                tracing::info!(peer = %peer.name, agent, "Mesh delegation");
+                let mut args = serde_json::json!({"agent": agent, "prompt": prompt});
+                if let Some(context) = context {
+                    args["context"] = Value::String(context);
+                }
+                delegate.execute(args).await
+            }
+        };
+        let result = result.unwrap_or_else(|e| ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(e.to_string()),
+        });
+        serde_json::to_value(result).map_err(|e| MeshRejection::BadRequest(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::transport::sign_request;
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    struct EchoTool(&'static str);
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn description(&self) -> &str {
+            "Echo the arguments"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            serde_json::json!({"type": "object", "properties": {}})
+        }
+
+        async fn execute(&self, args: Value) -> Result<ToolResult> {
+            Ok(ToolResult {
+                success: true,
+                output: format!("{}:{args}", self.0),
+                error: None,
+            })
+        }
+    }
+
+    fn mesh_config() -> NodeTransportConfig {
+        NodeTransportConfig {
+            node_name: Some("site-b".into()),
+            peers: vec![
+                MeshPeerConfig {
+                    name: "site-a".into(),
+                    address: "site-a:443".into(),
+                    shared_secret: "a-b-secret".into(),
+                    allowed_tools: vec!["echo".into()],
+                    allowed_agents: vec!["researcher".into()],
+                },
+                MeshPeerConfig {
+                    name: "site-c".into(),
+                    address: "site-c:443".into(),
+                    shared_secret: "b-c-secret".into(),
+                    allowed_tools: Vec::new(),
+                    allowed_agents: Vec::new(),
+                },
+            ],
+            ..NodeTransportConfig::default()
+        }
+    }
+
+    fn server() -> MeshServer {
+        MeshServer::from_config(
+            &mesh_config(),
+            Path::new("/nonexistent"),
+            vec![
+                Box::new(EchoTool("echo")),
+                Box::new(EchoTool("shell")),
+                Box::new(EchoTool(DELEGATE_TOOL_NAME)),
+                Box::new(EchoTool("peer:site-c:shell")),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn signed(body: &[u8], nonce: &str) -> (String, String) {
+        signed_with("a-b-secret", body, nonce)
+    }
+
+    fn signed_with(secret: &str, body: &[u8], nonce: &str) -> (String, String) {
+        let now = Utc::now().timestamp();
+        let sig = sign_request(secret, body, now, nonce).unwrap();
+        (now.to_string(), sig)
+    }
+
+    #[test]
+    fn mesh_requires_a_secret_per_peer() {
+        let mut config = mesh_config();
+        assert!(mesh_enabled(&config));
+        config.peers[1].shared_secret.clear();
+        assert!(!mesh_enabled(&config));
+        assert!(MeshClient::from_config(&config).is_none());
+        config.peers.clear();
+        assert!(!mesh_enabled(&config));
+    }
+
+    #[test]
+    fn mesh_request_round_trips_with_kind_tag() {
+        let request = MeshRequest {
+            from: "site-a".into(),
+            call: MeshCall::Invoke {
+                tool: "echo".into(),
+                args: serde_json::json!({"x": 1}),
+            },
+        };
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["kind"], "invoke");
+        assert_eq!(json["from"], "site-a");
+        let parsed: MeshRequest = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.call.endpoint(), "invoke");
+    }
+
+    #[test]
+    fn peer_ip_allowlist_matches_addresses_and_cidrs() {
+        let allowed = vec!["10.1.0.0/16".to_string(), "192.168.1.7".to_string()];
+        assert!(peer_ip_allowed(&allowed, "10.1.42.3".parse().unwrap()));
+        assert!(peer_ip_allowed(&allowed, "192.168.1.7".parse().unwrap()));
+        assert!(!peer_ip_allowed(&allowed, "10.2.0.1".parse().unwrap()));
+        assert!(!peer_ip_allowed(&allowed, "::1".parse().unwrap()));
+        assert!(peer_ip_allowed(&[], "203.0.113.9".parse().unwrap()));
+        assert!(peer_ip_allowed(
+            &["fd00::/8".to_string()],
+            "fd12::1".parse().unwrap()
+        ));
+    }
+
+    #[test]
+    fn advertisement_only_lists_allowlisted_tools_and_agents() {
+        let server = server();
+        let peer = &mesh_config().peers[0];
+        let ad = server.advertisement_for(peer);
+        assert_eq!(ad.node, "site-b");
+        let names: Vec<&str> = ad.tools.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["echo"]);
+        assert_eq!(ad.agents, vec!["researcher".to_string()]);
+        assert!(server.tool("peer:site-c:shell").is_none());
+    }
+
+    #[test]
+    fn advertisement_lists_sops_from_the_configured_dir() {
+        let tmp = tempfile::tempdir().unwrap();
+        for name in ["restart-pump", "not-a-sop"] {
+            std::fs::create_dir_all(tmp.path().join(name)).unwrap();
+        }
+        std::fs::write(tmp.path().join("restart-pump").join("SOP.toml"), "").unwrap();
+
+        let server = MeshServer::from_config(&mesh_config(), tmp.path(), Vec::new()).unwrap();
+        let ad = server.advertisement_for(&mesh_config().peers[0]);
+        assert_eq!(ad.sops, vec!["restart-pump".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn authenticated_invoke_respects_allowlist_and_rejects_replays() {
+        let server = server();
+        let body = serde_json::to_vec(&MeshRequest {
+            from: "site-a".into(),
+            call: MeshCall::Invoke {
+                tool: "echo".into(),
+                args: serde_json::json!({"x": 1}),
+            },
+        })
+        .unwrap();
+        let (ts, sig) = signed(&body, "n-1");
+
+        let (request, peer) = server
+            .authenticate("invoke", &body, &ts, "n-1", &sig)
+            .unwrap();
+        let peer = peer.clone();
+        let result = server.handle(&peer, request.call).await.unwrap();
+        assert_eq!(result["success"], true);
+        assert_eq!(result["output"], "echo:{\"x\":1}");
+
+        let replay = server.authenticate("invoke", &body, &ts, "n-1", &sig);
+        assert!(matches!(replay, Err(MeshRejection::Unauthorized(_))));
+
+        let denied = server
+            .handle(
+                &peer,
+                MeshCall::Invoke {
+                    tool: "shell".into(),
+                    args: Value::Null,
+                },
+            )
+            .await;
+        assert!(matches!(denied, Err(MeshRejection::Forbidden(_))));
+    }
+
+    #[test]
+    fn authenticate_rejects_unknown_peer_and_endpoint_mismatch() {
+        let server = server();
+        let body = serde_json::to_vec(&MeshRequest {
+            from: "site-z".into(),
+            call: MeshCall::Advertise,
+        })
+        .unwrap();
+        let (ts, sig) = signed(&body, "n-2");
+        assert!(matches!(
+            server.authenticate("advertise", &body, &ts, "n-2", &sig),
+            Err(MeshRejection::Forbidden(_))
+        ));
+
+        let body = serde_json::to_vec(&MeshRequest {
+            from: "site-a".into(),
+            call: MeshCall::Advertise,
+        })
+        .unwrap();
+        let (ts, sig) = signed(&body, "n-3");
+        assert!(matches!(
+            server.authenticate("invoke", &body, &ts, "n-3", &sig),
+            Err(MeshRejection::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn authenticate_rejects_a_peer_claiming_another_peers_name() {
+        let server = server();
+        let body = serde_json::to_vec(&MeshRequest {
+            from: "site-a".into(),
+            call: MeshCall::Advertise,
+        })
+        .unwrap();
+        // site-c only knows its own key with site-b.
+        let (ts, sig) = signed_with("b-c-secret", &body, "n-4");
+        assert!(matches!(
+            server.authenticate("advertise", &body, &ts, "n-4", &sig),
+            Err(MeshRejection::Unauthorized(_))
+        ));
+
+        let body = serde_json::to_vec(&MeshRequest {
+            from: "site-c".into(),
+            call: MeshCall::Advertise,
+        })
+        .unwrap();
+        let (ts, sig) = signed_with("b-c-secret", &body, "n-5");
+        let (_, peer) = server
+            .authenticate("advertise", &body, &ts, "n-5", &sig)
+            .unwrap();
+        assert_eq!(peer.name, "site-c");
+    }
+}
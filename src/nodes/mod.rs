@@ -1,3 +1,4 @@
+pub mod mesh;
 pub mod transport;
 
 pub use transport::NodeTransport;
@@ -4,10 +4,13 @@
 //! no custom binary framing, no UDP tunneling.  This makes the transport
 //! compatible with corporate proxies, firewalls, and IT audit expectations.
 
+use crate::config::NodeTransportConfig;
 use anyhow::{bail, Result};
 use chrono::Utc;
 use hmac::{Hmac, Mac};
+use parking_lot::Mutex;
 use sha2::Sha256;
+use std::collections::HashMap;
 
 type HmacSha256 = Hmac<Sha256>;
 
@@ -57,6 +60,30 @@ fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
         == 0
 }
 
+/// Nonces accepted within the replay window.
+///
+/// The timestamp check alone lets a captured request be replayed until it
+/// ages out; remembering nonces for the same window closes that gap.
+#[derive(Debug, Default)]
+struct NonceCache {
+    seen: Mutex<HashMap<String, i64>>,
+}
+
+impl NonceCache {
+    /// Record `nonce`, returning `false` if it was already used. Entries
+    /// older than `max_age_secs` are pruned on the way.
+    fn insert(&self, nonce: &str, timestamp: i64, max_age_secs: i64) -> bool {
+        let now = Utc::now().timestamp();
+        let mut seen = self.seen.lock();
+        seen.retain(|_, ts| (now - *ts).abs() <= max_age_secs);
+        if seen.contains_key(nonce) {
+            return false;
+        }
+        seen.insert(nonce.to_string(), timestamp);
+        true
+    }
+}
+
 // ── Node transport client ───────────────────────────────────────
 
 /// Sends authenticated HTTPS requests to peer nodes.
@@ -71,6 +98,8 @@ pub struct NodeTransport {
     http: reqwest::Client,
     shared_secret: String,
     max_request_age_secs: i64,
+    require_https: bool,
+    seen_nonces: NonceCache,
 }
 
 impl NodeTransport {
@@ -82,22 +111,66 @@ impl NodeTransport {
                 .expect("HTTP client build"),
             shared_secret,
             max_request_age_secs: 300, // 5 min replay window
+            require_https: true,
+            seen_nonces: NonceCache::default(),
         }
     }
 
+    /// Build a transport from `[node_transport]`, honouring the configured
+    /// replay window and HTTPS requirement.
+    pub fn from_config(config: &NodeTransportConfig) -> Self {
+        let mut transport = Self::new(config.shared_secret.clone());
+        transport.max_request_age_secs = config.max_request_age_secs.max(1);
+        transport.require_https = config.require_https;
+        transport
+    }
+
+    /// Resolve the control URL for a peer. Bare `host:port` addresses use
+    /// HTTPS; an explicit `http://` is only accepted when `require_https`
+    /// is off (e.g. peers on a private overlay network).
+    fn control_url(&self, node_address: &str, endpoint: &str) -> Result<String> {
+        let address = node_address.trim().trim_end_matches('/');
+        let base = if address.starts_with("https://") {
+            address.to_string()
+        } else if address.starts_with("http://") {
+            if self.require_https {
+                bail!(
+                    "Peer address {address} is not HTTPS and node_transport.require_https is set"
+                );
+            }
+            address.to_string()
+        } else {
+            format!("https://{address}")
+        };
+        Ok(format!("{base}/api/node-control/{endpoint}"))
+    }
+
     /// Send an authenticated request to a peer node.
     pub async fn send(
         &self,
         node_address: &str,
         endpoint: &str,
         payload: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        self.send_signed(&self.shared_secret, node_address, endpoint, payload)
+            .await
+    }
+
+    /// Send a request signed with `shared_secret` instead of the transport's
+    /// own secret (e.g. a key shared with that one peer).
+    pub async fn send_signed(
+        &self,
+        shared_secret: &str,
+        node_address: &str,
+        endpoint: &str,
+        payload: serde_json::Value,
     ) -> Result<serde_json::Value> {
         let body = serde_json::to_vec(&payload)?;
         let timestamp = Utc::now().timestamp();
         let nonce = uuid::Uuid::new_v4().to_string();
-        let signature = sign_request(&self.shared_secret, &body, timestamp, &nonce)?;
+        let signature = sign_request(shared_secret, &body, timestamp, &nonce)?;
 
-        let url = format!("https://{node_address}/api/node-control/{endpoint}");
+        let url = self.control_url(node_address, endpoint)?;
         let resp = self
             .http
             .post(&url)
@@ -121,24 +194,54 @@ impl NodeTransport {
     }
 
     /// Verify an incoming request from a peer node.
+    ///
+    /// A correctly signed request whose nonce was already accepted inside
+    /// the replay window is rejected as a replay.
     pub fn verify_incoming(
         &self,
         payload: &[u8],
         timestamp_header: &str,
         nonce_header: &str,
         signature_header: &str,
+    ) -> Result<bool> {
+        self.verify_incoming_with(
+            &self.shared_secret,
+            payload,
+            timestamp_header,
+            nonce_header,
+            signature_header,
+        )
+    }
+
+    /// [`Self::verify_incoming`] against `shared_secret` instead of the
+    /// transport's own secret. Nonces share one replay cache either way.
+    pub fn verify_incoming_with(
+        &self,
+        shared_secret: &str,
+        payload: &[u8],
+        timestamp_header: &str,
+        nonce_header: &str,
+        signature_header: &str,
     ) -> Result<bool> {
         let timestamp: i64 = timestamp_header
             .parse()
             .map_err(|_| anyhow::anyhow!("Invalid timestamp header"))?;
-        verify_request(
-            &self.shared_secret,
+        let valid = verify_request(
+            shared_secret,
             payload,
             timestamp,
             nonce_header,
             signature_header,
             self.max_request_age_secs,
-        )
+        )?;
+        if valid
+            && !self
+                .seen_nonces
+                .insert(nonce_header, timestamp, self.max_request_age_secs)
+        {
+            bail!("Replayed request nonce");
+        }
+        Ok(valid)
     }
 }
 
@@ -223,6 +326,39 @@ mod tests {
         assert!(result.is_err(), "Non-numeric timestamp header must error");
     }
 
+    #[test]
+    fn node_transport_verify_incoming_rejects_replayed_nonce() {
+        let transport = NodeTransport::new(TEST_SECRET.into());
+        let now = Utc::now().timestamp();
+        let sig = sign_request(TEST_SECRET, b"body", now, "once").unwrap();
+
+        assert!(transport
+            .verify_incoming(b"body", &now.to_string(), "once", &sig)
+            .unwrap());
+        let replay = transport.verify_incoming(b"body", &now.to_string(), "once", &sig);
+        assert!(replay.is_err(), "Replayed nonce must be rejected");
+    }
+
+    #[test]
+    fn node_transport_control_url_enforces_https() {
+        let mut config = NodeTransportConfig::default();
+        let strict = NodeTransport::from_config(&config);
+        assert_eq!(
+            strict.control_url("site-b:443", "advertise").unwrap(),
+            "https://site-b:443/api/node-control/advertise"
+        );
+        assert!(strict.control_url("http://site-b:42617", "invoke").is_err());
+
+        config.require_https = false;
+        let relaxed = NodeTransport::from_config(&config);
+        assert_eq!(
+            relaxed
+                .control_url("http://10.0.0.2:42617/", "invoke")
+                .unwrap(),
+            "http://10.0.0.2:42617/api/node-control/invoke"
+        );
+    }
+
     #[test]
     fn sign_request_different_nonce_different_signature() {
         let sig1 = sign_request(TEST_SECRET, b"data", 1_700_000_000, "nonce-1").unwrap();
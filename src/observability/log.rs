@@ -34,13 +34,23 @@ impl Observer for LogObserver {
                 tool,
                 duration,
                 success,
+                ..
             } => {
                 let ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
                 info!(tool = %tool, duration_ms = ms, success = success, "tool.call");
             }
+            ObserverEvent::ApprovalRequested { tool, .. } => {
+                info!(tool = %tool, "tool.approval_requested");
+            }
+            ObserverEvent::TurnStart { provider, model } => {
+                info!(provider = %provider, model = %model, "turn.start");
+            }
             ObserverEvent::TurnComplete => {
                 info!("turn.complete");
             }
+            ObserverEvent::TurnFailed { error } => {
+                info!(error = %error, "turn.failed");
+            }
             ObserverEvent::ChannelMessage { channel, direction } => {
                 info!(channel = %channel, direction = %direction, "channel.message");
             }
@@ -205,6 +215,7 @@ mod tests {
             tool: "shell".into(),
             duration: Duration::from_millis(10),
             success: false,
+            output: None,
         });
         obs.record_event(&ObserverEvent::ChannelMessage {
             channel: "telegram".into(),
@@ -6,7 +6,9 @@ pub mod otel;
 #[cfg(feature = "observability-prometheus")]
 pub mod prometheus;
 pub mod runtime_trace;
+pub mod trace_cli;
 pub mod traits;
+pub mod turn_events;
 pub mod verbose;
 
 #[allow(unused_imports)]
@@ -24,8 +26,16 @@ pub use verbose::VerboseObserver;
 
 use crate::config::ObservabilityConfig;
 
-/// Factory: create the right observer from config
+/// Factory: create the right observer from config.
+///
+/// The backend observer is wrapped so its events also feed the process-wide
+/// turn event stream (see [`turn_events`]).
 pub fn create_observer(config: &ObservabilityConfig) -> Box<dyn Observer> {
+    let backend = create_backend_observer(config);
+    Box::new(turn_events::TurnEventObserver::new(backend))
+}
+
+fn create_backend_observer(config: &ObservabilityConfig) -> Box<dyn Observer> {
     match config.backend.as_str() {
         "log" => Box::new(LogObserver::new()),
         "verbose" => Box::new(VerboseObserver::new()),
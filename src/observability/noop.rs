@@ -56,6 +56,7 @@ mod tests {
             tool: "shell".into(),
             duration: Duration::from_secs(1),
             success: true,
+            output: None,
         });
         obs.record_event(&ObserverEvent::ChannelMessage {
             channel: "cli".into(),
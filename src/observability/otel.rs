@@ -210,7 +210,10 @@ impl Observer for OtelObserver {
             }
             ObserverEvent::LlmRequest { .. }
             | ObserverEvent::ToolCallStart { .. }
-            | ObserverEvent::TurnComplete => {}
+            | ObserverEvent::ApprovalRequested { .. }
+            | ObserverEvent::TurnStart { .. }
+            | ObserverEvent::TurnComplete
+            | ObserverEvent::TurnFailed { .. } => {}
             ObserverEvent::LlmResponse {
                 provider,
                 model,
@@ -296,6 +299,7 @@ impl Observer for OtelObserver {
                 tool,
                 duration,
                 success,
+                ..
             } => {
                 let secs = duration.as_secs_f64();
                 let start_time = SystemTime::now()
@@ -556,11 +560,13 @@ mod tests {
             tool: "shell".into(),
             duration: Duration::from_millis(10),
             success: true,
+            output: None,
         });
         obs.record_event(&ObserverEvent::ToolCall {
             tool: "file_read".into(),
             duration: Duration::from_millis(5),
             success: false,
+            output: None,
         });
         obs.record_event(&ObserverEvent::TurnComplete);
         obs.record_event(&ObserverEvent::ChannelMessage {
@@ -4,6 +4,7 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs::{self, OpenOptions};
+use std::future::Future;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, LazyLock, RwLock};
@@ -43,6 +44,9 @@ pub struct RuntimeTraceEvent {
     pub model: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub turn_id: Option<String>,
+    /// Conversation the event belongs to (for channels: the history key).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub success: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -144,6 +148,21 @@ impl RuntimeTraceLogger {
 static TRACE_LOGGER: LazyLock<RwLock<Option<Arc<RuntimeTraceLogger>>>> =
     LazyLock::new(|| RwLock::new(None));
 
+tokio::task_local! {
+    static TRACE_SESSION: String;
+}
+
+/// Run `future` with every runtime trace event it records tagged with
+/// `session`, so turns can later be filtered per conversation.
+pub async fn with_session<F: Future>(session: String, future: F) -> F::Output {
+    TRACE_SESSION.scope(session, future).await
+}
+
+/// Session set by the enclosing [`with_session`] scope, if any.
+pub fn current_session() -> Option<String> {
+    TRACE_SESSION.try_with(Clone::clone).ok()
+}
+
 /// Resolve runtime trace storage mode from config.
 pub fn storage_mode_from_config(config: &ObservabilityConfig) -> RuntimeTraceStorageMode {
     let mode = RuntimeTraceStorageMode::from_raw(&config.runtime_trace_mode);
@@ -219,6 +238,7 @@ pub fn record_event(
         provider: provider.map(str::to_string),
         model: model.map(str::to_string),
         turn_id: turn_id.map(str::to_string),
+        session: current_session(),
         success,
         message: message.map(str::to_string),
         payload,
@@ -236,24 +256,7 @@ pub fn load_events(
     event_filter: Option<&str>,
     contains: Option<&str>,
 ) -> Result<Vec<RuntimeTraceEvent>> {
-    if !path.exists() {
-        return Ok(Vec::new());
-    }
-
-    let raw = fs::read_to_string(path)?;
-    let mut events = Vec::new();
-
-    for line in raw.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        match serde_json::from_str::<RuntimeTraceEvent>(trimmed) {
-            Ok(event) => events.push(event),
-            Err(err) => tracing::warn!("Skipping malformed runtime trace line: {err}"),
-        }
-    }
+    let mut events = read_events(path)?;
 
     if let Some(filter) = event_filter.map(str::trim).filter(|f| !f.is_empty()) {
         let normalized = filter.to_ascii_lowercase();
@@ -291,6 +294,30 @@ pub fn load_events(
     Ok(events)
 }
 
+/// Read every runtime trace event from storage, oldest first.
+pub fn read_events(path: &Path) -> Result<Vec<RuntimeTraceEvent>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let raw = fs::read_to_string(path)?;
+    let mut events = Vec::new();
+
+    for line in raw.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<RuntimeTraceEvent>(trimmed) {
+            Ok(event) => events.push(event),
+            Err(err) => tracing::warn!("Skipping malformed runtime trace line: {err}"),
+        }
+    }
+
+    Ok(events)
+}
+
 /// Find a runtime trace event by id.
 pub fn find_event_by_id(path: &Path, id: &str) -> Result<Option<RuntimeTraceEvent>> {
     if !path.exists() {
@@ -373,6 +400,7 @@ mod tests {
                 provider: None,
                 model: None,
                 turn_id: None,
+                session: None,
                 success: None,
                 message: Some(format!("event-{i}")),
                 payload: serde_json::json!({ "i": i }),
@@ -401,6 +429,7 @@ mod tests {
             provider: Some("openrouter".into()),
             model: Some("x".into()),
             turn_id: Some("turn-1".into()),
+            session: None,
             success: Some(false),
             message: Some("boom".into()),
             payload: serde_json::json!({ "error": "boom" }),
@@ -411,4 +440,12 @@ mod tests {
         assert!(found.is_some());
         assert_eq!(found.unwrap().id, target_id);
     }
+
+    #[tokio::test]
+    async fn with_session_scopes_current_session() {
+        assert_eq!(current_session(), None);
+        let inside = with_session("telegram_alice".into(), async { current_session() }).await;
+        assert_eq!(inside.as_deref(), Some("telegram_alice"));
+        assert_eq!(current_session(), None);
+    }
 }
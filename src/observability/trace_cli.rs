@@ -0,0 +1,753 @@
+//! `zeroclaw trace` — read `runtime-trace.jsonl` back as agent turns.
+//!
+//! Events recorded by the agent loop share a `turn_id`; this module groups
+//! them into [`TraceTurn`]s, renders a turn as a tree of model calls and tool
+//! calls, and exports a turn as a JSON trace fixture for the integration
+//! test harness.
+
+use super::runtime_trace::{self, RuntimeTraceEvent};
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use chrono::DateTime;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Handle `zeroclaw trace <subcommand>` CLI commands.
+pub fn handle_command(command: crate::TraceCommands, config: &Config) -> Result<()> {
+    let path = runtime_trace::resolve_trace_path(&config.observability, &config.workspace_dir);
+    if !path.exists() {
+        println!(
+            "Runtime trace file not found: {}.\n\
+             Enable [observability] runtime_trace_mode = \"rolling\" or \"full\", then reproduce the issue.",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    let turns = group_turns(runtime_trace::read_events(&path)?);
+    match command {
+        crate::TraceCommands::List {
+            channel,
+            session,
+            tool,
+            limit,
+        } => {
+            let filter = TurnFilter {
+                channel,
+                session,
+                tool,
+            };
+            handle_list(&path, &turns, &filter, limit);
+            Ok(())
+        }
+        crate::TraceCommands::Show { turn_id } => {
+            let turn = find_turn(&turns, &turn_id)?;
+            print!("{}", render_turn_tree(turn));
+            Ok(())
+        }
+        crate::TraceCommands::Export { turn_id, output } => {
+            let turn = find_turn(&turns, &turn_id)?;
+            let fixture = serde_json::to_string_pretty(&turn_fixture(turn))?;
+            match output {
+                Some(output) => {
+                    std::fs::write(&output, format!("{fixture}\n"))
+                        .with_context(|| format!("Failed to write {}", output.display()))?;
+                    println!("Exported turn {} to {}", turn.turn_id, output.display());
+                }
+                None => println!("{fixture}"),
+            }
+            Ok(())
+        }
+    }
+}
+
+fn handle_list(path: &Path, turns: &[TraceTurn], filter: &TurnFilter, limit: usize) {
+    let matched: Vec<&TraceTurn> = turns
+        .iter()
+        .rev()
+        .filter(|turn| filter.matches(turn))
+        .take(limit.max(1))
+        .collect();
+
+    if matched.is_empty() {
+        println!("No traced turns matched (path: {}).", path.display());
+        return;
+    }
+
+    println!("Traced turns (newest first)");
+    println!("Path: {}", path.display());
+    println!();
+    for turn in matched {
+        let tools = turn.tool_names();
+        let tools = if tools.is_empty() {
+            "no tools".to_string()
+        } else {
+            tools.join(", ")
+        };
+        println!(
+            "- {} | {} | {} | {} | {} model calls | {} | {} | {}",
+            turn.started_at,
+            turn.turn_id,
+            turn.channel.as_deref().unwrap_or("-"),
+            turn.session.as_deref().unwrap_or("-"),
+            turn.model_calls(),
+            tools,
+            format_duration(turn.duration_ms()),
+            turn.outcome(),
+        );
+    }
+    println!();
+    println!("Use `zeroclaw trace show <turn-id>` to render a turn.");
+}
+
+/// One agent turn: every runtime trace event sharing a `turn_id`.
+#[derive(Debug, Clone)]
+pub struct TraceTurn {
+    pub turn_id: String,
+    pub channel: Option<String>,
+    pub session: Option<String>,
+    pub provider: Option<String>,
+    pub model: Option<String>,
+    pub started_at: String,
+    pub events: Vec<RuntimeTraceEvent>,
+}
+
+impl TraceTurn {
+    /// Distinct tool names called during the turn, in first-call order.
+    pub fn tool_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for event in &self.events {
+            if let Some(tool) = event.payload.get("tool").and_then(Value::as_str) {
+                if !names.contains(&tool) {
+                    names.push(tool);
+                }
+            }
+        }
+        names
+    }
+
+    /// Number of model calls that produced a response or an error.
+    pub fn model_calls(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|e| e.event_type == "llm_response")
+            .count()
+    }
+
+    /// Input and output tokens summed over every model call that reported them.
+    pub fn token_totals(&self) -> (u64, u64) {
+        self.events
+            .iter()
+            .filter(|e| e.event_type == "llm_response")
+            .fold((0, 0), |(input, output), e| {
+                (
+                    input + payload_u64(&e.payload, "input_tokens").unwrap_or(0),
+                    output + payload_u64(&e.payload, "output_tokens").unwrap_or(0),
+                )
+            })
+    }
+
+    /// Wall-clock time between the first and last event of the turn.
+    pub fn duration_ms(&self) -> Option<i64> {
+        let first = DateTime::parse_from_rfc3339(&self.events.first()?.timestamp).ok()?;
+        let last = DateTime::parse_from_rfc3339(&self.events.last()?.timestamp).ok()?;
+        Some((last - first).num_milliseconds())
+    }
+
+    /// How the turn ended, as recorded by its last significant event.
+    pub fn outcome(&self) -> &'static str {
+        for event in self.events.iter().rev() {
+            match event.event_type.as_str() {
+                "turn_final_response" => return "final response",
+                "tool_loop_exhausted" => return "iterations exhausted",
+                "llm_response" if event.success == Some(false) => return "model error",
+                _ => {}
+            }
+        }
+        "incomplete"
+    }
+}
+
+/// Group events into turns by `turn_id`, ordered by each turn's first event.
+/// Events without a `turn_id` (channel bookkeeping) are skipped.
+pub fn group_turns(events: Vec<RuntimeTraceEvent>) -> Vec<TraceTurn> {
+    let mut turns: Vec<TraceTurn> = Vec::new();
+    for event in events {
+        let Some(turn_id) = event.turn_id.clone() else {
+            continue;
+        };
+        let index = match turns.iter().rposition(|t| t.turn_id == turn_id) {
+            Some(index) => index,
+            None => {
+                turns.push(TraceTurn {
+                    turn_id,
+                    channel: None,
+                    session: None,
+                    provider: None,
+                    model: None,
+                    started_at: event.timestamp.clone(),
+                    events: Vec::new(),
+                });
+                turns.len() - 1
+            }
+        };
+        let turn = &mut turns[index];
+        turn.channel = turn.channel.take().or_else(|| event.channel.clone());
+        turn.session = turn.session.take().or_else(|| event.session.clone());
+        turn.provider = turn.provider.take().or_else(|| event.provider.clone());
+        turn.model = turn.model.take().or_else(|| event.model.clone());
+        turn.events.push(event);
+    }
+    turns
+}
+
+/// Filters for `zeroclaw trace list`. Unset fields match every turn.
+#[derive(Debug, Clone, Default)]
+pub struct TurnFilter {
+    pub channel: Option<String>,
+    pub session: Option<String>,
+    pub tool: Option<String>,
+}
+
+impl TurnFilter {
+    pub fn matches(&self, turn: &TraceTurn) -> bool {
+        let channel_ok = self.channel.as_deref().is_none_or(|channel| {
+            turn.channel
+                .as_deref()
+                .is_some_and(|c| c.eq_ignore_ascii_case(channel))
+        });
+        let session_ok = self
+            .session
+            .as_deref()
+            .is_none_or(|session| turn.session.as_deref() == Some(session));
+        let tool_ok = self
+            .tool
+            .as_deref()
+            .is_none_or(|tool| turn.tool_names().contains(&tool));
+        channel_ok && session_ok && tool_ok
+    }
+}
+
+fn find_turn<'a>(turns: &'a [TraceTurn], turn_id: &str) -> Result<&'a TraceTurn> {
+    let turn_id = turn_id.trim();
+    if let Some(turn) = turns.iter().find(|t| t.turn_id == turn_id) {
+        return Ok(turn);
+    }
+    let candidates: Vec<&TraceTurn> = turns
+        .iter()
+        .filter(|t| !turn_id.is_empty() && t.turn_id.starts_with(turn_id))
+        .collect();
+    match candidates.as_slice() {
+        [turn] => Ok(turn),
+        [] => bail!("No traced turn matches '{turn_id}'"),
+        _ => bail!(
+            "Turn id prefix '{turn_id}' is ambiguous ({} matches)",
+            candidates.len()
+        ),
+    }
+}
+
+/// A tool call reconstructed from its start and result events.
+struct ToolNode<'a> {
+    name: String,
+    start: Option<&'a RuntimeTraceEvent>,
+    result: Option<&'a RuntimeTraceEvent>,
+}
+
+/// Events of one loop iteration: a model call and the tools it requested.
+struct IterationNode<'a> {
+    number: u64,
+    response: Option<&'a RuntimeTraceEvent>,
+    notes: Vec<&'a RuntimeTraceEvent>,
+    tools: Vec<ToolNode<'a>>,
+}
+
+fn build_iterations(turn: &TraceTurn) -> Vec<IterationNode<'_>> {
+    let mut iterations: Vec<IterationNode<'_>> = Vec::new();
+    for event in &turn.events {
+        let Some(number) = payload_u64(&event.payload, "iteration") else {
+            continue;
+        };
+        if iterations.last().is_none_or(|it| it.number != number) {
+            iterations.push(IterationNode {
+                number,
+                response: None,
+                notes: Vec::new(),
+                tools: Vec::new(),
+            });
+        }
+        let Some(iteration) = iterations.last_mut() else {
+            continue;
+        };
+        let tool = event
+            .payload
+            .get("tool")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        match event.event_type.as_str() {
+            "llm_response" => iteration.response = Some(event),
+            "tool_call_start" => iteration.tools.push(ToolNode {
+                name: tool.to_string(),
+                start: Some(event),
+                result: None,
+            }),
+            "tool_call_result" => {
+                match iteration
+                    .tools
+                    .iter_mut()
+                    .find(|t| t.name == tool && t.result.is_none())
+                {
+                    Some(node) => node.result = Some(event),
+                    // Denied, cancelled and deduplicated calls never start.
+                    None => iteration.tools.push(ToolNode {
+                        name: tool.to_string(),
+                        start: None,
+                        result: Some(event),
+                    }),
+                }
+            }
+            "llm_request" | "turn_final_response" => {}
+            _ => iteration.notes.push(event),
+        }
+    }
+    iterations
+}
+
+/// Render a turn as a tree of model calls with their tool calls nested below.
+pub fn render_turn_tree(turn: &TraceTurn) -> String {
+    let mut out = String::new();
+    let (input_tokens, output_tokens) = turn.token_totals();
+    let _ = writeln!(out, "Turn {}", turn.turn_id);
+    let _ = writeln!(
+        out,
+        "  channel: {} | session: {} | model: {}/{}",
+        turn.channel.as_deref().unwrap_or("-"),
+        turn.session.as_deref().unwrap_or("-"),
+        turn.provider.as_deref().unwrap_or("-"),
+        turn.model.as_deref().unwrap_or("-"),
+    );
+    let _ = writeln!(
+        out,
+        "  started: {} | duration: {} | tokens: {input_tokens} in / {output_tokens} out | {}",
+        turn.started_at,
+        format_duration(turn.duration_ms()),
+        turn.outcome(),
+    );
+
+    let mut lines: Vec<(String, Vec<String>)> = Vec::new();
+    for iteration in build_iterations(turn) {
+        let mut label = format!("model call #{}", iteration.number);
+        if let Some(response) = iteration.response {
+            let duration = payload_u64(&response.payload, "duration_ms");
+            let _ = write!(label, "  {}", format_duration(duration.map(u64_to_i64)));
+            if let (Some(input), Some(output)) = (
+                payload_u64(&response.payload, "input_tokens"),
+                payload_u64(&response.payload, "output_tokens"),
+            ) {
+                let _ = write!(label, "  tokens {input}/{output}");
+            }
+            if response.success == Some(false) {
+                let _ = write!(label, "  FAILED: {}", preview(response.message.as_deref()));
+            }
+        }
+
+        let mut children = Vec::new();
+        for note in iteration.notes {
+            children.push(format!(
+                "! {}: {}",
+                note.event_type,
+                preview(note.message.as_deref())
+            ));
+        }
+        for tool in iteration.tools {
+            children.push(render_tool(&tool));
+        }
+        lines.push((label, children));
+    }
+
+    for event in &turn.events {
+        match event.event_type.as_str() {
+            "turn_final_response" => {
+                let text = event.payload.get("text").and_then(Value::as_str);
+                lines.push((format!("final response: {}", preview(text)), Vec::new()));
+            }
+            "tool_loop_exhausted" => {
+                lines.push((
+                    format!("stopped: {}", preview(event.message.as_deref())),
+                    Vec::new(),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    for (i, (label, children)) in lines.iter().enumerate() {
+        let last = i + 1 == lines.len();
+        let _ = writeln!(out, "{}{label}", if last { "└─ " } else { "├─ " });
+        for (j, child) in children.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "{}{}{child}",
+                if last { "   " } else { "│  " },
+                if j + 1 == children.len() {
+                    "└─ "
+                } else {
+                    "├─ "
+                },
+            );
+        }
+    }
+    out
+}
+
+fn render_tool(tool: &ToolNode<'_>) -> String {
+    let mut line = format!("tool {}", tool.name);
+    let arguments = tool
+        .start
+        .or(tool.result)
+        .and_then(|e| e.payload.get("arguments"))
+        .and_then(Value::as_str);
+    if let Some(arguments) = arguments {
+        let _ = write!(line, " {}", truncate(arguments, 60));
+    }
+    match tool.result {
+        Some(result) => {
+            if let Some(duration) = payload_u64(&result.payload, "duration_ms") {
+                let _ = write!(line, "  {}", format_duration(Some(u64_to_i64(duration))));
+            }
+            if result.success == Some(false) {
+                let _ = write!(line, "  FAILED: {}", preview(result.message.as_deref()));
+            } else {
+                line.push_str("  ok");
+            }
+        }
+        None => line.push_str("  (no result recorded)"),
+    }
+    line
+}
+
+/// Build a trace fixture for a turn in the layout replayed by the
+/// integration test harness (`tests/support/trace.rs`).
+///
+/// Each successful model call becomes a step: a `tool_calls` step with the
+/// calls the agent executed, or a `text` step with the raw reply. Ids and
+/// timestamps are left out so the fixture is stable; `expects` pins the tool
+/// trail that was observed, and `source` records where the turn came from.
+pub fn turn_fixture(turn: &TraceTurn) -> Value {
+    let mut steps = Vec::new();
+    let mut tools_used: Vec<&str> = Vec::new();
+    let mut tool_call_count = 0_usize;
+    let mut all_tools_succeeded = true;
+
+    let iterations = build_iterations(turn);
+    for iteration in &iterations {
+        let Some(response) = iteration.response.filter(|r| r.success != Some(false)) else {
+            continue;
+        };
+        let input_tokens = payload_u64(&response.payload, "input_tokens").unwrap_or(0);
+        let output_tokens = payload_u64(&response.payload, "output_tokens").unwrap_or(0);
+
+        if iteration.tools.is_empty() {
+            let content = response
+                .payload
+                .get("raw_response")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            steps.push(json!({
+                "response": {
+                    "type": "text",
+                    "content": content,
+                    "input_tokens": input_tokens,
+                    "output_tokens": output_tokens,
+                }
+            }));
+            continue;
+        }
+
+        let mut tool_calls = Vec::new();
+        for tool in &iteration.tools {
+            tool_call_count += 1;
+            if !tools_used.contains(&tool.name.as_str()) {
+                tools_used.push(&tool.name);
+            }
+            all_tools_succeeded &= tool.result.and_then(|r| r.success) == Some(true);
+            let arguments = tool
+                .start
+                .or(tool.result)
+                .and_then(|e| e.payload.get("arguments"))
+                .and_then(Value::as_str)
+                .map_or_else(
+                    || json!({}),
+                    |raw| serde_json::from_str(raw).unwrap_or_else(|_| json!(raw)),
+                );
+            tool_calls.push(json!({
+                "id": format!("call_{tool_call_count}"),
+                "name": tool.name,
+                "arguments": arguments,
+            }));
+        }
+        steps.push(json!({
+            "response": {
+                "type": "tool_calls",
+                "tool_calls": tool_calls,
+                "input_tokens": input_tokens,
+                "output_tokens": output_tokens,
+            }
+        }));
+    }
+
+    let user_input = turn
+        .events
+        .iter()
+        .find_map(|e| e.payload.get("user_input").and_then(Value::as_str))
+        .unwrap_or_default();
+
+    json!({
+        "model_name": format!("trace-{}", turn.turn_id),
+        "turns": [{
+            "user_input": user_input,
+            "steps": steps,
+        }],
+        "expects": {
+            "tools_used": tools_used,
+            "max_tool_calls": tool_call_count,
+            "all_tools_succeeded": all_tools_succeeded,
+        },
+        "source": {
+            "turn_id": turn.turn_id,
+            "channel": turn.channel,
+            "session": turn.session,
+            "provider": turn.provider,
+            "model": turn.model,
+            "outcome": turn.outcome(),
+        },
+    })
+}
+
+fn payload_u64(payload: &Value, key: &str) -> Option<u64> {
+    payload.get(key).and_then(Value::as_u64)
+}
+
+fn u64_to_i64(value: u64) -> i64 {
+    i64::try_from(value).unwrap_or(i64::MAX)
+}
+
+fn format_duration(ms: Option<i64>) -> String {
+    match ms {
+        Some(ms) if ms >= 1000 => format!("{:.1}s", ms as f64 / 1000.0),
+        Some(ms) => format!("{ms}ms"),
+        None => "-".to_string(),
+    }
+}
+
+fn preview(text: Option<&str>) -> String {
+    truncate(text.unwrap_or_default(), 80)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    let flat = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    crate::util::truncate_with_ellipsis(&flat, max_chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(
+        turn_id: Option<&str>,
+        timestamp: &str,
+        event_type: &str,
+        success: Option<bool>,
+        message: Option<&str>,
+        payload: Value,
+    ) -> RuntimeTraceEvent {
+        RuntimeTraceEvent {
+            id: format!("{event_type}-{timestamp}"),
+            timestamp: timestamp.to_string(),
+            event_type: event_type.to_string(),
+            channel: Some("telegram".into()),
+            provider: Some("openrouter".into()),
+            model: Some("gpt-4o".into()),
+            turn_id: turn_id.map(str::to_string),
+            session: Some("telegram_alice".into()),
+            success,
+            message: message.map(str::to_string),
+            payload,
+        }
+    }
+
+    fn sample_events() -> Vec<RuntimeTraceEvent> {
+        let t = |s: u32| format!("2026-01-01T00:00:{s:02}Z");
+        vec![
+            event(
+                None,
+                &t(0),
+                "channel_message_inbound",
+                None,
+                None,
+                json!({}),
+            ),
+            event(
+                Some("turn-a"),
+                &t(1),
+                "llm_request",
+                None,
+                None,
+                json!({"iteration": 1}),
+            ),
+            event(
+                Some("turn-a"),
+                &t(2),
+                "llm_response",
+                Some(true),
+                None,
+                json!({
+                    "iteration": 1,
+                    "duration_ms": 900,
+                    "input_tokens": 100,
+                    "output_tokens": 20,
+                    "raw_response": "calling shell",
+                }),
+            ),
+            event(
+                Some("turn-a"),
+                &t(2),
+                "tool_call_start",
+                None,
+                None,
+                json!({"iteration": 1, "tool": "shell", "arguments": "{\"command\":\"ls\"}"}),
+            ),
+            event(
+                Some("turn-a"),
+                &t(3),
+                "tool_call_result",
+                Some(false),
+                Some("permission denied"),
+                json!({"iteration": 1, "tool": "shell", "duration_ms": 12, "output": ""}),
+            ),
+            event(
+                Some("turn-b"),
+                &t(4),
+                "llm_response",
+                Some(true),
+                None,
+                json!({"iteration": 1, "raw_response": "hi"}),
+            ),
+            event(
+                Some("turn-a"),
+                &t(5),
+                "llm_response",
+                Some(true),
+                None,
+                json!({
+                    "iteration": 2,
+                    "duration_ms": 1500,
+                    "input_tokens": 150,
+                    "output_tokens": 30,
+                    "raw_response": "done",
+                }),
+            ),
+            event(
+                Some("turn-a"),
+                &t(6),
+                "turn_final_response",
+                Some(true),
+                None,
+                json!({"iteration": 2, "text": "done"}),
+            ),
+        ]
+    }
+
+    #[test]
+    fn group_turns_collects_events_by_turn_id() {
+        let turns = group_turns(sample_events());
+        assert_eq!(turns.len(), 2);
+        let turn = &turns[0];
+        assert_eq!(turn.turn_id, "turn-a");
+        assert_eq!(turn.events.len(), 6);
+        assert_eq!(turn.session.as_deref(), Some("telegram_alice"));
+        assert_eq!(turn.model_calls(), 2);
+        assert_eq!(turn.token_totals(), (250, 50));
+        assert_eq!(turn.duration_ms(), Some(5000));
+        assert_eq!(turn.tool_names(), ["shell"]);
+        assert_eq!(turn.outcome(), "final response");
+        assert_eq!(turns[1].outcome(), "incomplete");
+    }
+
+    #[test]
+    fn turn_filter_matches_channel_session_and_tool() {
+        let turns = group_turns(sample_events());
+        let by_tool = TurnFilter {
+            tool: Some("shell".into()),
+            ..TurnFilter::default()
+        };
+        assert!(by_tool.matches(&turns[0]));
+        assert!(!by_tool.matches(&turns[1]));
+
+        let by_channel = TurnFilter {
+            channel: Some("Telegram".into()),
+            session: Some("telegram_alice".into()),
+            ..TurnFilter::default()
+        };
+        assert!(turns.iter().all(|t| by_channel.matches(t)));
+
+        let other_session = TurnFilter {
+            session: Some("telegram_bob".into()),
+            ..TurnFilter::default()
+        };
+        assert!(!other_session.matches(&turns[0]));
+    }
+
+    #[test]
+    fn find_turn_accepts_unique_prefix() {
+        let turns = group_turns(sample_events());
+        assert_eq!(find_turn(&turns, "turn-b").unwrap().turn_id, "turn-b");
+        assert_eq!(find_turn(&turns, "turn-a").unwrap().turn_id, "turn-a");
+        assert!(find_turn(&turns, "turn-")
+            .unwrap_err()
+            .to_string()
+            .contains("ambiguous"));
+        assert!(find_turn(&turns, "nope").is_err());
+    }
+
+    #[test]
+    fn render_turn_tree_nests_tools_under_model_calls() {
+        let turns = group_turns(sample_events());
+        let tree = render_turn_tree(&turns[0]);
+        assert!(tree.contains("tokens: 250 in / 50 out | final response"));
+        assert!(tree.contains("├─ model call #1  900ms  tokens 100/20"));
+        assert!(
+            tree.contains("│  └─ tool shell {\"command\":\"ls\"}  12ms  FAILED: permission denied")
+        );
+        assert!(tree.contains("├─ model call #2  1.5s  tokens 150/30"));
+        assert!(tree.ends_with("└─ final response: done\n"));
+    }
+
+    #[test]
+    fn turn_fixture_matches_trace_harness_layout() {
+        let mut events = sample_events();
+        events[1].payload["user_input"] = json!("list my files");
+        let turns = group_turns(events);
+        let fixture = turn_fixture(&turns[0]);
+
+        assert_eq!(fixture["model_name"], "trace-turn-a");
+        let turn = &fixture["turns"][0];
+        assert_eq!(turn["user_input"], "list my files");
+        let steps = turn["steps"].as_array().unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0]["response"]["type"], "tool_calls");
+        assert_eq!(steps[0]["response"]["input_tokens"], 100);
+        let call = &steps[0]["response"]["tool_calls"][0];
+        assert_eq!(call["id"], "call_1");
+        assert_eq!(call["name"], "shell");
+        assert_eq!(call["arguments"]["command"], "ls");
+        assert_eq!(steps[1]["response"]["type"], "text");
+        assert_eq!(steps[1]["response"]["content"], "done");
+
+        assert_eq!(fixture["expects"]["tools_used"], json!(["shell"]));
+        assert_eq!(fixture["expects"]["max_tool_calls"], 1);
+        assert_eq!(fixture["expects"]["all_tools_succeeded"], false);
+        assert_eq!(fixture["source"]["session"], "telegram_alice");
+    }
+}
@@ -49,9 +49,23 @@ pub enum ObserverEvent {
         tool: String,
         duration: Duration,
         success: bool,
+        /// Truncated, credential-scrubbed tool output or error, when available.
+        output: Option<String>,
     },
+    /// A tool call is waiting for operator or sender approval.
+    ApprovalRequested {
+        tool: String,
+        arguments: Option<String>,
+    },
+    /// The agent started processing a user message.
+    TurnStart { provider: String, model: String },
     /// The agent produced a final answer for the current user message.
     TurnComplete,
+    /// The agent gave up on the current user message.
+    TurnFailed {
+        /// Human-readable error description. Must not contain secrets or tokens.
+        error: String,
+    },
     /// A message was sent or received through a channel.
     ChannelMessage {
         /// Channel name (e.g., `"telegram"`, `"discord"`).
@@ -226,6 +240,7 @@ mod tests {
             tool: "shell".into(),
             duration: Duration::from_millis(10),
             success: true,
+            output: None,
         };
         let metric = ObserverMetric::RequestLatency(Duration::from_millis(8));
 
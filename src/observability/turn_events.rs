@@ -0,0 +1,353 @@
+//! Typed agent turn lifecycle stream for external subscribers.
+//!
+//! [`TurnEventObserver`] wraps the configured observer and republishes the
+//! [`ObserverEvent`]s it receives as [`TurnEvent`]s on a process-wide
+//! broadcast bus, tagged with the turn, session and channel they belong to.
+//! The gateway serves the bus as SSE (`/api/events/turns`) and WebSocket
+//! (`/ws/events`) streams.
+
+use super::runtime_trace;
+use super::traits::{Observer, ObserverEvent, ObserverMetric};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::LazyLock;
+use std::time::Instant;
+use tokio::sync::broadcast;
+
+/// Buffered events per subscriber before slow subscribers start lagging.
+const TURN_EVENT_CAPACITY: usize = 512;
+
+static TURN_EVENTS: LazyLock<broadcast::Sender<TurnEvent>> =
+    LazyLock::new(|| broadcast::channel(TURN_EVENT_CAPACITY).0);
+
+tokio::task_local! {
+    static CURRENT_TURN: TurnContext;
+}
+
+/// Identity of the agent turn being processed by the current task.
+#[derive(Debug, Clone)]
+pub struct TurnContext {
+    pub turn_id: String,
+    pub channel: String,
+    started_at: Instant,
+}
+
+impl TurnContext {
+    pub fn new(turn_id: impl Into<String>, channel: impl Into<String>) -> Self {
+        Self {
+            turn_id: turn_id.into(),
+            channel: channel.into(),
+            started_at: Instant::now(),
+        }
+    }
+}
+
+/// Run `future` as agent turn `turn`: observer events it records are
+/// published with the turn's id and channel.
+pub async fn with_turn<F: Future>(turn: TurnContext, future: F) -> F::Output {
+    CURRENT_TURN.scope(turn, future).await
+}
+
+/// Turn set by the enclosing [`with_turn`] scope, if any.
+pub fn current_turn() -> Option<TurnContext> {
+    CURRENT_TURN.try_with(Clone::clone).ok()
+}
+
+/// One event on the turn stream.
+#[derive(Debug, Clone, Serialize)]
+pub struct TurnEvent {
+    pub turn_id: Option<String>,
+    pub session: Option<String>,
+    pub channel: Option<String>,
+    pub timestamp: String,
+    #[serde(flatten)]
+    pub kind: TurnEventKind,
+}
+
+/// What happened, serialized as `{"type": "<snake_case>", ...}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TurnEventKind {
+    TurnStarted {
+        provider: String,
+        model: String,
+    },
+    ModelCall {
+        provider: String,
+        model: String,
+        duration_ms: u64,
+        success: bool,
+        error: Option<String>,
+        input_tokens: Option<u64>,
+        output_tokens: Option<u64>,
+    },
+    ToolCallStarted {
+        tool: String,
+        arguments: Option<String>,
+    },
+    ToolCallFinished {
+        tool: String,
+        duration_ms: u64,
+        success: bool,
+        result: Option<String>,
+    },
+    ApprovalRequested {
+        tool: String,
+        arguments: Option<String>,
+    },
+    CostDelta {
+        model: String,
+        input_tokens: u64,
+        output_tokens: u64,
+        cost_usd: f64,
+    },
+    TurnFinished {
+        success: bool,
+        duration_ms: Option<u64>,
+        error: Option<String>,
+    },
+}
+
+/// Subscriber-side filter; unset fields match everything.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TurnEventFilter {
+    pub session: Option<String>,
+    pub channel: Option<String>,
+}
+
+impl TurnEventFilter {
+    pub fn matches(&self, event: &TurnEvent) -> bool {
+        let field_matches = |wanted: &Option<String>, actual: &Option<String>| {
+            wanted
+                .as_ref()
+                .is_none_or(|wanted| actual.as_ref() == Some(wanted))
+        };
+        field_matches(&self.session, &event.session) && field_matches(&self.channel, &event.channel)
+    }
+}
+
+/// Subscribe to the process-wide turn stream.
+pub fn subscribe() -> broadcast::Receiver<TurnEvent> {
+    TURN_EVENTS.subscribe()
+}
+
+/// Publish the turn stream view of `event`, if it has one.
+pub fn publish(event: &ObserverEvent) {
+    if TURN_EVENTS.receiver_count() == 0 {
+        return;
+    }
+    let turn = current_turn();
+    for kind in turn_event_kinds(event, turn.as_ref()) {
+        let _ = TURN_EVENTS.send(TurnEvent {
+            turn_id: turn.as_ref().map(|turn| turn.turn_id.clone()),
+            session: runtime_trace::current_session(),
+            channel: turn.as_ref().map(|turn| turn.channel.clone()),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            kind,
+        });
+    }
+}
+
+fn millis(duration: std::time::Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn turn_event_kinds(event: &ObserverEvent, turn: Option<&TurnContext>) -> Vec<TurnEventKind> {
+    let turn_duration = || turn.map(|turn| millis(turn.started_at.elapsed()));
+    match event {
+        ObserverEvent::TurnStart { provider, model } => vec![TurnEventKind::TurnStarted {
+            provider: provider.clone(),
+            model: model.clone(),
+        }],
+        ObserverEvent::LlmResponse {
+            provider,
+            model,
+            duration,
+            success,
+            error_message,
+            input_tokens,
+            output_tokens,
+        } => {
+            let mut kinds = vec![TurnEventKind::ModelCall {
+                provider: provider.clone(),
+                model: model.clone(),
+                duration_ms: millis(*duration),
+                success: *success,
+                error: error_message.clone(),
+                input_tokens: *input_tokens,
+                output_tokens: *output_tokens,
+            }];
+            if let (Some(input_tokens), Some(output_tokens)) = (input_tokens, output_tokens) {
+                if let Some(usage) =
+                    crate::cost::scope::price_llm_call(model, *input_tokens, *output_tokens)
+                {
+                    kinds.push(TurnEventKind::CostDelta {
+                        model: usage.model,
+                        input_tokens: usage.input_tokens,
+                        output_tokens: usage.output_tokens,
+                        cost_usd: usage.cost_usd,
+                    });
+                }
+            }
+            kinds
+        }
+        ObserverEvent::ToolCallStart { tool, arguments } => {
+            vec![TurnEventKind::ToolCallStarted {
+                tool: tool.clone(),
+                arguments: arguments.clone(),
+            }]
+        }
+        ObserverEvent::ToolCall {
+            tool,
+            duration,
+            success,
+            output,
+        } => vec![TurnEventKind::ToolCallFinished {
+            tool: tool.clone(),
+            duration_ms: millis(*duration),
+            success: *success,
+            result: output.clone(),
+        }],
+        ObserverEvent::ApprovalRequested { tool, arguments } => {
+            vec![TurnEventKind::ApprovalRequested {
+                tool: tool.clone(),
+                arguments: arguments.clone(),
+            }]
+        }
+        ObserverEvent::TurnComplete => vec![TurnEventKind::TurnFinished {
+            success: true,
+            duration_ms: turn_duration(),
+            error: None,
+        }],
+        ObserverEvent::TurnFailed { error } => vec![TurnEventKind::TurnFinished {
+            success: false,
+            duration_ms: turn_duration(),
+            error: Some(error.clone()),
+        }],
+        _ => Vec::new(),
+    }
+}
+
+/// Observer wrapper that publishes every event it forwards to the turn
+/// stream. Transparent to `name()` and `as_any()` so backend downcasts
+/// (e.g. the Prometheus `/metrics` handler) keep working.
+pub struct TurnEventObserver {
+    inner: Box<dyn Observer>,
+}
+
+impl TurnEventObserver {
+    pub fn new(inner: Box<dyn Observer>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Observer for TurnEventObserver {
+    fn record_event(&self, event: &ObserverEvent) {
+        self.inner.record_event(event);
+        publish(event);
+    }
+
+    fn record_metric(&self, metric: &ObserverMetric) {
+        self.inner.record_metric(metric);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self.inner.as_any()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::NoopObserver;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn observer_events_are_published_with_turn_and_session() {
+        let mut rx = subscribe();
+        let observer = TurnEventObserver::new(Box::new(NoopObserver));
+        let turn = TurnContext::new("turn-events-test", "telegram");
+
+        runtime_trace::with_session(
+            "telegram_alice".into(),
+            with_turn(turn, async {
+                observer.record_event(&ObserverEvent::TurnStart {
+                    provider: "openrouter".into(),
+                    model: "m".into(),
+                });
+                observer.record_event(&ObserverEvent::ToolCall {
+                    tool: "shell".into(),
+                    duration: Duration::from_millis(12),
+                    success: true,
+                    output: Some("ok".into()),
+                });
+                observer.record_event(&ObserverEvent::HeartbeatTick);
+                observer.record_event(&ObserverEvent::TurnComplete);
+            }),
+        )
+        .await;
+
+        // Other tests may publish concurrently; keep only this turn's events.
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if event.turn_id.as_deref() == Some("turn-events-test") {
+                events.push(event);
+            }
+        }
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].session.as_deref(), Some("telegram_alice"));
+        assert_eq!(events[0].channel.as_deref(), Some("telegram"));
+
+        let json = serde_json::to_value(&events[1]).unwrap();
+        assert_eq!(json["type"], "tool_call_finished");
+        assert_eq!(json["tool"], "shell");
+        assert_eq!(json["result"], "ok");
+        assert_eq!(json["duration_ms"], 12);
+
+        let json = serde_json::to_value(&events[2]).unwrap();
+        assert_eq!(json["type"], "turn_finished");
+        assert_eq!(json["success"], true);
+    }
+
+    #[test]
+    fn filter_matches_session_and_channel() {
+        let event = TurnEvent {
+            turn_id: None,
+            session: Some("slack_bob".into()),
+            channel: Some("slack".into()),
+            timestamp: String::new(),
+            kind: TurnEventKind::TurnFinished {
+                success: true,
+                duration_ms: None,
+                error: None,
+            },
+        };
+
+        assert!(TurnEventFilter::default().matches(&event));
+        let by_channel = TurnEventFilter {
+            channel: Some("slack".into()),
+            ..Default::default()
+        };
+        assert!(by_channel.matches(&event));
+        let other_session = TurnEventFilter {
+            session: Some("slack_alice".into()),
+            channel: Some("slack".into()),
+        };
+        assert!(!other_session.matches(&event));
+    }
+
+    #[test]
+    fn wrapper_is_transparent_to_name_and_downcast() {
+        let observer = TurnEventObserver::new(Box::new(NoopObserver));
+        assert_eq!(observer.name(), "noop");
+        assert!(observer.as_any().downcast_ref::<NoopObserver>().is_some());
+    }
+}
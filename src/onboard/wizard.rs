@@ -128,6 +128,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
     let config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        active_profile: None,
         api_key: if api_key.is_empty() {
             None
         } else {
@@ -142,6 +143,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         default_temperature: 0.7,
         provider_timeout_secs: 120,
         extra_headers: std::collections::HashMap::new(),
+        llama_server: crate::config::LlamaServerConfig::default(),
         observability: ObservabilityConfig::default(),
         autonomy: AutonomyConfig::default(),
         backup: crate::config::BackupConfig::default(),
@@ -160,6 +162,7 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        sop: crate::config::SopConfig::default(),
         channels_config,
         memory: memory_config, // User-selected memory backend
         storage: StorageConfig::default(),
@@ -184,12 +187,14 @@ pub async fn run_wizard(force: bool) -> Result<Config> {
         peripherals: crate::config::PeripheralsConfig::default(),
         agents: std::collections::HashMap::new(),
         swarms: std::collections::HashMap::new(),
+        profiles: std::collections::HashMap::new(),
         hooks: crate::config::HooksConfig::default(),
         hardware: hardware_config,
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
         tts: crate::config::TtsConfig::default(),
         mcp: crate::config::McpConfig::default(),
+        app_mcp: crate::config::AppMcpConfig::default(),
         nodes: crate::config::NodesConfig::default(),
         workspace: crate::config::WorkspaceConfig::default(),
         notion: crate::config::NotionConfig::default(),
@@ -396,12 +401,14 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         archive_after_days: if profile.uses_sqlite_hygiene { 7 } else { 0 },
         purge_after_days: if profile.uses_sqlite_hygiene { 30 } else { 0 },
         conversation_retention_days: 30,
+        importance_half_life_days: 0,
         embedding_provider: "none".to_string(),
         embedding_model: "text-embedding-3-small".to_string(),
         embedding_dimensions: 1536,
         vector_weight: 0.7,
         keyword_weight: 0.3,
         min_relevance_score: 0.4,
+        recency_half_life_days: 30,
         embedding_cache_size: if profile.uses_sqlite_hygiene {
             10000
         } else {
@@ -416,6 +423,8 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         snapshot_on_hygiene: false,
         auto_hydrate: true,
         sqlite_open_timeout_secs: None,
+        vector_index: "flat".to_string(),
+        hnsw: crate::config::HnswConfig::default(),
         qdrant: crate::config::QdrantConfig::default(),
     }
 }
@@ -506,6 +515,7 @@ async fn run_quick_setup_with_home(
     let config = Config {
         workspace_dir: workspace_dir.clone(),
         config_path: config_path.clone(),
+        active_profile: None,
         api_key: credential_override.map(|c| {
             let mut s = String::with_capacity(c.len());
             s.push_str(c);
@@ -520,6 +530,7 @@ async fn run_quick_setup_with_home(
         default_temperature: 0.7,
         provider_timeout_secs: 120,
         extra_headers: std::collections::HashMap::new(),
+        llama_server: crate::config::LlamaServerConfig::default(),
         observability: ObservabilityConfig::default(),
         autonomy: AutonomyConfig::default(),
         backup: crate::config::BackupConfig::default(),
@@ -538,6 +549,7 @@ async fn run_quick_setup_with_home(
         embedding_routes: Vec::new(),
         heartbeat: HeartbeatConfig::default(),
         cron: crate::config::CronConfig::default(),
+        sop: crate::config::SopConfig::default(),
         channels_config: ChannelsConfig::default(),
         memory: memory_config,
         storage: StorageConfig::default(),
@@ -562,12 +574,14 @@ async fn run_quick_setup_with_home(
         peripherals: crate::config::PeripheralsConfig::default(),
         agents: std::collections::HashMap::new(),
         swarms: std::collections::HashMap::new(),
+        profiles: std::collections::HashMap::new(),
         hooks: crate::config::HooksConfig::default(),
         hardware: crate::config::HardwareConfig::default(),
         query_classification: crate::config::QueryClassificationConfig::default(),
         transcription: crate::config::TranscriptionConfig::default(),
         tts: crate::config::TtsConfig::default(),
         mcp: crate::config::McpConfig::default(),
+        app_mcp: crate::config::AppMcpConfig::default(),
         nodes: crate::config::NodesConfig::default(),
         workspace: crate::config::WorkspaceConfig::default(),
         notion: crate::config::NotionConfig::default(),
@@ -0,0 +1,116 @@
+//! Board-originated peripheral events.
+//!
+//! Serial boards may interleave unsolicited `{"event": "<signal>", ...}`
+//! frames with their command responses. The per-board serial reader
+//! publishes them here; the SOP runtime subscribes and forwards each one to
+//! `dispatch_peripheral_signal` so `peripheral` triggers can fire.
+
+use serde_json::Value;
+use std::sync::LazyLock;
+use tokio::sync::broadcast;
+
+/// Buffered events per subscriber before slow subscribers start lagging.
+const PERIPHERAL_EVENT_CAPACITY: usize = 256;
+
+static PERIPHERAL_EVENTS: LazyLock<broadcast::Sender<PeripheralEvent>> =
+    LazyLock::new(|| broadcast::channel(PERIPHERAL_EVENT_CAPACITY).0);
+
+/// One event reported by a board, e.g. a button press or sensor threshold.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeripheralEvent {
+    /// Configured board name (`[[peripherals.boards]].board`).
+    pub board: String,
+    /// Event name from the frame's `event` field (e.g. `"button"`, `"pin_2"`).
+    pub signal: String,
+    /// Scalar `value` as text, or the `data` object as JSON.
+    pub payload: Option<String>,
+}
+
+impl PeripheralEvent {
+    /// Parse an event frame. Returns `None` for anything that is not an
+    /// event (command responses, malformed frames).
+    ///
+    /// Accepted shapes:
+    /// - `{"event":"button","value":1}` → payload `"1"`
+    /// - `{"event":"temp","value":"high"}` → payload `"high"`
+    /// - `{"event":"imu","data":{"x":0.1}}` → payload `{"x":0.1}`
+    pub fn from_frame(board: &str, frame: &Value) -> Option<Self> {
+        let signal = frame.get("event")?.as_str()?.trim();
+        if signal.is_empty() {
+            return None;
+        }
+        let payload = match (frame.get("value"), frame.get("data")) {
+            (Some(Value::String(s)), _) => Some(s.clone()),
+            (Some(value), _) if !value.is_null() => Some(value.to_string()),
+            (_, Some(data)) => Some(data.to_string()),
+            _ => None,
+        };
+        Some(Self {
+            board: board.to_string(),
+            signal: signal.to_string(),
+            payload,
+        })
+    }
+}
+
+/// Subscribe to events from every connected board.
+pub fn subscribe() -> broadcast::Receiver<PeripheralEvent> {
+    PERIPHERAL_EVENTS.subscribe()
+}
+
+/// Publish an event. Dropped silently when nothing is subscribed.
+pub fn publish(event: PeripheralEvent) {
+    let _ = PERIPHERAL_EVENTS.send(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn from_frame_parses_scalar_and_structured_events() {
+        let press =
+            PeripheralEvent::from_frame("nucleo", &json!({"event":"button","value":1})).unwrap();
+        assert_eq!(press.board, "nucleo");
+        assert_eq!(press.signal, "button");
+        assert_eq!(press.payload.as_deref(), Some("1"));
+
+        let level =
+            PeripheralEvent::from_frame("uno", &json!({"event":"temp","value":"high"})).unwrap();
+        assert_eq!(level.payload.as_deref(), Some("high"));
+
+        let imu =
+            PeripheralEvent::from_frame("esp32", &json!({"event":"imu","data":{"x":0.5}})).unwrap();
+        let data: Value = serde_json::from_str(imu.payload.as_deref().unwrap()).unwrap();
+        assert_eq!(data["x"], 0.5);
+
+        let bare = PeripheralEvent::from_frame("uno", &json!({"event":"boot"})).unwrap();
+        assert!(bare.payload.is_none());
+    }
+
+    #[test]
+    fn from_frame_ignores_responses_and_malformed_frames() {
+        assert!(PeripheralEvent::from_frame("uno", &json!({"id":"1","ok":true})).is_none());
+        assert!(PeripheralEvent::from_frame("uno", &json!({"event":""})).is_none());
+        assert!(PeripheralEvent::from_frame("uno", &json!({"event":3})).is_none());
+    }
+
+    #[tokio::test]
+    async fn published_events_reach_subscribers() {
+        let mut rx = subscribe();
+        publish(PeripheralEvent {
+            board: "bus-test".into(),
+            signal: "pin_2".into(),
+            payload: Some("0".into()),
+        });
+        // Other tests may publish concurrently; skip until ours arrives.
+        loop {
+            let event = rx.recv().await.unwrap();
+            if event.board == "bus-test" {
+                assert_eq!(event.signal, "pin_2");
+                break;
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@
 //! Peripherals extend the agent with physical capabilities. See
 //! `docs/hardware-peripherals-design.md` for the full design.
 
+pub mod events;
 pub mod traits;
 
 #[cfg(feature = "hardware")]
@@ -95,6 +96,7 @@ pub async fn handle_command(cmd: crate::PeripheralCommands, config: &Config) ->
                 transport: transport.to_string(),
                 path: path_opt,
                 baud: 115_200,
+                watch_pins: Vec::new(),
             });
             cfg.save().await?;
             println!("Added {} at {}. Restart daemon to apply.", board, path);
@@ -248,8 +250,10 @@ mod tests {
                 transport: "serial".into(),
                 path: Some("/dev/ttyACM0".into()),
                 baud: 115_200,
+                watch_pins: Vec::new(),
             }],
             datasheet_dir: None,
+            datasheet_embeddings: false,
         };
         let result = list_configured_boards(&config);
         assert!(
@@ -268,15 +272,18 @@ mod tests {
                     transport: "serial".into(),
                     path: Some("/dev/ttyACM0".into()),
                     baud: 115_200,
+                    watch_pins: Vec::new(),
                 },
                 PeripheralBoardConfig {
                     board: "rpi-gpio".into(),
                     transport: "native".into(),
                     path: None,
                     baud: 115_200,
+                    watch_pins: Vec::new(),
                 },
             ],
             datasheet_dir: None,
+            datasheet_embeddings: false,
         };
         let result = list_configured_boards(&config);
         assert_eq!(result.len(), 2);
@@ -290,6 +297,7 @@ mod tests {
             enabled: true,
             boards: vec![],
             datasheet_dir: None,
+            datasheet_embeddings: false,
         };
         let result = list_configured_boards(&config);
         assert!(
@@ -304,6 +312,7 @@ mod tests {
             enabled: false,
             boards: vec![],
             datasheet_dir: None,
+            datasheet_embeddings: false,
         };
         let tools = create_peripheral_tools(&config).await.unwrap();
         assert!(
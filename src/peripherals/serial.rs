@@ -3,17 +3,21 @@
 //! Protocol: newline-delimited JSON.
 //! Request:  {"id":"1","cmd":"gpio_write","args":{"pin":13,"value":1}}
 //! Response: {"id":"1","ok":true,"result":"done"}
+//! Event:    {"event":"button","value":1}  (unsolicited, see `events`)
 
 use crate::config::PeripheralBoardConfig;
+use crate::peripherals::events::{self, PeripheralEvent};
 use crate::peripherals::Peripheral;
 use crate::tools::traits::{Tool, ToolResult};
 use async_trait::async_trait;
 use portable_atomic::{AtomicU64, Ordering};
 use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::sync::Mutex;
-use tokio_serial::{SerialPortBuilderExt, SerialStream};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::{oneshot, Mutex};
+use tokio::task::JoinHandle;
+use tokio_serial::SerialPortBuilderExt;
 
 /// Allowed serial path patterns (security: deny arbitrary paths).
 const ALLOWED_PATH_PREFIXES: &[&str] = &[
@@ -30,53 +34,129 @@ fn is_path_allowed(path: &str) -> bool {
     ALLOWED_PATH_PREFIXES.iter().any(|p| path.starts_with(p))
 }
 
-/// JSON request/response over serial.
-async fn send_request(port: &mut SerialStream, cmd: &str, args: Value) -> anyhow::Result<Value> {
-    static ID: AtomicU64 = AtomicU64::new(0);
-    let id = ID.fetch_add(1, Ordering::Relaxed);
-    let id_str = id.to_string();
+/// Requests awaiting a response, keyed by request id.
+type PendingResponses = Arc<parking_lot::Mutex<HashMap<String, oneshot::Sender<Value>>>>;
 
-    let req = json!({
-        "id": id_str,
-        "cmd": cmd,
-        "args": args
-    });
-    let line = format!("{}\n", req);
+/// Removes a request's pending entry when it completes, fails or times out.
+struct PendingSlot<'a> {
+    pending: &'a PendingResponses,
+    id: String,
+}
 
-    port.write_all(line.as_bytes()).await?;
-    port.flush().await?;
+impl Drop for PendingSlot<'_> {
+    fn drop(&mut self) {
+        self.pending.lock().remove(&self.id);
+    }
+}
 
+/// Read newline-delimited frames from a board until the port closes.
+///
+/// Responses go to the request waiting on their `id`; `{"event": ...}` frames
+/// are published on the peripheral event bus.
+async fn read_frames<R: AsyncRead + Unpin>(board: String, reader: R, pending: PendingResponses) {
+    let mut reader = BufReader::new(reader);
     let mut buf = Vec::new();
-    let mut b = [0u8; 1];
-    while port.read_exact(&mut b).await.is_ok() {
-        if b[0] == b'\n' {
-            break;
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf).await {
+            Ok(0) => break,
+            Ok(_) => route_frame(&board, &String::from_utf8_lossy(&buf), &pending),
+            Err(e) => {
+                tracing::warn!(board = %board, "Serial read failed: {e}");
+                break;
+            }
         }
-        buf.push(b[0]);
     }
-    let line_str = String::from_utf8_lossy(&buf);
-    let resp: Value = serde_json::from_str(line_str.trim())?;
-    let resp_id = resp["id"].as_str().unwrap_or("");
-    if resp_id != id_str {
-        anyhow::bail!("Response id mismatch: expected {}, got {}", id_str, resp_id);
+    // Fail outstanding requests now rather than at their timeout
+    pending.lock().clear();
+}
+
+fn route_frame(board: &str, line: &str, pending: &PendingResponses) {
+    let line = line.trim();
+    if line.is_empty() {
+        return;
+    }
+    let Ok(frame) = serde_json::from_str::<Value>(line) else {
+        tracing::debug!(board, "Ignoring non-JSON serial line: {line}");
+        return;
+    };
+    if let Some(event) = PeripheralEvent::from_frame(board, &frame) {
+        tracing::debug!(board, signal = %event.signal, "Peripheral event received");
+        events::publish(event);
+        return;
+    }
+    let id = frame["id"].as_str().unwrap_or_default();
+    match pending.lock().remove(id) {
+        Some(tx) => {
+            let _ = tx.send(frame);
+        }
+        None => tracing::debug!(board, "Dropping serial response with unknown id {id:?}"),
     }
-    Ok(resp)
 }
 
 /// Shared serial transport for tools. Pub(crate) for capabilities tool.
+///
+/// A background task owns the read half of the port and demultiplexes
+/// command responses from board-originated events.
 pub(crate) struct SerialTransport {
-    port: Mutex<SerialStream>,
+    writer: Mutex<Box<dyn AsyncWrite + Send + Unpin>>,
+    pending: PendingResponses,
+    reader: JoinHandle<()>,
 }
 
 /// Timeout for serial request/response (seconds).
 const SERIAL_TIMEOUT_SECS: u64 = 5;
 
 impl SerialTransport {
+    /// Split `stream` and start the frame reader for `board`.
+    fn spawn<S>(board: &str, stream: S) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (reader, writer) = tokio::io::split(stream);
+        let pending = PendingResponses::default();
+        let reader = tokio::spawn(read_frames(board.to_string(), reader, pending.clone()));
+        Self {
+            writer: Mutex::new(Box::new(writer)),
+            pending,
+            reader,
+        }
+    }
+
+    /// JSON request/response over serial.
+    async fn send_request(&self, cmd: &str, args: Value) -> anyhow::Result<Value> {
+        static ID: AtomicU64 = AtomicU64::new(0);
+        let id = ID.fetch_add(1, Ordering::Relaxed);
+        let id_str = id.to_string();
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().insert(id_str.clone(), tx);
+        let _slot = PendingSlot {
+            pending: &self.pending,
+            id: id_str.clone(),
+        };
+
+        let req = json!({
+            "id": id_str,
+            "cmd": cmd,
+            "args": args
+        });
+        let line = format!("{}\n", req);
+
+        {
+            let mut writer = self.writer.lock().await;
+            writer.write_all(line.as_bytes()).await?;
+            writer.flush().await?;
+        }
+
+        rx.await
+            .map_err(|_| anyhow::anyhow!("Serial port closed before response to request {id_str}"))
+    }
+
     async fn request(&self, cmd: &str, args: Value) -> anyhow::Result<ToolResult> {
-        let mut port = self.port.lock().await;
         let resp = tokio::time::timeout(
             std::time::Duration::from_secs(SERIAL_TIMEOUT_SECS),
-            send_request(&mut port, cmd, args),
+            self.send_request(cmd, args),
         )
         .await
         .map_err(|_| {
@@ -103,6 +183,12 @@ impl SerialTransport {
     }
 }
 
+impl Drop for SerialTransport {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
 /// Serial peripheral for STM32, Arduino, etc. over USB CDC.
 pub struct SerialPeripheral {
     name: String,
@@ -111,8 +197,7 @@ pub struct SerialPeripheral {
 }
 
 impl SerialPeripheral {
-    /// Create and connect to a serial peripheral.
-    #[allow(clippy::unused_async)]
+    /// Create and connect to a serial peripheral, arming `watch_pins`.
     pub async fn connect(config: &PeripheralBoardConfig) -> anyhow::Result<Self> {
         let path = config
             .path
@@ -131,9 +216,23 @@ impl SerialPeripheral {
             .map_err(|e| anyhow::anyhow!("Failed to open {}: {}", path, e))?;
 
         let name = format!("{}-{}", config.board, path.replace('/', "_"));
-        let transport = Arc::new(SerialTransport {
-            port: Mutex::new(port),
-        });
+        let transport = Arc::new(SerialTransport::spawn(&config.board, port));
+
+        // Older firmware answers "Unknown command"; the board still works
+        // for tools, it just cannot report pin changes.
+        for pin in &config.watch_pins {
+            match transport.request("gpio_watch", json!({ "pin": pin })).await {
+                Ok(r) if r.success => {}
+                Ok(r) => tracing::warn!(
+                    board = %config.board,
+                    "gpio_watch on pin {pin} rejected: {}",
+                    r.error.unwrap_or_default()
+                ),
+                Err(e) => {
+                    tracing::warn!(board = %config.board, "gpio_watch on pin {pin} failed: {e}")
+                }
+            }
+        }
 
         Ok(Self {
             name: name.clone(),
@@ -273,3 +372,63 @@ impl Tool for GpioWriteTool {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reader_routes_responses_by_id_and_publishes_events() {
+        let (host, board) = tokio::io::duplex(1024);
+        let transport = SerialTransport::spawn("demux-test", host);
+        let mut events = events::subscribe();
+
+        let (board_rx, mut board_tx) = tokio::io::split(board);
+        let device = tokio::spawn(async move {
+            let mut lines = BufReader::new(board_rx).lines();
+            let line = lines.next_line().await.unwrap().unwrap();
+            let req: Value = serde_json::from_str(&line).unwrap();
+            assert_eq!(req["cmd"], "gpio_read");
+            // Event and noise arrive before the response
+            board_tx
+                .write_all(b"{\"event\":\"button\",\"value\":1}\nboot banner\n")
+                .await
+                .unwrap();
+            let resp = json!({"id": req["id"], "ok": true, "result": "1"});
+            board_tx
+                .write_all(format!("{resp}\n").as_bytes())
+                .await
+                .unwrap();
+        });
+
+        let result = transport
+            .request("gpio_read", json!({ "pin": 2 }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.output, "1");
+        device.await.unwrap();
+
+        loop {
+            let event = events.recv().await.unwrap();
+            if event.board == "demux-test" {
+                assert_eq!(event.signal, "button");
+                assert_eq!(event.payload.as_deref(), Some("1"));
+                break;
+            }
+        }
+        assert!(transport.pending.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn closed_port_fails_request_before_timeout() {
+        let (host, board) = tokio::io::duplex(1024);
+        let transport = SerialTransport::spawn("closed-test", host);
+        drop(board);
+
+        let started = std::time::Instant::now();
+        assert!(transport.request("ping", json!({})).await.is_err());
+        assert!(started.elapsed() < std::time::Duration::from_secs(SERIAL_TIMEOUT_SECS));
+        assert!(transport.pending.lock().is_empty());
+    }
+}
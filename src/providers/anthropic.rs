@@ -1,6 +1,8 @@
+use crate::providers::streaming::{response_events, sse_data};
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall,
+    ChatEventStream, ChatMessage, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, ChatStreamEvent, Provider, ProviderCapabilities,
+    StreamError, StreamResult, TokenUsage, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -61,6 +63,8 @@ struct NativeChatRequest<'a> {
     thinking: Option<ThinkingConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
     output_config: Option<OutputConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
 }
 
 #[derive(Debug, Serialize)]
@@ -230,6 +234,119 @@ struct NativeContentIn {
     input: Option<serde_json::Value>,
 }
 
+/// One `data:` payload of a Messages API SSE stream.
+#[derive(Debug, Deserialize)]
+struct StreamEventIn {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    message: Option<StreamMessageIn>,
+    #[serde(default)]
+    content_block: Option<NativeContentIn>,
+    #[serde(default)]
+    delta: Option<StreamDeltaIn>,
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+    #[serde(default)]
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamMessageIn {
+    #[serde(default)]
+    usage: Option<AnthropicUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamDeltaIn {
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    partial_json: Option<String>,
+    #[serde(default)]
+    thinking: Option<String>,
+}
+
+fn usage_event(usage: AnthropicUsage) -> ChatStreamEvent {
+    ChatStreamEvent::Usage(TokenUsage {
+        input_tokens: usage.input_tokens,
+        output_tokens: usage.output_tokens,
+        cached_input_tokens: usage.cache_read_input_tokens,
+    })
+}
+
+/// Parse one SSE line of a Messages API stream. Tool call indexes are the
+/// content block indexes, so text blocks leave gaps.
+fn parse_stream_line(line: &str) -> StreamResult<Vec<ChatStreamEvent>> {
+    let Some(data) = sse_data(line) else {
+        return Ok(Vec::new());
+    };
+    let event: StreamEventIn = serde_json::from_str(data).map_err(StreamError::Json)?;
+    let index = event.index.unwrap_or(0);
+    let mut events = Vec::new();
+
+    match event.kind.as_str() {
+        "message_start" => {
+            if let Some(usage) = event.message.and_then(|m| m.usage) {
+                events.push(usage_event(usage));
+            }
+        }
+        "content_block_start" => {
+            if let Some(block) = event.content_block {
+                if block.kind == "tool_use" {
+                    events.push(ChatStreamEvent::ToolCallStart {
+                        index,
+                        id: block.id.unwrap_or_default(),
+                        name: block.name.unwrap_or_default(),
+                    });
+                } else if let Some(text) = block.text.filter(|t| !t.is_empty()) {
+                    events.push(ChatStreamEvent::TextDelta(text));
+                }
+            }
+        }
+        "content_block_delta" => {
+            if let Some(delta) = event.delta {
+                match delta.kind.as_deref() {
+                    Some("text_delta") => {
+                        if let Some(text) = delta.text.filter(|t| !t.is_empty()) {
+                            events.push(ChatStreamEvent::TextDelta(text));
+                        }
+                    }
+                    Some("input_json_delta") => {
+                        if let Some(arguments) = delta.partial_json.filter(|a| !a.is_empty()) {
+                            events.push(ChatStreamEvent::ToolCallDelta { index, arguments });
+                        }
+                    }
+                    Some("thinking_delta") => {
+                        if let Some(thinking) = delta.thinking.filter(|t| !t.is_empty()) {
+                            events.push(ChatStreamEvent::ReasoningDelta(thinking));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        "message_delta" => {
+            if let Some(usage) = event.usage {
+                events.push(usage_event(usage));
+            }
+        }
+        "error" => {
+            let detail = event.error.map(|e| e.to_string()).unwrap_or_default();
+            return Err(StreamError::Provider(format!(
+                "Anthropic stream error: {detail}"
+            )));
+        }
+        _ => {}
+    }
+
+    Ok(events)
+}
+
 impl AnthropicProvider {
     pub fn new(credential: Option<&str>) -> Self {
         Self::with_base_url(credential, None)
@@ -340,10 +457,14 @@ impl AnthropicProvider {
             .map(|tool| {
                 if tool.name == "computer" {
                     has_computer_tool = true;
-                    let w = tool.parameters.get("__display_width_px")
+                    let w = tool
+                        .parameters
+                        .get("__display_width_px")
                         .and_then(serde_json::Value::as_u64)
                         .unwrap_or(1024) as u32;
-                    let h = tool.parameters.get("__display_height_px")
+                    let h = tool
+                        .parameters
+                        .get("__display_height_px")
                         .and_then(serde_json::Value::as_u64)
                         .unwrap_or(768) as u32;
                     NativeToolDef::ComputerUse(ComputerUseToolSpec {
@@ -439,7 +560,10 @@ impl AnthropicProvider {
     /// lines are present the original string is returned as-is via the plain
     /// `Text` variant so that the serialised payload is unchanged.
     fn parse_tool_result_content(result: &str) -> ToolResultContent {
-        if !result.lines().any(|l| l.trim_start().starts_with("data:image/")) {
+        if !result
+            .lines()
+            .any(|l| l.trim_start().starts_with("data:image/"))
+        {
             return ToolResultContent::Text(result.to_string());
         }
 
@@ -759,6 +883,91 @@ impl AnthropicProvider {
     fn http_client(&self) -> Client {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.anthropic", 120, 10)
     }
+
+    /// Build and send a Messages API request with native tools; `stream`
+    /// requests an SSE response. Non-success statuses become errors.
+    async fn send_native(
+        &self,
+        request: ProviderChatRequest<'_>,
+        credential: &str,
+        model: &str,
+        temperature: f64,
+        stream: bool,
+    ) -> anyhow::Result<reqwest::Response> {
+        let is_setup = Self::is_setup_token(credential);
+        let (system_prompt, mut messages) = Self::convert_messages(request.messages, is_setup);
+
+        // Auto-cache last message if conversation is long
+        if Self::should_cache_conversation(request.messages) {
+            Self::apply_cache_to_last_message(&mut messages);
+        }
+
+        let thinking = self.thinking_mode.as_deref().and_then(|mode| match mode {
+            "adaptive" | "enabled" => {
+                let budget = self.thinking_budget.unwrap_or(10000);
+                Some(ThinkingConfig {
+                    thinking_type: mode.to_string(),
+                    budget_tokens: Some(budget),
+                })
+            }
+            "disabled" => None, // default behavior, no need to send
+            other => {
+                tracing::warn!("Unknown ANTHROPIC_THINKING_MODE '{}', ignoring", other);
+                None
+            }
+        });
+        let output_config = self.effort.as_deref().and_then(|e| match e {
+            "low" | "medium" | "high" | "max" => Some(OutputConfig {
+                effort: e.to_string(),
+            }),
+            other => {
+                tracing::warn!("Unknown ANTHROPIC_EFFORT '{}', ignoring", other);
+                None
+            }
+        });
+
+        // Thinking requires temperature=1 and max_tokens > budget_tokens
+        let (temp, max_tok) = if let Some(ref t) = thinking {
+            let budget = t.budget_tokens.unwrap_or(10000);
+            (1.0, std::cmp::max(16384, budget + 1024))
+        } else {
+            (temperature, 4096)
+        };
+
+        let (converted_tools, has_computer_tool) = Self::convert_tools(request.tools);
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            max_tokens: max_tok,
+            system: system_prompt,
+            messages,
+            temperature: temp,
+            tool_choice: if request.tool_choice == Some("required") && converted_tools.is_some() {
+                Some(serde_json::json!({"type": "any"}))
+            } else {
+                None
+            },
+            tools: converted_tools,
+            thinking,
+            output_config,
+            stream: stream.then_some(true),
+        };
+
+        let req = self
+            .http_client()
+            .post(format!("{}/v1/messages", self.base_url))
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&native_request);
+
+        let response = self
+            .apply_auth(req, credential, has_computer_tool)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(super::api_error("Anthropic", response).await);
+        }
+        Ok(response)
+    }
 }
 
 #[async_trait]
@@ -840,77 +1049,34 @@ impl Provider for AnthropicProvider {
             )
         })?;
 
-        let is_setup = Self::is_setup_token(credential);
-        let (system_prompt, mut messages) = Self::convert_messages(request.messages, is_setup);
-
-        // Auto-cache last message if conversation is long
-        if Self::should_cache_conversation(request.messages) {
-            Self::apply_cache_to_last_message(&mut messages);
-        }
-
-        let thinking = self.thinking_mode.as_deref().and_then(|mode| match mode {
-            "adaptive" | "enabled" => {
-                let budget = self.thinking_budget.unwrap_or(10000);
-                Some(ThinkingConfig {
-                    thinking_type: mode.to_string(),
-                    budget_tokens: Some(budget),
-                })
-            }
-            "disabled" => None, // default behavior, no need to send
-            other => {
-                tracing::warn!("Unknown ANTHROPIC_THINKING_MODE '{}', ignoring", other);
-                None
-            }
-        });
-        let output_config = self.effort.as_deref().and_then(|e| match e {
-            "low" | "medium" | "high" | "max" => Some(OutputConfig {
-                effort: e.to_string(),
-            }),
-            other => {
-                tracing::warn!("Unknown ANTHROPIC_EFFORT '{}', ignoring", other);
-                None
-            }
-        });
-
-        // Thinking requires temperature=1 and max_tokens > budget_tokens
-        let (temp, max_tok) = if let Some(ref t) = thinking {
-            let budget = t.budget_tokens.unwrap_or(10000);
-            (1.0, std::cmp::max(16384, budget + 1024))
-        } else {
-            (temperature, 4096)
-        };
+        let response = self
+            .send_native(request, credential, model, temperature, false)
+            .await?;
 
-        let (converted_tools, has_computer_tool) = Self::convert_tools(request.tools);
-        let native_request = NativeChatRequest {
-            model: model.to_string(),
-            max_tokens: max_tok,
-            system: system_prompt,
-            messages,
-            temperature: temp,
-            tool_choice: if request.tool_choice == Some("required") && converted_tools.is_some() {
-                Some(serde_json::json!({"type": "any"}))
-            } else {
-                None
-            },
-            tools: converted_tools,
-            thinking,
-            output_config,
-        };
+        let native_response: NativeChatResponse = response.json().await?;
+        Ok(Self::parse_native_response(native_response))
+    }
 
-        let req = self
-            .http_client()
-            .post(format!("{}/v1/messages", self.base_url))
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json")
-            .json(&native_request);
+    fn supports_streaming_chat(&self) -> bool {
+        true
+    }
 
-        let response = self.apply_auth(req, credential, has_computer_tool).send().await?;
-        if !response.status().is_success() {
-            return Err(super::api_error("Anthropic", response).await);
-        }
+    async fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatEventStream> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Anthropic credentials not set. Set ANTHROPIC_API_KEY or ANTHROPIC_OAUTH_TOKEN (setup-token)."
+            )
+        })?;
 
-        let native_response: NativeChatResponse = response.json().await?;
-        Ok(Self::parse_native_response(native_response))
+        let response = self
+            .send_native(request, credential, model, temperature, true)
+            .await?;
+        Ok(response_events(response, parse_stream_line))
     }
 
     fn capabilities(&self) -> ProviderCapabilities {
@@ -918,6 +1084,7 @@ impl Provider for AnthropicProvider {
             native_tool_calling: true,
             vision: true,
             prompt_caching: true,
+            context_window: None,
         }
     }
 
@@ -1635,6 +1802,7 @@ mod tests {
             tool_choice: None,
             thinking: None,
             output_config: None,
+            stream: None,
         };
 
         let json = serde_json::to_string(&req).unwrap();
@@ -1642,6 +1810,55 @@ mod tests {
         assert!(json.contains(r#""system":"System""#));
     }
 
+    #[test]
+    fn stream_events_fold_into_text_tool_call_and_usage() {
+        use crate::providers::traits::ChatStreamAccumulator;
+
+        let lines = [
+            "event: message_start",
+            r#"data: {"type":"message_start","message":{"usage":{"input_tokens":20,"cache_read_input_tokens":5,"output_tokens":1}}}"#,
+            r#"data: {"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}"#,
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Checking"}}"#,
+            r#"data: {"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_1","name":"shell","input":{}}}"#,
+            r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"command\":"}}"#,
+            r#"data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"\"ls\"}"}}"#,
+            r#"data: {"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":17}}"#,
+            r#"data: {"type":"message_stop"}"#,
+        ];
+        let mut acc = ChatStreamAccumulator::new();
+        for line in lines {
+            for event in parse_stream_line(line).unwrap() {
+                acc.push(&event);
+            }
+        }
+        let resp = acc.finish();
+
+        assert_eq!(resp.text.as_deref(), Some("Checking"));
+        assert_eq!(resp.tool_calls.len(), 1);
+        assert_eq!(resp.tool_calls[0].id, "toolu_1");
+        assert_eq!(resp.tool_calls[0].name, "shell");
+        assert_eq!(resp.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+        let usage = resp.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(20));
+        assert_eq!(usage.output_tokens, Some(17));
+        assert_eq!(usage.cached_input_tokens, Some(5));
+    }
+
+    #[test]
+    fn stream_thinking_and_error_events() {
+        let events = parse_stream_line(
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"hmm"}}"#,
+        )
+        .unwrap();
+        assert!(matches!(&events[0], ChatStreamEvent::ReasoningDelta(t) if t == "hmm"));
+
+        let err = parse_stream_line(
+            r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Overloaded"));
+    }
+
     #[tokio::test]
     async fn warmup_without_key_is_noop() {
         let provider = AnthropicProvider::new(None);
@@ -2133,7 +2350,10 @@ mod tests {
         let msg = AnthropicProvider::parse_tool_result_message(&payload).unwrap();
         let json = serde_json::to_value(&msg.content[0]).unwrap();
         // Plain text — content must be a JSON string, not an array.
-        assert!(json["content"].is_string(), "content should be a plain string");
+        assert!(
+            json["content"].is_string(),
+            "content should be a plain string"
+        );
         assert_eq!(json["content"], "just text");
     }
 }
@@ -313,6 +313,7 @@ impl Provider for AzureOpenAiProvider {
             native_tool_calling: true,
             vision: true,
             prompt_caching: false,
+            context_window: None,
         }
     }
 
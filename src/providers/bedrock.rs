@@ -989,6 +989,7 @@ impl Provider for BedrockProvider {
             native_tool_calling: true,
             vision: true,
             prompt_caching: false,
+            context_window: None,
         }
     }
 
@@ -3,10 +3,11 @@
 //! This module provides a single implementation that works for all of them.
 
 use crate::multimodal;
+use crate::providers::streaming::{parse_openai_stream_line, response_events};
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, StreamChunk, StreamError, StreamOptions, StreamResult, TokenUsage,
-    ToolCall as ProviderToolCall,
+    chat_response_events, ChatEventStream, ChatMessage, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, Provider, StreamChunk, StreamError, StreamOptions,
+    StreamResult, TokenUsage, ToolCall as ProviderToolCall,
 };
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
@@ -1243,6 +1244,7 @@ impl Provider for OpenAiCompatibleProvider {
             native_tool_calling: self.native_tool_calling,
             vision: self.supports_vision,
             prompt_caching: false,
+            context_window: None,
         }
     }
 
@@ -1739,6 +1741,78 @@ impl Provider for OpenAiCompatibleProvider {
         true
     }
 
+    fn supports_streaming_chat(&self) -> bool {
+        true
+    }
+
+    async fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatEventStream> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} API key not set. Run `zeroclaw onboard` or set the appropriate env var.",
+                self.name
+            )
+        })?;
+
+        let tools = Self::convert_tool_specs(request.tools);
+        let effective_messages = if self.merge_system_into_user {
+            Self::flatten_system_messages(request.messages)
+        } else {
+            request.messages.to_vec()
+        };
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages_for_native(
+                &effective_messages,
+                !self.merge_system_into_user,
+            ),
+            temperature,
+            stream: Some(true),
+            reasoning_effort: self.effective_reasoning_effort(model),
+            tool_choice: tools
+                .as_ref()
+                .map(|_| request.tool_choice.unwrap_or("auto").to_string()),
+            tools,
+        };
+
+        let url = self.chat_completions_url();
+        let response = self
+            .apply_auth_header(
+                self.http_client()
+                    .post(&url)
+                    .header("Accept", "text/event-stream")
+                    .json(&native_request),
+                credential,
+            )
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error = response.text().await?;
+            let sanitized = super::sanitize_api_error(&error);
+
+            // Schema rejections and missing chat completions are handled by
+            // the buffered path's fallbacks; replay its result instead.
+            if Self::is_native_tool_schema_unsupported(status, &sanitized)
+                || (status == reqwest::StatusCode::NOT_FOUND && self.supports_responses_fallback)
+            {
+                let response = self.chat(request, model, temperature).await?;
+                return Ok(
+                    stream::iter(chat_response_events(response).into_iter().map(Ok)).boxed(),
+                );
+            }
+
+            anyhow::bail!("{} API error ({status}): {sanitized}", self.name);
+        }
+
+        Ok(response_events(response, parse_openai_stream_line))
+    }
+
     fn stream_chat_with_system(
         &self,
         system_prompt: Option<&str>,
@@ -6,8 +6,10 @@
 
 use crate::auth::AuthService;
 use crate::multimodal;
+use crate::providers::streaming::{response_events, sse_data};
 use crate::providers::traits::{
-    ChatMessage, ChatResponse, Provider, ProviderCapabilities, TokenUsage, ToolCall, ToolsPayload,
+    ChatEventStream, ChatMessage, ChatResponse, ChatStreamEvent, Provider, ProviderCapabilities,
+    StreamError, StreamResult, TokenUsage, ToolCall, ToolsPayload,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -296,6 +298,63 @@ impl GenerateContentResponse {
     }
 }
 
+/// Build a parser for `streamGenerateContent?alt=sse` lines. Function calls
+/// arrive whole, so each gets the next tool index and its full arguments.
+fn stream_line_parser() -> impl FnMut(&str) -> StreamResult<Vec<ChatStreamEvent>> + Send {
+    let mut next_tool_index = 0usize;
+    move |line: &str| {
+        let Some(data) = sse_data(line) else {
+            return Ok(Vec::new());
+        };
+        let chunk: GenerateContentResponse =
+            serde_json::from_str(data).map_err(StreamError::Json)?;
+        let chunk = chunk.into_effective_response();
+        if let Some(err) = chunk.error {
+            return Err(StreamError::Provider(format!(
+                "Gemini API error: {}",
+                err.message
+            )));
+        }
+
+        let mut events = Vec::new();
+        let parts = chunk
+            .candidates
+            .and_then(|c| c.into_iter().next())
+            .and_then(|c| c.content)
+            .map(|c| c.parts)
+            .unwrap_or_default();
+        for part in parts {
+            if let Some(fc) = part.function_call {
+                let index = next_tool_index;
+                next_tool_index += 1;
+                events.push(ChatStreamEvent::ToolCallStart {
+                    index,
+                    id: format!("gemini_{}", uuid::Uuid::new_v4()),
+                    name: fc.name,
+                });
+                events.push(ChatStreamEvent::ToolCallDelta {
+                    index,
+                    arguments: serde_json::to_string(&fc.args).unwrap_or_default(),
+                });
+            } else if let Some(text) = part.text.filter(|t| !t.is_empty()) {
+                events.push(if part.thought {
+                    ChatStreamEvent::ReasoningDelta(text)
+                } else {
+                    ChatStreamEvent::TextDelta(text)
+                });
+            }
+        }
+        if let Some(u) = chunk.usage_metadata {
+            events.push(ChatStreamEvent::Usage(TokenUsage {
+                input_tokens: u.prompt_token_count,
+                output_tokens: u.candidates_token_count,
+                cached_input_tokens: None,
+            }));
+        }
+        Ok(events)
+    }
+}
+
 // ══════════════════════════════════════════════════════════════════════════════
 // GEMINI CLI TOKEN STRUCTURES
 // ══════════════════════════════════════════════════════════════════════════════
@@ -847,6 +906,153 @@ impl GeminiProvider {
         }
     }
 
+    /// Convert a structured chat request into Gemini contents, system
+    /// instruction, tool declarations and tool config.
+    fn build_chat_payload(
+        &self,
+        request: &crate::providers::traits::ChatRequest<'_>,
+    ) -> (
+        Vec<Content>,
+        Option<Content>,
+        Option<Vec<serde_json::Value>>,
+        Option<serde_json::Value>,
+    ) {
+        let mut system_parts: Vec<&str> = Vec::new();
+        let mut contents: Vec<Content> = Vec::new();
+
+        for msg in request.messages {
+            match msg.role.as_str() {
+                "system" => system_parts.push(&msg.content),
+                "user" => contents.push(Content {
+                    role: Some("user".to_string()),
+                    parts: Self::build_user_parts(&msg.content),
+                }),
+                "assistant" => {
+                    // Check if this is a tool-call history message (JSON array of tool calls)
+                    if let Ok(tool_calls) = serde_json::from_str::<Vec<ToolCall>>(&msg.content) {
+                        let parts = tool_calls
+                            .iter()
+                            .map(|tc| {
+                                let args: serde_json::Value = serde_json::from_str(&tc.arguments)
+                                    .unwrap_or(serde_json::Value::Object(
+                                        serde_json::Map::default(),
+                                    ));
+                                Part::FunctionCall {
+                                    function_call: FunctionCallData {
+                                        name: tc.name.clone(),
+                                        args,
+                                    },
+                                }
+                            })
+                            .collect();
+                        contents.push(Content {
+                            role: Some("model".to_string()),
+                            parts,
+                        });
+                    } else {
+                        contents.push(Content {
+                            role: Some("model".to_string()),
+                            parts: vec![Part::Text {
+                                text: msg.content.clone(),
+                            }],
+                        });
+                    }
+                }
+                "tool" => {
+                    // Tool result message: parse as JSON to extract name and result
+                    // Expected format: {"tool_call_id":"...","name":"tool_name","content":"result"}
+                    if let Ok(result) = serde_json::from_str::<serde_json::Value>(&msg.content) {
+                        let name = result
+                            .get("name")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("unknown");
+                        let content = result
+                            .get("content")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or(&msg.content);
+                        // Extract any base64 images embedded in the tool result.
+                        let (text_content, image_parts) = Self::extract_tool_result_images(content);
+                        let response_val = serde_json::json!({ "result": text_content });
+                        let mut parts = vec![Part::FunctionResponse {
+                            function_response: FunctionResponseData {
+                                name: name.to_string(),
+                                response: response_val,
+                            },
+                        }];
+                        parts.extend(image_parts);
+                        contents.push(Content {
+                            role: Some("user".to_string()),
+                            parts,
+                        });
+                    } else {
+                        // Fallback: wrap raw content as function response
+                        contents.push(Content {
+                            role: Some("user".to_string()),
+                            parts: vec![Part::FunctionResponse {
+                                function_response: FunctionResponseData {
+                                    name: "unknown".to_string(),
+                                    response: serde_json::json!({ "result": msg.content }),
+                                },
+                            }],
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let system_instruction = if system_parts.is_empty() {
+            None
+        } else {
+            Some(Content {
+                role: None,
+                parts: vec![Part::Text {
+                    text: system_parts.join("\n\n"),
+                }],
+            })
+        };
+
+        // Convert tools to Gemini format if provided
+        let gemini_tools = request.tools.and_then(|tools| {
+            if tools.is_empty() {
+                return None;
+            }
+            match self.convert_tools(tools) {
+                ToolsPayload::Gemini {
+                    function_declarations,
+                } => Some(vec![serde_json::json!({
+                    "functionDeclarations": function_declarations,
+                })]),
+                _ => None,
+            }
+        });
+
+        let gemini_tool_config =
+            if request.tool_choice == Some("required") && gemini_tools.is_some() {
+                Some(serde_json::json!({"functionCallingConfig": {"mode": "ANY"}}))
+            } else {
+                None
+            };
+
+        (
+            contents,
+            system_instruction,
+            gemini_tools,
+            gemini_tool_config,
+        )
+    }
+
+    /// SSE variant of [`Self::build_generate_content_url`].
+    fn build_stream_generate_content_url(model: &str, auth: &GeminiAuth) -> String {
+        let url = Self::build_generate_content_url(model, auth).replacen(
+            ":generateContent",
+            ":streamGenerateContent",
+            1,
+        );
+        let separator = if url.contains('?') { '&' } else { '?' };
+        format!("{url}{separator}alt=sse")
+    }
+
     fn http_client(&self) -> Client {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.gemini", 120, 10)
     }
@@ -1086,7 +1292,10 @@ impl GeminiProvider {
         Ok((text.unwrap_or_default(), usage))
     }
 
-    async fn send_generate_content_inner(
+    /// Send a (stream)generateContent request, handling OAuth rotation and
+    /// generationConfig retries. Returns the successful HTTP response.
+    #[allow(clippy::too_many_arguments)]
+    async fn send_generate_content_request(
         &self,
         contents: Vec<Content>,
         system_instruction: Option<Content>,
@@ -1094,7 +1303,8 @@ impl GeminiProvider {
         tool_config: Option<serde_json::Value>,
         model: &str,
         temperature: f64,
-    ) -> anyhow::Result<(Option<String>, Vec<ToolCall>, Option<TokenUsage>)> {
+        stream: bool,
+    ) -> anyhow::Result<reqwest::Response> {
         let auth = self.auth.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
                 "Gemini API key not found. Options:\n\
@@ -1148,7 +1358,11 @@ impl GeminiProvider {
             tool_config,
         };
 
-        let url = Self::build_generate_content_url(model, auth);
+        let url = if stream {
+            Self::build_stream_generate_content_url(model, auth)
+        } else {
+            Self::build_generate_content_url(model, auth)
+        };
 
         let mut response = self
             .build_generate_content_request(
@@ -1275,6 +1489,30 @@ impl GeminiProvider {
             anyhow::bail!("Gemini API error ({status}): {error_text}");
         }
 
+        Ok(response)
+    }
+
+    async fn send_generate_content_inner(
+        &self,
+        contents: Vec<Content>,
+        system_instruction: Option<Content>,
+        tools: Option<Vec<serde_json::Value>>,
+        tool_config: Option<serde_json::Value>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<(Option<String>, Vec<ToolCall>, Option<TokenUsage>)> {
+        let response = self
+            .send_generate_content_request(
+                contents,
+                system_instruction,
+                tools,
+                tool_config,
+                model,
+                temperature,
+                false,
+            )
+            .await?;
+
         let result: GenerateContentResponse = response.json().await?;
         if let Some(err) = &result.error {
             anyhow::bail!("Gemini API error: {}", err.message);
@@ -1329,6 +1567,7 @@ impl Provider for GeminiProvider {
             native_tool_calling: true,
             vision: true,
             prompt_caching: false,
+            context_window: None,
         }
     }
 
@@ -1429,129 +1668,40 @@ impl Provider for GeminiProvider {
         Ok(text)
     }
 
+    fn supports_streaming_chat(&self) -> bool {
+        true
+    }
+
+    async fn stream_chat(
+        &self,
+        request: crate::providers::traits::ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatEventStream> {
+        let (contents, system_instruction, gemini_tools, gemini_tool_config) =
+            self.build_chat_payload(&request);
+        let response = self
+            .send_generate_content_request(
+                contents,
+                system_instruction,
+                gemini_tools,
+                gemini_tool_config,
+                model,
+                temperature,
+                true,
+            )
+            .await?;
+        Ok(response_events(response, stream_line_parser()))
+    }
+
     async fn chat(
         &self,
         request: crate::providers::traits::ChatRequest<'_>,
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let mut system_parts: Vec<&str> = Vec::new();
-        let mut contents: Vec<Content> = Vec::new();
-
-        for msg in request.messages {
-            match msg.role.as_str() {
-                "system" => system_parts.push(&msg.content),
-                "user" => contents.push(Content {
-                    role: Some("user".to_string()),
-                    parts: Self::build_user_parts(&msg.content),
-                }),
-                "assistant" => {
-                    // Check if this is a tool-call history message (JSON array of tool calls)
-                    if let Ok(tool_calls) = serde_json::from_str::<Vec<ToolCall>>(&msg.content) {
-                        let parts = tool_calls
-                            .iter()
-                            .map(|tc| {
-                                let args: serde_json::Value = serde_json::from_str(&tc.arguments)
-                                    .unwrap_or(serde_json::Value::Object(
-                                        serde_json::Map::default(),
-                                    ));
-                                Part::FunctionCall {
-                                    function_call: FunctionCallData {
-                                        name: tc.name.clone(),
-                                        args,
-                                    },
-                                }
-                            })
-                            .collect();
-                        contents.push(Content {
-                            role: Some("model".to_string()),
-                            parts,
-                        });
-                    } else {
-                        contents.push(Content {
-                            role: Some("model".to_string()),
-                            parts: vec![Part::Text {
-                                text: msg.content.clone(),
-                            }],
-                        });
-                    }
-                }
-                "tool" => {
-                    // Tool result message: parse as JSON to extract name and result
-                    // Expected format: {"tool_call_id":"...","name":"tool_name","content":"result"}
-                    if let Ok(result) = serde_json::from_str::<serde_json::Value>(&msg.content) {
-                        let name = result
-                            .get("name")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("unknown");
-                        let content = result
-                            .get("content")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or(&msg.content);
-                        // Extract any base64 images embedded in the tool result.
-                        let (text_content, image_parts) =
-                            Self::extract_tool_result_images(content);
-                        let response_val = serde_json::json!({ "result": text_content });
-                        let mut parts = vec![Part::FunctionResponse {
-                            function_response: FunctionResponseData {
-                                name: name.to_string(),
-                                response: response_val,
-                            },
-                        }];
-                        parts.extend(image_parts);
-                        contents.push(Content {
-                            role: Some("user".to_string()),
-                            parts,
-                        });
-                    } else {
-                        // Fallback: wrap raw content as function response
-                        contents.push(Content {
-                            role: Some("user".to_string()),
-                            parts: vec![Part::FunctionResponse {
-                                function_response: FunctionResponseData {
-                                    name: "unknown".to_string(),
-                                    response: serde_json::json!({ "result": msg.content }),
-                                },
-                            }],
-                        });
-                    }
-                }
-                _ => {}
-            }
-        }
-
-        let system_instruction = if system_parts.is_empty() {
-            None
-        } else {
-            Some(Content {
-                role: None,
-                parts: vec![Part::Text {
-                    text: system_parts.join("\n\n"),
-                }],
-            })
-        };
-
-        // Convert tools to Gemini format if provided
-        let gemini_tools = request.tools.and_then(|tools| {
-            if tools.is_empty() {
-                return None;
-            }
-            match self.convert_tools(tools) {
-                ToolsPayload::Gemini {
-                    function_declarations,
-                } => Some(vec![serde_json::json!({
-                    "functionDeclarations": function_declarations,
-                })]),
-                _ => None,
-            }
-        });
-
-        let gemini_tool_config =
-            if request.tool_choice == Some("required") && gemini_tools.is_some() {
-                Some(serde_json::json!({"functionCallingConfig": {"mode": "ANY"}}))
-            } else {
-                None
-            };
+        let (contents, system_instruction, gemini_tools, gemini_tool_config) =
+            self.build_chat_payload(&request);
 
         let (text, tool_calls, usage) = self
             .send_generate_content_inner(
@@ -1825,6 +1975,42 @@ mod tests {
         assert!(!url.contains("?key="));
     }
 
+    #[test]
+    fn stream_urls_use_sse_endpoint() {
+        let auth = GeminiAuth::ExplicitKey("api-key-123".into());
+        let url = GeminiProvider::build_stream_generate_content_url("gemini-2.0-flash", &auth);
+        assert!(url.contains(":streamGenerateContent?key=api-key-123&alt=sse"));
+
+        let auth = test_oauth_auth("ya29.test-token");
+        let url = GeminiProvider::build_stream_generate_content_url("gemini-2.0-flash", &auth);
+        assert!(url.ends_with(":streamGenerateContent?alt=sse"));
+    }
+
+    #[test]
+    fn stream_parser_emits_text_thoughts_tool_calls_and_usage() {
+        use crate::providers::traits::ChatStreamAccumulator;
+
+        let mut parse = stream_line_parser();
+        let mut acc = ChatStreamAccumulator::new();
+        for line in [
+            r#"data: {"candidates":[{"content":{"parts":[{"text":"plan","thought":true}]}}]}"#,
+            r#"data: {"candidates":[{"content":{"parts":[{"text":"Let me "}]}}]}"#,
+            r#"data: {"response":{"candidates":[{"content":{"parts":[{"text":"check."},{"functionCall":{"name":"shell","args":{"command":"ls"}}}]}}],"usageMetadata":{"promptTokenCount":9,"candidatesTokenCount":4}}}"#,
+        ] {
+            for event in parse(line).unwrap() {
+                acc.push(&event);
+            }
+        }
+        let resp = acc.finish();
+        assert_eq!(resp.text.as_deref(), Some("Let me check."));
+        assert_eq!(resp.reasoning_content.as_deref(), Some("plan"));
+        assert_eq!(resp.tool_calls.len(), 1);
+        assert_eq!(resp.tool_calls[0].name, "shell");
+        assert_eq!(resp.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+        assert!(resp.tool_calls[0].id.starts_with("gemini_"));
+        assert_eq!(resp.usage.unwrap().output_tokens, Some(4));
+    }
+
     #[test]
     fn api_key_url_uses_public_endpoint() {
         let auth = GeminiAuth::ExplicitKey("api-key-123".into());
@@ -2559,8 +2745,7 @@ mod tests {
 
     #[test]
     fn extract_tool_result_images_multiple_images() {
-        let input =
-            "data:image/png;base64,img1\nsome text\ndata:image/webp;base64,img2";
+        let input = "data:image/png;base64,img1\nsome text\ndata:image/webp;base64,img2";
         let (text, parts) = GeminiProvider::extract_tool_result_images(input);
         assert_eq!(text, "some text");
         assert_eq!(parts.len(), 2);
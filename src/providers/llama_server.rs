@@ -0,0 +1,610 @@
+//! Provider backed by a local `llama-server` process that ZeroClaw owns.
+//!
+//! Unlike the `llamacpp` provider, which talks to a server someone else
+//! started, `llama-server` spawns the binary from `[llama_server]`, waits for
+//! it to report healthy, and restarts it when it exits. Requests are served
+//! through the OpenAI-compatible adapter pointed at the managed port.
+//!
+//! One process is shared per distinct `[llama_server]` config, so every
+//! provider instance (channels, gateway, delegates) talks to the same model.
+
+use super::traits::{
+    build_tool_instructions_text, ChatEventStream, ChatMessage, ChatRequest, ChatResponse,
+    ProviderCapabilities,
+};
+use super::Provider;
+use crate::config::LlamaServerConfig;
+use anyhow::Context;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Child;
+
+/// Canonical provider name and alias accepted in config.
+pub fn is_llama_server_provider(name: &str) -> bool {
+    matches!(name, "llama-server" | "llama_server")
+}
+
+const STARTUP_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+static SERVERS: LazyLock<parking_lot::Mutex<HashMap<LlamaServerConfig, Arc<LlamaServer>>>> =
+    LazyLock::new(|| parking_lot::Mutex::new(HashMap::new()));
+
+// ── Process management ───────────────────────────────────────────
+
+/// A supervised local model server process.
+pub struct LlamaServer {
+    config: LlamaServerConfig,
+    port: u16,
+    client: reqwest::Client,
+    child: tokio::sync::Mutex<Option<Child>>,
+    context_window: parking_lot::RwLock<Option<usize>>,
+}
+
+impl LlamaServer {
+    /// Return the process-wide server for `config`, creating it on first use.
+    ///
+    /// The process itself is not started until [`LlamaServer::ensure_running`].
+    pub fn shared(config: &LlamaServerConfig) -> anyhow::Result<Arc<Self>> {
+        let mut servers = SERVERS.lock();
+        if let Some(server) = servers.get(config) {
+            return Ok(Arc::clone(server));
+        }
+        let server = Arc::new(Self::new(config.clone())?);
+        servers.insert(config.clone(), Arc::clone(&server));
+        Ok(server)
+    }
+
+    fn new(config: LlamaServerConfig) -> anyhow::Result<Self> {
+        let port = if config.port == 0 {
+            pick_free_port(&config.host)?
+        } else {
+            config.port
+        };
+        let client = reqwest::Client::builder().timeout(PROBE_TIMEOUT).build()?;
+        Ok(Self {
+            config,
+            port,
+            client,
+            child: tokio::sync::Mutex::new(None),
+            context_window: parking_lot::RwLock::new(None),
+        })
+    }
+
+    /// Root URL of the server, without the `/v1` suffix.
+    pub fn base_url(&self) -> String {
+        let host = match self.config.host.trim() {
+            "" | "0.0.0.0" => "127.0.0.1".to_string(),
+            "::" => "[::1]".to_string(),
+            host if host.contains(':') => format!("[{host}]"),
+            host => host.to_string(),
+        };
+        format!("http://{host}:{}", self.port)
+    }
+
+    /// Context window reported by the running server, falling back to the
+    /// configured `ctx_size`. `None` until the server has started once.
+    pub fn context_window(&self) -> Option<usize> {
+        *self.context_window.read()
+    }
+
+    pub fn native_tools(&self) -> bool {
+        self.config.native_tools
+    }
+
+    /// Arguments passed to the server binary.
+    pub fn command_args(&self) -> Vec<String> {
+        if !self.config.args.is_empty() {
+            return self
+                .config
+                .args
+                .iter()
+                .map(|arg| {
+                    arg.replace("{model}", &self.config.model_path)
+                        .replace("{host}", &self.config.host)
+                        .replace("{port}", &self.port.to_string())
+                        .replace("{ctx_size}", &self.config.ctx_size.to_string())
+                })
+                .collect();
+        }
+
+        let mut args = vec![
+            "--model".to_string(),
+            self.config.model_path.clone(),
+            "--host".to_string(),
+            self.config.host.clone(),
+            "--port".to_string(),
+            self.port.to_string(),
+        ];
+        if self.config.ctx_size > 0 {
+            args.push("--ctx-size".into());
+            args.push(self.config.ctx_size.to_string());
+        }
+        if self.config.native_tools {
+            args.push("--jinja".into());
+        }
+        args.extend(self.config.extra_args.iter().cloned());
+        args
+    }
+
+    /// Start the server if it is not running, waiting until it is healthy.
+    ///
+    /// Callers racing on a cold start wait for the same process rather than
+    /// spawning their own.
+    pub async fn ensure_running(&self) -> anyhow::Result<()> {
+        let mut slot = self.child.lock().await;
+        if let Some(child) = slot.as_mut() {
+            match child.try_wait()? {
+                None => return Ok(()),
+                Some(status) => {
+                    tracing::warn!(%status, "llama-server exited; restarting");
+                    *slot = None;
+                }
+            }
+        }
+
+        let mut child = self.spawn()?;
+        if let Err(error) = self.wait_until_healthy(&mut child).await {
+            let _ = child.kill().await;
+            return Err(error);
+        }
+
+        let context_window = self
+            .probe_context_window()
+            .await
+            .or_else(|| (self.config.ctx_size > 0).then(|| self.config.ctx_size as usize));
+        *self.context_window.write() = context_window;
+        tracing::info!(
+            url = %self.base_url(),
+            context_window = ?context_window,
+            "llama-server ready"
+        );
+        *slot = Some(child);
+        Ok(())
+    }
+
+    /// Keep the server up, returning an error once it exits or fails
+    /// `max_failed_health_checks` consecutive health checks.
+    ///
+    /// Meant to run under the daemon's component supervisor, which calls it
+    /// again with backoff; the next call restarts the process.
+    pub async fn supervise(&self) -> anyhow::Result<()> {
+        self.ensure_running().await?;
+        let interval = Duration::from_secs(self.config.health_check_interval_secs.max(1));
+        let mut failures = 0u32;
+
+        loop {
+            tokio::time::sleep(interval).await;
+
+            {
+                let mut slot = self.child.lock().await;
+                let Some(child) = slot.as_mut() else {
+                    anyhow::bail!("llama-server was stopped");
+                };
+                if let Some(status) = child.try_wait()? {
+                    *slot = None;
+                    anyhow::bail!("llama-server exited ({status})");
+                }
+            }
+
+            if self.is_healthy().await {
+                failures = 0;
+                continue;
+            }
+            failures += 1;
+            tracing::warn!(failures, "llama-server health check failed");
+            if failures >= self.config.max_failed_health_checks.max(1) {
+                self.stop().await;
+                anyhow::bail!("llama-server failed {failures} consecutive health checks");
+            }
+        }
+    }
+
+    /// Kill the server process if it is running.
+    pub async fn stop(&self) {
+        if let Some(mut child) = self.child.lock().await.take() {
+            let _ = child.kill().await;
+        }
+    }
+
+    pub async fn is_healthy(&self) -> bool {
+        let url = format!("{}{}", self.base_url(), self.config.health_path);
+        self.client
+            .get(url)
+            .send()
+            .await
+            .is_ok_and(|response| response.status().is_success())
+    }
+
+    fn spawn(&self) -> anyhow::Result<Child> {
+        if self.config.model_path.trim().is_empty() && self.config.args.is_empty() {
+            anyhow::bail!(
+                "llama-server provider requires llama_server.model_path (or custom llama_server.args)"
+            );
+        }
+        let args = self.command_args();
+        tracing::info!(
+            binary = %self.config.binary,
+            port = self.port,
+            "Starting llama-server"
+        );
+        let mut child = tokio::process::Command::new(&self.config.binary)
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("failed to start {}", self.config.binary))?;
+
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    tracing::debug!(target: "llama_server", "{line}");
+                }
+            });
+        }
+        Ok(child)
+    }
+
+    async fn wait_until_healthy(&self, child: &mut Child) -> anyhow::Result<()> {
+        let timeout = Duration::from_secs(self.config.startup_timeout_secs);
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(status) = child.try_wait()? {
+                anyhow::bail!("llama-server exited during startup ({status})");
+            }
+            // llama-server answers 503 while the model is still loading.
+            if self.is_healthy().await {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                anyhow::bail!(
+                    "llama-server did not become healthy within {}s",
+                    timeout.as_secs()
+                );
+            }
+            tokio::time::sleep(STARTUP_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn probe_context_window(&self) -> Option<usize> {
+        let base = self.base_url();
+        if let Some(props) = self.get_json(&format!("{base}/props")).await {
+            if let Some(n_ctx) = context_window_from_props(&props) {
+                return Some(n_ctx);
+            }
+        }
+        let models = self.get_json(&format!("{base}/v1/models")).await?;
+        context_window_from_models(&models)
+    }
+
+    async fn get_json(&self, url: &str) -> Option<serde_json::Value> {
+        let response = self.client.get(url).send().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        response.json().await.ok()
+    }
+}
+
+/// Stop every managed server. Called by the daemon on shutdown.
+pub async fn stop_all() {
+    let servers: Vec<Arc<LlamaServer>> = SERVERS.lock().values().cloned().collect();
+    for server in servers {
+        server.stop().await;
+    }
+}
+
+fn pick_free_port(host: &str) -> anyhow::Result<u16> {
+    let listener = std::net::TcpListener::bind((host, 0))
+        .with_context(|| format!("failed to pick a free port on {host}"))?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// llama.cpp `GET /props`: `default_generation_settings.n_ctx`.
+fn context_window_from_props(props: &serde_json::Value) -> Option<usize> {
+    props
+        .pointer("/default_generation_settings/n_ctx")
+        .or_else(|| props.get("n_ctx"))
+        .and_then(serde_json::Value::as_u64)
+        .and_then(|n| usize::try_from(n).ok())
+}
+
+/// OpenAI-style `GET /v1/models`: vLLM's `max_model_len` or llama.cpp's
+/// `meta.n_ctx_train` on the first model.
+fn context_window_from_models(models: &serde_json::Value) -> Option<usize> {
+    let model = models.pointer("/data/0")?;
+    model
+        .get("max_model_len")
+        .or_else(|| model.pointer("/meta/n_ctx_train"))
+        .and_then(serde_json::Value::as_u64)
+        .and_then(|n| usize::try_from(n).ok())
+}
+
+// ── Provider ─────────────────────────────────────────────────────
+
+/// Provider that starts its managed server on demand and forwards requests
+/// to an OpenAI-compatible client bound to it.
+pub struct LlamaServerProvider {
+    server: Arc<LlamaServer>,
+    inner: Box<dyn Provider>,
+}
+
+impl LlamaServerProvider {
+    pub fn new(server: Arc<LlamaServer>, inner: Box<dyn Provider>) -> Self {
+        Self { server, inner }
+    }
+
+    /// Without `--jinja` the server ignores tool schemas, so tools are folded
+    /// into the system prompt instead, as the default `Provider::chat` does.
+    fn prompt_guided_messages(&self, request: &ChatRequest<'_>) -> Option<Vec<ChatMessage>> {
+        let tools = request.tools.filter(|tools| !tools.is_empty())?;
+        if self.server.native_tools() {
+            return None;
+        }
+        let instructions = build_tool_instructions_text(tools);
+        let mut messages = request.messages.to_vec();
+        if let Some(system) = messages.iter_mut().find(|m| m.role == "system") {
+            if !system.content.is_empty() {
+                system.content.push_str("\n\n");
+            }
+            system.content.push_str(&instructions);
+        } else {
+            messages.insert(0, ChatMessage::system(instructions));
+        }
+        Some(messages)
+    }
+}
+
+#[async_trait]
+impl Provider for LlamaServerProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: self.server.native_tools(),
+            vision: false,
+            prompt_caching: false,
+            context_window: self.server.context_window(),
+        }
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.server.ensure_running().await
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.server.ensure_running().await?;
+        self.inner
+            .chat_with_system(system_prompt, message, model, temperature)
+            .await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.server.ensure_running().await?;
+        self.inner
+            .chat_with_history(messages, model, temperature)
+            .await
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        self.server.ensure_running().await?;
+        let guided = self.prompt_guided_messages(&request);
+        let request = match &guided {
+            Some(messages) => ChatRequest {
+                messages,
+                tools: None,
+                tool_choice: None,
+            },
+            None => request,
+        };
+        self.inner.chat(request, model, temperature).await
+    }
+
+    fn supports_streaming_chat(&self) -> bool {
+        self.inner.supports_streaming_chat()
+    }
+
+    async fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatEventStream> {
+        self.server.ensure_running().await?;
+        let guided = self.prompt_guided_messages(&request);
+        let request = match &guided {
+            Some(messages) => ChatRequest {
+                messages,
+                tools: None,
+                tool_choice: None,
+            },
+            None => request,
+        };
+        self.inner.stream_chat(request, model, temperature).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tools::ToolSpec;
+
+    fn config() -> LlamaServerConfig {
+        LlamaServerConfig {
+            model_path: "/models/qwen.gguf".into(),
+            port: 18_080,
+            ..LlamaServerConfig::default()
+        }
+    }
+
+    #[test]
+    fn default_args_target_llama_server() {
+        let server = LlamaServer::new(LlamaServerConfig {
+            ctx_size: 8192,
+            native_tools: true,
+            extra_args: vec!["-ngl".into(), "99".into()],
+            ..config()
+        })
+        .unwrap();
+        assert_eq!(
+            server.command_args(),
+            [
+                "--model",
+                "/models/qwen.gguf",
+                "--host",
+                "127.0.0.1",
+                "--port",
+                "18080",
+                "--ctx-size",
+                "8192",
+                "--jinja",
+                "-ngl",
+                "99"
+            ]
+        );
+        assert_eq!(server.base_url(), "http://127.0.0.1:18080");
+    }
+
+    #[test]
+    fn custom_args_substitute_placeholders() {
+        let server = LlamaServer::new(LlamaServerConfig {
+            args: vec![
+                "serve".into(),
+                "{model}".into(),
+                "--port={port}".into(),
+                "--max-model-len={ctx_size}".into(),
+            ],
+            ctx_size: 4096,
+            ..config()
+        })
+        .unwrap();
+        assert_eq!(
+            server.command_args(),
+            [
+                "serve",
+                "/models/qwen.gguf",
+                "--port=18080",
+                "--max-model-len=4096"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_model_is_rejected_and_free_port_is_picked() {
+        let unconfigured = LlamaServer::new(LlamaServerConfig::default()).unwrap();
+        let err = unconfigured.ensure_running().await.unwrap_err().to_string();
+        assert!(err.contains("llama_server.model_path"), "{err}");
+
+        let server = LlamaServer::new(LlamaServerConfig {
+            port: 0,
+            ..config()
+        })
+        .unwrap();
+        assert_ne!(server.port, 0);
+    }
+
+    #[test]
+    fn context_window_is_read_from_props_then_models() {
+        let props = serde_json::json!({"default_generation_settings": {"n_ctx": 32768}});
+        assert_eq!(context_window_from_props(&props), Some(32768));
+        assert_eq!(context_window_from_props(&serde_json::json!({})), None);
+
+        let vllm = serde_json::json!({"data": [{"id": "m", "max_model_len": 16384}]});
+        assert_eq!(context_window_from_models(&vllm), Some(16384));
+        let llama = serde_json::json!({"data": [{"id": "m", "meta": {"n_ctx_train": 131_072}}]});
+        assert_eq!(context_window_from_models(&llama), Some(131_072));
+        assert_eq!(
+            context_window_from_models(&serde_json::json!({"data": []})),
+            None
+        );
+    }
+
+    #[test]
+    fn shared_returns_one_server_per_config() {
+        let cfg = LlamaServerConfig {
+            port: 18_081,
+            ..config()
+        };
+        let a = LlamaServer::shared(&cfg).unwrap();
+        let b = LlamaServer::shared(&cfg).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+
+    #[test]
+    fn tools_are_prompt_guided_without_native_support() {
+        let server = Arc::new(LlamaServer::new(config()).unwrap());
+        let provider = LlamaServerProvider::new(
+            server,
+            Box::new(crate::providers::compatible::OpenAiCompatibleProvider::new(
+                "llama-server",
+                "http://127.0.0.1:18080/v1",
+                None,
+                crate::providers::compatible::AuthStyle::Bearer,
+            )),
+        );
+        assert!(!provider.supports_native_tools());
+        assert_eq!(provider.capabilities().context_window, None);
+
+        let tools = vec![ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let messages = vec![ChatMessage::system("base"), ChatMessage::user("hi")];
+        let request = ChatRequest {
+            messages: &messages,
+            tools: Some(&tools),
+            tool_choice: None,
+        };
+        let guided = provider.prompt_guided_messages(&request).unwrap();
+        assert_eq!(guided.len(), 2);
+        assert!(guided[0].content.starts_with("base\n\n"));
+        assert!(guided[0].content.contains("shell"));
+    }
+
+    #[tokio::test]
+    async fn ensure_running_reports_missing_binary() {
+        let server = LlamaServer::new(LlamaServerConfig {
+            binary: "/nonexistent/llama-server".into(),
+            port: 18_082,
+            ..config()
+        })
+        .unwrap();
+        let err = server.ensure_running().await.unwrap_err().to_string();
+        assert!(err.contains("failed to start"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn ensure_running_fails_fast_when_process_exits() {
+        let server = LlamaServer::new(LlamaServerConfig {
+            binary: "sh".into(),
+            args: vec!["-c".into(), "exit 3".into()],
+            port: 18_083,
+            ..config()
+        })
+        .unwrap();
+        let err = server.ensure_running().await.unwrap_err().to_string();
+        assert!(err.contains("exited during startup"), "{err}");
+    }
+}
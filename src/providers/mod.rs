@@ -25,19 +25,23 @@ pub mod copilot;
 pub mod gemini;
 pub mod gemini_cli;
 pub mod kilocli;
+pub mod llama_server;
 pub mod ollama;
 pub mod openai;
 pub mod openai_codex;
 pub mod openrouter;
 pub mod reliable;
+pub mod replay;
 pub mod router;
+pub mod streaming;
 pub mod telnyx;
+pub mod tokenizer;
 pub mod traits;
 
 #[allow(unused_imports)]
 pub use traits::{
-    ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderCapabilityError,
-    ToolCall, ToolResultMessage,
+    ChatMessage, ChatRequest, ChatResponse, ChatStreamEvent, ConversationMessage, Provider,
+    ProviderCapabilityError, ToolCall, ToolResultMessage,
 };
 
 use crate::auth::AuthService;
@@ -696,6 +700,8 @@ pub struct ProviderRuntimeOptions {
     pub custom_provider_auth_header: Option<String>,
     /// Optional service tier for priority processing (e.g. "priority").
     pub service_tier: Option<String>,
+    /// Managed local server settings for the `llama-server` provider.
+    pub llama_server: crate::config::LlamaServerConfig,
 }
 
 impl Default for ProviderRuntimeOptions {
@@ -713,6 +719,7 @@ impl Default for ProviderRuntimeOptions {
             reasoning_level: None,
             custom_provider_auth_header: None,
             service_tier: None,
+            llama_server: crate::config::LlamaServerConfig::default(),
         }
     }
 }
@@ -733,6 +740,7 @@ pub fn provider_runtime_options_from_config(
         reasoning_level: config.runtime.reasoning_level.clone(),
         custom_provider_auth_header: config.custom_provider_auth_header.clone(),
         service_tier: config.runtime.service_tier.clone(),
+        llama_server: config.llama_server.clone(),
     }
 }
 
@@ -1378,6 +1386,16 @@ fn create_provider_with_url_and_options(
                 AuthStyle::Bearer,
             )))
         }
+        name if llama_server::is_llama_server_provider(name) => {
+            let server = llama_server::LlamaServer::shared(&options.llama_server)?;
+            let inner = compat(OpenAiCompatibleProvider::new(
+                "llama-server",
+                &format!("{}/v1", server.base_url()),
+                Some("llama-server"),
+                AuthStyle::Bearer,
+            ));
+            Ok(Box::new(llama_server::LlamaServerProvider::new(server, inner)))
+        }
         "sglang" => {
             let base_url = api_url
                 .map(str::trim)
@@ -2032,6 +2050,12 @@ pub fn list_providers() -> Vec<ProviderInfo> {
             aliases: &["llama.cpp"],
             local: true,
         },
+        ProviderInfo {
+            name: "llama-server",
+            display_name: "llama-server (managed)",
+            aliases: &["llama_server"],
+            local: true,
+        },
         ProviderInfo {
             name: "sglang",
             display_name: "SGLang",
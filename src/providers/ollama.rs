@@ -1,6 +1,8 @@
 use crate::multimodal;
+use crate::providers::streaming::response_events;
 use crate::providers::traits::{
-    ChatMessage, ChatResponse, Provider, ProviderCapabilities, TokenUsage, ToolCall,
+    ChatEventStream, ChatMessage, ChatResponse, ChatStreamEvent, Provider, ProviderCapabilities,
+    StreamError, StreamResult, TokenUsage, ToolCall,
 };
 use async_trait::async_trait;
 use reqwest::Client;
@@ -62,6 +64,7 @@ struct Options {
 
 #[derive(Debug, Deserialize)]
 struct ApiChatResponse {
+    #[serde(default)]
     message: ResponseMessage,
     #[serde(default)]
     prompt_eval_count: Option<u64>,
@@ -69,7 +72,7 @@ struct ApiChatResponse {
     eval_count: Option<u64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 struct ResponseMessage {
     #[serde(default)]
     content: String,
@@ -109,6 +112,80 @@ where
         Ok(value)
     }
 }
+// ─── Streaming ────────────────────────────────────────────────────────────────
+
+/// Build a parser for `/api/chat` NDJSON stream lines.
+///
+/// `<think>` blocks inside `content` are emitted as reasoning; tags are
+/// recognised when they arrive whole within one delta, which is how Ollama
+/// tokenizes them.
+fn stream_line_parser() -> impl FnMut(&str) -> StreamResult<Vec<ChatStreamEvent>> + Send {
+    let mut next_tool_index = 0usize;
+    let mut in_think = false;
+    move |line: &str| {
+        let value: serde_json::Value = serde_json::from_str(line).map_err(StreamError::Json)?;
+        if let Some(error) = value.get("error").and_then(serde_json::Value::as_str) {
+            return Err(StreamError::Provider(format!("Ollama error: {error}")));
+        }
+        let chunk: ApiChatResponse = serde_json::from_value(value).map_err(StreamError::Json)?;
+        let mut events = Vec::new();
+
+        if let Some(thinking) = chunk.message.thinking.filter(|t| !t.is_empty()) {
+            events.push(ChatStreamEvent::ReasoningDelta(thinking));
+        }
+
+        let mut rest = chunk.message.content.as_str();
+        while !rest.is_empty() {
+            let tag = if in_think { "</think>" } else { "<think>" };
+            let (before, after) = match rest.find(tag) {
+                Some(pos) => (&rest[..pos], Some(&rest[pos + tag.len()..])),
+                None => (rest, None),
+            };
+            if !before.is_empty() {
+                events.push(if in_think {
+                    ChatStreamEvent::ReasoningDelta(before.to_string())
+                } else {
+                    ChatStreamEvent::TextDelta(before.to_string())
+                });
+            }
+            match after {
+                Some(after) => {
+                    in_think = !in_think;
+                    rest = after;
+                }
+                None => break,
+            }
+        }
+
+        for tc in &chunk.message.tool_calls {
+            let (name, args) = OllamaProvider::unwrap_tool_call(tc);
+            let index = next_tool_index;
+            next_tool_index += 1;
+            events.push(ChatStreamEvent::ToolCallStart {
+                index,
+                id: tc
+                    .id
+                    .clone()
+                    .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                name,
+            });
+            events.push(ChatStreamEvent::ToolCallDelta {
+                index,
+                arguments: serde_json::to_string(&args).unwrap_or_else(|_| "{}".to_string()),
+            });
+        }
+
+        if chunk.prompt_eval_count.is_some() || chunk.eval_count.is_some() {
+            events.push(ChatStreamEvent::Usage(TokenUsage {
+                input_tokens: chunk.prompt_eval_count,
+                output_tokens: chunk.eval_count,
+                cached_input_tokens: None,
+            }));
+        }
+        Ok(events)
+    }
+}
+
 // ─── Implementation ───────────────────────────────────────────────────────────
 
 impl OllamaProvider {
@@ -455,7 +532,34 @@ impl OllamaProvider {
             request.tools.as_ref().map_or(0, |t| t.len()),
         );
 
-        let mut request_builder = self.http_client().post(&url).json(&request);
+        let response = self.post_chat(&url, &request, should_auth).await?;
+        let body = response.bytes().await?;
+        tracing::debug!("Ollama response body length: {} bytes", body.len());
+
+        let chat_response: ApiChatResponse = match serde_json::from_slice(&body) {
+            Ok(r) => r,
+            Err(e) => {
+                let raw = String::from_utf8_lossy(&body);
+                let sanitized = super::sanitize_api_error(&raw);
+                tracing::error!(
+                    "Ollama response deserialization failed: {e}. body_excerpt={}",
+                    sanitized
+                );
+                anyhow::bail!("Failed to parse Ollama response: {e}");
+            }
+        };
+
+        Ok(chat_response)
+    }
+
+    /// POST a chat request, turning non-success statuses into errors.
+    async fn post_chat(
+        &self,
+        url: &str,
+        request: &ChatRequest,
+        should_auth: bool,
+    ) -> anyhow::Result<reqwest::Response> {
+        let mut request_builder = self.http_client().post(url).json(request);
 
         if should_auth {
             if let Some(key) = self.api_key.as_ref() {
@@ -467,10 +571,8 @@ impl OllamaProvider {
         let status = response.status();
         tracing::debug!("Ollama response status: {}", status);
 
-        let body = response.bytes().await?;
-        tracing::debug!("Ollama response body length: {} bytes", body.len());
-
         if !status.is_success() {
+            let body = response.bytes().await?;
             let raw = String::from_utf8_lossy(&body);
             let sanitized = super::sanitize_api_error(&raw);
             tracing::error!(
@@ -485,20 +587,55 @@ impl OllamaProvider {
             );
         }
 
-        let chat_response: ApiChatResponse = match serde_json::from_slice(&body) {
-            Ok(r) => r,
-            Err(e) => {
-                let raw = String::from_utf8_lossy(&body);
-                let sanitized = super::sanitize_api_error(&raw);
-                tracing::error!(
-                    "Ollama response deserialization failed: {e}. body_excerpt={}",
-                    sanitized
+        Ok(response)
+    }
+
+    /// Open a streaming `/api/chat` request, retrying once without `think`
+    /// when reasoning is enabled (mirrors [`Self::send_request`]).
+    async fn send_stream_request(
+        &self,
+        messages: Vec<Message>,
+        model: &str,
+        temperature: f64,
+        should_auth: bool,
+        tools: Option<&[serde_json::Value]>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = format!("{}/api/chat", self.base_url);
+        let mut request = self.build_chat_request(messages, model, temperature, tools);
+        request.stream = true;
+
+        match self.post_chat(&url, &request, should_auth).await {
+            Ok(response) => Ok(response),
+            Err(first_err) if self.reasoning_enabled == Some(true) => {
+                tracing::warn!(
+                    model = model,
+                    error = %first_err,
+                    "Ollama stream request failed with think=true; retrying without reasoning"
                 );
-                anyhow::bail!("Failed to parse Ollama response: {e}");
+                request.think = None;
+                self.post_chat(&url, &request, should_auth)
+                    .await
+                    .map_err(|_| first_err)
             }
-        };
+            Err(e) => Err(e),
+        }
+    }
 
-        Ok(chat_response)
+    /// Convert tool specs to the OpenAI-style JSON that `/api/chat` accepts.
+    fn tool_specs_to_json(specs: &[crate::tools::ToolSpec]) -> Vec<serde_json::Value> {
+        specs
+            .iter()
+            .map(|s| {
+                serde_json::json!({
+                    "type": "function",
+                    "function": {
+                        "name": s.name,
+                        "description": s.description,
+                        "parameters": s.parameters
+                    }
+                })
+            })
+            .collect()
     }
 
     /// Send a request to Ollama and get the parsed response.
@@ -589,6 +726,10 @@ impl OllamaProvider {
 
     /// Extract the actual tool name and arguments from potentially nested structures
     fn extract_tool_name_and_args(&self, tc: &OllamaToolCall) -> (String, serde_json::Value) {
+        Self::unwrap_tool_call(tc)
+    }
+
+    fn unwrap_tool_call(tc: &OllamaToolCall) -> (String, serde_json::Value) {
         let name = &tc.function.name;
         let args = &tc.function.arguments;
 
@@ -633,6 +774,7 @@ impl Provider for OllamaProvider {
             native_tool_calling: true,
             vision: true,
             prompt_caching: false,
+            context_window: None,
         }
     }
 
@@ -830,6 +972,35 @@ impl Provider for OllamaProvider {
         true
     }
 
+    fn supports_streaming_chat(&self) -> bool {
+        true
+    }
+
+    async fn stream_chat(
+        &self,
+        request: crate::providers::traits::ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatEventStream> {
+        let (normalized_model, should_auth) = self.resolve_request_details(model)?;
+        let api_messages = self.convert_messages(request.messages);
+        let tools = request
+            .tools
+            .filter(|specs| !specs.is_empty())
+            .map(Self::tool_specs_to_json);
+
+        let response = self
+            .send_stream_request(
+                api_messages,
+                &normalized_model,
+                temperature,
+                should_auth,
+                tools.as_deref(),
+            )
+            .await?;
+        Ok(response_events(response, stream_line_parser()))
+    }
+
     async fn chat(
         &self,
         request: crate::providers::traits::ChatRequest<'_>,
@@ -839,19 +1010,7 @@ impl Provider for OllamaProvider {
         // Convert ToolSpec to OpenAI-compatible JSON and delegate to chat_with_tools.
         if let Some(specs) = request.tools {
             if !specs.is_empty() {
-                let tools: Vec<serde_json::Value> = specs
-                    .iter()
-                    .map(|s| {
-                        serde_json::json!({
-                            "type": "function",
-                            "function": {
-                                "name": s.name,
-                                "description": s.description,
-                                "parameters": s.parameters
-                            }
-                        })
-                    })
-                    .collect();
+                let tools = Self::tool_specs_to_json(specs);
                 return self
                     .chat_with_tools(request.messages, &tools, model, temperature)
                     .await;
@@ -877,6 +1036,41 @@ impl Provider for OllamaProvider {
 mod tests {
     use super::*;
 
+    #[test]
+    fn stream_parser_splits_think_tags_tool_calls_and_usage() {
+        use crate::providers::traits::ChatStreamAccumulator;
+
+        let mut parse = stream_line_parser();
+        let mut acc = ChatStreamAccumulator::new();
+        for line in [
+            r#"{"message":{"role":"assistant","content":"<think>"},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":"plan"},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":"</think>Sure"},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"tool.shell","arguments":{"command":"ls"}}}]},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":""},"done":true,"prompt_eval_count":11,"eval_count":6}"#,
+        ] {
+            for event in parse(line).unwrap() {
+                acc.push(&event);
+            }
+        }
+        let resp = acc.finish();
+        assert_eq!(resp.text.as_deref(), Some("Sure"));
+        assert_eq!(resp.reasoning_content.as_deref(), Some("plan"));
+        assert_eq!(resp.tool_calls.len(), 1);
+        assert_eq!(resp.tool_calls[0].name, "shell");
+        assert_eq!(resp.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+        let usage = resp.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(11));
+        assert_eq!(usage.output_tokens, Some(6));
+    }
+
+    #[test]
+    fn stream_parser_surfaces_error_lines() {
+        let mut parse = stream_line_parser();
+        let err = parse(r#"{"error":"model not found"}"#).unwrap_err();
+        assert!(err.to_string().contains("model not found"));
+    }
+
     #[test]
     fn default_url() {
         let p = OllamaProvider::new(None, None);
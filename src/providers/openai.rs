@@ -1,6 +1,7 @@
+use crate::providers::streaming::{parse_openai_stream_line, response_events};
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, TokenUsage, ToolCall as ProviderToolCall,
+    ChatEventStream, ChatMessage, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, Provider, TokenUsage, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -62,6 +63,15 @@ struct NativeChatRequest {
     tools: Option<Vec<NativeToolSpec>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptionsPayload>,
+}
+
+#[derive(Debug, Serialize)]
+struct StreamOptionsPayload {
+    include_usage: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -413,6 +423,8 @@ impl Provider for OpenAiProvider {
                 .as_ref()
                 .map(|_| request.tool_choice.unwrap_or("auto").to_string()),
             tools,
+            stream: None,
+            stream_options: None,
         };
 
         let response = self
@@ -448,6 +460,53 @@ impl Provider for OpenAiProvider {
         true
     }
 
+    fn supports_streaming_chat(&self) -> bool {
+        true
+    }
+
+    async fn stream_chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatEventStream> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
+        })?;
+
+        let adjusted_temperature = Self::adjust_temperature_for_model(model, temperature);
+
+        let tools = Self::convert_tools(request.tools);
+        let native_request = NativeChatRequest {
+            model: model.to_string(),
+            messages: Self::convert_messages(request.messages),
+            temperature: adjusted_temperature,
+            tool_choice: tools
+                .as_ref()
+                .map(|_| request.tool_choice.unwrap_or("auto").to_string()),
+            tools,
+            stream: Some(true),
+            stream_options: Some(StreamOptionsPayload {
+                include_usage: true,
+            }),
+        };
+
+        let response = self
+            .http_client()
+            .post(format!("{}/chat/completions", self.base_url))
+            .header("Authorization", format!("Bearer {credential}"))
+            .header("Accept", "text/event-stream")
+            .json(&native_request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenAI", response).await);
+        }
+
+        Ok(response_events(response, parse_openai_stream_line))
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -479,6 +538,8 @@ impl Provider for OpenAiProvider {
             temperature: adjusted_temperature,
             tool_choice: native_tools.as_ref().map(|_| "auto".to_string()),
             tools: native_tools,
+            stream: None,
+            stream_options: None,
         };
 
         let response = self
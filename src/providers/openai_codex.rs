@@ -722,6 +722,7 @@ impl Provider for OpenAiCodexProvider {
             native_tool_calling: false,
             vision: true,
             prompt_caching: false,
+            context_window: None,
         }
     }
 
@@ -1156,6 +1157,7 @@ data: [DONE]
             custom_provider_auth_header: None,
             reasoning_level: None,
             service_tier: None,
+            llama_server: crate::config::LlamaServerConfig::default(),
         };
         let provider =
             OpenAiCodexProvider::new(&options, None).expect("provider should initialize");
@@ -307,6 +307,7 @@ impl Provider for OpenRouterProvider {
             native_tool_calling: true,
             vision: true,
             prompt_caching: false,
+            context_window: None,
         }
     }
 
@@ -0,0 +1,528 @@
+//! Record-and-replay provider for deterministic, offline tests.
+//!
+//! In record mode [`ReplayProvider`] wraps a real provider and appends every
+//! `chat` exchange to a JSONL cassette. In replay mode it serves the recorded
+//! responses back, keyed by a hash of the normalized request, and errors on
+//! any request it has not seen. All one-shot helpers (`chat_with_system`,
+//! `chat_with_history`) are routed through `chat`, so they are captured too.
+//!
+//! Cassette layout: the first line is a header carrying the wrapped
+//! provider's capabilities; each following line is one exchange.
+//!
+//! ```text
+//! {"version":1,"capabilities":{"native_tool_calling":true,...}}
+//! {"key":"3f1c…","request":{...},"response":{"text":"…","tool_calls":[],...}}
+//! ```
+
+use super::traits::{ChatMessage, ChatRequest, ChatResponse, ProviderCapabilities};
+use super::Provider;
+use anyhow::Context;
+use async_trait::async_trait;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+const CASSETTE_VERSION: u32 = 1;
+
+/// Values that legitimately differ between runs and must not affect the key.
+static UUID_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}\b").unwrap()
+});
+static TIMESTAMP_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\d{4}-\d{2}-\d{2}[ T]\d{2}:\d{2}:\d{2}(?:\.\d+)?(?:Z|[+-]\d{2}:?\d{2})?").unwrap()
+});
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CassetteHeader {
+    version: u32,
+    capabilities: ProviderCapabilities,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Exchange {
+    key: String,
+    /// Normalized request, kept for humans diffing a cassette.
+    request: serde_json::Value,
+    response: ChatResponse,
+}
+
+enum Mode {
+    Record {
+        inner: Box<dyn Provider>,
+        file: parking_lot::Mutex<std::fs::File>,
+    },
+    Replay {
+        /// Responses per request key, in recorded order, so identical
+        /// requests replay in the sequence they were made.
+        exchanges: parking_lot::Mutex<HashMap<String, VecDeque<ChatResponse>>>,
+    },
+}
+
+/// Provider that records real exchanges to a cassette or replays them.
+pub struct ReplayProvider {
+    mode: Mode,
+    capabilities: ProviderCapabilities,
+    path: PathBuf,
+}
+
+impl ReplayProvider {
+    /// Wrap `inner` and record every exchange to `path`, truncating any
+    /// existing cassette.
+    pub fn record(inner: Box<dyn Provider>, path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let capabilities = ProviderCapabilities {
+            native_tool_calling: inner.supports_native_tools(),
+            vision: inner.supports_vision(),
+            ..inner.capabilities()
+        };
+        let mut file = std::fs::File::create(&path)
+            .with_context(|| format!("failed to create cassette {}", path.display()))?;
+        let header = CassetteHeader {
+            version: CASSETTE_VERSION,
+            capabilities: capabilities.clone(),
+        };
+        writeln!(file, "{}", serde_json::to_string(&header)?)?;
+
+        Ok(Self {
+            mode: Mode::Record {
+                inner,
+                file: parking_lot::Mutex::new(file),
+            },
+            capabilities,
+            path,
+        })
+    }
+
+    /// Load a cassette recorded by [`ReplayProvider::record`].
+    pub fn replay(path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let file = std::fs::File::open(&path)
+            .with_context(|| format!("failed to open cassette {}", path.display()))?;
+        let mut lines = std::io::BufReader::new(file).lines();
+
+        let header_line = lines
+            .next()
+            .transpose()?
+            .with_context(|| format!("cassette {} is empty", path.display()))?;
+        let header: CassetteHeader = serde_json::from_str(&header_line)
+            .with_context(|| format!("invalid cassette header in {}", path.display()))?;
+        if header.version != CASSETTE_VERSION {
+            anyhow::bail!(
+                "cassette {} has version {}, expected {CASSETTE_VERSION}",
+                path.display(),
+                header.version
+            );
+        }
+
+        let mut exchanges: HashMap<String, VecDeque<ChatResponse>> = HashMap::new();
+        for (index, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let exchange: Exchange = serde_json::from_str(&line).with_context(|| {
+                format!(
+                    "invalid exchange on line {} of {}",
+                    index + 2,
+                    path.display()
+                )
+            })?;
+            exchanges
+                .entry(exchange.key)
+                .or_default()
+                .push_back(exchange.response);
+        }
+
+        Ok(Self {
+            mode: Mode::Replay {
+                exchanges: parking_lot::Mutex::new(exchanges),
+            },
+            capabilities: header.capabilities,
+            path,
+        })
+    }
+
+    /// Cassette this provider reads from or writes to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Recorded exchanges not yet served. Always 0 in record mode.
+    ///
+    /// Tests can assert this is 0 to catch turns that stopped calling the
+    /// model earlier than when the cassette was recorded.
+    pub fn unused_exchanges(&self) -> usize {
+        match &self.mode {
+            Mode::Record { .. } => 0,
+            Mode::Replay { exchanges } => exchanges.lock().values().map(VecDeque::len).sum(),
+        }
+    }
+
+    /// Stable key for a request: a SHA-256 over [`normalize_request`].
+    pub fn request_key(request: &ChatRequest<'_>, model: &str, temperature: f64) -> String {
+        key_for(&normalize_request(request, model, temperature))
+    }
+}
+
+/// Reduce a request to the fields that determine the response.
+///
+/// UUIDs and timestamps inside message content are masked so generated
+/// tool-call ids and the system prompt clock do not break replay. Tools are
+/// sorted by name and temperature is rounded so float formatting noise does
+/// not change the key.
+pub fn normalize_request(
+    request: &ChatRequest<'_>,
+    model: &str,
+    temperature: f64,
+) -> serde_json::Value {
+    let messages: Vec<serde_json::Value> = request
+        .messages
+        .iter()
+        .map(|m| {
+            serde_json::json!({
+                "role": m.role,
+                "content": scrub_volatile(&m.content),
+            })
+        })
+        .collect();
+
+    let tools = request.tools.map(|tools| {
+        let mut tools: Vec<serde_json::Value> = tools
+            .iter()
+            .map(|t| {
+                serde_json::json!({
+                    "name": t.name,
+                    "description": t.description,
+                    "parameters": t.parameters,
+                })
+            })
+            .collect();
+        tools.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+        tools
+    });
+
+    serde_json::json!({
+        "model": model,
+        "temperature": format!("{temperature:.3}"),
+        "tool_choice": request.tool_choice,
+        "tools": tools,
+        "messages": messages,
+    })
+}
+
+fn key_for(normalized: &serde_json::Value) -> String {
+    hex::encode(Sha256::digest(normalized.to_string().as_bytes()))
+}
+
+fn scrub_volatile(content: &str) -> String {
+    let content = UUID_RE.replace_all(content, "<uuid>");
+    TIMESTAMP_RE
+        .replace_all(&content, "<timestamp>")
+        .into_owned()
+}
+
+/// Short description of a request for mismatch errors.
+fn describe_request(request: &ChatRequest<'_>) -> String {
+    let last = request
+        .messages
+        .last()
+        .map(|m| {
+            let excerpt: String = m.content.chars().take(120).collect();
+            format!("{}: {excerpt:?}", m.role)
+        })
+        .unwrap_or_else(|| "<no messages>".into());
+    format!(
+        "{} message(s), {} tool(s), last {last}",
+        request.messages.len(),
+        request.tools.map_or(0, <[_]>::len)
+    )
+}
+
+#[async_trait]
+impl Provider for ReplayProvider {
+    fn capabilities(&self) -> ProviderCapabilities {
+        self.capabilities.clone()
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::with_capacity(2);
+        if let Some(system) = system_prompt {
+            messages.push(ChatMessage::system(system));
+        }
+        messages.push(ChatMessage::user(message));
+        self.chat_with_history(&messages, model, temperature).await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let request = ChatRequest {
+            messages,
+            tools: None,
+            tool_choice: None,
+        };
+        let response = self.chat(request, model, temperature).await?;
+        Ok(response.text.unwrap_or_default())
+    }
+
+    async fn chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatResponse> {
+        let normalized = normalize_request(&request, model, temperature);
+        let key = key_for(&normalized);
+        match &self.mode {
+            Mode::Record { inner, file } => {
+                let response = inner.chat(request, model, temperature).await?;
+                let exchange = Exchange {
+                    key,
+                    request: normalized,
+                    response,
+                };
+                let line = serde_json::to_string(&exchange)?;
+                {
+                    let mut file = file.lock();
+                    writeln!(file, "{line}")?;
+                    file.flush()?;
+                }
+                Ok(exchange.response)
+            }
+            Mode::Replay { exchanges } => {
+                let mut exchanges = exchanges.lock();
+                if let Some(response) = exchanges.get_mut(&key).and_then(VecDeque::pop_front) {
+                    return Ok(response);
+                }
+                let remaining: usize = exchanges.values().map(VecDeque::len).sum();
+                anyhow::bail!(
+                    "no recorded exchange in cassette {} matches request {key} ({}); {remaining} unused exchange(s) remain — re-record the cassette if the request changed intentionally",
+                    self.path.display(),
+                    describe_request(&request)
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::traits::TokenUsage;
+    use crate::providers::ToolCall;
+    use crate::tools::ToolSpec;
+
+    /// Returns canned responses in order and counts calls.
+    struct CannedProvider {
+        responses: parking_lot::Mutex<VecDeque<ChatResponse>>,
+    }
+
+    impl CannedProvider {
+        fn new(responses: Vec<ChatResponse>) -> Self {
+            Self {
+                responses: parking_lot::Mutex::new(responses.into()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for CannedProvider {
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                native_tool_calling: true,
+                ..ProviderCapabilities::default()
+            }
+        }
+
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("chat_with_system should not be called")
+        }
+
+        async fn chat(
+            &self,
+            _request: ChatRequest<'_>,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<ChatResponse> {
+            self.responses
+                .lock()
+                .pop_front()
+                .ok_or_else(|| anyhow::anyhow!("canned provider exhausted"))
+        }
+    }
+
+    fn text(text: &str) -> ChatResponse {
+        ChatResponse {
+            text: Some(text.into()),
+            tool_calls: Vec::new(),
+            usage: None,
+            reasoning_content: None,
+        }
+    }
+
+    fn request(messages: &[ChatMessage]) -> ChatRequest<'_> {
+        ChatRequest {
+            messages,
+            tools: None,
+            tool_choice: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn records_and_replays_tool_calls_and_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let cassette = dir.path().join("session.jsonl");
+        let tool_response = ChatResponse {
+            text: Some(String::new()),
+            tool_calls: vec![ToolCall {
+                id: "call_1".into(),
+                name: "shell".into(),
+                arguments: r#"{"command":"ls"}"#.into(),
+            }],
+            usage: Some(TokenUsage {
+                input_tokens: Some(12),
+                output_tokens: Some(3),
+                cached_input_tokens: None,
+            }),
+            reasoning_content: Some("look first".into()),
+        };
+        let tools = vec![ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let first = vec![ChatMessage::user("list files")];
+        let second = vec![ChatMessage::user("list files"), ChatMessage::tool("a.txt")];
+
+        let recorder = ReplayProvider::record(
+            Box::new(CannedProvider::new(vec![tool_response, text("a.txt")])),
+            &cassette,
+        )
+        .unwrap();
+        let with_tools = ChatRequest {
+            tools: Some(&tools),
+            ..request(&first)
+        };
+        recorder.chat(with_tools, "m", 0.7).await.unwrap();
+        recorder.chat(request(&second), "m", 0.7).await.unwrap();
+
+        let replayer = ReplayProvider::replay(&cassette).unwrap();
+        assert!(replayer.supports_native_tools());
+        assert_eq!(replayer.unused_exchanges(), 2);
+
+        let replayed = replayer.chat(with_tools, "m", 0.7).await.unwrap();
+        assert_eq!(replayed.tool_calls.len(), 1);
+        assert_eq!(replayed.tool_calls[0].name, "shell");
+        assert_eq!(replayed.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+        assert_eq!(replayed.usage.unwrap().input_tokens, Some(12));
+        assert_eq!(replayed.reasoning_content.as_deref(), Some("look first"));
+
+        let replayed = replayer.chat(request(&second), "m", 0.7).await.unwrap();
+        assert_eq!(replayed.text.as_deref(), Some("a.txt"));
+        assert_eq!(replayer.unused_exchanges(), 0);
+    }
+
+    #[tokio::test]
+    async fn replay_fails_loudly_on_unknown_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let cassette = dir.path().join("session.jsonl");
+        let messages = vec![ChatMessage::user("hello")];
+        let recorder =
+            ReplayProvider::record(Box::new(CannedProvider::new(vec![text("hi")])), &cassette)
+                .unwrap();
+        recorder.chat(request(&messages), "m", 0.0).await.unwrap();
+
+        let replayer = ReplayProvider::replay(&cassette).unwrap();
+        let other = vec![ChatMessage::user("goodbye")];
+        let err = replayer
+            .chat(request(&other), "m", 0.0)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no recorded exchange"), "{err}");
+        assert!(err.contains("goodbye"), "{err}");
+        assert!(err.contains("1 unused exchange"), "{err}");
+
+        // A different model is a different request too.
+        assert!(replayer
+            .chat(request(&messages), "other", 0.0)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn identical_requests_replay_in_recorded_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let cassette = dir.path().join("session.jsonl");
+        let messages = vec![ChatMessage::user("roll a die")];
+        let recorder = ReplayProvider::record(
+            Box::new(CannedProvider::new(vec![text("4"), text("2")])),
+            &cassette,
+        )
+        .unwrap();
+        recorder.chat(request(&messages), "m", 1.0).await.unwrap();
+        recorder.chat(request(&messages), "m", 1.0).await.unwrap();
+
+        let replayer = ReplayProvider::replay(&cassette).unwrap();
+        let first = replayer.chat(request(&messages), "m", 1.0).await.unwrap();
+        let second = replayer.chat(request(&messages), "m", 1.0).await.unwrap();
+        assert_eq!(first.text.as_deref(), Some("4"));
+        assert_eq!(second.text.as_deref(), Some("2"));
+        assert!(replayer.chat(request(&messages), "m", 1.0).await.is_err());
+    }
+
+    #[test]
+    fn request_key_ignores_uuids_and_timestamps() {
+        let a = vec![
+            ChatMessage::system("## Current Date & Time\n\n2025-01-01 09:00:00 (UTC)"),
+            ChatMessage::tool(r#"{"tool_call_id":"0b9f7f7e-3c1a-4c2e-9f7e-1a2b3c4d5e6f"}"#),
+        ];
+        let b = vec![
+            ChatMessage::system("## Current Date & Time\n\n2026-10-18 17:42:13 (UTC)"),
+            ChatMessage::tool(r#"{"tool_call_id":"7d1e2f3a-0000-4abc-8def-0123456789ab"}"#),
+        ];
+        assert_eq!(
+            ReplayProvider::request_key(&request(&a), "m", 0.7),
+            ReplayProvider::request_key(&request(&b), "m", 0.7)
+        );
+
+        let c = vec![ChatMessage::system("different"), a[1].clone()];
+        assert_ne!(
+            ReplayProvider::request_key(&request(&a), "m", 0.7),
+            ReplayProvider::request_key(&request(&c), "m", 0.7)
+        );
+    }
+
+    #[test]
+    fn replay_rejects_missing_or_malformed_cassette() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(ReplayProvider::replay(dir.path().join("missing.jsonl")).is_err());
+
+        let bad = dir.path().join("bad.jsonl");
+        std::fs::write(&bad, "{\"version\":1,\"capabilities\":{}}\nnot json\n").unwrap();
+        let err = ReplayProvider::replay(&bad).err().unwrap().to_string();
+        assert!(err.contains("line 2"), "{err}");
+    }
+}
@@ -1,4 +1,6 @@
-use super::traits::{ChatMessage, ChatRequest, ChatResponse};
+use super::traits::{
+    ChatEventStream, ChatMessage, ChatRequest, ChatResponse, ProviderCapabilities,
+};
 use super::Provider;
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -137,6 +139,25 @@ impl Provider for RouterProvider {
         provider.chat(request, &resolved_model, temperature).await
     }
 
+    fn supports_streaming_chat(&self) -> bool {
+        self.providers
+            .get(self.default_index)
+            .is_some_and(|(_, p)| p.supports_streaming_chat())
+    }
+
+    async fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatEventStream> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider
+            .stream_chat(request, &resolved_model, temperature)
+            .await
+    }
+
     async fn chat_with_tools(
         &self,
         messages: &[ChatMessage],
@@ -151,6 +172,10 @@ impl Provider for RouterProvider {
             .await
     }
 
+    fn supports_route_hint(&self, hint: &str) -> bool {
+        self.routes.contains_key(hint)
+    }
+
     fn supports_native_tools(&self) -> bool {
         self.providers
             .get(self.default_index)
@@ -164,6 +189,24 @@ impl Provider for RouterProvider {
             .any(|(_, provider)| provider.supports_vision())
     }
 
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            native_tool_calling: self.supports_native_tools(),
+            vision: self.supports_vision(),
+            ..self
+                .providers
+                .get(self.default_index)
+                .map(|(_, p)| p.capabilities())
+                .unwrap_or_default()
+        }
+    }
+
+    fn context_window(&self, model: &str) -> Option<usize> {
+        let (provider_idx, resolved_model) = self.resolve(model);
+        let (_, provider) = &self.providers[provider_idx];
+        provider.context_window(&resolved_model)
+    }
+
     async fn warmup(&self) -> anyhow::Result<()> {
         for (name, provider) in &self.providers {
             tracing::info!(provider = name, "Warming up routed provider");
@@ -352,6 +395,17 @@ mod tests {
         assert_eq!(model, "gpt-4o");
     }
 
+    #[test]
+    fn supports_route_hint_reflects_route_table() {
+        let (router, _) = make_router(
+            vec![("fast", "ok"), ("smart", "ok")],
+            vec![("fast", "fast", "model-a")],
+        );
+
+        assert!(router.supports_route_hint("fast"));
+        assert!(!router.supports_route_hint("smart"));
+    }
+
     #[test]
     fn resolve_strips_hint_prefix() {
         let (router, _) = make_router(
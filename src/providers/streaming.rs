@@ -0,0 +1,265 @@
+//! Shared plumbing for [`Provider::stream_chat`](super::traits::Provider::stream_chat).
+//!
+//! Providers turn their HTTP response body into [`ChatStreamEvent`]s by
+//! handing a per-line parser to [`response_events`]. The OpenAI chat
+//! completions chunk format is parsed here because several providers
+//! (OpenAI, OpenAI-compatible) share it.
+
+use super::traits::{ChatEventStream, ChatStreamEvent, StreamError, StreamResult, TokenUsage};
+use futures_util::{stream, StreamExt};
+use serde::Deserialize;
+
+/// Split a streaming response body into lines and map each through `parse_line`.
+///
+/// Works for both SSE and newline-delimited JSON bodies. Lines are split on
+/// raw bytes so multi-byte characters spanning network chunks stay intact.
+pub fn response_events<P>(response: reqwest::Response, mut parse_line: P) -> ChatEventStream
+where
+    P: FnMut(&str) -> StreamResult<Vec<ChatStreamEvent>> + Send + 'static,
+{
+    let (tx, rx) = tokio::sync::mpsc::channel::<StreamResult<ChatStreamEvent>>(100);
+
+    tokio::spawn(async move {
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut bytes_stream = response.bytes_stream();
+
+        'read: loop {
+            let next = bytes_stream.next().await;
+            let at_end = next.is_none();
+            match next {
+                Some(Ok(bytes)) => buffer.extend_from_slice(&bytes),
+                Some(Err(e)) => {
+                    let _ = tx.send(Err(StreamError::Http(e))).await;
+                    return;
+                }
+                None => buffer.push(b'\n'),
+            }
+
+            while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+                let raw: Vec<u8> = buffer.drain(..=pos).collect();
+                let line = match std::str::from_utf8(&raw) {
+                    Ok(line) => line.trim(),
+                    Err(e) => {
+                        let _ = tx
+                            .send(Err(StreamError::InvalidSse(format!("Invalid UTF-8: {e}"))))
+                            .await;
+                        return;
+                    }
+                };
+                if line.is_empty() {
+                    continue;
+                }
+                match parse_line(line) {
+                    Ok(events) => {
+                        for event in events {
+                            if tx.send(Ok(event)).await.is_err() {
+                                return; // Receiver dropped
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                }
+            }
+
+            if at_end {
+                break 'read;
+            }
+        }
+    });
+
+    stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|event| (event, rx))
+    })
+    .boxed()
+}
+
+/// Payload of an SSE `data:` line, or `None` for comments, other fields and
+/// the `[DONE]` sentinel.
+pub fn sse_data(line: &str) -> Option<&str> {
+    let data = line.strip_prefix("data:")?.trim();
+    (!data.is_empty() && data != "[DONE]").then_some(data)
+}
+
+// ── OpenAI chat completions chunks ──────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct OpenAiStreamChunk {
+    #[serde(default)]
+    choices: Vec<OpenAiStreamChoice>,
+    #[serde(default)]
+    usage: Option<OpenAiStreamUsage>,
+    #[serde(default)]
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiStreamChoice {
+    #[serde(default)]
+    delta: OpenAiStreamDelta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenAiStreamDelta {
+    #[serde(default)]
+    content: Option<String>,
+    #[serde(default)]
+    reasoning_content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<OpenAiStreamToolCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiStreamToolCall {
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<OpenAiStreamFunction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiStreamFunction {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiStreamUsage {
+    #[serde(default)]
+    prompt_tokens: Option<u64>,
+    #[serde(default)]
+    completion_tokens: Option<u64>,
+    #[serde(default)]
+    prompt_tokens_details: Option<OpenAiPromptTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiPromptTokensDetails {
+    #[serde(default)]
+    cached_tokens: Option<u64>,
+}
+
+/// Parse one SSE line of an OpenAI-format `chat/completions` stream.
+pub fn parse_openai_stream_line(line: &str) -> StreamResult<Vec<ChatStreamEvent>> {
+    let Some(data) = sse_data(line) else {
+        return Ok(Vec::new());
+    };
+    let chunk: OpenAiStreamChunk = serde_json::from_str(data).map_err(StreamError::Json)?;
+    if let Some(error) = chunk.error {
+        return Err(StreamError::Provider(error.to_string()));
+    }
+
+    let mut events = Vec::new();
+    if let Some(choice) = chunk.choices.into_iter().next() {
+        let delta = choice.delta;
+        if let Some(reasoning) = delta.reasoning_content.filter(|r| !r.is_empty()) {
+            events.push(ChatStreamEvent::ReasoningDelta(reasoning));
+        }
+        if let Some(content) = delta.content.filter(|c| !c.is_empty()) {
+            events.push(ChatStreamEvent::TextDelta(content));
+        }
+        for (position, call) in delta.tool_calls.into_iter().enumerate() {
+            let index = call.index.unwrap_or(position);
+            let (name, arguments) = call
+                .function
+                .map(|f| (f.name, f.arguments))
+                .unwrap_or_default();
+            if call.id.is_some() || name.is_some() {
+                events.push(ChatStreamEvent::ToolCallStart {
+                    index,
+                    id: call.id.unwrap_or_default(),
+                    name: name.unwrap_or_default(),
+                });
+            }
+            if let Some(arguments) = arguments.filter(|a| !a.is_empty()) {
+                events.push(ChatStreamEvent::ToolCallDelta { index, arguments });
+            }
+        }
+    }
+    if let Some(usage) = chunk.usage {
+        events.push(ChatStreamEvent::Usage(TokenUsage {
+            input_tokens: usage.prompt_tokens,
+            output_tokens: usage.completion_tokens,
+            cached_input_tokens: usage.prompt_tokens_details.and_then(|d| d.cached_tokens),
+        }));
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::traits::ChatStreamAccumulator;
+
+    fn fold(lines: &[&str]) -> crate::providers::traits::ChatResponse {
+        let mut acc = ChatStreamAccumulator::new();
+        for line in lines {
+            for event in parse_openai_stream_line(line).unwrap() {
+                acc.push(&event);
+            }
+        }
+        acc.finish()
+    }
+
+    #[test]
+    fn sse_data_skips_non_data_and_done() {
+        assert_eq!(sse_data("data: {\"a\":1}"), Some("{\"a\":1}"));
+        assert_eq!(sse_data("data: [DONE]"), None);
+        assert_eq!(sse_data(": keep-alive"), None);
+        assert_eq!(sse_data("event: ping"), None);
+    }
+
+    #[test]
+    fn openai_text_and_usage() {
+        let resp = fold(&[
+            r#"data: {"choices":[{"delta":{"role":"assistant","content":"Hel"}}]}"#,
+            r#"data: {"choices":[{"delta":{"content":"lo"}}]}"#,
+            r#"data: {"choices":[],"usage":{"prompt_tokens":12,"completion_tokens":3,"prompt_tokens_details":{"cached_tokens":8}}}"#,
+            "data: [DONE]",
+        ]);
+        assert_eq!(resp.text.as_deref(), Some("Hello"));
+        let usage = resp.usage.unwrap();
+        assert_eq!(usage.input_tokens, Some(12));
+        assert_eq!(usage.output_tokens, Some(3));
+        assert_eq!(usage.cached_input_tokens, Some(8));
+    }
+
+    #[test]
+    fn openai_tool_call_fragments_are_joined() {
+        let resp = fold(&[
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"id":"call_1","type":"function","function":{"name":"shell","arguments":""}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"command\":"}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"ls\"}"}}]}}]}"#,
+            r#"data: {"choices":[{"delta":{"tool_calls":[{"index":1,"id":"call_2","function":{"name":"time"}}]}}]}"#,
+        ]);
+        assert!(resp.text.is_none());
+        assert_eq!(resp.tool_calls.len(), 2);
+        assert_eq!(resp.tool_calls[0].id, "call_1");
+        assert_eq!(resp.tool_calls[0].name, "shell");
+        assert_eq!(resp.tool_calls[0].arguments, r#"{"command":"ls"}"#);
+        assert_eq!(resp.tool_calls[1].name, "time");
+        assert_eq!(resp.tool_calls[1].arguments, "{}");
+    }
+
+    #[test]
+    fn openai_reasoning_delta() {
+        let events = parse_openai_stream_line(
+            r#"data: {"choices":[{"delta":{"reasoning_content":"hmm"}}]}"#,
+        )
+        .unwrap();
+        assert!(matches!(&events[0], ChatStreamEvent::ReasoningDelta(r) if r == "hmm"));
+    }
+
+    #[test]
+    fn openai_error_chunk_is_surfaced() {
+        let err =
+            parse_openai_stream_line(r#"data: {"error":{"message":"overloaded"}}"#).unwrap_err();
+        assert!(err.to_string().contains("overloaded"));
+    }
+}
@@ -0,0 +1,488 @@
+//! Token counting for context budgeting.
+//!
+//! The agent loop, channel history trimming, the memory loader and the cost
+//! tracker all need to know how many tokens a prompt will consume *before*
+//! sending it. [`tokenizer_for_model`] picks the best available counter for a
+//! model:
+//!
+//! - OpenAI-family models use byte-pair encoding with the published
+//!   `o200k_base` / `cl100k_base` rank tables when the `.tiktoken` file is
+//!   present in the tokenizer directory (`$ZEROCLAW_TOKENIZER_DIR`, or
+//!   `~/.zeroclaw/tokenizers`). Without the table they fall back to an
+//!   approximation.
+//! - Anthropic and Gemini do not publish their vocabularies, so they use
+//!   approximations calibrated against the usage those APIs report.
+//! - Everything else uses the historical ~4 bytes/token heuristic.
+//!
+//! The module also carries the context windows of well-known models, used
+//! when a provider does not report one through [`ProviderCapabilities`].
+//!
+//! [`ProviderCapabilities`]: super::traits::ProviderCapabilities
+
+use super::traits::ChatMessage;
+use anyhow::Context;
+use base64::Engine;
+use directories::UserDirs;
+use parking_lot::Mutex;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+/// Framing tokens charged per chat message (role markers and delimiters).
+pub const MESSAGE_OVERHEAD_TOKENS: usize = 4;
+
+/// Fraction of a model's context window kept free for the system prompt,
+/// tool schemas and the model's reply when deriving a history budget.
+const CONTEXT_WINDOW_RESERVE_DIVISOR: usize = 4;
+
+/// Pre-tokenization split used before BPE merges.
+///
+/// Mirrors the tiktoken `cl100k_base` pattern minus the `\s+(?!\S)`
+/// look-ahead, which the `regex` crate does not support. The only effect is
+/// that a run of spaces before a word is split one token differently, which
+/// is well within budgeting tolerance.
+const BPE_SPLIT_PATTERN: &str = r"(?i:'s|'t|'re|'ve|'m|'ll|'d)|[^\r\n\p{L}\p{N}]?\p{L}+|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+";
+
+/// Counts tokens for a particular model family.
+pub trait Tokenizer: Send + Sync {
+    /// Short identifier of the encoding, e.g. `o200k_base` or `anthropic-approx`.
+    fn name(&self) -> &str;
+
+    /// Number of tokens `text` encodes to.
+    fn count(&self, text: &str) -> usize;
+
+    /// Number of tokens a single chat message consumes, including framing.
+    fn count_message(&self, message: &ChatMessage) -> usize {
+        self.count(&message.content) + MESSAGE_OVERHEAD_TOKENS
+    }
+}
+
+/// Total tokens for a message list, including per-message framing.
+pub fn count_messages(tokenizer: &dyn Tokenizer, messages: &[ChatMessage]) -> usize {
+    messages.iter().map(|m| tokenizer.count_message(m)).sum()
+}
+
+// ── Byte-pair encoding ───────────────────────────────────────────
+
+/// Byte-pair encoder driven by a tiktoken-format rank table.
+///
+/// The table is one `<base64 token> <rank>` pair per line; lower ranks merge
+/// first. Only counting is implemented — the agent never needs token ids.
+pub struct BpeTokenizer {
+    name: String,
+    ranks: HashMap<Vec<u8>, u32>,
+    split: Regex,
+}
+
+impl BpeTokenizer {
+    /// Parse a tiktoken rank table from its text contents.
+    pub fn from_tiktoken(name: impl Into<String>, contents: &str) -> anyhow::Result<Self> {
+        let mut ranks = HashMap::new();
+        for (idx, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (token, rank) = line
+                .split_once(' ')
+                .with_context(|| format!("malformed rank table line {}", idx + 1))?;
+            let token = base64::engine::general_purpose::STANDARD
+                .decode(token)
+                .with_context(|| format!("invalid base64 token on line {}", idx + 1))?;
+            let rank: u32 = rank
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid rank on line {}", idx + 1))?;
+            ranks.insert(token, rank);
+        }
+        anyhow::ensure!(!ranks.is_empty(), "rank table is empty");
+
+        Ok(Self {
+            name: name.into(),
+            ranks,
+            split: Regex::new(BPE_SPLIT_PATTERN)?,
+        })
+    }
+
+    /// Load a `.tiktoken` rank table from disk.
+    pub fn load(name: impl Into<String>, path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read rank table {}", path.display()))?;
+        Self::from_tiktoken(name, &contents)
+    }
+
+    /// Count the tokens of one pre-tokenized piece by repeatedly merging the
+    /// adjacent pair with the lowest rank.
+    fn count_piece(&self, piece: &[u8]) -> usize {
+        if piece.is_empty() {
+            return 0;
+        }
+        if self.ranks.contains_key(piece) {
+            return 1;
+        }
+
+        // `bounds[i]..bounds[i + 1]` is the i-th part; start from single bytes.
+        let mut bounds: Vec<usize> = (0..=piece.len()).collect();
+        loop {
+            let best = (0..bounds.len().saturating_sub(2))
+                .filter_map(|i| {
+                    self.ranks
+                        .get(&piece[bounds[i]..bounds[i + 2]])
+                        .map(|rank| (*rank, i))
+                })
+                .min();
+            match best {
+                Some((_, i)) => {
+                    bounds.remove(i + 1);
+                }
+                None => break,
+            }
+        }
+        bounds.len() - 1
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn count(&self, text: &str) -> usize {
+        self.split
+            .find_iter(text)
+            .map(|m| self.count_piece(m.as_str().as_bytes()))
+            .sum()
+    }
+}
+
+// ── Approximations ───────────────────────────────────────────────
+
+/// Character-ratio approximation for models with unpublished vocabularies.
+///
+/// ASCII text is divided by a per-family chars/token ratio; other scripts
+/// (CJK, Cyrillic, emoji…) are charged one token per character, which is
+/// how every major vocabulary behaves for them in practice.
+pub struct ApproxTokenizer {
+    name: &'static str,
+    chars_per_token: f64,
+}
+
+impl ApproxTokenizer {
+    pub const fn new(name: &'static str, chars_per_token: f64) -> Self {
+        Self {
+            name,
+            chars_per_token,
+        }
+    }
+}
+
+impl Tokenizer for ApproxTokenizer {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn count(&self, text: &str) -> usize {
+        let (ascii, other) = text.chars().fold((0usize, 0usize), |(a, o), c| {
+            if c.is_ascii() {
+                (a + 1, o)
+            } else {
+                (a, o + 1)
+            }
+        });
+        (ascii as f64 / self.chars_per_token).ceil() as usize + other
+    }
+}
+
+/// The historical ~4 bytes/token heuristic, used for unknown models.
+pub struct HeuristicTokenizer;
+
+impl Tokenizer for HeuristicTokenizer {
+    fn name(&self) -> &str {
+        "heuristic"
+    }
+
+    fn count(&self, text: &str) -> usize {
+        text.len().div_ceil(4)
+    }
+}
+
+// ── Model lookup ─────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ModelFamily {
+    OpenAi(&'static str),
+    Anthropic,
+    Gemini,
+    Unknown,
+}
+
+/// Lowercased model id without any `provider/` routing prefix.
+fn bare_model(model: &str) -> String {
+    model
+        .rsplit('/')
+        .next()
+        .unwrap_or(model)
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn model_family(model: &str) -> ModelFamily {
+    let bare = bare_model(model);
+    let m = bare.as_str();
+
+    if m.contains("claude") {
+        ModelFamily::Anthropic
+    } else if m.starts_with("gemini") || m.starts_with("gemma") {
+        ModelFamily::Gemini
+    } else if m.starts_with("gpt-4o")
+        || m.starts_with("chatgpt-4o")
+        || m.starts_with("gpt-4.1")
+        || m.starts_with("gpt-4.5")
+        || m.starts_with("gpt-5")
+        || m.starts_with("gpt-oss")
+        || m.starts_with("o1")
+        || m.starts_with("o3")
+        || m.starts_with("o4")
+        || m.starts_with("codex")
+    {
+        ModelFamily::OpenAi("o200k_base")
+    } else if m.starts_with("gpt-4") || m.starts_with("gpt-3.5") || m.starts_with("text-embedding")
+    {
+        ModelFamily::OpenAi("cl100k_base")
+    } else {
+        ModelFamily::Unknown
+    }
+}
+
+/// Directory holding `.tiktoken` rank tables.
+pub fn tokenizer_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var("ZEROCLAW_TOKENIZER_DIR")
+        .ok()
+        .map(|d| d.trim().to_string())
+        .filter(|d| !d.is_empty())
+    {
+        return Some(PathBuf::from(dir));
+    }
+    UserDirs::new().map(|dirs| dirs.home_dir().join(".zeroclaw").join("tokenizers"))
+}
+
+/// Loaded rank tables by encoding name; `None` records a missing table.
+type BpeCache = Mutex<HashMap<&'static str, Option<Arc<BpeTokenizer>>>>;
+
+/// Load (once per process) the BPE table for an encoding, if available.
+fn bpe_for_encoding(encoding: &'static str) -> Option<Arc<BpeTokenizer>> {
+    static CACHE: OnceLock<BpeCache> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut cache = cache.lock();
+    cache
+        .entry(encoding)
+        .or_insert_with(|| {
+            let path = tokenizer_dir()?.join(format!("{encoding}.tiktoken"));
+            if !path.is_file() {
+                return None;
+            }
+            match BpeTokenizer::load(encoding, &path) {
+                Ok(tokenizer) => Some(Arc::new(tokenizer)),
+                Err(e) => {
+                    tracing::warn!(
+                        path = %path.display(),
+                        "Failed to load BPE rank table, using approximation: {e}"
+                    );
+                    None
+                }
+            }
+        })
+        .clone()
+}
+
+/// Best available tokenizer for `model`.
+pub fn tokenizer_for_model(model: &str) -> Arc<dyn Tokenizer> {
+    static ANTHROPIC: OnceLock<Arc<dyn Tokenizer>> = OnceLock::new();
+    static GEMINI: OnceLock<Arc<dyn Tokenizer>> = OnceLock::new();
+    static OPENAI: OnceLock<Arc<dyn Tokenizer>> = OnceLock::new();
+    static FALLBACK: OnceLock<Arc<dyn Tokenizer>> = OnceLock::new();
+
+    match model_family(model) {
+        ModelFamily::OpenAi(encoding) => match bpe_for_encoding(encoding) {
+            Some(bpe) => bpe,
+            None => OPENAI
+                .get_or_init(|| Arc::new(ApproxTokenizer::new("openai-approx", 4.0)))
+                .clone(),
+        },
+        ModelFamily::Anthropic => ANTHROPIC
+            .get_or_init(|| Arc::new(ApproxTokenizer::new("anthropic-approx", 3.5)))
+            .clone(),
+        ModelFamily::Gemini => GEMINI
+            .get_or_init(|| Arc::new(ApproxTokenizer::new("gemini-approx", 4.0)))
+            .clone(),
+        ModelFamily::Unknown => FALLBACK
+            .get_or_init(|| Arc::new(HeuristicTokenizer))
+            .clone(),
+    }
+}
+
+/// Published context window (in tokens) of well-known models.
+///
+/// Used when the provider itself does not report one; `None` means unknown.
+pub fn known_context_window(model: &str) -> Option<usize> {
+    let bare = bare_model(model);
+    let m = bare.as_str();
+
+    let window = if m.contains("claude") {
+        200_000
+    } else if m.starts_with("gpt-4.1") {
+        1_047_576
+    } else if m.starts_with("gpt-5") || m.starts_with("codex") {
+        400_000
+    } else if m.starts_with("gpt-4o")
+        || m.starts_with("chatgpt-4o")
+        || m.starts_with("gpt-4-turbo")
+        || m.starts_with("gpt-4.5")
+        || m.starts_with("gpt-oss")
+    {
+        128_000
+    } else if m.starts_with("o1") || m.starts_with("o3") || m.starts_with("o4") {
+        200_000
+    } else if m.starts_with("gpt-4-32k") {
+        32_768
+    } else if m.starts_with("gpt-4") {
+        8_192
+    } else if m.starts_with("gpt-3.5") {
+        16_385
+    } else if m.starts_with("gemini-1.5") || m.starts_with("gemini-2") || m.starts_with("gemini-3")
+    {
+        1_048_576
+    } else if m.starts_with("gemini") {
+        32_768
+    } else {
+        return None;
+    };
+    Some(window)
+}
+
+/// History token budget: the configured limit, tightened so it never
+/// exceeds the usable part of the model's context window.
+pub fn context_budget(configured: usize, context_window: Option<usize>) -> usize {
+    match context_window {
+        Some(window) if window > 0 => {
+            configured.min(window - window / CONTEXT_WINDOW_RESERVE_DIVISOR)
+        }
+        _ => configured,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn b64(bytes: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    }
+
+    /// A toy rank table: all lowercase letters and space, then a few merges.
+    fn toy_table() -> String {
+        let mut lines = Vec::new();
+        let mut rank = 0;
+        for byte in b" abcdefghijklmnopqrstuvwxyz" {
+            lines.push(format!("{} {rank}", b64(&[*byte])));
+            rank += 1;
+        }
+        for merge in [
+            "he", "ll", "hell", "hello", " w", "or", " wor", "ld", " world",
+        ] {
+            lines.push(format!("{} {rank}", b64(merge.as_bytes())));
+            rank += 1;
+        }
+        lines.join("\n")
+    }
+
+    #[test]
+    fn bpe_merges_by_rank() {
+        let bpe = BpeTokenizer::from_tiktoken("toy", &toy_table()).unwrap();
+        assert_eq!(bpe.name(), "toy");
+        assert_eq!(bpe.count("hello"), 1);
+        assert_eq!(bpe.count("hello world"), 2);
+        // "help" → "hel" can't fully merge: "hell" needs "ll", so he + l + p.
+        assert_eq!(bpe.count("help"), 3);
+        assert_eq!(bpe.count(""), 0);
+    }
+
+    #[test]
+    fn bpe_counts_unknown_bytes_individually() {
+        let bpe = BpeTokenizer::from_tiktoken("toy", &toy_table()).unwrap();
+        // Digits are absent from the table, so each stays a single-byte part.
+        assert_eq!(bpe.count("123"), 3);
+    }
+
+    #[test]
+    fn bpe_rejects_malformed_tables() {
+        assert!(BpeTokenizer::from_tiktoken("bad", "").is_err());
+        assert!(BpeTokenizer::from_tiktoken("bad", "not-base64! 1").is_err());
+        assert!(BpeTokenizer::from_tiktoken("bad", "aGk=").is_err());
+    }
+
+    #[test]
+    fn approx_charges_non_ascii_per_character() {
+        let tok = ApproxTokenizer::new("t", 4.0);
+        assert_eq!(tok.count("abcdefgh"), 2);
+        assert_eq!(tok.count("日本語"), 3);
+        assert_eq!(tok.count("ab日本"), 3);
+    }
+
+    #[test]
+    fn heuristic_matches_legacy_estimate() {
+        assert_eq!(HeuristicTokenizer.count("hello world"), 3);
+        let msgs = [ChatMessage::user("hello world")];
+        assert_eq!(count_messages(&HeuristicTokenizer, &msgs), 7);
+    }
+
+    #[test]
+    fn tokenizer_selection_follows_model_family() {
+        assert_eq!(
+            tokenizer_for_model("claude-sonnet-4-20250514").name(),
+            "anthropic-approx"
+        );
+        assert_eq!(
+            tokenizer_for_model("anthropic/claude-3-haiku").name(),
+            "anthropic-approx"
+        );
+        assert_eq!(
+            tokenizer_for_model("gemini-2.5-pro").name(),
+            "gemini-approx"
+        );
+        assert_eq!(tokenizer_for_model("llama3.1:8b").name(), "heuristic");
+
+        let openai = tokenizer_for_model("openai/gpt-4o");
+        assert!(matches!(openai.name(), "o200k_base" | "openai-approx"));
+        assert_eq!(
+            model_family("gpt-4-0613"),
+            ModelFamily::OpenAi("cl100k_base")
+        );
+    }
+
+    #[test]
+    fn known_context_windows() {
+        assert_eq!(
+            known_context_window("claude-opus-4-20250514"),
+            Some(200_000)
+        );
+        assert_eq!(known_context_window("openai/gpt-4o-mini"), Some(128_000));
+        assert_eq!(known_context_window("gpt-4"), Some(8_192));
+        assert_eq!(known_context_window("gemini-2.0-flash"), Some(1_048_576));
+        assert_eq!(known_context_window("qwen2.5-coder"), None);
+    }
+
+    #[test]
+    fn context_budget_respects_window_headroom() {
+        assert_eq!(context_budget(32_000, None), 32_000);
+        assert_eq!(context_budget(32_000, Some(200_000)), 32_000);
+        assert_eq!(context_budget(32_000, Some(8_192)), 6_144);
+        assert_eq!(context_budget(32_000, Some(0)), 32_000);
+    }
+}
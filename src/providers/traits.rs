@@ -50,7 +50,7 @@ pub struct ToolCall {
 }
 
 /// Raw token counts from a single LLM API response.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TokenUsage {
     pub input_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
@@ -60,11 +60,12 @@ pub struct TokenUsage {
 }
 
 /// An LLM response that may contain text, tool calls, or both.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChatResponse {
     /// Text content of the response (may be empty if only tool calls).
     pub text: Option<String>,
     /// Tool calls requested by the LLM.
+    #[serde(default)]
     pub tool_calls: Vec<ToolCall>,
     /// Token usage reported by the provider, if available.
     pub usage: Option<TokenUsage>,
@@ -216,6 +217,136 @@ pub enum StreamError {
     Io(#[from] std::io::Error),
 }
 
+/// A typed event from a streaming structured chat (see [`Provider::stream_chat`]).
+///
+/// The end of the stream marks the end of the response.
+#[derive(Debug, Clone)]
+pub enum ChatStreamEvent {
+    /// Incremental assistant text.
+    TextDelta(String),
+    /// Incremental reasoning/thinking content from thinking models.
+    ReasoningDelta(String),
+    /// A tool call began at position `index` of the response.
+    ToolCallStart {
+        index: usize,
+        id: String,
+        name: String,
+    },
+    /// A fragment of the JSON arguments for the tool call at `index`.
+    ToolCallDelta { index: usize, arguments: String },
+    /// Token usage. May arrive more than once; later values override
+    /// earlier ones field by field.
+    Usage(TokenUsage),
+}
+
+/// Boxed stream of structured chat events.
+pub type ChatEventStream = stream::BoxStream<'static, StreamResult<ChatStreamEvent>>;
+
+/// Folds [`ChatStreamEvent`]s back into a [`ChatResponse`].
+#[derive(Debug, Default)]
+pub struct ChatStreamAccumulator {
+    text: String,
+    reasoning: String,
+    tool_calls: Vec<ToolCall>,
+    usage: Option<TokenUsage>,
+}
+
+impl ChatStreamAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply one event.
+    pub fn push(&mut self, event: &ChatStreamEvent) {
+        match event {
+            ChatStreamEvent::TextDelta(delta) => self.text.push_str(delta),
+            ChatStreamEvent::ReasoningDelta(delta) => self.reasoning.push_str(delta),
+            ChatStreamEvent::ToolCallStart { index, id, name } => {
+                let call = self.tool_call_at(*index);
+                if !id.is_empty() {
+                    call.id.clone_from(id);
+                }
+                if !name.is_empty() {
+                    call.name.clone_from(name);
+                }
+            }
+            ChatStreamEvent::ToolCallDelta { index, arguments } => {
+                self.tool_call_at(*index).arguments.push_str(arguments);
+            }
+            ChatStreamEvent::Usage(usage) => {
+                let merged = self.usage.get_or_insert_with(TokenUsage::default);
+                merged.input_tokens = usage.input_tokens.or(merged.input_tokens);
+                merged.output_tokens = usage.output_tokens.or(merged.output_tokens);
+                merged.cached_input_tokens =
+                    usage.cached_input_tokens.or(merged.cached_input_tokens);
+            }
+        }
+    }
+
+    /// Finish the response. Tool calls without a name are dropped, missing
+    /// ids are generated and empty arguments become `{}`.
+    pub fn finish(self) -> ChatResponse {
+        let tool_calls = self
+            .tool_calls
+            .into_iter()
+            .filter(|call| !call.name.is_empty())
+            .map(|mut call| {
+                if call.id.is_empty() {
+                    call.id = uuid::Uuid::new_v4().to_string();
+                }
+                if call.arguments.trim().is_empty() {
+                    call.arguments = "{}".to_string();
+                }
+                call
+            })
+            .collect();
+        ChatResponse {
+            text: (!self.text.is_empty()).then_some(self.text),
+            tool_calls,
+            usage: self.usage,
+            reasoning_content: (!self.reasoning.is_empty()).then_some(self.reasoning),
+        }
+    }
+
+    fn tool_call_at(&mut self, index: usize) -> &mut ToolCall {
+        while self.tool_calls.len() <= index {
+            self.tool_calls.push(ToolCall {
+                id: String::new(),
+                name: String::new(),
+                arguments: String::new(),
+            });
+        }
+        &mut self.tool_calls[index]
+    }
+}
+
+/// Replay a complete response as the event sequence a streaming provider
+/// would have produced.
+pub fn chat_response_events(response: ChatResponse) -> Vec<ChatStreamEvent> {
+    let mut events = Vec::new();
+    if let Some(reasoning) = response.reasoning_content.filter(|r| !r.is_empty()) {
+        events.push(ChatStreamEvent::ReasoningDelta(reasoning));
+    }
+    if let Some(text) = response.text.filter(|t| !t.is_empty()) {
+        events.push(ChatStreamEvent::TextDelta(text));
+    }
+    for (index, call) in response.tool_calls.into_iter().enumerate() {
+        events.push(ChatStreamEvent::ToolCallStart {
+            index,
+            id: call.id,
+            name: call.name,
+        });
+        events.push(ChatStreamEvent::ToolCallDelta {
+            index,
+            arguments: call.arguments,
+        });
+    }
+    if let Some(usage) = response.usage {
+        events.push(ChatStreamEvent::Usage(usage));
+    }
+    events
+}
+
 /// Structured error returned when a requested capability is not supported.
 #[derive(Debug, Clone, thiserror::Error)]
 #[error("provider_capability_error provider={provider} capability={capability} message={message}")]
@@ -229,7 +360,8 @@ pub struct ProviderCapabilityError {
 ///
 /// Describes what features a provider supports, enabling intelligent
 /// adaptation of tool calling modes and request formatting.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ProviderCapabilities {
     /// Whether the provider supports native tool calling via API primitives.
     ///
@@ -243,6 +375,9 @@ pub struct ProviderCapabilities {
     /// Whether the provider supports prompt caching (Anthropic cache_control,
     /// OpenAI automatic prompt caching).
     pub prompt_caching: bool,
+    /// Context window of the loaded model in tokens, when the provider can
+    /// report it (e.g. a managed local server). `None` means unknown.
+    pub context_window: Option<usize>,
 }
 
 /// Provider-specific tool payload formats.
@@ -398,6 +533,16 @@ pub trait Provider: Send + Sync {
         self.capabilities().vision
     }
 
+    /// Context window (in tokens) for `model`, if known.
+    ///
+    /// Defaults to the window reported in [`ProviderCapabilities`], then to
+    /// the published window of well-known models.
+    fn context_window(&self, model: &str) -> Option<usize> {
+        self.capabilities()
+            .context_window
+            .or_else(|| super::tokenizer::known_context_window(model))
+    }
+
     /// Warm up the HTTP connection pool (TLS handshake, DNS, HTTP/2 setup).
     /// Default implementation is a no-op; providers with HTTP clients should override.
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -423,6 +568,34 @@ pub trait Provider: Send + Sync {
         })
     }
 
+    /// Whether `stream_chat` streams natively rather than replaying a
+    /// buffered `chat` call. Default implementation returns false.
+    fn supports_streaming_chat(&self) -> bool {
+        false
+    }
+
+    /// Whether a `hint:<name>` model is routed by this provider.
+    /// Default implementation returns false.
+    fn supports_route_hint(&self, _hint: &str) -> bool {
+        false
+    }
+
+    /// Streaming variant of `chat`: yields text deltas, tool-call argument
+    /// fragments, reasoning and usage as typed events.
+    ///
+    /// Errors establishing the request are returned directly so wrappers can
+    /// retry or fall back; errors mid-stream arrive as stream items.
+    /// Default implementation runs `chat` and replays the result as events.
+    async fn stream_chat(
+        &self,
+        request: ChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ChatEventStream> {
+        let response = self.chat(request, model, temperature).await?;
+        Ok(stream::iter(chat_response_events(response).into_iter().map(Ok)).boxed())
+    }
+
     /// Whether provider supports streaming responses.
     /// Default implementation returns false.
     fn supports_streaming(&self) -> bool {
@@ -509,6 +682,7 @@ mod tests {
                 native_tool_calling: true,
                 vision: true,
                 prompt_caching: false,
+                context_window: None,
             }
         }
 
@@ -626,16 +800,19 @@ mod tests {
             native_tool_calling: true,
             vision: false,
             prompt_caching: false,
+            context_window: None,
         };
         let caps2 = ProviderCapabilities {
             native_tool_calling: true,
             vision: false,
             prompt_caching: false,
+            context_window: None,
         };
         let caps3 = ProviderCapabilities {
             native_tool_calling: false,
             vision: false,
             prompt_caching: false,
+            context_window: None,
         };
 
         assert_eq!(caps1, caps2);
@@ -5,10 +5,66 @@
 //! - PDF ingestion (with `rag-pdf` feature)
 //! - Pin/alias tables (e.g. `red_led: 13`) for explicit lookup
 //! - Keyword retrieval (default) or semantic search via embeddings (optional)
+//!
+//! Chunk embeddings are cached per workspace in
+//! `state/datasheet_embeddings.json`, keyed by embedder fingerprint and chunk
+//! content hash, so only new or edited chunks are sent to the provider.
 
 use crate::memory::chunker;
-use std::collections::HashMap;
-use std::path::Path;
+use crate::memory::embeddings::EmbeddingProvider;
+use crate::memory::vector::cosine_similarity;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+/// Chunks sent to the embedding provider per request.
+const EMBED_BATCH_SIZE: usize = 32;
+
+/// Embedding cache file under `<workspace>/state/`.
+const EMBEDDING_CACHE_FILE: &str = "datasheet_embeddings.json";
+
+/// Chunk embeddings keyed by [`embedding_cache_key`].
+type EmbeddingCache = HashMap<String, Vec<f32>>;
+
+/// Embedding caches by cache file. Loaded from disk on first use and kept
+/// for the life of the process.
+static EMBEDDING_CACHE: LazyLock<parking_lot::Mutex<HashMap<PathBuf, EmbeddingCache>>> =
+    LazyLock::new(|| parking_lot::Mutex::new(HashMap::new()));
+
+/// `<embedder fingerprint>:<sha256 of the chunk>`.
+fn embedding_cache_key(fingerprint: &str, content: &str) -> String {
+    format!(
+        "{fingerprint}:{}",
+        hex::encode(Sha256::digest(content.as_bytes()))
+    )
+}
+
+fn read_embedding_cache(path: &Path) -> EmbeddingCache {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_embedding_cache(path: &Path, entries: &EmbeddingCache) {
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| {
+            let json = serde_json::to_vec(entries).map_err(std::io::Error::other)?;
+            std::fs::write(path, json)
+        });
+    if let Err(e) = result {
+        tracing::warn!(
+            "Failed to persist datasheet embeddings to {}: {e}",
+            path.display()
+        );
+    }
+}
+
+/// Similarity bonus for chunks tagged with one of the configured boards.
+const SEMANTIC_BOARD_BONUS: f32 = 0.1;
 
 /// A chunk of datasheet content with board metadata.
 #[derive(Debug, Clone)]
@@ -143,6 +199,16 @@ pub struct HardwareRag {
     chunks: Vec<DatasheetChunk>,
     /// Per-board pin aliases (board -> alias -> pin).
     pin_aliases: HashMap<String, PinAliases>,
+    /// Chunk embeddings when semantic retrieval is enabled.
+    semantic: Option<SemanticIndex>,
+    /// Where chunk embeddings are cached between runs.
+    embedding_cache: PathBuf,
+}
+
+/// Chunk embeddings, parallel to `HardwareRag::chunks`, and the provider that made them.
+struct SemanticIndex {
+    embedder: Arc<dyn EmbeddingProvider>,
+    embeddings: Vec<Vec<f32>>,
 }
 
 impl HardwareRag {
@@ -151,10 +217,13 @@ impl HardwareRag {
     /// Supports `## Pin Aliases` section for explicit alias→pin mapping.
     pub fn load(workspace_dir: &Path, datasheet_dir: &str) -> anyhow::Result<Self> {
         let base = workspace_dir.join(datasheet_dir);
+        let embedding_cache = workspace_dir.join("state").join(EMBEDDING_CACHE_FILE);
         if !base.exists() || !base.is_dir() {
             return Ok(Self {
                 chunks: Vec::new(),
                 pin_aliases: HashMap::new(),
+                semantic: None,
+                embedding_cache,
             });
         }
 
@@ -212,9 +281,138 @@ impl HardwareRag {
         Ok(Self {
             chunks,
             pin_aliases,
+            semantic: None,
+            embedding_cache,
         })
     }
 
+    /// Embed every chunk with `embedder` so retrieval ranks by meaning.
+    /// Chunks already in the workspace embedding cache are reused; the rest
+    /// are embedded in batches and added to it. On failure, logs a warning
+    /// and keeps keyword retrieval.
+    pub async fn with_embeddings(mut self, embedder: Arc<dyn EmbeddingProvider>) -> Self {
+        if embedder.dimensions() == 0 || self.chunks.is_empty() {
+            return self;
+        }
+
+        let fingerprint = embedder.fingerprint();
+        let keys: Vec<String> = self
+            .chunks
+            .iter()
+            .map(|c| embedding_cache_key(&fingerprint, &c.content))
+            .collect();
+        let mut cached: Vec<Option<Vec<f32>>> = {
+            let mut caches = EMBEDDING_CACHE.lock();
+            let cache = caches
+                .entry(self.embedding_cache.clone())
+                .or_insert_with(|| read_embedding_cache(&self.embedding_cache));
+            keys.iter().map(|key| cache.get(key).cloned()).collect()
+        };
+
+        let missing: Vec<usize> = (0..cached.len()).filter(|&i| cached[i].is_none()).collect();
+        for batch in missing.chunks(EMBED_BATCH_SIZE) {
+            let texts: Vec<&str> = batch
+                .iter()
+                .map(|&i| self.chunks[i].content.as_str())
+                .collect();
+            match embedder.embed(&texts).await {
+                Ok(vectors) if vectors.len() == texts.len() => {
+                    for (&i, vector) in batch.iter().zip(vectors) {
+                        cached[i] = Some(vector);
+                    }
+                }
+                Ok(vectors) => {
+                    tracing::warn!(
+                        expected = texts.len(),
+                        got = vectors.len(),
+                        "Datasheet embedding returned the wrong number of vectors; using keyword retrieval"
+                    );
+                    return self;
+                }
+                Err(e) => {
+                    tracing::warn!("Datasheet embedding failed; using keyword retrieval: {e}");
+                    return self;
+                }
+            }
+        }
+
+        let embeddings: Vec<Vec<f32>> = cached.into_iter().flatten().collect();
+        {
+            let mut caches = EMBEDDING_CACHE.lock();
+            let cache = caches.entry(self.embedding_cache.clone()).or_default();
+            let before = cache.len();
+            // Drop vectors of chunks that were edited or removed.
+            let current: HashSet<&String> = keys.iter().collect();
+            let prefix = format!("{fingerprint}:");
+            cache.retain(|key, _| !key.starts_with(&prefix) || current.contains(key));
+            let pruned = cache.len() != before;
+            for (key, embedding) in keys.iter().zip(&embeddings) {
+                cache.insert(key.clone(), embedding.clone());
+            }
+            if !missing.is_empty() || pruned {
+                write_embedding_cache(&self.embedding_cache, cache);
+            }
+        }
+
+        self.semantic = Some(SemanticIndex {
+            embedder,
+            embeddings,
+        });
+        self
+    }
+
+    /// Whether chunks were embedded and [`Self::retrieve_semantic`] ranks by similarity.
+    pub fn is_semantic(&self) -> bool {
+        self.semantic.is_some()
+    }
+
+    /// Retrieve chunks by embedding similarity to the query, favouring the
+    /// given boards. Falls back to [`Self::retrieve`] when chunks are not
+    /// embedded or the query cannot be embedded.
+    pub async fn retrieve_semantic(
+        &self,
+        query: &str,
+        boards: &[String],
+        limit: usize,
+    ) -> Vec<&DatasheetChunk> {
+        let Some(semantic) = &self.semantic else {
+            return self.retrieve(query, boards, limit);
+        };
+        if limit == 0 {
+            return Vec::new();
+        }
+        let query_embedding = match semantic.embedder.embed_one(query).await {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::warn!("Datasheet query embedding failed; using keyword retrieval: {e}");
+                return self.retrieve(query, boards, limit);
+            }
+        };
+
+        let mut scored: Vec<(&DatasheetChunk, f32)> = self
+            .chunks
+            .iter()
+            .zip(&semantic.embeddings)
+            .filter_map(|(chunk, embedding)| {
+                let similarity = cosine_similarity(&query_embedding, embedding);
+                if similarity <= 0.0 {
+                    return None;
+                }
+                let board_match = chunk.board.as_ref().is_some_and(|b| boards.contains(b));
+                let bonus = if board_match {
+                    SEMANTIC_BOARD_BONUS
+                } else {
+                    0.0
+                };
+                Some((chunk, similarity + bonus))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+        scored.into_iter().map(|(c, _)| c).collect()
+    }
+
     /// Get pin aliases for a board (e.g. "red_led" -> 13).
     pub fn pin_aliases_for_board(&self, board: &str) -> Option<&PinAliases> {
         self.pin_aliases.get(board)
@@ -392,4 +590,138 @@ Pin 13: LED
         let rag = HardwareRag::load(tmp.path(), "empty_ds").unwrap();
         assert!(rag.is_empty());
     }
+
+    /// Embeds text as counts of a few hardware words, so "blink" and "led"
+    /// land near the GPIO chunk without sharing keywords with it.
+    struct TopicEmbedding;
+
+    #[async_trait::async_trait]
+    impl EmbeddingProvider for TopicEmbedding {
+        fn name(&self) -> &str {
+            "topics"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|t| {
+                    let t = t.to_lowercase();
+                    let count = |words: &[&str]| words.iter().filter(|w| t.contains(*w)).count();
+                    vec![
+                        count(&["led", "blink", "light"]) as f32,
+                        count(&["clock", "timer", "pll"]) as f32,
+                    ]
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn hardware_rag_semantic_retrieval_ranks_by_embedding() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("board-a.md"), "# GPIO\nThe LED is on pin 13.").unwrap();
+        std::fs::write(base.join("board-b.md"), "# RCC\nThe PLL drives the timer.").unwrap();
+
+        let rag = HardwareRag::load(tmp.path(), "datasheets").unwrap();
+        assert!(!rag.is_semantic());
+        let boards = vec!["board-a".to_string(), "board-b".to_string()];
+        // No keyword overlap: keyword retrieval finds nothing.
+        assert!(rag.retrieve("make it blink", &boards, 5).is_empty());
+
+        let rag = rag.with_embeddings(Arc::new(TopicEmbedding)).await;
+        assert!(rag.is_semantic());
+        let chunks = rag.retrieve_semantic("make it blink", &boards, 5).await;
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].board.as_deref(), Some("board-a"));
+    }
+
+    /// [`TopicEmbedding`] that counts the texts it was asked to embed.
+    #[derive(Default)]
+    struct CountingEmbedding(std::sync::atomic::AtomicUsize);
+
+    #[async_trait::async_trait]
+    impl EmbeddingProvider for CountingEmbedding {
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            self.0
+                .fetch_add(texts.len(), std::sync::atomic::Ordering::SeqCst);
+            TopicEmbedding.embed(texts).await
+        }
+    }
+
+    #[tokio::test]
+    async fn hardware_rag_reuses_cached_chunk_embeddings() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("board-a.md"), "# GPIO\nThe LED is on pin 13.").unwrap();
+        std::fs::write(base.join("board-b.md"), "# RCC\nThe PLL drives the timer.").unwrap();
+        let embedder = Arc::new(CountingEmbedding::default());
+        let embedded = || embedder.0.load(std::sync::atomic::Ordering::SeqCst);
+
+        let rag = HardwareRag::load(tmp.path(), "datasheets")
+            .unwrap()
+            .with_embeddings(embedder.clone())
+            .await;
+        assert!(rag.is_semantic());
+        assert_eq!(embedded(), 2);
+        assert!(tmp.path().join("state").join(EMBEDDING_CACHE_FILE).exists());
+
+        let rag = HardwareRag::load(tmp.path(), "datasheets")
+            .unwrap()
+            .with_embeddings(embedder.clone())
+            .await;
+        assert!(rag.is_semantic());
+        assert_eq!(embedded(), 2, "unchanged chunks must not be re-embedded");
+
+        std::fs::write(base.join("board-b.md"), "# RCC\nThe clock feeds the PLL.").unwrap();
+        HardwareRag::load(tmp.path(), "datasheets")
+            .unwrap()
+            .with_embeddings(embedder.clone())
+            .await;
+        assert_eq!(embedded(), 3, "only the edited chunk is embedded");
+
+        // A fresh process reads the persisted vectors.
+        EMBEDDING_CACHE
+            .lock()
+            .remove(&tmp.path().join("state").join(EMBEDDING_CACHE_FILE));
+        let rag = HardwareRag::load(tmp.path(), "datasheets")
+            .unwrap()
+            .with_embeddings(embedder.clone())
+            .await;
+        assert_eq!(embedded(), 3);
+        let boards = vec!["board-a".to_string()];
+        let chunks = rag.retrieve_semantic("make it blink", &boards, 1).await;
+        assert_eq!(chunks[0].board.as_deref(), Some("board-a"));
+    }
+
+    #[tokio::test]
+    async fn hardware_rag_without_embedder_keeps_keyword_retrieval() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("datasheets");
+        std::fs::create_dir_all(&base).unwrap();
+        std::fs::write(base.join("board-a.md"), "# GPIO\nThe LED is on pin 13.").unwrap();
+
+        let rag = HardwareRag::load(tmp.path(), "datasheets")
+            .unwrap()
+            .with_embeddings(Arc::new(crate::memory::embeddings::NoopEmbedding))
+            .await;
+        assert!(!rag.is_semantic());
+        let boards = vec!["board-a".to_string()];
+        assert_eq!(rag.retrieve_semantic("led pin", &boards, 5).await.len(), 1);
+    }
 }
@@ -1,6 +1,8 @@
 pub mod docker;
 pub mod native;
 pub mod traits;
+#[cfg(feature = "runtime-wasm")]
+pub mod wasm;
 
 pub use docker::DockerRuntime;
 pub use native::NativeRuntime;
@@ -7,6 +7,10 @@
 //! - **No filesystem access**: by default, tools are pure computation
 //! - **No network access**: unless explicitly allowlisted hosts are configured
 //!
+//! [`WasmRuntime::execute_wasi`] runs WASI command modules (e.g. `kind = "wasm"`
+//! skill tools) with captured stdio, read-only preopens and HTTP limited to
+//! the allowed hosts.
+//!
 //! # Feature gate
 //! This module is only compiled when `--features runtime-wasm` is enabled.
 //! The default ZeroClaw binary excludes it to maintain the 4.6 MB size target.
@@ -49,6 +53,9 @@ pub struct WasmCapabilities {
     pub fuel_override: u64,
     /// Custom memory override in MB (0 = use config default)
     pub memory_override_mb: u64,
+    /// Directories preopened read-only for WASI modules, as
+    /// (guest name, canonical host path)
+    pub read_dirs: Vec<(String, PathBuf)>,
 }
 
 impl WasmRuntime {
@@ -107,6 +114,7 @@ impl WasmRuntime {
             allowed_hosts: self.config.allowed_hosts.clone(),
             fuel_override: 0,
             memory_override_mb: 0,
+            read_dirs: Vec::new(),
         }
     }
 
@@ -181,8 +189,8 @@ impl WasmRuntime {
         let mut store = Store::new(&engine, ());
         let fuel = self.effective_fuel(caps);
         if fuel > 0 {
-            store.set_fuel(fuel).with_context(|| {
-                format!("Failed to set fuel budget ({fuel}) for module: {module_name}")
+            store.set_fuel(fuel).map_err(|e| {
+                anyhow::anyhow!("Failed to set fuel budget ({fuel}) for module: {module_name}: {e}")
             })?;
         }
 
@@ -229,13 +237,38 @@ impl WasmRuntime {
         let fuel_consumed = fuel_before.saturating_sub(fuel_after);
 
         Ok(WasmExecutionResult {
-            stdout: String::new(),  // No WASI stdout yet — pure computation
+            stdout: String::new(), // No WASI stdout yet — pure computation
             stderr: String::new(),
             exit_code,
             fuel_consumed,
         })
     }
 
+    /// Execute a WASI (preview1) command module with `stdin` as its input.
+    ///
+    /// Unlike [`Self::execute_module`], stdout and stderr are captured,
+    /// `caps.read_dirs` are preopened read-only and `caps.allowed_hosts` are
+    /// reachable through the `zeroclaw.http_request(req_ptr, req_len,
+    /// resp_ptr, resp_cap) -> i32` import. Every other WASI call fails with
+    /// `ENOSYS`.
+    pub fn execute_wasi(
+        &self,
+        module_name: &str,
+        wasm_bytes: &[u8],
+        stdin: &[u8],
+        caps: &WasmCapabilities,
+    ) -> Result<WasmExecutionResult> {
+        let memory_bytes = usize::try_from(self.effective_memory_bytes(caps)).unwrap_or(usize::MAX);
+        wasi::run(
+            module_name,
+            wasm_bytes,
+            stdin,
+            self.effective_fuel(caps),
+            memory_bytes,
+            caps,
+        )
+    }
+
     /// Stub for when the `runtime-wasm` feature is not enabled.
     #[cfg(not(feature = "runtime-wasm"))]
     pub fn execute_module(
@@ -316,6 +349,698 @@ impl RuntimeAdapter for WasmRuntime {
     }
 }
 
+// ── WASI host ───────────────────────────────────────────────────
+
+/// Minimal WASI preview1 host on top of `wasmi`, used by [`WasmRuntime::execute_wasi`].
+mod wasi {
+    use super::{WasmCapabilities, WasmExecutionResult};
+    use anyhow::{bail, Context, Result};
+    use std::collections::HashMap;
+    use std::io::Read;
+    use std::path::{Component, Path, PathBuf};
+    use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+    use wasmi::core::TrapCode;
+    use wasmi::{
+        errors::LinkerError, Caller, Config, Engine, Extern, ExternType, Linker, Module, Store,
+        StoreLimits, StoreLimitsBuilder, Val,
+    };
+
+    /// Per-stream cap on captured stdout/stderr; extra output is dropped.
+    const MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+    const WASI: &str = "wasi_snapshot_preview1";
+    const HOST: &str = "zeroclaw";
+
+    /// Largest file a module may open through a read grant.
+    const MAX_READ_FILE_BYTES: u64 = 16 * 1024 * 1024;
+    const MAX_HTTP_BODY_BYTES: u64 = 1024 * 1024;
+    const MAX_HTTP_REQUESTS: u32 = 16;
+    const HTTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+    const ERRNO_SUCCESS: i32 = 0;
+    const ERRNO_BADF: i32 = 8;
+    const ERRNO_FAULT: i32 = 21;
+    const ERRNO_FBIG: i32 = 22;
+    const ERRNO_INVAL: i32 = 28;
+    const ERRNO_IO: i32 = 29;
+    const ERRNO_ISDIR: i32 = 31;
+    const ERRNO_NOENT: i32 = 44;
+    const ERRNO_NOSYS: i32 = 52;
+    const ERRNO_NOTDIR: i32 = 54;
+    const ERRNO_SPIPE: i32 = 70;
+    const ERRNO_NOTCAPABLE: i32 = 76;
+
+    const FILETYPE_CHARACTER_DEVICE: u8 = 2;
+    const FILETYPE_DIRECTORY: u8 = 3;
+    const FILETYPE_REGULAR_FILE: u8 = 4;
+
+    const OFLAGS_CREAT: u32 = 1;
+    const OFLAGS_DIRECTORY: u32 = 2;
+    const OFLAGS_EXCL: u32 = 4;
+    const OFLAGS_TRUNC: u32 = 8;
+    const RIGHTS_FD_WRITE: u64 = 1 << 6;
+    const FDFLAGS_APPEND: u32 = 1;
+
+    /// `zeroclaw.http_request` status codes (non-negative = response length).
+    const HTTP_DENIED: i32 = -1;
+    const HTTP_FAILED: i32 = -2;
+
+    const FIRST_PREOPEN_FD: u32 = 3;
+
+    type Errno = i32;
+
+    enum Handle {
+        Dir(PathBuf),
+        File { data: Vec<u8>, pos: usize },
+    }
+
+    struct HostState {
+        limits: StoreLimits,
+        stdin: Vec<u8>,
+        stdin_pos: usize,
+        stdout: Vec<u8>,
+        stderr: Vec<u8>,
+        /// Guest names of preopened directories, indexed from fd 3.
+        preopens: Vec<String>,
+        /// Canonical host roots every opened path must stay under.
+        roots: Vec<PathBuf>,
+        handles: HashMap<u32, Handle>,
+        next_fd: u32,
+        allowed_hosts: Vec<String>,
+        http_requests: u32,
+        started: Instant,
+    }
+
+    impl HostState {
+        fn new(stdin: &[u8], memory_bytes: usize, caps: &WasmCapabilities) -> Self {
+            let mut handles = HashMap::new();
+            let mut preopens = Vec::new();
+            let mut roots = Vec::new();
+            let mut fd = FIRST_PREOPEN_FD;
+            for (guest, host) in &caps.read_dirs {
+                handles.insert(fd, Handle::Dir(host.clone()));
+                preopens.push(guest.clone());
+                roots.push(host.clone());
+                fd += 1;
+            }
+            Self {
+                limits: StoreLimitsBuilder::new()
+                    .memory_size(memory_bytes)
+                    .instances(1)
+                    .build(),
+                stdin: stdin.to_vec(),
+                stdin_pos: 0,
+                stdout: Vec::new(),
+                stderr: Vec::new(),
+                preopens,
+                roots,
+                handles,
+                next_fd: fd,
+                allowed_hosts: caps.allowed_hosts.clone(),
+                http_requests: 0,
+                started: Instant::now(),
+            }
+        }
+
+        fn open_path(&mut self, dir_fd: u32, path: &str, oflags: u32) -> Result<u32, Errno> {
+            let Some(Handle::Dir(dir)) = self.handles.get(&dir_fd) else {
+                return Err(ERRNO_BADF);
+            };
+            let rel = Path::new(path);
+            if !rel
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+            {
+                return Err(ERRNO_NOTCAPABLE);
+            }
+            let resolved = dir.join(rel).canonicalize().map_err(|_| ERRNO_NOENT)?;
+            if !self.roots.iter().any(|root| resolved.starts_with(root)) {
+                return Err(ERRNO_NOTCAPABLE);
+            }
+
+            let metadata = std::fs::metadata(&resolved).map_err(|_| ERRNO_IO)?;
+            let handle = if metadata.is_dir() {
+                Handle::Dir(resolved)
+            } else if oflags & OFLAGS_DIRECTORY != 0 {
+                return Err(ERRNO_NOTDIR);
+            } else if metadata.len() > MAX_READ_FILE_BYTES {
+                return Err(ERRNO_FBIG);
+            } else {
+                Handle::File {
+                    data: std::fs::read(&resolved).map_err(|_| ERRNO_IO)?,
+                    pos: 0,
+                }
+            };
+
+            let fd = self.next_fd;
+            self.next_fd = self.next_fd.checked_add(1).ok_or(ERRNO_IO)?;
+            self.handles.insert(fd, handle);
+            Ok(fd)
+        }
+
+        fn http_request(&mut self, request: &[u8]) -> Result<Vec<u8>, i32> {
+            #[derive(serde::Deserialize)]
+            struct Request {
+                #[serde(default = "default_method")]
+                method: String,
+                url: String,
+                #[serde(default)]
+                headers: HashMap<String, String>,
+                #[serde(default)]
+                body: Option<String>,
+            }
+            fn default_method() -> String {
+                "GET".into()
+            }
+
+            let request: Request = serde_json::from_slice(request).map_err(|_| HTTP_DENIED)?;
+            let url = reqwest::Url::parse(&request.url).map_err(|_| HTTP_DENIED)?;
+            if !matches!(url.scheme(), "http" | "https") {
+                return Err(HTTP_DENIED);
+            }
+            let host = url.host_str().ok_or(HTTP_DENIED)?;
+            if !host_allowed(host, &self.allowed_hosts)
+                || crate::tools::http_request::is_private_or_local_host(host)
+            {
+                tracing::warn!(host, "WASM module requested a host it was not granted");
+                return Err(HTTP_DENIED);
+            }
+            if self.http_requests >= MAX_HTTP_REQUESTS {
+                return Err(HTTP_DENIED);
+            }
+            self.http_requests += 1;
+
+            let method =
+                reqwest::Method::from_bytes(request.method.to_ascii_uppercase().as_bytes())
+                    .map_err(|_| HTTP_DENIED)?;
+            // Redirects could leave the granted hosts, so they are not followed.
+            let client = reqwest::blocking::Client::builder()
+                .timeout(HTTP_TIMEOUT)
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .map_err(|_| HTTP_FAILED)?;
+            let mut builder = client.request(method, url);
+            for (name, value) in &request.headers {
+                builder = builder.header(name, value);
+            }
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+            let response = builder.send().map_err(|_| HTTP_FAILED)?;
+            let status = response.status().as_u16();
+            let mut body = Vec::new();
+            response
+                .take(MAX_HTTP_BODY_BYTES)
+                .read_to_end(&mut body)
+                .map_err(|_| HTTP_FAILED)?;
+
+            serde_json::to_vec(&serde_json::json!({
+                "status": status,
+                "body": String::from_utf8_lossy(&body),
+            }))
+            .map_err(|_| HTTP_FAILED)
+        }
+    }
+
+    /// Whether `host` matches one of the allowed host patterns
+    /// (`example.com` or `*.example.com`).
+    pub(super) fn host_allowed(host: &str, allowed: &[String]) -> bool {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        allowed.iter().any(|pattern| {
+            let pattern = pattern.trim().to_ascii_lowercase();
+            match pattern.strip_prefix("*.") {
+                Some(suffix) => host
+                    .strip_suffix(suffix)
+                    .is_some_and(|prefix| prefix.ends_with('.') && prefix.len() > 1),
+                None => host == pattern,
+            }
+        })
+    }
+
+    fn append_capped(buf: &mut Vec<u8>, bytes: &[u8]) {
+        let room = MAX_OUTPUT_BYTES.saturating_sub(buf.len());
+        buf.extend_from_slice(&bytes[..bytes.len().min(room)]);
+    }
+
+    // ── Guest memory helpers ─────────────────────────────────────────
+
+    fn slice(mem: &[u8], ptr: u32, len: u32) -> Result<&[u8], Errno> {
+        let start = ptr as usize;
+        let end = start.checked_add(len as usize).ok_or(ERRNO_FAULT)?;
+        mem.get(start..end).ok_or(ERRNO_FAULT)
+    }
+
+    fn slice_mut(mem: &mut [u8], ptr: u32, len: u32) -> Result<&mut [u8], Errno> {
+        let start = ptr as usize;
+        let end = start.checked_add(len as usize).ok_or(ERRNO_FAULT)?;
+        mem.get_mut(start..end).ok_or(ERRNO_FAULT)
+    }
+
+    fn read_u32(mem: &[u8], ptr: u32) -> Result<u32, Errno> {
+        let bytes = slice(mem, ptr, 4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn write_u32(mem: &mut [u8], ptr: u32, value: u32) -> Result<(), Errno> {
+        slice_mut(mem, ptr, 4)?.copy_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+
+    fn write_u64(mem: &mut [u8], ptr: u32, value: u64) -> Result<(), Errno> {
+        slice_mut(mem, ptr, 8)?.copy_from_slice(&value.to_le_bytes());
+        Ok(())
+    }
+
+    /// Resolve a WASI iovec array into (pointer, length) pairs.
+    fn iovecs(mem: &[u8], iovs: u32, count: u32) -> Result<Vec<(u32, u32)>, Errno> {
+        (0..count)
+            .map(|i| {
+                let at = iovs.checked_add(i.checked_mul(8).ok_or(ERRNO_FAULT)?);
+                let at = at.ok_or(ERRNO_FAULT)?;
+                Ok((read_u32(mem, at)?, read_u32(mem, at + 4)?))
+            })
+            .collect()
+    }
+
+    fn to_u32(len: usize) -> Result<u32, Errno> {
+        u32::try_from(len).map_err(|_| ERRNO_INVAL)
+    }
+
+    /// Run `f` against the guest's exported memory and host state,
+    /// flattening the outcome into a WASI errno.
+    fn with_memory(
+        caller: &mut Caller<'_, HostState>,
+        f: impl FnOnce(&mut [u8], &mut HostState) -> Result<(), Errno>,
+    ) -> i32 {
+        let Some(memory) = caller.get_export("memory").and_then(Extern::into_memory) else {
+            return ERRNO_NOSYS;
+        };
+        let (mem, state) = memory.data_and_store_mut(caller);
+        match f(mem, state) {
+            Ok(()) => ERRNO_SUCCESS,
+            Err(errno) => errno,
+        }
+    }
+
+    // ── Host functions ───────────────────────────────────────────────
+
+    #[allow(clippy::too_many_lines)]
+    fn link_wasi(linker: &mut Linker<HostState>) -> Result<()> {
+        // No arguments or environment are exposed to the module.
+        for (sizes, values) in [
+            ("args_sizes_get", "args_get"),
+            ("environ_sizes_get", "environ_get"),
+        ] {
+            linker.func_wrap(
+                WASI,
+                sizes,
+                |mut caller: Caller<'_, HostState>, count: u32, buf_size: u32| -> i32 {
+                    with_memory(&mut caller, |mem, _| {
+                        write_u32(mem, count, 0)?;
+                        write_u32(mem, buf_size, 0)
+                    })
+                },
+            )?;
+            linker.func_wrap(WASI, values, |_: Caller<'_, HostState>, _: u32, _: u32| {
+                ERRNO_SUCCESS
+            })?;
+        }
+
+        linker.func_wrap(
+            WASI,
+            "fd_write",
+            |mut caller: Caller<'_, HostState>, fd: u32, iovs: u32, count: u32, written: u32| {
+                with_memory(&mut caller, |mem, state| {
+                    let target = match fd {
+                        1 => &mut state.stdout,
+                        2 => &mut state.stderr,
+                        _ => return Err(ERRNO_BADF),
+                    };
+                    let mut total = 0usize;
+                    for (ptr, len) in iovecs(mem, iovs, count)? {
+                        append_capped(target, slice(mem, ptr, len)?);
+                        total += len as usize;
+                    }
+                    write_u32(mem, written, to_u32(total)?)
+                })
+            },
+        )?;
+
+        linker.func_wrap(
+            WASI,
+            "fd_read",
+            |mut caller: Caller<'_, HostState>, fd: u32, iovs: u32, count: u32, read: u32| {
+                with_memory(&mut caller, |mem, state| {
+                    let (source, pos) = match fd {
+                        0 => (&state.stdin, &mut state.stdin_pos),
+                        _ => match state.handles.get_mut(&fd) {
+                            Some(Handle::File { data, pos }) => (&*data, pos),
+                            Some(Handle::Dir(_)) => return Err(ERRNO_ISDIR),
+                            None => return Err(ERRNO_BADF),
+                        },
+                    };
+                    let mut total = 0usize;
+                    for (ptr, len) in iovecs(mem, iovs, count)? {
+                        let remaining = &source[(*pos).min(source.len())..];
+                        let n = remaining.len().min(len as usize);
+                        slice_mut(mem, ptr, to_u32(n)?)?.copy_from_slice(&remaining[..n]);
+                        *pos += n;
+                        total += n;
+                        if n < len as usize {
+                            break;
+                        }
+                    }
+                    write_u32(mem, read, to_u32(total)?)
+                })
+            },
+        )?;
+
+        linker.func_wrap(
+            WASI,
+            "fd_seek",
+            |mut caller: Caller<'_, HostState>, fd: u32, offset: i64, whence: u32, out: u32| {
+                with_memory(&mut caller, |mem, state| {
+                    let Some(Handle::File { data, pos }) = state.handles.get_mut(&fd) else {
+                        return Err(if fd <= 2 { ERRNO_SPIPE } else { ERRNO_BADF });
+                    };
+                    let base = match whence {
+                        0 => 0,
+                        1 => i64::try_from(*pos).map_err(|_| ERRNO_INVAL)?,
+                        2 => i64::try_from(data.len()).map_err(|_| ERRNO_INVAL)?,
+                        _ => return Err(ERRNO_INVAL),
+                    };
+                    let target = base.checked_add(offset).ok_or(ERRNO_INVAL)?;
+                    *pos = usize::try_from(target).map_err(|_| ERRNO_INVAL)?;
+                    write_u64(mem, out, target.unsigned_abs())
+                })
+            },
+        )?;
+
+        linker.func_wrap(
+            WASI,
+            "fd_close",
+            |mut caller: Caller<'_, HostState>, fd: u32| -> i32 {
+                if fd <= 2 || caller.data_mut().handles.remove(&fd).is_some() {
+                    ERRNO_SUCCESS
+                } else {
+                    ERRNO_BADF
+                }
+            },
+        )?;
+
+        linker.func_wrap(
+            WASI,
+            "fd_fdstat_get",
+            |mut caller: Caller<'_, HostState>, fd: u32, out: u32| {
+                with_memory(&mut caller, |mem, state| {
+                    let filetype = match (fd, state.handles.get(&fd)) {
+                        (0..=2, _) => FILETYPE_CHARACTER_DEVICE,
+                        (_, Some(Handle::Dir(_))) => FILETYPE_DIRECTORY,
+                        (_, Some(Handle::File { .. })) => FILETYPE_REGULAR_FILE,
+                        (_, None) => return Err(ERRNO_BADF),
+                    };
+                    let stat = slice_mut(mem, out, 24)?;
+                    stat.fill(0);
+                    stat[0] = filetype;
+                    // Rights are enforced by the host on each call, not advertised.
+                    stat[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+                    stat[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+                    Ok(())
+                })
+            },
+        )?;
+
+        linker.func_wrap(
+            WASI,
+            "fd_filestat_get",
+            |mut caller: Caller<'_, HostState>, fd: u32, out: u32| {
+                with_memory(&mut caller, |mem, state| {
+                    let (filetype, size) = match (fd, state.handles.get(&fd)) {
+                        (0..=2, _) => (FILETYPE_CHARACTER_DEVICE, 0),
+                        (_, Some(Handle::Dir(_))) => (FILETYPE_DIRECTORY, 0),
+                        (_, Some(Handle::File { data, .. })) => {
+                            (FILETYPE_REGULAR_FILE, data.len() as u64)
+                        }
+                        (_, None) => return Err(ERRNO_BADF),
+                    };
+                    let stat = slice_mut(mem, out, 64)?;
+                    stat.fill(0);
+                    stat[16] = filetype;
+                    stat[24..32].copy_from_slice(&1u64.to_le_bytes());
+                    stat[32..40].copy_from_slice(&size.to_le_bytes());
+                    Ok(())
+                })
+            },
+        )?;
+
+        linker.func_wrap(
+            WASI,
+            "fd_prestat_get",
+            |mut caller: Caller<'_, HostState>, fd: u32, out: u32| {
+                with_memory(&mut caller, |mem, state| {
+                    let idx = fd.checked_sub(FIRST_PREOPEN_FD).ok_or(ERRNO_BADF)?;
+                    let name = state.preopens.get(idx as usize).ok_or(ERRNO_BADF)?;
+                    let prestat = slice_mut(mem, out, 8)?;
+                    prestat.fill(0);
+                    prestat[4..8].copy_from_slice(&to_u32(name.len())?.to_le_bytes());
+                    Ok(())
+                })
+            },
+        )?;
+
+        linker.func_wrap(
+            WASI,
+            "fd_prestat_dir_name",
+            |mut caller: Caller<'_, HostState>, fd: u32, path: u32, len: u32| {
+                with_memory(&mut caller, |mem, state| {
+                    let idx = fd.checked_sub(FIRST_PREOPEN_FD).ok_or(ERRNO_BADF)?;
+                    let name = state.preopens.get(idx as usize).ok_or(ERRNO_BADF)?;
+                    if (len as usize) < name.len() {
+                        return Err(ERRNO_INVAL);
+                    }
+                    slice_mut(mem, path, to_u32(name.len())?)?.copy_from_slice(name.as_bytes());
+                    Ok(())
+                })
+            },
+        )?;
+
+        linker.func_wrap(
+            WASI,
+            "path_open",
+            |mut caller: Caller<'_, HostState>,
+             dir_fd: u32,
+             _dirflags: u32,
+             path: u32,
+             path_len: u32,
+             oflags: u32,
+             rights: u64,
+             _inheriting: u64,
+             fdflags: u32,
+             out: u32| {
+                with_memory(&mut caller, |mem, state| {
+                    if oflags & (OFLAGS_CREAT | OFLAGS_EXCL | OFLAGS_TRUNC) != 0
+                        || rights & RIGHTS_FD_WRITE != 0
+                        || fdflags & FDFLAGS_APPEND != 0
+                    {
+                        return Err(ERRNO_NOTCAPABLE);
+                    }
+                    let path = std::str::from_utf8(slice(mem, path, path_len)?)
+                        .map_err(|_| ERRNO_INVAL)?
+                        .to_string();
+                    let fd = state.open_path(dir_fd, &path, oflags)?;
+                    write_u32(mem, out, fd)
+                })
+            },
+        )?;
+
+        linker.func_wrap(
+            WASI,
+            "clock_time_get",
+            |mut caller: Caller<'_, HostState>, id: u32, _precision: u64, out: u32| {
+                with_memory(&mut caller, |mem, state| {
+                    let elapsed = match id {
+                        0 => SystemTime::now()
+                            .duration_since(UNIX_EPOCH)
+                            .unwrap_or_default(),
+                        1..=3 => state.started.elapsed(),
+                        _ => return Err(ERRNO_INVAL),
+                    };
+                    write_u64(
+                        mem,
+                        out,
+                        u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX),
+                    )
+                })
+            },
+        )?;
+
+        linker.func_wrap(
+            WASI,
+            "clock_res_get",
+            |mut caller: Caller<'_, HostState>, _id: u32, out: u32| {
+                with_memory(&mut caller, |mem, _| write_u64(mem, out, 1_000))
+            },
+        )?;
+
+        linker.func_wrap(
+            WASI,
+            "random_get",
+            |mut caller: Caller<'_, HostState>, buf: u32, len: u32| {
+                with_memory(&mut caller, |mem, _| {
+                    for chunk in slice_mut(mem, buf, len)?.chunks_mut(8) {
+                        let bytes = rand::random::<u64>().to_le_bytes();
+                        chunk.copy_from_slice(&bytes[..chunk.len()]);
+                    }
+                    Ok(())
+                })
+            },
+        )?;
+
+        linker.func_wrap(WASI, "sched_yield", |_: Caller<'_, HostState>| {
+            ERRNO_SUCCESS
+        })?;
+
+        linker.func_wrap(
+            WASI,
+            "proc_exit",
+            |_: Caller<'_, HostState>, code: i32| -> Result<(), wasmi::Error> {
+                Err(wasmi::Error::i32_exit(code))
+            },
+        )?;
+
+        linker.func_wrap(
+            HOST,
+            "http_request",
+            |mut caller: Caller<'_, HostState>,
+             req: u32,
+             req_len: u32,
+             resp: u32,
+             resp_cap: u32|
+             -> i32 {
+                let Some(memory) = caller.get_export("memory").and_then(Extern::into_memory) else {
+                    return HTTP_DENIED;
+                };
+                let (mem, state) = memory.data_and_store_mut(&mut caller);
+                let Ok(request) = slice(mem, req, req_len) else {
+                    return HTTP_DENIED;
+                };
+                let request = request.to_vec();
+                let response = match state.http_request(&request) {
+                    Ok(response) => response,
+                    Err(code) => return code,
+                };
+                let n = response.len().min(resp_cap as usize);
+                let Ok(n32) = to_u32(n) else {
+                    return HTTP_FAILED;
+                };
+                match slice_mut(mem, resp, n32) {
+                    Ok(out) => out.copy_from_slice(&response[..n]),
+                    Err(_) => return HTTP_DENIED,
+                }
+                i32::try_from(response.len()).unwrap_or(i32::MAX)
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Define every remaining WASI import the module asks for as an `ENOSYS`
+    /// stub, so toolchains that import more than we implement still load.
+    fn stub_unsupported_imports(linker: &mut Linker<HostState>, module: &Module) -> Result<()> {
+        for import in module.imports() {
+            if import.module() != WASI {
+                continue;
+            }
+            let ExternType::Func(ty) = import.ty() else {
+                continue;
+            };
+            let returns_errno = matches!(ty.results(), [wasmi::core::ValType::I32]);
+            let name = import.name().to_string();
+            let stub = move |_: Caller<'_, HostState>, _: &[Val], results: &mut [Val]| {
+                if returns_errno {
+                    results[0] = Val::I32(ERRNO_NOSYS);
+                    Ok(())
+                } else {
+                    Err(wasmi::Error::new(format!(
+                        "WASI call {name} is not supported"
+                    )))
+                }
+            };
+            match linker.func_new(WASI, import.name(), ty.clone(), stub) {
+                // Implemented by `link_wasi`.
+                Ok(_) | Err(LinkerError::DuplicateDefinition { .. }) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn run(
+        module_name: &str,
+        wasm_bytes: &[u8],
+        stdin: &[u8],
+        fuel: u64,
+        memory_bytes: usize,
+        caps: &WasmCapabilities,
+    ) -> Result<WasmExecutionResult> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm_bytes)
+            .with_context(|| format!("Failed to parse WASM module: {module_name}"))?;
+
+        let mut linker = Linker::new(&engine);
+        link_wasi(&mut linker)?;
+        stub_unsupported_imports(&mut linker, &module)?;
+
+        let mut store = Store::new(&engine, HostState::new(stdin, memory_bytes, caps));
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(fuel).map_err(|e| {
+            anyhow::anyhow!("Failed to set fuel budget ({fuel}) for module: {module_name}: {e}")
+        })?;
+
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .and_then(|pre| pre.start(&mut store))
+            .with_context(|| format!("Failed to instantiate WASM module: {module_name}"))?;
+        let entry = instance
+            .get_typed_func::<(), ()>(&store, "_start")
+            .with_context(|| {
+                format!("WASM module '{module_name}' must export a WASI `_start` function")
+            })?;
+
+        let exit_code = match entry.call(&mut store, ()) {
+            Ok(()) => 0,
+            Err(e) => {
+                if let Some(code) = e.i32_exit_status() {
+                    code
+                } else if e.as_trap_code() == Some(TrapCode::OutOfFuel) {
+                    let state = store.into_data();
+                    return Ok(WasmExecutionResult {
+                        stdout: String::from_utf8_lossy(&state.stdout).into_owned(),
+                        stderr: format!(
+                            "WASM module '{module_name}' exceeded fuel limit ({fuel} ticks) — likely an infinite loop"
+                        ),
+                        exit_code: -1,
+                        fuel_consumed: fuel,
+                    });
+                } else {
+                    bail!("WASM execution error in '{module_name}': {e}");
+                }
+            }
+        };
+
+        let fuel_consumed = fuel.saturating_sub(store.get_fuel().unwrap_or(0));
+        let state = store.into_data();
+        Ok(WasmExecutionResult {
+            stdout: String::from_utf8_lossy(&state.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&state.stderr).into_owned(),
+            exit_code,
+            fuel_consumed,
+        })
+    }
+}
+
 // ── Tests ───────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -379,7 +1104,10 @@ mod tests {
         let rt = WasmRuntime::new(default_config());
         let result = rt.build_shell_command("echo hello", Path::new("/tmp"));
         assert!(result.is_err());
-        assert!(result.unwrap_err().to_string().contains("does not support shell"));
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("does not support shell"));
     }
 
     #[test]
@@ -391,7 +1119,10 @@ mod tests {
     #[test]
     fn wasm_storage_path_with_workspace() {
         let rt = WasmRuntime::with_workspace(default_config(), PathBuf::from("/home/user/project"));
-        assert_eq!(rt.storage_path(), PathBuf::from("/home/user/project/.zeroclaw"));
+        assert_eq!(
+            rt.storage_path(),
+            PathBuf::from("/home/user/project/.zeroclaw")
+        );
     }
 
     // ── Config validation ──────────────────────────────────────
@@ -576,6 +1307,35 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn execute_wasi_captures_stdout_from_stdin() {
+        let bytes = std::fs::read(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/wasm/echo.wasm"),
+        )
+        .unwrap();
+        let rt = WasmRuntime::new(default_config());
+        let result = rt
+            .execute_wasi("echo", &bytes, b"ping", &WasmCapabilities::default())
+            .unwrap();
+        assert_eq!(result.exit_code, 0);
+        assert_eq!(result.stdout, "ping");
+        assert!(result.fuel_consumed > 0);
+    }
+
+    #[test]
+    fn host_allowed_matches_exact_and_wildcard_hosts() {
+        use super::wasi::host_allowed;
+
+        let allowed = vec!["api.example.com".to_string(), "*.CDN.test".to_string()];
+        assert!(host_allowed("api.example.com", &allowed));
+        assert!(host_allowed("API.Example.com.", &allowed));
+        assert!(host_allowed("img.cdn.test", &allowed));
+        assert!(!host_allowed("cdn.test", &allowed));
+        assert!(!host_allowed("evilcdn.test", &allowed));
+        assert!(!host_allowed("example.com", &allowed));
+        assert!(!host_allowed("api.example.com.evil.io", &allowed));
+    }
+
     // ── Feature gate check ─────────────────────────────────────
 
     #[test]
@@ -636,10 +1396,7 @@ mod tests {
         let rt = WasmRuntime::new(default_config());
         let caps = WasmCapabilities::default();
         let mem_bytes = rt.effective_memory_bytes(&caps);
-        assert!(
-            mem_bytes > 0,
-            "default memory limit must be > 0"
-        );
+        assert!(mem_bytes > 0, "default memory limit must be > 0");
         assert!(
             mem_bytes <= 4096 * 1024 * 1024,
             "default memory must not exceed 4 GB safety limit"
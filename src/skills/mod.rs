@@ -10,9 +10,11 @@ mod audit;
 pub mod read_skill;
 mod templates;
 mod tool_handler;
+mod wasm_tool;
 
 pub use read_skill::ReadSkillTool;
 pub use tool_handler::SkillToolHandler;
+pub use wasm_tool::WasmSkillTool;
 
 const OPEN_SKILLS_REPO_URL: &str = "https://github.com/besoeasy/open-skills";
 const OPEN_SKILLS_SYNC_MARKER: &str = ".zeroclaw-open-skills-sync";
@@ -49,12 +51,36 @@ pub struct Skill {
 pub struct SkillTool {
     pub name: String,
     pub description: String,
-    /// "shell", "http", "script"
+    /// "shell", "http", "script", "wasm"
     pub kind: String,
-    /// The command/URL/script to execute
+    /// The command/URL/script to execute (skill-relative `.wasm` path for `kind = "wasm"`)
     pub command: String,
     #[serde(default)]
     pub args: HashMap<String, String>,
+    /// Sandbox grants for `kind = "wasm"` tools (`[tools.capabilities]`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capabilities: Option<SkillToolCapabilities>,
+}
+
+/// Capabilities a `kind = "wasm"` skill tool requests from the sandbox.
+///
+/// Anything not granted here is unavailable to the module. Budgets are capped
+/// by the global `[wasm]` limits.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillToolCapabilities {
+    /// Hosts reachable through the `zeroclaw.http_request` import
+    /// (exact names, or `*.example.com` for subdomains).
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+    /// Skill-relative directories preopened read-only for the module.
+    #[serde(default)]
+    pub read_paths: Vec<String>,
+    /// Fuel budget per call (defaults to `[wasm].fuel_limit`).
+    #[serde(default)]
+    pub fuel: Option<u64>,
+    /// Linear memory ceiling per call in MiB (defaults to `[wasm].memory_limit_mb`).
+    #[serde(default)]
+    pub memory_mb: Option<u64>,
 }
 
 /// A prompt entry in SKILL.toml (`[[prompts]]` table array).
@@ -998,16 +1024,21 @@ pub fn skills_dir(workspace_dir: &Path) -> PathBuf {
 pub fn create_skill_tools(
     skills: &[Skill],
     security: std::sync::Arc<crate::security::SecurityPolicy>,
+    wasm: &crate::config::WasmConfig,
 ) -> Vec<Box<dyn crate::tools::Tool>> {
-    create_skill_tools_with_override(skills, security, None)
+    create_skill_tools_with_override(skills, security, wasm, None)
 }
 
 /// Create skill tools with an optional global `tool_choice_required` setting.
 /// When `global_override` is `Some(true)`, all skill tools use `tool_choice: "required"`.
 /// When `None` or `Some(false)`, skill tools use default behavior.
+///
+/// `kind = "wasm"` tools run in the in-process sandbox bounded by `wasm`;
+/// every other kind goes through [`SkillToolHandler`].
 pub fn create_skill_tools_with_override(
     skills: &[Skill],
     security: std::sync::Arc<crate::security::SecurityPolicy>,
+    wasm: &crate::config::WasmConfig,
     global_override: Option<bool>,
 ) -> Vec<Box<dyn crate::tools::Tool>> {
     let mut tools: Vec<Box<dyn crate::tools::Tool>> = Vec::new();
@@ -1015,19 +1046,39 @@ pub fn create_skill_tools_with_override(
 
     for skill in skills {
         for tool_def in &skill.tools {
-            match SkillToolHandler::new(
-                skill.name.clone(),
-                tool_def.clone(),
-                security.clone(),
-                force,
-            ) {
-                Ok(handler) => {
+            let created: Result<Box<dyn crate::tools::Tool>> =
+                if tool_def.kind.eq_ignore_ascii_case("wasm") {
+                    skill_dir(skill)
+                        .context("wasm skill tools need an installed skill directory")
+                        .and_then(|dir| {
+                            WasmSkillTool::new(
+                                skill.name.clone(),
+                                dir,
+                                tool_def.clone(),
+                                wasm,
+                                security.clone(),
+                                force,
+                            )
+                        })
+                        .map(|tool| Box::new(tool) as Box<dyn crate::tools::Tool>)
+                } else {
+                    SkillToolHandler::new(
+                        skill.name.clone(),
+                        tool_def.clone(),
+                        security.clone(),
+                        force,
+                    )
+                    .map(|handler| Box::new(handler) as Box<dyn crate::tools::Tool>)
+                };
+
+            match created {
+                Ok(tool) => {
                     tracing::debug!(
                         skill = %skill.name,
                         tool = %tool_def.name,
                         "Registered skill tool"
                     );
-                    tools.push(Box::new(handler));
+                    tools.push(tool);
                 }
                 Err(e) => {
                     tracing::warn!(
@@ -1044,6 +1095,11 @@ pub fn create_skill_tools_with_override(
     tools
 }
 
+/// Directory containing a loaded skill's manifest, if it came from disk.
+fn skill_dir(skill: &Skill) -> Option<&Path> {
+    skill.location.as_deref().and_then(Path::parent)
+}
+
 /// Initialize the skills directory with a README
 pub fn init_skills_dir(workspace_dir: &Path) -> Result<()> {
     let dir = skills_dir(workspace_dir);
@@ -2757,6 +2813,7 @@ command = "echo hello"
                 kind: "shell".to_string(),
                 command: "echo hi".to_string(),
                 args: HashMap::new(),
+                capabilities: None,
             }],
             prompts: vec!["Do the thing.".to_string()],
             location: Some(PathBuf::from("/tmp/workspace/skills/test/SKILL.md")),
@@ -2792,6 +2849,7 @@ command = "echo hello"
                 kind: "shell".to_string(),
                 command: "echo hi".to_string(),
                 args: HashMap::new(),
+                capabilities: None,
             }],
             prompts: vec!["Do the thing every time.".to_string()],
             location: Some(PathBuf::from("/tmp/workspace/skills/always-skill/SKILL.md")),
@@ -2959,6 +3017,54 @@ command = "https://api.example.com/deploy"
         assert_eq!(s.tools[2].kind, "http");
     }
 
+    #[test]
+    fn toml_skill_wasm_tool_registers_with_capabilities() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill_dir = dir.path().join("skills").join("weather");
+        fs::create_dir_all(skill_dir.join("data")).unwrap();
+        fs::write(skill_dir.join("tool.wasm"), b"\x00asm\x01\x00\x00\x00").unwrap();
+        fs::write(
+            skill_dir.join("SKILL.toml"),
+            r#"
+[skill]
+name = "weather"
+description = "Sandboxed weather lookup"
+
+[[tools]]
+name = "weather_lookup"
+description = "Current weather"
+kind = "wasm"
+command = "tool.wasm"
+
+[tools.capabilities]
+allowed_hosts = ["api.open-meteo.com"]
+read_paths = ["data"]
+fuel = 1000
+"#,
+        )
+        .unwrap();
+
+        let skills = load_skills(dir.path());
+        assert_eq!(skills.len(), 1);
+        let caps = skills[0].tools[0].capabilities.as_ref().unwrap();
+        assert_eq!(caps.allowed_hosts, vec!["api.open-meteo.com"]);
+        assert_eq!(caps.read_paths, vec!["data"]);
+        assert_eq!(caps.fuel, Some(1000));
+        assert_eq!(caps.memory_mb, None);
+
+        let security = std::sync::Arc::new(crate::security::SecurityPolicy::default());
+        let wasm = crate::config::WasmConfig::default();
+        let tools = create_skill_tools(&skills, security.clone(), &wasm);
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name(), "weather_lookup");
+
+        let disabled = crate::config::WasmConfig {
+            enabled: false,
+            ..crate::config::WasmConfig::default()
+        };
+        assert!(create_skill_tools(&skills, security, &disabled).is_empty());
+    }
+
     #[test]
     fn toml_skill_minimal() {
         let dir = tempfile::tempdir().unwrap();
@@ -3025,6 +3131,7 @@ description = "Bare minimum"
                 kind: "shell".to_string(),
                 command: "curl wttr.in".to_string(),
                 args: HashMap::new(),
+                capabilities: None,
             }],
             prompts: vec![],
             location: None,
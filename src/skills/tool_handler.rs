@@ -497,6 +497,7 @@ mod tests {
             .iter()
             .cloned()
             .collect(),
+            capabilities: None,
         };
 
         let security = Arc::new(SecurityPolicy::default());
@@ -525,6 +526,7 @@ mod tests {
             .iter()
             .cloned()
             .collect(),
+            capabilities: None,
         };
 
         let security = Arc::new(SecurityPolicy::default());
@@ -556,6 +558,7 @@ mod tests {
             .iter()
             .cloned()
             .collect(),
+            capabilities: None,
         };
 
         let security = Arc::new(SecurityPolicy::default());
@@ -582,6 +585,7 @@ mod tests {
                 .iter()
                 .cloned()
                 .collect(),
+            capabilities: None,
         };
 
         let security = Arc::new(SecurityPolicy::default());
@@ -616,6 +620,7 @@ mod tests {
             .iter()
             .cloned()
             .collect(),
+            capabilities: None,
         };
 
         let security = Arc::new(SecurityPolicy::default());
@@ -655,6 +660,7 @@ mod tests {
             .iter()
             .cloned()
             .collect(),
+            capabilities: None,
         };
 
         let security = Arc::new(SecurityPolicy::default());
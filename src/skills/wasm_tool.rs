@@ -0,0 +1,612 @@
+//! Sandboxed `kind = "wasm"` skill tools.
+//!
+//! A wasm skill tool is a WASI (preview1) command module shipped inside the
+//! skill directory. The model's JSON arguments are written to the module's
+//! stdin and whatever it prints to stdout becomes the tool result. Modules
+//! built from the `zeroclaw skill new` templates print
+//! `{"success": .., "output": .., "error": ..}`; plain text output is passed
+//! through as-is.
+//!
+//! ## Manifest
+//!
+//! ```toml
+//! [[tools]]
+//! name = "weather_lookup"
+//! description = "Current weather for a city"
+//! kind = "wasm"
+//! command = "tools/weather/tool.wasm"
+//! [tools.args]
+//! city = "City name"
+//! [tools.capabilities]
+//! allowed_hosts = ["api.open-meteo.com"]
+//! read_paths = ["data"]
+//! fuel = 50000000
+//! ```
+//!
+//! ## Security
+//!
+//! Modules run in the in-process `WasmRuntime` sandbox (`runtime::wasm`,
+//! feature `runtime-wasm`) with no ambient authority:
+//!
+//! - Filesystem: only `read_paths` are visible, preopened read-only
+//! - Network: only `allowed_hosts`, through the `zeroclaw.http_request` import
+//! - CPU and memory: `fuel` / `memory_mb`, capped by the `[wasm]` config
+//! - Every other WASI call fails with `ENOSYS`
+//!
+//! Grants are reviewed by `skills/audit.rs` before a skill is loaded.
+//!
+//! ## Host imports
+//!
+//! `zeroclaw.http_request(req_ptr, req_len, resp_ptr, resp_cap) -> i32` takes
+//! a JSON request `{"method", "url", "headers", "body"}` and writes
+//! `{"status", "body"}` into the response buffer. It returns the full
+//! response length (only `resp_cap` bytes are written), `-1` when the request
+//! is denied and `-2` when it fails. Redirects are not followed.
+
+use crate::config::WasmConfig;
+use crate::security::SecurityPolicy;
+use crate::skills::SkillTool;
+use crate::tools::traits::{Tool, ToolResult};
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+/// Largest module we are willing to load.
+const MAX_MODULE_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Everything a single module run is allowed to use.
+#[derive(Debug, Clone)]
+struct SandboxGrants {
+    fuel: u64,
+    memory_mb: u64,
+    /// Lowercased host patterns (`example.com` or `*.example.com`).
+    allowed_hosts: Vec<String>,
+    /// Preopened directories as (guest name, canonical host path).
+    read_dirs: Vec<(String, PathBuf)>,
+}
+
+/// A skill tool backed by a sandboxed WASI module.
+pub struct WasmSkillTool {
+    skill_name: String,
+    tool_def: SkillTool,
+    module_path: PathBuf,
+    grants: SandboxGrants,
+    security: Arc<SecurityPolicy>,
+    force_tool_use: bool,
+}
+
+impl WasmSkillTool {
+    /// Create a wasm tool for `tool_def`, resolving its module and grants
+    /// against `skill_dir` and the global `[wasm]` limits.
+    pub fn new(
+        skill_name: String,
+        skill_dir: &Path,
+        tool_def: SkillTool,
+        config: &WasmConfig,
+        security: Arc<SecurityPolicy>,
+        force_tool_use: bool,
+    ) -> Result<Self> {
+        if !tool_def.kind.eq_ignore_ascii_case("wasm") {
+            bail!("Unsupported tool kind '{}': expected wasm", tool_def.kind);
+        }
+        if !config.enabled {
+            bail!("WASM skill tools are disabled ([wasm].enabled = false)");
+        }
+
+        if !Path::new(&tool_def.command)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("wasm"))
+        {
+            bail!(
+                "wasm tool command must point at a .wasm module, got '{}'",
+                tool_def.command
+            );
+        }
+        let module_path = resolve_skill_path(skill_dir, &tool_def.command)?;
+        let metadata = std::fs::metadata(&module_path)
+            .with_context(|| format!("Failed to stat WASM module {}", module_path.display()))?;
+        if !metadata.is_file() {
+            bail!("WASM module {} is not a file", module_path.display());
+        }
+        if metadata.len() > MAX_MODULE_BYTES {
+            bail!(
+                "WASM module {} exceeds the {} MB size limit",
+                module_path.display(),
+                MAX_MODULE_BYTES / (1024 * 1024)
+            );
+        }
+
+        let caps = tool_def.capabilities.clone().unwrap_or_default();
+        let fuel = caps
+            .fuel
+            .map_or(config.fuel_limit, |fuel| fuel.min(config.fuel_limit));
+        if fuel == 0 {
+            bail!("wasm tool has a zero fuel budget");
+        }
+        let memory_mb = caps
+            .memory_mb
+            .map_or(config.memory_limit_mb, |mb| mb.min(config.memory_limit_mb));
+        if memory_mb == 0 {
+            bail!("wasm tool has a zero memory limit");
+        }
+
+        let mut read_dirs = Vec::with_capacity(caps.read_paths.len());
+        for raw in &caps.read_paths {
+            let host = resolve_skill_path(skill_dir, raw)?;
+            if !host.is_dir() {
+                bail!("read_paths entry '{raw}' is not a directory");
+            }
+            let guest = raw
+                .trim_start_matches("./")
+                .trim_end_matches('/')
+                .to_string();
+            read_dirs.push((guest, host));
+        }
+
+        Ok(Self {
+            skill_name,
+            module_path,
+            grants: SandboxGrants {
+                fuel,
+                memory_mb,
+                allowed_hosts: caps
+                    .allowed_hosts
+                    .iter()
+                    .map(|host| host.trim().to_ascii_lowercase())
+                    .collect(),
+                read_dirs,
+            },
+            tool_def,
+            security,
+            force_tool_use,
+        })
+    }
+
+    fn generate_schema(&self) -> serde_json::Value {
+        let mut names: Vec<&String> = self.tool_def.args.keys().collect();
+        names.sort();
+        let properties: serde_json::Map<String, serde_json::Value> = names
+            .into_iter()
+            .map(|name| {
+                (
+                    name.clone(),
+                    serde_json::json!({
+                        "type": "string",
+                        "description": self.tool_def.args[name],
+                    }),
+                )
+            })
+            .collect();
+
+        serde_json::json!({
+            "type": "object",
+            "properties": properties,
+        })
+    }
+}
+
+#[async_trait]
+impl Tool for WasmSkillTool {
+    fn name(&self) -> &str {
+        &self.tool_def.name
+    }
+
+    fn description(&self) -> &str {
+        &self.tool_def.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.generate_schema()
+    }
+
+    fn force_tool_use(&self) -> bool {
+        self.force_tool_use
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> Result<ToolResult> {
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                output: "Rate limit exceeded — try again later.".into(),
+                success: false,
+                error: None,
+            });
+        }
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                output: "Action limit exceeded — try again later.".into(),
+                success: false,
+                error: None,
+            });
+        }
+
+        let bytes = tokio::fs::read(&self.module_path).await.with_context(|| {
+            format!("Failed to read WASM module {}", self.module_path.display())
+        })?;
+        let input = serde_json::to_vec(&args).context("Failed to encode wasm tool arguments")?;
+        let name = self.tool_def.name.clone();
+        let grants = self.grants.clone();
+
+        tracing::debug!(
+            skill = %self.skill_name,
+            tool = %self.tool_def.name,
+            module = %self.module_path.display(),
+            fuel = grants.fuel,
+            "Executing wasm skill tool"
+        );
+
+        let outcome =
+            tokio::task::spawn_blocking(move || run_module(&name, &bytes, &input, &grants))
+                .await
+                .context("wasm skill tool task panicked")?;
+
+        match outcome {
+            Ok(result) => {
+                tracing::debug!(
+                    skill = %self.skill_name,
+                    tool = %self.tool_def.name,
+                    success = result.success,
+                    "Wasm skill tool execution completed"
+                );
+                Ok(result)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    skill = %self.skill_name,
+                    tool = %self.tool_def.name,
+                    error = %e,
+                    "Wasm skill tool failed"
+                );
+                Ok(ToolResult {
+                    success: false,
+                    output: format!("WASM tool failed: {e}"),
+                    error: Some(e.to_string()),
+                })
+            }
+        }
+    }
+}
+
+/// Resolve a manifest path relative to the skill directory, refusing
+/// absolute paths, `..` segments, and symlinks that lead outside the skill.
+fn resolve_skill_path(skill_dir: &Path, raw: &str) -> Result<PathBuf> {
+    let rel = Path::new(raw.trim());
+    if raw.trim().is_empty()
+        || !rel
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        bail!("'{raw}' must be a relative path inside the skill directory");
+    }
+
+    let root = skill_dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve skill directory {}", skill_dir.display()))?;
+    let resolved = root
+        .join(rel)
+        .canonicalize()
+        .with_context(|| format!("'{raw}' does not exist in the skill directory"))?;
+    if !resolved.starts_with(&root) {
+        bail!("'{raw}' resolves outside the skill directory");
+    }
+    Ok(resolved)
+}
+
+/// Run the module in the [`WasmRuntime`](crate::runtime::wasm::WasmRuntime)
+/// sandbox with exactly `grants`.
+#[cfg(feature = "runtime-wasm")]
+fn run_module(
+    name: &str,
+    bytes: &[u8],
+    input: &[u8],
+    grants: &SandboxGrants,
+) -> Result<ToolResult> {
+    use crate::config::WasmRuntimeConfig;
+    use crate::runtime::wasm::{WasmCapabilities, WasmRuntime};
+
+    // Grants are already capped by `[wasm]`, so they set the sandbox limits.
+    let runtime = WasmRuntime::new(WasmRuntimeConfig {
+        fuel_limit: grants.fuel,
+        memory_limit_mb: grants.memory_mb,
+        ..WasmRuntimeConfig::default()
+    });
+    let caps = WasmCapabilities {
+        allowed_hosts: grants.allowed_hosts.clone(),
+        read_dirs: grants.read_dirs.clone(),
+        ..WasmCapabilities::default()
+    };
+    let output = runtime.execute_wasi(name, bytes, input, &caps)?;
+    Ok(tool_result_from_output(
+        output.exit_code,
+        &output.stdout,
+        &output.stderr,
+    ))
+}
+
+#[cfg(not(feature = "runtime-wasm"))]
+fn run_module(
+    _name: &str,
+    _bytes: &[u8],
+    _input: &[u8],
+    _grants: &SandboxGrants,
+) -> Result<ToolResult> {
+    bail!(
+        "WASM runtime is not available in this build. \
+         Rebuild with `cargo build --features runtime-wasm` to run wasm skill tools."
+    )
+}
+
+/// Map captured module output onto a tool result.
+fn tool_result_from_output(exit_code: i32, stdout: &str, stderr: &str) -> ToolResult {
+    let stdout = crate::agent::loop_::scrub_credentials(stdout);
+    let stderr = crate::agent::loop_::scrub_credentials(stderr);
+
+    if exit_code != 0 {
+        let detail = if stderr.trim().is_empty() {
+            stdout.trim().to_string()
+        } else {
+            stderr.trim().to_string()
+        };
+        return ToolResult {
+            success: false,
+            output: format!("WASM tool exited with status {exit_code}:\n{detail}"),
+            error: Some(detail),
+        };
+    }
+
+    // Structured `{"success", "output", "error"}` envelope from the templates.
+    if let Ok(serde_json::Value::Object(envelope)) =
+        serde_json::from_str::<serde_json::Value>(stdout.trim())
+    {
+        if let Some(success) = envelope.get("success").and_then(serde_json::Value::as_bool) {
+            let output = match envelope.get("output") {
+                Some(serde_json::Value::String(text)) => text.clone(),
+                Some(serde_json::Value::Null) | None => String::new(),
+                Some(other) => other.to_string(),
+            };
+            let error = envelope
+                .get("error")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_string);
+            return ToolResult {
+                success,
+                output,
+                error,
+            };
+        }
+    }
+
+    ToolResult {
+        success: true,
+        output: stdout,
+        error: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::SkillToolCapabilities;
+
+    fn wasm_tool_def(command: &str, capabilities: Option<SkillToolCapabilities>) -> SkillTool {
+        SkillTool {
+            name: "wasm_tool".to_string(),
+            description: "Sandboxed tool".to_string(),
+            kind: "wasm".to_string(),
+            command: command.to_string(),
+            args: [("city".to_string(), "City name".to_string())]
+                .into_iter()
+                .collect(),
+            capabilities,
+        }
+    }
+
+    /// Skill directory holding a copy of `tests/fixtures/wasm/<fixture>.wasm`
+    /// as `tool.wasm`, plus `data/note.txt`.
+    fn skill_with_fixture(fixture: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let source = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/wasm")
+            .join(format!("{fixture}.wasm"));
+        std::fs::copy(source, dir.path().join("tool.wasm")).unwrap();
+        std::fs::create_dir_all(dir.path().join("data")).unwrap();
+        std::fs::write(dir.path().join("data/note.txt"), "hello from data").unwrap();
+        dir
+    }
+
+    fn new_tool(
+        dir: &Path,
+        capabilities: Option<SkillToolCapabilities>,
+        config: &WasmConfig,
+    ) -> Result<WasmSkillTool> {
+        WasmSkillTool::new(
+            "demo".to_string(),
+            dir,
+            wasm_tool_def("tool.wasm", capabilities),
+            config,
+            Arc::new(SecurityPolicy::default()),
+            false,
+        )
+    }
+
+    #[test]
+    fn resolve_skill_path_stays_inside_skill_dir() {
+        let dir = skill_with_fixture("echo");
+        assert!(resolve_skill_path(dir.path(), "tool.wasm").is_ok());
+        assert!(resolve_skill_path(dir.path(), "./data").is_ok());
+        assert!(resolve_skill_path(dir.path(), "../tool.wasm").is_err());
+        assert!(resolve_skill_path(dir.path(), "/etc/passwd").is_err());
+        assert!(resolve_skill_path(dir.path(), "").is_err());
+        assert!(resolve_skill_path(dir.path(), "missing.wasm").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolve_skill_path_rejects_symlink_escape() {
+        let outside = tempfile::tempdir().unwrap();
+        let dir = skill_with_fixture("echo");
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("escape")).unwrap();
+        let err = resolve_skill_path(dir.path(), "escape").unwrap_err();
+        assert!(err.to_string().contains("outside the skill directory"));
+    }
+
+    #[test]
+    fn new_caps_budgets_at_global_limits() {
+        let dir = skill_with_fixture("echo");
+        let config = WasmConfig {
+            fuel_limit: 1_000,
+            memory_limit_mb: 4,
+            ..WasmConfig::default()
+        };
+        let caps = SkillToolCapabilities {
+            allowed_hosts: vec!["API.Example.com".to_string()],
+            read_paths: vec!["data/".to_string()],
+            fuel: Some(5_000),
+            memory_mb: Some(2),
+        };
+        let tool = new_tool(dir.path(), Some(caps), &config).unwrap();
+        assert_eq!(tool.grants.fuel, 1_000);
+        assert_eq!(tool.grants.memory_mb, 2);
+        assert_eq!(tool.grants.allowed_hosts, vec!["api.example.com"]);
+        assert_eq!(tool.grants.read_dirs[0].0, "data");
+
+        let defaults = new_tool(dir.path(), None, &config).unwrap();
+        assert_eq!(defaults.grants.fuel, 1_000);
+        assert!(defaults.grants.read_dirs.is_empty());
+    }
+
+    #[test]
+    fn new_rejects_bad_definitions() {
+        let dir = skill_with_fixture("echo");
+        let config = WasmConfig::default();
+        let security = Arc::new(SecurityPolicy::default());
+
+        let disabled = WasmConfig {
+            enabled: false,
+            ..WasmConfig::default()
+        };
+        assert!(new_tool(dir.path(), None, &disabled).is_err());
+
+        for command in ["data/note.txt", "../tool.wasm", "missing.wasm"] {
+            let result = WasmSkillTool::new(
+                "demo".to_string(),
+                dir.path(),
+                wasm_tool_def(command, None),
+                &config,
+                security.clone(),
+                false,
+            );
+            assert!(result.is_err(), "{command} should be rejected");
+        }
+
+        let zero_fuel = SkillToolCapabilities {
+            fuel: Some(0),
+            ..SkillToolCapabilities::default()
+        };
+        assert!(new_tool(dir.path(), Some(zero_fuel), &config).is_err());
+
+        let bad_read = SkillToolCapabilities {
+            read_paths: vec!["../".to_string()],
+            ..SkillToolCapabilities::default()
+        };
+        assert!(new_tool(dir.path(), Some(bad_read), &config).is_err());
+    }
+
+    #[test]
+    fn schema_lists_manifest_args() {
+        let dir = skill_with_fixture("echo");
+        let tool = new_tool(dir.path(), None, &WasmConfig::default()).unwrap();
+        let schema = tool.parameters_schema();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["properties"]["city"]["description"], "City name");
+    }
+
+    #[test]
+    fn tool_result_reads_envelope_or_raw_output() {
+        let result =
+            tool_result_from_output(0, r#"{"success":false,"output":"","error":"bad city"}"#, "");
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("bad city"));
+
+        let result = tool_result_from_output(0, r#"{"success":true,"output":{"temp":21}}"#, "");
+        assert!(result.success);
+        assert_eq!(result.output, r#"{"temp":21}"#);
+
+        assert_eq!(
+            tool_result_from_output(0, "plain text", "").output,
+            "plain text"
+        );
+
+        let result = tool_result_from_output(2, "", "boom");
+        assert!(!result.success);
+        assert!(result.output.contains("status 2"));
+        assert_eq!(result.error.as_deref(), Some("boom"));
+    }
+
+    #[cfg(not(feature = "runtime-wasm"))]
+    #[tokio::test]
+    async fn execute_reports_missing_runtime() {
+        let dir = skill_with_fixture("echo");
+        let tool = new_tool(dir.path(), None, &WasmConfig::default()).unwrap();
+        let result = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.output.contains("runtime-wasm"));
+    }
+
+    #[cfg(feature = "runtime-wasm")]
+    #[tokio::test]
+    async fn execute_passes_arguments_on_stdin() {
+        let dir = skill_with_fixture("echo");
+        let tool = new_tool(dir.path(), None, &WasmConfig::default()).unwrap();
+        let result = tool
+            .execute(serde_json::json!({"city": "Oslo"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.output, r#"{"city":"Oslo"}"#);
+    }
+
+    #[cfg(feature = "runtime-wasm")]
+    #[tokio::test]
+    async fn execute_stops_at_fuel_budget() {
+        let dir = skill_with_fixture("spin");
+        let caps = SkillToolCapabilities {
+            fuel: Some(10_000),
+            ..SkillToolCapabilities::default()
+        };
+        let tool = new_tool(dir.path(), Some(caps), &WasmConfig::default()).unwrap();
+        let result = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.output.contains("fuel"), "{}", result.output);
+    }
+
+    #[cfg(feature = "runtime-wasm")]
+    #[tokio::test]
+    async fn execute_reports_proc_exit_status() {
+        let dir = skill_with_fixture("exit");
+        let tool = new_tool(dir.path(), None, &WasmConfig::default()).unwrap();
+        let result = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.output.contains("status 3"), "{}", result.output);
+    }
+
+    #[cfg(feature = "runtime-wasm")]
+    #[tokio::test]
+    async fn execute_reads_only_granted_paths() {
+        let dir = skill_with_fixture("cat");
+        let granted = SkillToolCapabilities {
+            read_paths: vec!["data".to_string()],
+            ..SkillToolCapabilities::default()
+        };
+        let tool = new_tool(dir.path(), Some(granted), &WasmConfig::default()).unwrap();
+        let result = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(result.success, "{result:?}");
+        assert_eq!(result.output, "hello from data");
+
+        // Without a grant there is no preopened fd 3, so path_open fails with EBADF.
+        let tool = new_tool(dir.path(), None, &WasmConfig::default()).unwrap();
+        let result = tool.execute(serde_json::json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.output.contains("status 8"), "{}", result.output);
+    }
+}
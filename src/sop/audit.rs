@@ -3,7 +3,7 @@ use std::sync::Arc;
 use anyhow::Result;
 use tracing::{info, warn};
 
-use super::types::{SopRun, SopStepResult};
+use super::types::{SopBranch, SopRun, SopStepResult};
 use crate::memory::traits::{Memory, MemoryCategory};
 
 const SOP_CATEGORY: &str = "sop";
@@ -12,7 +12,9 @@ const SOP_CATEGORY: &str = "sop";
 ///
 /// Storage keys:
 /// - `sop_run_{run_id}` — full `SopRun` JSON (created on start, updated on complete)
-/// - `sop_step_{run_id}_{step_number}` — `SopStepResult` JSON (one per step)
+/// - `sop_step_{run_id}_{step_number}` — `SopStepResult` JSON (first visit of a step)
+/// - `sop_step_{run_id}_{step_number}_v{visit}` — later visits in looping runs
+/// - `sop_branch_{run_id}_{seq}` — `SopBranch` JSON (goto / on_failure / bypass)
 pub struct SopAuditLogger {
     memory: Arc<dyn Memory>,
 }
@@ -34,14 +36,37 @@ impl SopAuditLogger {
         Ok(())
     }
 
-    /// Log a step result.
-    pub async fn log_step_result(&self, run_id: &str, result: &SopStepResult) -> Result<()> {
-        let key = step_key(run_id, result.step_number);
+    /// Log a step result. `visit` is 1-based: revisits of a step in a
+    /// non-linear run get their own key instead of overwriting the first.
+    pub async fn log_step_result(
+        &self,
+        run_id: &str,
+        result: &SopStepResult,
+        visit: usize,
+    ) -> Result<()> {
+        let key = step_key(run_id, result.step_number, visit);
         let content = serde_json::to_string_pretty(result)?;
         self.memory.store(&key, &content, category(), None).await?;
         Ok(())
     }
 
+    /// Log a non-sequential transition. `seq` is the branch's index in
+    /// `SopRun::branches`.
+    pub async fn log_branch(&self, run_id: &str, seq: usize, branch: &SopBranch) -> Result<()> {
+        let key = format!("sop_branch_{run_id}_{seq}");
+        let content = serde_json::to_string_pretty(branch)?;
+        self.memory.store(&key, &content, category(), None).await?;
+        info!(
+            "SOP audit: run {run_id} branched from step {} to {} ({})",
+            branch.from_step,
+            branch
+                .to_step
+                .map_or_else(|| "end".to_string(), |n| n.to_string()),
+            branch.kind
+        );
+        Ok(())
+    }
+
     /// Log run completion (updates the run record with final state).
     pub async fn log_run_complete(&self, run: &SopRun) -> Result<()> {
         let key = run_key(&run.run_id);
@@ -136,8 +161,12 @@ fn run_key(run_id: &str) -> String {
     format!("sop_run_{run_id}")
 }
 
-fn step_key(run_id: &str, step_number: u32) -> String {
-    format!("sop_step_{run_id}_{step_number}")
+fn step_key(run_id: &str, step_number: u32, visit: usize) -> String {
+    if visit <= 1 {
+        format!("sop_step_{run_id}_{step_number}")
+    } else {
+        format!("sop_step_{run_id}_{step_number}_v{visit}")
+    }
 }
 
 fn category() -> MemoryCategory {
@@ -166,6 +195,7 @@ mod tests {
             completed_at: None,
             step_results: Vec::new(),
             waiting_since: None,
+            branches: Vec::new(),
         }
     }
 
@@ -197,7 +227,7 @@ mod tests {
 
         // Log step result
         let step = test_step_result(1);
-        logger.log_step_result(&run.run_id, &step).await.unwrap();
+        logger.log_step_result(&run.run_id, &step, 1).await.unwrap();
 
         // Log run complete
         let mut completed_run = run.clone();
@@ -263,6 +293,47 @@ mod tests {
         assert!(timeout_keys[0].key.contains("run-test-001"));
     }
 
+    #[tokio::test]
+    async fn revisited_step_and_branch_get_distinct_keys() {
+        let mem_cfg = crate::config::MemoryConfig {
+            backend: "sqlite".into(),
+            ..crate::config::MemoryConfig::default()
+        };
+        let tmp = tempfile::tempdir().unwrap();
+        let memory: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+
+        let logger = SopAuditLogger::new(memory.clone());
+        let step = test_step_result(2);
+        logger.log_step_result("run-x", &step, 1).await.unwrap();
+        logger.log_step_result("run-x", &step, 2).await.unwrap();
+        logger
+            .log_branch(
+                "run-x",
+                0,
+                &SopBranch {
+                    from_step: 2,
+                    to_step: Some(1),
+                    kind: crate::sop::types::SopBranchKind::Goto,
+                    at: "2026-02-19T12:00:05Z".into(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let entries = memory.list(Some(&category()), None).await.unwrap();
+        let mut keys: Vec<_> = entries.iter().map(|e| e.key.as_str()).collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            vec![
+                "sop_branch_run-x_0",
+                "sop_step_run-x_2",
+                "sop_step_run-x_2_v2"
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn get_nonexistent_run_returns_none() {
         let mem_cfg = crate::config::MemoryConfig {
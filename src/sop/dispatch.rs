@@ -1,16 +1,22 @@
 //! Unified SOP event dispatch helpers.
 //!
-//! All event sources (MQTT, webhook, cron, peripheral) route through
-//! `dispatch_sop_event` so that locking, audit, and health bookkeeping
-//! happen in exactly one place.
+//! All event sources (MQTT, webhook, cron, peripheral, channel, file watch)
+//! route through `dispatch_sop_event` so that locking, audit, and health
+//! bookkeeping happen in exactly one place.
 
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
+use tokio::sync::broadcast::error::RecvError;
 use tracing::{debug, info, warn};
 
 use super::audit::SopAuditLogger;
-use super::engine::{now_iso8601, SopEngine};
-use super::types::{SopEvent, SopRun, SopRunAction, SopTriggerSource};
+use super::engine::{file_glob_matches, now_iso8601, SopEngine};
+use super::types::{SopEvent, SopRun, SopRunAction, SopTrigger, SopTriggerSource};
+use crate::channels::traits::ChannelMessage;
+use crate::peripherals::events::PeripheralEvent;
 
 // ── Dispatch result ─────────────────────────────────────────────
 
@@ -35,7 +41,7 @@ pub enum DispatchResult {
 // ── Action helpers ──────────────────────────────────────────────
 
 /// Extract the `run_id` from any `SopRunAction` variant.
-fn extract_run_id_from_action(action: &SopRunAction) -> &str {
+pub(crate) fn extract_run_id_from_action(action: &SopRunAction) -> &str {
     match action {
         SopRunAction::ExecuteStep { run_id, .. }
         | SopRunAction::WaitApproval { run_id, .. }
@@ -158,7 +164,7 @@ pub async fn dispatch_sop_event(
 /// approval timeout polling in the scheduler handles progression.
 /// For `ExecuteStep` actions, the run is started in the engine but steps
 /// cannot be executed without an agent loop — this is logged as a warning.
-pub async fn process_headless_results(results: &[DispatchResult]) {
+pub fn process_headless_results(results: &[DispatchResult]) {
     for result in results {
         match result {
             DispatchResult::Started {
@@ -219,6 +225,67 @@ pub async fn dispatch_peripheral_signal(
     dispatch_sop_event(engine, audit, event).await
 }
 
+/// Dispatch a board-originated event from the peripheral event bus.
+pub async fn dispatch_peripheral_event(
+    engine: &Arc<Mutex<SopEngine>>,
+    audit: &SopAuditLogger,
+    event: &PeripheralEvent,
+) -> Vec<DispatchResult> {
+    dispatch_peripheral_signal(
+        engine,
+        audit,
+        &event.board,
+        &event.signal,
+        event.payload.as_deref(),
+    )
+    .await
+}
+
+/// Run the peripheral event listener loop.
+///
+/// Forwards every event reported by a connected board (button presses,
+/// watched pins, sensor thresholds) to the SOP engine. Lagged events are
+/// logged and skipped; runs until the event bus closes.
+pub async fn run_peripheral_event_listener(
+    engine: Arc<Mutex<SopEngine>>,
+    audit: Arc<SopAuditLogger>,
+) {
+    let mut events = crate::peripherals::events::subscribe();
+    loop {
+        match events.recv().await {
+            Ok(event) => {
+                let results = dispatch_peripheral_event(&engine, &audit, &event).await;
+                process_headless_results(&results);
+            }
+            Err(RecvError::Lagged(skipped)) => {
+                warn!("SOP peripheral listener lagged, dropped {skipped} events");
+            }
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
+
+// ── Channel message helper ──────────────────────────────────────
+
+/// Convenience wrapper for channel message handlers.
+///
+/// Builds a `SopEvent` with source `Channel`, topic `"{channel}/{sender}"`
+/// and the message content as payload, then dispatches it through the
+/// standard path (cooldown and `max_concurrent` apply as usual).
+pub async fn dispatch_channel_message(
+    engine: &Arc<Mutex<SopEngine>>,
+    audit: &SopAuditLogger,
+    msg: &ChannelMessage,
+) -> Vec<DispatchResult> {
+    let event = SopEvent {
+        source: SopTriggerSource::Channel,
+        topic: Some(format!("{}/{}", msg.channel, msg.sender)),
+        payload: Some(msg.content.clone()),
+        timestamp: now_iso8601(),
+    };
+    dispatch_sop_event(engine, audit, event).await
+}
+
 // ── Cron SOP cache + check ──────────────────────────────────────
 
 /// Pre-parsed cron schedules for SOP triggers.
@@ -250,7 +317,7 @@ impl SopCronCache {
             for trigger in &sop.triggers {
                 if let super::types::SopTrigger::Cron { expression } = trigger {
                     // Normalize 5-field crontab to 6-field (prepend seconds)
-                    let normalized = match crate::cron::schedule::normalize_expression(expression) {
+                    let normalized = match crate::cron::normalize_expression(expression) {
                         Ok(n) => n,
                         Err(e) => {
                             warn!(
@@ -324,6 +391,157 @@ pub async fn check_sop_cron_triggers(
     all_results
 }
 
+// ── File-watch SOP state + check ────────────────────────────────
+
+/// Snapshot of workspace files covered by SOP `file_watch` triggers.
+///
+/// Built once at daemon startup. Files already present at build time do not
+/// fire; later passes diff modification times against the previous scan.
+pub struct SopFileWatchState {
+    workspace_dir: PathBuf,
+    globs: Vec<String>,
+    seen: HashMap<PathBuf, SystemTime>,
+}
+
+impl SopFileWatchState {
+    /// Build state from the current engine state and take the initial snapshot.
+    pub fn from_engine(engine: &Arc<Mutex<SopEngine>>, workspace_dir: &Path) -> Self {
+        let mut globs = Vec::new();
+        match engine.lock() {
+            Ok(eng) => {
+                for sop in eng.sops() {
+                    for trigger in &sop.triggers {
+                        if let SopTrigger::FileWatch { glob } = trigger {
+                            if !globs.contains(glob) {
+                                globs.push(glob.clone());
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => warn!("SopFileWatchState: engine lock poisoned: {e}"),
+        }
+
+        let mut state = Self {
+            workspace_dir: workspace_dir.to_path_buf(),
+            globs,
+            seen: HashMap::new(),
+        };
+        state.seen = state.scan();
+        info!(
+            "SopFileWatchState: watching {} glob(s), {} existing file(s)",
+            state.globs.len(),
+            state.seen.len()
+        );
+        state
+    }
+
+    /// Whether any SOP declares a `file_watch` trigger.
+    pub fn is_empty(&self) -> bool {
+        self.globs.is_empty()
+    }
+
+    /// Collect modification times for every file matching a watched glob.
+    /// Invalid globs are logged and skipped (fail-closed).
+    fn scan(&self) -> HashMap<PathBuf, SystemTime> {
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..glob::MatchOptions::new()
+        };
+        let mut files = HashMap::new();
+        for pattern in &self.globs {
+            let full = if Path::new(pattern).is_absolute() {
+                pattern.clone()
+            } else {
+                let base = glob::Pattern::escape(&self.workspace_dir.to_string_lossy());
+                format!("{base}/{pattern}")
+            };
+            let paths = match glob::glob_with(&full, options) {
+                Ok(p) => p,
+                Err(e) => {
+                    warn!("SopFileWatchState: invalid glob '{pattern}': {e}");
+                    continue;
+                }
+            };
+            for path in paths.flatten() {
+                let Ok(meta) = std::fs::metadata(&path) else {
+                    continue;
+                };
+                if meta.is_file() {
+                    files.insert(path, meta.modified().unwrap_or(SystemTime::UNIX_EPOCH));
+                }
+            }
+        }
+        files
+    }
+
+    /// Event topics for a path, in the form each covering glob was written:
+    /// workspace-relative for relative globs, absolute for absolute ones.
+    fn topics_for(&self, path: &Path) -> Vec<String> {
+        let absolute = path.to_string_lossy().into_owned();
+        let relative = path
+            .strip_prefix(&self.workspace_dir)
+            .ok()
+            .map(|p| p.to_string_lossy().into_owned());
+        let covered = |absolute_glob: bool, topic: &str| {
+            self.globs.iter().any(|glob| {
+                Path::new(glob).is_absolute() == absolute_glob && file_glob_matches(glob, topic)
+            })
+        };
+        let mut topics = Vec::new();
+        if let Some(relative) = relative.filter(|rel| covered(false, rel)) {
+            topics.push(relative);
+        }
+        if covered(true, &absolute) {
+            topics.push(absolute);
+        }
+        topics
+    }
+}
+
+/// Rescan watched globs and dispatch a `FileWatch` event for every file that
+/// was created, modified, or removed since the previous pass.
+pub async fn check_sop_file_triggers(
+    engine: &Arc<Mutex<SopEngine>>,
+    audit: &SopAuditLogger,
+    state: &mut SopFileWatchState,
+) -> Vec<DispatchResult> {
+    if state.is_empty() {
+        return Vec::new();
+    }
+
+    let current = state.scan();
+    let mut changes: Vec<(PathBuf, &'static str)> = Vec::new();
+    for (path, mtime) in &current {
+        match state.seen.get(path) {
+            None => changes.push((path.clone(), "created")),
+            Some(prev) if prev != mtime => changes.push((path.clone(), "modified")),
+            Some(_) => {}
+        }
+    }
+    for path in state.seen.keys() {
+        if !current.contains_key(path) {
+            changes.push((path.clone(), "removed"));
+        }
+    }
+    changes.sort();
+    state.seen = current;
+
+    let mut all_results = Vec::new();
+    for (path, change) in changes {
+        for topic in state.topics_for(&path) {
+            let event = SopEvent {
+                source: SopTriggerSource::FileWatch,
+                payload: Some(serde_json::json!({ "path": topic, "change": change }).to_string()),
+                topic: Some(topic),
+                timestamp: now_iso8601(),
+            };
+            all_results.extend(dispatch_sop_event(engine, audit, event).await);
+        }
+    }
+    all_results
+}
+
 // ── Tests ───────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -349,9 +567,13 @@ mod tests {
                 body: "Do step one".into(),
                 suggested_tools: vec![],
                 requires_confirmation: false,
+                condition: None,
+                on_failure: None,
+                goto: None,
             }],
             cooldown_secs: 0,
             max_concurrent: 2,
+            max_step_visits: 3,
             location: None,
         }
     }
@@ -607,6 +829,210 @@ mod tests {
         assert!(matches!(&results[0], DispatchResult::NoMatch));
     }
 
+    #[tokio::test]
+    async fn peripheral_event_applies_trigger_condition() {
+        let engine = test_engine(vec![test_sop(
+            "button-sop",
+            vec![SopTrigger::Peripheral {
+                board: "nucleo-f401re".into(),
+                signal: "button".into(),
+                condition: Some("> 0".into()),
+            }],
+        )]);
+        let audit = test_audit();
+        let event = |value: &str| PeripheralEvent {
+            board: "nucleo-f401re".into(),
+            signal: "button".into(),
+            payload: Some(value.into()),
+        };
+
+        let released = dispatch_peripheral_event(&engine, &audit, &event("0")).await;
+        assert!(matches!(&released[0], DispatchResult::NoMatch));
+
+        let pressed = dispatch_peripheral_event(&engine, &audit, &event("1")).await;
+        assert!(
+            matches!(&pressed[0], DispatchResult::Started { sop_name, .. } if sop_name == "button-sop")
+        );
+    }
+
+    fn channel_message(channel: &str, sender: &str, content: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: "msg-1".into(),
+            sender: sender.into(),
+            reply_target: sender.into(),
+            content: content.into(),
+            channel: channel.into(),
+            timestamp: 0,
+            thread_ts: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn channel_message_dispatches_to_matching_sop() {
+        let engine = test_engine(vec![test_sop(
+            "oncall",
+            vec![SopTrigger::Channel {
+                channel: Some("slack".into()),
+                sender: None,
+                pattern: Some("(?i)page me".into()),
+            }],
+        )]);
+        let audit = test_audit();
+
+        let results = dispatch_channel_message(
+            &engine,
+            &audit,
+            &channel_message("slack", "U42", "Please PAGE ME, disk full"),
+        )
+        .await;
+        assert!(
+            matches!(&results[0], DispatchResult::Started { sop_name, .. } if sop_name == "oncall")
+        );
+
+        let results =
+            dispatch_channel_message(&engine, &audit, &channel_message("slack", "U42", "lunch?"))
+                .await;
+        assert!(matches!(&results[0], DispatchResult::NoMatch));
+    }
+
+    #[tokio::test]
+    async fn channel_message_respects_max_concurrent() {
+        let mut sop = test_sop(
+            "oncall",
+            vec![SopTrigger::Channel {
+                channel: None,
+                sender: None,
+                pattern: Some("incident".into()),
+            }],
+        );
+        sop.max_concurrent = 1;
+        let engine = test_engine(vec![sop]);
+        let audit = test_audit();
+        let msg = channel_message("telegram", "alice", "incident in prod");
+
+        let first = dispatch_channel_message(&engine, &audit, &msg).await;
+        assert!(matches!(&first[0], DispatchResult::Started { .. }));
+
+        // First run is still active → second message is skipped
+        let second = dispatch_channel_message(&engine, &audit, &msg).await;
+        assert!(
+            matches!(&second[0], DispatchResult::Skipped { sop_name, .. } if sop_name == "oncall")
+        );
+    }
+
+    #[tokio::test]
+    async fn file_watch_fires_only_for_new_matching_files() {
+        let workspace = tempfile::tempdir().unwrap();
+        let drop_dir = workspace.path().join("drop");
+        std::fs::create_dir_all(&drop_dir).unwrap();
+        std::fs::write(drop_dir.join("old.log"), "existing").unwrap();
+
+        let engine = test_engine(vec![test_sop(
+            "log-drop",
+            vec![SopTrigger::FileWatch {
+                glob: "drop/*.log".into(),
+            }],
+        )]);
+        let audit = test_audit();
+        let mut state = SopFileWatchState::from_engine(&engine, workspace.path());
+        assert!(!state.is_empty());
+
+        // Nothing changed since the initial snapshot
+        let results = check_sop_file_triggers(&engine, &audit, &mut state).await;
+        assert!(results.is_empty());
+
+        std::fs::write(drop_dir.join("crash.log"), "boom").unwrap();
+        std::fs::write(drop_dir.join("notes.txt"), "ignored").unwrap();
+        let results = check_sop_file_triggers(&engine, &audit, &mut state).await;
+        assert_eq!(results.len(), 1);
+        match &results[0] {
+            DispatchResult::Started {
+                sop_name, run_id, ..
+            } => {
+                assert_eq!(sop_name, "log-drop");
+                let eng = engine.lock().unwrap();
+                let run = eng.get_run(run_id).unwrap();
+                assert_eq!(run.trigger_event.topic.as_deref(), Some("drop/crash.log"));
+                assert!(run
+                    .trigger_event
+                    .payload
+                    .as_deref()
+                    .unwrap()
+                    .contains("\"created\""));
+            }
+            other => panic!("Expected Started, got {other:?}"),
+        }
+
+        // Second pass with no changes dispatches nothing
+        let results = check_sop_file_triggers(&engine, &audit, &mut state).await;
+        assert!(results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn file_watch_matches_absolute_globs_inside_the_workspace() {
+        let workspace = tempfile::tempdir().unwrap();
+        let drop_dir = workspace.path().join("drop");
+        std::fs::create_dir_all(&drop_dir).unwrap();
+        let absolute_glob = format!(
+            "{}/*.log",
+            glob::Pattern::escape(&drop_dir.to_string_lossy())
+        );
+
+        let engine = test_engine(vec![
+            test_sop(
+                "absolute",
+                vec![SopTrigger::FileWatch {
+                    glob: absolute_glob,
+                }],
+            ),
+            test_sop(
+                "relative",
+                vec![SopTrigger::FileWatch {
+                    glob: "drop/*.log".into(),
+                }],
+            ),
+        ]);
+        let audit = test_audit();
+        let mut state = SopFileWatchState::from_engine(&engine, workspace.path());
+
+        let crash = drop_dir.join("crash.log");
+        std::fs::write(&crash, "boom").unwrap();
+        let results = check_sop_file_triggers(&engine, &audit, &mut state).await;
+        let mut started: Vec<(String, Option<String>)> = results
+            .iter()
+            .map(|result| match result {
+                DispatchResult::Started {
+                    sop_name, run_id, ..
+                } => {
+                    let eng = engine.lock().unwrap();
+                    let topic = eng.get_run(run_id).unwrap().trigger_event.topic.clone();
+                    (sop_name.clone(), topic)
+                }
+                other => panic!("Expected Started, got {other:?}"),
+            })
+            .collect();
+        started.sort();
+        assert_eq!(
+            started,
+            vec![
+                (
+                    "absolute".to_string(),
+                    Some(crash.to_string_lossy().into_owned())
+                ),
+                ("relative".to_string(), Some("drop/crash.log".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn file_watch_state_empty_without_triggers() {
+        let workspace = tempfile::tempdir().unwrap();
+        let engine = test_engine(vec![test_sop("manual-sop", vec![SopTrigger::Manual])]);
+        let state = SopFileWatchState::from_engine(&engine, workspace.path());
+        assert!(state.is_empty());
+    }
+
     #[test]
     fn cron_cache_skips_invalid_expression() {
         let sop = test_sop(
@@ -1,15 +1,16 @@
 use std::collections::HashMap;
 use std::fmt::Write as _;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Result};
 use tracing::{info, warn};
 
 use super::condition::evaluate_condition;
 use super::load_sops;
+use super::store;
 use super::types::{
-    Sop, SopEvent, SopPriority, SopRun, SopRunAction, SopRunStatus, SopStep, SopStepResult,
-    SopStepStatus, SopTrigger, SopTriggerSource,
+    Sop, SopBranch, SopBranchKind, SopEvent, SopPriority, SopResumePolicy, SopRun, SopRunAction,
+    SopRunStatus, SopStep, SopStepResult, SopStepStatus, SopTrigger, SopTriggerSource,
 };
 use crate::config::SopConfig;
 
@@ -19,8 +20,13 @@ pub struct SopEngine {
     active_runs: HashMap<String, SopRun>,
     /// Completed/failed/cancelled runs (kept for status queries).
     finished_runs: Vec<SopRun>,
+    /// Compiled `channel` trigger patterns, keyed by pattern source.
+    /// Invalid patterns are absent and never match.
+    channel_patterns: HashMap<String, regex::Regex>,
     config: SopConfig,
     run_counter: u64,
+    /// Workspace whose `sop/runs.db` mirrors run state (None = in-memory only).
+    persist_dir: Option<PathBuf>,
 }
 
 impl SopEngine {
@@ -30,21 +36,30 @@ impl SopEngine {
             sops: Vec::new(),
             active_runs: HashMap::new(),
             finished_runs: Vec::new(),
+            channel_patterns: HashMap::new(),
             config,
             run_counter: 0,
+            persist_dir: None,
         }
     }
 
     /// Load/reload SOPs from the configured directory.
     pub fn reload(&mut self, workspace_dir: &Path) {
-        self.sops = load_sops(
+        let sops = load_sops(
             workspace_dir,
             self.config.sops_dir.as_deref(),
             self.config.default_execution_mode,
         );
+        self.set_sops(sops);
         info!("SOP engine loaded {} SOPs", self.sops.len());
     }
 
+    /// Replace loaded SOPs and compile their `channel` trigger patterns.
+    fn set_sops(&mut self, sops: Vec<Sop>) {
+        self.channel_patterns = compile_channel_patterns(&sops);
+        self.sops = sops;
+    }
+
     /// Return all loaded SOP definitions.
     pub fn sops(&self) -> &[Sop] {
         &self.sops
@@ -74,7 +89,11 @@ impl SopEngine {
     pub fn match_trigger(&self, event: &SopEvent) -> Vec<&Sop> {
         self.sops
             .iter()
-            .filter(|sop| sop.triggers.iter().any(|t| trigger_matches(t, event)))
+            .filter(|sop| {
+                sop.triggers
+                    .iter()
+                    .any(|t| trigger_matches(t, event, &self.channel_patterns))
+            })
             .collect()
     }
 
@@ -154,26 +173,15 @@ impl SopEngine {
             completed_at: None,
             step_results: Vec::new(),
             waiting_since: None,
+            branches: Vec::new(),
         };
 
         self.active_runs.insert(run_id.clone(), run);
 
         info!("SOP run {} started for '{}'", run_id, sop_name);
 
-        // Determine first action based on execution mode
-        let step = sop.steps[0].clone();
-        let context = format_step_context(&sop, &self.active_runs[&run_id], &step);
-        let action = resolve_step_action(&sop, &step, run_id.clone(), context);
-
-        // If the action is WaitApproval, update run status and record timestamp
-        if matches!(action, SopRunAction::WaitApproval { .. }) {
-            if let Some(run) = self.active_runs.get_mut(&run_id) {
-                run.status = SopRunStatus::WaitingApproval;
-                run.waiting_since = Some(now_iso8601());
-            }
-        }
-
-        Ok(action)
+        // Step 1 may itself be bypassed by its condition
+        Ok(self.route_to_step(&run_id, &sop, 1))
     }
 
     /// Report the result of the current step and advance the run.
@@ -193,41 +201,42 @@ impl SopEngine {
 
         // Record step result
         run.step_results.push(result.clone());
+        let current = run.current_step;
+        let step = sop.steps.iter().find(|s| s.number == current);
 
         // Check if step failed
         if result.status == SopStepStatus::Failed {
+            if let Some(target) = step.and_then(|s| s.on_failure) {
+                info!("SOP run {run_id}: step {current} failed, branching to step {target}");
+                run.branches.push(SopBranch {
+                    from_step: current,
+                    to_step: Some(target),
+                    kind: SopBranchKind::OnFailure,
+                    at: now_iso8601(),
+                });
+                return Ok(self.route_to_step(run_id, &sop, target));
+            }
             let reason = format!("Step {} failed: {}", result.step_number, result.output);
             warn!("SOP run {run_id}: {reason}");
             return Ok(self.finish_run(run_id, SopRunStatus::Failed, Some(reason)));
         }
 
-        // Advance to next step
-        let next_step_num = run.current_step + 1;
-        if next_step_num > run.total_steps {
-            // All steps completed
-            info!("SOP run {run_id} completed successfully");
-            return Ok(self.finish_run(run_id, SopRunStatus::Completed, None));
-        }
-
-        // Update run state
-        let run = self.active_runs.get_mut(run_id).unwrap();
-        run.current_step = next_step_num;
-
-        let step_idx = (next_step_num - 1) as usize;
-        let step = sop.steps[step_idx].clone();
-        let context = format_step_context(&sop, run, &step);
-        let run_id_str = run_id.to_string();
-        let action = resolve_step_action(&sop, &step, run_id_str.clone(), context);
-
-        // If the action is WaitApproval, update run status and record timestamp
-        if matches!(action, SopRunAction::WaitApproval { .. }) {
-            if let Some(run) = self.active_runs.get_mut(&run_id_str) {
-                run.status = SopRunStatus::WaitingApproval;
-                run.waiting_since = Some(now_iso8601());
+        // Follow an explicit goto, otherwise advance sequentially
+        let next = match step.and_then(|s| s.goto) {
+            Some(target) => {
+                info!("SOP run {run_id}: step {current} done, branching to step {target}");
+                run.branches.push(SopBranch {
+                    from_step: current,
+                    to_step: Some(target),
+                    kind: SopBranchKind::Goto,
+                    at: now_iso8601(),
+                });
+                target
             }
-        }
+            None => current + 1,
+        };
 
-        Ok(action)
+        Ok(self.route_to_step(run_id, &sop, next))
     }
 
     /// Cancel an active run.
@@ -267,6 +276,7 @@ impl SopEngine {
         let step_idx = (run.current_step - 1) as usize;
         let step = sop.steps[step_idx].clone();
         let context = format_step_context(&sop, run, &step);
+        self.persist_run(run_id);
 
         Ok(SopRunAction::ExecuteStep {
             run_id: run_id.to_string(),
@@ -283,6 +293,68 @@ impl SopEngine {
             .collect()
     }
 
+    // ── Persistence ───────────────────────────────────────────────
+
+    /// Mirror run state to `<workspace>/sop/runs.db` and rehydrate runs left
+    /// over from a previous process. Call after `reload()` so SOP definitions
+    /// are available.
+    ///
+    /// Finished runs are restored for status queries and cooldowns. Active
+    /// runs are handled per `policy`; the returned actions must be dispatched
+    /// exactly like those from `start_run` (runs whose SOP is no longer
+    /// loaded are always interrupted and reported as `Failed`).
+    pub fn enable_persistence(
+        &mut self,
+        workspace_dir: &Path,
+        policy: SopResumePolicy,
+    ) -> Result<Vec<SopRunAction>> {
+        let limit = match self.config.max_finished_runs {
+            0 => usize::MAX,
+            max => max,
+        };
+        let finished = store::load_finished_runs(workspace_dir, limit)?;
+        let active = store::load_active_runs(workspace_dir)?;
+        self.persist_dir = Some(workspace_dir.to_path_buf());
+
+        self.finished_runs.extend(finished);
+        let mut actions = Vec::new();
+        for run in active {
+            let run_id = run.run_id.clone();
+            let sop = self.get_sop(&run.sop_name).cloned();
+            self.active_runs.insert(run_id.clone(), run);
+
+            let action = match (policy, sop) {
+                (_, None) => {
+                    warn!("SOP run {run_id}: SOP no longer loaded, marking interrupted");
+                    self.finish_run(
+                        &run_id,
+                        SopRunStatus::Interrupted,
+                        Some("SOP no longer loaded after restart".into()),
+                    )
+                }
+                (SopResumePolicy::Interrupt, Some(_)) => {
+                    info!("SOP run {run_id}: interrupted by restart");
+                    self.finish_run(
+                        &run_id,
+                        SopRunStatus::Interrupted,
+                        Some("Interrupted by daemon restart".into()),
+                    )
+                }
+                (SopResumePolicy::Resume, Some(sop)) => {
+                    info!("SOP run {run_id}: resuming at current step");
+                    self.rehydrate_step(&run_id, &sop, false)
+                }
+                (SopResumePolicy::Rerequest, Some(sop)) => {
+                    info!("SOP run {run_id}: re-requesting approval after restart");
+                    self.rehydrate_step(&run_id, &sop, true)
+                }
+            };
+            actions.push(action);
+        }
+
+        Ok(actions)
+    }
+
     // ── Approval timeout ──────────────────────────────────────────
 
     /// Check all WaitingApproval runs for timeout. For Critical/High-priority SOPs,
@@ -341,11 +413,124 @@ impl SopEngine {
     /// Replace loaded SOPs (for testing from other modules).
     #[cfg(test)]
     pub(crate) fn set_sops_for_test(&mut self, sops: Vec<Sop>) {
-        self.sops = sops;
+        self.set_sops(sops);
     }
 
     // ── Internal helpers ────────────────────────────────────────
 
+    /// Move an active run to step `target` and return the action for it.
+    ///
+    /// Steps whose condition does not hold against prior results are bypassed
+    /// (recorded as `ConditionNotMet` branches). A target past the last step
+    /// completes the run; a step that would exceed `max_step_visits` fails it.
+    fn route_to_step(&mut self, run_id: &str, sop: &Sop, mut target: u32) -> SopRunAction {
+        loop {
+            let run = self.active_runs.get_mut(run_id).unwrap();
+
+            let Some(step) = sop.steps.iter().find(|s| s.number == target) else {
+                if target == 0 {
+                    let reason = "Branch target 0 is not a valid step".to_string();
+                    warn!("SOP run {run_id}: {reason}");
+                    return self.finish_run(run_id, SopRunStatus::Failed, Some(reason));
+                }
+                info!("SOP run {run_id} completed successfully");
+                return self.finish_run(run_id, SopRunStatus::Completed, None);
+            };
+
+            if run.step_visits(target) >= sop.max_step_visits as usize {
+                let reason = format!(
+                    "Step {target} exceeded max_step_visits ({})",
+                    sop.max_step_visits
+                );
+                warn!("SOP run {run_id}: {reason}");
+                return self.finish_run(run_id, SopRunStatus::Failed, Some(reason));
+            }
+
+            if let Some(ref cond) = step.condition {
+                if !evaluate_condition(cond, Some(&run.results_payload())) {
+                    let next = target + 1;
+                    info!("SOP run {run_id}: step {target} condition not met, bypassing");
+                    run.branches.push(SopBranch {
+                        from_step: target,
+                        to_step: (next <= run.total_steps).then_some(next),
+                        kind: SopBranchKind::ConditionNotMet,
+                        at: now_iso8601(),
+                    });
+                    target = next;
+                    continue;
+                }
+            }
+
+            run.current_step = target;
+            let is_first = run.step_results.is_empty();
+            let context = format_step_context(sop, run, step);
+            let action = resolve_step_action(sop, step, is_first, run_id.to_string(), context);
+
+            // If the action is WaitApproval, update run status and record timestamp
+            if matches!(action, SopRunAction::WaitApproval { .. }) {
+                run.status = SopRunStatus::WaitingApproval;
+                run.waiting_since = Some(now_iso8601());
+            }
+
+            self.persist_run(run_id);
+            return action;
+        }
+    }
+
+    /// Re-issue the current step of a rehydrated run. With `rerequest` the
+    /// run is put behind a fresh approval gate; otherwise a run that was
+    /// waiting keeps its original `waiting_since` so timeouts still apply.
+    fn rehydrate_step(&mut self, run_id: &str, sop: &Sop, rerequest: bool) -> SopRunAction {
+        let run = self.active_runs.get_mut(run_id).unwrap();
+        let Some(step) = sop.steps.iter().find(|s| s.number == run.current_step) else {
+            let reason = format!("Step {} no longer defined after restart", run.current_step);
+            warn!("SOP run {run_id}: {reason}");
+            return self.finish_run(run_id, SopRunStatus::Interrupted, Some(reason));
+        };
+        let step = step.clone();
+        let context = format_step_context(sop, run, &step);
+
+        if rerequest {
+            run.status = SopRunStatus::WaitingApproval;
+            run.waiting_since = Some(now_iso8601());
+        } else if run.status != SopRunStatus::WaitingApproval {
+            run.status = SopRunStatus::Running;
+        }
+
+        let action = if run.status == SopRunStatus::WaitingApproval {
+            SopRunAction::WaitApproval {
+                run_id: run_id.to_string(),
+                step,
+                context,
+            }
+        } else {
+            SopRunAction::ExecuteStep {
+                run_id: run_id.to_string(),
+                step,
+                context,
+            }
+        };
+        self.persist_run(run_id);
+        action
+    }
+
+    /// Write the run's current state to the store, if persistence is enabled.
+    /// Failures are logged, never propagated: the in-memory run stays authoritative.
+    fn persist_run(&self, run_id: &str) {
+        let Some(dir) = self.persist_dir.as_deref() else {
+            return;
+        };
+        let run = self
+            .active_runs
+            .get(run_id)
+            .or_else(|| self.finished_runs.iter().rev().find(|r| r.run_id == run_id));
+        if let Some(run) = run {
+            if let Err(e) = store::save_run(dir, run) {
+                warn!("SOP run {run_id}: failed to persist state: {e}");
+            }
+        }
+    }
+
     fn last_finished_run(&self, sop_name: &str) -> Option<&SopRun> {
         self.finished_runs
             .iter()
@@ -373,8 +558,15 @@ impl SopEngine {
             self.finished_runs.drain(..excess);
         }
 
+        self.persist_run(&run_id_owned);
+        if let (Some(dir), true) = (self.persist_dir.as_deref(), max > 0) {
+            if let Err(e) = store::prune_finished_runs(dir, max) {
+                warn!("SOP run store prune failed: {e}");
+            }
+        }
+
         match status {
-            SopRunStatus::Failed => SopRunAction::Failed {
+            SopRunStatus::Failed | SopRunStatus::Interrupted => SopRunAction::Failed {
                 run_id: run_id_owned,
                 sop_name,
                 reason: reason.unwrap_or_default(),
@@ -390,7 +582,11 @@ impl SopEngine {
 // ── Trigger matching ────────────────────────────────────────────
 
 /// Check whether a single trigger definition matches an incoming event.
-fn trigger_matches(trigger: &SopTrigger, event: &SopEvent) -> bool {
+fn trigger_matches(
+    trigger: &SopTrigger,
+    event: &SopEvent,
+    channel_patterns: &HashMap<String, regex::Regex>,
+) -> bool {
     match (trigger, event.source) {
         (SopTrigger::Mqtt { topic, condition }, SopTriggerSource::Mqtt) => {
             let topic_match = event
@@ -437,14 +633,92 @@ fn trigger_matches(trigger: &SopTrigger, event: &SopEvent) -> bool {
             event.topic.as_deref().map_or(false, |t| t == expression)
         }
 
+        (
+            SopTrigger::Channel {
+                channel,
+                sender,
+                pattern,
+            },
+            SopTriggerSource::Channel,
+        ) => {
+            // Topic is "{channel}/{sender}"; sender ids may themselves contain '/'
+            let Some((ev_channel, ev_sender)) =
+                event.topic.as_deref().and_then(|t| t.split_once('/'))
+            else {
+                return false;
+            };
+            if channel.as_deref().is_some_and(|c| c != ev_channel)
+                || sender.as_deref().is_some_and(|s| s != ev_sender)
+            {
+                return false;
+            }
+            match pattern {
+                Some(pat) => channel_patterns
+                    .get(pat)
+                    .is_some_and(|re| re.is_match(event.payload.as_deref().unwrap_or_default())),
+                None => true,
+            }
+        }
+
+        (SopTrigger::FileWatch { glob }, SopTriggerSource::FileWatch) => event
+            .topic
+            .as_deref()
+            .map_or(false, |t| file_glob_matches(glob, t)),
+
         (SopTrigger::Manual, SopTriggerSource::Manual) => true,
 
         _ => false,
     }
 }
 
+/// Compile the `channel` trigger patterns of `sops` once, at load time.
+/// Invalid patterns are logged and left out, so they fail closed.
+fn compile_channel_patterns(sops: &[Sop]) -> HashMap<String, regex::Regex> {
+    let mut patterns = HashMap::new();
+    for sop in sops {
+        for trigger in &sop.triggers {
+            let SopTrigger::Channel {
+                pattern: Some(pattern),
+                ..
+            } = trigger
+            else {
+                continue;
+            };
+            if patterns.contains_key(pattern) {
+                continue;
+            }
+            match regex::Regex::new(pattern) {
+                Ok(re) => {
+                    patterns.insert(pattern.clone(), re);
+                }
+                Err(e) => warn!(
+                    "SOP '{}' channel trigger: invalid pattern '{pattern}': {e}",
+                    sop.name
+                ),
+            }
+        }
+    }
+    patterns
+}
+
+/// Match a workspace-relative path against a `file_watch` glob.
+/// Invalid globs fail closed.
+pub(crate) fn file_glob_matches(glob: &str, path: &str) -> bool {
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..glob::MatchOptions::new()
+    };
+    match glob::Pattern::new(glob) {
+        Ok(p) => p.matches_with(path, options),
+        Err(e) => {
+            warn!("SOP file_watch trigger: invalid glob '{glob}': {e}");
+            false
+        }
+    }
+}
+
 /// Simple MQTT topic matching with `+` (single-level) and `#` (multi-level) wildcards.
-fn mqtt_topic_matches(pattern: &str, topic: &str) -> bool {
+pub(crate) fn mqtt_topic_matches(pattern: &str, topic: &str) -> bool {
     let pat_parts: Vec<&str> = pattern.split('/').collect();
     let top_parts: Vec<&str> = topic.split('/').collect();
 
@@ -476,7 +750,15 @@ fn mqtt_topic_matches(pattern: &str, topic: &str) -> bool {
 // ── Execution mode resolution ───────────────────────────────────
 
 /// Determine the action for a step based on SOP execution mode.
-fn resolve_step_action(sop: &Sop, step: &SopStep, run_id: String, context: String) -> SopRunAction {
+/// `is_first` is true for the first step a run executes, which is not
+/// necessarily step 1 when conditions bypass it.
+fn resolve_step_action(
+    sop: &Sop,
+    step: &SopStep,
+    is_first: bool,
+    run_id: String,
+    context: String,
+) -> SopRunAction {
     // Steps with requires_confirmation always need approval
     if step.requires_confirmation {
         return SopRunAction::WaitApproval {
@@ -490,7 +772,7 @@ fn resolve_step_action(sop: &Sop, step: &SopStep, run_id: String, context: Strin
         crate::sop::SopExecutionMode::Auto => false,
         crate::sop::SopExecutionMode::Supervised => {
             // Supervised: approval only before the first step
-            step.number == 1
+            is_first
         }
         crate::sop::SopExecutionMode::StepByStep => true,
         crate::sop::SopExecutionMode::PriorityBased => {
@@ -498,7 +780,7 @@ fn resolve_step_action(sop: &Sop, step: &SopStep, run_id: String, context: Strin
                 SopPriority::Critical | SopPriority::High => false,
                 SopPriority::Normal | SopPriority::Low => {
                     // Supervised behavior for normal/low
-                    step.number == 1
+                    is_first
                 }
             }
         }
@@ -548,6 +830,18 @@ fn format_step_context(sop: &Sop, run: &SopRun, step: &SopStep) -> String {
         );
     }
 
+    if let Some(branch) = run
+        .branches
+        .last()
+        .filter(|b| b.to_step == Some(step.number))
+    {
+        let _ = writeln!(
+            ctx,
+            "Branch: arrived from step {} ({})",
+            branch.from_step, branch.kind
+        );
+    }
+
     let _ = write!(ctx, "\nCurrent step: **{}**\n{}\n", step.title, step.body);
 
     if !step.suggested_tools.is_empty() {
@@ -680,6 +974,9 @@ mod tests {
                     body: "Do step one".into(),
                     suggested_tools: vec!["shell".into()],
                     requires_confirmation: false,
+                    condition: None,
+                    on_failure: None,
+                    goto: None,
                 },
                 SopStep {
                     number: 2,
@@ -687,17 +984,21 @@ mod tests {
                     body: "Do step two".into(),
                     suggested_tools: vec![],
                     requires_confirmation: false,
+                    condition: None,
+                    on_failure: None,
+                    goto: None,
                 },
             ],
             cooldown_secs: 0,
             max_concurrent: 1,
+            max_step_visits: 3,
             location: None,
         }
     }
 
     fn engine_with_sops(sops: Vec<Sop>) -> SopEngine {
         let mut engine = SopEngine::new(SopConfig::default());
-        engine.sops = sops;
+        engine.set_sops(sops);
         engine
     }
 
@@ -1024,6 +1325,101 @@ mod tests {
         assert_eq!(engine.match_trigger(&event).len(), 1);
     }
 
+    fn channel_event(channel: &str, sender: &str, content: &str) -> SopEvent {
+        SopEvent {
+            source: SopTriggerSource::Channel,
+            topic: Some(format!("{channel}/{sender}")),
+            payload: Some(content.into()),
+            timestamp: now_iso8601(),
+        }
+    }
+
+    #[test]
+    fn channel_trigger_filters_by_channel_sender_and_pattern() {
+        let sop = Sop {
+            triggers: vec![SopTrigger::Channel {
+                channel: Some("slack".into()),
+                sender: Some("U123".into()),
+                pattern: Some(r"(?i)\bincident\b".into()),
+            }],
+            ..test_sop("oncall", SopExecutionMode::Auto, SopPriority::High)
+        };
+        let engine = engine_with_sops(vec![sop]);
+
+        let hit = channel_event("slack", "U123", "INCIDENT: db is down");
+        assert_eq!(engine.match_trigger(&hit).len(), 1);
+
+        let wrong_channel = channel_event("telegram", "U123", "incident");
+        assert!(engine.match_trigger(&wrong_channel).is_empty());
+
+        let wrong_sender = channel_event("slack", "U999", "incident");
+        assert!(engine.match_trigger(&wrong_sender).is_empty());
+
+        let no_keyword = channel_event("slack", "U123", "all good");
+        assert!(engine.match_trigger(&no_keyword).is_empty());
+    }
+
+    #[test]
+    fn channel_trigger_unset_fields_match_any() {
+        let sop = Sop {
+            triggers: vec![SopTrigger::Channel {
+                channel: None,
+                sender: None,
+                pattern: None,
+            }],
+            ..test_sop("any-msg", SopExecutionMode::Auto, SopPriority::Normal)
+        };
+        let engine = engine_with_sops(vec![sop]);
+
+        // Sender containing '/' still splits on the first separator only
+        let event = channel_event("matrix", "@ops:example.org/device", "hi");
+        assert_eq!(engine.match_trigger(&event).len(), 1);
+    }
+
+    #[test]
+    fn channel_trigger_invalid_pattern_fails_closed() {
+        let sop = Sop {
+            triggers: vec![SopTrigger::Channel {
+                channel: None,
+                sender: None,
+                pattern: Some("(unclosed".into()),
+            }],
+            ..test_sop("bad-regex", SopExecutionMode::Auto, SopPriority::Normal)
+        };
+        let engine = engine_with_sops(vec![sop]);
+        let event = channel_event("slack", "U1", "(unclosed");
+        assert!(engine.match_trigger(&event).is_empty());
+    }
+
+    #[test]
+    fn file_watch_trigger_matches_glob() {
+        let sop = Sop {
+            triggers: vec![SopTrigger::FileWatch {
+                glob: "incoming/*.log".into(),
+            }],
+            ..test_sop("log-drop", SopExecutionMode::Auto, SopPriority::Normal)
+        };
+        let engine = engine_with_sops(vec![sop]);
+
+        let file_event = |path: &str| SopEvent {
+            source: SopTriggerSource::FileWatch,
+            topic: Some(path.into()),
+            payload: None,
+            timestamp: now_iso8601(),
+        };
+        assert_eq!(
+            engine.match_trigger(&file_event("incoming/app.log")).len(),
+            1
+        );
+        // `*` does not cross directory boundaries
+        assert!(engine
+            .match_trigger(&file_event("incoming/old/app.log"))
+            .is_empty());
+        assert!(engine
+            .match_trigger(&file_event("incoming/app.txt"))
+            .is_empty());
+    }
+
     // ── Run lifecycle ───────────────────────────────────
 
     #[test]
@@ -1142,6 +1538,220 @@ mod tests {
         assert!(engine.cancel_run("nonexistent").is_err());
     }
 
+    // ── Branching ───────────────────────────────────────
+
+    fn step_result(step_number: u32, status: SopStepStatus, output: &str) -> SopStepResult {
+        SopStepResult {
+            step_number,
+            status,
+            output: output.into(),
+            started_at: now_iso8601(),
+            completed_at: Some(now_iso8601()),
+        }
+    }
+
+    /// Health check (1) → deploy done (2, goto 4) → rollback (3) → verify (4).
+    /// Step 1 branches to rollback on failure.
+    fn branching_sop() -> Sop {
+        let mut sop = test_sop("deploy", SopExecutionMode::Auto, SopPriority::Normal);
+        let step = |number: u32, title: &str| SopStep {
+            number,
+            title: title.into(),
+            body: format!("Do {title}"),
+            suggested_tools: vec![],
+            requires_confirmation: false,
+            condition: None,
+            on_failure: None,
+            goto: None,
+        };
+        sop.steps = vec![
+            SopStep {
+                on_failure: Some(3),
+                ..step(1, "Health check")
+            },
+            SopStep {
+                goto: Some(4),
+                ..step(2, "Promote")
+            },
+            step(3, "Rollback"),
+            step(4, "Verify"),
+        ];
+        sop
+    }
+
+    fn current_step_number(action: &SopRunAction) -> u32 {
+        match action {
+            SopRunAction::ExecuteStep { step, .. } | SopRunAction::WaitApproval { step, .. } => {
+                step.number
+            }
+            other => panic!("Expected a step action, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn on_failure_branches_instead_of_failing() {
+        let mut engine = engine_with_sops(vec![branching_sop()]);
+        let action = engine.start_run("deploy", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+
+        let action = engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Failed, "503"))
+            .unwrap();
+        assert_eq!(current_step_number(&action), 3);
+        assert!(
+            matches!(&action, SopRunAction::ExecuteStep { context, .. } if context.contains("Branch: arrived from step 1 (on_failure)"))
+        );
+
+        let run = engine.get_run(&run_id).unwrap();
+        assert_eq!(run.status, SopRunStatus::Running);
+        assert_eq!(run.branches.len(), 1);
+        assert_eq!(run.branches[0].kind, SopBranchKind::OnFailure);
+        assert_eq!(run.branches[0].to_step, Some(3));
+    }
+
+    #[test]
+    fn goto_skips_to_target_and_completes() {
+        let mut engine = engine_with_sops(vec![branching_sop()]);
+        let action = engine.start_run("deploy", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+
+        engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Completed, "ok"))
+            .unwrap();
+        let action = engine
+            .advance_step(
+                &run_id,
+                step_result(2, SopStepStatus::Completed, "promoted"),
+            )
+            .unwrap();
+        // Rollback (3) is jumped over
+        assert_eq!(current_step_number(&action), 4);
+
+        let action = engine
+            .advance_step(
+                &run_id,
+                step_result(4, SopStepStatus::Completed, "verified"),
+            )
+            .unwrap();
+        assert!(matches!(action, SopRunAction::Completed { .. }));
+        let run = engine.get_run(&run_id).unwrap();
+        assert_eq!(run.step_results.len(), 3);
+        assert_eq!(run.branches[0].kind, SopBranchKind::Goto);
+    }
+
+    #[test]
+    fn loop_bound_fails_run() {
+        let mut sop = branching_sop();
+        // Verify (4) loops back to the health check
+        sop.steps[3].goto = Some(1);
+        sop.steps[0].on_failure = None;
+        sop.max_step_visits = 2;
+        let mut engine = engine_with_sops(vec![sop]);
+        let action = engine.start_run("deploy", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+
+        let mut action = action;
+        for _ in 0..10 {
+            let n = match &action {
+                SopRunAction::ExecuteStep { step, .. } => step.number,
+                _ => break,
+            };
+            action = engine
+                .advance_step(&run_id, step_result(n, SopStepStatus::Completed, "ok"))
+                .unwrap();
+        }
+
+        assert!(
+            matches!(&action, SopRunAction::Failed { reason, .. } if reason.contains("max_step_visits")),
+            "got {action:?}"
+        );
+        // 1, 2, 4 twice each before step 1 would be visited a third time
+        assert_eq!(engine.get_run(&run_id).unwrap().step_visits(1), 2);
+    }
+
+    #[test]
+    fn condition_bypasses_step_when_not_met() {
+        let mut sop = branching_sop();
+        sop.steps[0].on_failure = None;
+        sop.steps[1].goto = None;
+        // Rollback only when promotion reported an error
+        sop.steps[2].condition = Some(r#"$.steps.2.output.error == true"#.into());
+        let mut engine = engine_with_sops(vec![sop]);
+
+        let action = engine.start_run("deploy", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+        engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Completed, "ok"))
+            .unwrap();
+        let action = engine
+            .advance_step(
+                &run_id,
+                step_result(2, SopStepStatus::Completed, r#"{"error": false}"#),
+            )
+            .unwrap();
+        assert_eq!(current_step_number(&action), 4);
+
+        let run = engine.get_run(&run_id).unwrap();
+        assert_eq!(run.branches.len(), 1);
+        assert_eq!(run.branches[0].kind, SopBranchKind::ConditionNotMet);
+        assert_eq!(run.branches[0].from_step, 3);
+    }
+
+    #[test]
+    fn condition_runs_step_when_met() {
+        let mut sop = branching_sop();
+        sop.steps[1].goto = None;
+        sop.steps[2].condition = Some(r#"$.last.output.error == true"#.into());
+        let mut engine = engine_with_sops(vec![sop]);
+
+        let action = engine.start_run("deploy", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+        engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Completed, "ok"))
+            .unwrap();
+        let action = engine
+            .advance_step(
+                &run_id,
+                step_result(2, SopStepStatus::Completed, r#"{"error": true}"#),
+            )
+            .unwrap();
+        assert_eq!(current_step_number(&action), 3);
+        assert!(engine.get_run(&run_id).unwrap().branches.is_empty());
+    }
+
+    #[test]
+    fn supervised_approval_applies_to_first_executed_step() {
+        let mut sop = branching_sop();
+        sop.execution_mode = SopExecutionMode::Supervised;
+        // No prior results → condition on step 1 fails closed → bypassed
+        sop.steps[0].condition = Some("$.last.status == \"completed\"".into());
+        let mut engine = engine_with_sops(vec![sop]);
+
+        let action = engine.start_run("deploy", manual_event()).unwrap();
+        assert!(matches!(action, SopRunAction::WaitApproval { ref step, .. } if step.number == 2));
+        let run_id = extract_run_id(&action).to_string();
+        assert_eq!(
+            engine.get_run(&run_id).unwrap().status,
+            SopRunStatus::WaitingApproval
+        );
+    }
+
+    #[test]
+    fn trailing_condition_bypass_completes_run() {
+        let mut sop = test_sop("s1", SopExecutionMode::Auto, SopPriority::Normal);
+        sop.steps[1].condition = Some("$.steps.1.status == \"failed\"".into());
+        let mut engine = engine_with_sops(vec![sop]);
+        let action = engine.start_run("s1", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+
+        let action = engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Completed, "fine"))
+            .unwrap();
+        assert!(matches!(action, SopRunAction::Completed { .. }));
+        let run = engine.get_run(&run_id).unwrap();
+        assert_eq!(run.branches[0].to_step, None);
+    }
+
     // ── Concurrency ─────────────────────────────────────
 
     #[test]
@@ -1167,7 +1777,7 @@ mod tests {
             max_concurrent_total: 1,
             ..SopConfig::default()
         });
-        engine.sops = sops;
+        engine.set_sops(sops);
 
         engine.start_run("s1", manual_event()).unwrap();
         assert!(!engine.can_start("s2"));
@@ -1359,6 +1969,7 @@ mod tests {
             completed_at: None,
             step_results: Vec::new(),
             waiting_since: None,
+            branches: Vec::new(),
         };
         let ctx = format_step_context(&sop, &run, &sop.steps[0]);
         assert!(ctx.contains("pump-shutdown"));
@@ -1548,7 +2159,7 @@ mod tests {
         let mut sop = test_sop("s1", SopExecutionMode::Auto, SopPriority::Normal);
         sop.steps = vec![sop.steps[0].clone()];
         sop.max_concurrent = 10;
-        engine.sops = vec![sop];
+        engine.set_sops(vec![sop]);
 
         // Complete 3 runs
         let mut finished_ids = Vec::new();
@@ -1591,7 +2202,7 @@ mod tests {
         let mut sop = test_sop("s1", SopExecutionMode::Auto, SopPriority::Normal);
         sop.steps = vec![sop.steps[0].clone()];
         sop.max_concurrent = 10;
-        engine.sops = vec![sop];
+        engine.set_sops(vec![sop]);
 
         for _ in 0..5 {
             let action = engine.start_run("s1", manual_event()).unwrap();
@@ -1628,4 +2239,162 @@ mod tests {
         assert_eq!(run.status, SopRunStatus::Running);
         assert!(run.waiting_since.is_none());
     }
+
+    // ── Persistence / rehydration ────────────────────────────────
+
+    /// Start an Auto run in a persistent engine and complete step 1, leaving
+    /// it Running at step 2. Returns the run id.
+    fn persisted_run_at_step_two(workspace: &Path) -> String {
+        let mut engine = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Auto,
+            SopPriority::Normal,
+        )]);
+        assert!(engine
+            .enable_persistence(workspace, SopResumePolicy::Resume)
+            .unwrap()
+            .is_empty());
+        let action = engine.start_run("s1", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+        engine
+            .advance_step(&run_id, step_result(1, SopStepStatus::Completed, "ok"))
+            .unwrap();
+        run_id
+    }
+
+    #[test]
+    fn resume_policy_reissues_current_step() {
+        let tmp = tempfile::tempdir().unwrap();
+        let run_id = persisted_run_at_step_two(tmp.path());
+
+        let mut engine = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Auto,
+            SopPriority::Normal,
+        )]);
+        let actions = engine
+            .enable_persistence(tmp.path(), SopResumePolicy::Resume)
+            .unwrap();
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(actions[0], SopRunAction::ExecuteStep { .. }));
+        assert_eq!(current_step_number(&actions[0]), 2);
+
+        let run = engine.get_run(&run_id).unwrap();
+        assert_eq!(run.status, SopRunStatus::Running);
+        assert_eq!(run.step_results.len(), 1);
+
+        // The resumed run continues to completion and is persisted as such
+        let action = engine
+            .advance_step(&run_id, step_result(2, SopStepStatus::Completed, "ok"))
+            .unwrap();
+        assert!(matches!(action, SopRunAction::Completed { .. }));
+        assert!(store::load_active_runs(tmp.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn resume_policy_keeps_pending_approval_gate() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut engine = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Supervised,
+            SopPriority::Normal,
+        )]);
+        engine
+            .enable_persistence(tmp.path(), SopResumePolicy::Resume)
+            .unwrap();
+        let action = engine.start_run("s1", manual_event()).unwrap();
+        let run_id = extract_run_id(&action).to_string();
+        let waiting_since = engine.get_run(&run_id).unwrap().waiting_since.clone();
+        assert_eq!(store::list_pending_approvals(tmp.path()).unwrap().len(), 1);
+
+        let mut engine = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Supervised,
+            SopPriority::Normal,
+        )]);
+        let actions = engine
+            .enable_persistence(tmp.path(), SopResumePolicy::Resume)
+            .unwrap();
+        assert!(matches!(actions[0], SopRunAction::WaitApproval { .. }));
+        let run = engine.get_run(&run_id).unwrap();
+        assert_eq!(run.status, SopRunStatus::WaitingApproval);
+        assert_eq!(run.waiting_since, waiting_since);
+
+        engine.approve_step(&run_id).unwrap();
+        assert!(store::list_pending_approvals(tmp.path())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn interrupt_policy_finishes_active_runs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let run_id = persisted_run_at_step_two(tmp.path());
+
+        let mut engine = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Auto,
+            SopPriority::Normal,
+        )]);
+        let actions = engine
+            .enable_persistence(tmp.path(), SopResumePolicy::Interrupt)
+            .unwrap();
+        assert!(matches!(actions[0], SopRunAction::Failed { .. }));
+        assert!(engine.active_runs().is_empty());
+        let finished = engine.finished_runs(Some("s1"));
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].run_id, run_id);
+        assert_eq!(finished[0].status, SopRunStatus::Interrupted);
+
+        // Interrupted runs stay finished across a further restart
+        let mut engine = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Auto,
+            SopPriority::Normal,
+        )]);
+        let actions = engine
+            .enable_persistence(tmp.path(), SopResumePolicy::Resume)
+            .unwrap();
+        assert!(actions.is_empty());
+        assert_eq!(engine.finished_runs(None).len(), 1);
+    }
+
+    #[test]
+    fn rerequest_policy_gates_running_runs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let run_id = persisted_run_at_step_two(tmp.path());
+
+        let mut engine = engine_with_sops(vec![test_sop(
+            "s1",
+            SopExecutionMode::Auto,
+            SopPriority::Normal,
+        )]);
+        let actions = engine
+            .enable_persistence(tmp.path(), SopResumePolicy::Rerequest)
+            .unwrap();
+        assert!(matches!(actions[0], SopRunAction::WaitApproval { .. }));
+        assert_eq!(current_step_number(&actions[0]), 2);
+        let run = engine.get_run(&run_id).unwrap();
+        assert_eq!(run.status, SopRunStatus::WaitingApproval);
+        assert!(run.waiting_since.is_some());
+
+        let action = engine.approve_step(&run_id).unwrap();
+        assert_eq!(current_step_number(&action), 2);
+    }
+
+    #[test]
+    fn rehydrate_interrupts_runs_of_unloaded_sops() {
+        let tmp = tempfile::tempdir().unwrap();
+        persisted_run_at_step_two(tmp.path());
+
+        let mut engine = engine_with_sops(Vec::new());
+        let actions = engine
+            .enable_persistence(tmp.path(), SopResumePolicy::Resume)
+            .unwrap();
+        assert!(matches!(actions[0], SopRunAction::Failed { .. }));
+        assert_eq!(
+            engine.finished_runs(None)[0].status,
+            SopRunStatus::Interrupted
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::RwLock;
 use std::time::Instant;
 
@@ -6,7 +6,7 @@ use chrono::{DateTime, NaiveDateTime, Utc};
 use serde_json::json;
 use tracing::warn;
 
-use super::types::{SopRun, SopRunStatus, SopStepStatus};
+use super::types::{SopBranchKind, SopRun, SopRunStatus, SopStepStatus};
 use crate::memory::traits::{Memory, MemoryCategory};
 
 /// Maximum recent runs kept in each ring buffer (global + per-SOP).
@@ -27,10 +27,18 @@ struct MetricCounters {
     runs_completed: u64,
     runs_failed: u64,
     runs_cancelled: u64,
+    /// Runs left unfinished by a daemon restart.
+    runs_interrupted: u64,
     steps_executed: u64,
     steps_defined: u64,
     steps_failed: u64,
     steps_skipped: u64,
+    /// Distinct steps that completed per run (revisits in loops count once).
+    steps_completed: u64,
+    /// Steps bypassed because their condition did not hold.
+    steps_bypassed: u64,
+    /// `on_failure` branches taken instead of failing the run.
+    failure_recoveries: u64,
     human_approvals: u64,
     timeout_auto_approvals: u64,
 }
@@ -49,6 +57,9 @@ struct RunSnapshot {
     steps_defined: u64,
     steps_failed: u64,
     steps_skipped: u64,
+    steps_completed: u64,
+    steps_bypassed: u64,
+    failure_recoveries: u64,
     human_approval_count: u64,
     timeout_approval_count: u64,
 }
@@ -94,7 +105,7 @@ impl SopMetricsCollector {
 
     // ── Push methods (sync, write lock) ────────────────────────
 
-    /// Record a terminal run (Completed/Failed/Cancelled).
+    /// Record a terminal run (Completed/Failed/Cancelled/Interrupted).
     ///
     /// Call after `audit.log_run_complete()`.
     pub fn record_run_complete(&self, run: &SopRun) {
@@ -200,10 +211,7 @@ impl SopMetricsCollector {
         for entry in &entries {
             if entry.key.starts_with("sop_run_") {
                 if let Ok(run) = serde_json::from_str::<SopRun>(&entry.content) {
-                    if matches!(
-                        run.status,
-                        SopRunStatus::Completed | SopRunStatus::Failed | SopRunStatus::Cancelled
-                    ) {
+                    if run.status.is_terminal() {
                         runs.insert(run.run_id.clone(), run);
                     }
                 }
@@ -462,13 +470,37 @@ fn build_snapshot(run: &SopRun, human_count: u64, timeout_count: u64) -> RunSnap
         .filter(|s| s.status == SopStepStatus::Skipped)
         .count() as u64;
 
+    // Non-linear runs: loops revisit steps and conditions bypass them, so
+    // adherence is measured on distinct steps against the path actually taken.
+    let completed: HashSet<u32> = run
+        .step_results
+        .iter()
+        .filter(|s| s.status == SopStepStatus::Completed)
+        .map(|s| s.step_number)
+        .collect();
+    let bypassed: HashSet<u32> = run
+        .branches
+        .iter()
+        .filter(|b| b.kind == SopBranchKind::ConditionNotMet)
+        .map(|b| b.from_step)
+        .filter(|n| !completed.contains(n))
+        .collect();
+    let failure_recoveries = run
+        .branches
+        .iter()
+        .filter(|b| b.kind == SopBranchKind::OnFailure)
+        .count() as u64;
+
     RunSnapshot {
         completed_at,
         terminal_status: run.status,
         steps_executed,
-        steps_defined: u64::from(run.total_steps),
+        steps_defined: u64::from(run.total_steps).saturating_sub(bypassed.len() as u64),
         steps_failed,
         steps_skipped,
+        steps_completed: completed.len() as u64,
+        steps_bypassed: bypassed.len() as u64,
+        failure_recoveries,
         human_approval_count: human_count,
         timeout_approval_count: timeout_count,
     }
@@ -480,12 +512,16 @@ fn apply_run(sop: &mut SopCounters, snap: &RunSnapshot) {
         SopRunStatus::Completed => c.runs_completed += 1,
         SopRunStatus::Failed => c.runs_failed += 1,
         SopRunStatus::Cancelled => c.runs_cancelled += 1,
+        SopRunStatus::Interrupted => c.runs_interrupted += 1,
         _ => {}
     }
     c.steps_executed += snap.steps_executed;
     c.steps_defined += snap.steps_defined;
     c.steps_failed += snap.steps_failed;
     c.steps_skipped += snap.steps_skipped;
+    c.steps_completed += snap.steps_completed;
+    c.steps_bypassed += snap.steps_bypassed;
+    c.failure_recoveries += snap.failure_recoveries;
 
     sop.recent_runs.push_back(snap.clone());
     if sop.recent_runs.len() > MAX_RECENT_RUNS {
@@ -519,12 +555,16 @@ fn aggregate_windowed(
                 SopRunStatus::Completed => wc.runs_completed += 1,
                 SopRunStatus::Failed => wc.runs_failed += 1,
                 SopRunStatus::Cancelled => wc.runs_cancelled += 1,
+                SopRunStatus::Interrupted => wc.runs_interrupted += 1,
                 _ => {}
             }
             wc.steps_executed += snap.steps_executed;
             wc.steps_defined += snap.steps_defined;
             wc.steps_failed += snap.steps_failed;
             wc.steps_skipped += snap.steps_skipped;
+            wc.steps_completed += snap.steps_completed;
+            wc.steps_bypassed += snap.steps_bypassed;
+            wc.failure_recoveries += snap.failure_recoveries;
             wc.human_approvals += snap.human_approval_count;
             wc.timeout_auto_approvals += snap.timeout_approval_count;
         }
@@ -562,6 +602,7 @@ fn resolve_from_counters(c: &MetricCounters, metric: &str) -> Option<serde_json:
         "runs_completed" => Some(json!(c.runs_completed)),
         "runs_failed" => Some(json!(c.runs_failed)),
         "runs_cancelled" => Some(json!(c.runs_cancelled)),
+        "runs_interrupted" => Some(json!(c.runs_interrupted)),
         "deviation_rate" => {
             if c.steps_executed == 0 {
                 Some(json!(0.0))
@@ -575,13 +616,11 @@ fn resolve_from_counters(c: &MetricCounters, metric: &str) -> Option<serde_json:
             if c.steps_defined == 0 {
                 Some(json!(0.0))
             } else {
-                let good = c
-                    .steps_executed
-                    .saturating_sub(c.steps_failed)
-                    .saturating_sub(c.steps_skipped);
-                Some(json!(good as f64 / c.steps_defined as f64))
+                Some(json!(c.steps_completed as f64 / c.steps_defined as f64))
             }
         }
+        "steps_bypassed" => Some(json!(c.steps_bypassed)),
+        "failure_recoveries" => Some(json!(c.failure_recoveries)),
         "human_intervention_count" => Some(json!(c.human_approvals)),
         "human_intervention_rate" => Some(json!(
             c.human_approvals as f64 / c.runs_completed.max(1) as f64
@@ -591,7 +630,7 @@ fn resolve_from_counters(c: &MetricCounters, metric: &str) -> Option<serde_json:
             c.timeout_auto_approvals as f64 / c.runs_completed.max(1) as f64
         )),
         "completion_rate" => {
-            let total = c.runs_completed + c.runs_failed + c.runs_cancelled;
+            let total = c.runs_completed + c.runs_failed + c.runs_cancelled + c.runs_interrupted;
             Some(json!(c.runs_completed as f64 / total.max(1) as f64))
         }
         _ => None,
@@ -604,10 +643,14 @@ fn counters_to_json(sop: &SopCounters) -> serde_json::Value {
         "runs_completed": c.runs_completed,
         "runs_failed": c.runs_failed,
         "runs_cancelled": c.runs_cancelled,
+        "runs_interrupted": c.runs_interrupted,
         "steps_executed": c.steps_executed,
         "steps_defined": c.steps_defined,
         "steps_failed": c.steps_failed,
         "steps_skipped": c.steps_skipped,
+        "steps_completed": c.steps_completed,
+        "steps_bypassed": c.steps_bypassed,
+        "failure_recoveries": c.failure_recoveries,
         "human_approvals": c.human_approvals,
         "timeout_auto_approvals": c.timeout_auto_approvals,
         "recent_runs_depth": sop.recent_runs.len(),
@@ -621,12 +664,18 @@ mod tests {
     use super::*;
     use crate::sop::types::{SopEvent, SopStepResult, SopTriggerSource};
 
+    /// RFC 3339 timestamp `minutes` before now, so fixtures stay inside
+    /// the windowed metrics.
+    fn minutes_ago(minutes: i64) -> String {
+        (Utc::now() - chrono::Duration::minutes(minutes)).to_rfc3339()
+    }
+
     fn make_event() -> SopEvent {
         SopEvent {
             source: SopTriggerSource::Manual,
             topic: None,
             payload: None,
-            timestamp: "2026-02-19T12:00:00Z".into(),
+            timestamp: minutes_ago(10),
         }
     }
 
@@ -644,10 +693,11 @@ mod tests {
             status,
             current_step: total_steps,
             total_steps,
-            started_at: "2026-02-19T12:00:00Z".into(),
-            completed_at: Some("2026-02-19T12:05:00Z".into()),
+            started_at: minutes_ago(10),
+            completed_at: Some(minutes_ago(5)),
             step_results,
             waiting_since: None,
+            branches: Vec::new(),
         }
     }
 
@@ -656,8 +706,8 @@ mod tests {
             step_number: number,
             status,
             output: format!("Step {number}"),
-            started_at: "2026-02-19T12:00:00Z".into(),
-            completed_at: Some("2026-02-19T12:01:00Z".into()),
+            started_at: minutes_ago(10),
+            completed_at: Some(minutes_ago(9)),
         }
     }
 
@@ -745,7 +795,7 @@ mod tests {
         );
         c.record_run_complete(&run);
 
-        // adherence = (2 - 1 - 0) / 3 = 1/3
+        // adherence = 1 distinct completed / 3 defined = 1/3
         let val = c
             .get_metric_value("sop.protocol_adherence_rate")
             .unwrap()
@@ -793,7 +843,7 @@ mod tests {
         );
         c.record_run_complete(&run);
 
-        // adherence = (3 - 1 - 1) / 3 = 1/3
+        // adherence = 1 distinct completed / 3 defined = 1/3
         let val = c
             .get_metric_value("sop.protocol_adherence_rate")
             .unwrap()
@@ -802,6 +852,51 @@ mod tests {
         assert!((val - 1.0 / 3.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn protocol_adherence_rate_non_linear_run() {
+        use crate::sop::types::{SopBranch, SopBranchKind};
+
+        let c = SopMetricsCollector::new();
+        // 1 fails → on_failure to 3; 3 completes; 4 bypassed by condition;
+        // loop back to 1 which now completes.
+        let mut run = make_run(
+            "r1",
+            "test-sop",
+            SopRunStatus::Completed,
+            4,
+            vec![
+                make_step(1, SopStepStatus::Failed),
+                make_step(3, SopStepStatus::Completed),
+                make_step(1, SopStepStatus::Completed),
+            ],
+        );
+        let branch = |from_step: u32, to_step: Option<u32>, kind: SopBranchKind| SopBranch {
+            from_step,
+            to_step,
+            kind,
+            at: "2026-02-19T12:01:00Z".into(),
+        };
+        run.branches = vec![
+            branch(1, Some(3), SopBranchKind::OnFailure),
+            branch(4, None, SopBranchKind::ConditionNotMet),
+            branch(3, Some(1), SopBranchKind::Goto),
+        ];
+        c.record_run_complete(&run);
+
+        // defined = 4 - 1 bypassed = 3; distinct completed = {1, 3}
+        let val = c
+            .get_metric_value("sop.protocol_adherence_rate")
+            .unwrap()
+            .as_f64()
+            .unwrap();
+        assert!((val - 2.0 / 3.0).abs() < 1e-10);
+        assert_eq!(c.get_metric_value("sop.steps_bypassed"), Some(json!(1u64)));
+        assert_eq!(
+            c.get_metric_value("sop.failure_recoveries"),
+            Some(json!(1u64))
+        );
+    }
+
     #[test]
     fn derived_rate_metrics() {
         let c = SopMetricsCollector::new();
@@ -1004,6 +1099,25 @@ mod tests {
         assert!((cr - 0.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn runs_interrupted_tracking() {
+        let c = SopMetricsCollector::new();
+        let run = make_run(
+            "r1",
+            "test-sop",
+            SopRunStatus::Interrupted,
+            2,
+            vec![make_step(1, SopStepStatus::Completed)],
+        );
+        c.record_run_complete(&run);
+
+        assert_eq!(
+            c.get_metric_value("sop.runs_interrupted"),
+            Some(json!(1u64))
+        );
+        assert_eq!(c.get_metric_value("sop.runs_cancelled"), Some(json!(0u64)));
+    }
+
     // ── BUG 1 regression: multiple approvals per run ──────────
 
     #[test]
@@ -1094,6 +1208,9 @@ mod tests {
                 steps_defined: 1,
                 steps_failed: 0,
                 steps_skipped: 0,
+                steps_completed: 1,
+                steps_bypassed: 0,
+                failure_recoveries: 0,
                 human_approval_count: 0,
                 timeout_approval_count: 0,
             };
@@ -1241,10 +1358,11 @@ mod tests {
             status: SopRunStatus::Running,
             current_step: 1,
             total_steps: 3,
-            started_at: "2026-02-19T12:00:00Z".into(),
+            started_at: minutes_ago(10),
             completed_at: None,
             step_results: vec![],
             waiting_since: None,
+            branches: Vec::new(),
         };
         audit.log_run_start(&run).await.unwrap();
 
@@ -1338,10 +1456,11 @@ mod tests {
             status: SopRunStatus::Running,
             current_step: 1,
             total_steps: 3,
-            started_at: "2026-02-19T12:00:00Z".into(),
+            started_at: minutes_ago(10),
             completed_at: None,
             step_results: vec![],
             waiting_since: None,
+            branches: Vec::new(),
         };
         audit.log_run_start(&running_run).await.unwrap();
         audit.log_approval(&running_run, 1).await.unwrap();
@@ -1433,6 +1552,9 @@ mod tests {
                 steps_defined: 1,
                 steps_failed: 0,
                 steps_skipped: 0,
+                steps_completed: 1,
+                steps_bypassed: 0,
+                failure_recoveries: 0,
                 human_approval_count: 0,
                 timeout_approval_count: 0,
             };
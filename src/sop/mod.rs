@@ -5,6 +5,8 @@ pub mod engine;
 #[cfg(feature = "ampersona-gates")]
 pub mod gates;
 pub mod metrics;
+pub mod runtime;
+pub mod store;
 pub mod types;
 
 pub use audit::SopAuditLogger;
@@ -12,10 +14,12 @@ pub use engine::SopEngine;
 #[cfg(feature = "ampersona-gates")]
 pub use gates::GateEvalState;
 pub use metrics::SopMetricsCollector;
+pub use runtime::SopRuntime;
 #[allow(unused_imports)]
 pub use types::{
-    Sop, SopEvent, SopExecutionMode, SopPriority, SopRun, SopRunAction, SopRunStatus, SopStep,
-    SopStepResult, SopStepStatus, SopTrigger, SopTriggerSource,
+    Sop, SopBranch, SopBranchKind, SopEvent, SopExecutionMode, SopPriority, SopResumePolicy,
+    SopRun, SopRunAction, SopRunStatus, SopStep, SopStepResult, SopStepStatus, SopTrigger,
+    SopTriggerSource,
 };
 
 use anyhow::Result;
@@ -112,6 +116,7 @@ fn load_sop(sop_dir: &Path, default_execution_mode: SopExecutionMode) -> Result<
         execution_mode,
         cooldown_secs,
         max_concurrent,
+        max_step_visits,
     } = manifest.sop;
 
     Ok(Sop {
@@ -124,6 +129,7 @@ fn load_sop(sop_dir: &Path, default_execution_mode: SopExecutionMode) -> Result<
         steps,
         cooldown_secs,
         max_concurrent,
+        max_step_visits,
         location: Some(sop_dir.to_path_buf()),
     })
 }
@@ -134,7 +140,8 @@ fn load_sop(sop_dir: &Path, default_execution_mode: SopExecutionMode) -> Result<
 ///
 /// Expects a `## Steps` heading followed by numbered items (`1.`, `2.`, …).
 /// Each item's first bold text (`**...**`) is the step title; the rest is body.
-/// Sub-bullets `- tools:` and `- requires_confirmation: true` are parsed.
+/// Sub-bullets `- tools:`, `- requires_confirmation: true`, `- condition:`,
+/// `- on_failure: N` and `- goto: N` are parsed.
 pub fn parse_steps(md: &str) -> Vec<SopStep> {
     let mut steps = Vec::new();
     let mut in_steps_section = false;
@@ -143,6 +150,7 @@ pub fn parse_steps(md: &str) -> Vec<SopStep> {
     let mut current_body = String::new();
     let mut current_tools: Vec<String> = Vec::new();
     let mut current_requires_confirmation = false;
+    let mut current_flow = PendingFlow::default();
 
     for line in md.lines() {
         let trimmed = line.trim();
@@ -164,6 +172,7 @@ pub fn parse_steps(md: &str) -> Vec<SopStep> {
                     &mut current_body,
                     &mut current_tools,
                     &mut current_requires_confirmation,
+                    &mut current_flow,
                 );
                 in_steps_section = false;
             }
@@ -184,6 +193,7 @@ pub fn parse_steps(md: &str) -> Vec<SopStep> {
                 &mut current_body,
                 &mut current_tools,
                 &mut current_requires_confirmation,
+                &mut current_flow,
             );
 
             let step_num = u32::try_from(steps.len())
@@ -217,6 +227,13 @@ pub fn parse_steps(md: &str) -> Vec<SopStep> {
                 if let Some(val) = bullet.strip_prefix("requires_confirmation:") {
                     current_requires_confirmation = val.trim().eq_ignore_ascii_case("true");
                 }
+            } else if let Some(cond) = bullet.strip_prefix("condition:") {
+                let cond = cond.trim().trim_matches('`').trim();
+                current_flow.condition = (!cond.is_empty()).then(|| cond.to_string());
+            } else if let Some(target) = bullet.strip_prefix("on_failure:") {
+                current_flow.on_failure = parse_step_target(target);
+            } else if let Some(target) = bullet.strip_prefix("goto:") {
+                current_flow.goto = parse_step_target(target);
             } else {
                 // Continuation body line
                 if !current_body.is_empty() {
@@ -244,11 +261,20 @@ pub fn parse_steps(md: &str) -> Vec<SopStep> {
         &mut current_body,
         &mut current_tools,
         &mut current_requires_confirmation,
+        &mut current_flow,
     );
 
     steps
 }
 
+/// Branching sub-bullets accumulated for the step being parsed.
+#[derive(Default)]
+struct PendingFlow {
+    condition: Option<String>,
+    on_failure: Option<u32>,
+    goto: Option<u32>,
+}
+
 /// Flush accumulated step state into the steps vector.
 fn flush_step(
     steps: &mut Vec<SopStep>,
@@ -257,7 +283,9 @@ fn flush_step(
     body: &mut String,
     tools: &mut Vec<String>,
     requires_confirmation: &mut bool,
+    flow: &mut PendingFlow,
 ) {
+    let flow = std::mem::take(flow);
     if let Some(n) = number.take() {
         steps.push(SopStep {
             number: n,
@@ -265,12 +293,27 @@ fn flush_step(
             body: body.trim().to_string(),
             suggested_tools: std::mem::take(tools),
             requires_confirmation: *requires_confirmation,
+            condition: flow.condition,
+            on_failure: flow.on_failure,
+            goto: flow.goto,
         });
         *body = String::new();
         *requires_confirmation = false;
     }
 }
 
+/// Parse a step target like `4`, `step 4` or `#4`.
+fn parse_step_target(raw: &str) -> Option<u32> {
+    let raw = raw.trim();
+    let raw = raw
+        .strip_prefix("step")
+        .or_else(|| raw.strip_prefix("Step"))
+        .unwrap_or(raw)
+        .trim()
+        .trim_start_matches('#');
+    raw.parse().ok()
+}
+
 /// Try to parse `N. rest` from a line, returning `rest` if successful.
 fn parse_numbered_item(line: &str) -> Option<&str> {
     let dot_pos = line.find(". ")?;
@@ -321,7 +364,32 @@ pub fn validate_sop(sop: &Sop) -> Vec<String> {
         warnings.push("SOP has no steps (missing or empty SOP.md)".into());
     }
 
-    // Check step numbering continuity
+    // Invalid patterns never match at runtime (fail-closed); surface them here
+    for trigger in &sop.triggers {
+        match trigger {
+            SopTrigger::Channel {
+                pattern: Some(pattern),
+                ..
+            } => {
+                if let Err(e) = regex::Regex::new(pattern) {
+                    warnings.push(format!("Invalid channel trigger pattern '{pattern}': {e}"));
+                }
+            }
+            SopTrigger::FileWatch { glob } => {
+                if let Err(e) = glob::Pattern::new(glob) {
+                    warnings.push(format!("Invalid file_watch glob '{glob}': {e}"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if sop.max_step_visits == 0 {
+        warnings.push("max_step_visits is 0; no step can run".into());
+    }
+
+    // Check step numbering continuity and branch targets
+    let step_count = u32::try_from(sop.steps.len()).unwrap_or(u32::MAX);
     for (i, step) in sop.steps.iter().enumerate() {
         let expected = u32::try_from(i).unwrap_or(u32::MAX).saturating_add(1);
         if step.number != expected {
@@ -333,172 +401,19 @@ pub fn validate_sop(sop: &Sop) -> Vec<String> {
         if step.title.is_empty() {
             warnings.push(format!("Step {} has an empty title", step.number));
         }
-    }
-
-    warnings
-}
-
-// ── CLI handler ─────────────────────────────────────────────────
-
-/// Handle the `sop` CLI subcommand.
-pub fn handle_command(command: crate::SopCommands, config: &crate::config::Config) -> Result<()> {
-    let sops_dir_override = config.sop.sops_dir.as_deref();
-
-    match command {
-        crate::SopCommands::List => {
-            let sops = load_sops(
-                &config.workspace_dir,
-                sops_dir_override,
-                config.sop.default_execution_mode,
-            );
-            if sops.is_empty() {
-                println!("No SOPs found.");
-                println!();
-                println!("  Create one: mkdir -p ~/.zeroclaw/workspace/sops/my-sop");
-                println!("              # Add SOP.toml and SOP.md");
-                println!();
-                println!(
-                    "  SOPs directory: {}",
-                    resolve_sops_dir(&config.workspace_dir, sops_dir_override).display()
-                );
-            } else {
-                println!("SOPs ({}):", sops.len());
-                println!();
-                for sop in &sops {
-                    let triggers: Vec<String> =
-                        sop.triggers.iter().map(ToString::to_string).collect();
-                    println!(
-                        "  {} {} [{}] — {}",
-                        console::style(&sop.name).white().bold(),
-                        console::style(format!("v{}", sop.version)).dim(),
-                        console::style(&sop.priority).cyan(),
-                        sop.description
-                    );
-                    println!(
-                        "    Mode: {}  Steps: {}  Triggers: {}",
-                        sop.execution_mode,
-                        sop.steps.len(),
-                        triggers.join(", ")
-                    );
-                    if sop.cooldown_secs > 0 {
-                        println!("    Cooldown: {}s", sop.cooldown_secs);
-                    }
+        for (label, target) in [("on_failure", step.on_failure), ("goto", step.goto)] {
+            if let Some(t) = target {
+                if t == 0 || t > step_count {
+                    warnings.push(format!(
+                        "Step {} {label} target {t} does not exist (1..={step_count})",
+                        step.number
+                    ));
                 }
             }
-            println!();
-            Ok(())
-        }
-
-        crate::SopCommands::Validate { name } => {
-            let sops = load_sops(
-                &config.workspace_dir,
-                sops_dir_override,
-                config.sop.default_execution_mode,
-            );
-            let matching: Vec<&Sop> = if let Some(ref name) = name {
-                sops.iter().filter(|s| s.name == *name).collect()
-            } else {
-                sops.iter().collect()
-            };
-
-            if matching.is_empty() {
-                if let Some(name) = name {
-                    anyhow::bail!("SOP not found: {name}");
-                }
-                println!("No SOPs to validate.");
-                return Ok(());
-            }
-
-            let mut any_warnings = false;
-            for sop in &matching {
-                let warnings = validate_sop(sop);
-                if warnings.is_empty() {
-                    println!(
-                        "  {} {} — valid",
-                        console::style("✓").green().bold(),
-                        sop.name
-                    );
-                } else {
-                    any_warnings = true;
-                    println!(
-                        "  {} {} — {} warning(s):",
-                        console::style("!").yellow().bold(),
-                        sop.name,
-                        warnings.len()
-                    );
-                    for w in &warnings {
-                        println!("      {w}");
-                    }
-                }
-            }
-            println!();
-
-            if any_warnings {
-                anyhow::bail!("Validation completed with warnings");
-            }
-            Ok(())
-        }
-
-        crate::SopCommands::Show { name } => {
-            let sops = load_sops(
-                &config.workspace_dir,
-                sops_dir_override,
-                config.sop.default_execution_mode,
-            );
-            let sop = sops
-                .iter()
-                .find(|s| s.name == name)
-                .ok_or_else(|| anyhow::anyhow!("SOP not found: {name}"))?;
-
-            println!(
-                "{} v{}",
-                console::style(&sop.name).white().bold(),
-                sop.version
-            );
-            println!("{}", sop.description);
-            println!();
-            println!("Priority:       {}", sop.priority);
-            println!("Execution mode: {}", sop.execution_mode);
-            println!("Cooldown:       {}s", sop.cooldown_secs);
-            println!("Max concurrent: {}", sop.max_concurrent);
-            println!();
-
-            if !sop.triggers.is_empty() {
-                println!("Triggers:");
-                for trigger in &sop.triggers {
-                    println!("  - {trigger}");
-                }
-                println!();
-            }
-
-            if !sop.steps.is_empty() {
-                println!("Steps:");
-                for step in &sop.steps {
-                    let confirm_tag = if step.requires_confirmation {
-                        " [requires confirmation]"
-                    } else {
-                        ""
-                    };
-                    println!(
-                        "  {}. {}{}",
-                        step.number,
-                        console::style(&step.title).bold(),
-                        confirm_tag
-                    );
-                    if !step.body.is_empty() {
-                        for line in step.body.lines() {
-                            println!("     {line}");
-                        }
-                    }
-                    if !step.suggested_tools.is_empty() {
-                        println!("     Tools: {}", step.suggested_tools.join(", "));
-                    }
-                }
-            }
-            println!();
-            Ok(())
         }
     }
+
+    warnings
 }
 
 #[cfg(test)]
@@ -704,6 +619,7 @@ type = "manual"
             steps: Vec::new(),
             cooldown_secs: 0,
             max_concurrent: 1,
+            max_step_visits: 3,
             location: None,
         };
 
@@ -729,9 +645,13 @@ type = "manual"
                 body: "Do the thing".into(),
                 suggested_tools: vec!["shell".into()],
                 requires_confirmation: false,
+                condition: None,
+                on_failure: None,
+                goto: None,
             }],
             cooldown_secs: 0,
             max_concurrent: 1,
+            max_step_visits: 3,
             location: None,
         };
 
@@ -760,6 +680,64 @@ type = "manual"
         assert_eq!(body, "Set GPIO pin LOW.");
     }
 
+    #[test]
+    fn parse_steps_branching_bullets() {
+        let md = r#"## Steps
+
+1. **Health check** — Probe /healthz.
+   - tools: http_request
+   - on_failure: 3
+
+2. **Promote** — Shift traffic.
+   - goto: step 4
+
+3. **Rollback** — Revert the release.
+   - condition: `$.steps.1.status == "failed"`
+
+4. **Verify** — Confirm metrics.
+"#;
+        let steps = parse_steps(md);
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[0].on_failure, Some(3));
+        assert_eq!(steps[0].suggested_tools, vec!["http_request"]);
+        assert_eq!(steps[1].goto, Some(4));
+        assert_eq!(
+            steps[2].condition.as_deref(),
+            Some(r#"$.steps.1.status == "failed""#)
+        );
+        assert!(steps[3].condition.is_none());
+        assert!(steps[3].goto.is_none());
+        // Branch bullets are not folded into the body
+        assert_eq!(steps[2].body, "Revert the release.");
+    }
+
+    #[test]
+    fn validate_sop_flags_bad_branch_targets() {
+        let mut sop = Sop {
+            name: "branchy".into(),
+            description: "Branching SOP".into(),
+            version: "1.0.0".into(),
+            priority: SopPriority::Normal,
+            execution_mode: SopExecutionMode::Auto,
+            triggers: vec![SopTrigger::Manual],
+            steps: parse_steps(
+                "## Steps\n\n1. **A** — a\n   - goto: 5\n2. **B** — b\n   - on_failure: 0\n",
+            ),
+            cooldown_secs: 0,
+            max_concurrent: 1,
+            max_step_visits: 3,
+            location: None,
+        };
+        let warnings = validate_sop(&sop);
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].contains("goto target 5"));
+        assert!(warnings[1].contains("on_failure target 0"));
+
+        sop.steps[0].goto = Some(1);
+        sop.steps[1].on_failure = Some(1);
+        assert!(validate_sop(&sop).is_empty());
+    }
+
     #[test]
     fn extract_bold_title_no_separator() {
         let (title, body) = extract_bold_title("**Close valve** Set pin LOW.").unwrap();
@@ -813,4 +791,46 @@ type = "manual"
         ));
         assert!(matches!(manifest.triggers[4], SopTrigger::Manual));
     }
+
+    #[test]
+    fn validate_sop_flags_invalid_trigger_patterns() {
+        let mut sop = Sop {
+            name: "bad-patterns".into(),
+            description: "Has broken triggers".into(),
+            version: "1.0.0".into(),
+            priority: SopPriority::Normal,
+            execution_mode: SopExecutionMode::Auto,
+            triggers: vec![
+                SopTrigger::Channel {
+                    channel: None,
+                    sender: None,
+                    pattern: Some("(unclosed".into()),
+                },
+                SopTrigger::FileWatch {
+                    glob: "logs/[".into(),
+                },
+            ],
+            steps: vec![SopStep {
+                number: 1,
+                title: "Step".into(),
+                body: "Do it".into(),
+                suggested_tools: vec![],
+                requires_confirmation: false,
+                condition: None,
+                on_failure: None,
+                goto: None,
+            }],
+            cooldown_secs: 0,
+            max_concurrent: 1,
+            max_step_visits: 3,
+            location: None,
+        };
+        let warnings = validate_sop(&sop);
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+
+        sop.triggers = vec![SopTrigger::FileWatch {
+            glob: "logs/*.log".into(),
+        }];
+        assert!(validate_sop(&sop).is_empty());
+    }
 }
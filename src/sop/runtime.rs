@@ -0,0 +1,332 @@
+//! Process-wide SOP runtime.
+//!
+//! The daemon, the channel pipeline and the SOP tools share one engine so a
+//! run started by a channel message or a file change can be inspected and
+//! advanced from any agent turn in the same process.
+
+use std::path::Path;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use tracing::{info, warn};
+
+use super::dispatch::{
+    check_sop_file_triggers, extract_run_id_from_action, process_headless_results,
+    run_peripheral_event_listener, DispatchResult, SopFileWatchState,
+};
+use super::{SopAuditLogger, SopEngine, SopMetricsCollector};
+use crate::channels::mqtt::run_mqtt_sop_listener;
+use crate::config::{Config, MqttConfig};
+
+static RUNTIME: LazyLock<parking_lot::Mutex<Option<Arc<SopRuntime>>>> =
+    LazyLock::new(|| parking_lot::Mutex::new(None));
+
+/// Engine plus the audit and metrics sinks every SOP entry point records to.
+pub struct SopRuntime {
+    pub engine: Arc<Mutex<SopEngine>>,
+    pub audit: Arc<SopAuditLogger>,
+    pub collector: Arc<SopMetricsCollector>,
+}
+
+impl SopRuntime {
+    /// Return the process-wide runtime, building it on first use.
+    ///
+    /// `None` when `[sop].enabled` is false or the audit memory cannot be
+    /// opened (logged; SOP triggers are then inert rather than fatal).
+    pub fn shared(config: &Config) -> Option<Arc<Self>> {
+        if !config.sop.enabled {
+            return None;
+        }
+        let mut slot = RUNTIME.lock();
+        if let Some(runtime) = slot.as_ref() {
+            return Some(Arc::clone(runtime));
+        }
+        match Self::new(config) {
+            Ok(runtime) => {
+                let runtime = Arc::new(runtime);
+                *slot = Some(Arc::clone(&runtime));
+                Some(runtime)
+            }
+            Err(e) => {
+                warn!("SOP runtime unavailable: {e}");
+                None
+            }
+        }
+    }
+
+    fn new(config: &Config) -> Result<Self> {
+        let memory = crate::memory::create_memory_with_storage_and_routes(
+            &config.memory,
+            &config.embedding_routes,
+            Some(&config.storage.provider.config),
+            &config.workspace_dir,
+            config.api_key.as_deref(),
+        )?;
+        let mut engine = SopEngine::new(config.sop.clone());
+        engine.reload(&config.workspace_dir);
+
+        // Runs left active by the previous process come back per resume_policy
+        let resumed: Vec<DispatchResult> = engine
+            .enable_persistence(&config.workspace_dir, config.sop.resume_policy)?
+            .into_iter()
+            .map(|action| {
+                let run_id = extract_run_id_from_action(&action).to_string();
+                let sop_name = engine
+                    .get_run(&run_id)
+                    .map(|run| run.sop_name.clone())
+                    .unwrap_or_default();
+                DispatchResult::Started {
+                    run_id,
+                    sop_name,
+                    action,
+                }
+            })
+            .collect();
+        if !resumed.is_empty() {
+            info!(
+                "SOP runtime rehydrated {} run(s) with policy '{}'",
+                resumed.len(),
+                config.sop.resume_policy
+            );
+            process_headless_results(&resumed);
+        }
+
+        Ok(Self {
+            engine: Arc::new(Mutex::new(engine)),
+            audit: Arc::new(SopAuditLogger::new(Arc::from(memory))),
+            collector: Arc::new(SopMetricsCollector::new()),
+        })
+    }
+}
+
+/// Daemon component: forward board events from the peripheral event bus and
+/// publishes on `[channels_config.mqtt].topics`, and poll `file_watch`
+/// triggers every `[sop].poll_interval_secs` until the daemon stops.
+pub async fn run(config: Config) -> Result<()> {
+    let runtime = SopRuntime::shared(&config)
+        .ok_or_else(|| anyhow::anyhow!("SOP runtime could not be initialized"))?;
+    let mqtt = config
+        .channels_config
+        .mqtt
+        .as_ref()
+        .filter(|mqtt| !mqtt.topics.is_empty());
+    serve(
+        runtime,
+        &config.workspace_dir,
+        Duration::from_secs(config.sop.poll_interval_secs.max(1)),
+        mqtt,
+    )
+    .await
+}
+
+async fn serve(
+    runtime: Arc<SopRuntime>,
+    workspace_dir: &Path,
+    poll_interval: Duration,
+    mqtt: Option<&MqttConfig>,
+) -> Result<()> {
+    let peripheral_events =
+        run_peripheral_event_listener(Arc::clone(&runtime.engine), Arc::clone(&runtime.audit));
+    tokio::pin!(peripheral_events);
+
+    let mqtt_events = async {
+        match mqtt {
+            Some(mqtt) => {
+                run_mqtt_sop_listener(
+                    mqtt,
+                    Arc::clone(&runtime.engine),
+                    Arc::clone(&runtime.audit),
+                )
+                .await
+            }
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(mqtt_events);
+
+    let mut file_watch = SopFileWatchState::from_engine(&runtime.engine, workspace_dir);
+    let mut interval = tokio::time::interval(poll_interval);
+    loop {
+        tokio::select! {
+            () = &mut peripheral_events => anyhow::bail!("peripheral event bus closed"),
+            result = &mut mqtt_events => {
+                result?;
+                anyhow::bail!("MQTT SOP listener stopped");
+            }
+            _ = interval.tick() => {
+                let results =
+                    check_sop_file_triggers(&runtime.engine, &runtime.audit, &mut file_watch).await;
+                process_headless_results(&results);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sop::engine::now_iso8601;
+    use crate::sop::{SopEvent, SopResumePolicy, SopRunStatus, SopTriggerSource};
+    use std::fs;
+
+    fn config_with_sop(tmp: &tempfile::TempDir, policy: SopResumePolicy) -> Config {
+        let workspace = tmp.path().join("workspace");
+        let sop_dir = workspace.join("sops").join("restart-flow");
+        fs::create_dir_all(&sop_dir).unwrap();
+        fs::write(
+            sop_dir.join("SOP.toml"),
+            r#"
+[sop]
+name = "restart-flow"
+description = "Survives restarts"
+execution_mode = "auto"
+
+[[triggers]]
+type = "manual"
+"#,
+        )
+        .unwrap();
+        fs::write(
+            sop_dir.join("SOP.md"),
+            "## Steps\n\n1. **First** — Do it.\n\n2. **Second** — Do more.\n",
+        )
+        .unwrap();
+
+        let mut config = Config {
+            workspace_dir: workspace,
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.memory.backend = "markdown".into();
+        config.sop.enabled = true;
+        config.sop.resume_policy = policy;
+        config
+    }
+
+    fn start_run(runtime: &SopRuntime) -> String {
+        let event = SopEvent {
+            source: SopTriggerSource::Manual,
+            topic: None,
+            payload: None,
+            timestamp: now_iso8601(),
+        };
+        let action = runtime
+            .engine
+            .lock()
+            .unwrap()
+            .start_run("restart-flow", event)
+            .unwrap();
+        extract_run_id_from_action(&action).to_string()
+    }
+
+    #[test]
+    fn new_runtime_resumes_persisted_runs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = config_with_sop(&tmp, SopResumePolicy::Resume);
+        let run_id = start_run(&SopRuntime::new(&config).unwrap());
+
+        let restarted = SopRuntime::new(&config).unwrap();
+        let engine = restarted.engine.lock().unwrap();
+        assert_eq!(
+            engine.active_runs().get(&run_id).map(|run| run.status),
+            Some(SopRunStatus::Running)
+        );
+    }
+
+    #[test]
+    fn new_runtime_applies_interrupt_policy() {
+        let tmp = tempfile::tempdir().unwrap();
+        let config = config_with_sop(&tmp, SopResumePolicy::Interrupt);
+        let run_id = start_run(&SopRuntime::new(&config).unwrap());
+
+        let restarted = SopRuntime::new(&config).unwrap();
+        let engine = restarted.engine.lock().unwrap();
+        assert!(engine.active_runs().is_empty());
+        assert_eq!(
+            engine.get_run(&run_id).map(|run| run.status),
+            Some(SopRunStatus::Interrupted)
+        );
+    }
+
+    #[tokio::test]
+    async fn serve_starts_runs_for_peripheral_events() {
+        use crate::peripherals::events::{publish, PeripheralEvent};
+        use crate::sop::{Sop, SopExecutionMode, SopPriority, SopStep, SopTrigger};
+
+        let tmp = tempfile::tempdir().unwrap();
+        let mut engine = SopEngine::new(crate::config::SopConfig::default());
+        engine.set_sops_for_test(vec![Sop {
+            name: "button-press".into(),
+            description: "React to the user button".into(),
+            version: "1.0.0".into(),
+            priority: SopPriority::Normal,
+            execution_mode: SopExecutionMode::Auto,
+            triggers: vec![SopTrigger::Peripheral {
+                board: "sop-runtime-test".into(),
+                signal: "button".into(),
+                condition: Some("> 0".into()),
+            }],
+            steps: vec![SopStep {
+                number: 1,
+                title: "Acknowledge".into(),
+                body: "Log the press".into(),
+                suggested_tools: vec![],
+                requires_confirmation: false,
+                condition: None,
+                goto: None,
+                on_failure: None,
+            }],
+            cooldown_secs: 0,
+            max_concurrent: 1,
+            max_step_visits: 3,
+            location: None,
+        }]);
+        let memory_config = crate::config::MemoryConfig {
+            backend: "markdown".into(),
+            ..crate::config::MemoryConfig::default()
+        };
+        let memory = crate::memory::create_memory(&memory_config, tmp.path(), None).unwrap();
+        let runtime = Arc::new(SopRuntime {
+            engine: Arc::new(Mutex::new(engine)),
+            audit: Arc::new(SopAuditLogger::new(Arc::from(memory))),
+            collector: Arc::new(SopMetricsCollector::new()),
+        });
+
+        let workspace = tmp.path().to_path_buf();
+        let served = Arc::clone(&runtime);
+        let server =
+            tokio::spawn(
+                async move { serve(served, &workspace, Duration::from_secs(60), None).await },
+            );
+
+        // The listener subscribes once the component is first polled, so
+        // keep publishing until the press is picked up.
+        let deadline = std::time::Instant::now() + Duration::from_secs(2);
+        loop {
+            publish(PeripheralEvent {
+                board: "sop-runtime-test".into(),
+                signal: "button".into(),
+                payload: Some("1".into()),
+            });
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let started: Vec<String> = runtime
+                .engine
+                .lock()
+                .unwrap()
+                .active_runs()
+                .values()
+                .map(|run| run.sop_name.clone())
+                .collect();
+            if !started.is_empty() {
+                assert_eq!(started, vec!["button-press".to_string()]);
+                break;
+            }
+            assert!(
+                std::time::Instant::now() < deadline,
+                "peripheral event never started a run"
+            );
+        }
+        server.abort();
+    }
+}
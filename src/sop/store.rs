@@ -0,0 +1,404 @@
+//! SQLite persistence for SOP runs, step results, and pending approvals.
+//!
+//! Mirrors the `heartbeat/store.rs` pattern: fresh connection per call,
+//! schema auto-created. The engine upserts a run after every state
+//! transition so that active runs survive a daemon restart.
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use std::path::{Path, PathBuf};
+
+use super::types::{SopRun, SopRunStatus, SopStepResult, SopStepStatus};
+
+/// Insert or replace a run together with its step results and approval gate.
+///
+/// The pending-approval row exists exactly while the run is `WaitingApproval`.
+pub fn save_run(workspace_dir: &Path, run: &SopRun) -> Result<()> {
+    let trigger_event = serde_json::to_string(&run.trigger_event)?;
+    let branches = serde_json::to_string(&run.branches)?;
+    with_connection(workspace_dir, |conn| {
+        let tx = conn.unchecked_transaction()?;
+
+        tx.execute(
+            "INSERT OR REPLACE INTO sop_runs
+                (run_id, sop_name, trigger_event, status, current_step, total_steps,
+                 started_at, completed_at, waiting_since, branches)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                run.run_id,
+                run.sop_name,
+                trigger_event,
+                run.status.to_string(),
+                run.current_step,
+                run.total_steps,
+                run.started_at,
+                run.completed_at,
+                run.waiting_since,
+                branches,
+            ],
+        )
+        .context("Failed to upsert SOP run")?;
+
+        tx.execute(
+            "DELETE FROM sop_step_results WHERE run_id = ?1",
+            params![run.run_id],
+        )?;
+        for (seq, result) in run.step_results.iter().enumerate() {
+            tx.execute(
+                "INSERT INTO sop_step_results
+                    (run_id, seq, step_number, status, output, started_at, completed_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    run.run_id,
+                    i64::try_from(seq).unwrap_or(i64::MAX),
+                    result.step_number,
+                    result.status.to_string(),
+                    result.output,
+                    result.started_at,
+                    result.completed_at,
+                ],
+            )
+            .context("Failed to insert SOP step result")?;
+        }
+
+        if run.status == SopRunStatus::WaitingApproval {
+            tx.execute(
+                "INSERT OR REPLACE INTO sop_pending_approvals
+                    (run_id, sop_name, step_number, requested_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    run.run_id,
+                    run.sop_name,
+                    run.current_step,
+                    run.waiting_since.as_deref().unwrap_or(&run.started_at),
+                ],
+            )
+            .context("Failed to upsert SOP approval gate")?;
+        } else {
+            tx.execute(
+                "DELETE FROM sop_pending_approvals WHERE run_id = ?1",
+                params![run.run_id],
+            )?;
+        }
+
+        tx.commit()
+            .context("Failed to commit SOP run transaction")?;
+        Ok(())
+    })
+}
+
+/// Load all non-terminal runs (Pending, Running, WaitingApproval).
+pub fn load_active_runs(workspace_dir: &Path) -> Result<Vec<SopRun>> {
+    load_runs(
+        workspace_dir,
+        "WHERE status IN ('pending', 'running', 'waiting_approval')
+         ORDER BY started_at ASC, run_id ASC",
+        None,
+    )
+}
+
+/// Load the most recent terminal runs, oldest first.
+pub fn load_finished_runs(workspace_dir: &Path, limit: usize) -> Result<Vec<SopRun>> {
+    let lim = i64::try_from(limit).unwrap_or(i64::MAX);
+    let mut runs = load_runs(
+        workspace_dir,
+        "WHERE status NOT IN ('pending', 'running', 'waiting_approval')
+         ORDER BY completed_at DESC, run_id DESC
+         LIMIT ?1",
+        Some(lim),
+    )?;
+    runs.reverse();
+    Ok(runs)
+}
+
+/// List pending approval gates as `(run_id, step_number, requested_at)`.
+pub fn list_pending_approvals(workspace_dir: &Path) -> Result<Vec<(String, u32, String)>> {
+    with_connection(workspace_dir, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT run_id, step_number, requested_at
+             FROM sop_pending_approvals
+             ORDER BY requested_at ASC, run_id ASC",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        let mut gates = Vec::new();
+        for row in rows {
+            gates.push(row?);
+        }
+        Ok(gates)
+    })
+}
+
+/// Delete terminal runs beyond the newest `keep`.
+pub fn prune_finished_runs(workspace_dir: &Path, keep: usize) -> Result<()> {
+    let keep = i64::try_from(keep).unwrap_or(i64::MAX);
+    with_connection(workspace_dir, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute(
+            "DELETE FROM sop_runs
+             WHERE status NOT IN ('pending', 'running', 'waiting_approval')
+               AND run_id NOT IN (
+                   SELECT run_id FROM sop_runs
+                   WHERE status NOT IN ('pending', 'running', 'waiting_approval')
+                   ORDER BY completed_at DESC, run_id DESC
+                   LIMIT ?1
+               )",
+            params![keep],
+        )
+        .context("Failed to prune SOP run history")?;
+        tx.execute(
+            "DELETE FROM sop_step_results
+             WHERE run_id NOT IN (SELECT run_id FROM sop_runs)",
+            [],
+        )?;
+        tx.commit()
+            .context("Failed to commit SOP prune transaction")?;
+        Ok(())
+    })
+}
+
+fn load_runs(workspace_dir: &Path, clause: &str, limit: Option<i64>) -> Result<Vec<SopRun>> {
+    with_connection(workspace_dir, |conn| {
+        let sql = format!(
+            "SELECT run_id, sop_name, trigger_event, status, current_step, total_steps,
+                    started_at, completed_at, waiting_since, branches
+             FROM sop_runs {clause}"
+        );
+        let mut stmt = conn.prepare(&sql)?;
+        let map_row = |row: &rusqlite::Row<'_>| {
+            Ok(SopRun {
+                run_id: row.get(0)?,
+                sop_name: row.get(1)?,
+                trigger_event: parse_json(&row.get::<_, String>(2)?).map_err(sql_err)?,
+                status: parse_run_status(&row.get::<_, String>(3)?).map_err(sql_err)?,
+                current_step: row.get(4)?,
+                total_steps: row.get(5)?,
+                started_at: row.get(6)?,
+                completed_at: row.get(7)?,
+                step_results: Vec::new(),
+                waiting_since: row.get(8)?,
+                branches: parse_json(&row.get::<_, String>(9)?).map_err(sql_err)?,
+            })
+        };
+        let rows = match limit {
+            Some(lim) => stmt.query_map(params![lim], map_row)?,
+            None => stmt.query_map([], map_row)?,
+        };
+
+        let mut runs = Vec::new();
+        for row in rows {
+            runs.push(row?);
+        }
+
+        let mut results_stmt = conn.prepare(
+            "SELECT step_number, status, output, started_at, completed_at
+             FROM sop_step_results
+             WHERE run_id = ?1
+             ORDER BY seq ASC",
+        )?;
+        for run in &mut runs {
+            let rows = results_stmt.query_map(params![run.run_id], |row| {
+                Ok(SopStepResult {
+                    step_number: row.get(0)?,
+                    status: parse_step_status(&row.get::<_, String>(1)?).map_err(sql_err)?,
+                    output: row.get(2)?,
+                    started_at: row.get(3)?,
+                    completed_at: row.get(4)?,
+                })
+            })?;
+            for row in rows {
+                run.step_results.push(row?);
+            }
+        }
+        Ok(runs)
+    })
+}
+
+fn db_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("sop").join("runs.db")
+}
+
+fn with_connection<T>(workspace_dir: &Path, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let path = db_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create SOP directory: {}", parent.display()))?;
+    }
+
+    let conn = Connection::open(&path)
+        .with_context(|| format!("Failed to open SOP run DB: {}", path.display()))?;
+
+    conn.execute_batch(
+        "PRAGMA journal_mode = WAL;
+         PRAGMA synchronous = NORMAL;
+         PRAGMA temp_store = MEMORY;
+
+         CREATE TABLE IF NOT EXISTS sop_runs (
+            run_id         TEXT PRIMARY KEY,
+            sop_name       TEXT NOT NULL,
+            trigger_event  TEXT NOT NULL,
+            status         TEXT NOT NULL,
+            current_step   INTEGER NOT NULL,
+            total_steps    INTEGER NOT NULL,
+            started_at     TEXT NOT NULL,
+            completed_at   TEXT,
+            waiting_since  TEXT,
+            branches       TEXT NOT NULL DEFAULT '[]'
+         );
+         CREATE INDEX IF NOT EXISTS idx_sop_runs_status ON sop_runs(status);
+
+         CREATE TABLE IF NOT EXISTS sop_step_results (
+            run_id         TEXT NOT NULL,
+            seq            INTEGER NOT NULL,
+            step_number    INTEGER NOT NULL,
+            status         TEXT NOT NULL,
+            output         TEXT NOT NULL,
+            started_at     TEXT NOT NULL,
+            completed_at   TEXT,
+            PRIMARY KEY (run_id, seq)
+         );
+
+         CREATE TABLE IF NOT EXISTS sop_pending_approvals (
+            run_id         TEXT PRIMARY KEY,
+            sop_name       TEXT NOT NULL,
+            step_number    INTEGER NOT NULL,
+            requested_at   TEXT NOT NULL
+         );",
+    )
+    .context("Failed to initialize SOP run schema")?;
+
+    f(&conn)
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(raw: &str) -> Result<T> {
+    serde_json::from_str(raw).with_context(|| format!("Invalid JSON in SOP run DB: {raw}"))
+}
+
+fn parse_run_status(raw: &str) -> Result<SopRunStatus> {
+    parse_json(&format!("\"{raw}\""))
+}
+
+fn parse_step_status(raw: &str) -> Result<SopStepStatus> {
+    parse_json(&format!("\"{raw}\""))
+}
+
+fn sql_err(err: anyhow::Error) -> rusqlite::Error {
+    rusqlite::Error::ToSqlConversionFailure(err.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sop::types::{SopBranch, SopBranchKind, SopEvent, SopTriggerSource};
+
+    fn run(run_id: &str, status: SopRunStatus) -> SopRun {
+        SopRun {
+            run_id: run_id.into(),
+            sop_name: "deploy".into(),
+            trigger_event: SopEvent {
+                source: SopTriggerSource::Webhook,
+                topic: Some("/sop/deploy".into()),
+                payload: Some(r#"{"env":"prod"}"#.into()),
+                timestamp: "2026-02-19T12:00:00Z".into(),
+            },
+            status,
+            current_step: 2,
+            total_steps: 3,
+            started_at: format!("2026-02-19T12:00:0{}Z", run_id.len() % 10),
+            completed_at: None,
+            step_results: vec![SopStepResult {
+                step_number: 1,
+                status: SopStepStatus::Completed,
+                output: "checked".into(),
+                started_at: "2026-02-19T12:00:01Z".into(),
+                completed_at: Some("2026-02-19T12:00:02Z".into()),
+            }],
+            waiting_since: None,
+            branches: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn save_and_load_active_run_roundtrip() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut r = run("run-1", SopRunStatus::WaitingApproval);
+        r.waiting_since = Some("2026-02-19T12:00:03Z".into());
+        r.branches.push(SopBranch {
+            from_step: 1,
+            to_step: Some(2),
+            kind: SopBranchKind::Goto,
+            at: "2026-02-19T12:00:02Z".into(),
+        });
+        save_run(tmp.path(), &r).unwrap();
+
+        let loaded = load_active_runs(tmp.path()).unwrap();
+        assert_eq!(loaded.len(), 1);
+        let l = &loaded[0];
+        assert_eq!(l.run_id, "run-1");
+        assert_eq!(l.status, SopRunStatus::WaitingApproval);
+        assert_eq!(l.trigger_event.topic.as_deref(), Some("/sop/deploy"));
+        assert_eq!(l.step_results.len(), 1);
+        assert_eq!(l.step_results[0].output, "checked");
+        assert_eq!(l.branches, r.branches);
+        assert_eq!(l.waiting_since.as_deref(), Some("2026-02-19T12:00:03Z"));
+
+        let gates = list_pending_approvals(tmp.path()).unwrap();
+        assert_eq!(
+            gates,
+            vec![("run-1".to_string(), 2, "2026-02-19T12:00:03Z".to_string())]
+        );
+    }
+
+    #[test]
+    fn terminal_run_clears_gate_and_leaves_active_set() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut r = run("run-1", SopRunStatus::WaitingApproval);
+        save_run(tmp.path(), &r).unwrap();
+
+        r.status = SopRunStatus::Completed;
+        r.completed_at = Some("2026-02-19T12:05:00Z".into());
+        save_run(tmp.path(), &r).unwrap();
+
+        assert!(load_active_runs(tmp.path()).unwrap().is_empty());
+        assert!(list_pending_approvals(tmp.path()).unwrap().is_empty());
+        let finished = load_finished_runs(tmp.path(), 10).unwrap();
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].status, SopRunStatus::Completed);
+    }
+
+    #[test]
+    fn resave_replaces_step_results() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mut r = run("run-1", SopRunStatus::Running);
+        save_run(tmp.path(), &r).unwrap();
+        r.step_results.push(SopStepResult {
+            step_number: 2,
+            status: SopStepStatus::Failed,
+            output: "boom".into(),
+            started_at: "2026-02-19T12:00:03Z".into(),
+            completed_at: None,
+        });
+        save_run(tmp.path(), &r).unwrap();
+
+        let loaded = load_active_runs(tmp.path()).unwrap();
+        assert_eq!(loaded[0].step_results.len(), 2);
+        assert_eq!(loaded[0].step_results[1].status, SopStepStatus::Failed);
+    }
+
+    #[test]
+    fn prune_keeps_newest_finished_runs() {
+        let tmp = tempfile::tempdir().unwrap();
+        for i in 0..4 {
+            let mut r = run(&format!("run-{i}"), SopRunStatus::Completed);
+            r.completed_at = Some(format!("2026-02-19T12:0{i}:00Z"));
+            save_run(tmp.path(), &r).unwrap();
+        }
+        save_run(tmp.path(), &run("run-active", SopRunStatus::Running)).unwrap();
+
+        prune_finished_runs(tmp.path(), 2).unwrap();
+
+        let finished = load_finished_runs(tmp.path(), 10).unwrap();
+        let ids: Vec<_> = finished.iter().map(|r| r.run_id.as_str()).collect();
+        assert_eq!(ids, vec!["run-2", "run-3"]);
+        assert_eq!(load_active_runs(tmp.path()).unwrap().len(), 1);
+    }
+}
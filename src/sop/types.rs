@@ -78,6 +78,22 @@ pub enum SopTrigger {
         #[serde(default)]
         condition: Option<String>,
     },
+    /// Incoming channel message (Slack, Telegram, …). Unset fields match any.
+    Channel {
+        #[serde(default)]
+        channel: Option<String>,
+        #[serde(default)]
+        sender: Option<String>,
+        /// Regex matched against the message content.
+        #[serde(default)]
+        pattern: Option<String>,
+    },
+    /// Workspace file created, modified or removed. `glob` is relative to the
+    /// workspace directory unless absolute.
+    #[serde(rename = "file_watch")]
+    FileWatch {
+        glob: String,
+    },
     Manual,
 }
 
@@ -88,6 +104,15 @@ impl fmt::Display for SopTrigger {
             Self::Webhook { path } => write!(f, "webhook:{path}"),
             Self::Cron { expression } => write!(f, "cron:{expression}"),
             Self::Peripheral { board, signal, .. } => write!(f, "peripheral:{board}/{signal}"),
+            Self::Channel {
+                channel, sender, ..
+            } => write!(
+                f,
+                "channel:{}/{}",
+                channel.as_deref().unwrap_or("*"),
+                sender.as_deref().unwrap_or("*")
+            ),
+            Self::FileWatch { glob } => write!(f, "file_watch:{glob}"),
             Self::Manual => write!(f, "manual"),
         }
     }
@@ -105,6 +130,16 @@ pub struct SopStep {
     pub suggested_tools: Vec<String>,
     #[serde(default)]
     pub requires_confirmation: bool,
+    /// Run this step only when the condition holds against prior step results
+    /// (see `SopRun::results_payload`); otherwise skip to the next step.
+    #[serde(default)]
+    pub condition: Option<String>,
+    /// Step to jump to when this step fails, instead of failing the run.
+    #[serde(default)]
+    pub on_failure: Option<u32>,
+    /// Step to jump to after this step succeeds, instead of the next one.
+    #[serde(default)]
+    pub goto: Option<u32>,
 }
 
 // ── SOP ─────────────────────────────────────────────────────────
@@ -123,6 +158,10 @@ pub struct Sop {
     pub cooldown_secs: u64,
     #[serde(default = "default_max_concurrent")]
     pub max_concurrent: u32,
+    /// Loop bound: how many times a single step may run within one run
+    /// before the run fails.
+    #[serde(default = "default_max_step_visits")]
+    pub max_step_visits: u32,
     #[serde(skip)]
     pub location: Option<PathBuf>,
 }
@@ -135,6 +174,10 @@ fn default_max_concurrent() -> u32 {
     1
 }
 
+fn default_max_step_visits() -> u32 {
+    3
+}
+
 // ── TOML manifest (internal parse target) ───────────────────────
 
 /// Top-level SOP.toml structure.
@@ -160,6 +203,8 @@ pub(crate) struct SopMeta {
     pub cooldown_secs: u64,
     #[serde(default = "default_max_concurrent")]
     pub max_concurrent: u32,
+    #[serde(default = "default_max_step_visits")]
+    pub max_step_visits: u32,
 }
 
 fn default_sop_version() -> String {
@@ -176,6 +221,9 @@ pub enum SopTriggerSource {
     Webhook,
     Cron,
     Peripheral,
+    Channel,
+    #[serde(rename = "file_watch")]
+    FileWatch,
     Manual,
 }
 
@@ -186,6 +234,8 @@ impl fmt::Display for SopTriggerSource {
             Self::Webhook => write!(f, "webhook"),
             Self::Cron => write!(f, "cron"),
             Self::Peripheral => write!(f, "peripheral"),
+            Self::Channel => write!(f, "channel"),
+            Self::FileWatch => write!(f, "file_watch"),
             Self::Manual => write!(f, "manual"),
         }
     }
@@ -196,6 +246,8 @@ impl fmt::Display for SopTriggerSource {
 pub struct SopEvent {
     pub source: SopTriggerSource,
     /// Topic, path, or signal identifier (depends on source type).
+    /// Channel events use `"{channel}/{sender}"`; file events use the
+    /// workspace-relative path.
     #[serde(default)]
     pub topic: Option<String>,
     /// Raw payload (JSON string, sensor reading, etc.).
@@ -217,6 +269,15 @@ pub enum SopRunStatus {
     Completed,
     Failed,
     Cancelled,
+    /// Active when the daemon stopped and not resumed on restart.
+    Interrupted,
+}
+
+impl SopRunStatus {
+    /// Whether the run has ended and will not transition again.
+    pub fn is_terminal(self) -> bool {
+        !matches!(self, Self::Pending | Self::Running | Self::WaitingApproval)
+    }
 }
 
 impl fmt::Display for SopRunStatus {
@@ -228,6 +289,30 @@ impl fmt::Display for SopRunStatus {
             Self::Completed => write!(f, "completed"),
             Self::Failed => write!(f, "failed"),
             Self::Cancelled => write!(f, "cancelled"),
+            Self::Interrupted => write!(f, "interrupted"),
+        }
+    }
+}
+
+/// What to do with runs that were still active when the daemon stopped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SopResumePolicy {
+    /// Re-issue the pending step (or approval gate) exactly where it stopped.
+    #[default]
+    Resume,
+    /// Mark every rehydrated run as `Interrupted`.
+    Interrupt,
+    /// Put every rehydrated run back behind a fresh approval gate.
+    Rerequest,
+}
+
+impl fmt::Display for SopResumePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Resume => write!(f, "resume"),
+            Self::Interrupt => write!(f, "interrupt"),
+            Self::Rerequest => write!(f, "rerequest"),
         }
     }
 }
@@ -261,6 +346,38 @@ pub struct SopStepResult {
     pub completed_at: Option<String>,
 }
 
+/// Why a run left sequential step order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SopBranchKind {
+    /// Step succeeded and its `goto` target was followed.
+    Goto,
+    /// Step failed and its `on_failure` target was followed.
+    OnFailure,
+    /// Step condition evaluated false; the step was bypassed.
+    ConditionNotMet,
+}
+
+impl fmt::Display for SopBranchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Goto => write!(f, "goto"),
+            Self::OnFailure => write!(f, "on_failure"),
+            Self::ConditionNotMet => write!(f, "condition_not_met"),
+        }
+    }
+}
+
+/// A non-sequential transition taken during a run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SopBranch {
+    pub from_step: u32,
+    /// Target step, or `None` when the branch ran past the last step.
+    pub to_step: Option<u32>,
+    pub kind: SopBranchKind,
+    pub at: String,
+}
+
 /// A full SOP execution run (from trigger to completion).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SopRun {
@@ -276,6 +393,49 @@ pub struct SopRun {
     /// ISO-8601 timestamp when the run entered WaitingApproval (for timeout tracking).
     #[serde(default)]
     pub waiting_since: Option<String>,
+    /// Non-sequential transitions (goto, on_failure, bypassed steps), in order.
+    #[serde(default)]
+    pub branches: Vec<SopBranch>,
+}
+
+impl SopRun {
+    /// How many times `step_number` has produced a result in this run.
+    pub fn step_visits(&self, step_number: u32) -> usize {
+        self.step_results
+            .iter()
+            .filter(|r| r.step_number == step_number)
+            .count()
+    }
+
+    /// JSON document that step conditions are evaluated against:
+    /// `{"steps": {"<n>": {"status", "output"}}, "last": {"step", "status", "output"}}`.
+    /// Outputs that parse as JSON are embedded as JSON; later visits of a step
+    /// overwrite earlier ones.
+    pub fn results_payload(&self) -> String {
+        fn output_value(output: &str) -> serde_json::Value {
+            serde_json::from_str(output)
+                .unwrap_or_else(|_| serde_json::Value::String(output.to_string()))
+        }
+
+        let mut steps = serde_json::Map::new();
+        for r in &self.step_results {
+            steps.insert(
+                r.step_number.to_string(),
+                serde_json::json!({ "status": r.status, "output": output_value(&r.output) }),
+            );
+        }
+        let last = self
+            .step_results
+            .last()
+            .map_or(serde_json::Value::Null, |r| {
+                serde_json::json!({
+                    "step": r.step_number,
+                    "status": r.status,
+                    "output": output_value(&r.output),
+                })
+            });
+        serde_json::json!({ "steps": steps, "last": last }).to_string()
+    }
 }
 
 /// What the engine instructs the caller to do next after a state transition.
@@ -363,6 +523,40 @@ condition = "$.value > 85"
         );
     }
 
+    #[test]
+    fn trigger_channel_and_file_watch_toml() {
+        let channel: SopTrigger = toml::from_str(
+            r#"
+type = "channel"
+channel = "slack"
+pattern = "(?i)incident"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            channel,
+            SopTrigger::Channel {
+                channel: Some("slack".into()),
+                sender: None,
+                pattern: Some("(?i)incident".into()),
+            }
+        );
+        assert_eq!(channel.to_string(), "channel:slack/*");
+
+        let watch: SopTrigger = toml::from_str(
+            r#"
+type = "file_watch"
+glob = "logs/*.log"
+"#,
+        )
+        .unwrap();
+        assert_eq!(watch.to_string(), "file_watch:logs/*.log");
+        assert_eq!(
+            serde_json::to_string(&SopTriggerSource::FileWatch).unwrap(),
+            "\"file_watch\""
+        );
+    }
+
     #[test]
     fn trigger_manual_toml() {
         let toml_str = r#"type = "manual""#;
@@ -376,6 +570,15 @@ condition = "$.value > 85"
             SopRunStatus::WaitingApproval.to_string(),
             "waiting_approval"
         );
+        assert_eq!(SopRunStatus::Interrupted.to_string(), "interrupted");
+    }
+
+    #[test]
+    fn run_status_terminal() {
+        assert!(!SopRunStatus::Running.is_terminal());
+        assert!(!SopRunStatus::WaitingApproval.is_terminal());
+        assert!(SopRunStatus::Cancelled.is_terminal());
+        assert!(SopRunStatus::Interrupted.is_terminal());
     }
 
     #[test]
@@ -385,6 +588,9 @@ condition = "$.value > 85"
                 .unwrap();
         assert!(step.suggested_tools.is_empty());
         assert!(!step.requires_confirmation);
+        assert!(step.condition.is_none());
+        assert!(step.on_failure.is_none());
+        assert!(step.goto.is_none());
     }
 
     #[test]
@@ -459,6 +665,12 @@ path = "/sop/test"
                 completed_at: Some("2026-02-19T12:00:05Z".into()),
             }],
             waiting_since: None,
+            branches: vec![SopBranch {
+                from_step: 1,
+                to_step: Some(2),
+                kind: SopBranchKind::Goto,
+                at: "2026-02-19T12:00:05Z".into(),
+            }],
         };
         let json = serde_json::to_string(&run).unwrap();
         let parsed: SopRun = serde_json::from_str(&json).unwrap();
@@ -466,5 +678,57 @@ path = "/sop/test"
         assert_eq!(parsed.status, SopRunStatus::Running);
         assert_eq!(parsed.step_results.len(), 1);
         assert_eq!(parsed.step_results[0].status, SopStepStatus::Completed);
+        assert_eq!(parsed.branches[0].kind, SopBranchKind::Goto);
+    }
+
+    #[test]
+    fn sop_run_without_branches_deserializes() {
+        let json = r#"{
+            "run_id": "run-001", "sop_name": "s",
+            "trigger_event": {"source": "manual", "timestamp": "2026-02-19T12:00:00Z"},
+            "status": "running", "current_step": 1, "total_steps": 1,
+            "started_at": "2026-02-19T12:00:00Z", "completed_at": null,
+            "step_results": []
+        }"#;
+        let run: SopRun = serde_json::from_str(json).unwrap();
+        assert!(run.branches.is_empty());
+    }
+
+    #[test]
+    fn results_payload_embeds_json_outputs() {
+        let step = |n: u32, status: SopStepStatus, output: &str| SopStepResult {
+            step_number: n,
+            status,
+            output: output.into(),
+            started_at: "2026-02-19T12:00:00Z".into(),
+            completed_at: None,
+        };
+        let run = SopRun {
+            run_id: "run-001".into(),
+            sop_name: "s".into(),
+            trigger_event: SopEvent {
+                source: SopTriggerSource::Manual,
+                topic: None,
+                payload: None,
+                timestamp: "2026-02-19T12:00:00Z".into(),
+            },
+            status: SopRunStatus::Running,
+            current_step: 3,
+            total_steps: 3,
+            started_at: "2026-02-19T12:00:00Z".into(),
+            completed_at: None,
+            step_results: vec![
+                step(1, SopStepStatus::Completed, "plain text"),
+                step(2, SopStepStatus::Failed, r#"{"http_status": 503}"#),
+            ],
+            waiting_since: None,
+            branches: Vec::new(),
+        };
+        let payload: serde_json::Value = serde_json::from_str(&run.results_payload()).unwrap();
+        assert_eq!(payload["steps"]["1"]["output"], "plain text");
+        assert_eq!(payload["steps"]["2"]["output"]["http_status"], 503);
+        assert_eq!(payload["last"]["status"], "failed");
+        assert_eq!(run.step_visits(2), 1);
+        assert_eq!(run.step_visits(3), 0);
     }
 }
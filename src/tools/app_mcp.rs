@@ -0,0 +1,1364 @@
+//! appMCP — app-manifest tool registry.
+//!
+//! Installed apps drop an `appMCP.json` manifest into `<apps_dir>/<appId>/`.
+//! [`AppMcpRegistry`] polls that directory, turns every declared tool into an
+//! [`AppMcpTool`] named `<app>__<tool>`, and hot-adds or removes the tools from
+//! every subscribed [`ActivatedToolSet`] as manifests appear, change, or
+//! disappear. The agent loop rebuilds tool specs from the activated set on each
+//! iteration, so newly installed apps become callable without a restart.
+//!
+//! Apps are MCP servers that connect *back* to the agent over WebSocket at
+//! `ws://<listen_addr>/mcp/<appId>` (Lisa is the MCP client), presenting the
+//! registry's connect token. An app does not need to be running for its tools
+//! to be registered: the first invocation launches it via the manifest's
+//! `launch` spec and waits for it to connect.
+//!
+//! See `lisa/docs/architecture/appMCP-design.md` for the full design.
+
+use std::collections::{HashMap, HashSet};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::SystemTime;
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use futures_util::stream::SplitSink;
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::{Mutex, RwLock};
+use portable_atomic::{AtomicBool, AtomicU64, Ordering};
+use serde::Deserialize;
+use serde_json::json;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{oneshot, Notify};
+use tokio::time::{timeout, Duration, Instant};
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+
+use crate::config::AppMcpConfig;
+use crate::security::pairing::constant_time_eq;
+use crate::tools::mcp_deferred::ActivatedToolSet;
+use crate::tools::mcp_protocol::{JsonRpcRequest, JsonRpcResponse, MCP_PROTOCOL_VERSION};
+use crate::tools::traits::{Tool, ToolResult};
+
+/// File name every app drops into its `<apps_dir>/<appId>/` directory.
+pub const MANIFEST_FILE_NAME: &str = "appMCP.json";
+
+/// URL path prefix apps use to identify themselves when connecting back.
+const CONNECT_PATH_PREFIX: &str = "/mcp/";
+
+/// Environment variable carrying the connect token to `exec`-launched apps.
+pub const CONNECT_TOKEN_ENV: &str = "ZEROCLAW_APP_MCP_TOKEN";
+
+/// Default per-tool timeout when the manifest omits `timeoutMs`.
+const DEFAULT_TOOL_TIMEOUT_MS: u64 = 10_000;
+
+/// Hard ceiling on manifest-declared tool timeouts.
+const MAX_TOOL_TIMEOUT_MS: u64 = 600_000;
+
+/// Timeout for the MCP `initialize` handshake after an app connects.
+const HANDSHAKE_TIMEOUT_SECS: u64 = 10;
+
+// ── Manifest ──────────────────────────────────────────────────────────────
+
+/// Parsed `appMCP.json` manifest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppManifest {
+    /// App package id (e.g. `com.netflix`). Must match the directory name.
+    pub app_id: String,
+    /// Human-readable app name.
+    pub name: String,
+    /// App version.
+    pub version: String,
+    /// MCP protocol version spoken by the app.
+    pub mcp_version: String,
+    /// Optional category, used to group tools for pruning.
+    #[serde(default)]
+    pub category: Option<String>,
+    /// How to start the app when it is not connected.
+    pub launch: AppLaunchSpec,
+    /// How the app talks MCP to the agent.
+    pub transport: AppTransportSpec,
+    /// Tools declared by the app.
+    pub tools: Vec<AppToolSpec>,
+}
+
+/// `launch` section of a manifest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLaunchSpec {
+    pub method: AppLaunchMethod,
+    /// `luna-send` service URI, or the D-Bus destination for `dbus`.
+    #[serde(default)]
+    pub uri: Option<String>,
+    /// `luna-send` payload, or `{ "path", "method" }` for `dbus`.
+    #[serde(default)]
+    pub params: Option<serde_json::Value>,
+    /// Executable for the `exec` method.
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Arguments for the `exec` method.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+/// Supported app launch mechanisms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AppLaunchMethod {
+    /// webOS Luna service call (`luna-send -n 1 <uri> <params>`).
+    LunaSend,
+    /// Spawn a local executable.
+    Exec,
+    /// Session-bus method call via `dbus-send`.
+    Dbus,
+}
+
+/// `transport` section of a manifest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppTransportSpec {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    #[serde(default = "default_transport_direction")]
+    pub direction: String,
+    #[serde(default)]
+    pub registration: Option<String>,
+}
+
+fn default_transport_direction() -> String {
+    "app-to-lisa".into()
+}
+
+/// One tool declared in a manifest.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppToolSpec {
+    pub name: String,
+    pub description: String,
+    pub input_schema: serde_json::Value,
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+}
+
+impl AppManifest {
+    /// Parse and validate a manifest file. `dir_name` is the name of the
+    /// directory the manifest was found in; it must equal `appId` so an app
+    /// cannot register tools under another app's identity.
+    pub fn load(path: &Path, dir_name: &str) -> Result<Self> {
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let manifest: Self = serde_json::from_str(&raw)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        manifest.validate(dir_name)?;
+        Ok(manifest)
+    }
+
+    fn validate(&self, dir_name: &str) -> Result<()> {
+        if self.app_id.trim().is_empty() {
+            bail!("appId must not be empty");
+        }
+        if self.app_id != dir_name {
+            bail!(
+                "appId `{}` does not match its directory `{dir_name}`",
+                self.app_id
+            );
+        }
+        if self.transport.kind != "websocket" {
+            bail!(
+                "unsupported transport type `{}` (expected `websocket`)",
+                self.transport.kind
+            );
+        }
+        if self.transport.direction != "app-to-lisa" {
+            bail!(
+                "unsupported transport direction `{}` (expected `app-to-lisa`)",
+                self.transport.direction
+            );
+        }
+        match self.launch.method {
+            AppLaunchMethod::LunaSend | AppLaunchMethod::Dbus if self.launch.uri.is_none() => {
+                bail!(
+                    "launch.uri is required for launch method {:?}",
+                    self.launch.method
+                );
+            }
+            AppLaunchMethod::Exec
+                if self
+                    .launch
+                    .command
+                    .as_deref()
+                    .map_or(true, |c| c.trim().is_empty()) =>
+            {
+                bail!("launch.command is required for launch method `exec`");
+            }
+            _ => {}
+        }
+        let mut seen = HashSet::new();
+        for tool in &self.tools {
+            if tool.name.trim().is_empty() {
+                bail!("tools[].name must not be empty");
+            }
+            if !seen.insert(tool.name.as_str()) {
+                bail!("duplicate tool name `{}`", tool.name);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Build the tool-name prefix for an app: the last segment of its id
+/// (`com.netflix` → `netflix`), or the full id when that segment is already
+/// taken by another app. Characters that providers reject in function names
+/// (such as `.`) are replaced with `_`.
+fn app_prefix(app_id: &str, taken: &HashSet<String>) -> String {
+    let short = sanitize_name(app_id.rsplit('.').next().unwrap_or(app_id));
+    if !short.is_empty() && !taken.contains(&short) {
+        return short;
+    }
+    sanitize_name(app_id)
+}
+
+fn sanitize_name(raw: &str) -> String {
+    raw.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+// ── Connections ───────────────────────────────────────────────────────────
+
+type WsSink = SplitSink<WebSocketStream<TcpStream>, Message>;
+
+/// A live MCP session with a connected app (Lisa = client, app = server).
+struct AppConnection {
+    sink: tokio::sync::Mutex<WsSink>,
+    pending: Mutex<HashMap<u64, oneshot::Sender<JsonRpcResponse>>>,
+    next_id: AtomicU64,
+    closed: AtomicBool,
+}
+
+impl AppConnection {
+    fn new(sink: WsSink) -> Self {
+        Self {
+            sink: tokio::sync::Mutex::new(sink),
+            pending: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    /// Send a JSON-RPC request and wait for the matching response.
+    async fn request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+        wait: Duration,
+    ) -> Result<serde_json::Value> {
+        if self.is_closed() {
+            bail!("connection closed");
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().insert(id, tx);
+
+        let payload = serde_json::to_string(&JsonRpcRequest::new(id, method, params))?;
+        if let Err(e) = self.sink.lock().await.send(Message::text(payload)).await {
+            self.pending.lock().remove(&id);
+            return Err(anyhow!("failed to send `{method}`: {e}"));
+        }
+
+        let resp = match timeout(wait, rx).await {
+            Ok(Ok(resp)) => resp,
+            Ok(Err(_)) => bail!("connection closed while waiting for `{method}`"),
+            Err(_) => {
+                self.pending.lock().remove(&id);
+                bail!(
+                    "timed out after {}ms waiting for `{method}`",
+                    wait.as_millis()
+                );
+            }
+        };
+        if let Some(err) = resp.error {
+            bail!("error {}: {}", err.code, err.message);
+        }
+        Ok(resp.result.unwrap_or(serde_json::Value::Null))
+    }
+
+    async fn notify(&self, method: &str, params: serde_json::Value) -> Result<()> {
+        let payload = serde_json::to_string(&JsonRpcRequest::notification(method, params))?;
+        self.sink.lock().await.send(Message::text(payload)).await?;
+        Ok(())
+    }
+
+    /// Route an inbound frame to the request waiting on its id.
+    fn dispatch(&self, text: &str) {
+        let Ok(resp) = serde_json::from_str::<JsonRpcResponse>(text) else {
+            return;
+        };
+        let Some(id) = resp.id.as_ref().and_then(serde_json::Value::as_u64) else {
+            return;
+        };
+        if let Some(tx) = self.pending.lock().remove(&id) {
+            let _ = tx.send(resp);
+        }
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        // Dropping the senders wakes every waiter with a "closed" error.
+        self.pending.lock().clear();
+    }
+}
+
+// ── Registry ──────────────────────────────────────────────────────────────
+
+struct LoadedApp {
+    manifest: AppManifest,
+    prefix: String,
+    modified: Option<SystemTime>,
+}
+
+impl LoadedApp {
+    fn tool_names(&self) -> impl Iterator<Item = String> + '_ {
+        self.manifest
+            .tools
+            .iter()
+            .map(|t| format!("{}__{}", self.prefix, t.name))
+    }
+}
+
+/// Outcome of one manifest directory scan.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AppScanReport {
+    /// Tool names registered by this scan.
+    pub added: Vec<String>,
+    /// Tool names removed by this scan.
+    pub removed: Vec<String>,
+}
+
+/// Registry of installed apps discovered from `appMCP.json` manifests.
+pub struct AppMcpRegistry {
+    apps_dir: PathBuf,
+    launch_timeout: Duration,
+    connect_token: String,
+    apps: RwLock<HashMap<String, LoadedApp>>,
+    /// Manifests that failed to load, keyed by app id, with the mtime that
+    /// failed. They are retried (and re-reported) only once the file changes.
+    broken: Mutex<HashMap<String, Option<SystemTime>>>,
+    connections: Mutex<HashMap<String, Arc<AppConnection>>>,
+    launch_locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+    connected: Notify,
+    subscribers: Mutex<Vec<Weak<std::sync::Mutex<ActivatedToolSet>>>>,
+}
+
+static SHARED_REGISTRY: tokio::sync::OnceCell<Arc<AppMcpRegistry>> =
+    tokio::sync::OnceCell::const_new();
+
+impl AppMcpRegistry {
+    pub fn new(
+        apps_dir: impl Into<PathBuf>,
+        launch_timeout: Duration,
+        connect_token: impl Into<String>,
+    ) -> Self {
+        Self {
+            apps_dir: apps_dir.into(),
+            launch_timeout,
+            connect_token: connect_token.into(),
+            apps: RwLock::new(HashMap::new()),
+            broken: Mutex::new(HashMap::new()),
+            connections: Mutex::new(HashMap::new()),
+            launch_locks: Mutex::new(HashMap::new()),
+            connected: Notify::new(),
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Start (once per process) the shared registry described by `config`:
+    /// scan the apps directory, bind the app connect-back endpoint, and spawn
+    /// the manifest watcher. Subsequent calls return the same registry.
+    pub async fn shared(config: &AppMcpConfig) -> Result<Arc<Self>> {
+        SHARED_REGISTRY
+            .get_or_try_init(|| async {
+                let apps_dir = shellexpand::tilde(&config.apps_dir).into_owned();
+                let connect_token = config
+                    .connect_token
+                    .clone()
+                    .filter(|t| !t.trim().is_empty())
+                    .unwrap_or_else(generate_connect_token);
+                let registry = Arc::new(Self::new(
+                    apps_dir,
+                    Duration::from_secs(config.launch_timeout_secs),
+                    connect_token,
+                ));
+                let addr: SocketAddr = config
+                    .listen_addr
+                    .parse()
+                    .with_context(|| format!("invalid app_mcp.listen_addr `{}`", config.listen_addr))?;
+                let bound = registry.serve(addr).await?;
+                let report = registry.rescan();
+                tracing::info!(
+                    "appMCP: {} app(s), {} tool(s) registered; apps connect at ws://{bound}{CONNECT_PATH_PREFIX}<appId>",
+                    registry.app_count(),
+                    report.added.len()
+                );
+                registry.spawn_watcher(Duration::from_secs(config.poll_interval_secs));
+                Ok(registry)
+            })
+            .await
+            .cloned()
+    }
+
+    /// Number of apps with a valid manifest.
+    pub fn app_count(&self) -> usize {
+        self.apps.read().len()
+    }
+
+    /// All currently registered prefixed tool names.
+    pub fn tool_names(&self) -> Vec<String> {
+        self.apps
+            .read()
+            .values()
+            .flat_map(LoadedApp::tool_names)
+            .collect()
+    }
+
+    /// Whether `app_id` currently has a live connection.
+    pub fn is_connected(&self, app_id: &str) -> bool {
+        self.live_connection(app_id).is_some()
+    }
+
+    /// Build tool wrappers for every registered app tool.
+    pub fn tools(self: &Arc<Self>) -> Vec<Arc<dyn Tool>> {
+        let apps = self.apps.read();
+        apps.values().flat_map(|app| self.app_tools(app)).collect()
+    }
+
+    fn app_tools(self: &Arc<Self>, app: &LoadedApp) -> Vec<Arc<dyn Tool>> {
+        app.manifest
+            .tools
+            .iter()
+            .map(|spec| {
+                Arc::new(AppMcpTool::new(
+                    &app.prefix,
+                    &app.manifest,
+                    spec,
+                    Arc::clone(self),
+                )) as Arc<dyn Tool>
+            })
+            .collect()
+    }
+
+    /// Register `activated` to receive hot-added and removed app tools. All
+    /// currently known app tools are activated immediately. Subscribing the
+    /// same set again only re-activates the tools.
+    pub fn subscribe(self: &Arc<Self>, activated: &Arc<std::sync::Mutex<ActivatedToolSet>>) {
+        {
+            let mut set = activated.lock().unwrap();
+            for tool in self.tools() {
+                set.activate(tool.name().to_string(), tool);
+            }
+        }
+        let weak = Arc::downgrade(activated);
+        let mut subscribers = self.subscribers.lock();
+        subscribers.retain(|existing| existing.strong_count() > 0);
+        if !subscribers.iter().any(|existing| existing.ptr_eq(&weak)) {
+            subscribers.push(weak);
+        }
+    }
+
+    /// Rescan the apps directory and push any tool changes to subscribers.
+    pub fn rescan(self: &Arc<Self>) -> AppScanReport {
+        let found = discover_manifests(&self.apps_dir);
+        let mut report = AppScanReport::default();
+        let mut added_tools: Vec<Arc<dyn Tool>> = Vec::new();
+
+        {
+            let mut apps = self.apps.write();
+
+            // Drop apps whose manifest vanished or changed; changed manifests
+            // are re-parsed below and keep their previous prefix.
+            let mut previous_prefix = HashMap::new();
+            apps.retain(|app_id, app| {
+                if found.get(app_id) == Some(&app.modified) {
+                    return true;
+                }
+                report.removed.extend(app.tool_names());
+                previous_prefix.insert(app_id.clone(), app.prefix.clone());
+                false
+            });
+            for app_id in previous_prefix.keys() {
+                if !found.contains_key(app_id) {
+                    tracing::info!("appMCP: app `{app_id}` removed");
+                    if let Some(conn) = self.connections.lock().remove(app_id) {
+                        conn.close();
+                    }
+                }
+            }
+
+            let mut broken = self.broken.lock();
+            broken.retain(|app_id, modified| found.get(app_id) == Some(modified));
+
+            let mut new_ids: Vec<&String> = found
+                .keys()
+                .filter(|id| !apps.contains_key(*id) && !broken.contains_key(*id))
+                .collect();
+            new_ids.sort();
+            for app_id in new_ids {
+                let path = self.apps_dir.join(app_id).join(MANIFEST_FILE_NAME);
+                let manifest = match AppManifest::load(&path, app_id) {
+                    Ok(manifest) => manifest,
+                    Err(e) => {
+                        tracing::warn!("appMCP: skipping `{app_id}`: {e:#}");
+                        broken.insert(app_id.clone(), found[app_id]);
+                        continue;
+                    }
+                };
+                let prefix = previous_prefix.remove(app_id).unwrap_or_else(|| {
+                    let taken = apps.values().map(|a| a.prefix.clone()).collect();
+                    app_prefix(app_id, &taken)
+                });
+                let app = LoadedApp {
+                    manifest,
+                    prefix,
+                    modified: found[app_id],
+                };
+                report.added.extend(app.tool_names());
+                added_tools.extend(self.app_tools(&app));
+                tracing::info!(
+                    "appMCP: app `{app_id}` registered with {} tool(s)",
+                    app.manifest.tools.len()
+                );
+                apps.insert(app_id.clone(), app);
+            }
+        }
+
+        if !report.removed.is_empty() || !added_tools.is_empty() {
+            self.publish(&report.removed, &added_tools);
+        }
+        report
+    }
+
+    fn publish(&self, removed: &[String], added: &[Arc<dyn Tool>]) {
+        let mut subscribers = self.subscribers.lock();
+        subscribers.retain(|weak| {
+            let Some(activated) = weak.upgrade() else {
+                return false;
+            };
+            let mut set = activated.lock().unwrap();
+            for name in removed {
+                set.deactivate(name);
+            }
+            for tool in added {
+                set.activate(tool.name().to_string(), Arc::clone(tool));
+            }
+            true
+        });
+    }
+
+    fn spawn_watcher(self: &Arc<Self>, interval: Duration) {
+        let weak = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(registry) = weak.upgrade() else {
+                    break;
+                };
+                registry.rescan();
+            }
+        });
+    }
+
+    /// Bind the connect-back endpoint and accept app connections in the
+    /// background. Returns the bound address.
+    pub async fn serve(self: &Arc<Self>, addr: SocketAddr) -> Result<SocketAddr> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("appMCP: failed to bind {addr}"))?;
+        let bound = listener.local_addr()?;
+        let registry = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                let (stream, peer) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        tracing::warn!("appMCP: accept failed: {e}");
+                        continue;
+                    }
+                };
+                let registry = Arc::clone(&registry);
+                tokio::spawn(async move {
+                    if let Err(e) = registry.handle_connection(stream).await {
+                        tracing::warn!("appMCP: connection from {peer} rejected: {e:#}");
+                    }
+                });
+            }
+        });
+        Ok(bound)
+    }
+
+    /// Whether a connect-back request carries the registry's token, either as
+    /// a bearer `Authorization` header or a `token` query parameter.
+    fn is_authorized(&self, req: &Request) -> bool {
+        let bearer = req
+            .headers()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        let query = req
+            .uri()
+            .query()
+            .and_then(|q| q.split('&').find_map(|pair| pair.strip_prefix("token=")));
+        bearer
+            .or(query)
+            .is_some_and(|token| constant_time_eq(token.trim(), &self.connect_token))
+    }
+
+    #[allow(clippy::result_large_err)] // tungstenite's handshake callback signature
+    async fn handle_connection(self: Arc<Self>, stream: TcpStream) -> Result<()> {
+        let mut app_id = None;
+        let ws = tokio_tungstenite::accept_hdr_async(stream, |req: &Request, resp: Response| {
+            if !self.is_authorized(req) {
+                let mut reject =
+                    ErrorResponse::new(Some("missing or invalid connect token".to_string()));
+                *reject.status_mut() = StatusCode::UNAUTHORIZED;
+                return Err(reject);
+            }
+            let id = req
+                .uri()
+                .path()
+                .strip_prefix(CONNECT_PATH_PREFIX)
+                .unwrap_or_default()
+                .to_string();
+            if !self.apps.read().contains_key(&id) {
+                let mut reject = ErrorResponse::new(Some(format!("unknown app `{id}`")));
+                *reject.status_mut() = StatusCode::FORBIDDEN;
+                return Err(reject);
+            }
+            app_id = Some(id);
+            Ok(resp)
+        })
+        .await
+        .context("websocket handshake failed")?;
+        let app_id = app_id.ok_or_else(|| anyhow!("missing app id"))?;
+
+        let (sink, mut stream) = ws.split();
+        let conn = Arc::new(AppConnection::new(sink));
+        let reader_conn = Arc::clone(&conn);
+        let reader = tokio::spawn(async move {
+            while let Some(Ok(msg)) = stream.next().await {
+                match msg {
+                    Message::Text(text) => reader_conn.dispatch(&text),
+                    Message::Close(_) => break,
+                    _ => {}
+                }
+            }
+            reader_conn.close();
+        });
+
+        let handshake = async {
+            conn.request(
+                "initialize",
+                json!({
+                    "protocolVersion": MCP_PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {
+                        "name": "zeroclaw",
+                        "version": env!("CARGO_PKG_VERSION")
+                    }
+                }),
+                Duration::from_secs(HANDSHAKE_TIMEOUT_SECS),
+            )
+            .await?;
+            conn.notify("notifications/initialized", json!({})).await
+        };
+        if let Err(e) = handshake.await {
+            reader.abort();
+            return Err(e.context(format!("app `{app_id}` failed MCP initialize")));
+        }
+
+        tracing::info!("appMCP: app `{app_id}` connected");
+        if let Some(old) = self
+            .connections
+            .lock()
+            .insert(app_id.clone(), Arc::clone(&conn))
+        {
+            old.close();
+        }
+        self.connected.notify_waiters();
+
+        let _ = reader.await;
+        let mut connections = self.connections.lock();
+        if connections
+            .get(&app_id)
+            .is_some_and(|current| Arc::ptr_eq(current, &conn))
+        {
+            connections.remove(&app_id);
+            tracing::info!("appMCP: app `{app_id}` disconnected");
+        }
+        Ok(())
+    }
+
+    fn live_connection(&self, app_id: &str) -> Option<Arc<AppConnection>> {
+        self.connections
+            .lock()
+            .get(app_id)
+            .filter(|c| !c.is_closed())
+            .cloned()
+    }
+
+    /// Return the app's connection, launching it and waiting for it to
+    /// connect back if it is not running.
+    async fn ensure_connected(&self, app_id: &str) -> Result<Arc<AppConnection>> {
+        if let Some(conn) = self.live_connection(app_id) {
+            return Ok(conn);
+        }
+        let lock = Arc::clone(
+            self.launch_locks
+                .lock()
+                .entry(app_id.to_string())
+                .or_default(),
+        );
+        let _guard = lock.lock().await;
+        // Another caller may have launched the app while we waited.
+        if let Some(conn) = self.live_connection(app_id) {
+            return Ok(conn);
+        }
+
+        let manifest = self
+            .apps
+            .read()
+            .get(app_id)
+            .map(|app| app.manifest.clone())
+            .ok_or_else(|| anyhow!("app `{app_id}` is no longer installed"))?;
+        launch_app(&manifest, &self.connect_token, self.launch_timeout)
+            .await
+            .with_context(|| format!("could not launch app `{}`", manifest.name))?;
+
+        let deadline = Instant::now() + self.launch_timeout;
+        loop {
+            let notified = self.connected.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if let Some(conn) = self.live_connection(app_id) {
+                return Ok(conn);
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                bail!(
+                    "app `{}` did not connect within {}s after launch",
+                    manifest.name,
+                    self.launch_timeout.as_secs()
+                );
+            }
+        }
+    }
+
+    /// Invoke a tool declared in `app_id`'s manifest.
+    pub async fn call_tool(
+        &self,
+        app_id: &str,
+        tool_name: &str,
+        arguments: serde_json::Value,
+        wait: Duration,
+    ) -> Result<AppToolOutput> {
+        let declared = self
+            .apps
+            .read()
+            .get(app_id)
+            .is_some_and(|app| app.manifest.tools.iter().any(|t| t.name == tool_name));
+        if !declared {
+            bail!("app `{app_id}` does not declare tool `{tool_name}`");
+        }
+        let conn = self.ensure_connected(app_id).await?;
+        let result = conn
+            .request(
+                "tools/call",
+                json!({ "name": tool_name, "arguments": arguments }),
+                wait,
+            )
+            .await
+            .with_context(|| format!("app `{app_id}` failed tool call `{tool_name}`"))?;
+        Ok(AppToolOutput::from_result(&result))
+    }
+}
+
+/// Wire appMCP tools into `activated` when `[app_mcp]` is enabled, creating
+/// the set when MCP deferred loading has not already done so. App tools are
+/// then hot-added to and removed from the set by the manifest watcher, so they
+/// reach the LLM without rebuilding the tool registry. Non-fatal: failures
+/// are logged.
+pub async fn attach_app_mcp_tools(
+    config: &AppMcpConfig,
+    activated: &mut Option<Arc<std::sync::Mutex<ActivatedToolSet>>>,
+) {
+    if !config.enabled {
+        return;
+    }
+    match AppMcpRegistry::shared(config).await {
+        Ok(registry) => {
+            let activated = activated
+                .get_or_insert_with(|| Arc::new(std::sync::Mutex::new(ActivatedToolSet::new())));
+            registry.subscribe(activated);
+        }
+        Err(e) => tracing::error!("appMCP registry failed to initialize: {e:#}"),
+    }
+}
+
+/// Random per-process connect token, used when `app_mcp.connect_token` is unset.
+fn generate_connect_token() -> String {
+    let bytes: [u8; 32] = rand::random();
+    hex::encode(bytes)
+}
+
+/// Map `<appId>` → manifest mtime for every `<apps_dir>/<appId>/appMCP.json`.
+fn discover_manifests(apps_dir: &Path) -> HashMap<String, Option<SystemTime>> {
+    let mut found = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(apps_dir) else {
+        return found;
+    };
+    for entry in entries.flatten() {
+        let manifest = entry.path().join(MANIFEST_FILE_NAME);
+        let Ok(meta) = std::fs::metadata(&manifest) else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        if let Some(app_id) = entry.file_name().to_str() {
+            found.insert(app_id.to_string(), meta.modified().ok());
+        }
+    }
+    found
+}
+
+/// Start an app using its manifest `launch` spec.
+async fn launch_app(manifest: &AppManifest, connect_token: &str, wait: Duration) -> Result<()> {
+    let launch = &manifest.launch;
+    tracing::info!(
+        "appMCP: launching `{}` via {:?}",
+        manifest.app_id,
+        launch.method
+    );
+    let mut cmd = match launch.method {
+        AppLaunchMethod::Exec => {
+            // Long-running app process: spawn and reap it in the background.
+            let command = launch.command.as_deref().unwrap_or_default();
+            let mut child = tokio::process::Command::new(command)
+                .args(&launch.args)
+                .env(CONNECT_TOKEN_ENV, connect_token)
+                .stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .spawn()
+                .with_context(|| format!("failed to spawn `{command}`"))?;
+            tokio::spawn(async move {
+                let _ = child.wait().await;
+            });
+            return Ok(());
+        }
+        AppLaunchMethod::LunaSend => {
+            let mut cmd = tokio::process::Command::new("luna-send");
+            cmd.arg("-n")
+                .arg("1")
+                .arg(launch.uri.as_deref().unwrap_or_default());
+            cmd.arg(
+                launch
+                    .params
+                    .clone()
+                    .unwrap_or_else(|| json!({}))
+                    .to_string(),
+            );
+            cmd
+        }
+        AppLaunchMethod::Dbus => {
+            let params = launch.params.clone().unwrap_or_else(|| json!({}));
+            let path = params.get("path").and_then(|v| v.as_str()).unwrap_or("/");
+            let method = params
+                .get("method")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("launch.params.method is required for `dbus`"))?;
+            let mut cmd = tokio::process::Command::new("dbus-send");
+            cmd.arg("--session")
+                .arg("--type=method_call")
+                .arg(format!(
+                    "--dest={}",
+                    launch.uri.as_deref().unwrap_or_default()
+                ))
+                .arg(path)
+                .arg(method);
+            cmd
+        }
+    };
+    let output = timeout(wait, cmd.kill_on_drop(true).output())
+        .await
+        .map_err(|_| anyhow!("launcher timed out after {}s", wait.as_secs()))?
+        .context("failed to run launcher")?;
+    if !output.status.success() {
+        bail!(
+            "launcher exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+// ── Tool wrapper ──────────────────────────────────────────────────────────
+
+/// An app's `tools/call` result, reduced to what the agent loop sees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppToolOutput {
+    /// Joined `text` content items, or the pretty-printed result when the
+    /// app returned no text content.
+    pub text: String,
+    /// The app's `isError` flag: the call ran but the tool failed.
+    pub is_error: bool,
+}
+
+impl AppToolOutput {
+    /// Extract the text content and `isError` flag from a `tools/call` result.
+    pub fn from_result(result: &serde_json::Value) -> Self {
+        let is_error = result
+            .get("isError")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let texts: Vec<&str> = result
+            .get("content")
+            .and_then(serde_json::Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter(|item| item.get("type").and_then(|t| t.as_str()) == Some("text"))
+                    .filter_map(|item| item.get("text").and_then(|t| t.as_str()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let text = if texts.is_empty() {
+            serde_json::to_string_pretty(result).unwrap_or_else(|_| result.to_string())
+        } else {
+            texts.join("\n")
+        };
+        Self { text, is_error }
+    }
+}
+
+/// A zeroclaw [`Tool`] backed by a tool declared in an app manifest.
+pub struct AppMcpTool {
+    /// Prefixed name: `<app>__<tool>`.
+    prefixed_name: String,
+    app_id: String,
+    tool_name: String,
+    description: String,
+    input_schema: serde_json::Value,
+    timeout: Duration,
+    registry: Arc<AppMcpRegistry>,
+}
+
+impl AppMcpTool {
+    fn new(
+        prefix: &str,
+        manifest: &AppManifest,
+        spec: &AppToolSpec,
+        registry: Arc<AppMcpRegistry>,
+    ) -> Self {
+        let timeout_ms = spec
+            .timeout_ms
+            .unwrap_or(DEFAULT_TOOL_TIMEOUT_MS)
+            .clamp(1, MAX_TOOL_TIMEOUT_MS);
+        Self {
+            prefixed_name: format!("{prefix}__{}", spec.name),
+            app_id: manifest.app_id.clone(),
+            tool_name: spec.name.clone(),
+            description: spec.description.clone(),
+            input_schema: spec.input_schema.clone(),
+            timeout: Duration::from_millis(timeout_ms),
+            registry,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for AppMcpTool {
+    fn name(&self) -> &str {
+        &self.prefixed_name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.input_schema.clone()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        // Apps are MCP servers and do not know about the `approved` field.
+        let args = match args {
+            serde_json::Value::Object(mut map) => {
+                map.remove("approved");
+                serde_json::Value::Object(map)
+            }
+            other => other,
+        };
+        match self
+            .registry
+            .call_tool(&self.app_id, &self.tool_name, args, self.timeout)
+            .await
+        {
+            Ok(output) if output.is_error => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(output.text),
+            }),
+            Ok(output) => Ok(ToolResult {
+                success: true,
+                output: output.text,
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("{e:#}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+
+    fn manifest_json(app_id: &str, tools: &[&str]) -> String {
+        let tools: Vec<serde_json::Value> = tools
+            .iter()
+            .map(|name| {
+                json!({
+                    "name": name,
+                    "description": format!("{name} tool"),
+                    "inputSchema": { "type": "object", "properties": {} }
+                })
+            })
+            .collect();
+        json!({
+            "appId": app_id,
+            "name": app_id,
+            "version": "1.0.0",
+            "mcpVersion": "2025-01-01",
+            "launch": { "method": "exec", "command": "/bin/false" },
+            "transport": {
+                "type": "websocket",
+                "endpoint": format!("ws://localhost:9100/mcp/{app_id}"),
+                "direction": "app-to-lisa"
+            },
+            "tools": tools
+        })
+        .to_string()
+    }
+
+    fn install(dir: &TempDir, app_id: &str, body: &str) {
+        let app_dir = dir.path().join(app_id);
+        std::fs::create_dir_all(&app_dir).unwrap();
+        std::fs::write(app_dir.join(MANIFEST_FILE_NAME), body).unwrap();
+    }
+
+    const TOKEN: &str = "test-connect-token";
+
+    fn registry(dir: &TempDir) -> Arc<AppMcpRegistry> {
+        Arc::new(AppMcpRegistry::new(
+            dir.path(),
+            Duration::from_secs(2),
+            TOKEN,
+        ))
+    }
+
+    #[test]
+    fn parses_design_doc_manifest() {
+        let raw = r#"{
+            "appId": "com.netflix",
+            "name": "Netflix",
+            "version": "2.1.0",
+            "mcpVersion": "2025-01-01",
+            "category": "entertainment",
+            "launch": {
+                "method": "luna-send",
+                "uri": "luna://com.webos.service.applicationManager/launch",
+                "params": { "id": "com.netflix" }
+            },
+            "transport": {
+                "type": "websocket",
+                "endpoint": "ws://localhost:9100/mcp/{appId}",
+                "direction": "app-to-lisa",
+                "registration": "dynamic"
+            },
+            "tools": [
+                { "name": "play", "description": "Play", "inputSchema": {"type": "object"}, "timeoutMs": 10000 }
+            ]
+        }"#;
+        let manifest: AppManifest = serde_json::from_str(raw).unwrap();
+        manifest.validate("com.netflix").unwrap();
+        assert_eq!(manifest.launch.method, AppLaunchMethod::LunaSend);
+        assert_eq!(manifest.category.as_deref(), Some("entertainment"));
+        assert_eq!(manifest.tools[0].timeout_ms, Some(10_000));
+    }
+
+    #[test]
+    fn rejects_app_id_not_matching_directory() {
+        let manifest: AppManifest =
+            serde_json::from_str(&manifest_json("com.netflix", &["search"])).unwrap();
+        let err = manifest.validate("com.evil").unwrap_err();
+        assert!(err.to_string().contains("does not match"), "got: {err}");
+    }
+
+    #[test]
+    fn prefix_falls_back_to_full_app_id_on_collision() {
+        let mut taken = HashSet::new();
+        assert_eq!(app_prefix("com.netflix", &taken), "netflix");
+        taken.insert("netflix".to_string());
+        assert_eq!(app_prefix("com.lge.netflix", &taken), "com_lge_netflix");
+    }
+
+    #[test]
+    fn tool_output_joins_text_content_and_reads_is_error() {
+        let output = AppToolOutput::from_result(&json!({
+            "content": [
+                {"type": "text", "text": "first"},
+                {"type": "image", "data": "...", "mimeType": "image/png"},
+                {"type": "text", "text": "second"}
+            ],
+            "isError": true
+        }));
+        assert_eq!(output.text, "first\nsecond");
+        assert!(output.is_error);
+
+        let raw = AppToolOutput::from_result(&json!({"answer": 42}));
+        assert!(raw.text.contains("\"answer\": 42"));
+        assert!(!raw.is_error);
+    }
+
+    #[test]
+    fn rescan_hot_adds_and_removes_tools() {
+        let dir = TempDir::new().unwrap();
+        install(
+            &dir,
+            "com.netflix",
+            &manifest_json("com.netflix", &["search", "play"]),
+        );
+        install(&dir, "com.broken", "{ not json");
+        let registry = registry(&dir);
+        let activated = Arc::new(std::sync::Mutex::new(ActivatedToolSet::new()));
+        registry.subscribe(&activated);
+
+        let report = registry.rescan();
+        let mut added = report.added.clone();
+        added.sort();
+        assert_eq!(added, vec!["netflix__play", "netflix__search"]);
+        assert!(activated.lock().unwrap().is_activated("netflix__search"));
+        assert_eq!(registry.app_count(), 1);
+
+        // Unchanged manifests are not re-registered.
+        assert_eq!(registry.rescan(), AppScanReport::default());
+
+        std::fs::remove_dir_all(dir.path().join("com.netflix")).unwrap();
+        let report = registry.rescan();
+        assert_eq!(report.removed.len(), 2);
+        assert!(!activated.lock().unwrap().is_activated("netflix__search"));
+        assert_eq!(registry.app_count(), 0);
+    }
+
+    #[test]
+    fn broken_manifest_is_retried_only_after_it_changes() {
+        let dir = TempDir::new().unwrap();
+        install(&dir, "com.broken", "{ not json");
+        let registry = registry(&dir);
+
+        registry.rescan();
+        let failed_at = registry.broken.lock().get("com.broken").copied();
+        assert!(failed_at.is_some());
+        assert_eq!(registry.rescan(), AppScanReport::default());
+        assert_eq!(registry.broken.lock().get("com.broken").copied(), failed_at);
+
+        install(&dir, "com.broken", &manifest_json("com.broken", &["fix"]));
+        let manifest = std::fs::File::options()
+            .write(true)
+            .open(dir.path().join("com.broken").join(MANIFEST_FILE_NAME))
+            .unwrap();
+        manifest
+            .set_modified(SystemTime::now() + std::time::Duration::from_secs(5))
+            .unwrap();
+        let report = registry.rescan();
+        assert_eq!(report.added, vec!["broken__fix"]);
+        assert!(registry.broken.lock().is_empty());
+    }
+
+    #[test]
+    fn subscribe_activates_existing_tools() {
+        let dir = TempDir::new().unwrap();
+        install(
+            &dir,
+            "com.youtube",
+            &manifest_json("com.youtube", &["search"]),
+        );
+        let registry = registry(&dir);
+        registry.rescan();
+
+        let activated = Arc::new(std::sync::Mutex::new(ActivatedToolSet::new()));
+        registry.subscribe(&activated);
+        assert!(activated.lock().unwrap().is_activated("youtube__search"));
+    }
+
+    #[test]
+    fn subscribe_registers_each_set_once_and_prunes_dropped_sets() {
+        let dir = TempDir::new().unwrap();
+        let registry = registry(&dir);
+
+        let activated = Arc::new(std::sync::Mutex::new(ActivatedToolSet::new()));
+        for _ in 0..3 {
+            registry.subscribe(&activated);
+        }
+        assert_eq!(registry.subscribers.lock().len(), 1);
+
+        for _ in 0..3 {
+            let transient = Arc::new(std::sync::Mutex::new(ActivatedToolSet::new()));
+            registry.subscribe(&transient);
+        }
+        registry.subscribe(&activated);
+        assert_eq!(registry.subscribers.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn execute_reports_launch_failure_as_tool_error() {
+        let dir = TempDir::new().unwrap();
+        let mut body: serde_json::Value =
+            serde_json::from_str(&manifest_json("com.melon", &["play"])).unwrap();
+        body["launch"]["command"] = json!("/nonexistent/zeroclaw-app-mcp-test");
+        install(&dir, "com.melon", &body.to_string());
+        let registry = registry(&dir);
+        registry.rescan();
+
+        let tool = registry.tools().pop().unwrap();
+        let result = tool.execute(json!({})).await.unwrap();
+        assert!(!result.success);
+        assert!(
+            result
+                .error
+                .unwrap_or_default()
+                .contains("could not launch"),
+            "launch failure should be surfaced to the LLM"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_connection_from_unknown_app() {
+        let dir = TempDir::new().unwrap();
+        let registry = registry(&dir);
+        let addr = registry
+            .serve("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let result =
+            tokio_tungstenite::connect_async(format!("ws://{addr}/mcp/com.unknown?token={TOKEN}"))
+                .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_connection_without_valid_token() {
+        let dir = TempDir::new().unwrap();
+        install(
+            &dir,
+            "com.netflix",
+            &manifest_json("com.netflix", &["search"]),
+        );
+        let registry = registry(&dir);
+        registry.rescan();
+        let addr = registry
+            .serve("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+
+        let missing =
+            tokio_tungstenite::connect_async(format!("ws://{addr}/mcp/com.netflix")).await;
+        assert!(missing.is_err());
+        let wrong =
+            tokio_tungstenite::connect_async(format!("ws://{addr}/mcp/com.netflix?token=guess"))
+                .await;
+        assert!(wrong.is_err());
+        assert!(!registry.is_connected("com.netflix"));
+    }
+
+    #[tokio::test]
+    async fn routes_tool_call_to_connected_app() {
+        let dir = TempDir::new().unwrap();
+        install(
+            &dir,
+            "com.netflix",
+            &manifest_json("com.netflix", &["search"]),
+        );
+        let registry = registry(&dir);
+        registry.rescan();
+        let addr = registry
+            .serve("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+
+        // Fake app: answer initialize and echo tools/call arguments as text
+        // content, flagging calls without a query as tool errors.
+        let mut request = format!("ws://{addr}/mcp/com.netflix")
+            .into_client_request()
+            .unwrap();
+        request
+            .headers_mut()
+            .insert("authorization", format!("Bearer {TOKEN}").parse().unwrap());
+        let (ws, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+        let (mut tx, mut rx) = ws.split();
+        tokio::spawn(async move {
+            while let Some(Ok(Message::Text(text))) = rx.next().await {
+                let req: serde_json::Value = serde_json::from_str(&text).unwrap();
+                let Some(id) = req.get("id").cloned() else {
+                    continue;
+                };
+                let arguments = &req["params"]["arguments"];
+                let result = match req["method"].as_str() {
+                    Some("initialize") => json!({ "capabilities": { "tools": {} } }),
+                    _ => json!({
+                        "content": [{ "type": "text", "text": arguments.to_string() }],
+                        "isError": arguments.get("query").is_none()
+                    }),
+                };
+                let resp = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+                tx.send(Message::text(resp.to_string())).await.unwrap();
+            }
+        });
+
+        let tool = registry.tools().pop().unwrap();
+        let result = tool
+            .execute(json!({ "query": "squid game", "approved": true }))
+            .await
+            .unwrap();
+        assert!(result.success, "error: {:?}", result.error);
+        assert_eq!(result.output, r#"{"query":"squid game"}"#);
+        assert!(registry.is_connected("com.netflix"));
+
+        let failed = tool.execute(json!({})).await.unwrap();
+        assert!(!failed.success);
+        assert_eq!(failed.error.as_deref(), Some("{}"));
+    }
+}
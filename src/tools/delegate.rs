@@ -282,26 +282,67 @@ impl Tool for DelegateTool {
 
         let temperature = agent_config.temperature.unwrap_or(0.7);
 
+        crate::cost::scope::with_agent(
+            agent_name,
+            Box::pin(self.run_agent(
+                agent_name,
+                agent_config,
+                &*provider,
+                &full_prompt,
+                temperature,
+            )),
+        )
+        .await
+    }
+}
+
+impl DelegateTool {
+    /// Run one delegation against `provider`, with usage attributed to the
+    /// agent by the caller's cost scope.
+    async fn run_agent(
+        &self,
+        agent_name: &str,
+        agent_config: &DelegateAgentConfig,
+        provider: &dyn Provider,
+        full_prompt: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ToolResult> {
         // Agentic mode: run full tool-call loop with allowlisted tools.
         if agent_config.agentic {
             return self
-                .execute_agentic(
-                    agent_name,
-                    agent_config,
-                    &*provider,
-                    &full_prompt,
-                    temperature,
-                )
+                .execute_agentic(agent_name, agent_config, provider, full_prompt, temperature)
                 .await;
         }
 
+        let mut messages = Vec::new();
+        if let Some(system_prompt) = agent_config.system_prompt.as_ref() {
+            messages.push(ChatMessage::system(system_prompt.clone()));
+        }
+        messages.push(ChatMessage::user(full_prompt.to_string()));
+        let budget_model = match crate::cost::scope::gate_llm_call(
+            provider,
+            Some(&agent_config.provider),
+            &agent_config.model,
+            &messages,
+        ) {
+            Ok(budget_model) => budget_model,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Agent '{agent_name}' failed: {e}")),
+                });
+            }
+        };
+        let model = budget_model.as_deref().unwrap_or(&agent_config.model);
+
         // Wrap the provider call in a timeout to prevent indefinite blocking
         let result = tokio::time::timeout(
             Duration::from_secs(DELEGATE_TIMEOUT_SECS),
             provider.chat_with_system(
                 agent_config.system_prompt.as_deref(),
-                &full_prompt,
-                &agent_config.model,
+                full_prompt,
+                model,
                 temperature,
             ),
         )
@@ -322,6 +363,7 @@ impl Tool for DelegateTool {
 
         match result {
             Ok(response) => {
+                crate::cost::scope::record_llm_call(model, None, None, &messages, &response);
                 let mut rendered = response;
                 if rendered.trim().is_empty() {
                     rendered = "[Empty response]".to_string();
@@ -332,7 +374,6 @@ impl Tool for DelegateTool {
                     output: format!(
                         "[Agent '{agent_name}' ({provider}/{model})]\n{rendered}",
                         provider = agent_config.provider,
-                        model = agent_config.model
                     ),
                     error: None,
                 })
@@ -344,9 +385,7 @@ impl Tool for DelegateTool {
             }),
         }
     }
-}
 
-impl DelegateTool {
     async fn execute_agentic(
         &self,
         agent_name: &str,
@@ -162,19 +162,42 @@ impl DeferredMcpToolSet {
 /// to include in the LLM request.
 pub struct ActivatedToolSet {
     tools: HashMap<String, Arc<dyn Tool>>,
+    /// Names that may be activated; `None` allows every tool.
+    allowed: Option<Vec<String>>,
 }
 
 impl ActivatedToolSet {
     pub fn new() -> Self {
         Self {
             tools: HashMap::new(),
+            allowed: None,
         }
     }
 
+    /// Drop activated tools missing from `allowed` and ignore later
+    /// activations of them (e.g. a profile's `allowed_tools`).
+    pub fn restrict_to(&mut self, allowed: Vec<String>) {
+        self.tools
+            .retain(|name, _| allowed.iter().any(|allowed| allowed == name));
+        self.allowed = Some(allowed);
+    }
+
     pub fn activate(&mut self, name: String, tool: Arc<dyn Tool>) {
+        if self
+            .allowed
+            .as_ref()
+            .is_some_and(|allowed| !allowed.contains(&name))
+        {
+            return;
+        }
         self.tools.insert(name, tool);
     }
 
+    /// Remove a previously activated tool (e.g. an uninstalled appMCP app).
+    pub fn deactivate(&mut self, name: &str) -> bool {
+        self.tools.remove(name).is_some()
+    }
+
     pub fn is_activated(&self, name: &str) -> bool {
         self.tools.contains_key(name)
     }
@@ -380,6 +403,45 @@ mod tests {
         assert!(set.get_resolved("extract_text").is_none());
     }
 
+    #[test]
+    fn restricted_set_ignores_tools_outside_the_allowlist() {
+        use crate::tools::traits::ToolResult;
+        use async_trait::async_trait;
+
+        struct FakeTool(&'static str);
+        #[async_trait]
+        impl Tool for FakeTool {
+            fn name(&self) -> &str {
+                self.0
+            }
+            fn description(&self) -> &str {
+                "fake tool"
+            }
+            fn parameters_schema(&self) -> serde_json::Value {
+                serde_json::json!({})
+            }
+            async fn execute(&self, _: serde_json::Value) -> anyhow::Result<ToolResult> {
+                Ok(ToolResult {
+                    success: true,
+                    output: String::new(),
+                    error: None,
+                })
+            }
+        }
+
+        let mut set = ActivatedToolSet::new();
+        set.activate("fs__read".into(), Arc::new(FakeTool("fs__read")));
+        set.activate("fs__write".into(), Arc::new(FakeTool("fs__write")));
+        set.restrict_to(vec!["fs__read".into(), "app__open".into()]);
+        assert!(set.is_activated("fs__read"));
+        assert!(!set.is_activated("fs__write"));
+
+        set.activate("fs__write".into(), Arc::new(FakeTool("fs__write")));
+        set.activate("app__open".into(), Arc::new(FakeTool("app__open")));
+        assert!(!set.is_activated("fs__write"));
+        assert!(set.is_activated("app__open"));
+    }
+
     #[test]
     fn build_deferred_section_empty_when_no_stubs() {
         let set = DeferredMcpToolSet {
@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::{Memory, MemoryCategory};
+use crate::memory::{Memory, MemoryCategory, MemoryMetadata};
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
@@ -43,6 +43,19 @@ impl Tool for MemoryStoreTool {
                 "category": {
                     "type": "string",
                     "description": "Memory category: 'core' (permanent), 'daily' (session), 'conversation' (chat), or a custom category name. Defaults to 'core'."
+                },
+                "tags": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Optional labels for this memory"
+                },
+                "importance": {
+                    "type": "number",
+                    "description": "How important this memory is, from 0.0 to 1.0 (default 0.5). Important memories are preferred when recalling context."
+                },
+                "ttl_hours": {
+                    "type": "number",
+                    "description": "Forget this memory after this many hours. Omit to keep it indefinitely."
                 }
             },
             "required": ["key", "content"]
@@ -67,6 +80,50 @@ impl Tool for MemoryStoreTool {
             Some(other) => MemoryCategory::Custom(other.to_string()),
         };
 
+        let importance = args.get("importance").and_then(|v| v.as_f64());
+        if importance.is_some_and(|value| !(0.0..=1.0).contains(&value)) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("'importance' must be between 0.0 and 1.0".into()),
+            });
+        }
+        let expires_at = match args.get("ttl_hours").and_then(|v| v.as_f64()) {
+            Some(hours) if hours.is_finite() && hours > 0.0 => {
+                // Out-of-range TTLs saturate and are treated as no expiry.
+                #[allow(clippy::cast_possible_truncation)]
+                chrono::Duration::try_seconds((hours * 3600.0) as i64)
+                    .and_then(|ttl| chrono::Utc::now().checked_add_signed(ttl))
+                    .map(|expires_at| expires_at.to_rfc3339())
+            }
+            Some(_) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some("'ttl_hours' must be a positive number".into()),
+                });
+            }
+            None => None,
+        };
+        // Attribute the memory to the channel and sender of the current request.
+        let scope = crate::cost::scope::current_scope();
+        let metadata = MemoryMetadata {
+            tags: args
+                .get("tags")
+                .and_then(|v| v.as_array())
+                .map(|tags| {
+                    tags.iter()
+                        .filter_map(|tag| tag.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            source_channel: scope.channel,
+            source_sender: scope.sender,
+            importance,
+            expires_at,
+            ..Default::default()
+        };
+
         if let Err(error) = self
             .security
             .enforce_tool_operation(ToolOperation::Act, "memory_store")
@@ -78,7 +135,11 @@ impl Tool for MemoryStoreTool {
             });
         }
 
-        match self.memory.store(key, content, category, None).await {
+        match self
+            .memory
+            .store_with_metadata(key, content, category, None, &metadata)
+            .await
+        {
             Ok(()) => Ok(ToolResult {
                 success: true,
                 output: format!("Stored memory: {key}"),
@@ -221,4 +282,50 @@ mod tests {
             .contains("Rate limit exceeded"));
         assert!(mem.get("lang").await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn store_with_tags_importance_and_ttl() {
+        let (_tmp, mem) = test_mem();
+        let tool = MemoryStoreTool::new(mem.clone(), test_security());
+        let scope = crate::cost::UsageScope {
+            channel: Some("telegram".into()),
+            sender: Some("alice".into()),
+            ..Default::default()
+        };
+        let result = crate::cost::scope::with_scope(
+            scope,
+            tool.execute(json!({
+                "key": "deadline",
+                "content": "Report due Friday",
+                "tags": ["work", 3],
+                "importance": 0.9,
+                "ttl_hours": 48
+            })),
+        )
+        .await
+        .unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let metadata = mem.get("deadline").await.unwrap().unwrap().metadata;
+        assert_eq!(metadata.tags, ["work"]);
+        assert_eq!(metadata.importance, Some(0.9));
+        assert_eq!(metadata.source_channel.as_deref(), Some("telegram"));
+        assert_eq!(metadata.source_sender.as_deref(), Some("alice"));
+        assert!(!metadata.is_expired());
+        assert!(metadata.expires_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn store_rejects_out_of_range_importance_and_ttl() {
+        let (_tmp, mem) = test_mem();
+        let tool = MemoryStoreTool::new(mem.clone(), test_security());
+        for args in [
+            json!({"key": "k", "content": "c", "importance": 1.5}),
+            json!({"key": "k", "content": "c", "ttl_hours": -1}),
+        ] {
+            let result = tool.execute(args).await.unwrap();
+            assert!(!result.success);
+        }
+        assert!(mem.get("k").await.unwrap().is_none());
+    }
 }
@@ -16,6 +16,7 @@
 //! [`all_tools_with_runtime`]. See `AGENTS.md` §7.3 for the full change playbook.
 
 pub mod a2web_render;
+pub mod app_mcp;
 pub mod backup_tool;
 pub mod browser;
 #[cfg(feature = "browser-cdp")]
@@ -65,6 +66,7 @@ pub mod model_routing_config;
 pub mod node_tool;
 pub mod notion_tool;
 pub mod pdf_read;
+pub mod peer_tool;
 pub mod project_intel;
 pub mod proxy_config;
 pub mod pushover;
@@ -76,6 +78,11 @@ pub mod screenshot;
 pub mod screen_control;
 pub mod security_ops;
 pub mod shell;
+pub mod sop_advance;
+pub mod sop_approve;
+pub mod sop_execute;
+pub mod sop_list;
+pub mod sop_status;
 pub mod swarm;
 pub mod tool_search;
 pub mod traits;
@@ -137,11 +144,17 @@ pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
 pub use security_ops::SecurityOpsTool;
 pub use shell::ShellTool;
+pub use sop_advance::SopAdvanceTool;
+pub use sop_approve::SopApproveTool;
+pub use sop_execute::SopExecuteTool;
+pub use sop_list::SopListTool;
+pub use sop_status::SopStatusTool;
 pub use swarm::SwarmTool;
 pub use tool_search::ToolSearchTool;
+pub(crate) use traits::collect_reply_attachments;
 pub use traits::Tool;
 #[allow(unused_imports)]
-pub use traits::{ToolResult, ToolSpec};
+pub use traits::{attach_to_reply, ToolResult, ToolSpec};
 pub use web_fetch::WebFetchTool;
 pub use web_search_tool::WebSearchTool;
 pub use workspace_tool::WorkspaceTool;
@@ -430,6 +443,27 @@ pub fn all_tools_with_runtime(
         )));
     }
 
+    // Standard operating procedures, backed by the process-wide engine
+    if let Some(sop) = crate::sop::SopRuntime::shared(root_config) {
+        tool_arcs.push(Arc::new(SopListTool::new(sop.engine.clone())));
+        tool_arcs.push(Arc::new(
+            SopExecuteTool::new(sop.engine.clone()).with_audit(sop.audit.clone()),
+        ));
+        tool_arcs.push(Arc::new(
+            SopStatusTool::new(sop.engine.clone()).with_collector(sop.collector.clone()),
+        ));
+        tool_arcs.push(Arc::new(
+            SopApproveTool::new(sop.engine.clone())
+                .with_audit(sop.audit.clone())
+                .with_collector(sop.collector.clone()),
+        ));
+        tool_arcs.push(Arc::new(
+            SopAdvanceTool::new(sop.engine.clone())
+                .with_audit(sop.audit.clone())
+                .with_collector(sop.collector.clone()),
+        ));
+    }
+
     // Backup tool (enabled by default)
     if root_config.backup.enabled {
         tool_arcs.push(Arc::new(BackupTool::new(
@@ -478,7 +512,7 @@ pub fn all_tools_with_runtime(
     if root_config.screen_control.enabled {
         use screen_control::tool::ComputerTool;
 
-        let controller: std::sync::Arc<dyn screen_control::ScreenController> =
+        let controller: anyhow::Result<std::sync::Arc<dyn screen_control::ScreenController>> =
             match root_config.screen_control.backend {
                 crate::config::ScreenControlBackend::Mac => {
                     #[cfg(not(target_os = "macos"))]
@@ -487,22 +521,46 @@ pub fn all_tools_with_runtime(
                         panic!("screen_control backend='mac' requires macOS");
                     }
                     #[cfg(target_os = "macos")]
-                    std::sync::Arc::new(screen_control::mac::MacScreenController::new(
-                        root_config.screen_control.resize_width,
+                    Ok(std::sync::Arc::new(
+                        screen_control::mac::MacScreenController::new(
+                            root_config.screen_control.resize_width,
+                        ),
                     ))
                 }
                 crate::config::ScreenControlBackend::Linux => {
-                    tracing::error!("screen_control backend='linux' is not implemented yet");
-                    panic!("screen_control backend='linux' is not implemented");
+                    #[cfg(not(target_os = "linux"))]
+                    {
+                        tracing::error!(
+                            "screen_control backend='linux' is only available on Linux"
+                        );
+                        panic!("screen_control backend='linux' requires Linux");
+                    }
+                    #[cfg(target_os = "linux")]
+                    screen_control::linux::LinuxScreenController::connect(
+                        root_config.screen_control.resize_width,
+                        root_config.screen_control.display.clone(),
+                    )
+                    .map(|controller| {
+                        std::sync::Arc::new(controller)
+                            as std::sync::Arc<dyn screen_control::ScreenController>
+                    })
                 }
             };
-        let scale = screen_control::tool::new_scale_handle();
-        tool_arcs.push(Arc::new(ComputerTool::new(
-            controller,
-            root_config.screen_control.resize_width,
-            root_config.screen_control.screenshot_delay_ms,
-            scale,
-        )));
+        match controller {
+            Ok(controller) => {
+                let scale = screen_control::tool::new_scale_handle();
+                tool_arcs.push(Arc::new(ComputerTool::new(
+                    controller,
+                    root_config.screen_control.resize_width,
+                    root_config.screen_control.screenshot_delay_ms,
+                    scale,
+                )));
+            }
+            Err(e) => {
+                tracing::error!("screen_control disabled, registering screenshot instead: {e:#}");
+                tool_arcs.push(Arc::new(ScreenshotTool::new(security.clone())));
+            }
+        }
     } else {
         tool_arcs.push(Arc::new(ScreenshotTool::new(security.clone())));
     }
@@ -586,17 +644,7 @@ pub fn all_tools_with_runtime(
 
     // Knowledge graph tool
     if root_config.knowledge.enabled {
-        let db_path_str = root_config.knowledge.db_path.replace(
-            '~',
-            &directories::UserDirs::new()
-                .map(|u| u.home_dir().to_string_lossy().to_string())
-                .unwrap_or_else(|| ".".to_string()),
-        );
-        let db_path = std::path::PathBuf::from(&db_path_str);
-        match crate::memory::knowledge_graph::KnowledgeGraph::new(
-            &db_path,
-            root_config.knowledge.max_nodes,
-        ) {
+        match crate::memory::knowledge_graph::KnowledgeGraph::from_config(&root_config.knowledge) {
             Ok(graph) => {
                 tool_arcs.push(Arc::new(KnowledgeTool::new(Arc::new(graph))));
             }
@@ -627,6 +675,7 @@ pub fn all_tools_with_runtime(
         reasoning_level: root_config.runtime.reasoning_level.clone(),
         custom_provider_auth_header: root_config.custom_provider_auth_header.clone(),
         service_tier: root_config.runtime.service_tier.clone(),
+        llama_server: root_config.llama_server.clone(),
     };
 
     let delegate_handle: Option<DelegateParentToolsHandle> = if agents.is_empty() {
@@ -741,6 +790,7 @@ mod tests {
         );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"browser_open"));
+        assert!(!names.contains(&"sop_list"));
         assert!(names.contains(&"schedule"));
         assert!(names.contains(&"model_routing_config"));
         assert!(names.contains(&"pushover"));
@@ -789,6 +839,47 @@ mod tests {
         assert!(names.contains(&"proxy_config"));
     }
 
+    #[test]
+    fn all_tools_includes_sop_tools_when_enabled() {
+        let tmp = TempDir::new().unwrap();
+        let security = Arc::new(SecurityPolicy::default());
+        let mem_cfg = MemoryConfig {
+            backend: "markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+
+        let mut cfg = test_config(&tmp);
+        cfg.memory = mem_cfg;
+        cfg.sop.enabled = true;
+
+        let (tools, _) = all_tools(
+            Arc::new(Config::default()),
+            &security,
+            mem,
+            None,
+            None,
+            &BrowserConfig::default(),
+            &crate::config::HttpRequestConfig::default(),
+            &crate::config::WebFetchConfig::default(),
+            tmp.path(),
+            &HashMap::new(),
+            None,
+            &cfg,
+        );
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        for name in [
+            "sop_list",
+            "sop_execute",
+            "sop_status",
+            "sop_approve",
+            "sop_advance",
+        ] {
+            assert!(names.contains(&name), "missing {name}");
+        }
+    }
+
     #[test]
     fn default_tools_names() {
         let security = Arc::new(SecurityPolicy::default());
@@ -0,0 +1,378 @@
+//! Wraps tools and delegate agents advertised by mesh peers as zeroclaw
+//! [`Tool`]s so the agent loop can call them like local ones.
+//!
+//! Peer tool names are prefixed with the peer name: `peer:<peer>:<tool>`.
+//! Remote delegate agents are reached through a single `mesh_delegate` tool.
+//!
+//! Peers are usually learned after the tool registry is built, so peer tools
+//! live in an [`ActivatedToolSet`] that is re-synced with the mesh directory
+//! after every refresh.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, LazyLock, Mutex, Weak};
+
+use async_trait::async_trait;
+use serde_json::json;
+
+use crate::config::NodeTransportConfig;
+use crate::nodes::mesh::{MeshAdvertisement, MeshClient};
+use crate::tools::mcp_deferred::ActivatedToolSet;
+use crate::tools::traits::{Tool, ToolResult};
+
+/// Name of the tool that delegates a task to a peer's agent.
+pub const MESH_DELEGATE_TOOL_NAME: &str = "mesh_delegate";
+
+fn failure(error: String) -> ToolResult {
+    ToolResult {
+        success: false,
+        output: String::new(),
+        error: Some(error),
+    }
+}
+
+/// A zeroclaw [`Tool`] backed by a tool on a mesh peer.
+pub struct PeerTool {
+    /// Prefixed name: `peer:<peer>:<tool>`.
+    prefixed_name: String,
+    /// Peer name from `[[node_transport.peers]]`.
+    peer: String,
+    /// The tool's name on the peer.
+    tool_name: String,
+    /// Description advertised by the peer.
+    description: String,
+    /// JSON schema advertised by the peer.
+    parameters: serde_json::Value,
+    client: Arc<MeshClient>,
+}
+
+impl PeerTool {
+    /// Create a new peer tool wrapper.
+    pub fn new(
+        peer: String,
+        tool_name: String,
+        description: String,
+        parameters: serde_json::Value,
+        client: Arc<MeshClient>,
+    ) -> Self {
+        Self {
+            prefixed_name: Self::tool_name(&peer, &tool_name),
+            description: format!("[peer {peer}] {description}"),
+            peer,
+            tool_name,
+            parameters,
+            client,
+        }
+    }
+
+    /// Build the prefixed tool name for a peer tool.
+    pub fn tool_name(peer: &str, tool_name: &str) -> String {
+        format!("peer:{peer}:{tool_name}")
+    }
+}
+
+#[async_trait]
+impl Tool for PeerTool {
+    fn name(&self) -> &str {
+        &self.prefixed_name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.parameters.clone()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        // Strip the `approved` field (same as MCP and node tools)
+        let args = match args {
+            serde_json::Value::Object(mut map) => {
+                map.remove("approved");
+                serde_json::Value::Object(map)
+            }
+            other => other,
+        };
+
+        match self.client.invoke(&self.peer, &self.tool_name, args).await {
+            Ok(result) => Ok(result),
+            Err(e) => Ok(failure(format!(
+                "Peer '{}' tool '{}' failed: {e}",
+                self.peer, self.tool_name
+            ))),
+        }
+    }
+}
+
+/// Delegates a task to a delegate agent on a mesh peer.
+pub struct PeerDelegateTool {
+    /// Agents each peer lets this node use, keyed by peer name.
+    agents: BTreeMap<String, Vec<String>>,
+    client: Arc<MeshClient>,
+}
+
+impl PeerDelegateTool {
+    pub fn new(agents: BTreeMap<String, Vec<String>>, client: Arc<MeshClient>) -> Self {
+        Self { agents, client }
+    }
+}
+
+#[async_trait]
+impl Tool for PeerDelegateTool {
+    fn name(&self) -> &str {
+        MESH_DELEGATE_TOOL_NAME
+    }
+
+    fn description(&self) -> &str {
+        "Delegate a task to an agent running on another ZeroClaw site in the mesh. \
+         Use when the task needs that site's data, devices or specialised agent."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        let available: Vec<String> = self
+            .agents
+            .iter()
+            .flat_map(|(peer, agents)| agents.iter().map(move |agent| format!("{peer}/{agent}")))
+            .collect();
+        json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "peer": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "Name of the peer site"
+                },
+                "agent": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": format!(
+                        "Agent on that peer. Available (peer/agent): {}",
+                        available.join(", ")
+                    )
+                },
+                "prompt": {
+                    "type": "string",
+                    "minLength": 1,
+                    "description": "The task/prompt to send to the remote agent"
+                },
+                "context": {
+                    "type": "string",
+                    "description": "Optional context to prepend (e.g. relevant findings)"
+                }
+            },
+            "required": ["peer", "agent", "prompt"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let field = |name: &str| {
+            args.get(name)
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let (Some(peer), Some(agent), Some(prompt)) =
+            (field("peer"), field("agent"), field("prompt"))
+        else {
+            return Ok(failure(
+                "'peer', 'agent' and 'prompt' are required".to_string(),
+            ));
+        };
+        if !self
+            .agents
+            .get(peer)
+            .is_some_and(|agents| agents.iter().any(|a| a == agent))
+        {
+            return Ok(failure(format!(
+                "Agent '{agent}' is not offered by peer '{peer}'"
+            )));
+        }
+
+        let context = field("context").map(str::to_string);
+        match self.client.delegate(peer, agent, prompt, context).await {
+            Ok(result) => Ok(result),
+            Err(e) => Ok(failure(format!("Peer '{peer}' delegation failed: {e}"))),
+        }
+    }
+}
+
+/// Build tools for everything the peers in `directory` advertise.
+fn peer_tools(
+    directory: &[(String, MeshAdvertisement)],
+    client: &Arc<MeshClient>,
+) -> Vec<Arc<dyn Tool>> {
+    let mut tools: Vec<Arc<dyn Tool>> = Vec::new();
+    let mut agents = BTreeMap::new();
+    for (peer, ad) in directory {
+        for spec in &ad.tools {
+            tools.push(Arc::new(PeerTool::new(
+                peer.clone(),
+                spec.name.clone(),
+                spec.description.clone(),
+                spec.parameters.clone(),
+                Arc::clone(client),
+            )));
+        }
+        if !ad.agents.is_empty() {
+            agents.insert(peer.clone(), ad.agents.clone());
+        }
+    }
+    if !agents.is_empty() {
+        tools.push(Arc::new(PeerDelegateTool::new(agents, Arc::clone(client))));
+    }
+    tools
+}
+
+/// Activated tool sets that receive peer tools.
+static SUBSCRIBERS: LazyLock<parking_lot::Mutex<Vec<PeerToolSubscriber>>> =
+    LazyLock::new(|| parking_lot::Mutex::new(Vec::new()));
+
+struct PeerToolSubscriber {
+    activated: Weak<Mutex<ActivatedToolSet>>,
+    client: Arc<MeshClient>,
+    /// Peer tools currently activated in the set.
+    names: HashSet<String>,
+}
+
+impl PeerToolSubscriber {
+    /// Replace the set's peer tools with those in `directory`. Returns
+    /// `false` once the set has been dropped.
+    fn sync(&mut self, directory: &[(String, MeshAdvertisement)]) -> bool {
+        let Some(activated) = self.activated.upgrade() else {
+            return false;
+        };
+        let tools = peer_tools(directory, &self.client);
+        let names: HashSet<String> = tools.iter().map(|tool| tool.name().to_string()).collect();
+        let mut set = activated.lock().unwrap();
+        for stale in self.names.difference(&names) {
+            set.deactivate(stale);
+        }
+        for tool in tools {
+            set.activate(tool.name().to_string(), tool);
+        }
+        self.names = names;
+        true
+    }
+}
+
+/// Wire mesh peer tools into `activated` when the mesh is configured,
+/// creating the set when MCP deferred loading has not already done so. Peer
+/// tools are then added and removed as the mesh directory changes.
+pub fn attach_peer_tools(
+    config: &NodeTransportConfig,
+    activated: &mut Option<Arc<Mutex<ActivatedToolSet>>>,
+) {
+    let Some(client) = MeshClient::from_config(config) else {
+        return;
+    };
+    let activated = activated.get_or_insert_with(|| Arc::new(Mutex::new(ActivatedToolSet::new())));
+    let mut subscriber = PeerToolSubscriber {
+        activated: Arc::downgrade(activated),
+        client: Arc::new(client),
+        names: HashSet::new(),
+    };
+    subscriber.sync(&crate::nodes::mesh::directory());
+    SUBSCRIBERS.lock().push(subscriber);
+}
+
+/// Re-sync every attached tool set with `directory`; called after each mesh
+/// refresh.
+pub fn sync_peer_tools(directory: &[(String, MeshAdvertisement)]) {
+    SUBSCRIBERS
+        .lock()
+        .retain_mut(|subscriber| subscriber.sync(directory));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{MeshPeerConfig, NodeTransportConfig};
+    use crate::tools::traits::ToolSpec;
+
+    fn client() -> Arc<MeshClient> {
+        let config = NodeTransportConfig {
+            peers: vec![MeshPeerConfig {
+                name: "site-b".into(),
+                address: "127.0.0.1:9".into(),
+                shared_secret: "a-b-secret".into(),
+                allowed_tools: Vec::new(),
+                allowed_agents: Vec::new(),
+            }],
+            ..NodeTransportConfig::default()
+        };
+        Arc::new(MeshClient::from_config(&config).unwrap())
+    }
+
+    #[test]
+    fn peer_tools_surface_advertised_tools_and_agents() {
+        let ad = MeshAdvertisement {
+            node: "site-b".into(),
+            tools: vec![ToolSpec {
+                name: "shell".into(),
+                description: "Run a command".into(),
+                parameters: json!({"type": "object", "properties": {}}),
+            }],
+            agents: vec!["researcher".into()],
+            sops: Vec::new(),
+        };
+        let tools = peer_tools(&[("site-b".into(), ad)], &client());
+        let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
+        assert_eq!(names, vec!["peer:site-b:shell", MESH_DELEGATE_TOOL_NAME]);
+        assert_eq!(tools[0].description(), "[peer site-b] Run a command");
+        let schema = tools[1].parameters_schema();
+        assert!(schema["properties"]["agent"]["description"]
+            .as_str()
+            .unwrap()
+            .contains("site-b/researcher"));
+    }
+
+    #[test]
+    fn subscriber_tracks_directory_changes() {
+        let ad = |tools: &[&str]| MeshAdvertisement {
+            node: "site-b".into(),
+            tools: tools
+                .iter()
+                .map(|name| ToolSpec {
+                    name: (*name).into(),
+                    description: String::new(),
+                    parameters: json!({"type": "object"}),
+                })
+                .collect(),
+            agents: Vec::new(),
+            sops: Vec::new(),
+        };
+        let activated = Arc::new(Mutex::new(ActivatedToolSet::new()));
+        let mut subscriber = PeerToolSubscriber {
+            activated: Arc::downgrade(&activated),
+            client: client(),
+            names: HashSet::new(),
+        };
+
+        assert!(subscriber.sync(&[("site-b".into(), ad(&["shell", "camera"]))]));
+        assert!(activated.lock().unwrap().is_activated("peer:site-b:camera"));
+
+        assert!(subscriber.sync(&[("site-b".into(), ad(&["shell"]))]));
+        let set = activated.lock().unwrap();
+        assert!(set.is_activated("peer:site-b:shell"));
+        assert!(!set.is_activated("peer:site-b:camera"));
+        drop(set);
+
+        drop(activated);
+        assert!(!subscriber.sync(&[]));
+    }
+
+    #[tokio::test]
+    async fn mesh_delegate_rejects_agents_the_peer_does_not_offer() {
+        let tool = PeerDelegateTool::new(
+            BTreeMap::from([("site-b".to_string(), vec!["researcher".to_string()])]),
+            client(),
+        );
+        let result = tool
+            .execute(json!({"peer": "site-b", "agent": "admin", "prompt": "hi"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not offered"));
+    }
+}
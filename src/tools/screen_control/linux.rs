@@ -0,0 +1,526 @@
+//! Linux ScreenController 구현 (X11 / Xvfb)
+//!
+//! 의존성:
+//! - 캡처: `maim` / `import` (ImageMagick) / `scrot`
+//! - 입력: `xdotool`
+//!
+//! Wayland는 지원하지 않는다. xdotool은 X11 전용이라 Wayland 세션에서는
+//! 입력이 전달되지 않으므로, `connect()`가 에러를 반환하고 호출자는
+//! `[screen_control].display`로 X11/Xvfb 디스플레이를 지정해야 한다.
+//!
+//! 리사이즈와 JPEG 인코딩은 `image` crate로 프로세스 내에서 처리하므로
+//! `CaptureResult`의 scale 계산은 macOS 백엔드와 동일하다.
+
+use super::{CaptureResult, ScreenController};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use std::time::Duration;
+use tokio::process::Command;
+
+const COMMAND_TIMEOUT_SECS: u64 = 15;
+
+/// JPEG 품질 (macOS `sips formatOptions 40`과 동일)
+const JPEG_QUALITY: u8 = 40;
+
+/// 화면 캡처 도구
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CaptureBackend {
+    Maim,
+    Import,
+    Scrot,
+}
+
+pub struct LinuxScreenController {
+    pub default_resize_width: u32,
+    /// X 디스플레이 (예: ":0", ":99"). None이면 프로세스의 $DISPLAY 사용.
+    display: Option<String>,
+    /// `connect()` 시점에 xdotool로 조회한 화면 해상도
+    resolution: (u32, u32),
+}
+
+impl LinuxScreenController {
+    /// X 디스플레이에 연결하고 해상도를 조회한다.
+    ///
+    /// `display`가 None이면 프로세스의 $DISPLAY를 사용한다. Wayland 세션이거나
+    /// 해상도를 조회할 수 없으면 에러.
+    pub fn connect(default_resize_width: u32, display: Option<String>) -> Result<Self> {
+        if is_wayland_session(
+            display.as_deref(),
+            std::env::var_os("WAYLAND_DISPLAY").is_some(),
+        ) {
+            anyhow::bail!(
+                "screen_control backend='linux' does not support Wayland sessions \
+                 (xdotool is X11-only); set [screen_control].display to an X11 or Xvfb \
+                 display such as \":99\""
+            );
+        }
+        let mut controller = Self {
+            default_resize_width,
+            display,
+            resolution: (0, 0),
+        };
+        controller.resolution = controller.query_resolution()?;
+        Ok(controller)
+    }
+
+    fn command(&self, program: &str) -> Command {
+        let mut cmd = Command::new(program);
+        if let Some(display) = &self.display {
+            cmd.env("DISPLAY", display);
+        }
+        cmd
+    }
+
+    async fn run(&self, program: &str, args: &[&str]) -> Result<Vec<u8>> {
+        let output = tokio::time::timeout(
+            Duration::from_secs(COMMAND_TIMEOUT_SECS),
+            self.command(program).args(args).kill_on_drop(true).output(),
+        )
+        .await
+        .with_context(|| format!("{program} timed out"))?
+        .with_context(|| format!("failed to run {program}"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("{program} failed: {stderr}");
+        }
+        Ok(output.stdout)
+    }
+
+    async fn xdotool(&self, args: &[&str]) -> Result<()> {
+        self.run("xdotool", args).await?;
+        Ok(())
+    }
+
+    /// 설치된 X11 캡처 도구 중 첫 번째
+    fn capture_backend(&self) -> Result<CaptureBackend> {
+        let candidates: &[(CaptureBackend, &str)] = &[
+            (CaptureBackend::Maim, "maim"),
+            (CaptureBackend::Import, "import"),
+            (CaptureBackend::Scrot, "scrot"),
+        ];
+        candidates
+            .iter()
+            .find(|(_, bin)| which::which(bin).is_ok())
+            .map(|(backend, _)| *backend)
+            .ok_or_else(|| {
+                let names: Vec<&str> = candidates.iter().map(|(_, bin)| *bin).collect();
+                anyhow::anyhow!(
+                    "no screen capture tool found (install one of: {})",
+                    names.join(", ")
+                )
+            })
+    }
+
+    async fn capture_png(&self) -> Result<Vec<u8>> {
+        match self.capture_backend()? {
+            CaptureBackend::Maim => self.run("maim", &["--format", "png"]).await,
+            CaptureBackend::Import => self.run("import", &["-window", "root", "png:-"]).await,
+            CaptureBackend::Scrot => {
+                let tmp = tempfile::Builder::new()
+                    .prefix("lisa_snap_")
+                    .suffix(".png")
+                    .tempfile()
+                    .context("failed to create temp png")?;
+                let path = tmp.path().to_string_lossy().to_string();
+                self.run("scrot", &["--overwrite", &path]).await?;
+                tokio::fs::read(&path)
+                    .await
+                    .context("failed to read scrot output")
+            }
+        }
+    }
+
+    /// `xdotool getdisplaygeometry` — sync blocking, `connect()`에서 1회만 호출
+    fn query_resolution(&self) -> Result<(u32, u32)> {
+        let mut cmd = std::process::Command::new("xdotool");
+        cmd.arg("getdisplaygeometry");
+        if let Some(display) = &self.display {
+            cmd.env("DISPLAY", display);
+        }
+        let output = cmd.output().context("failed to run xdotool")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("xdotool getdisplaygeometry failed: {stderr}");
+        }
+        let text = String::from_utf8_lossy(&output.stdout);
+        let mut dims = text
+            .split_whitespace()
+            .filter_map(|v| v.parse::<u32>().ok());
+        match (dims.next(), dims.next()) {
+            (Some(w), Some(h)) if w > 0 && h > 0 => Ok((w, h)),
+            _ => anyhow::bail!("failed to parse display geometry: {text}"),
+        }
+    }
+
+    /// 이름 → xdotool keysym ("return" → "Return", "ctrl" → "ctrl")
+    fn name_to_keysym(name: &str) -> Result<String> {
+        let lower = name.to_ascii_lowercase();
+        let keysym = match lower.as_str() {
+            // 제어 키
+            "return" | "enter" => "Return",
+            "tab" => "Tab",
+            "space" => "space",
+            "delete" | "backspace" => "BackSpace",
+            "forwarddelete" => "Delete",
+            "escape" | "esc" => "Escape",
+            // 방향 / 내비게이션
+            "up" => "Up",
+            "down" => "Down",
+            "left" => "Left",
+            "right" => "Right",
+            "home" => "Home",
+            "end" => "End",
+            "pageup" | "page_up" => "Prior",
+            "pagedown" | "page_down" => "Next",
+            // 특수문자
+            "-" | "minus" => "minus",
+            "=" | "equal" => "equal",
+            "[" | "leftbracket" => "bracketleft",
+            "]" | "rightbracket" => "bracketright",
+            "\\" | "backslash" => "backslash",
+            ";" | "semicolon" => "semicolon",
+            "'" | "quote" => "apostrophe",
+            "," | "comma" => "comma",
+            "." | "period" => "period",
+            "/" | "slash" => "slash",
+            "`" | "grave" => "grave",
+            // F키
+            f if f.len() <= 3
+                && f.starts_with('f')
+                && f[1..].parse::<u8>().is_ok_and(|n| (1..=12).contains(&n)) =>
+            {
+                return Ok(f.to_ascii_uppercase());
+            }
+            // 영문/숫자 단일 문자
+            c if c.len() == 1 && c.chars().all(|ch| ch.is_ascii_alphanumeric()) => {
+                return Ok(c.to_string());
+            }
+            _ => anyhow::bail!("unsupported key: {name}"),
+        };
+        Ok(keysym.to_string())
+    }
+
+    fn modifier_keysym(name: &str) -> Result<&'static str> {
+        Ok(match name.to_ascii_lowercase().as_str() {
+            "cmd" | "command" | "super" | "meta" => "super",
+            "ctrl" | "control" => "ctrl",
+            "alt" | "option" | "opt" => "alt",
+            "shift" => "shift",
+            _ => anyhow::bail!("unknown modifier: {name}"),
+        })
+    }
+
+    /// "ctrl+shift+s" → "ctrl+shift+s" (xdotool 형식), 단일 키도 처리
+    fn to_xdotool_combo(key: &str) -> Result<String> {
+        let parts: Vec<&str> = key.split('+').map(str::trim).collect();
+        if parts.iter().any(|p| p.is_empty()) {
+            anyhow::bail!("invalid combo: {key}");
+        }
+        let (last, modifiers) = parts.split_last().expect("split yields at least one part");
+        let mut out: Vec<String> = modifiers
+            .iter()
+            .map(|m| Self::modifier_keysym(m).map(str::to_string))
+            .collect::<Result<_>>()?;
+        out.push(Self::name_to_keysym(last)?);
+        Ok(out.join("+"))
+    }
+}
+
+/// 명시한 X 디스플레이 없이 Wayland 세션에서 실행 중인지
+fn is_wayland_session(display: Option<&str>, wayland_display_set: bool) -> bool {
+    display.is_none() && wayland_display_set
+}
+
+/// PNG 원본 → 리사이즈 + JPEG base64 (`CaptureResult` 생성)
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn encode_capture(png: &[u8], target_width: u32) -> Result<CaptureResult> {
+    let img = image::load_from_memory(png).context("failed to decode screenshot")?;
+    let (orig_w, orig_h) = (img.width(), img.height());
+    let img = if target_width > 0 && orig_w > target_width {
+        let target_height = (f64::from(orig_h) * f64::from(target_width) / f64::from(orig_w))
+            .round()
+            .max(1.0) as u32;
+        img.resize_exact(
+            target_width,
+            target_height,
+            image::imageops::FilterType::Triangle,
+        )
+    } else {
+        img
+    };
+    let (resized_w, resized_h) = (img.width(), img.height());
+
+    let mut bytes = Vec::new();
+    let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY);
+    encoder
+        .encode_image(&img.to_rgb8())
+        .context("failed to encode jpeg")?;
+    let b64 = base64::engine::general_purpose::STANDARD.encode(&bytes);
+
+    let scale_x = if resized_w > 0 {
+        f64::from(orig_w) / f64::from(resized_w)
+    } else {
+        1.0
+    };
+    let scale_y = if resized_h > 0 {
+        f64::from(orig_h) / f64::from(resized_h)
+    } else {
+        1.0
+    };
+
+    Ok(CaptureResult {
+        data_uri: format!("data:image/jpeg;base64,{b64}"),
+        orig_width: orig_w,
+        orig_height: orig_h,
+        resized_width: resized_w,
+        resized_height: resized_h,
+        scale_x,
+        scale_y,
+        file_size_bytes: bytes.len() as u64,
+    })
+}
+
+#[async_trait]
+impl ScreenController for LinuxScreenController {
+    async fn capture(&self, resize_width: Option<u32>) -> Result<CaptureResult> {
+        let target_width = resize_width.unwrap_or(self.default_resize_width);
+        let png = self.capture_png().await?;
+        tokio::task::spawn_blocking(move || encode_capture(&png, target_width))
+            .await
+            .context("capture encoding task failed")?
+    }
+
+    async fn click(&self, x: i32, y: i32) -> Result<()> {
+        self.xdotool(&[
+            "mousemove",
+            "--sync",
+            &x.to_string(),
+            &y.to_string(),
+            "click",
+            "1",
+        ])
+        .await
+    }
+
+    async fn double_click(&self, x: i32, y: i32) -> Result<()> {
+        self.xdotool(&[
+            "mousemove",
+            "--sync",
+            &x.to_string(),
+            &y.to_string(),
+            "click",
+            "--repeat",
+            "2",
+            "1",
+        ])
+        .await
+    }
+
+    async fn right_click(&self, x: i32, y: i32) -> Result<()> {
+        self.xdotool(&[
+            "mousemove",
+            "--sync",
+            &x.to_string(),
+            &y.to_string(),
+            "click",
+            "3",
+        ])
+        .await
+    }
+
+    async fn triple_click(&self, x: i32, y: i32) -> Result<()> {
+        self.xdotool(&[
+            "mousemove",
+            "--sync",
+            &x.to_string(),
+            &y.to_string(),
+            "click",
+            "--repeat",
+            "3",
+            "1",
+        ])
+        .await
+    }
+
+    async fn type_text(&self, text: &str) -> Result<()> {
+        // xdotool type은 keysym 매핑으로 유니코드(한글 포함)를 직접 입력 — 클립보드 미사용
+        self.xdotool(&["type", "--clearmodifiers", "--delay", "12", "--", text])
+            .await
+    }
+
+    async fn press_key(&self, key: &str) -> Result<()> {
+        let combo = Self::to_xdotool_combo(key)?;
+        self.xdotool(&["key", "--clearmodifiers", &combo]).await
+    }
+
+    async fn scroll(&self, direction: &str, amount: u32) -> Result<()> {
+        // X11 휠 버튼: 4=up, 5=down, 6=left, 7=right
+        let button = match direction.to_ascii_lowercase().as_str() {
+            "up" => "4",
+            "down" => "5",
+            "left" => "6",
+            "right" => "7",
+            _ => anyhow::bail!("unknown scroll direction: {direction}"),
+        };
+        let repeat = amount.max(1).to_string();
+        self.xdotool(&["click", "--repeat", &repeat, button]).await
+    }
+
+    async fn drag(&self, from: (i32, i32), to: (i32, i32)) -> Result<()> {
+        self.xdotool(&[
+            "mousemove",
+            "--sync",
+            &from.0.to_string(),
+            &from.1.to_string(),
+            "mousedown",
+            "1",
+            "mousemove",
+            "--sync",
+            &to.0.to_string(),
+            &to.1.to_string(),
+            "mouseup",
+            "1",
+        ])
+        .await
+    }
+
+    async fn move_cursor(&self, x: i32, y: i32) -> Result<()> {
+        self.xdotool(&["mousemove", "--sync", &x.to_string(), &y.to_string()])
+            .await
+    }
+
+    async fn cursor_position(&self) -> Result<(i32, i32)> {
+        let out = self
+            .run("xdotool", &["getmouselocation", "--shell"])
+            .await?;
+        let text = String::from_utf8_lossy(&out);
+        let field = |name: &str| {
+            text.lines()
+                .find_map(|l| l.strip_prefix(name))
+                .and_then(|v| v.trim().parse::<i32>().ok())
+        };
+        match (field("X="), field("Y=")) {
+            (Some(x), Some(y)) => Ok((x, y)),
+            _ => anyhow::bail!("failed to parse cursor position: {text}"),
+        }
+    }
+
+    async fn mouse_down(&self, x: i32, y: i32) -> Result<()> {
+        self.xdotool(&[
+            "mousemove",
+            "--sync",
+            &x.to_string(),
+            &y.to_string(),
+            "mousedown",
+            "1",
+        ])
+        .await
+    }
+
+    async fn mouse_up(&self, x: i32, y: i32) -> Result<()> {
+        self.xdotool(&[
+            "mousemove",
+            "--sync",
+            &x.to_string(),
+            &y.to_string(),
+            "mouseup",
+            "1",
+        ])
+        .await
+    }
+
+    async fn hold_key(&self, key: &str, duration_secs: f64) -> Result<()> {
+        let combo = Self::to_xdotool_combo(key)?;
+        self.xdotool(&["keydown", &combo]).await?;
+        tokio::time::sleep(Duration::from_secs_f64(duration_secs.clamp(0.0, 10.0))).await;
+        self.xdotool(&["keyup", &combo]).await
+    }
+
+    fn resolution(&self) -> (u32, u32) {
+        self.resolution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_of(width: u32, height: u32) -> Vec<u8> {
+        let img = image::RgbImage::from_pixel(width, height, image::Rgb([200, 40, 40]));
+        let mut buf = std::io::Cursor::new(Vec::new());
+        img.write_to(&mut buf, image::ImageFormat::Png).unwrap();
+        buf.into_inner()
+    }
+
+    #[test]
+    fn encode_capture_scales_like_mac_backend() {
+        let result = encode_capture(&png_of(2560, 1600), 1024).unwrap();
+        assert!(result.data_uri.starts_with("data:image/jpeg;base64,"));
+        assert_eq!((result.orig_width, result.orig_height), (2560, 1600));
+        assert_eq!((result.resized_width, result.resized_height), (1024, 640));
+        assert!((result.scale_x - 2.5).abs() < f64::EPSILON);
+        assert!((result.scale_y - 2.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn encode_capture_keeps_small_images() {
+        let result = encode_capture(&png_of(800, 600), 1024).unwrap();
+        assert_eq!((result.resized_width, result.resized_height), (800, 600));
+        assert!((result.scale_x - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn combo_keys_map_to_xdotool_syntax() {
+        assert_eq!(
+            LinuxScreenController::to_xdotool_combo("Return").unwrap(),
+            "Return"
+        );
+        assert_eq!(
+            LinuxScreenController::to_xdotool_combo("cmd+shift+s").unwrap(),
+            "super+shift+s"
+        );
+        assert_eq!(
+            LinuxScreenController::to_xdotool_combo("ctrl+F5").unwrap(),
+            "ctrl+F5"
+        );
+        assert!(LinuxScreenController::to_xdotool_combo("hyper+x").is_err());
+        assert!(LinuxScreenController::to_xdotool_combo("ctrl+").is_err());
+    }
+
+    #[test]
+    fn wayland_session_requires_an_explicit_x11_display() {
+        assert!(is_wayland_session(None, true));
+        assert!(!is_wayland_session(Some(":99"), true));
+        assert!(!is_wayland_session(None, false));
+    }
+
+    /// 실제 Xvfb 디스플레이로 캡처/입력 검증
+    #[tokio::test]
+    #[ignore = "requires Xvfb, xdotool and one of maim/import/scrot"]
+    async fn xvfb_capture_and_input_roundtrip() {
+        let display = ":97";
+        let mut xvfb = std::process::Command::new("Xvfb")
+            .args([display, "-screen", "0", "1280x800x24", "-nolisten", "tcp"])
+            .spawn()
+            .expect("spawn Xvfb");
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let ctrl = LinuxScreenController::connect(640, Some(display.into())).unwrap();
+        assert_eq!(ctrl.resolution(), (1280, 800));
+
+        let shot = ctrl.capture(None).await.unwrap();
+        assert_eq!((shot.resized_width, shot.resized_height), (640, 400));
+        assert!((shot.scale_x - 2.0).abs() < f64::EPSILON);
+
+        ctrl.move_cursor(300, 200).await.unwrap();
+        assert_eq!(ctrl.cursor_position().await.unwrap(), (300, 200));
+        ctrl.drag((10, 10), (50, 60)).await.unwrap();
+        assert_eq!(ctrl.cursor_position().await.unwrap(), (50, 60));
+        ctrl.scroll("down", 2).await.unwrap();
+        ctrl.press_key("ctrl+a").await.unwrap();
+
+        let _ = xvfb.kill();
+        let _ = xvfb.wait();
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! `ScreenController` trait으로 플랫폼 추상화:
 //! - `MacScreenController`  — macOS (screencapture, cliclick, osascript)
-//! - `LinuxScreenController` — Linux (scrot/gnome-screenshot, xdotool) — TODO
+//! - `LinuxScreenController` — Linux X11/Xvfb (maim/import/scrot, xdotool). Wayland 미지원
 //! - `WebOSScreenController` — webOS TV (luna-send) — TODO
 //!
 //! LLM에는 하나의 tool로 노출:
@@ -11,11 +11,12 @@
 use anyhow::Result;
 use async_trait::async_trait;
 
+#[cfg(target_os = "linux")]
+pub mod linux;
 #[cfg(target_os = "macos")]
 pub mod mac;
 pub mod tool;
 
-
 /// 캡처 결과
 #[derive(Debug, Clone)]
 pub struct CaptureResult {
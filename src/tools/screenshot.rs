@@ -1,4 +1,5 @@
-use super::traits::{Tool, ToolResult};
+use super::traits::{attach_to_reply, Tool, ToolResult};
+use crate::channels::traits::{Attachment, AttachmentKind};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
@@ -155,6 +156,7 @@ impl ScreenshotTool {
         const MAX_RAW_BYTES: u64 = 1_572_864; // ~1.5 MB (base64 expands ~33%)
         if let Ok(meta) = tokio::fs::metadata(output_path).await {
             if meta.len() > MAX_RAW_BYTES {
+                attach_to_reply(Attachment::from_path(AttachmentKind::Image, output_path));
                 return Ok(ToolResult {
                     success: true,
                     output: format!(
@@ -200,6 +202,7 @@ impl ScreenshotTool {
                 };
                 let _ = write!(output_msg, "\ndata:{mime};base64,{encoded}");
 
+                attach_to_reply(Attachment::from_path(AttachmentKind::Image, output_path));
                 Ok(ToolResult {
                     success: true,
                     output: output_msg,
@@ -315,6 +318,27 @@ mod tests {
         assert!(result.error.unwrap().contains("unsafe for shell execution"));
     }
 
+    #[tokio::test]
+    async fn read_and_encode_attaches_the_screenshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("shot.png");
+        std::fs::write(&path, b"png-bytes").unwrap();
+
+        let sink = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let result = crate::tools::collect_reply_attachments(
+            Arc::clone(&sink),
+            ScreenshotTool::read_and_encode(&path),
+        )
+        .await
+        .unwrap();
+        assert!(result.success);
+        let attachments = sink.lock();
+        assert_eq!(attachments.len(), 1);
+        assert_eq!(attachments[0].kind, AttachmentKind::Image);
+        assert_eq!(attachments[0].file_name.as_deref(), Some("shot.png"));
+        assert_eq!(attachments[0].size_bytes, Some(9));
+    }
+
     #[test]
     fn screenshot_command_contains_output_path() {
         let cmd = ScreenshotTool::screenshot_command("/tmp/my_screenshot.png").unwrap();
@@ -5,7 +5,7 @@ use serde_json::json;
 use tracing::warn;
 
 use super::traits::{Tool, ToolResult};
-use crate::sop::types::{SopRunAction, SopStepResult, SopStepStatus};
+use crate::sop::types::{SopBranch, SopRunAction, SopStepResult, SopStepStatus};
 use crate::sop::{SopAuditLogger, SopEngine, SopMetricsCollector};
 
 /// Report a step result and advance an SOP run to the next step.
@@ -42,7 +42,7 @@ impl Tool for SopAdvanceTool {
     }
 
     fn description(&self) -> &str {
-        "Report the result of the current SOP step and advance to the next step. Provide the run_id, whether the step succeeded or failed, and a brief output summary."
+        "Report the result of the current SOP step and advance the run. Provide the run_id, whether the step succeeded or failed, and a brief output summary. The next step follows the SOP's branching rules (goto, on_failure, step conditions), so it is not always the next number. If the output is JSON, later step conditions can inspect its fields."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -99,15 +99,15 @@ impl Tool for SopAdvanceTool {
         };
 
         // Lock engine, advance step, snapshot data for audit, then drop lock
-        let (action, step_result_ok, finished_run) = {
+        let (action, step_result_ok, finished_run, visit, new_branches) = {
             let mut engine = self
                 .engine
                 .lock()
                 .map_err(|e| anyhow::anyhow!("Engine lock poisoned: {e}"))?;
 
-            let current_step = engine
+            let (current_step, branches_before) = engine
                 .get_run(run_id)
-                .map(|r| r.current_step)
+                .map(|r| (r.current_step, r.branches.len()))
                 .ok_or_else(|| anyhow::anyhow!("Run not found: {run_id}"))?;
 
             let now = now_iso8601();
@@ -128,20 +128,45 @@ impl Tool for SopAdvanceTool {
                         | SopRunAction::Failed { run_id, .. } => engine.get_run(run_id).cloned(),
                         _ => None,
                     };
+                    // Visit number and branches taken by this advance (non-linear runs)
+                    let (visit, new_branches): (usize, Vec<(usize, SopBranch)>) = engine
+                        .get_run(run_id)
+                        .map(|r| {
+                            let branches = r
+                                .branches
+                                .iter()
+                                .enumerate()
+                                .skip(branches_before)
+                                .map(|(seq, b)| (seq, b.clone()))
+                                .collect();
+                            (r.step_visits(current_step), branches)
+                        })
+                        .unwrap_or((1, Vec::new()));
                     // Only audit step result when advance succeeded
-                    (Ok(action), Some(step_result_clone), finished)
+                    (
+                        Ok(action),
+                        Some(step_result_clone),
+                        finished,
+                        visit,
+                        new_branches,
+                    )
                 }
-                Err(e) => (Err(e), None, None),
+                Err(e) => (Err(e), None, None, 0, Vec::new()),
             }
         };
 
         // Audit logging (engine lock dropped, safe to await)
         if let Some(ref audit) = self.audit {
             if let Some(ref sr) = step_result_ok {
-                if let Err(e) = audit.log_step_result(run_id, sr).await {
+                if let Err(e) = audit.log_step_result(run_id, sr, visit).await {
                     warn!("SOP audit log_step_result failed: {e}");
                 }
             }
+            for (seq, branch) in &new_branches {
+                if let Err(e) = audit.log_branch(run_id, *seq, branch).await {
+                    warn!("SOP audit log_branch failed: {e}");
+                }
+            }
             if let Some(ref run) = finished_run {
                 if let Err(e) = audit.log_run_complete(run).await {
                     warn!("SOP audit log_run_complete failed: {e}");
@@ -222,6 +247,9 @@ mod tests {
                     body: "Do step one".into(),
                     suggested_tools: vec![],
                     requires_confirmation: false,
+                    condition: None,
+                    on_failure: None,
+                    goto: None,
                 },
                 SopStep {
                     number: 2,
@@ -229,10 +257,14 @@ mod tests {
                     body: "Do step two".into(),
                     suggested_tools: vec![],
                     requires_confirmation: false,
+                    condition: None,
+                    on_failure: None,
+                    goto: None,
                 },
             ],
             cooldown_secs: 0,
             max_concurrent: 1,
+            max_step_visits: 3,
             location: None,
         }
     }
@@ -429,4 +461,54 @@ mod tests {
             "step audit should be written on success"
         );
     }
+
+    #[tokio::test]
+    async fn advance_failure_follows_on_failure_and_audits_branch() {
+        let mut sop = test_sop();
+        sop.steps[0].on_failure = Some(2);
+        let mut engine = SopEngine::new(SopConfig::default());
+        engine.set_sops_for_test(vec![sop]);
+        let event = SopEvent {
+            source: SopTriggerSource::Manual,
+            topic: None,
+            payload: None,
+            timestamp: "2026-02-19T12:00:00Z".into(),
+        };
+        engine.start_run("test-sop", event).unwrap();
+        let run_id = engine.active_runs().keys().next().unwrap().clone();
+        let engine = Arc::new(Mutex::new(engine));
+
+        let tmp = tempfile::tempdir().unwrap();
+        let mem_cfg = crate::config::MemoryConfig {
+            backend: "sqlite".into(),
+            ..crate::config::MemoryConfig::default()
+        };
+        let memory: Arc<dyn Memory> =
+            Arc::from(crate::memory::create_memory(&mem_cfg, tmp.path(), None).unwrap());
+        let audit = Arc::new(SopAuditLogger::new(memory.clone()));
+
+        let tool = SopAdvanceTool::new(engine).with_audit(audit);
+        let result = tool
+            .execute(json!({
+                "run_id": run_id,
+                "status": "failed",
+                "output": "health check 503"
+            }))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("Step two"), "{}", result.output);
+        assert!(result.output.contains("on_failure"));
+
+        let entries = memory
+            .list(
+                Some(&crate::memory::traits::MemoryCategory::Custom("sop".into())),
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(entries
+            .iter()
+            .any(|e| e.key == format!("sop_branch_{run_id}_0")));
+    }
 }
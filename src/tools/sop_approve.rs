@@ -143,9 +143,13 @@ mod tests {
                 body: "Do it".into(),
                 suggested_tools: vec![],
                 requires_confirmation: false,
+                condition: None,
+                on_failure: None,
+                goto: None,
             }],
             cooldown_secs: 0,
             max_concurrent: 1,
+            max_step_visits: 3,
             location: None,
         }
     }
@@ -168,6 +168,9 @@ mod tests {
                     body: "Do step one".into(),
                     suggested_tools: vec!["shell".into()],
                     requires_confirmation: false,
+                    condition: None,
+                    on_failure: None,
+                    goto: None,
                 },
                 SopStep {
                     number: 2,
@@ -175,10 +178,14 @@ mod tests {
                     body: "Do step two".into(),
                     suggested_tools: vec![],
                     requires_confirmation: false,
+                    condition: None,
+                    on_failure: None,
+                    goto: None,
                 },
             ],
             cooldown_secs: 0,
             max_concurrent: 1,
+            max_step_visits: 3,
             location: None,
         }
     }
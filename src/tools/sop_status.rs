@@ -167,6 +167,19 @@ impl Tool for SopStatusTool {
                             );
                         }
                     }
+                    if !run.branches.is_empty() {
+                        let _ = writeln!(output, "\nBranches:");
+                        for branch in &run.branches {
+                            let target = branch
+                                .to_step
+                                .map_or_else(|| "end".to_string(), |n| format!("step {n}"));
+                            let _ = writeln!(
+                                output,
+                                "  Step {} → {target} ({})",
+                                branch.from_step, branch.kind
+                            );
+                        }
+                    }
                     self.append_gate_status(&mut output, include_gate_status);
                     Ok(ToolResult {
                         success: true,
@@ -309,9 +322,13 @@ mod tests {
                 body: "Do it".into(),
                 suggested_tools: vec![],
                 requires_confirmation: false,
+                condition: None,
+                on_failure: None,
+                goto: None,
             }],
             cooldown_secs: 0,
             max_concurrent: 2,
+            max_step_visits: 3,
             location: None,
         }
     }
@@ -431,6 +448,7 @@ mod tests {
                 completed_at: Some("2026-02-19T12:01:00Z".into()),
             }],
             waiting_since: None,
+            branches: Vec::new(),
         };
         collector.record_run_complete(&run);
 
@@ -466,6 +484,7 @@ mod tests {
                 completed_at: Some("2026-02-19T12:01:00Z".into()),
             }],
             waiting_since: None,
+            branches: Vec::new(),
         };
         collector.record_run_complete(&run);
 
@@ -5,7 +5,8 @@ use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -13,7 +14,8 @@ use std::time::Duration;
 const SWARM_AGENT_TIMEOUT_SECS: u64 = 120;
 
 /// Tool that orchestrates multiple agents as a swarm. Supports sequential
-/// (pipeline), parallel (fan-out/fan-in), and router (LLM-selected) strategies.
+/// (pipeline), parallel (fan-out/fan-in), router (LLM-selected), and graph
+/// (dependency DAG) strategies.
 pub struct SwarmTool {
     swarms: Arc<HashMap<String, SwarmConfig>>,
     agents: Arc<HashMap<String, DelegateAgentConfig>>,
@@ -415,6 +417,397 @@ impl SwarmTool {
             }),
         }
     }
+
+    async fn execute_graph(
+        &self,
+        swarm_config: &SwarmConfig,
+        prompt: &str,
+        context: &str,
+    ) -> anyhow::Result<ToolResult> {
+        let plan = match plan_graph(swarm_config) {
+            Ok(plan) => plan,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e),
+                });
+            }
+        };
+
+        let mut agents = HashMap::new();
+        for node in &plan.nodes {
+            let Some(agent_config) = self.agents.get(&node.name) else {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Swarm references unknown agent '{}'", node.name)),
+                });
+            };
+            let provider = match self.create_provider_for_agent(agent_config, &node.name) {
+                Ok(p) => p,
+                Err(result) => return Ok(result),
+            };
+            agents.insert(node.name.clone(), (provider, agent_config.clone()));
+        }
+
+        let node_count = plan.nodes.len();
+        let run = match tokio::time::timeout(
+            Duration::from_secs(swarm_config.timeout_secs),
+            run_graph(plan.nodes, agents, prompt, context),
+        )
+        .await
+        {
+            Ok(run) => run,
+            Err(_) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Swarm graph timed out after {}s",
+                        swarm_config.timeout_secs
+                    )),
+                });
+            }
+        };
+
+        if let Some(error) = run.error {
+            return Ok(ToolResult {
+                success: false,
+                output: format!("[Completed: {}]", run.completed.join(", ")),
+                error: Some(error),
+            });
+        }
+
+        let results: Vec<String> = plan
+            .terminal
+            .iter()
+            .map(|name| {
+                let output = run.outputs.get(name).map_or("", String::as_str);
+                match self.agents.get(name) {
+                    Some(cfg) => format!("[{name} ({}/{})]\n{output}", cfg.provider, cfg.model),
+                    None => format!("[{name}]\n{output}"),
+                }
+            })
+            .collect();
+
+        Ok(ToolResult {
+            success: true,
+            output: format!(
+                "[Swarm graph — {node_count} agents, order: {}]\n\n{}",
+                run.completed.join(" → "),
+                results.join("\n\n---\n\n")
+            ),
+            error: None,
+        })
+    }
+}
+
+/// A `graph` swarm member with its dependencies resolved.
+#[derive(Debug, Clone)]
+struct GraphNode {
+    name: String,
+    depends_on: Vec<String>,
+    prompt_template: Option<String>,
+    timeout_secs: u64,
+    retries: u32,
+}
+
+/// Validated execution plan for a `graph` swarm.
+#[derive(Debug)]
+struct GraphPlan {
+    /// Nodes in a topological order.
+    nodes: Vec<GraphNode>,
+    /// Nodes whose outputs form the swarm result: the aggregator, or every
+    /// node nothing else depends on.
+    terminal: Vec<String>,
+}
+
+/// Outcome of running a graph: outputs of every finished node, the order in
+/// which they finished, and the error that stopped the run (if any).
+#[derive(Debug, Default)]
+struct GraphRun {
+    outputs: HashMap<String, String>,
+    completed: Vec<String>,
+    error: Option<String>,
+}
+
+/// Resolve `[swarms.<name>.nodes]` against the swarm's agent list and check
+/// that the dependencies form a DAG.
+fn plan_graph(swarm_config: &SwarmConfig) -> Result<GraphPlan, String> {
+    let members: HashSet<&str> = swarm_config.agents.iter().map(String::as_str).collect();
+
+    if let Some(key) = swarm_config
+        .nodes
+        .keys()
+        .find(|key| !members.contains(key.as_str()))
+    {
+        return Err(format!(
+            "Graph node settings for '{key}' do not match any agent in the swarm"
+        ));
+    }
+
+    let mut nodes: Vec<GraphNode> = Vec::with_capacity(swarm_config.agents.len());
+    for name in &swarm_config.agents {
+        if nodes.iter().any(|n| &n.name == name) {
+            return Err(format!(
+                "Agent '{name}' appears more than once in the swarm"
+            ));
+        }
+        let settings = swarm_config.nodes.get(name);
+        let depends_on = settings.map(|s| s.depends_on.clone()).unwrap_or_default();
+        for dep in &depends_on {
+            if dep == name {
+                return Err(format!("Graph node '{name}' cannot depend on itself"));
+            }
+            if !members.contains(dep.as_str()) {
+                return Err(format!(
+                    "Graph node '{name}' depends on '{dep}', which is not in the swarm"
+                ));
+            }
+        }
+        nodes.push(GraphNode {
+            name: name.clone(),
+            depends_on,
+            prompt_template: settings.and_then(|s| s.prompt_template.clone()),
+            timeout_secs: settings
+                .and_then(|s| s.timeout_secs)
+                .unwrap_or(swarm_config.timeout_secs),
+            retries: settings.map_or(0, |s| s.retries),
+        });
+    }
+
+    let terminal = if let Some(aggregator) = &swarm_config.aggregator {
+        let Some(index) = nodes.iter().position(|n| &n.name == aggregator) else {
+            return Err(format!(
+                "Graph aggregator '{aggregator}' is not an agent in the swarm"
+            ));
+        };
+        if let Some(node) = nodes.iter().find(|n| n.depends_on.contains(aggregator)) {
+            return Err(format!(
+                "Graph aggregator '{aggregator}' cannot be a dependency of '{}'",
+                node.name
+            ));
+        }
+        if nodes[index].depends_on.is_empty() {
+            let depended: HashSet<&str> = nodes
+                .iter()
+                .flat_map(|n| n.depends_on.iter().map(String::as_str))
+                .collect();
+            let sinks: Vec<String> = nodes
+                .iter()
+                .filter(|n| &n.name != aggregator && !depended.contains(n.name.as_str()))
+                .map(|n| n.name.clone())
+                .collect();
+            nodes[index].depends_on = sinks;
+        }
+        vec![aggregator.clone()]
+    } else {
+        let depended: HashSet<&str> = nodes
+            .iter()
+            .flat_map(|n| n.depends_on.iter().map(String::as_str))
+            .collect();
+        nodes
+            .iter()
+            .filter(|n| !depended.contains(n.name.as_str()))
+            .map(|n| n.name.clone())
+            .collect()
+    };
+
+    // Kahn's algorithm; ties keep the configured agent order.
+    let mut ordered = Vec::with_capacity(nodes.len());
+    let mut placed: HashSet<String> = HashSet::new();
+    let mut remaining = nodes;
+    while !remaining.is_empty() {
+        let (ready, blocked): (Vec<GraphNode>, Vec<GraphNode>) = remaining
+            .into_iter()
+            .partition(|n| n.depends_on.iter().all(|d| placed.contains(d)));
+        if ready.is_empty() {
+            let names: Vec<&str> = blocked.iter().map(|n| n.name.as_str()).collect();
+            return Err(format!(
+                "Graph swarm has a dependency cycle involving: {}",
+                names.join(", ")
+            ));
+        }
+        placed.extend(ready.iter().map(|n| n.name.clone()));
+        ordered.extend(ready);
+        remaining = blocked;
+    }
+
+    Ok(GraphPlan {
+        nodes: ordered,
+        terminal,
+    })
+}
+
+/// Build the prompt for a graph node from the task, the caller's context and
+/// the outputs of its dependencies.
+///
+/// With a `prompt_template`, `{{task}}`, `{{context}}` and `{{<agent>}}` for
+/// each dependency are substituted. Without one, upstream outputs are
+/// prepended to the task.
+fn render_node_prompt(
+    node: &GraphNode,
+    task: &str,
+    context: &str,
+    outputs: &HashMap<String, String>,
+) -> String {
+    let upstream = node
+        .depends_on
+        .iter()
+        .map(|dep| (dep.as_str(), outputs.get(dep).map_or("", String::as_str)));
+
+    if let Some(template) = &node.prompt_template {
+        let mut vars: HashMap<&str, &str> = upstream.collect();
+        vars.insert("task", task);
+        vars.insert("context", context);
+        return substitute_placeholders(template, &vars);
+    }
+
+    let mut prompt = String::new();
+    if !context.is_empty() {
+        let _ = write!(prompt, "[Context]\n{context}\n\n");
+    }
+    for (dep, output) in upstream {
+        let _ = write!(prompt, "[Output of {dep}]\n{output}\n\n");
+    }
+    if prompt.is_empty() {
+        prompt.push_str(task);
+    } else {
+        let _ = write!(prompt, "[Task]\n{task}");
+    }
+    prompt
+}
+
+/// Replace `{{key}}` placeholders in a single pass. Unknown keys are left
+/// untouched, and substituted values are never re-scanned.
+fn substitute_placeholders(template: &str, vars: &HashMap<&str, &str>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        match vars.get(after[..end].trim()) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(&rest[start..start + end + 4]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Run planned graph nodes. Each node starts as soon as all of its
+/// dependencies have finished, so independent branches run concurrently.
+/// The first node to exhaust its retries stops the run.
+async fn run_graph(
+    nodes: Vec<GraphNode>,
+    mut agents: HashMap<String, (Box<dyn Provider>, DelegateAgentConfig)>,
+    task: &str,
+    context: &str,
+) -> GraphRun {
+    let mut run = GraphRun::default();
+    let mut pending = nodes;
+    let mut join_set = tokio::task::JoinSet::new();
+
+    loop {
+        let (ready, waiting): (Vec<GraphNode>, Vec<GraphNode>) = pending
+            .into_iter()
+            .partition(|n| n.depends_on.iter().all(|d| run.outputs.contains_key(d)));
+        pending = waiting;
+
+        for node in ready {
+            let Some((provider, agent_config)) = agents.remove(&node.name) else {
+                join_set.abort_all();
+                run.error = Some(format!("Swarm references unknown agent '{}'", node.name));
+                return run;
+            };
+            let prompt = render_node_prompt(&node, task, context, &run.outputs);
+            join_set.spawn(run_graph_node(node, provider, agent_config, prompt));
+        }
+
+        let Some(joined) = join_set.join_next().await else {
+            break;
+        };
+        match joined {
+            Ok((name, Ok(output))) => {
+                run.completed.push(name.clone());
+                run.outputs.insert(name, output);
+            }
+            Ok((_, Err(e))) => {
+                join_set.abort_all();
+                run.error = Some(e);
+                return run;
+            }
+            Err(e) => {
+                join_set.abort_all();
+                run.error = Some(format!("Swarm graph task failed: {e}"));
+                return run;
+            }
+        }
+    }
+
+    if let Some(node) = pending.first() {
+        run.error = Some(format!(
+            "Graph node '{}' never became ready; check its dependencies",
+            node.name
+        ));
+    }
+    run
+}
+
+/// Call one graph node, retrying failed or timed-out attempts.
+async fn run_graph_node(
+    node: GraphNode,
+    provider: Box<dyn Provider>,
+    agent_config: DelegateAgentConfig,
+    prompt: String,
+) -> (String, Result<String, String>) {
+    let temperature = agent_config.temperature.unwrap_or(0.7);
+    let mut last_error = String::new();
+
+    for attempt in 0..=node.retries {
+        let result = tokio::time::timeout(
+            Duration::from_secs(node.timeout_secs),
+            provider.chat_with_system(
+                agent_config.system_prompt.as_deref(),
+                &prompt,
+                &agent_config.model,
+                temperature,
+            ),
+        )
+        .await;
+
+        last_error = match result {
+            Ok(Ok(text)) if text.trim().is_empty() => {
+                return (node.name, Ok("[Empty response]".to_string()));
+            }
+            Ok(Ok(text)) => return (node.name, Ok(text)),
+            Ok(Err(e)) => format!("Agent '{}' failed: {e}", node.name),
+            Err(_) => format!(
+                "Agent '{}' timed out after {}s",
+                node.name, node.timeout_secs
+            ),
+        };
+
+        if attempt < node.retries {
+            tracing::warn!(
+                agent = %node.name,
+                attempt = attempt + 1,
+                "Swarm graph node failed, retrying: {last_error}"
+            );
+        }
+    }
+
+    let attempts = node.retries + 1;
+    (
+        node.name,
+        Err(format!("{last_error} (after {attempts} attempt(s))")),
+    )
 }
 
 #[async_trait]
@@ -425,7 +818,9 @@ impl Tool for SwarmTool {
 
     fn description(&self) -> &str {
         "Orchestrate a swarm of agents to collaboratively handle a task. Supports sequential \
-         (pipeline), parallel (fan-out/fan-in), and router (LLM-selected) strategies."
+         (pipeline), parallel (fan-out/fan-in), router (LLM-selected), and graph (agents run \
+         once their declared dependencies finish, with upstream outputs in their prompts) \
+         strategies."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -539,6 +934,7 @@ impl Tool for SwarmTool {
             }
             SwarmStrategy::Parallel => self.execute_parallel(swarm_config, prompt, context).await,
             SwarmStrategy::Router => self.execute_router(swarm_config, prompt, context).await,
+            SwarmStrategy::Graph => self.execute_graph(swarm_config, prompt, context).await,
         }
     }
 }
@@ -546,7 +942,9 @@ impl Tool for SwarmTool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::SwarmNodeConfig;
     use crate::security::{AutonomyLevel, SecurityPolicy};
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     fn test_security() -> Arc<SecurityPolicy> {
         Arc::new(SecurityPolicy::default())
@@ -595,6 +993,8 @@ mod tests {
                 router_prompt: None,
                 description: Some("Research then write".to_string()),
                 timeout_secs: 300,
+                nodes: HashMap::new(),
+                aggregator: None,
             },
         );
         swarms.insert(
@@ -605,6 +1005,8 @@ mod tests {
                 router_prompt: None,
                 description: None,
                 timeout_secs: 300,
+                nodes: HashMap::new(),
+                aggregator: None,
             },
         );
         swarms.insert(
@@ -615,6 +1017,8 @@ mod tests {
                 router_prompt: Some("Pick the best agent.".to_string()),
                 description: None,
                 timeout_secs: 300,
+                nodes: HashMap::new(),
+                aggregator: None,
             },
         );
         swarms
@@ -772,6 +1176,8 @@ mod tests {
                 router_prompt: None,
                 description: None,
                 timeout_secs: 60,
+                nodes: HashMap::new(),
+                aggregator: None,
             },
         );
         let tool = SwarmTool::new(
@@ -800,6 +1206,8 @@ mod tests {
                 router_prompt: None,
                 description: None,
                 timeout_secs: 60,
+                nodes: HashMap::new(),
+                aggregator: None,
             },
         );
         let tool = SwarmTool::new(
@@ -878,6 +1286,8 @@ mod tests {
                 router_prompt: None,
                 description: None,
                 timeout_secs: 60,
+                nodes: HashMap::new(),
+                aggregator: None,
             },
         );
         // researcher uses "ollama" which won't be running in CI
@@ -907,6 +1317,8 @@ mod tests {
                 router_prompt: None,
                 description: None,
                 timeout_secs: 60,
+                nodes: HashMap::new(),
+                aggregator: None,
             },
         );
         let tool = SwarmTool::new(
@@ -935,6 +1347,8 @@ mod tests {
                 router_prompt: Some("Pick.".to_string()),
                 description: None,
                 timeout_secs: 60,
+                nodes: HashMap::new(),
+                aggregator: None,
             },
         );
         let tool = SwarmTool::new(
@@ -950,4 +1364,282 @@ mod tests {
             .unwrap();
         assert!(!result.success);
     }
+
+    fn graph_swarm(
+        agents: &[&str],
+        deps: &[(&str, &[&str])],
+        aggregator: Option<&str>,
+    ) -> SwarmConfig {
+        SwarmConfig {
+            agents: agents.iter().map(ToString::to_string).collect(),
+            strategy: SwarmStrategy::Graph,
+            router_prompt: None,
+            description: None,
+            timeout_secs: 60,
+            nodes: deps
+                .iter()
+                .map(|(name, depends_on)| {
+                    (
+                        (*name).to_string(),
+                        SwarmNodeConfig {
+                            depends_on: depends_on.iter().map(ToString::to_string).collect(),
+                            ..SwarmNodeConfig::default()
+                        },
+                    )
+                })
+                .collect(),
+            aggregator: aggregator.map(ToString::to_string),
+        }
+    }
+
+    /// Provider that records prompts, optionally fails its first calls and
+    /// optionally waits on a barrier shared with sibling nodes.
+    struct GraphMockProvider {
+        name: &'static str,
+        calls: AtomicUsize,
+        fail_first: usize,
+        barrier: Option<Arc<tokio::sync::Barrier>>,
+        prompts: Arc<parking_lot::Mutex<Vec<(String, String)>>>,
+    }
+
+    #[async_trait]
+    impl Provider for GraphMockProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.prompts
+                .lock()
+                .push((self.name.to_string(), message.to_string()));
+            if let Some(barrier) = &self.barrier {
+                barrier.wait().await;
+            }
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.fail_first {
+                anyhow::bail!("transient failure");
+            }
+            Ok(format!("{}-out", self.name))
+        }
+    }
+
+    type GraphAgents = HashMap<String, (Box<dyn Provider>, DelegateAgentConfig)>;
+
+    fn graph_agents(
+        names: &[&'static str],
+        failing: &[(&str, usize)],
+        barrier_for: &[&str],
+        prompts: &Arc<parking_lot::Mutex<Vec<(String, String)>>>,
+    ) -> GraphAgents {
+        let barrier = Arc::new(tokio::sync::Barrier::new(barrier_for.len().max(1)));
+        let config = sample_agents()["researcher"].clone();
+        names
+            .iter()
+            .map(|name| {
+                let provider: Box<dyn Provider> = Box::new(GraphMockProvider {
+                    name,
+                    calls: AtomicUsize::new(0),
+                    fail_first: failing
+                        .iter()
+                        .find(|(n, _)| n == name)
+                        .map_or(0, |(_, count)| *count),
+                    barrier: barrier_for.contains(name).then(|| barrier.clone()),
+                    prompts: prompts.clone(),
+                });
+                ((*name).to_string(), (provider, config.clone()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plan_graph_orders_nodes_and_wires_aggregator() {
+        let swarm = graph_swarm(
+            &["writer", "critic", "summarize_a", "summarize_b", "search"],
+            &[
+                ("summarize_a", &["search"]),
+                ("summarize_b", &["search"]),
+                ("critic", &["summarize_a", "summarize_b"]),
+            ],
+            Some("writer"),
+        );
+        let plan = plan_graph(&swarm).unwrap();
+        let order: Vec<&str> = plan.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(
+            order,
+            ["search", "summarize_a", "summarize_b", "critic", "writer"]
+        );
+        assert_eq!(plan.nodes[4].depends_on, ["critic"]);
+        assert_eq!(plan.nodes[4].timeout_secs, 60);
+        assert_eq!(plan.terminal, ["writer"]);
+
+        let without_aggregator = graph_swarm(
+            &["search", "summarize_a", "summarize_b"],
+            &[("summarize_a", &["search"]), ("summarize_b", &["search"])],
+            None,
+        );
+        let plan = plan_graph(&without_aggregator).unwrap();
+        assert_eq!(plan.terminal, ["summarize_a", "summarize_b"]);
+    }
+
+    #[test]
+    fn plan_graph_rejects_invalid_graphs() {
+        let cycle = graph_swarm(&["a", "b"], &[("a", &["b"]), ("b", &["a"])], None);
+        assert!(plan_graph(&cycle).unwrap_err().contains("cycle"));
+
+        let unknown = graph_swarm(&["a"], &[("a", &["ghost"])], None);
+        assert!(plan_graph(&unknown).unwrap_err().contains("ghost"));
+
+        let stray = graph_swarm(&["a"], &[("b", &[])], None);
+        assert!(plan_graph(&stray).unwrap_err().contains("'b'"));
+
+        let upstream_aggregator = graph_swarm(&["a", "b"], &[("b", &["a"])], Some("a"));
+        assert!(plan_graph(&upstream_aggregator)
+            .unwrap_err()
+            .contains("cannot be a dependency"));
+    }
+
+    #[test]
+    fn substitute_placeholders_is_single_pass_and_utf8_safe() {
+        let vars: HashMap<&str, &str> = [("task", "搜索"), ("search", "{{task}}")]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            substitute_placeholders("Résumé {{task}} → {{ search }} {{missing}} {{", &vars),
+            "Résumé 搜索 → {{task}} {{missing}} {{"
+        );
+    }
+
+    #[test]
+    fn render_node_prompt_prepends_upstream_outputs_without_template() {
+        let node = GraphNode {
+            name: "critic".into(),
+            depends_on: vec!["search".into()],
+            prompt_template: None,
+            timeout_secs: 60,
+            retries: 0,
+        };
+        let outputs: HashMap<String, String> =
+            [("search".to_string(), "found it".to_string())].into();
+        let prompt = render_node_prompt(&node, "review", "ctx", &outputs);
+        assert_eq!(
+            prompt,
+            "[Context]\nctx\n\n[Output of search]\nfound it\n\n[Task]\nreview"
+        );
+    }
+
+    #[tokio::test]
+    async fn run_graph_runs_branches_concurrently_and_templates_outputs() {
+        let mut swarm = graph_swarm(
+            &["search", "summarize_a", "summarize_b", "writer"],
+            &[("summarize_a", &["search"]), ("summarize_b", &["search"])],
+            Some("writer"),
+        );
+        swarm.nodes.insert(
+            "writer".into(),
+            SwarmNodeConfig {
+                prompt_template: Some(
+                    "Write about {{task}} using {{summarize_a}} and {{summarize_b}}".into(),
+                ),
+                ..SwarmNodeConfig::default()
+            },
+        );
+        let plan = plan_graph(&swarm).unwrap();
+        let prompts = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        // Both summarizers block on a shared barrier, so the run only
+        // completes if they execute concurrently.
+        let agents = graph_agents(
+            &["search", "summarize_a", "summarize_b", "writer"],
+            &[],
+            &["summarize_a", "summarize_b"],
+            &prompts,
+        );
+
+        let run = tokio::time::timeout(
+            Duration::from_secs(5),
+            run_graph(plan.nodes, agents, "rust", ""),
+        )
+        .await
+        .expect("independent branches should run concurrently");
+
+        assert!(run.error.is_none(), "{:?}", run.error);
+        assert_eq!(run.completed.first().map(String::as_str), Some("search"));
+        assert_eq!(run.completed.last().map(String::as_str), Some("writer"));
+        assert_eq!(run.outputs["writer"], "writer-out");
+        let prompts = prompts.lock();
+        let writer_prompt = &prompts.iter().find(|(n, _)| n == "writer").unwrap().1;
+        assert_eq!(
+            writer_prompt,
+            "Write about rust using summarize_a-out and summarize_b-out"
+        );
+        let summary_prompt = &prompts.iter().find(|(n, _)| n == "summarize_a").unwrap().1;
+        assert!(summary_prompt.contains("[Output of search]\nsearch-out"));
+    }
+
+    #[tokio::test]
+    async fn run_graph_retries_failed_nodes() {
+        let mut swarm = graph_swarm(&["search", "writer"], &[("writer", &["search"])], None);
+        swarm.nodes.insert(
+            "search".into(),
+            SwarmNodeConfig {
+                retries: 2,
+                ..SwarmNodeConfig::default()
+            },
+        );
+        let prompts = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let agents = graph_agents(&["search", "writer"], &[("search", 2)], &[], &prompts);
+        let run = run_graph(plan_graph(&swarm).unwrap().nodes, agents, "task", "").await;
+        assert!(run.error.is_none(), "{:?}", run.error);
+        assert_eq!(run.completed, ["search", "writer"]);
+        assert_eq!(
+            prompts.lock().iter().filter(|(n, _)| n == "search").count(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn run_graph_stops_when_node_exhausts_retries() {
+        let mut swarm = graph_swarm(&["search", "writer"], &[("writer", &["search"])], None);
+        swarm.nodes.insert(
+            "search".into(),
+            SwarmNodeConfig {
+                retries: 1,
+                ..SwarmNodeConfig::default()
+            },
+        );
+        let prompts = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let agents = graph_agents(&["search", "writer"], &[("search", 5)], &[], &prompts);
+        let run = run_graph(plan_graph(&swarm).unwrap().nodes, agents, "task", "").await;
+        let error = run.error.unwrap();
+        assert!(error.contains("Agent 'search' failed"));
+        assert!(error.contains("after 2 attempt(s)"));
+        assert!(run.completed.is_empty());
+        assert!(prompts.lock().iter().all(|(n, _)| n == "search"));
+    }
+
+    #[tokio::test]
+    async fn graph_swarm_with_cycle_returns_error() {
+        let mut swarms = HashMap::new();
+        swarms.insert(
+            "loop".to_string(),
+            graph_swarm(
+                &["researcher", "writer"],
+                &[("researcher", &["writer"]), ("writer", &["researcher"])],
+                None,
+            ),
+        );
+        let tool = SwarmTool::new(
+            swarms,
+            sample_agents(),
+            None,
+            test_security(),
+            providers::ProviderRuntimeOptions::default(),
+        );
+        let result = tool
+            .execute(json!({"swarm": "loop", "prompt": "test"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("dependency cycle"));
+    }
 }
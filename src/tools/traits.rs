@@ -1,5 +1,9 @@
+use crate::channels::traits::Attachment;
 use async_trait::async_trait;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
 
 /// Result of a tool execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +13,28 @@ pub struct ToolResult {
     pub error: Option<String>,
 }
 
+tokio::task_local! {
+    static REPLY_ATTACHMENTS: Arc<Mutex<Vec<Attachment>>>;
+}
+
+/// Queue a file (e.g. a generated chart) for delivery with the reply to the
+/// current channel message. Returns `false` when no channel reply is being
+/// built, in which case the file is not delivered.
+pub fn attach_to_reply(attachment: Attachment) -> bool {
+    REPLY_ATTACHMENTS
+        .try_with(|sink| sink.lock().push(attachment))
+        .is_ok()
+}
+
+/// Run `future`, collecting everything its tools pass to [`attach_to_reply`]
+/// into `sink`.
+pub(crate) async fn collect_reply_attachments<F: Future>(
+    sink: Arc<Mutex<Vec<Attachment>>>,
+    future: F,
+) -> F::Output {
+    REPLY_ATTACHMENTS.scope(sink, future).await
+}
+
 /// Description of a tool for the LLM
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolSpec {
@@ -124,4 +150,28 @@ mod tests {
         assert!(!parsed.success);
         assert_eq!(parsed.error.as_deref(), Some("boom"));
     }
+
+    #[tokio::test]
+    async fn attach_to_reply_reaches_the_enclosing_sink_only() {
+        use crate::channels::traits::AttachmentKind;
+
+        assert!(!attach_to_reply(Attachment::from_path(
+            AttachmentKind::Image,
+            "/tmp/outside.png"
+        )));
+
+        let sink = Arc::new(Mutex::new(Vec::new()));
+        let attached = collect_reply_attachments(Arc::clone(&sink), async {
+            attach_to_reply(Attachment::from_path(
+                AttachmentKind::Image,
+                "/tmp/chart.png",
+            ))
+        })
+        .await;
+
+        assert!(attached);
+        let collected = sink.lock();
+        assert_eq!(collected.len(), 1);
+        assert_eq!(collected[0].kind, AttachmentKind::Image);
+    }
 }
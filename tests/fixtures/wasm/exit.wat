@@ -0,0 +1,6 @@
+;; Calls proc_exit(3) immediately.
+;; Fixture for src/skills/wasm_tool.rs. Rebuild with: wat2wasm exit.wat -o exit.wasm
+(module
+  (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+  (memory (export "memory") 1)
+  (func (export "_start") (call $proc_exit (i32.const 3))))
@@ -0,0 +1,5 @@
+;; Loops forever; used to check fuel exhaustion.
+;; Fixture for src/skills/wasm_tool.rs. Rebuild with: wat2wasm spin.wat -o spin.wasm
+(module
+  (memory (export "memory") 1)
+  (func (export "_start") (loop $l (br $l))))
@@ -124,6 +124,7 @@ impl Channel for MatrixTestChannel {
             channel: self.channel_name.clone(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         })
         .await
         .map_err(|e| anyhow::anyhow!(e.to_string()))
@@ -564,6 +565,7 @@ fn channel_message_thread_ts_preserved_on_clone() {
         channel: "slack".into(),
         timestamp: 1700000000,
         thread_ts: Some("1700000000.000001".into()),
+        attachments: Vec::new(),
     };
 
     let cloned = msg.clone();
@@ -580,6 +582,7 @@ fn channel_message_none_thread_ts_preserved() {
         channel: "telegram".into(),
         timestamp: 1700000000,
         thread_ts: None,
+        attachments: Vec::new(),
     };
 
     assert!(msg.clone().thread_ts.is_none());
@@ -633,6 +636,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "telegram".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "discord" => ChannelMessage {
             id: "dc_1".into(),
@@ -642,6 +646,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "discord".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "slack" => ChannelMessage {
             id: "sl_1".into(),
@@ -651,6 +656,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "slack".into(),
             timestamp: 1700000000,
             thread_ts: Some("1700000000.000001".into()),
+            attachments: Vec::new(),
         },
         "imessage" => ChannelMessage {
             id: "im_1".into(),
@@ -660,6 +666,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "imessage".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "irc" => ChannelMessage {
             id: "irc_1".into(),
@@ -669,6 +676,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "irc".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "email" => ChannelMessage {
             id: "email_1".into(),
@@ -678,6 +686,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "email".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "signal" => ChannelMessage {
             id: "sig_1".into(),
@@ -687,6 +696,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "signal".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "mattermost" => ChannelMessage {
             id: "mm_1".into(),
@@ -696,6 +706,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "mattermost".into(),
             timestamp: 1700000000,
             thread_ts: Some("root_msg_id".into()),
+            attachments: Vec::new(),
         },
         "whatsapp" => ChannelMessage {
             id: "wa_1".into(),
@@ -705,6 +716,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "whatsapp".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "nextcloud_talk" => ChannelMessage {
             id: "nc_1".into(),
@@ -714,6 +726,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "nextcloud_talk".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "wecom" => ChannelMessage {
             id: "wc_1".into(),
@@ -723,6 +736,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "wecom".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "dingtalk" => ChannelMessage {
             id: "dt_1".into(),
@@ -732,6 +746,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "dingtalk".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "qq" => ChannelMessage {
             id: "qq_1".into(),
@@ -741,6 +756,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "qq".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "linq" => ChannelMessage {
             id: "lq_1".into(),
@@ -750,6 +766,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "linq".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "wati" => ChannelMessage {
             id: "wt_1".into(),
@@ -759,6 +776,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "wati".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         "cli" => ChannelMessage {
             id: "cli_1".into(),
@@ -768,6 +786,7 @@ fn make_platform_message(platform: &str) -> ChannelMessage {
             channel: "cli".into(),
             timestamp: 1700000000,
             thread_ts: None,
+            attachments: Vec::new(),
         },
         _ => panic!("Unknown platform: {platform}"),
     }
@@ -1068,6 +1087,7 @@ fn channel_message_zero_timestamp() {
         channel: "ch".into(),
         timestamp: 0,
         thread_ts: None,
+        attachments: Vec::new(),
     };
     assert_eq!(msg.timestamp, 0);
 }
@@ -1082,6 +1102,7 @@ fn channel_message_max_timestamp() {
         channel: "ch".into(),
         timestamp: u64::MAX,
         thread_ts: None,
+        attachments: Vec::new(),
     };
     assert_eq!(msg.timestamp, u64::MAX);
 }
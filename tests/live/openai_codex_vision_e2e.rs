@@ -159,6 +159,7 @@ async fn openai_codex_second_vision_support() -> Result<()> {
         reasoning_level: None,
         custom_provider_auth_header: None,
         service_tier: None,
+        llama_server: zeroclaw::config::LlamaServerConfig::default(),
     };
 
     let provider = zeroclaw::providers::create_provider_with_options("openai-codex", None, &opts)?;